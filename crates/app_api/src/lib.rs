@@ -16,11 +16,22 @@ pub trait UiApp {
     fn set_bus_sender(&mut self, _tx: mpsc::Sender<CoreCommand>) {}
     fn on_core_event(&mut self, _event: CoreEvent) {}
 
+    // window title, queried after every frame; `None` keeps the current one
+    fn window_title(&self) -> Option<String> {
+        None
+    }
+
+    // OS theme, reported before the first frame and whenever it changes
+    fn set_dark_mode(&mut self, _dark: bool) {}
+
     // repaint
     fn set_repaint_handle(&mut self, _h: RepaintHandle) {}
     fn needs_redraw(&self) -> bool {
         false
     }
+
+    // lifecycle: called once before the window closes
+    fn on_exit(&mut self) {}
 }
 
 pub trait Repaint: Send + Sync {
@@ -0,0 +1,90 @@
+use benches::{
+    CORPUS, build_dom, layout_page, parse_stylesheets, style_page, tokenize_streaming,
+    tokenize_whole,
+};
+use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
+
+const STREAMING_CHUNK_SIZES: [usize; 3] = [64, 1024, 16 * 1024];
+
+fn bench_tokenize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tokenize");
+    for page in CORPUS {
+        group.throughput(Throughput::Bytes(page.html.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::new("whole", page.name),
+            page.html,
+            |b, html| {
+                b.iter(|| black_box(tokenize_whole(black_box(html))));
+            },
+        );
+        for chunk_size in STREAMING_CHUNK_SIZES {
+            group.bench_with_input(
+                BenchmarkId::new(format!("streaming-{chunk_size}"), page.name),
+                page.html,
+                |b, html| b.iter(|| black_box(tokenize_streaming(black_box(html), chunk_size))),
+            );
+        }
+    }
+    group.finish();
+}
+
+fn bench_build_dom(c: &mut Criterion) {
+    let mut group = c.benchmark_group("build_dom");
+    for page in CORPUS {
+        group.throughput(Throughput::Bytes(page.html.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(page.name),
+            page.html,
+            |b, html| {
+                b.iter(|| black_box(build_dom(black_box(html))));
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_parse_stylesheets(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse_stylesheet");
+    for page in CORPUS {
+        let dom = build_dom(page.html);
+        group.bench_with_input(BenchmarkId::from_parameter(page.name), &dom, |b, dom| {
+            b.iter(|| black_box(parse_stylesheets(black_box(dom))));
+        });
+    }
+    group.finish();
+}
+
+fn bench_style(c: &mut Criterion) {
+    let mut group = c.benchmark_group("style");
+    for page in CORPUS {
+        let dom = build_dom(page.html);
+        let sheets = parse_stylesheets(&dom);
+        group.bench_function(BenchmarkId::from_parameter(page.name), |b| {
+            b.iter(|| black_box(style_page(black_box(&dom), &sheets)));
+        });
+    }
+    group.finish();
+}
+
+fn bench_layout(c: &mut Criterion) {
+    let mut group = c.benchmark_group("layout");
+    for page in CORPUS {
+        let dom = build_dom(page.html);
+        let sheets = parse_stylesheets(&dom);
+        let style = style_page(&dom, &sheets);
+        group.bench_function(BenchmarkId::from_parameter(page.name), |b| {
+            b.iter(|| black_box(layout_page(black_box(&style))));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_tokenize,
+    bench_build_dom,
+    bench_parse_stylesheets,
+    bench_style,
+    bench_layout
+);
+criterion_main!(benches);
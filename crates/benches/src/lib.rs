@@ -0,0 +1,180 @@
+//! Benchmarks of the engine's hot paths on representative pages.
+//!
+//! Each stage of the document pipeline gets a function here that the
+//! criterion suite in `benches/pipeline.rs` times in isolation: tokenizing a
+//! whole page versus feeding it to the streaming tokenizer in chunks,
+//! building the DOM, parsing the page's stylesheets, cascading them onto the
+//! DOM, and laying the styled tree out. The corpus pages live in `corpus/`.
+//!
+//! Run with `cargo bench -p benches`; pass a filter such as
+//! `cargo bench -p benches -- layout` to time one stage, and
+//! `--save-baseline <name>` / `--baseline <name>` to compare a redesign
+//! against the code before it.
+
+use css::{
+    CascadeOrigin, ParseOptions, StylePhaseOutput, StylesheetCascadeInput, StylesheetParse,
+    build_style_tree_from_computed_styles, compute_document_styles_from_resolved_styles,
+    parse_stylesheet_with_options, resolve_document_styles_from_cascade_inputs,
+};
+use html::dom_utils::collect_style_texts;
+use html::html5::{DocumentParseContext, Html5Tokenizer, Input, TokenizeResult, TokenizerConfig};
+use html::{ElementNamespace, HtmlParseOptions, Node, parse_document};
+use layout::{LayoutPhaseInput, layout_document};
+use layout_test_support::{FIXTURE_UA_STYLESHEET, FixedTextMeasurer};
+
+/// A corpus page.
+#[derive(Clone, Copy, Debug)]
+pub struct Page {
+    pub name: &'static str,
+    pub html: &'static str,
+}
+
+pub const CORPUS: &[Page] = &[
+    Page {
+        name: "blog-article",
+        html: include_str!("../corpus/blog-article.html"),
+    },
+    Page {
+        name: "news-front-page",
+        html: include_str!("../corpus/news-front-page.html"),
+    },
+    Page {
+        name: "docs-reference",
+        html: include_str!("../corpus/docs-reference.html"),
+    },
+];
+
+/// Viewport width pages are laid out in.
+pub const VIEWPORT_WIDTH: f32 = 1024.0;
+
+/// Tokenize `html` pushed as a single chunk; returns the number of tokens.
+pub fn tokenize_whole(html: &str) -> usize {
+    tokenize_chunks(std::iter::once(html))
+}
+
+/// Tokenize `html` fed to the tokenizer `chunk_size` bytes at a time (rounded
+/// down to character boundaries); returns the number of tokens.
+pub fn tokenize_streaming(html: &str, chunk_size: usize) -> usize {
+    tokenize_chunks(chunks(html, chunk_size))
+}
+
+fn tokenize_chunks<'a>(chunks: impl IntoIterator<Item = &'a str>) -> usize {
+    let mut ctx = DocumentParseContext::new();
+    let mut tokenizer = Html5Tokenizer::new(TokenizerConfig::default(), &mut ctx);
+    let mut input = Input::new();
+    let mut tokens = 0;
+    for chunk in chunks {
+        input.push_str(chunk);
+        loop {
+            let result = tokenizer.push_input(&mut input, &mut ctx);
+            tokens += tokenizer.next_batch(&mut input).tokens().len();
+            if result == TokenizeResult::NeedMoreInput {
+                break;
+            }
+        }
+    }
+    tokenizer.finish(&input);
+    tokens + tokenizer.next_batch(&mut input).tokens().len()
+}
+
+fn chunks(text: &str, chunk_size: usize) -> impl Iterator<Item = &str> {
+    let chunk_size = chunk_size.max(4);
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let mut end = chunk_size.min(rest.len());
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+        let (chunk, tail) = rest.split_at(end);
+        rest = tail;
+        Some(chunk)
+    })
+}
+
+/// Parse `html` into a DOM through the tokenizer and tree builder.
+pub fn build_dom(html: &str) -> Node {
+    parse_document(html, HtmlParseOptions::default())
+        .expect("corpus pages parse")
+        .document
+}
+
+/// The user-agent and author stylesheets a page is styled with.
+pub struct PageStylesheets {
+    pub user_agent: StylesheetParse,
+    pub author: StylesheetParse,
+}
+
+/// Parse the fixture user-agent stylesheet and the page's `<style>` text.
+pub fn parse_stylesheets(dom: &Node) -> PageStylesheets {
+    let mut author_css = String::new();
+    collect_style_texts(dom, &mut author_css);
+    PageStylesheets {
+        user_agent: parse_stylesheet_with_options(
+            FIXTURE_UA_STYLESHEET,
+            &ParseOptions::stylesheet(),
+        ),
+        author: parse_stylesheet_with_options(&author_css, &ParseOptions::stylesheet()),
+    }
+}
+
+/// Cascade `sheets` onto `dom` and build the styled tree layout consumes.
+pub fn style_page<'dom>(dom: &'dom Node, sheets: &PageStylesheets) -> StylePhaseOutput<'dom> {
+    let inputs = [
+        StylesheetCascadeInput::user_agent_for_namespace(
+            &sheets.user_agent,
+            ElementNamespace::Html,
+        ),
+        StylesheetCascadeInput::new(CascadeOrigin::Author, &sheets.author),
+    ];
+    let resolved =
+        resolve_document_styles_from_cascade_inputs(dom, &inputs).expect("corpus pages cascade");
+    let computed = compute_document_styles_from_resolved_styles(dom, &resolved)
+        .expect("corpus pages compute styles");
+    StylePhaseOutput::new(
+        build_style_tree_from_computed_styles(dom, &computed).expect("corpus pages style"),
+    )
+}
+
+/// Lay `style` out in a [`VIEWPORT_WIDTH`] viewport with fixed text metrics;
+/// returns the document height.
+pub fn layout_page(style: &StylePhaseOutput<'_>) -> f32 {
+    let output = layout_document(LayoutPhaseInput::from_style_output(
+        style,
+        VIEWPORT_WIDTH,
+        &FixedTextMeasurer,
+        None,
+    ));
+    output.root().rect.height
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CORPUS, build_dom, layout_page, parse_stylesheets, style_page, tokenize_streaming,
+        tokenize_whole,
+    };
+
+    #[test]
+    fn every_corpus_page_runs_through_every_stage() {
+        for page in CORPUS {
+            let tokens = tokenize_whole(page.html);
+            assert!(tokens > 1_000, "{}: {tokens} tokens", page.name);
+            // Text runs may split at chunk boundaries, so streaming can only
+            // produce more tokens, never fewer.
+            assert!(tokenize_streaming(page.html, 64) >= tokens, "{}", page.name);
+
+            let dom = build_dom(page.html);
+            let sheets = parse_stylesheets(&dom);
+            assert!(
+                sheets.author.stylesheet.rules.len() > 20,
+                "{}: author CSS is collected",
+                page.name
+            );
+            let style = style_page(&dom, &sheets);
+            assert!(layout_page(&style) > 1_000.0, "{}", page.name);
+        }
+    }
+}
@@ -0,0 +1,246 @@
+//! Bookmarks owned by the browser shell.
+//!
+//! The store is an ordered list of `(folder, title, url)` entries persisted to
+//! a small tab-separated file in the user data directory. Every mutation is
+//! written back immediately; the file is tiny and writes are user-initiated.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::user_data::{escape_field, unescape_field, user_data_dir, write_atomically};
+
+const BOOKMARKS_FILE: &str = "bookmarks.tsv";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bookmark {
+    pub title: String,
+    pub url: String,
+    /// Folder name, or `None` for top-level bookmarks shown directly on the bar.
+    pub folder: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct BookmarkStore {
+    entries: Vec<Bookmark>,
+    /// Backing file; `None` keeps the store in memory only.
+    path: Option<PathBuf>,
+}
+
+impl BookmarkStore {
+    /// Create an empty store that is never persisted.
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Load bookmarks from `bookmarks.tsv` in the user data directory.
+    ///
+    /// Falls back to an in-memory store if no data directory can be resolved.
+    /// A missing or unreadable file yields an empty store.
+    pub fn load_default() -> Self {
+        match user_data_dir() {
+            Some(dir) => Self::load(dir.join(BOOKMARKS_FILE)),
+            None => Self::in_memory(),
+        }
+    }
+
+    /// Load bookmarks from `path`, persisting future changes to the same file.
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let entries = fs::read_to_string(&path)
+            .map(|text| parse(&text))
+            .unwrap_or_default();
+        Self {
+            entries,
+            path: Some(path),
+        }
+    }
+
+    pub fn list(&self) -> &[Bookmark] {
+        &self.entries
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn contains(&self, url: &str) -> bool {
+        self.entries.iter().any(|bookmark| bookmark.url == url)
+    }
+
+    /// Distinct folder names in first-appearance order.
+    pub fn folders(&self) -> Vec<&str> {
+        let mut folders: Vec<&str> = Vec::new();
+        for folder in self.entries.iter().filter_map(|b| b.folder.as_deref()) {
+            if !folders.contains(&folder) {
+                folders.push(folder);
+            }
+        }
+        folders
+    }
+
+    /// Add a bookmark. Returns `false` (and changes nothing) if `url` is
+    /// already bookmarked. If saving fails the store is left unchanged.
+    pub fn add(&mut self, bookmark: Bookmark) -> io::Result<bool> {
+        if self.contains(&bookmark.url) {
+            return Ok(false);
+        }
+        let mut entries = self.entries.clone();
+        entries.push(bookmark);
+        self.commit(entries)?;
+        Ok(true)
+    }
+
+    /// Remove the bookmark for `url`. Returns `false` if none existed. If
+    /// saving fails the store is left unchanged.
+    pub fn remove(&mut self, url: &str) -> io::Result<bool> {
+        if !self.contains(url) {
+            return Ok(false);
+        }
+        let entries = self
+            .entries
+            .iter()
+            .filter(|bookmark| bookmark.url != url)
+            .cloned()
+            .collect();
+        self.commit(entries)?;
+        Ok(true)
+    }
+
+    /// Save `entries` and make them the store's only once they are on disk.
+    fn commit(&mut self, entries: Vec<Bookmark>) -> io::Result<()> {
+        if let Some(path) = &self.path {
+            write_atomically(path, &serialize(&entries))?;
+        }
+        self.entries = entries;
+        Ok(())
+    }
+}
+
+// One bookmark per line: `folder \t title \t url`, with an empty folder for
+// top-level entries. Tabs, newlines and backslashes inside fields are escaped.
+
+fn serialize(entries: &[Bookmark]) -> String {
+    let mut out = String::new();
+    for bookmark in entries {
+        out.push_str(&escape_field(bookmark.folder.as_deref().unwrap_or("")));
+        out.push('\t');
+        out.push_str(&escape_field(&bookmark.title));
+        out.push('\t');
+        out.push_str(&escape_field(&bookmark.url));
+        out.push('\n');
+    }
+    out
+}
+
+fn parse(text: &str) -> Vec<Bookmark> {
+    text.lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let folder = unescape_field(fields.next()?);
+            let title = unescape_field(fields.next()?);
+            let url = unescape_field(fields.next()?);
+            if url.is_empty() || fields.next().is_some() {
+                return None;
+            }
+            Some(Bookmark {
+                title,
+                url,
+                folder: Some(folder).filter(|folder| !folder.is_empty()),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bookmark(title: &str, url: &str, folder: Option<&str>) -> Bookmark {
+        Bookmark {
+            title: title.to_string(),
+            url: url.to_string(),
+            folder: folder.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn add_rejects_duplicate_urls_and_remove_reports_misses() {
+        let mut store = BookmarkStore::in_memory();
+        assert!(store.add(bookmark("A", "https://a.test/", None)).unwrap());
+        assert!(!store.add(bookmark("A2", "https://a.test/", None)).unwrap());
+        assert_eq!(store.list().len(), 1);
+
+        assert!(store.remove("https://a.test/").unwrap());
+        assert!(!store.remove("https://a.test/").unwrap());
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn folders_are_listed_once_in_insertion_order() {
+        let mut store = BookmarkStore::in_memory();
+        store
+            .add(bookmark("A", "https://a.test/", Some("Work")))
+            .unwrap();
+        store.add(bookmark("B", "https://b.test/", None)).unwrap();
+        store
+            .add(bookmark("C", "https://c.test/", Some("News")))
+            .unwrap();
+        store
+            .add(bookmark("D", "https://d.test/", Some("Work")))
+            .unwrap();
+        assert_eq!(store.folders(), ["Work", "News"]);
+    }
+
+    #[test]
+    fn serialization_round_trips_escaped_fields() {
+        let entries = vec![
+            bookmark("Tabs\tand\\slashes", "https://a.test/?q=1", None),
+            bookmark("Multi\nline", "https://b.test/", Some("Fol\tder")),
+        ];
+        assert_eq!(parse(&serialize(&entries)), entries);
+    }
+
+    #[test]
+    fn malformed_lines_are_skipped() {
+        let parsed = parse("only-one-field\n\tTitle\t\n\tOk\thttps://ok.test/\n");
+        assert_eq!(parsed, vec![bookmark("Ok", "https://ok.test/", None)]);
+    }
+
+    #[test]
+    fn store_persists_to_its_file() {
+        let dir = std::env::temp_dir().join(format!("borrowser-bookmarks-{}", std::process::id()));
+        let path = dir.join(BOOKMARKS_FILE);
+        let _ = fs::remove_dir_all(&dir);
+
+        let mut store = BookmarkStore::load(&path);
+        assert!(store.is_empty());
+        store
+            .add(bookmark("Example", "https://example.com/", Some("Docs")))
+            .unwrap();
+
+        let reloaded = BookmarkStore::load(&path);
+        assert_eq!(reloaded.list(), store.list());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn failed_saves_leave_the_store_unchanged() {
+        let blocker = std::env::temp_dir().join(format!(
+            "borrowser-bookmarks-blocked-{}",
+            std::process::id()
+        ));
+        fs::write(&blocker, "not a directory").unwrap();
+        let mut store = BookmarkStore::in_memory();
+        store.add(bookmark("A", "https://a.test/", None)).unwrap();
+        // The parent of the file is a plain file, so every save fails.
+        store.path = Some(blocker.join(BOOKMARKS_FILE));
+
+        assert!(store.add(bookmark("B", "https://b.test/", None)).is_err());
+        assert!(store.remove("https://a.test/").is_err());
+        assert_eq!(store.list(), [bookmark("A", "https://a.test/", None)]);
+
+        let _ = fs::remove_file(&blocker);
+    }
+}
@@ -1,11 +1,17 @@
+use bus::LayoutStylesheet;
 use core_types::StylesheetSlotId;
 use css::{
-    CascadeOrigin, ParseOptions, StylesheetCascadeInput, StylesheetParse,
-    parse_stylesheet_with_options,
+    CascadeOrigin, MediaEnvironment, ParseOptions, StylesheetCascadeInput, StylesheetParse,
+    media_query_list_matches, parse_stylesheet_with_options, resolve_media_rules,
+    stylesheet_imports,
 };
 use html::Node;
+use std::borrow::Cow;
 use std::sync::OnceLock;
-use url::Url;
+
+/// How deep `@import` rules may nest below a document stylesheet. Deeper
+/// imports are ignored, so a chain of distinct URLs cannot fetch forever.
+const MAX_IMPORT_DEPTH: usize = 8;
 
 const MINIMAL_UA_STYLESHEET: &str = r#"
 html, body, div, p, section, article, header, footer, main, nav, aside,
@@ -22,9 +28,21 @@ input, button, textarea {
     display: inline-block;
 }
 
+textarea {
+    resize: both;
+}
+
 head, title, meta, link, style, script {
     display: none;
 }
+
+a:link {
+    color: #0000ee;
+}
+
+a:visited {
+    color: #551a8b;
+}
 "#;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -36,7 +54,12 @@ enum StylesheetSlotKey {
 #[derive(Clone, Debug)]
 enum StylesheetSlotState {
     Pending,
-    Loaded(StylesheetParse),
+    /// Keeps the source text so the sheet can be parsed again when the media
+    /// environment changes.
+    Loaded {
+        css_text: String,
+        stylesheet: Box<StylesheetParse>,
+    },
     Failed,
     Aborted,
 }
@@ -46,12 +69,24 @@ struct StylesheetSlot {
     id: StylesheetSlotId,
     key: StylesheetSlotKey,
     state: StylesheetSlotState,
+    /// Media query list of the `@import` rule that loads this sheet; empty
+    /// for document sheets and unconditional imports.
+    media: String,
+    /// Sheets the `@import` rules of this one load, in rule order. They
+    /// cascade ahead of the sheet's own rules.
+    imports: Vec<StylesheetSlot>,
 }
 
-#[derive(Clone, Debug)]
-struct CascadedStylesheet {
-    origin: CascadeOrigin,
-    stylesheet: StylesheetParse,
+impl StylesheetSlot {
+    fn new(id: StylesheetSlotId, key: StylesheetSlotKey, state: StylesheetSlotState) -> Self {
+        Self {
+            id,
+            key,
+            state,
+            media: String::new(),
+            imports: Vec::new(),
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -68,16 +103,21 @@ pub(crate) struct StylesheetReconcileResult {
 
 #[derive(Clone, Debug)]
 pub(crate) struct DocumentStyleSet {
+    media: MediaEnvironment,
     next_slot_id: u64,
     slots: Vec<StylesheetSlot>,
+    /// Stylesheets fetched on a preload hint that no `<link>` in the DOM has
+    /// claimed yet; they apply once reconciliation adopts them.
+    preloads: Vec<StylesheetSlot>,
     loaded_stylesheets: Vec<StylesheetParse>,
-    cascade_stylesheets: Vec<CascadedStylesheet>,
+    cascade_stylesheets: Vec<LayoutStylesheet>,
 }
 
 impl DocumentStyleSet {
     pub(crate) fn clear(&mut self) {
         self.next_slot_id = 0;
         self.slots.clear();
+        self.preloads.clear();
         self.loaded_stylesheets.clear();
         self.rebuild_cascade_stylesheets();
     }
@@ -99,6 +139,7 @@ impl DocumentStyleSet {
         let mut used = vec![false; old_slots.len()];
         let mut fetches = Vec::new();
         let mut new_slots = Vec::with_capacity(discovered.len());
+        let mut inline_imports = Vec::new();
 
         for key in discovered {
             if let Some((index, old)) = old_slots
@@ -110,12 +151,17 @@ impl DocumentStyleSet {
                 new_slots.push(old.clone());
                 continue;
             }
+            if let Some(index) = self.preloads.iter().position(|slot| slot.key == key) {
+                new_slots.push(self.preloads.swap_remove(index));
+                continue;
+            }
 
             let id = self.allocate_slot_id();
             let state = match &key {
-                StylesheetSlotKey::Inline(text) => StylesheetSlotState::Loaded(
-                    parse_stylesheet_with_options(text, &ParseOptions::stylesheet()),
-                ),
+                StylesheetSlotKey::Inline(text) => {
+                    inline_imports.push((id, stylesheet_imports(text)));
+                    loaded_state(text, &self.media)
+                }
                 StylesheetSlotKey::External(url) => {
                     fetches.push(StylesheetFetch {
                         slot_id: id,
@@ -125,22 +171,95 @@ impl DocumentStyleSet {
                 }
             };
 
-            new_slots.push(StylesheetSlot { id, key, state });
+            new_slots.push(StylesheetSlot::new(id, key, state));
         }
 
         self.slots = new_slots;
+        for (id, imports) in inline_imports {
+            for import in imports {
+                fetches.extend(self.add_import(id, &import.href, &import.media, base_url));
+            }
+        }
         self.rebuild_loaded_stylesheets();
         StylesheetReconcileResult { fetches, changed }
     }
 
+    /// Start fetching the stylesheet at `href` before its `<link>` reaches the
+    /// DOM. Returns `None` when it is already known to this document.
+    pub(crate) fn preload_external(
+        &mut self,
+        href: &str,
+        base_url: Option<&str>,
+    ) -> Option<StylesheetFetch> {
+        let url = weburl::resolve_str(base_url, href)?;
+        let key = StylesheetSlotKey::External(url.clone());
+        if self
+            .slots
+            .iter()
+            .chain(&self.preloads)
+            .any(|slot| slot.key == key)
+        {
+            return None;
+        }
+        let id = self.allocate_slot_id();
+        self.preloads
+            .push(StylesheetSlot::new(id, key, StylesheetSlotState::Pending));
+        Some(StylesheetFetch { slot_id: id, url })
+    }
+
+    /// Register the sheet that an `@import` of `href` in the sheet `parent`
+    /// loads under the media query list `media`. Relative URLs resolve
+    /// against the importing sheet, or `base_url` for a `<style>` element.
+    ///
+    /// Returns `None` when `parent` is unknown or already imports the URL,
+    /// and when the import would loop or nest deeper than
+    /// [`MAX_IMPORT_DEPTH`].
+    pub(crate) fn add_import(
+        &mut self,
+        parent: StylesheetSlotId,
+        href: &str,
+        media: &str,
+        base_url: Option<&str>,
+    ) -> Option<StylesheetFetch> {
+        let mut chain = Vec::new();
+        if !self
+            .slots
+            .iter()
+            .chain(&self.preloads)
+            .any(|slot| slot_chain(slot, parent, &mut chain))
+        {
+            return None;
+        }
+        let importer = *chain.last()?;
+        let url = match &importer.key {
+            StylesheetSlotKey::External(url) => weburl::resolve_str(Some(url), href),
+            StylesheetSlotKey::Inline(_) => weburl::resolve_str(base_url, href),
+        }?;
+        let key = StylesheetSlotKey::External(url.clone());
+        if chain.len() > MAX_IMPORT_DEPTH
+            || chain.iter().any(|slot| slot.key == key)
+            || importer.imports.iter().any(|slot| slot.key == key)
+        {
+            return None;
+        }
+
+        let id = self.allocate_slot_id();
+        let importer = self.slot_mut(parent)?;
+        importer.imports.push(StylesheetSlot {
+            media: media.to_string(),
+            ..StylesheetSlot::new(id, key, StylesheetSlotState::Pending)
+        });
+        Some(StylesheetFetch { slot_id: id, url })
+    }
+
     #[cfg(test)]
     pub(crate) fn register_external_for_tests(&mut self, url: &str) -> StylesheetSlotId {
         let id = self.allocate_slot_id();
-        self.slots.push(StylesheetSlot {
+        self.slots.push(StylesheetSlot::new(
             id,
-            key: StylesheetSlotKey::External(url.to_string()),
-            state: StylesheetSlotState::Pending,
-        });
+            StylesheetSlotKey::External(url.to_string()),
+            StylesheetSlotState::Pending,
+        ));
         id
     }
 
@@ -149,6 +268,7 @@ impl DocumentStyleSet {
         slot_id: StylesheetSlotId,
         css_text: &str,
     ) -> bool {
+        let media = self.media;
         let Some(slot) = self.slot_mut(slot_id) else {
             return false;
         };
@@ -157,14 +277,14 @@ impl DocumentStyleSet {
         }
         if !matches!(
             slot.state,
-            StylesheetSlotState::Pending | StylesheetSlotState::Loaded(_)
+            StylesheetSlotState::Pending | StylesheetSlotState::Loaded { .. }
         ) {
             return false;
         }
-        slot.state = StylesheetSlotState::Loaded(parse_stylesheet_with_options(
-            css_text,
-            &ParseOptions::stylesheet(),
-        ));
+        slot.state = loaded_state(css_text, &media);
+        if !self.is_active(slot_id) {
+            return false;
+        }
         self.rebuild_loaded_stylesheets();
         true
     }
@@ -181,9 +301,9 @@ impl DocumentStyleSet {
 
     pub(crate) fn mark_external_failed(&mut self, slot_id: StylesheetSlotId) -> bool {
         if let Some(slot) = self.slot_mut(slot_id) {
-            let had_loaded_style = matches!(slot.state, StylesheetSlotState::Loaded(_));
+            let had_loaded_style = matches!(slot.state, StylesheetSlotState::Loaded { .. });
             slot.state = StylesheetSlotState::Failed;
-            if had_loaded_style {
+            if had_loaded_style && self.is_active(slot_id) {
                 self.rebuild_loaded_stylesheets();
                 return true;
             }
@@ -193,9 +313,9 @@ impl DocumentStyleSet {
 
     pub(crate) fn mark_external_aborted(&mut self, slot_id: StylesheetSlotId) -> bool {
         if let Some(slot) = self.slot_mut(slot_id) {
-            let had_loaded_style = matches!(slot.state, StylesheetSlotState::Loaded(_));
+            let had_loaded_style = matches!(slot.state, StylesheetSlotState::Loaded { .. });
             slot.state = StylesheetSlotState::Aborted;
-            if had_loaded_style {
+            if had_loaded_style && self.is_active(slot_id) {
                 self.rebuild_loaded_stylesheets();
                 return true;
             }
@@ -203,11 +323,42 @@ impl DocumentStyleSet {
         false
     }
 
+    pub(crate) fn media_environment(&self) -> MediaEnvironment {
+        self.media
+    }
+
+    /// Switch the environment `@media` rules are evaluated against.
+    ///
+    /// Returns whether any loaded stylesheet changed as a result.
+    pub(crate) fn set_media_environment(&mut self, media: MediaEnvironment) -> bool {
+        if self.media == media {
+            return false;
+        }
+        let previous = std::mem::replace(&mut self.media, media);
+        for slot in &mut self.preloads {
+            reevaluate_media(slot, &previous, &media);
+        }
+        let mut changed = false;
+        for slot in &mut self.slots {
+            changed |= reevaluate_media(slot, &previous, &media);
+        }
+        if changed {
+            self.rebuild_loaded_stylesheets();
+        }
+        changed
+    }
+
     pub(crate) fn pending_count(&self) -> usize {
-        self.slots
-            .iter()
-            .filter(|slot| matches!(slot.state, StylesheetSlotState::Pending))
-            .count()
+        fn count(slots: &[StylesheetSlot]) -> usize {
+            slots
+                .iter()
+                .map(|slot| {
+                    usize::from(matches!(slot.state, StylesheetSlotState::Pending))
+                        + count(&slot.imports)
+                })
+                .sum()
+        }
+        count(&self.slots)
     }
 
     pub(crate) fn stylesheets(&self) -> &[StylesheetParse] {
@@ -221,18 +372,15 @@ impl DocumentStyleSet {
         // does not.
         self.cascade_stylesheets
             .iter()
-            .map(|entry| match entry.origin {
-                CascadeOrigin::UserAgent => StylesheetCascadeInput::user_agent_for_namespace(
-                    &entry.stylesheet,
-                    html::ElementNamespace::Html,
-                ),
-                CascadeOrigin::User | CascadeOrigin::Author => {
-                    StylesheetCascadeInput::new(entry.origin, &entry.stylesheet)
-                }
-            })
+            .map(LayoutStylesheet::cascade_input)
             .collect()
     }
 
+    /// The cascade stylesheets, for laying the document out off the UI thread.
+    pub(crate) fn layout_stylesheets(&self) -> Vec<LayoutStylesheet> {
+        self.cascade_stylesheets.clone()
+    }
+
     fn allocate_slot_id(&mut self) -> StylesheetSlotId {
         self.next_slot_id = self
             .next_slot_id
@@ -242,24 +390,31 @@ impl DocumentStyleSet {
     }
 
     fn slot_mut(&mut self, slot_id: StylesheetSlotId) -> Option<&mut StylesheetSlot> {
-        self.slots.iter_mut().find(|slot| slot.id == slot_id)
+        find_slot_mut(self.slots.iter_mut().chain(&mut self.preloads), slot_id)
+    }
+
+    /// Whether the slot belongs to the document rather than to an unclaimed
+    /// preload.
+    fn is_active(&self, slot_id: StylesheetSlotId) -> bool {
+        fn contains(slots: &[StylesheetSlot], slot_id: StylesheetSlotId) -> bool {
+            slots
+                .iter()
+                .any(|slot| slot.id == slot_id || contains(&slot.imports, slot_id))
+        }
+        contains(&self.slots, slot_id)
     }
 
     fn rebuild_loaded_stylesheets(&mut self) {
         self.loaded_stylesheets.clear();
-        self.loaded_stylesheets
-            .extend(self.slots.iter().filter_map(|slot| match &slot.state {
-                StylesheetSlotState::Loaded(stylesheet) => Some(stylesheet.clone()),
-                StylesheetSlotState::Pending
-                | StylesheetSlotState::Failed
-                | StylesheetSlotState::Aborted => None,
-            }));
+        for slot in &self.slots {
+            push_loaded_stylesheets(slot, &self.media, &mut self.loaded_stylesheets);
+        }
         self.rebuild_cascade_stylesheets();
     }
 
     fn rebuild_cascade_stylesheets(&mut self) {
         self.cascade_stylesheets.clear();
-        self.cascade_stylesheets.push(CascadedStylesheet {
+        self.cascade_stylesheets.push(LayoutStylesheet {
             origin: CascadeOrigin::UserAgent,
             stylesheet: minimal_ua_stylesheet_parse(),
         });
@@ -268,7 +423,7 @@ impl DocumentStyleSet {
                 self.loaded_stylesheets
                     .iter()
                     .cloned()
-                    .map(|stylesheet| CascadedStylesheet {
+                    .map(|stylesheet| LayoutStylesheet {
                         origin: CascadeOrigin::Author,
                         stylesheet,
                     }),
@@ -279,8 +434,10 @@ impl DocumentStyleSet {
 impl Default for DocumentStyleSet {
     fn default() -> Self {
         let mut set = Self {
+            media: MediaEnvironment::default(),
             next_slot_id: 0,
             slots: Vec::new(),
+            preloads: Vec::new(),
             loaded_stylesheets: Vec::new(),
             cascade_stylesheets: Vec::new(),
         };
@@ -289,6 +446,117 @@ impl Default for DocumentStyleSet {
     }
 }
 
+fn find_slot_mut<'a>(
+    slots: impl IntoIterator<Item = &'a mut StylesheetSlot>,
+    slot_id: StylesheetSlotId,
+) -> Option<&'a mut StylesheetSlot> {
+    for slot in slots {
+        if slot.id == slot_id {
+            return Some(slot);
+        }
+        if let Some(found) = find_slot_mut(&mut slot.imports, slot_id) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+/// Collect the slots from `slot` down to the one with `slot_id` into
+/// `chain`; returns whether it was found.
+fn slot_chain<'a>(
+    slot: &'a StylesheetSlot,
+    slot_id: StylesheetSlotId,
+    chain: &mut Vec<&'a StylesheetSlot>,
+) -> bool {
+    chain.push(slot);
+    if slot.id == slot_id
+        || slot
+            .imports
+            .iter()
+            .any(|import| slot_chain(import, slot_id, chain))
+    {
+        return true;
+    }
+    chain.pop();
+    false
+}
+
+/// Push the loaded sheets of `slot` in cascade order: what it imports, then
+/// the sheet itself. An import whose media query list does not match
+/// contributes nothing.
+fn push_loaded_stylesheets(
+    slot: &StylesheetSlot,
+    media: &MediaEnvironment,
+    out: &mut Vec<StylesheetParse>,
+) {
+    if !media_query_list_matches(&slot.media, media) {
+        return;
+    }
+    for import in &slot.imports {
+        push_loaded_stylesheets(import, media, out);
+    }
+    if let StylesheetSlotState::Loaded { stylesheet, .. } = &slot.state {
+        out.push(StylesheetParse::clone(stylesheet));
+    }
+}
+
+/// Re-evaluate `slot` and its imports for `media`; returns whether what
+/// they contribute to the cascade may have changed.
+fn reevaluate_media(
+    slot: &mut StylesheetSlot,
+    previous: &MediaEnvironment,
+    media: &MediaEnvironment,
+) -> bool {
+    let mut changed = reparse_for_media(slot, previous, media);
+    changed |= media_query_list_matches(&slot.media, previous)
+        != media_query_list_matches(&slot.media, media);
+    for import in &mut slot.imports {
+        changed |= reevaluate_media(import, previous, media);
+    }
+    changed
+}
+
+/// Parse a loaded sheet again if its `@media` rules resolve differently for
+/// `media` than for `previous`; returns whether it changed.
+fn reparse_for_media(
+    slot: &mut StylesheetSlot,
+    previous: &MediaEnvironment,
+    media: &MediaEnvironment,
+) -> bool {
+    let StylesheetSlotState::Loaded {
+        css_text,
+        stylesheet,
+    } = &mut slot.state
+    else {
+        return false;
+    };
+    // Sheets without `@media` rules parse the same in any environment.
+    let Cow::Owned(resolved) = resolve_media_rules(css_text, media) else {
+        return false;
+    };
+    // Resizing the window changes the environment every frame, but only
+    // crossing a breakpoint changes which blocks apply.
+    if resolve_media_rules(css_text, previous) == resolved {
+        return false;
+    }
+    **stylesheet = parse_stylesheet_with_options(&resolved, &ParseOptions::stylesheet());
+    true
+}
+
+fn loaded_state(css_text: &str, media: &MediaEnvironment) -> StylesheetSlotState {
+    StylesheetSlotState::Loaded {
+        css_text: css_text.to_string(),
+        stylesheet: Box::new(parse_for_media(css_text, media)),
+    }
+}
+
+fn parse_for_media(css_text: &str, media: &MediaEnvironment) -> StylesheetParse {
+    parse_stylesheet_with_options(
+        &resolve_media_rules(css_text, media),
+        &ParseOptions::stylesheet(),
+    )
+}
+
 fn minimal_ua_stylesheet_parse() -> StylesheetParse {
     static MINIMAL_UA_STYLESHEET_PARSE: OnceLock<StylesheetParse> = OnceLock::new();
 
@@ -317,7 +585,7 @@ fn collect_stylesheet_inputs(
                 && name == "link"
                 && node.attr_has_token("rel", "stylesheet")
                 && let Some(href) = node.attr("href")
-                && let Some(url) = resolve_url(base_url, href)
+                && let Some(url) = weburl::resolve_str(base_url, href)
             {
                 out.push(StylesheetSlotKey::External(url));
             } else if element.namespace() == html::ElementNamespace::Html && name == "style" {
@@ -344,18 +612,10 @@ fn collect_stylesheet_inputs(
     }
 }
 
-fn resolve_url(base_url: Option<&str>, href: &str) -> Option<String> {
-    let href = href.trim();
-    if href.is_empty() {
-        return None;
-    }
-    let base = Url::parse(base_url?).ok()?;
-    base.join(href).ok().map(|url| url.to_string())
-}
-
 #[cfg(test)]
 mod tests {
     use super::DocumentStyleSet;
+    use css::MediaEnvironment;
     use html::internal::Id;
 
     fn element(
@@ -407,4 +667,42 @@ mod tests {
         assert!(result.fetches.is_empty());
         assert!(set.stylesheets().is_empty());
     }
+
+    #[test]
+    fn style_element_imports_are_fetched_and_follow_their_media_list() {
+        let dom = html::Node::Document {
+            id: Id(1),
+            doctype: None,
+            children: vec![element(
+                2,
+                html::ElementNamespace::Html,
+                "style",
+                Vec::new(),
+                vec![html::Node::Text {
+                    id: Id(3),
+                    text: "@import url(wide.css) (min-width: 600px); p { color: red; }".to_string(),
+                }],
+            )],
+        };
+        let mut set = DocumentStyleSet::default();
+        let result = set.reconcile_from_dom(&dom, Some("https://example.test/docs/"));
+        let [fetch] = result.fetches.as_slice() else {
+            panic!("expected one import fetch, got {:?}", result.fetches);
+        };
+        assert_eq!(fetch.url, "https://example.test/docs/wide.css");
+        assert_eq!(set.pending_count(), 1);
+
+        set.install_external_stylesheet(fetch.slot_id, "p { color: blue; }");
+        assert_eq!(
+            set.stylesheets().len(),
+            1,
+            "a narrow viewport skips the import"
+        );
+        let wide = MediaEnvironment::default().with_viewport_size(800.0, 600.0);
+        assert!(set.set_media_environment(wide));
+        assert_eq!(set.stylesheets().len(), 2);
+
+        let again = set.reconcile_from_dom(&dom, Some("https://example.test/docs/"));
+        assert!(again.fetches.is_empty());
+    }
 }
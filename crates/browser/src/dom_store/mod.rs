@@ -1,11 +0,0 @@
-mod arena;
-mod document;
-mod error;
-mod materialize;
-mod store;
-
-pub use error::DomPatchError;
-pub use store::DomStore;
-
-#[cfg(test)]
-mod tests;
@@ -3,8 +3,13 @@ use html::Node;
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum InputControlType {
     Text,
+    /// Single-line text control whose value is displayed masked.
+    Password,
     Checkbox,
     Radio,
+    /// Opens the native file picker; its chosen files live in the document
+    /// input state.
+    File,
     Other,
 }
 
@@ -30,8 +35,10 @@ pub fn input_control_type(node: &Node) -> InputControlType {
     match ty {
         None => InputControlType::Text, // missing type defaults to text
         Some(t) if t.eq_ignore_ascii_case("text") => InputControlType::Text,
+        Some(t) if t.eq_ignore_ascii_case("password") => InputControlType::Password,
         Some(t) if t.eq_ignore_ascii_case("checkbox") => InputControlType::Checkbox,
         Some(t) if t.eq_ignore_ascii_case("radio") => InputControlType::Radio,
+        Some(t) if t.eq_ignore_ascii_case("file") => InputControlType::File,
         _ => InputControlType::Other,
     }
 }
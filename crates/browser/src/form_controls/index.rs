@@ -1,4 +1,5 @@
-use gfx::input::{FormControlHandler, InputValueStore, from_input_id, to_input_id};
+use super::submit::FormIndex;
+use gfx::input::{FormControlHandler, FormSubmission, InputValueStore, from_input_id, to_input_id};
 use html::internal::Id;
 use input_core::{InputId, InputStore};
 use std::collections::HashMap;
@@ -6,6 +7,9 @@ use std::collections::HashMap;
 #[derive(Clone, Debug, Default)]
 pub struct FormControlIndex {
     pub(super) radio: RadioGroupIndex,
+    pub(super) forms: FormIndex,
+    /// File inputs, and whether each accepts several files (`multiple`).
+    pub(super) file_inputs: HashMap<Id, bool>,
 }
 
 impl FormControlIndex {
@@ -13,6 +17,16 @@ impl FormControlIndex {
         self.radio.click(store, radio_id)
     }
 
+    /// Whether the file input `id` accepts several files, or `None` if `id`
+    /// is not a file input.
+    pub fn file_input_accepts_multiple(&self, id: Id) -> Option<bool> {
+        self.file_inputs.get(&id).copied()
+    }
+
+    pub(super) fn register_file_input(&mut self, id: Id, multiple: bool) {
+        self.file_inputs.insert(id, multiple);
+    }
+
     pub(super) fn register_radio(
         &mut self,
         key: Option<RadioGroupKey>,
@@ -27,6 +41,15 @@ impl<S: InputStore> FormControlHandler<S> for FormControlIndex {
         // Convert InputId to html::internal::Id for group lookup, then use InputId for store operations
         self.radio.click_with_core(store, radio_id)
     }
+
+    fn form_submission(
+        &self,
+        store: &S,
+        trigger: InputId,
+        base_url: Option<&str>,
+    ) -> Option<FormSubmission> {
+        self.forms.submission(store, trigger, base_url)
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
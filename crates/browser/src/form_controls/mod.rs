@@ -1,6 +1,7 @@
 mod dom;
 mod index;
 mod seed;
+mod submit;
 
 pub use dom::{InputControlType, input_control_type};
 pub use index::FormControlIndex;
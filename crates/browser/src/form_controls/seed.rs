@@ -3,7 +3,9 @@ use super::dom::{
 };
 use super::index::{FormControlIndex, RadioGroupKey};
 use gfx::input::InputValueStore;
+use html::dom_utils::IdIndex;
 use html::{Node, internal::Id};
+use input_core::{InputConstraints, Pattern};
 use std::collections::HashMap;
 
 const DOCUMENT_SCOPE_ID: Id = Id(0);
@@ -18,7 +20,8 @@ pub fn seed_input_state_from_dom(store: &mut InputValueStore, dom: &Node) -> For
     let mut index = FormControlIndex::default();
     let mut radio_groups: HashMap<usize, RadioGroupSelection> = HashMap::new();
 
-    walk(store, dom, None, &mut index, &mut radio_groups);
+    let ids = IdIndex::new(dom);
+    walk(store, dom, None, &ids, &mut index, &mut radio_groups);
 
     index
 }
@@ -27,6 +30,7 @@ fn walk(
     store: &mut InputValueStore,
     node: &Node,
     scope_id: Option<Id>,
+    ids: &IdIndex<'_>,
     index: &mut FormControlIndex,
     radio_groups: &mut HashMap<usize, RadioGroupSelection>,
 ) {
@@ -34,13 +38,17 @@ fn walk(
         Node::Element { element }
             if element.namespace() == html::ElementNamespace::Html && element.name() == "input" =>
         {
-            handle_input(store, node, scope_id, index, radio_groups);
+            let owner = form_owner(node, scope_id, ids, index);
+            register_form_control(index, node, owner);
+            handle_input(store, node, owner, index, radio_groups);
         }
 
         Node::Element { element }
             if element.namespace() == html::ElementNamespace::Html
                 && element.name() == "textarea" =>
         {
+            let owner = form_owner(node, scope_id, ids, index);
+            register_form_control(index, node, owner);
             handle_textarea(store, node, element.children());
         }
 
@@ -49,18 +57,30 @@ fn walk(
                 store,
                 children,
                 next_scope_id(node, scope_id),
+                ids,
                 index,
                 radio_groups,
             );
         }
 
         Node::Element { element } => {
+            if element.namespace() == html::ElementNamespace::Html {
+                match element.name() {
+                    "form" => index.forms.register_form(node),
+                    "button" => {
+                        let owner = form_owner(node, scope_id, ids, index);
+                        register_form_control(index, node, owner);
+                    }
+                    _ => {}
+                }
+            }
             // Radio groups are scoped to their "form owner" (roughly: the nearest `<form>`).
             // If there is no form ancestor, group by the document scope.
             walk_children(
                 store,
                 element.children(),
                 next_scope_id(node, scope_id),
+                ids,
                 index,
                 radio_groups,
             );
@@ -84,29 +104,48 @@ fn handle_input(
     let already_present = store.has(id);
 
     match input_control_type(node) {
-        InputControlType::Text => {
-            if already_present {
-                return;
+        ty @ (InputControlType::Text | InputControlType::Password) => {
+            if !already_present {
+                let initial = value_attr(node).unwrap_or("").to_string();
+                store.ensure_initial(id, initial);
             }
-            let initial = value_attr(node).unwrap_or("").to_string();
-            store.ensure_initial(id, initial);
+            // Follows the current `type`, so switching it re-masks or reveals.
+            store.set_masked(id, ty == InputControlType::Password);
+            // Constraints follow the current DOM attributes even for inputs
+            // whose value is already user-owned.
+            store.set_constraints(id, text_constraints(node));
         }
 
         InputControlType::Checkbox => {
-            if already_present {
-                return;
+            if !already_present {
+                store.ensure_initial_checked(id, checked_attr(node));
             }
-            store.ensure_initial_checked(id, checked_attr(node));
+            store.set_constraints(
+                id,
+                InputConstraints {
+                    required: has_attr(node, "required"),
+                    ..InputConstraints::default()
+                },
+            );
         }
 
+        // `required` on radios is a group-level constraint; not tracked yet.
         InputControlType::Radio => {
             handle_radio(store, node, scope_id, already_present, index, radio_groups)
         }
 
+        InputControlType::File => index.register_file_input(id, has_attr(node, "multiple")),
+
         InputControlType::Other => {}
     }
 }
 
+fn register_form_control(index: &mut FormControlIndex, node: &Node, scope_id: Option<Id>) {
+    if let Some(form_id) = scope_id.filter(|id| *id != DOCUMENT_SCOPE_ID) {
+        index.forms.register_control(form_id, node);
+    }
+}
+
 fn handle_radio(
     store: &mut InputValueStore,
     node: &Node,
@@ -132,40 +171,76 @@ fn handle_radio(
 
 fn handle_textarea(store: &mut InputValueStore, node: &Node, children: &[Node]) {
     let id = node.id();
-    if store.has(id) {
-        return;
-    }
+    if !store.has(id) {
+        let mut initial = String::new();
+        collect_text(children, &mut initial);
+        // Parser-defined initial-LF suppression belongs to HTML tree construction.
+        // Runtime seeding only preserves the supplied DOM text while performing
+        // independent newline-representation normalization.
+        let initial = normalize_textarea_newlines(&initial);
 
-    let mut initial = String::new();
-    collect_text(children, &mut initial);
-    // Parser-defined initial-LF suppression belongs to HTML tree construction.
-    // Runtime seeding only preserves the supplied DOM text while performing
-    // independent newline-representation normalization.
-    let initial = normalize_textarea_newlines(&initial);
+        store.ensure_initial(id, initial);
+    }
 
-    store.ensure_initial(id, initial);
+    // `pattern`, `min` and `max` do not apply to textareas.
+    store.set_constraints(
+        id,
+        InputConstraints {
+            pattern: None,
+            min: None,
+            max: None,
+            ..text_constraints(node)
+        },
+    );
 }
 
 fn walk_children(
     store: &mut InputValueStore,
     children: &[Node],
     scope_id: Option<Id>,
+    ids: &IdIndex<'_>,
     index: &mut FormControlIndex,
     radio_groups: &mut HashMap<usize, RadioGroupSelection>,
 ) {
     for child in children {
-        walk(store, child, scope_id, index, radio_groups);
+        walk(store, child, scope_id, ids, index, radio_groups);
     }
 }
 
+/// A control's form owner: the form its `form` attribute names, otherwise
+/// the nearest ancestor form (`scope_id`). A `form` attribute that names no
+/// form leaves the control ownerless, grouped by the document scope.
+fn form_owner(
+    node: &Node,
+    scope_id: Option<Id>,
+    ids: &IdIndex<'_>,
+    index: &mut FormControlIndex,
+) -> Option<Id> {
+    let Some(form_id) = attr(node, "form") else {
+        return scope_id;
+    };
+    match ids.get(form_id).filter(|owner| is_html_form(owner)) {
+        Some(form) => {
+            // The form may come later in the tree than this control.
+            index.forms.register_form(form);
+            Some(form.id())
+        }
+        None => Some(DOCUMENT_SCOPE_ID),
+    }
+}
+
+fn is_html_form(node: &Node) -> bool {
+    matches!(
+        node,
+        Node::Element { element }
+            if element.namespace() == html::ElementNamespace::Html && element.name() == "form"
+    )
+}
+
 fn next_scope_id(node: &Node, scope_id: Option<Id>) -> Option<Id> {
     match node {
         Node::Document { .. } => Some(DOCUMENT_SCOPE_ID),
-        Node::Element { element }
-            if element.namespace() == html::ElementNamespace::Html && element.name() == "form" =>
-        {
-            Some(node.id())
-        }
+        _ if is_html_form(node) => Some(node.id()),
         _ => scope_id,
     }
 }
@@ -236,6 +311,30 @@ fn apply_radio_selection_policy(
     }
 }
 
+/// Constraint validation rules for text-editable controls.
+fn text_constraints(node: &Node) -> InputConstraints {
+    InputConstraints {
+        required: has_attr(node, "required"),
+        pattern: attr(node, "pattern").and_then(Pattern::new),
+        min_length: attr(node, "minlength").and_then(non_negative_integer),
+        max_length: attr(node, "maxlength").and_then(non_negative_integer),
+        min: attr(node, "min").and_then(finite_number),
+        max: attr(node, "max").and_then(finite_number),
+    }
+}
+
+fn non_negative_integer(value: &str) -> Option<usize> {
+    value.trim().parse().ok()
+}
+
+fn finite_number(value: &str) -> Option<f64> {
+    value
+        .trim()
+        .parse()
+        .ok()
+        .filter(|number: &f64| number.is_finite())
+}
+
 fn value_attr(node: &Node) -> Option<&str> {
     attr(node, "value")
 }
@@ -0,0 +1,270 @@
+use super::dom::{InputControlType, attr, has_attr, input_control_type};
+use gfx::input::{FormSubmission, from_input_id, to_input_id};
+use html::{Node, internal::Id};
+use input_core::{InputId, InputStore};
+use std::collections::HashMap;
+use url::Url;
+
+/// Forms in the document and the controls they own, each in tree order.
+#[derive(Clone, Debug, Default)]
+pub(super) struct FormIndex {
+    forms: Vec<FormEntry>,
+    form_by_id: HashMap<Id, usize>,
+    form_by_control: HashMap<Id, usize>,
+}
+
+#[derive(Clone, Debug)]
+struct FormEntry {
+    action: String,
+    method: FormMethod,
+    /// `novalidate`: submit without checking the controls' constraints.
+    no_validate: bool,
+    controls: Vec<FormControl>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FormMethod {
+    Get,
+    Post,
+}
+
+#[derive(Clone, Debug)]
+struct FormControl {
+    id: Id,
+    name: String,
+    kind: FormControlKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum FormControlKind {
+    /// Single-line text field; its value lives in the input store.
+    TextField,
+    /// Its value lives in the input store.
+    TextArea,
+    /// Checkbox or radio, submitted with `value` when checked.
+    Checkable {
+        value: String,
+    },
+    Hidden {
+        value: String,
+    },
+    /// Submitted with `value` only when it is the submitter;
+    /// `formnovalidate` skips constraint validation when it submits.
+    SubmitButton {
+        value: String,
+        no_validate: bool,
+    },
+}
+
+impl FormIndex {
+    /// Record `form`; registering the same form again is a no-op, since a
+    /// control's `form` attribute can name a form later in the tree.
+    pub(super) fn register_form(&mut self, form: &Node) {
+        if self.form_by_id.contains_key(&form.id()) {
+            return;
+        }
+        let method = match attr(form, "method") {
+            Some(method) if method.trim().eq_ignore_ascii_case("post") => FormMethod::Post,
+            _ => FormMethod::Get,
+        };
+        let entry = FormEntry {
+            action: attr(form, "action").unwrap_or("").trim().to_string(),
+            method,
+            no_validate: has_attr(form, "novalidate"),
+            controls: Vec::new(),
+        };
+        self.form_by_id.insert(form.id(), self.forms.len());
+        self.forms.push(entry);
+    }
+
+    /// Record `node` as a control of the form `form_id`, if it takes part in
+    /// submission.
+    pub(super) fn register_control(&mut self, form_id: Id, node: &Node) {
+        let Some(&form) = self.form_by_id.get(&form_id) else {
+            return;
+        };
+        let Some(kind) = form_control_kind(node) else {
+            return;
+        };
+        let id = node.id();
+        self.form_by_control.insert(id, form);
+        self.forms[form].controls.push(FormControl {
+            id,
+            name: attr(node, "name").unwrap_or("").to_string(),
+            kind,
+        });
+    }
+
+    /// Submission of the form owning `trigger`: a submit button, or a text
+    /// field doing implicit submission. A form with an invalid control does
+    /// not submit unless it has `novalidate` or the submitter has
+    /// `formnovalidate`.
+    pub(super) fn submission<S: InputStore + ?Sized>(
+        &self,
+        store: &S,
+        trigger: InputId,
+        base_url: Option<&str>,
+    ) -> Option<FormSubmission> {
+        let trigger = from_input_id(trigger);
+        let form = &self.forms[*self.form_by_control.get(&trigger)?];
+        let trigger_kind = &form.controls.iter().find(|c| c.id == trigger)?.kind;
+
+        let submitter = match trigger_kind {
+            FormControlKind::SubmitButton { .. } => Some(trigger),
+            // Implicit submission goes through the form's default button; a
+            // form without one only submits if this is its lone text field.
+            FormControlKind::TextField => match form.default_button() {
+                Some(button) => Some(button),
+                None if form.text_field_count() == 1 => None,
+                None => return None,
+            },
+            _ => return None,
+        };
+        if form.validates(submitter) && !form.controls_are_valid(store) {
+            return None;
+        }
+
+        let mut url = match base_url {
+            Some(base) => Url::parse(base).ok()?.join(&form.action).ok()?,
+            None => Url::parse(&form.action).ok()?,
+        };
+        let entries = form.entries(store, submitter);
+        let payload = match form.method {
+            // GET replaces the action's query with the form data.
+            FormMethod::Get => {
+                url.set_query(None);
+                url.query_pairs_mut().extend_pairs(entries);
+                Default::default()
+            }
+            FormMethod::Post => net::urlencoded_form(entries),
+        };
+        Some(FormSubmission {
+            url: url.to_string(),
+            payload,
+        })
+    }
+}
+
+impl FormEntry {
+    fn default_button(&self) -> Option<Id> {
+        self.controls
+            .iter()
+            .find(|c| matches!(c.kind, FormControlKind::SubmitButton { .. }))
+            .map(|c| c.id)
+    }
+
+    fn validates(&self, submitter: Option<Id>) -> bool {
+        let submitter_skips = self.controls.iter().any(|c| {
+            Some(c.id) == submitter
+                && matches!(
+                    c.kind,
+                    FormControlKind::SubmitButton {
+                        no_validate: true,
+                        ..
+                    }
+                )
+        });
+        !self.no_validate && !submitter_skips
+    }
+
+    /// Whether every control satisfies its constraints; controls without
+    /// constraints are valid.
+    fn controls_are_valid<S: InputStore + ?Sized>(&self, store: &S) -> bool {
+        self.controls
+            .iter()
+            .all(|control| store.validity(to_input_id(control.id)).is_valid())
+    }
+
+    fn text_field_count(&self) -> usize {
+        self.controls
+            .iter()
+            .filter(|c| c.kind == FormControlKind::TextField)
+            .count()
+    }
+
+    /// Name/value pairs of the successful controls, in tree order.
+    fn entries<S: InputStore + ?Sized>(
+        &self,
+        store: &S,
+        submitter: Option<Id>,
+    ) -> Vec<(String, String)> {
+        self.controls
+            .iter()
+            .filter(|control| !control.name.is_empty())
+            .filter_map(|control| {
+                let value = match &control.kind {
+                    FormControlKind::TextField | FormControlKind::TextArea => {
+                        store.get(to_input_id(control.id)).unwrap_or("")
+                    }
+                    FormControlKind::Checkable { value } => {
+                        if !store.is_checked(to_input_id(control.id)) {
+                            return None;
+                        }
+                        value
+                    }
+                    FormControlKind::Hidden { value } => value,
+                    FormControlKind::SubmitButton { value, .. } => {
+                        if submitter != Some(control.id) {
+                            return None;
+                        }
+                        value
+                    }
+                };
+                Some((normalize_newlines(&control.name), normalize_newlines(value)))
+            })
+            .collect()
+    }
+}
+
+fn form_control_kind(node: &Node) -> Option<FormControlKind> {
+    let Node::Element { element } = node else {
+        return None;
+    };
+    if element.namespace() != html::ElementNamespace::Html || has_attr(node, "disabled") {
+        return None;
+    }
+    let value = || attr(node, "value").unwrap_or("").to_string();
+    let submit_button = || FormControlKind::SubmitButton {
+        value: value(),
+        no_validate: has_attr(node, "formnovalidate"),
+    };
+    match element.name() {
+        "input" => match input_control_type(node) {
+            InputControlType::Text | InputControlType::Password => Some(FormControlKind::TextField),
+            InputControlType::Checkbox | InputControlType::Radio => {
+                Some(FormControlKind::Checkable {
+                    value: attr(node, "value").unwrap_or("on").to_string(),
+                })
+            }
+            // Chosen files are not submitted yet; without multipart bodies
+            // only their names would be sent anyway.
+            InputControlType::File => None,
+            InputControlType::Other => match attr(node, "type").map(str::trim) {
+                Some(ty) if ty.eq_ignore_ascii_case("hidden") => {
+                    Some(FormControlKind::Hidden { value: value() })
+                }
+                Some(ty) if ty.eq_ignore_ascii_case("submit") => Some(submit_button()),
+                _ => None,
+            },
+        },
+        "textarea" => Some(FormControlKind::TextArea),
+        "button" => match attr(node, "type").map(str::trim) {
+            Some(ty) if ty.eq_ignore_ascii_case("reset") || ty.eq_ignore_ascii_case("button") => {
+                None
+            }
+            // Missing and invalid types are the submit state.
+            _ => Some(submit_button()),
+        },
+        _ => None,
+    }
+}
+
+/// Form data uses CRLF line breaks.
+fn normalize_newlines(s: &str) -> String {
+    if !s.contains(['\r', '\n']) {
+        return s.to_string();
+    }
+    s.replace("\r\n", "\n")
+        .replace('\r', "\n")
+        .replace('\n', "\r\n")
+}
@@ -1,7 +1,8 @@
 use crate::form_controls::index::RadioGroupKey;
 
 use super::*;
-use gfx::input::InputValueStore;
+use core_types::{HttpMethod, RequestPayload};
+use gfx::input::{FormSubmission, InputValueStore};
 use html::{Node, internal::Id};
 
 fn elem(id: u32, name: &str, attributes: Vec<(&str, Option<&str>)>, children: Vec<Node>) -> Node {
@@ -449,3 +450,389 @@ fn seeds_parser_created_textarea_without_double_initial_lf_suppression() {
         "parser removes one source LF; runtime must not remove a second"
     );
 }
+
+#[test]
+fn seeds_validation_constraints_from_attributes() {
+    let dom = doc(vec![
+        input(
+            1,
+            "text",
+            vec![("required", None), ("pattern", Some("[a-z]+"))],
+        ),
+        input(
+            2,
+            "text",
+            vec![("value", Some("ab")), ("minlength", Some("3"))],
+        ),
+        input(3, "checkbox", vec![("required", None), ("checked", None)]),
+        elem(4, "textarea", vec![("required", None)], Vec::new()),
+        input(
+            5,
+            "text",
+            vec![
+                ("value", Some("12")),
+                ("min", Some(" 1 ")),
+                ("max", Some("10")),
+            ],
+        ),
+        input(6, "text", vec![("value", Some("0")), ("min", Some("NaN"))]),
+        elem(7, "textarea", vec![("min", Some("5"))], Vec::new()),
+    ]);
+
+    let mut store = InputValueStore::new();
+    let _ = seed_input_state_from_dom(&mut store, &dom);
+
+    assert_eq!(
+        store.validity(Id(1)).reason(),
+        Some(input_core::InvalidReason::ValueMissing)
+    );
+    assert_eq!(
+        store.validity(Id(2)).reason(),
+        Some(input_core::InvalidReason::TooShort)
+    );
+    assert!(store.validity(Id(3)).is_valid());
+    assert!(!store.validity(Id(4)).is_valid());
+    assert_eq!(
+        store.validity(Id(5)).reason(),
+        Some(input_core::InvalidReason::RangeOverflow)
+    );
+    assert!(store.validity(Id(6)).is_valid());
+    assert!(store.validity(Id(7)).is_valid());
+
+    // User edits revalidate against the seeded constraints.
+    store.set(Id(1), "abc".to_string());
+    assert!(store.validity(Id(1)).is_valid());
+    store.set(Id(1), "ABC".to_string());
+    assert_eq!(
+        store.validity(Id(1)).reason(),
+        Some(input_core::InvalidReason::PatternMismatch)
+    );
+}
+
+fn submission(
+    index: &FormControlIndex,
+    store: &InputValueStore,
+    trigger: u32,
+) -> Option<FormSubmission> {
+    use gfx::input::{FormControlHandler, to_input_id};
+    index.form_submission(
+        store.inner(),
+        to_input_id(Id(trigger)),
+        Some("https://example.com/dir/page?old=1#top"),
+    )
+}
+
+/// URL of a GET submission.
+fn submit(index: &FormControlIndex, store: &InputValueStore, trigger: u32) -> Option<String> {
+    let submission = submission(index, store, trigger)?;
+    assert_eq!(submission.payload, RequestPayload::default());
+    Some(submission.url)
+}
+
+#[test]
+fn get_submission_serializes_successful_controls_in_tree_order() {
+    let dom = doc(vec![elem(
+        1,
+        "form",
+        vec![("action", Some("search"))],
+        vec![
+            input(2, "text", vec![("name", Some("q"))]),
+            input(
+                3,
+                "hidden",
+                vec![("name", Some("lang")), ("value", Some("en"))],
+            ),
+            input(
+                4,
+                "checkbox",
+                vec![("name", Some("safe")), ("checked", None)],
+            ),
+            input(5, "checkbox", vec![("name", Some("off"))]),
+            input(6, "radio", vec![("name", Some("r")), ("value", Some("a"))]),
+            input(
+                7,
+                "radio",
+                vec![("name", Some("r")), ("value", Some("b")), ("checked", None)],
+            ),
+            input(8, "text", vec![("name", Some("x")), ("disabled", None)]),
+            elem(9, "textarea", vec![("name", Some("t"))], Vec::new()),
+            elem(
+                10,
+                "button",
+                vec![("name", Some("go")), ("value", Some("1"))],
+                vec![text(11, "Go")],
+            ),
+            elem(12, "button", vec![("type", Some("button"))], Vec::new()),
+        ],
+    )]);
+    let mut store = InputValueStore::new();
+    let index = seed_input_state_from_dom(&mut store, &dom);
+    store.insert_text(Id(2), "rust & egui");
+    store.insert_text_multiline(Id(9), "a\nb");
+
+    assert_eq!(
+        submit(&index, &store, 10).as_deref(),
+        Some("https://example.com/dir/search?q=rust+%26+egui&lang=en&safe=on&r=b&t=a%0D%0Ab&go=1")
+    );
+    // Enter in a text field submits through the default button.
+    assert_eq!(submit(&index, &store, 2), submit(&index, &store, 10));
+    assert_eq!(submit(&index, &store, 12), None);
+    assert_eq!(submit(&index, &store, 4), None);
+}
+
+#[test]
+fn form_attribute_associates_controls_outside_the_form() {
+    let dom = doc(vec![
+        input(
+            1,
+            "text",
+            vec![("name", Some("early")), ("form", Some("f"))],
+        ),
+        input(
+            2,
+            "radio",
+            vec![("name", Some("r")), ("form", Some("f")), ("checked", None)],
+        ),
+        elem(
+            3,
+            "form",
+            vec![("id", Some("f")), ("action", Some("go"))],
+            vec![
+                input(4, "radio", vec![("name", Some("r")), ("checked", None)]),
+                input(
+                    5,
+                    "text",
+                    vec![("name", Some("elsewhere")), ("form", Some("none"))],
+                ),
+                elem(6, "button", Vec::new(), Vec::new()),
+            ],
+        ),
+        input(7, "text", vec![("name", Some("late")), ("form", Some("f"))]),
+    ]);
+    let mut store = InputValueStore::new();
+    let index = seed_input_state_from_dom(&mut store, &dom);
+    store.insert_text(Id(1), "a");
+    store.insert_text(Id(5), "b");
+    store.insert_text(Id(7), "c");
+
+    // Radios owned by the same form group together wherever they sit, so
+    // the later default selection wins.
+    assert!(!store.is_checked(Id(2)));
+    assert!(store.is_checked(Id(4)));
+    // A `form` attribute naming no form leaves the control ownerless.
+    assert_eq!(
+        submit(&index, &store, 6).as_deref(),
+        Some("https://example.com/dir/go?early=a&r=on&late=c")
+    );
+    assert_eq!(submit(&index, &store, 5), None);
+}
+
+#[test]
+fn submission_requires_an_allowed_trigger() {
+    let dom = doc(vec![
+        elem(
+            1,
+            "form",
+            Vec::new(),
+            vec![
+                input(2, "text", vec![("name", Some("a"))]),
+                input(3, "text", vec![("name", Some("b"))]),
+            ],
+        ),
+        elem(6, "form", Vec::new(), vec![input(7, "text", Vec::new())]),
+        input(8, "text", vec![("name", Some("outside"))]),
+    ]);
+    let mut store = InputValueStore::new();
+    let index = seed_input_state_from_dom(&mut store, &dom);
+
+    // Several text fields and no submit button block implicit submission.
+    assert_eq!(submit(&index, &store, 2), None);
+    // A lone field submits to the document URL, with an empty query.
+    assert_eq!(
+        submit(&index, &store, 7).as_deref(),
+        Some("https://example.com/dir/page?")
+    );
+    assert_eq!(submit(&index, &store, 8), None);
+}
+
+#[test]
+fn invalid_controls_block_submission_unless_validation_is_skipped() {
+    let form = |id: u32, attrs: Vec<(&'static str, Option<&'static str>)>| {
+        elem(
+            id,
+            "form",
+            attrs,
+            vec![
+                input(
+                    id + 1,
+                    "text",
+                    vec![("name", Some("q")), ("required", None)],
+                ),
+                elem(id + 2, "button", Vec::new(), Vec::new()),
+                elem(id + 3, "button", vec![("formnovalidate", None)], Vec::new()),
+            ],
+        )
+    };
+    let dom = doc(vec![
+        form(1, vec![("action", Some("a"))]),
+        form(5, vec![("action", Some("b")), ("novalidate", None)]),
+    ]);
+    let mut store = InputValueStore::new();
+    let index = seed_input_state_from_dom(&mut store, &dom);
+
+    // The required field is empty.
+    assert_eq!(submit(&index, &store, 3), None);
+    assert_eq!(submit(&index, &store, 2), None);
+    assert_eq!(
+        submit(&index, &store, 4).as_deref(),
+        Some("https://example.com/dir/a?q=")
+    );
+    assert_eq!(
+        submit(&index, &store, 7).as_deref(),
+        Some("https://example.com/dir/b?q=")
+    );
+
+    store.insert_text(Id(2), "x");
+    assert_eq!(
+        submit(&index, &store, 3).as_deref(),
+        Some("https://example.com/dir/a?q=x")
+    );
+}
+
+#[test]
+fn out_of_range_values_block_submission_until_edited_into_range() {
+    let dom = doc(vec![elem(
+        1,
+        "form",
+        vec![("action", Some("search"))],
+        vec![input(
+            2,
+            "text",
+            vec![
+                ("name", Some("n")),
+                ("value", Some("42")),
+                ("min", Some("1")),
+                ("max", Some("10")),
+            ],
+        )],
+    )]);
+    let mut store = InputValueStore::new();
+    let index = seed_input_state_from_dom(&mut store, &dom);
+
+    assert_eq!(submit(&index, &store, 2), None);
+
+    store.set(Id(2), "0".to_string());
+    assert_eq!(submit(&index, &store, 2), None);
+
+    store.set(Id(2), "7".to_string());
+    assert_eq!(
+        submit(&index, &store, 2).as_deref(),
+        Some("https://example.com/dir/search?n=7")
+    );
+}
+
+#[test]
+fn post_submission_sends_urlencoded_entries_to_the_action_url() {
+    let dom = doc(vec![elem(
+        1,
+        "form",
+        vec![
+            ("method", Some(" PoSt ")),
+            ("action", Some("login?next=home")),
+        ],
+        vec![
+            input(2, "text", vec![("name", Some("user"))]),
+            elem(3, "textarea", vec![("name", Some("note"))], Vec::new()),
+            input(
+                4,
+                "submit",
+                vec![("name", Some("go")), ("value", Some("Sign in"))],
+            ),
+        ],
+    )]);
+    let mut store = InputValueStore::new();
+    let index = seed_input_state_from_dom(&mut store, &dom);
+    store.insert_text(Id(2), "a&b");
+    store.insert_text_multiline(Id(3), "x\ny");
+
+    let submission = submission(&index, &store, 2).expect("implicit POST submission");
+    // POST keeps the action's query; the entries go in the body.
+    assert_eq!(submission.url, "https://example.com/dir/login?next=home");
+    assert_eq!(submission.payload.method, HttpMethod::Post);
+    assert_eq!(
+        submission.payload.headers,
+        vec![(
+            "Content-Type".to_string(),
+            "application/x-www-form-urlencoded".to_string()
+        )]
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&submission.payload.body),
+        "user=a%26b&note=x%0D%0Ay&go=Sign+in"
+    );
+}
+
+#[test]
+fn password_inputs_seed_masked_text_state_and_submit_the_real_value() {
+    let dom = doc(vec![elem(
+        1,
+        "form",
+        Vec::new(),
+        vec![input(
+            2,
+            "PassWord",
+            vec![("name", Some("pw")), ("value", Some("s3cr€t"))],
+        )],
+    )]);
+    let mut store = InputValueStore::new();
+    let index = seed_input_state_from_dom(&mut store, &dom);
+
+    assert_eq!(
+        input_control_type(find_element(&dom, "input").unwrap()),
+        InputControlType::Password
+    );
+    assert!(store.is_masked(Id(2)));
+    assert_eq!(store.get(Id(2)), Some("s3cr€t"));
+    let (display, ..) = store.get_display_state(Id(2)).unwrap();
+    assert_eq!(display, "••••••");
+    assert_eq!(
+        submit(&index, &store, 2).as_deref(),
+        Some("https://example.com/dir/page?pw=s3cr%E2%82%ACt")
+    );
+
+    // Changing the type to text reveals the value on the next seed.
+    let dom = doc(vec![input(2, "text", vec![("value", Some("ignored"))])]);
+    let _ = seed_input_state_from_dom(&mut store, &dom);
+    assert!(!store.is_masked(Id(2)));
+    assert_eq!(store.get(Id(2)), Some("s3cr€t"));
+}
+
+#[test]
+fn file_inputs_record_multiple_and_are_not_submitted() {
+    let dom = doc(vec![elem(
+        1,
+        "form",
+        Vec::new(),
+        vec![
+            input(2, "FILE", vec![("name", Some("upload"))]),
+            input(3, "file", vec![("multiple", None)]),
+            input(4, "submit", Vec::new()),
+        ],
+    )]);
+    let mut store = InputValueStore::new();
+    let index = seed_input_state_from_dom(&mut store, &dom);
+
+    assert_eq!(
+        input_control_type(find_element(&dom, "input").unwrap()),
+        InputControlType::File
+    );
+    assert_eq!(index.file_input_accepts_multiple(Id(2)), Some(false));
+    assert_eq!(index.file_input_accepts_multiple(Id(3)), Some(true));
+    assert_eq!(index.file_input_accepts_multiple(Id(4)), None);
+
+    store.set(Id(2), "notes.txt".to_string());
+    assert_eq!(
+        submit(&index, &store, 4).as_deref(),
+        Some("https://example.com/dir/page?")
+    );
+}
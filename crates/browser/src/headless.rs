@@ -0,0 +1,160 @@
+//! Tabs without a window: load a URL in an offscreen tab driven straight
+//! from the runtime event channel, then print it, render it to pixels, or
+//! hand over a snapshot of its layout inputs.
+
+use crate::tab::Tab;
+use bus::{CoreCommand, CoreEvent, LayoutJob};
+use core_types::{ResourceKind, TabId};
+use egui::{Color32, ColorImage, Context, RawInput};
+use gfx::EguiTextMeasurer;
+use gfx::software::SoftwareRenderer;
+use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender};
+use std::time::{Duration, Instant};
+
+/// Quiet period after the document finished loading, or after a frame,
+/// before the page counts as settled, so the parser's final DOM patches and
+/// the layout runtime's results arrive.
+const SETTLE: Duration = Duration::from_millis(250);
+
+/// Navigate `tab` to `url` over the runtime bus and wait until the page
+/// loaded.
+///
+/// `cmd_tx` and `evt_rx` are the two ends of a running runtime bus. The page
+/// counts as loaded once its document arrived and no subresource is
+/// pending; if `timeout` runs out after the document arrived, it counts as
+/// loaded so far. The error is the reason the document did not load.
+pub fn load_url(
+    tab: &mut Tab,
+    url: &str,
+    cmd_tx: Sender<CoreCommand>,
+    evt_rx: &Receiver<CoreEvent>,
+    timeout: Duration,
+) -> Result<(), String> {
+    tab.set_bus_sender(cmd_tx);
+    tab.navigate_to_new(url.to_string());
+    if tab.current_url().is_none() {
+        return Err(load_error(tab, "invalid URL"));
+    }
+
+    let deadline = Instant::now() + timeout;
+    let mut document_done = false;
+    loop {
+        let now = Instant::now();
+        let ready = document_done && !tab.is_loading();
+        let wait = if ready {
+            SETTLE
+        } else {
+            deadline.saturating_duration_since(now)
+        };
+        match evt_rx.recv_timeout(wait) {
+            Ok(evt) => {
+                if evt.request() == Some((tab.tab_id, tab.nav_gen)) {
+                    match &evt {
+                        CoreEvent::NetworkDone {
+                            kind: ResourceKind::Html,
+                            ..
+                        } => document_done = true,
+                        CoreEvent::NetworkError {
+                            kind: ResourceKind::Html,
+                            message,
+                            ..
+                        } => return Err(message.clone()),
+                        CoreEvent::RuntimeCrashed { which, error, .. } => {
+                            return Err(format!("{} crashed: {error}", which.name()));
+                        }
+                        _ => {}
+                    }
+                }
+                tab.on_core_event(evt);
+            }
+            Err(RecvTimeoutError::Timeout) if document_done => return Ok(()),
+            Err(RecvTimeoutError::Timeout) => return Err(load_error(tab, "timed out")),
+            Err(RecvTimeoutError::Disconnected) => return Err("runtimes stopped".to_string()),
+        }
+        if Instant::now() >= deadline {
+            return if document_done {
+                Ok(())
+            } else {
+                Err(load_error(tab, "timed out"))
+            };
+        }
+    }
+}
+
+/// Load `url` in a new tab `tab_id` and render the page in a viewport of
+/// `size` pixels, as the window would show it. The tab is closed afterwards.
+///
+/// Frames are drawn until the page settles: no event arrives for a while
+/// after a frame and no layout is outstanding in the layout runtime.
+pub fn render_url(
+    url: &str,
+    tab_id: TabId,
+    cmd_tx: Sender<CoreCommand>,
+    evt_rx: &Receiver<CoreEvent>,
+    size: [usize; 2],
+    timeout: Duration,
+) -> Result<ColorImage, String> {
+    let mut tab = Tab::new(tab_id);
+    let deadline = Instant::now() + timeout;
+    let loaded = load_url(&mut tab, url, cmd_tx.clone(), evt_rx, timeout);
+    let image = loaded.map(|()| render_settled(&mut tab, evt_rx, size, deadline));
+    let _ = cmd_tx.send(CoreCommand::TabClosed { tab_id });
+    image
+}
+
+/// Load `url` in a new tab `tab_id` and snapshot what laying the page out in
+/// a viewport `viewport_width` px wide takes, measuring text with the fonts
+/// the window paints with. The tab is closed afterwards.
+pub fn load_layout_job(
+    url: &str,
+    tab_id: TabId,
+    cmd_tx: Sender<CoreCommand>,
+    evt_rx: &Receiver<CoreEvent>,
+    viewport_width: f32,
+    timeout: Duration,
+) -> Result<LayoutJob, String> {
+    let mut tab = Tab::new(tab_id);
+    let loaded = load_url(&mut tab, url, cmd_tx.clone(), evt_rx, timeout);
+    let job = loaded.and_then(|()| tab.layout_job(viewport_width, Box::new(text_measurer())));
+    let _ = cmd_tx.send(CoreCommand::TabClosed { tab_id });
+    job
+}
+
+/// egui loads its fonts in the first pass, so run an empty one before
+/// measuring.
+fn text_measurer() -> EguiTextMeasurer {
+    let ctx = Context::default();
+    let _ = ctx.run(RawInput::default(), |_| {});
+    EguiTextMeasurer::new(&ctx)
+}
+
+fn render_settled(
+    tab: &mut Tab,
+    evt_rx: &Receiver<CoreEvent>,
+    size: [usize; 2],
+    deadline: Instant,
+) -> ColorImage {
+    let mut renderer = SoftwareRenderer::new(Context::default());
+    loop {
+        let image = renderer.render(size, Color32::WHITE, |ctx| {
+            tab.ui_content(ctx);
+        });
+        let mut changed = false;
+        while Instant::now() < deadline
+            && let Ok(evt) = evt_rx.recv_timeout(SETTLE)
+        {
+            changed |= evt
+                .request()
+                .is_some_and(|(tab_id, _)| tab_id == tab.tab_id);
+            tab.on_core_event(evt);
+        }
+        let settled = !changed && !tab.layout_dirty();
+        if settled || Instant::now() >= deadline {
+            return image;
+        }
+    }
+}
+
+fn load_error(tab: &Tab, fallback: &str) -> String {
+    tab.status().unwrap_or(fallback).to_string()
+}
@@ -0,0 +1,338 @@
+//! Browsing history owned by the browser shell.
+//!
+//! Every completed document navigation bumps the visit count and timestamp of
+//! its URL. Entries are persisted to a tab-separated file in the user data
+//! directory and rewritten on each change; the oldest entries are dropped once
+//! the store grows past [`MAX_ENTRIES`].
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::user_data::{escape_field, unescape_field, user_data_dir, write_atomically};
+
+const HISTORY_FILE: &str = "history.tsv";
+
+/// Entries kept before the least recently visited ones are forgotten.
+pub const MAX_ENTRIES: usize = 5000;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HistoryEntry {
+    pub url: String,
+    /// Page title, or empty if the page had none.
+    pub title: String,
+    /// Seconds since the Unix epoch of the latest visit.
+    pub last_visit: u64,
+    pub visit_count: u32,
+}
+
+/// What a tab reports about the document it just loaded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HistoryUpdate {
+    /// The document at `url` finished loading.
+    Visit { url: String },
+    /// The document at `url` got (or changed) its title.
+    Title { url: String, title: String },
+}
+
+#[derive(Debug, Default)]
+pub struct HistoryStore {
+    /// Ordered by latest visit, most recent first.
+    entries: Vec<HistoryEntry>,
+    /// Backing file; `None` keeps the store in memory only.
+    path: Option<PathBuf>,
+}
+
+impl HistoryStore {
+    /// Create an empty store that is never persisted.
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Load history from `history.tsv` in the user data directory.
+    ///
+    /// Falls back to an in-memory store if no data directory can be resolved.
+    /// A missing or unreadable file yields an empty store.
+    pub fn load_default() -> Self {
+        match user_data_dir() {
+            Some(dir) => Self::load(dir.join(HISTORY_FILE)),
+            None => Self::in_memory(),
+        }
+    }
+
+    /// Load history from `path`, persisting future changes to the same file.
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let mut entries = fs::read_to_string(&path)
+            .map(|text| parse(&text))
+            .unwrap_or_default();
+        entries.sort_by(|a, b| b.last_visit.cmp(&a.last_visit));
+        Self {
+            entries,
+            path: Some(path),
+        }
+    }
+
+    /// All entries, most recently visited first.
+    pub fn list(&self) -> &[HistoryEntry] {
+        &self.entries
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, url: &str) -> Option<&HistoryEntry> {
+        self.entries.iter().find(|entry| entry.url == url)
+    }
+
+    pub fn apply(&mut self, update: HistoryUpdate) -> io::Result<()> {
+        match update {
+            HistoryUpdate::Visit { url } => self.record_visit(&url, now_secs()),
+            HistoryUpdate::Title { url, title } => self.set_title(&url, &title).map(|_| ()),
+        }
+    }
+
+    /// Count a visit to `url` at `visited_at` (seconds since the Unix epoch).
+    pub fn record_visit(&mut self, url: &str, visited_at: u64) -> io::Result<()> {
+        let mut entry = match self.entries.iter().position(|entry| entry.url == url) {
+            Some(idx) => self.entries.remove(idx),
+            None => HistoryEntry {
+                url: url.to_string(),
+                title: String::new(),
+                last_visit: 0,
+                visit_count: 0,
+            },
+        };
+        entry.last_visit = entry.last_visit.max(visited_at);
+        entry.visit_count = entry.visit_count.saturating_add(1);
+        self.entries.insert(0, entry);
+        self.entries.truncate(MAX_ENTRIES);
+        self.save()
+    }
+
+    /// Update the title of `url`. Returns `false` if it was never visited or
+    /// already had that title.
+    pub fn set_title(&mut self, url: &str, title: &str) -> io::Result<bool> {
+        let Some(entry) = self.entries.iter_mut().find(|entry| entry.url == url) else {
+            return Ok(false);
+        };
+        if entry.title == title {
+            return Ok(false);
+        }
+        entry.title = title.to_string();
+        self.save()?;
+        Ok(true)
+    }
+
+    /// Entries whose URL or title contains `query`, ignoring case, most
+    /// recently visited first. An empty query matches everything.
+    pub fn search(&self, query: &str) -> Vec<&HistoryEntry> {
+        let query = query.trim().to_lowercase();
+        self.entries
+            .iter()
+            .filter(|entry| matches_query(entry, &query))
+            .collect()
+    }
+
+    /// Up to `limit` entries to complete the URL-bar text `typed` with.
+    ///
+    /// URLs that start with the typed text (ignoring the scheme and `www.`)
+    /// rank above ones that only contain it in their URL or title; within
+    /// each group, more visits win and ties go to the most recent visit.
+    pub fn suggestions(&self, typed: &str, limit: usize) -> Vec<&HistoryEntry> {
+        let typed = typed.trim().to_lowercase();
+        if typed.is_empty() {
+            return Vec::new();
+        }
+        let mut ranked: Vec<(bool, &HistoryEntry)> = self
+            .entries
+            .iter()
+            .filter(|entry| matches_query(entry, &typed))
+            .map(|entry| {
+                let url = entry.url.to_lowercase();
+                let prefix = url.starts_with(&typed) || strip_url_prefix(&url).starts_with(&typed);
+                (prefix, entry)
+            })
+            .collect();
+        // Entries are already most recent first and the sort is stable.
+        ranked.sort_by(|(a_prefix, a), (b_prefix, b)| {
+            b_prefix
+                .cmp(a_prefix)
+                .then(b.visit_count.cmp(&a.visit_count))
+        });
+        ranked
+            .into_iter()
+            .take(limit)
+            .map(|(_, entry)| entry)
+            .collect()
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        write_atomically(path, &serialize(&self.entries))
+    }
+}
+
+/// Seconds since the Unix epoch, for visit timestamps.
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+/// `query` must already be trimmed and lowercased.
+fn matches_query(entry: &HistoryEntry, query: &str) -> bool {
+    query.is_empty()
+        || entry.url.to_lowercase().contains(query)
+        || entry.title.to_lowercase().contains(query)
+}
+
+/// `https://www.example.com/` → `example.com/`.
+fn strip_url_prefix(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    rest.strip_prefix("www.").unwrap_or(rest)
+}
+
+// One entry per line: `last_visit \t visit_count \t title \t url`. Tabs,
+// newlines and backslashes inside the text fields are escaped.
+
+fn serialize(entries: &[HistoryEntry]) -> String {
+    let mut out = String::new();
+    for entry in entries {
+        out.push_str(&entry.last_visit.to_string());
+        out.push('\t');
+        out.push_str(&entry.visit_count.to_string());
+        out.push('\t');
+        out.push_str(&escape_field(&entry.title));
+        out.push('\t');
+        out.push_str(&escape_field(&entry.url));
+        out.push('\n');
+    }
+    out
+}
+
+fn parse(text: &str) -> Vec<HistoryEntry> {
+    text.lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let last_visit = fields.next()?.parse().ok()?;
+            let visit_count = fields.next()?.parse().ok()?;
+            let title = unescape_field(fields.next()?);
+            let url = unescape_field(fields.next()?);
+            if url.is_empty() || fields.next().is_some() {
+                return None;
+            }
+            Some(HistoryEntry {
+                url,
+                title,
+                last_visit,
+                visit_count,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn urls(entries: &[&HistoryEntry]) -> Vec<String> {
+        entries.iter().map(|entry| entry.url.clone()).collect()
+    }
+
+    #[test]
+    fn visits_count_up_and_move_the_entry_to_the_front() {
+        let mut store = HistoryStore::in_memory();
+        store.record_visit("https://a.test/", 10).unwrap();
+        store.record_visit("https://b.test/", 20).unwrap();
+        store.record_visit("https://a.test/", 30).unwrap();
+
+        let a = store.get("https://a.test/").unwrap();
+        assert_eq!((a.visit_count, a.last_visit), (2, 30));
+        assert_eq!(store.list()[0].url, "https://a.test/");
+        assert_eq!(store.list().len(), 2);
+    }
+
+    #[test]
+    fn titles_only_attach_to_visited_urls() {
+        let mut store = HistoryStore::in_memory();
+        assert!(!store.set_title("https://a.test/", "A").unwrap());
+        store.record_visit("https://a.test/", 10).unwrap();
+        assert!(store.set_title("https://a.test/", "A").unwrap());
+        assert!(!store.set_title("https://a.test/", "A").unwrap());
+        assert_eq!(store.get("https://a.test/").unwrap().title, "A");
+    }
+
+    #[test]
+    fn search_matches_url_or_title_ignoring_case() {
+        let mut store = HistoryStore::in_memory();
+        store.record_visit("https://rust-lang.org/", 10).unwrap();
+        store.record_visit("https://docs.test/", 20).unwrap();
+        store
+            .set_title("https://docs.test/", "The Rust Book")
+            .unwrap();
+        store.record_visit("https://other.test/", 30).unwrap();
+
+        assert_eq!(
+            urls(&store.search(" RUST ")),
+            ["https://docs.test/", "https://rust-lang.org/"]
+        );
+        assert_eq!(store.search("").len(), 3);
+    }
+
+    #[test]
+    fn suggestions_rank_prefix_matches_then_visit_count_then_recency() {
+        let mut store = HistoryStore::in_memory();
+        store.record_visit("https://blog.test/example", 10).unwrap();
+        store.record_visit("https://blog.test/example", 11).unwrap();
+        store.record_visit("https://www.example.com/", 20).unwrap();
+        store.record_visit("https://example.org/", 30).unwrap();
+        store.record_visit("https://example.org/", 31).unwrap();
+
+        assert_eq!(
+            urls(&store.suggestions("exa", 10)),
+            [
+                "https://example.org/",
+                "https://www.example.com/",
+                "https://blog.test/example",
+            ]
+        );
+        assert_eq!(store.suggestions("exa", 1).len(), 1);
+        assert!(store.suggestions("  ", 10).is_empty());
+    }
+
+    #[test]
+    fn serialization_round_trips_escaped_fields() {
+        let entries = vec![HistoryEntry {
+            url: "https://a.test/?q=1".to_string(),
+            title: "Tabs\tand\nnewlines".to_string(),
+            last_visit: 42,
+            visit_count: 3,
+        }];
+        assert_eq!(parse(&serialize(&entries)), entries);
+        assert!(parse("x\t1\tTitle\thttps://a.test/\n").is_empty());
+    }
+
+    #[test]
+    fn store_persists_to_its_file() {
+        let dir = std::env::temp_dir().join(format!("borrowser-history-{}", std::process::id()));
+        let path = dir.join(HISTORY_FILE);
+        let _ = fs::remove_dir_all(&dir);
+
+        let mut store = HistoryStore::load(&path);
+        assert!(store.is_empty());
+        store.record_visit("https://a.test/", 10).unwrap();
+        store.record_visit("https://b.test/", 20).unwrap();
+        store.set_title("https://a.test/", "A").unwrap();
+
+        let reloaded = HistoryStore::load(&path);
+        assert_eq!(reloaded.list(), store.list());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -1,4 +1,8 @@
+use crate::scroll_animation::ScrollAnimation;
 use gfx::input::{InputValueStore, InteractionState};
+use html::internal::Id;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 /// Document-scoped input state owned by the browser layer.
 ///
@@ -9,6 +13,10 @@ use gfx::input::{InputValueStore, InteractionState};
 pub struct DocumentInputState {
     pub input_values: InputValueStore,
     pub interaction: InteractionState,
+    pub(crate) scroll_animation: ScrollAnimation,
+    /// Files picked for each file input, kept for form submission. The
+    /// input store holds the names shown in the control.
+    chosen_files: HashMap<Id, Vec<PathBuf>>,
 }
 
 impl DocumentInputState {
@@ -19,5 +27,34 @@ impl DocumentInputState {
     pub fn clear_for_navigation(&mut self) {
         self.input_values.clear();
         self.interaction.clear_for_navigation();
+        self.scroll_animation.stop();
+        self.chosen_files.clear();
     }
+
+    pub fn chosen_files(&self, id: Id) -> &[PathBuf] {
+        self.chosen_files.get(&id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Record the files picked for the file input `id` and show them in it.
+    pub fn set_chosen_files(&mut self, id: Id, paths: Vec<PathBuf>) {
+        self.input_values.set(id, chosen_files_label(&paths));
+        self.chosen_files.insert(id, paths);
+    }
+}
+
+/// Text a file input shows for its chosen files: the file name, or a count
+/// once there are several.
+fn chosen_files_label(paths: &[PathBuf]) -> String {
+    match paths {
+        [] => String::new(),
+        [path] => file_name(path),
+        paths => format!("{} files", paths.len()),
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy()
+        .into_owned()
 }
@@ -1,12 +1,21 @@
+pub mod bookmarks;
 mod document_style;
-pub mod dom_store;
 pub mod form_controls;
+pub mod headless;
+pub mod history;
 pub mod input_state;
+pub mod network_log;
+mod network_panel;
 pub mod page;
+pub mod preferences;
+pub mod print;
 pub mod rendering;
 pub mod resources;
+mod scroll_animation;
+pub mod session;
 pub mod shell;
 pub mod tab;
+pub mod user_data;
 pub mod view;
 
 pub use shell::ShellApp;
@@ -0,0 +1,353 @@
+//! Per-navigation record of a tab's network requests, shown in the network
+//! panel.
+//!
+//! A request is logged when the tab sends its `FetchStream` command and is
+//! updated from the `Network*` events for the current navigation. Events are
+//! matched by kind and requested URL, which the net layer echoes back in
+//! chunk and error events.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use bus::CoreEvent;
+use core_types::ResourceKind;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NetworkRequestState {
+    /// Sent, no response yet.
+    Pending,
+    /// Response headers arrived; the body is streaming.
+    Receiving,
+    Done,
+    Failed(String),
+}
+
+#[derive(Clone, Debug)]
+pub struct NetworkRequest {
+    pub url: String,
+    pub kind: ResourceKind,
+    /// URL after redirects, updated with every hop.
+    pub final_url: Option<String>,
+    pub status_code: Option<u16>,
+    pub content_type: Option<String>,
+    pub bytes_received: usize,
+    pub state: NetworkRequestState,
+    started_at: Instant,
+    response_at: Option<Instant>,
+    finished_at: Option<Instant>,
+}
+
+impl NetworkRequest {
+    fn new(url: String, kind: ResourceKind, started_at: Instant) -> Self {
+        Self {
+            url,
+            kind,
+            final_url: None,
+            status_code: None,
+            content_type: None,
+            bytes_received: 0,
+            state: NetworkRequestState::Pending,
+            started_at,
+            response_at: None,
+            finished_at: None,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self.state,
+            NetworkRequestState::Done | NetworkRequestState::Failed(_)
+        )
+    }
+
+    /// Time from sending the request until the response started.
+    pub fn time_to_response(&self) -> Option<Duration> {
+        Some(self.response_at?.duration_since(self.started_at))
+    }
+
+    /// Total time from sending the request until it finished or failed.
+    pub fn duration(&self) -> Option<Duration> {
+        Some(self.finished_at?.duration_since(self.started_at))
+    }
+}
+
+/// How often requests to one origin found a keep-alive connection in the
+/// net layer's pool.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ConnectionReuse {
+    pub reused: usize,
+    pub opened: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct NetworkLog {
+    requests: Vec<NetworkRequest>,
+    connections: BTreeMap<String, ConnectionReuse>,
+}
+
+impl NetworkLog {
+    /// Requests in the order they were sent.
+    pub fn requests(&self) -> &[NetworkRequest] {
+        &self.requests
+    }
+
+    /// Connection reuse per origin, in origin order.
+    pub fn connections(&self) -> &BTreeMap<String, ConnectionReuse> {
+        &self.connections
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.requests.clear();
+        self.connections.clear();
+    }
+
+    pub(crate) fn record_request(&mut self, url: String, kind: ResourceKind) {
+        self.requests
+            .push(NetworkRequest::new(url, kind, Instant::now()));
+    }
+
+    /// Apply a network event. Callers filter out events of other tabs and
+    /// stale navigations; non-network events are ignored.
+    pub(crate) fn record_event(&mut self, event: &CoreEvent) {
+        self.record_event_at(event, Instant::now());
+    }
+
+    fn record_event_at(&mut self, event: &CoreEvent, now: Instant) {
+        match event {
+            CoreEvent::NetworkRedirected { kind, from, to, .. } => {
+                if let Some(request) = self.in_flight_mut(*kind, from) {
+                    request.final_url = Some(to.clone());
+                }
+            }
+            CoreEvent::NetworkConnection { origin, reused, .. } => {
+                let connections = self.connections.entry(origin.clone()).or_default();
+                if *reused {
+                    connections.reused += 1;
+                } else {
+                    connections.opened += 1;
+                }
+            }
+            CoreEvent::NetworkStart { kind, response, .. } => {
+                if let Some(request) = self.in_flight_mut(*kind, &response.requested_url) {
+                    request.final_url = Some(response.final_url.clone());
+                    request.status_code = response.status_code;
+                    request.content_type = response.content_type.clone();
+                    request.state = NetworkRequestState::Receiving;
+                    request.response_at = Some(now);
+                }
+            }
+            CoreEvent::NetworkChunk {
+                kind, url, bytes, ..
+            } => {
+                if let Some(request) = self.in_flight_mut(*kind, url) {
+                    request.bytes_received = request.bytes_received.saturating_add(bytes.len());
+                }
+            }
+            CoreEvent::NetworkDone {
+                kind,
+                response,
+                bytes_received,
+                ..
+            } => {
+                if let Some(request) = self.in_flight_mut(*kind, &response.requested_url) {
+                    request.final_url = Some(response.final_url.clone());
+                    request.status_code = response.status_code;
+                    request.bytes_received = *bytes_received;
+                    request.state = NetworkRequestState::Done;
+                    request.response_at.get_or_insert(now);
+                    request.finished_at = Some(now);
+                }
+            }
+            CoreEvent::NetworkError {
+                kind,
+                url,
+                error,
+                message,
+                ..
+            } => {
+                if let Some(request) = self.in_flight_mut(*kind, url) {
+                    request.status_code = error.status_code().or(request.status_code);
+                    request.state = NetworkRequestState::Failed(message.clone());
+                    request.finished_at = Some(now);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Latest unfinished request for `url`, requested or redirected to; the
+    /// same URL may be requested again once an earlier request finished.
+    fn in_flight_mut(&mut self, kind: ResourceKind, url: &str) -> Option<&mut NetworkRequest> {
+        self.requests.iter_mut().rev().find(|request| {
+            request.kind == kind
+                && (request.url == url || request.final_url.as_deref() == Some(url))
+                && !request.is_finished()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core_types::NetworkResponseInfo;
+
+    fn response(url: &str, final_url: &str) -> NetworkResponseInfo {
+        NetworkResponseInfo {
+            requested_url: url.to_string(),
+            final_url: final_url.to_string(),
+            status_code: Some(200),
+            content_type: Some("image/png".to_string()),
+            content_length: None,
+            headers: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn events_update_the_matching_request_with_status_size_and_timing() {
+        let url = "https://a.test/cat.png";
+        let mut log = NetworkLog::default();
+        log.record_request("https://a.test/".to_string(), ResourceKind::Html);
+        log.record_request(url.to_string(), ResourceKind::Image);
+        let started = log.requests[1].started_at;
+
+        log.record_event_at(
+            &CoreEvent::NetworkStart {
+                tab_id: 1,
+                request_id: 1,
+                stylesheet_slot_id: None,
+                kind: ResourceKind::Image,
+                response: response(url, "https://cdn.a.test/cat.png"),
+            },
+            started + Duration::from_millis(20),
+        );
+        log.record_event_at(
+            &CoreEvent::NetworkChunk {
+                tab_id: 1,
+                request_id: 1,
+                stylesheet_slot_id: None,
+                kind: ResourceKind::Image,
+                url: url.to_string(),
+                bytes: vec![0; 10],
+            },
+            started + Duration::from_millis(30),
+        );
+        assert_eq!(log.requests[1].state, NetworkRequestState::Receiving);
+        assert_eq!(log.requests[1].bytes_received, 10);
+
+        log.record_event_at(
+            &CoreEvent::NetworkDone {
+                tab_id: 1,
+                request_id: 1,
+                stylesheet_slot_id: None,
+                kind: ResourceKind::Image,
+                response: response(url, "https://cdn.a.test/cat.png"),
+                bytes_received: 10,
+            },
+            started + Duration::from_millis(50),
+        );
+
+        let image = &log.requests()[1];
+        assert_eq!(image.state, NetworkRequestState::Done);
+        assert_eq!(image.status_code, Some(200));
+        assert_eq!(
+            image.final_url.as_deref(),
+            Some("https://cdn.a.test/cat.png")
+        );
+        assert_eq!(image.time_to_response(), Some(Duration::from_millis(20)));
+        assert_eq!(image.duration(), Some(Duration::from_millis(50)));
+        assert_eq!(log.requests()[0].state, NetworkRequestState::Pending);
+    }
+
+    #[test]
+    fn errors_finish_only_the_latest_in_flight_request() {
+        let url = "https://a.test/site.css";
+        let mut log = NetworkLog::default();
+        log.record_request(url.to_string(), ResourceKind::Css);
+        log.record_request(url.to_string(), ResourceKind::Css);
+
+        log.record_event(&CoreEvent::NetworkError {
+            tab_id: 1,
+            request_id: 1,
+            stylesheet_slot_id: None,
+            kind: ResourceKind::Css,
+            url: url.to_string(),
+            error: core_types::NetError::HttpStatus(404),
+            message: "not found".to_string(),
+        });
+
+        assert_eq!(log.requests()[0].state, NetworkRequestState::Pending);
+        assert_eq!(
+            log.requests()[1].state,
+            NetworkRequestState::Failed("not found".to_string())
+        );
+        assert_eq!(log.requests()[1].status_code, Some(404));
+    }
+
+    #[test]
+    fn redirects_follow_the_request_through_every_hop() {
+        let mut log = NetworkLog::default();
+        log.record_request("http://a.test/".to_string(), ResourceKind::Html);
+
+        for (from, to) in [
+            ("http://a.test/", "https://a.test/"),
+            ("https://a.test/", "https://www.a.test/"),
+        ] {
+            log.record_event(&CoreEvent::NetworkRedirected {
+                tab_id: 1,
+                request_id: 1,
+                stylesheet_slot_id: None,
+                kind: ResourceKind::Html,
+                from: from.to_string(),
+                to: to.to_string(),
+            });
+        }
+
+        let document = &log.requests()[0];
+        assert_eq!(document.url, "http://a.test/");
+        assert_eq!(document.final_url.as_deref(), Some("https://www.a.test/"));
+        assert_eq!(document.state, NetworkRequestState::Pending);
+    }
+
+    #[test]
+    fn connection_reuse_is_counted_per_origin() {
+        let mut log = NetworkLog::default();
+        for (origin, reused) in [
+            ("https://a.test", false),
+            ("https://a.test", true),
+            ("https://cdn.test", false),
+            ("https://a.test", true),
+        ] {
+            log.record_event(&CoreEvent::NetworkConnection {
+                tab_id: 1,
+                request_id: 1,
+                stylesheet_slot_id: None,
+                kind: ResourceKind::Image,
+                origin: origin.to_string(),
+                reused,
+            });
+        }
+
+        assert_eq!(
+            log.connections().iter().collect::<Vec<_>>(),
+            [
+                (
+                    &"https://a.test".to_string(),
+                    &ConnectionReuse {
+                        reused: 2,
+                        opened: 1
+                    }
+                ),
+                (
+                    &"https://cdn.test".to_string(),
+                    &ConnectionReuse {
+                        reused: 0,
+                        opened: 1
+                    }
+                ),
+            ]
+        );
+
+        log.clear();
+        assert!(log.connections().is_empty());
+    }
+}
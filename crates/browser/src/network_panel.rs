@@ -0,0 +1,178 @@
+//! DevTools-style network panel: every request of the active tab's current
+//! navigation, with details for the selected one.
+
+use std::time::Duration;
+
+use egui::{Color32, Context, Grid, RichText, ScrollArea, SidePanel, TopBottomPanel, Ui};
+
+use crate::network_log::{NetworkLog, NetworkRequest, NetworkRequestState};
+use crate::tab::elide_end;
+
+const URL_COLUMN_CHARS: usize = 72;
+
+#[derive(Debug, Default)]
+pub(crate) struct NetworkPanel {
+    selected: Option<usize>,
+}
+
+impl NetworkPanel {
+    /// Show the panel at the bottom of the window. Returns `false` once the
+    /// user closed it.
+    pub(crate) fn show(&mut self, ctx: &Context, log: &NetworkLog) -> bool {
+        let mut open = true;
+        TopBottomPanel::bottom("network_panel")
+            .resizable(true)
+            .default_height(220.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.strong("Network");
+                    ui.label(summary(log));
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.small_button("✕").on_hover_text("Close (F12)").clicked() {
+                            open = false;
+                        }
+                    });
+                });
+                ui.separator();
+
+                let selected = self.selected.and_then(|index| log.requests().get(index));
+                if let Some(request) = selected {
+                    SidePanel::right("network_request_detail")
+                        .resizable(true)
+                        .default_width(320.0)
+                        .show_inside(ui, |ui| request_details(ui, request));
+                }
+                ScrollArea::both()
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| self.request_table(ui, log));
+            });
+        open
+    }
+
+    fn request_table(&mut self, ui: &mut Ui, log: &NetworkLog) {
+        Grid::new("network_requests")
+            .striped(true)
+            .num_columns(5)
+            .show(ui, |ui| {
+                for heading in ["Status", "Kind", "Size", "Time", "URL"] {
+                    ui.strong(heading);
+                }
+                ui.end_row();
+
+                for (index, request) in log.requests().iter().enumerate() {
+                    ui.label(status_text(request));
+                    ui.label(request.kind.as_str());
+                    ui.label(format_size(request.bytes_received));
+                    ui.label(request.duration().map(format_duration).unwrap_or_default());
+                    let row = ui
+                        .selectable_label(
+                            self.selected == Some(index),
+                            elide_end(&request.url, URL_COLUMN_CHARS),
+                        )
+                        .on_hover_text(&request.url);
+                    if row.clicked() {
+                        self.selected = (self.selected != Some(index)).then_some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+}
+
+fn summary(log: &NetworkLog) -> String {
+    let requests = log.requests();
+    let bytes: usize = requests.iter().map(|request| request.bytes_received).sum();
+    let failed = requests
+        .iter()
+        .filter(|request| matches!(request.state, NetworkRequestState::Failed(_)))
+        .count();
+    let mut summary = format!("{} requests • {}", requests.len(), format_size(bytes));
+    if failed > 0 {
+        summary.push_str(&format!(" • {failed} failed"));
+    }
+    let (reused, opened) = log
+        .connections()
+        .values()
+        .fold((0, 0), |(reused, opened), origin| {
+            (reused + origin.reused, opened + origin.opened)
+        });
+    if reused + opened > 0 {
+        summary.push_str(&format!(
+            " • {reused}/{} connections reused",
+            reused + opened
+        ));
+    }
+    summary
+}
+
+fn request_details(ui: &mut Ui, request: &NetworkRequest) {
+    ScrollArea::vertical().show(ui, |ui| {
+        Grid::new("network_request_fields")
+            .num_columns(2)
+            .show(ui, |ui| {
+                let mut field = |name: &str, value: String| {
+                    ui.label(RichText::new(name).weak());
+                    ui.add(egui::Label::new(value).wrap());
+                    ui.end_row();
+                };
+                field("URL", request.url.clone());
+                if let Some(final_url) = request.final_url.as_ref().filter(|u| **u != request.url) {
+                    field("Redirected to", final_url.clone());
+                }
+                field(
+                    "Kind",
+                    format!("{} ({})", request.kind.as_str(), request.kind.role_str()),
+                );
+                field("Status", status_text(request).text().to_string());
+                if let Some(content_type) = &request.content_type {
+                    field("Content type", content_type.clone());
+                }
+                field("Size", format_size(request.bytes_received));
+                if let Some(waiting) = request.time_to_response() {
+                    field("Waiting", format_duration(waiting));
+                }
+                if let Some(total) = request.duration() {
+                    field("Total", format_duration(total));
+                }
+                if let NetworkRequestState::Failed(error) = &request.state {
+                    field("Error", error.clone());
+                }
+            });
+    });
+}
+
+fn status_text(request: &NetworkRequest) -> RichText {
+    let code = request.status_code.map(|code| code.to_string());
+    match &request.state {
+        NetworkRequestState::Pending => RichText::new("pending").weak(),
+        NetworkRequestState::Receiving => RichText::new(code.unwrap_or_default()).weak(),
+        NetworkRequestState::Done => {
+            let text = RichText::new(code.unwrap_or_else(|| "ok".to_string()));
+            if request.status_code.is_some_and(|code| code >= 400) {
+                text.color(Color32::LIGHT_RED)
+            } else {
+                text
+            }
+        }
+        NetworkRequestState::Failed(_) => {
+            RichText::new(code.unwrap_or_else(|| "failed".to_string())).color(Color32::LIGHT_RED)
+        }
+    }
+}
+
+pub(crate) fn format_size(bytes: usize) -> String {
+    match bytes {
+        0..1024 => format!("{bytes} B"),
+        1024..1_048_576 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+    }
+}
+
+pub(crate) fn format_duration(duration: Duration) -> String {
+    let millis = duration.as_millis();
+    if millis < 1000 {
+        format!("{millis} ms")
+    } else {
+        format!("{:.2} s", duration.as_secs_f64())
+    }
+}
@@ -39,7 +39,6 @@ impl PageState {
         self.rendering.style_dirty()
     }
 
-    #[cfg(test)]
     pub(crate) fn layout_dirty(&self) -> bool {
         self.rendering.layout_dirty()
     }
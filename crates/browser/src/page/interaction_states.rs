@@ -0,0 +1,154 @@
+//! Hover, focus and press state of the document's elements, for `:hover`,
+//! `:focus` and `:active`.
+//!
+//! The page keeps the nodes the input layer reports as hovered, focused and
+//! pressed, and works out the elements that match from the current DOM
+//! whenever styles are recomputed. `:hover` and `:active` also match every
+//! ancestor of their target, so a change restyles only the elements that
+//! enter or leave one of those chains.
+
+use css::SelectorElementStates;
+use gfx::input::InteractionState;
+use html::{Node, internal::Id};
+
+use crate::rendering::{
+    RenderInvalidationEntryPoint, RenderInvalidationRequest, render_invalidation_request,
+};
+
+use super::PageState;
+use super::restyle::StyleInvalidationScope;
+
+/// Nodes the input layer reports as under the pointer, focused and pressed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct InteractionTargets {
+    pub(crate) hover: Option<Id>,
+    pub(crate) focus: Option<Id>,
+    pub(crate) active: Option<Id>,
+}
+
+impl InteractionTargets {
+    pub(crate) fn from_interaction(interaction: &InteractionState) -> Self {
+        Self {
+            hover: interaction.hover,
+            focus: interaction.focused_node_id,
+            active: interaction.active.map(|active| active.id),
+        }
+    }
+}
+
+/// Elements matching each interaction pseudo-class.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct MatchingElements {
+    hovered: Vec<Id>,
+    focused: Option<Id>,
+    active: Vec<Id>,
+}
+
+impl MatchingElements {
+    fn resolve(dom: &Node, targets: InteractionTargets) -> Self {
+        Self {
+            hovered: targets
+                .hover
+                .map_or_else(Vec::new, |target| element_chain(dom, target)),
+            focused: targets
+                .focus
+                .filter(|&target| element_chain(dom, target).first() == Some(&target)),
+            active: targets
+                .active
+                .map_or_else(Vec::new, |target| element_chain(dom, target)),
+        }
+    }
+
+    /// Elements whose state differs between `self` and `other`.
+    fn changed_from(&self, other: &Self) -> Vec<Id> {
+        let mut changed = Vec::new();
+        for (now, before) in [
+            (&self.hovered, &other.hovered),
+            (&self.active, &other.active),
+        ] {
+            changed.extend(now.iter().filter(|id| !before.contains(id)));
+            changed.extend(before.iter().filter(|id| !now.contains(id)));
+        }
+        if self.focused != other.focused {
+            changed.extend(self.focused.into_iter().chain(other.focused));
+        }
+        changed.sort_unstable_by_key(|id| id.0);
+        changed.dedup();
+        changed
+    }
+}
+
+impl PageState {
+    /// Take the hovered, focused and pressed nodes from the input layer.
+    /// Returns `None` unless an element starts or stops matching `:hover`,
+    /// `:focus` or `:active`.
+    pub(crate) fn set_interaction_targets(
+        &mut self,
+        targets: InteractionTargets,
+    ) -> Option<RenderInvalidationRequest> {
+        if targets == self.interaction_targets {
+            return None;
+        }
+        let previous = std::mem::replace(&mut self.interaction_targets, targets);
+        let dom = self.dom.as_deref()?;
+        let node_ids = MatchingElements::resolve(dom, targets)
+            .changed_from(&MatchingElements::resolve(dom, previous));
+        if node_ids.is_empty() {
+            return None;
+        }
+
+        // Selector matching sees an element changing state the same way it
+        // sees an attribute change on that element.
+        self.rendering
+            .mark_style_inputs_changed(StyleInvalidationScope::AttributeSuffix { node_ids });
+        let entry_point = RenderInvalidationEntryPoint::DomAttributesChanged;
+        self.rendering.mark_dirty_for_entry_point(entry_point);
+        Some(render_invalidation_request(entry_point))
+    }
+
+    /// Link and interaction states of the document's elements, for selector
+    /// matching.
+    pub(super) fn element_states(&self) -> SelectorElementStates {
+        let mut states = self.link_element_states();
+        if let Some(dom) = self.dom.as_deref()
+            && self.interaction_targets != InteractionTargets::default()
+        {
+            let matching = MatchingElements::resolve(dom, self.interaction_targets);
+            for id in matching.hovered {
+                states.insert_hovered(id);
+            }
+            for id in matching.active {
+                states.insert_active(id);
+            }
+            states.set_focused(matching.focused);
+        }
+        states
+    }
+}
+
+/// `target` if it is an element, followed by its ancestor elements, nearest
+/// first. Empty when `target` is not in the tree.
+fn element_chain(root: &Node, target: Id) -> Vec<Id> {
+    let mut path: Vec<(&Node, usize)> = vec![(root, 0)];
+    while let Some((node, next_child)) = path.last_mut() {
+        if node.id() == target {
+            return path
+                .iter()
+                .rev()
+                .filter(|(node, _)| matches!(node, Node::Element { .. }))
+                .map(|(node, _)| node.id())
+                .collect();
+        }
+        let children = node.children().unwrap_or_default();
+        match children.get(*next_child) {
+            Some(child) => {
+                *next_child += 1;
+                path.push((child, 0));
+            }
+            None => {
+                path.pop();
+            }
+        }
+    }
+    Vec::new()
+}
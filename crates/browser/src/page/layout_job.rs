@@ -0,0 +1,222 @@
+//! Layout of large documents off the UI thread.
+//!
+//! Once a large document has been drawn, relayouts go to the layout runtime
+//! instead of running inside the frame. Until the runtime answers, frames
+//! keep drawing the retained layout, so the UI stays responsive while a big
+//! page streams in or restyles. One job runs per page at a time; a result
+//! laid out for inputs that changed meanwhile still stands in, and the next
+//! frame hands off the current inputs.
+
+use std::collections::HashMap;
+
+use bus::{LayoutJob, LayoutStylesheet};
+use css::{MediaEnvironment, SelectorElementStates};
+use html::{Node, internal::Id};
+use layout::{RetainedLayoutArtifact, RetainedLayoutKey, TextMeasurer};
+
+use crate::rendering::RenderEpoch;
+use crate::resources::ResourceManager;
+
+use super::PageState;
+
+/// Documents with at least this many nodes lay out in the layout runtime
+/// once they have been drawn; smaller ones lay out within the frame.
+const OFFTHREAD_LAYOUT_MIN_NODES: usize = 2_000;
+
+/// What a frame offers for laying the document out off the UI thread.
+pub(crate) struct LayoutOffload<'a> {
+    /// Width the page was last laid out at.
+    pub(crate) viewport_width: f32,
+    pub(crate) resources: &'a ResourceManager,
+    pub(crate) resized_controls: &'a HashMap<Id, (f32, f32)>,
+}
+
+/// A layout job the page handed off, still missing the DOM snapshot and the
+/// text measurer the tab supplies.
+pub(crate) struct LayoutJobRequest {
+    stylesheets: Vec<LayoutStylesheet>,
+    element_states: SelectorElementStates,
+    viewport_width: f32,
+    media: MediaEnvironment,
+    zoom: f32,
+    key: RetainedLayoutKey,
+    image_sources: HashMap<String, String>,
+    image_sizes: HashMap<String, (u32, u32)>,
+    resized_controls: HashMap<Id, (f32, f32)>,
+}
+
+impl LayoutJobRequest {
+    pub(crate) fn key(&self) -> RetainedLayoutKey {
+        self.key
+    }
+
+    pub(crate) fn into_job(
+        self,
+        dom: Box<Node>,
+        measurer: Box<dyn TextMeasurer + Send>,
+    ) -> LayoutJob {
+        LayoutJob {
+            dom,
+            stylesheets: self.stylesheets,
+            element_states: self.element_states,
+            viewport_width: self.viewport_width,
+            media: self.media,
+            zoom: self.zoom,
+            key: self.key,
+            image_sources: self.image_sources,
+            image_sizes: self.image_sizes,
+            resized_controls: self.resized_controls,
+            measurer,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(super) struct LayoutJobState {
+    key: RetainedLayoutKey,
+    /// Render epoch when the job was handed off; a later one means the
+    /// inputs were invalidated again.
+    epoch: RenderEpoch,
+    /// The runtime could not lay out this key; the frame lays it out.
+    failed: bool,
+}
+
+impl PageState {
+    /// Hand the relayout this frame needs to the layout runtime, if the
+    /// document is large and already has a layout to draw meanwhile.
+    pub(super) fn request_layout_job(
+        &mut self,
+        offload: &LayoutOffload<'_>,
+    ) -> Option<LayoutJobRequest> {
+        let rendering = &self.rendering;
+        let key = rendering
+            .retained_layout_key_seed()
+            .for_viewport_width(offload.viewport_width);
+        if let Some(job) = rendering.layout_job
+            && (!job.failed || job.key == key)
+        {
+            return None;
+        }
+        // Patched documents get a new identity domain, but the retained
+        // layout is anchored on node ids and still draws until the job lands.
+        let retained = rendering.layout_cache.as_ref()?.key();
+        if retained == key && !rendering.layout_dirty() {
+            return None;
+        }
+
+        let dom = self.dom.as_deref()?;
+        let mut snapshot = DocumentSnapshot::default();
+        snapshot.visit(dom, self.base_url.as_deref(), offload.resources);
+        if snapshot.nodes < OFFTHREAD_LAYOUT_MIN_NODES {
+            return None;
+        }
+
+        self.rendering.layout_job = Some(LayoutJobState {
+            key,
+            epoch: self.rendering.render_epoch,
+            failed: false,
+        });
+        Some(self.layout_job_request(key, snapshot, offload))
+    }
+
+    /// The layout job for the current document as `offload` sees it, however
+    /// large the document is, for laying it out outside the frame loop.
+    pub(crate) fn snapshot_layout_job(
+        &self,
+        offload: &LayoutOffload<'_>,
+    ) -> Option<LayoutJobRequest> {
+        let dom = self.dom.as_deref()?;
+        let mut snapshot = DocumentSnapshot::default();
+        snapshot.visit(dom, self.base_url.as_deref(), offload.resources);
+        let key = self
+            .rendering
+            .retained_layout_key_seed()
+            .for_viewport_width(offload.viewport_width);
+        Some(self.layout_job_request(key, snapshot, offload))
+    }
+
+    fn layout_job_request(
+        &self,
+        key: RetainedLayoutKey,
+        snapshot: DocumentSnapshot,
+        offload: &LayoutOffload<'_>,
+    ) -> LayoutJobRequest {
+        LayoutJobRequest {
+            stylesheets: self.rendering.document_styles.layout_stylesheets(),
+            element_states: self.element_states(),
+            viewport_width: offload.viewport_width,
+            media: self.rendering.document_styles.media_environment(),
+            zoom: self.zoom,
+            key,
+            image_sources: snapshot.image_sources,
+            image_sizes: snapshot.image_sizes,
+            resized_controls: offload.resized_controls.clone(),
+        }
+    }
+
+    /// A layout job is out, so frames draw the retained layout instead of
+    /// laying out.
+    pub(crate) fn layout_deferred(&self) -> bool {
+        self.rendering.layout_job.is_some_and(|job| !job.failed)
+    }
+
+    /// Take the layout runtime's answer for `key`. Returns whether the page
+    /// needs a frame to show it.
+    pub(crate) fn finish_layout_job(
+        &mut self,
+        key: RetainedLayoutKey,
+        layout: Result<Box<RetainedLayoutArtifact>, String>,
+    ) -> bool {
+        let Some(job) = self.rendering.layout_job else {
+            return false;
+        };
+        if job.key != key || job.failed {
+            return false;
+        }
+        let artifact = match layout {
+            Ok(artifact) => artifact,
+            Err(error) => {
+                eprintln!("layout runtime failed: {error}");
+                self.rendering.layout_job = Some(LayoutJobState {
+                    failed: true,
+                    ..job
+                });
+                return true;
+            }
+        };
+        self.rendering.layout_job = None;
+
+        let fresh = key.seed() == self.rendering.retained_layout_key_seed()
+            && job.epoch == self.rendering.render_epoch;
+        self.rendering.record_offthread_layout(*artifact, fresh);
+        true
+    }
+}
+
+#[derive(Default)]
+struct DocumentSnapshot {
+    nodes: usize,
+    image_sources: HashMap<String, String>,
+    image_sizes: HashMap<String, (u32, u32)>,
+}
+
+impl DocumentSnapshot {
+    fn visit(&mut self, node: &Node, base_url: Option<&str>, resources: &ResourceManager) {
+        self.nodes += 1;
+        if let Node::Element { element } = node
+            && element.namespace() == html::ElementNamespace::Html
+            && element.name().eq_ignore_ascii_case("img")
+            && let Some(source) = node.attr("src")
+            && !self.image_sources.contains_key(source)
+            && let Some(url) = weburl::resolve_str(base_url, source)
+        {
+            if let Some(size) = resources.image_intrinsic_size_px(&url) {
+                self.image_sizes.insert(url.clone(), size);
+            }
+            self.image_sources.insert(source.to_string(), url);
+        }
+        for child in node.children().unwrap_or_default() {
+            self.visit(child, base_url, resources);
+        }
+    }
+}
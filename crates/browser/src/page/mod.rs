@@ -1,10 +1,15 @@
 mod debug;
+mod interaction_states;
+mod layout_job;
 mod restyle;
 mod retained_render_state;
 mod style_cache;
 mod style_phase;
 mod stylesheets;
+mod visited_links;
 
+pub(crate) use interaction_states::InteractionTargets;
+pub(crate) use layout_job::{LayoutJobRequest, LayoutOffload};
 pub(crate) use restyle::{RestyleHint, RestyleTrigger};
 #[cfg(test)]
 pub(crate) use style_cache::{PageStyleGenerations, StyleRecalcKind};
@@ -13,8 +18,9 @@ pub(crate) use stylesheets::PageStylesheetReconcile;
 
 use crate::form_controls::{FormControlIndex, seed_input_state_from_dom};
 use crate::rendering::{
-    PendingRenderWork, RenderInvalidationRequest, RenderWorkPlan, RenderWorkPlanInput,
-    RetainedPaintArtifactKeySeed, RetainedPaintFrameResult, render_invalidation_request,
+    PendingRenderWork, RenderInvalidationEntryPoint, RenderInvalidationRequest, RenderWorkPlan,
+    RenderWorkPlanInput, RetainedPaintArtifactKeySeed, RetainedPaintFrameResult,
+    render_invalidation_request,
 };
 use gfx::input::InputValueStore;
 use gfx::paint::PaintArtifact;
@@ -27,8 +33,13 @@ use layout::{RetainedLayoutArtifact, RetainedLayoutFrameResult, RetainedLayoutKe
 
 use restyle::StyleInvalidationScope;
 use retained_render_state::RetainedRenderState;
+use std::collections::HashSet;
 
 pub struct PageState {
+    /// URL of the current document, after redirects.
+    pub document_url: Option<String>,
+    /// URL that relative references in the document resolve against: the
+    /// document URL, or the first `<base href>` once it is in the DOM.
     pub base_url: Option<String>,
     pub dom: Option<Box<Node>>,
     pub head: HeadMetadata,
@@ -36,28 +47,48 @@ pub struct PageState {
     pub visible_text_cache: String,
     pub form_controls: FormControlIndex,
 
+    /// Page zoom factor applied to computed lengths before layout. Survives
+    /// navigation, like the zoom level of a tab in other browsers.
+    zoom: f32,
+    /// Size of the page viewport in egui points (CSS px at zoom 1) as of the
+    /// last frame, for `@media` width and height features. Survives
+    /// navigation.
+    viewport_size: (f32, f32),
+    /// Normalized URLs of visited documents, for `:visited`. Survives
+    /// navigation.
+    visited_urls: HashSet<String>,
+    /// Nodes under the pointer, focused and pressed, for `:hover`, `:focus`
+    /// and `:active`.
+    interaction_targets: InteractionTargets,
     rendering: RetainedRenderState,
 }
 
 impl PageState {
     pub fn new() -> Self {
         Self {
+            document_url: None,
             base_url: None,
             dom: None,
             head: HeadMetadata::default(),
             visible_text_cache: String::new(),
             form_controls: FormControlIndex::default(),
+            zoom: 1.0,
+            viewport_size: (0.0, 0.0),
+            visited_urls: HashSet::new(),
+            interaction_targets: InteractionTargets::default(),
             rendering: RetainedRenderState::new(),
         }
     }
 
     // Clear all state for new navigation
     pub fn start_nav(&mut self, final_url: &str) {
+        self.document_url = Some(final_url.to_string());
         self.base_url = Some(final_url.to_string());
         self.dom = None;
         self.head = HeadMetadata::default();
         self.visible_text_cache.clear();
         self.form_controls = FormControlIndex::default();
+        self.interaction_targets = InteractionTargets::default();
         self.rendering.reset_for_navigation();
     }
 
@@ -67,6 +98,32 @@ impl PageState {
         } else {
             self.head = HeadMetadata::default();
         }
+        self.base_url =
+            self.document_url
+                .as_deref()
+                .map(|document_url| match weburl::parse(document_url) {
+                    Some(url) => {
+                        weburl::document_base_url(&url, self.head.base_href.as_deref()).into()
+                    }
+                    None => document_url.to_string(),
+                });
+    }
+
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    /// Set the page zoom factor. Returns `None` if the factor is unchanged.
+    pub(crate) fn set_zoom(&mut self, zoom: f32) -> Option<RenderInvalidationRequest> {
+        if zoom == self.zoom {
+            return None;
+        }
+        self.zoom = zoom;
+        self.rendering.mark_zoom_changed();
+        let media = self.rendering.document_styles.media_environment();
+        Some(self.apply_media_environment(media).unwrap_or_else(|| {
+            render_invalidation_request(RenderInvalidationEntryPoint::ViewportChanged)
+        }))
     }
 
     pub(crate) fn replace_dom(
@@ -15,8 +15,12 @@ impl RestyleTrigger {
         for patch in patches {
             let candidate = match patch {
                 DomPatch::Clear | DomPatch::CreateDocument { .. } => Self::DocumentReplaced,
-                DomPatch::SetAttributes { .. } => Self::AttributesChanged,
+                DomPatch::SetAttributes { .. }
+                | DomPatch::SetAttribute { .. }
+                | DomPatch::RemoveAttribute { .. } => Self::AttributesChanged,
                 DomPatch::SetText { .. } | DomPatch::AppendText { .. } => Self::TextMutated,
+                // Source positions do not feed styling.
+                DomPatch::SetSourceSpan { .. } => continue,
                 DomPatch::CreateElement { .. }
                 | DomPatch::CreateText { .. }
                 | DomPatch::CreateComment { .. }
@@ -57,6 +61,7 @@ pub(crate) struct RestyleHint {
 }
 
 impl RestyleHint {
+    #[cfg(test)]
     pub(crate) fn document_replaced() -> Self {
         Self {
             trigger: RestyleTrigger::DocumentReplaced,
@@ -115,7 +120,9 @@ pub(super) enum StyleInvalidationScope {
     /// This proof assumes the supported selector model has no selector that lets
     /// later or descendant elements affect an earlier ancestor or sibling, such
     /// as `:has()`. Adding that kind of selector must either widen this scope to
-    /// `Full` or add selector-aware invalidation dependencies.
+    /// `Full` or add selector-aware invalidation dependencies. Structural
+    /// pseudo-classes such as `:last-child` do look at later siblings, but only
+    /// at their positions, which attribute and state changes leave alone.
     AttributeSuffix { node_ids: Vec<Id> },
 }
 
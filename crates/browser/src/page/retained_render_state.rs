@@ -19,6 +19,7 @@ use layout::{
     RetainedLayoutFrameResult, RetainedLayoutKeySeed,
 };
 
+use super::layout_job::LayoutJobState;
 use super::restyle::{RestyleTrigger, StyleInvalidationScope};
 use super::style_cache::{PageStyleCache, PageStyleGenerations, StyleRecalcKind};
 
@@ -43,6 +44,8 @@ pub(super) struct RetainedRenderState {
     pub(super) layout_cache: Option<RetainedLayoutArtifact>,
     pub(super) layout_artifact_stats: RetainedLayoutArtifactStats,
     pub(super) last_layout_artifact_action: RetainedLayoutArtifactAction,
+    /// Layout handed to the layout runtime, if any.
+    pub(super) layout_job: Option<LayoutJobState>,
     pub(super) paint_cache: Option<RetainedPaintArtifactEntry>,
     pub(super) paint_artifact_stats: RetainedPaintArtifactStats,
     pub(super) last_paint_artifact_action: RetainedPaintArtifactAction,
@@ -72,6 +75,7 @@ impl RetainedRenderState {
             layout_cache: None,
             layout_artifact_stats: RetainedLayoutArtifactStats::default(),
             last_layout_artifact_action: RetainedLayoutArtifactAction::None,
+            layout_job: None,
             paint_cache: None,
             paint_artifact_stats: RetainedPaintArtifactStats::default(),
             last_paint_artifact_action: RetainedPaintArtifactAction::None,
@@ -94,6 +98,7 @@ impl RetainedRenderState {
         self.layout_cache = None;
         self.layout_artifact_stats = RetainedLayoutArtifactStats::default();
         self.last_layout_artifact_action = RetainedLayoutArtifactAction::None;
+        self.layout_job = None;
         self.paint_cache = None;
         self.paint_artifact_stats = RetainedPaintArtifactStats::default();
         self.last_paint_artifact_action = RetainedPaintArtifactAction::None;
@@ -294,6 +299,11 @@ impl RetainedRenderState {
 
     pub(super) fn record_layout_frame_result(&mut self, result: RetainedLayoutFrameResult) {
         match result.action {
+            RetainedLayoutFrameAction::Deferred => {
+                // The layout stays dirty until the runtime delivers it.
+                self.last_layout_artifact_action = RetainedLayoutArtifactAction::DeferredToRuntime;
+                return;
+            }
             RetainedLayoutFrameAction::Reused => {
                 self.layout_artifact_stats.reuse_count = self
                     .layout_artifact_stats
@@ -335,6 +345,31 @@ impl RetainedRenderState {
         self.dirty_state.clear_phase(DirtyPhase::Layout);
     }
 
+    /// Retain a layout the layout runtime computed. It only clears the
+    /// layout dirty state if it was laid out for the current inputs;
+    /// otherwise it stands in until the next one arrives.
+    pub(super) fn record_offthread_layout(
+        &mut self,
+        artifact: RetainedLayoutArtifact,
+        fresh: bool,
+    ) {
+        self.layout_artifact_stats.recompute_count = self
+            .layout_artifact_stats
+            .recompute_count
+            .checked_add(1)
+            .expect("retained layout artifact recompute count exhausted");
+        self.last_layout_artifact_action = RetainedLayoutArtifactAction::ComputedOffThread;
+        self.layout_cache = Some(artifact);
+        if fresh {
+            self.dirty_state.clear_phase(DirtyPhase::Layout);
+        }
+        self.dirty_state.push(DirtyEntry::new(
+            DirtyPhase::Paint,
+            DirtyReason::CascadedFromLayout,
+            DirtyScope::Document,
+        ));
+    }
+
     pub(super) fn record_paint_frame_result(&mut self, result: RetainedPaintFrameResult) {
         match result.action {
             RetainedPaintFrameAction::Reused => {
@@ -573,6 +608,22 @@ impl RetainedRenderState {
         self.invalidate_style(scope);
     }
 
+    /// Page zoom scales computed lengths on the way into layout, so retained
+    /// layout and paint keyed on the previous style generations are stale.
+    pub(super) fn mark_zoom_changed(&mut self) {
+        self.generations.layout_style = self
+            .generations
+            .layout_style
+            .checked_add(1)
+            .expect("layout style generation exhausted");
+        self.generations.paint_style = self
+            .generations
+            .paint_style
+            .checked_add(1)
+            .expect("paint style generation exhausted");
+        self.mark_dirty_for_entry_point(RenderInvalidationEntryPoint::ViewportChanged);
+    }
+
     pub(super) fn mark_stylesheets_changed(&mut self) {
         self.generations.stylesheets = self
             .generations
@@ -584,6 +635,21 @@ impl RetainedRenderState {
         self.mark_dirty_for_entry_point(RenderInvalidationEntryPoint::StylesheetSetChanged);
     }
 
+    /// Viewport units resolve at computed-value time, so resizing the
+    /// viewport restyles pages whose styles use them.
+    pub(super) fn mark_viewport_styles_changed(&mut self) {
+        self.advance_render_epoch();
+        self.invalidate_style(StyleInvalidationScope::Full);
+        self.mark_dirty_for_entry_point(RenderInvalidationEntryPoint::ViewportChanged);
+    }
+
+    /// Whether the retained computed styles resolved any `vw` or `vh` length.
+    pub(super) fn style_depends_on_viewport(&self) -> bool {
+        self.style_cache
+            .as_ref()
+            .is_some_and(|cache| cache.computed.depends_on_viewport())
+    }
+
     pub(super) fn invalidate_style(&mut self, scope: StyleInvalidationScope) {
         let merged = match self.pending_style_invalidation.take() {
             Some(existing) => existing.merge(scope),
@@ -1,12 +1,13 @@
 use css::{
     ComputedDocumentStyle, ComputedStyleResolutionError, ComputedStyleReuseStats,
-    ResolvedDocumentStyle, StyleResolutionLimits, StylesheetCascadeInput,
+    ResolvedDocumentStyle, SelectorElementStates, StyleResolutionLimits,
     compute_document_styles_from_resolved_styles_with_reuse_stats,
     compute_document_styles_incremental_suffix_from_cascade_inputs_with_limits,
-    resolve_document_styles_from_cascade_inputs,
+    try_resolve_document_styles_from_cascade_inputs_with_limits,
 };
 use html::Node;
 
+use crate::document_style::DocumentStyleSet;
 use crate::rendering::RetainedStyleArtifactKey;
 
 use super::restyle::StyleInvalidationScope;
@@ -52,12 +53,15 @@ pub(super) struct StyleRecomputeState<'a> {
 
 pub(super) fn recompute_styles(
     dom: &Node,
-    sheets: &[StylesheetCascadeInput<'_>],
+    document_styles: &DocumentStyleSet,
+    element_states: &SelectorElementStates,
     generations: PageStyleGenerations,
     key: RetainedStyleArtifactKey,
     pending: StyleInvalidationScope,
     state: StyleRecomputeState<'_>,
 ) -> Result<(), ComputedStyleResolutionError> {
+    let sheets = document_styles.cascade_stylesheet_inputs();
+    let media = document_styles.media_environment();
     if let StyleInvalidationScope::AttributeSuffix { node_ids } = &pending
         && let Some(cache) = state.style_cache.as_ref()
         && cache.key.stylesheet_generation == generations.stylesheets
@@ -66,11 +70,13 @@ pub(super) fn recompute_styles(
         if let Some(incremental) =
             compute_document_styles_incremental_suffix_from_cascade_inputs_with_limits(
                 dom,
-                sheets,
+                &sheets,
+                element_states,
                 &cache.resolved,
                 &cache.computed,
                 node_ids,
                 &limits,
+                &media,
             )?
         {
             *state.last_style_recalc = Some(StyleRecalcKind::IncrementalSuffix {
@@ -88,9 +94,15 @@ pub(super) fn recompute_styles(
         }
     }
 
-    let resolved = resolve_document_styles_from_cascade_inputs(dom, sheets)
-        .map_err(ComputedStyleResolutionError::StyleResolution)?;
-    let computed = compute_document_styles_from_resolved_styles_with_reuse_stats(dom, &resolved)?;
+    let resolved = try_resolve_document_styles_from_cascade_inputs_with_limits(
+        dom,
+        &sheets,
+        element_states,
+        &StyleResolutionLimits::default(),
+    )
+    .map_err(ComputedStyleResolutionError::StyleResolution)?;
+    let computed =
+        compute_document_styles_from_resolved_styles_with_reuse_stats(dom, &resolved, &media)?;
     let elements = computed.computed.entries().len();
     *state.last_style_recalc = Some(StyleRecalcKind::Full { elements });
     *state.last_style_reuse = Some(computed.reuse_stats);
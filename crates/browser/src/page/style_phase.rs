@@ -9,6 +9,7 @@ use crate::rendering::RetainedPaintArtifactKeySeed;
 use crate::rendering::{PendingRenderWork, RenderWorkPlan, RetainedStyleArtifactAction};
 
 use super::PageState;
+use super::layout_job::{LayoutJobRequest, LayoutOffload};
 use super::restyle::StyleInvalidationScope;
 use super::style_cache::{StyleRecalcKind, StyleRecomputeState, recompute_styles};
 
@@ -19,6 +20,8 @@ pub(crate) struct PreparedStylePhaseForFrame<'a> {
     pub(crate) retained_layout_artifact: Option<RetainedLayoutArtifact>,
     pub(crate) retained_paint_key_seed: RetainedPaintArtifactKeySeed,
     pub(crate) retained_paint_artifact: Option<PaintArtifact>,
+    pub(crate) layout_job: Option<LayoutJobRequest>,
+    pub(crate) layout_deferred: bool,
 }
 
 impl PageState {
@@ -44,20 +47,24 @@ impl PageState {
             .style_cache
             .as_ref()
             .expect("style cache must exist after successful style computation");
+        let zoom = self.zoom;
         build_style_tree_from_computed_styles(dom, &cache.computed)
-            .map(StylePhaseOutput::new)
+            .map(|root| StylePhaseOutput::new(root).with_zoom(zoom))
             .map(Some)
     }
 
     pub(crate) fn prepare_style_phase_for_frame(
         &mut self,
         pending_work: &PendingRenderWork,
+        offload: Option<&LayoutOffload<'_>>,
     ) -> Result<Option<PreparedStylePhaseForFrame<'_>>, ComputedStyleResolutionError> {
         if !self.ensure_retained_style_artifacts()? {
             return Ok(None);
         }
 
         let work_plan = self.derive_render_work_plan(pending_work);
+        let layout_job = offload.and_then(|offload| self.request_layout_job(offload));
+        let layout_deferred = self.layout_deferred();
         let retained_layout_key_seed = self.retained_layout_key_seed();
         let retained_layout_artifact = self.retained_layout_artifact().cloned();
         let retained_paint_key_seed = self.retained_paint_key_seed();
@@ -71,8 +78,9 @@ impl PageState {
             .style_cache
             .as_ref()
             .expect("style cache must exist after successful style computation");
+        let zoom = self.zoom;
         build_style_tree_from_computed_styles(dom, &cache.computed)
-            .map(StylePhaseOutput::new)
+            .map(|root| StylePhaseOutput::new(root).with_zoom(zoom))
             .map(|style_output| {
                 Some(PreparedStylePhaseForFrame {
                     style_output,
@@ -81,6 +89,8 @@ impl PageState {
                     retained_layout_artifact,
                     retained_paint_key_seed,
                     retained_paint_artifact,
+                    layout_job,
+                    layout_deferred,
                 })
             })
     }
@@ -90,6 +100,7 @@ impl PageState {
             return Ok(false);
         };
 
+        let element_states = self.element_states();
         let retained = &mut self.rendering;
         let needs_recompute = retained.style_dirty() || !retained.style_cache_matches_current_key();
 
@@ -107,7 +118,8 @@ impl PageState {
             let mut style_dirty = true;
             recompute_styles(
                 dom,
-                &retained.document_styles.cascade_stylesheet_inputs(),
+                &retained.document_styles,
+                &element_states,
                 retained.generations,
                 style_key,
                 pending_style_invalidation.unwrap_or(StyleInvalidationScope::Full),
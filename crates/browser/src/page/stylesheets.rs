@@ -3,7 +3,7 @@ use crate::rendering::{
     RenderInvalidationEntryPoint, RenderInvalidationRequest, render_invalidation_request,
 };
 use core_types::StylesheetSlotId;
-use css::StylesheetParse;
+use css::{ColorScheme, MediaEnvironment, StylesheetParse};
 
 use super::PageState;
 
@@ -37,6 +37,27 @@ impl PageState {
         }
     }
 
+    /// Reserve a slot for a hinted stylesheet; see
+    /// [`DocumentStyleSet::preload_external`](crate::document_style::DocumentStyleSet::preload_external).
+    pub(crate) fn preload_stylesheet(&mut self, href: &str) -> Option<StylesheetFetch> {
+        self.rendering
+            .document_styles
+            .preload_external(href, self.base_url.as_deref())
+    }
+
+    /// Register the sheet an `@import` rule of the sheet in `slot_id` loads;
+    /// see [`DocumentStyleSet::add_import`](crate::document_style::DocumentStyleSet::add_import).
+    pub(crate) fn import_stylesheet(
+        &mut self,
+        slot_id: StylesheetSlotId,
+        href: &str,
+        media: &str,
+    ) -> Option<StylesheetFetch> {
+        self.rendering
+            .document_styles
+            .add_import(slot_id, href, media, self.base_url.as_deref())
+    }
+
     #[cfg(test)]
     pub(crate) fn register_css(&mut self, absolute_url: &str) -> StylesheetSlotId {
         self.rendering
@@ -109,6 +130,62 @@ impl PageState {
         }
     }
 
+    /// Re-evaluate `@media` rules for `color_scheme`; stylesheets that use
+    /// them are parsed again and the page restyled.
+    pub(crate) fn set_color_scheme(
+        &mut self,
+        color_scheme: ColorScheme,
+    ) -> Option<RenderInvalidationRequest> {
+        let media = MediaEnvironment {
+            color_scheme,
+            ..self.rendering.document_styles.media_environment()
+        };
+        self.apply_media_environment(media)
+    }
+
+    /// Re-evaluate `@media` rules for a viewport of `width` by `height` egui
+    /// points (CSS px at zoom 1). Returns `None` unless a stylesheet crossed
+    /// one of its breakpoints or the page's styles use viewport units.
+    pub(crate) fn set_viewport_size(
+        &mut self,
+        width: f32,
+        height: f32,
+    ) -> Option<RenderInvalidationRequest> {
+        if self.viewport_size == (width, height) {
+            return None;
+        }
+        self.viewport_size = (width, height);
+        self.apply_media_environment(self.rendering.document_styles.media_environment())
+    }
+
+    /// Evaluate `@media` rules against `media` with the current viewport.
+    pub(super) fn apply_media_environment(
+        &mut self,
+        media: MediaEnvironment,
+    ) -> Option<RenderInvalidationRequest> {
+        // Media features see the viewport in CSS pixels, so zooming in
+        // narrows it.
+        let (width, height) = self.viewport_size;
+        let media = media.with_viewport_size(width / self.zoom, height / self.zoom);
+        let previous = self.rendering.document_styles.media_environment();
+        if self.rendering.document_styles.set_media_environment(media) {
+            self.rendering.mark_stylesheets_changed();
+            Some(render_invalidation_request(
+                RenderInvalidationEntryPoint::StylesheetSetChanged,
+            ))
+        } else if (previous.viewport_width, previous.viewport_height)
+            != (media.viewport_width, media.viewport_height)
+            && self.rendering.style_depends_on_viewport()
+        {
+            self.rendering.mark_viewport_styles_changed();
+            Some(render_invalidation_request(
+                RenderInvalidationEntryPoint::ViewportChanged,
+            ))
+        } else {
+            None
+        }
+    }
+
     pub fn pending_count(&self) -> usize {
         self.rendering.document_styles.pending_count()
     }
@@ -0,0 +1,81 @@
+//! Visited state of the document's links, for `:link` and `:visited`.
+//!
+//! The page keeps the URLs the browser has visited and works out which link
+//! elements point at one of them whenever styles are recomputed, so links
+//! added by DOM patches or retargeted by `<base href>` are always current.
+
+use css::SelectorElementStates;
+use html::{Node, internal::Id};
+
+use crate::rendering::{
+    RenderInvalidationEntryPoint, RenderInvalidationRequest, render_invalidation_request,
+};
+
+use super::PageState;
+use super::restyle::StyleInvalidationScope;
+
+impl PageState {
+    /// Remember that `url` was visited. Returns `None` unless a link in the
+    /// current document points at it, since nothing else restyles.
+    pub(crate) fn mark_visited(&mut self, url: &str) -> Option<RenderInvalidationRequest> {
+        let url = normalize_url(url);
+        if !self.visited_urls.insert(url.clone()) {
+            return None;
+        }
+        let dom = self.dom.as_deref()?;
+        let mut node_ids = Vec::new();
+        for_each_link(dom, self.base_url.as_deref(), |node_id, target| {
+            if target == url {
+                node_ids.push(node_id);
+            }
+        });
+        if node_ids.is_empty() {
+            return None;
+        }
+
+        // Selector matching sees a link changing state the same way it sees
+        // an attribute change on that link.
+        self.rendering
+            .mark_style_inputs_changed(StyleInvalidationScope::AttributeSuffix { node_ids });
+        let entry_point = RenderInvalidationEntryPoint::DomAttributesChanged;
+        self.rendering.mark_dirty_for_entry_point(entry_point);
+        Some(render_invalidation_request(entry_point))
+    }
+
+    /// The states of the document's link elements, for selector matching.
+    pub(super) fn link_element_states(&self) -> SelectorElementStates {
+        let mut states = SelectorElementStates::new();
+        if let Some(dom) = self.dom.as_deref()
+            && !self.visited_urls.is_empty()
+        {
+            for_each_link(dom, self.base_url.as_deref(), |node_id, target| {
+                if self.visited_urls.contains(&target) {
+                    states.insert_visited_link(node_id);
+                }
+            });
+        }
+        states
+    }
+}
+
+/// Call `visit` with every HTML `a` and `area` element whose `href` resolves
+/// against `base_url`, and the URL it resolves to.
+fn for_each_link(root: &Node, base_url: Option<&str>, mut visit: impl FnMut(Id, String)) {
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if let Node::Element { element } = node
+            && element.namespace() == html::ElementNamespace::Html
+            && matches!(element.name(), "a" | "area")
+            && let Some(href) = node.attr("href")
+            && let Some(target) = weburl::resolve_str(base_url, href)
+        {
+            visit(node.id(), target);
+        }
+        stack.extend(node.children().unwrap_or_default().iter().rev());
+    }
+}
+
+/// Visited URLs are compared in the normalized form links resolve to.
+fn normalize_url(url: &str) -> String {
+    weburl::parse(url).map_or_else(|| url.to_string(), String::from)
+}
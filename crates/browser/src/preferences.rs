@@ -0,0 +1,159 @@
+//! User preferences owned by the browser shell.
+//!
+//! Preferences persist to a small tab-separated `key \t value` file in the
+//! user data directory. Like bookmarks, every change is written back
+//! immediately; changes are rare and user-initiated.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::user_data::{user_data_dir, write_atomically};
+
+const PREFERENCES_FILE: &str = "preferences.tsv";
+
+const INSTANT_SCROLL: &str = "instant_scroll";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Preferences {
+    /// Page scrolls jump instead of animating.
+    pub instant_scroll: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct PreferenceStore {
+    preferences: Preferences,
+    /// Backing file; `None` keeps the store in memory only.
+    path: Option<PathBuf>,
+}
+
+impl PreferenceStore {
+    /// Create a store with default preferences that is never persisted.
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Load preferences from `preferences.tsv` in the user data directory.
+    ///
+    /// Falls back to an in-memory store if no data directory can be resolved.
+    /// A missing or unreadable file yields the defaults.
+    pub fn load_default() -> Self {
+        match user_data_dir() {
+            Some(dir) => Self::load(dir.join(PREFERENCES_FILE)),
+            None => Self::in_memory(),
+        }
+    }
+
+    /// Load preferences from `path`, persisting future changes to the same
+    /// file.
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let preferences = fs::read_to_string(&path)
+            .map(|text| parse(&text))
+            .unwrap_or_default();
+        Self {
+            preferences,
+            path: Some(path),
+        }
+    }
+
+    pub fn get(&self) -> Preferences {
+        self.preferences
+    }
+
+    /// Remember whether page scrolls jump instead of animating. If saving
+    /// fails the store is left unchanged.
+    pub fn set_instant_scroll(&mut self, instant: bool) -> io::Result<()> {
+        let mut preferences = self.preferences;
+        preferences.instant_scroll = instant;
+        self.commit(preferences)
+    }
+
+    /// Save `preferences` and make them the store's only once they are on
+    /// disk.
+    fn commit(&mut self, preferences: Preferences) -> io::Result<()> {
+        if preferences == self.preferences {
+            return Ok(());
+        }
+        if let Some(path) = &self.path {
+            write_atomically(path, &serialize(&preferences))?;
+        }
+        self.preferences = preferences;
+        Ok(())
+    }
+}
+
+// One preference per line: `key \t value`. Unknown keys and malformed values
+// are ignored, so the file stays readable across versions.
+
+fn serialize(preferences: &Preferences) -> String {
+    format!("{INSTANT_SCROLL}\t{}\n", preferences.instant_scroll)
+}
+
+fn parse(text: &str) -> Preferences {
+    let mut preferences = Preferences::default();
+    for line in text.lines() {
+        let Some((key, value)) = line.split_once('\t') else {
+            continue;
+        };
+        if key == INSTANT_SCROLL
+            && let Ok(instant) = value.parse()
+        {
+            preferences.instant_scroll = instant;
+        }
+    }
+    preferences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_keys_and_malformed_lines_are_ignored() {
+        assert_eq!(
+            parse("colour\tblue\ninstant_scroll\tmaybe\nno-tab\n"),
+            Preferences::default()
+        );
+        assert_eq!(
+            parse("colour\tblue\ninstant_scroll\ttrue\n"),
+            Preferences {
+                instant_scroll: true
+            }
+        );
+    }
+
+    #[test]
+    fn store_persists_to_its_file() {
+        let dir =
+            std::env::temp_dir().join(format!("borrowser-preferences-{}", std::process::id()));
+        let path = dir.join(PREFERENCES_FILE);
+        let _ = fs::remove_dir_all(&dir);
+
+        let mut store = PreferenceStore::load(&path);
+        assert!(!store.get().instant_scroll);
+        store.set_instant_scroll(true).unwrap();
+
+        let reloaded = PreferenceStore::load(&path);
+        assert!(reloaded.get().instant_scroll);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn failed_saves_leave_the_store_unchanged() {
+        let blocker = std::env::temp_dir().join(format!(
+            "borrowser-preferences-blocked-{}",
+            std::process::id()
+        ));
+        fs::write(&blocker, "not a directory").unwrap();
+        let mut store = PreferenceStore::in_memory();
+        // The parent of the file is a plain file, so every save fails.
+        store.path = Some(blocker.join(PREFERENCES_FILE));
+
+        assert!(store.set_instant_scroll(true).is_err());
+        assert!(!store.get().instant_scroll);
+
+        let _ = fs::remove_file(&blocker);
+    }
+}
@@ -0,0 +1,25 @@
+//! Printing without a window: load a URL in an offscreen tab, then print it.
+
+use super::{PrintError, PrintOptions};
+use crate::headless::load_url;
+use crate::tab::Tab;
+use bus::{CoreCommand, CoreEvent};
+use std::sync::mpsc::{Receiver, Sender};
+use std::time::Duration;
+
+/// Load `url` and render it as a PDF.
+///
+/// `cmd_tx` and `evt_rx` are the two ends of a running runtime bus. The page
+/// prints once its document arrived and no subresource is pending; if
+/// `timeout` runs out after the document arrived, it prints as loaded so far.
+pub fn print_url_to_pdf(
+    url: &str,
+    cmd_tx: Sender<CoreCommand>,
+    evt_rx: &Receiver<CoreEvent>,
+    options: &PrintOptions,
+    timeout: Duration,
+) -> Result<Vec<u8>, PrintError> {
+    let mut tab = Tab::new(1);
+    load_url(&mut tab, url, cmd_tx, evt_rx, timeout).map_err(PrintError::Load)?;
+    tab.print_pdf(options)
+}
@@ -0,0 +1,72 @@
+//! Helvetica metrics for laying out printed pages.
+//!
+//! Printed text is drawn with the standard Helvetica font in WinAnsi
+//! encoding, which every PDF viewer provides, so layout measures with the
+//! same advance widths. Characters WinAnsi cannot encode print as `?`.
+
+use css::{ComputedStyle, Length};
+use layout::TextMeasurer;
+
+/// Advance widths of WinAnsi codes 32..=126, in 1/1000 em, 16 per row.
+#[rustfmt::skip]
+const ASCII_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556,
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556,
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556,
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+/// Width used for encodable characters outside the ASCII table.
+const DEFAULT_WIDTH: u16 = 556;
+
+/// Lays text out with Helvetica advance widths and a 1.2 line height.
+pub(crate) struct HelveticaMeasurer;
+
+impl TextMeasurer for HelveticaMeasurer {
+    fn measure(&self, text: &str, style: &ComputedStyle) -> f32 {
+        let Length::Px(font_px) = style.font_size();
+        let units: u32 = text
+            .chars()
+            .map(|ch| u32::from(char_width(win_ansi_code(ch))))
+            .sum();
+        units as f32 * font_px / 1000.0
+    }
+
+    fn line_height(&self, style: &ComputedStyle) -> f32 {
+        let Length::Px(px) = style.font_size();
+        px * 1.2
+    }
+}
+
+/// WinAnsi byte for `ch`, or `?` if it has none.
+pub(super) fn win_ansi_code(ch: char) -> u8 {
+    match ch {
+        ' '..='~' => ch as u8,
+        '\u{a0}'..='\u{ff}' => ch as u32 as u8,
+        '€' => 0x80,
+        '…' => 0x85,
+        '‘' => 0x91,
+        '’' => 0x92,
+        '“' => 0x93,
+        '”' => 0x94,
+        '•' => 0x95,
+        '–' => 0x96,
+        '—' => 0x97,
+        '™' => 0x99,
+        _ => b'?',
+    }
+}
+
+fn char_width(code: u8) -> u16 {
+    match code {
+        32..=126 => ASCII_WIDTHS[usize::from(code - 32)],
+        0xa0 => 278,
+        0x91 | 0x92 => 222,
+        0x93 | 0x94 => 333,
+        0x95 => 350,
+        0x85 | 0x97 | 0x99 => 1000,
+        _ => DEFAULT_WIDTH,
+    }
+}
@@ -0,0 +1,418 @@
+//! Printing: the document laid out at a fixed page width, split into page
+//! boxes and written out as a PDF.
+//!
+//! Layout runs with Helvetica metrics so line breaks match the standard font
+//! the pages are drawn with. Page breaks never cut through a line of text,
+//! a list marker or replaced content that fits on one page; tall boxes such
+//! as backgrounds and borders continue on the next page.
+//!
+//! Images and form controls print as outlined placeholders.
+
+mod headless;
+mod metrics;
+mod pdf;
+
+pub use headless::print_url_to_pdf;
+
+use crate::page::PageState;
+use css::ComputedStyleResolutionError;
+use gfx::paint::{
+    PaintArtifact, PaintColor, PaintListMarkerKind, PaintNode, PaintPhaseInput, PaintPrimitive,
+};
+use layout::{LayoutPhaseInput, Rectangle, layout_document};
+use metrics::HelveticaMeasurer;
+use pdf::{ContentStream, PdfDocument, PdfRect};
+use std::fmt;
+use std::io;
+
+/// Points per CSS pixel (72 pt and 96 px per inch).
+const PT_PER_PX: f32 = 0.75;
+
+/// Grey of the placeholder outline drawn for images and form controls.
+const PLACEHOLDER_RGB: [f32; 3] = [0.6, 0.6, 0.6];
+
+/// Page geometry in PDF points.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PrintOptions {
+    pub page_width_pt: f32,
+    pub page_height_pt: f32,
+    /// Blank border on every side of the page.
+    pub margin_pt: f32,
+}
+
+impl PrintOptions {
+    /// A4 portrait with half-inch margins.
+    pub const A4: Self = Self {
+        page_width_pt: 595.0,
+        page_height_pt: 842.0,
+        margin_pt: 36.0,
+    };
+
+    /// Width the document is laid out at, in CSS px.
+    fn content_width_px(&self) -> f32 {
+        ((self.page_width_pt - 2.0 * self.margin_pt) / PT_PER_PX).max(1.0)
+    }
+
+    /// Document height that fits on one page, in CSS px.
+    fn content_height_px(&self) -> f32 {
+        ((self.page_height_pt - 2.0 * self.margin_pt) / PT_PER_PX).max(1.0)
+    }
+}
+
+impl Default for PrintOptions {
+    fn default() -> Self {
+        Self::A4
+    }
+}
+
+#[derive(Debug)]
+pub enum PrintError {
+    /// No document has been parsed yet.
+    NoDocument,
+    Style(ComputedStyleResolutionError),
+    /// The document could not be loaded for printing.
+    Load(String),
+    Io(io::Error),
+}
+
+impl fmt::Display for PrintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoDocument => write!(f, "there is no document to print"),
+            Self::Style(err) => write!(f, "styling failed: {err}"),
+            Self::Load(reason) => write!(f, "loading failed: {reason}"),
+            Self::Io(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for PrintError {}
+
+impl From<io::Error> for PrintError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// Lay out the document of `page` for printing and render it as a PDF.
+pub fn print_page(page: &mut PageState, options: &PrintOptions) -> Result<Vec<u8>, PrintError> {
+    let style_output = page
+        .build_style_phase_output()
+        .map_err(PrintError::Style)?
+        .ok_or(PrintError::NoDocument)?;
+    let measurer = HelveticaMeasurer;
+    let layout_output = layout_document(LayoutPhaseInput::from_style_output(
+        &style_output,
+        options.content_width_px(),
+        &measurer,
+        None,
+    ));
+    let artifact = PaintArtifact::from_phase_input(PaintPhaseInput::new(&layout_output), &measurer);
+    Ok(render_pdf(&artifact, options))
+}
+
+/// Paginate an artifact laid out at the options' content width.
+fn render_pdf(artifact: &PaintArtifact, options: &PrintOptions) -> Vec<u8> {
+    let mut primitives = Vec::new();
+    collect_primitives(artifact.tree().root(), &mut primitives);
+
+    let mut document = PdfDocument::new(options.page_width_pt, options.page_height_pt);
+    for (top, bottom) in page_breaks(&primitives, options.content_height_px()) {
+        let frame = PageFrame {
+            top,
+            margin_pt: options.margin_pt,
+            page_height_pt: options.page_height_pt,
+        };
+        let mut content = ContentStream::default();
+        content.save_clipped(frame.rect(&Rectangle {
+            x: 0.0,
+            y: top,
+            width: options.content_width_px(),
+            height: bottom - top,
+        }));
+        for primitive in &primitives {
+            draw_primitive(&mut content, &frame, primitive, bottom);
+        }
+        content.restore();
+        document.add_page(content);
+    }
+    document.finish()
+}
+
+/// Primitives in paint order: each node's own, then its children's, then its
+/// post-primitives.
+fn collect_primitives<'a>(node: &'a PaintNode, out: &mut Vec<&'a PaintPrimitive>) {
+    out.extend(node.primitives());
+    for child in node.children() {
+        collect_primitives(child, out);
+    }
+    out.extend(node.post_primitives());
+}
+
+/// `(top, bottom)` document offsets of each page, in CSS px. There is always
+/// at least one page.
+fn page_breaks(primitives: &[&PaintPrimitive], page_height: f32) -> Vec<(f32, f32)> {
+    let document_bottom = primitives
+        .iter()
+        .filter_map(|primitive| primitive_rect(primitive))
+        .map(|rect| rect.y + rect.height)
+        .fold(0.0, f32::max);
+    let unbreakable: Vec<(f32, f32)> = primitives
+        .iter()
+        .filter(|primitive| {
+            matches!(
+                primitive,
+                PaintPrimitive::Text(_)
+                    | PaintPrimitive::ListMarker(_)
+                    | PaintPrimitive::InlineBox(_)
+                    | PaintPrimitive::Replaced(_)
+            )
+        })
+        .filter_map(|primitive| primitive_rect(primitive))
+        .filter(|rect| rect.height <= page_height)
+        .map(|rect| (rect.y, rect.y + rect.height))
+        .collect();
+
+    let mut pages = Vec::new();
+    let mut top = 0.0;
+    loop {
+        let mut bottom = top + page_height;
+        if bottom >= document_bottom {
+            pages.push((top, document_bottom.max(top)));
+            return pages;
+        }
+        // Pull the break up to the start of anything it would cut through.
+        while let Some(start) = unbreakable
+            .iter()
+            .filter(|&&(start, end)| start > top && start < bottom && end > bottom)
+            .map(|&(start, _)| start)
+            .reduce(f32::min)
+        {
+            bottom = start;
+        }
+        pages.push((top, bottom));
+        top = bottom;
+    }
+}
+
+fn primitive_rect(primitive: &PaintPrimitive) -> Option<Rectangle> {
+    Some(match primitive {
+        PaintPrimitive::Background(background) => background.rect,
+        PaintPrimitive::Border(border) => border.rect,
+        PaintPrimitive::Outline(outline) => outline.outer_rect,
+        PaintPrimitive::ListMarker(marker) => marker.rect,
+        PaintPrimitive::Text(text) => text.rect,
+        PaintPrimitive::TextDecoration(decoration) => decoration.rect,
+        PaintPrimitive::InlineBox(inline_box) => inline_box.rect,
+        PaintPrimitive::Replaced(replaced) => replaced.rect,
+        PaintPrimitive::Clip(_) => return None,
+    })
+}
+
+/// Maps document px of one page slice to PDF points.
+struct PageFrame {
+    /// Document offset shown at the top of the page's content box.
+    top: f32,
+    margin_pt: f32,
+    page_height_pt: f32,
+}
+
+impl PageFrame {
+    fn x(&self, x_px: f32) -> f32 {
+        self.margin_pt + x_px * PT_PER_PX
+    }
+
+    /// PDF y grows upwards from the bottom of the page.
+    fn y(&self, y_px: f32) -> f32 {
+        self.page_height_pt - self.margin_pt - (y_px - self.top) * PT_PER_PX
+    }
+
+    fn rect(&self, rect: &Rectangle) -> PdfRect {
+        PdfRect {
+            x: self.x(rect.x),
+            y: self.y(rect.y + rect.height),
+            width: rect.width * PT_PER_PX,
+            height: rect.height * PT_PER_PX,
+        }
+    }
+}
+
+/// Draw the part of `primitive` on the page ending at `bottom`. Text and
+/// markers print on the page their line starts on.
+fn draw_primitive(
+    content: &mut ContentStream,
+    frame: &PageFrame,
+    primitive: &PaintPrimitive,
+    bottom: f32,
+) {
+    let Some(rect) = primitive_rect(primitive) else {
+        return;
+    };
+    let starts_here = rect.y >= frame.top && rect.y < bottom;
+    if rect.y + rect.height <= frame.top || rect.y >= bottom {
+        return;
+    }
+    let mut fill = |rect: Rectangle, color: PaintColor| {
+        if color.is_visible() && rect.width > 0.0 && rect.height > 0.0 {
+            content.fill_rect(frame.rect(&rect), rgb(color));
+        }
+    };
+    match primitive {
+        PaintPrimitive::Background(background) => fill(background.rect, background.color),
+        PaintPrimitive::Border(border) => {
+            let Rectangle {
+                x,
+                y,
+                width,
+                height,
+            } = border.rect;
+            let edges = border.edges;
+            let sides = [
+                (
+                    Rectangle {
+                        x,
+                        y,
+                        width,
+                        height: edges.top.width,
+                    },
+                    edges.top.color,
+                ),
+                (
+                    Rectangle {
+                        x,
+                        y: y + height - edges.bottom.width,
+                        width,
+                        height: edges.bottom.width,
+                    },
+                    edges.bottom.color,
+                ),
+                (
+                    Rectangle {
+                        x,
+                        y,
+                        width: edges.left.width,
+                        height,
+                    },
+                    edges.left.color,
+                ),
+                (
+                    Rectangle {
+                        x: x + width - edges.right.width,
+                        y,
+                        width: edges.right.width,
+                        height,
+                    },
+                    edges.right.color,
+                ),
+            ];
+            for (side, color) in sides {
+                fill(side, color);
+            }
+        }
+        PaintPrimitive::Outline(outline) => {
+            let Rectangle {
+                x,
+                y,
+                width,
+                height,
+            } = outline.outer_rect;
+            let w = outline.width;
+            let sides = [
+                Rectangle {
+                    x,
+                    y,
+                    width,
+                    height: w,
+                },
+                Rectangle {
+                    x,
+                    y: y + height - w,
+                    width,
+                    height: w,
+                },
+                Rectangle {
+                    x,
+                    y,
+                    width: w,
+                    height,
+                },
+                Rectangle {
+                    x: x + width - w,
+                    y,
+                    width: w,
+                    height,
+                },
+            ];
+            for side in sides {
+                fill(side, outline.color);
+            }
+        }
+        PaintPrimitive::TextDecoration(decoration) => fill(decoration.rect, decoration.color),
+        PaintPrimitive::InlineBox(inline_box) => {
+            if let Some(color) = inline_box.fallback_background {
+                fill(inline_box.rect, color);
+            }
+        }
+        PaintPrimitive::Text(text)
+            if starts_here && text.color.is_visible() && !text.text.trim().is_empty() =>
+        {
+            draw_text(
+                content,
+                frame,
+                &text.rect,
+                text.font_size_px,
+                text.color,
+                &text.text,
+            );
+        }
+        PaintPrimitive::ListMarker(marker) if starts_here && marker.color.is_visible() => {
+            let label = match marker.kind {
+                PaintListMarkerKind::Unordered => "•".to_string(),
+                PaintListMarkerKind::Ordered(index) => format!("{index}."),
+            };
+            draw_text(
+                content,
+                frame,
+                &marker.rect,
+                marker.font_size_px,
+                marker.color,
+                &label,
+            );
+        }
+        PaintPrimitive::Replaced(replaced) if starts_here => {
+            content.stroke_rect(frame.rect(&replaced.rect), PLACEHOLDER_RGB, 0.75);
+        }
+        _ => {}
+    }
+}
+
+/// Draw `text` vertically centred in its line rect the way glyphs sit in a
+/// Helvetica line box.
+fn draw_text(
+    content: &mut ContentStream,
+    frame: &PageFrame,
+    rect: &Rectangle,
+    font_size_px: f32,
+    color: PaintColor,
+    text: &str,
+) {
+    // Helvetica glyphs span 0.718 em above to 0.207 em below the baseline.
+    let baseline = rect.y + rect.height / 2.0 + font_size_px * (0.718 - 0.207) / 2.0;
+    content.text(
+        frame.x(rect.x),
+        frame.y(baseline),
+        font_size_px * PT_PER_PX,
+        rgb(color),
+        text,
+    );
+}
+
+/// Colour blended onto the white page by its alpha.
+fn rgb(color: PaintColor) -> [f32; 3] {
+    let alpha = f32::from(color.a) / 255.0;
+    let channel = |value: u8| (f32::from(value) / 255.0) * alpha + (1.0 - alpha);
+    [channel(color.r), channel(color.g), channel(color.b)]
+}
+
+#[cfg(test)]
+mod tests;
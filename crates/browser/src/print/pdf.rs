@@ -0,0 +1,161 @@
+//! Minimal PDF 1.4 writer: uncompressed page content streams drawing filled
+//! rectangles, stroked outlines and Helvetica text.
+
+use super::metrics::win_ansi_code;
+use std::fmt::Write;
+
+/// A rectangle in PDF user space: points, origin at the bottom-left corner.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct PdfRect {
+    pub(super) x: f32,
+    pub(super) y: f32,
+    pub(super) width: f32,
+    pub(super) height: f32,
+}
+
+/// Drawing operators of one page.
+#[derive(Default)]
+pub(super) struct ContentStream {
+    ops: String,
+}
+
+impl ContentStream {
+    /// Restrict everything drawn until [`ContentStream::restore`] to `rect`.
+    pub(super) fn save_clipped(&mut self, rect: PdfRect) {
+        let PdfRect {
+            x,
+            y,
+            width,
+            height,
+        } = rect;
+        let _ = writeln!(self.ops, "q {x:.2} {y:.2} {width:.2} {height:.2} re W n");
+    }
+
+    pub(super) fn restore(&mut self) {
+        self.ops.push_str("Q\n");
+    }
+
+    pub(super) fn fill_rect(&mut self, rect: PdfRect, rgb: [f32; 3]) {
+        let PdfRect {
+            x,
+            y,
+            width,
+            height,
+        } = rect;
+        let [r, g, b] = rgb;
+        let _ = writeln!(
+            self.ops,
+            "{r:.3} {g:.3} {b:.3} rg {x:.2} {y:.2} {width:.2} {height:.2} re f"
+        );
+    }
+
+    pub(super) fn stroke_rect(&mut self, rect: PdfRect, rgb: [f32; 3], line_width: f32) {
+        let PdfRect {
+            x,
+            y,
+            width,
+            height,
+        } = rect;
+        let [r, g, b] = rgb;
+        let _ = writeln!(
+            self.ops,
+            "{r:.3} {g:.3} {b:.3} RG {line_width:.2} w {x:.2} {y:.2} {width:.2} {height:.2} re S"
+        );
+    }
+
+    /// Draw `text` with its baseline starting at (`x`, `y`).
+    pub(super) fn text(&mut self, x: f32, y: f32, size: f32, rgb: [f32; 3], text: &str) {
+        let [r, g, b] = rgb;
+        let _ = write!(
+            self.ops,
+            "BT /F1 {size:.2} Tf {r:.3} {g:.3} {b:.3} rg {x:.2} {y:.2} Td ("
+        );
+        for ch in text.chars() {
+            match win_ansi_code(ch) {
+                code @ (b'(' | b')' | b'\\') => {
+                    self.ops.push('\\');
+                    self.ops.push(char::from(code));
+                }
+                code @ 0x20..=0x7e => self.ops.push(char::from(code)),
+                code => {
+                    let _ = write!(self.ops, "\\{code:03o}");
+                }
+            }
+        }
+        self.ops.push_str(") Tj ET\n");
+    }
+}
+
+/// Pages of one size, serialized with [`PdfDocument::finish`].
+pub(super) struct PdfDocument {
+    width_pt: f32,
+    height_pt: f32,
+    pages: Vec<ContentStream>,
+}
+
+impl PdfDocument {
+    pub(super) fn new(width_pt: f32, height_pt: f32) -> Self {
+        Self {
+            width_pt,
+            height_pt,
+            pages: Vec::new(),
+        }
+    }
+
+    pub(super) fn add_page(&mut self, content: ContentStream) {
+        self.pages.push(content);
+    }
+
+    pub(super) fn finish(self) -> Vec<u8> {
+        // Objects 1-3 are the catalog, the page tree and the font; page `i`
+        // is object 4 + 2i with its content stream right after it.
+        let page_ids: Vec<usize> = (0..self.pages.len()).map(|i| 4 + 2 * i).collect();
+        let kids = page_ids
+            .iter()
+            .map(|id| format!("{id} 0 R"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut objects = vec![
+            "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+            format!(
+                "<< /Type /Pages /Kids [{kids}] /Count {} >>",
+                self.pages.len()
+            ),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+                .to_string(),
+        ];
+        for (page, id) in self.pages.iter().zip(&page_ids) {
+            objects.push(format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {:.2} {:.2}] \
+/Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+                self.width_pt,
+                self.height_pt,
+                id + 1
+            ));
+            objects.push(format!(
+                "<< /Length {} >>\nstream\n{}endstream",
+                page.ops.len(),
+                page.ops
+            ));
+        }
+
+        let mut out = b"%PDF-1.4\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (idx, object) in objects.iter().enumerate() {
+            offsets.push(out.len());
+            out.extend_from_slice(format!("{} 0 obj\n{object}\nendobj\n", idx + 1).as_bytes());
+        }
+        let xref_offset = out.len();
+        let mut trailer = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+        for offset in offsets {
+            let _ = writeln!(trailer, "{offset:010} 00000 n ");
+        }
+        let _ = write!(
+            trailer,
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n",
+            objects.len() + 1
+        );
+        out.extend_from_slice(trailer.as_bytes());
+        out
+    }
+}
@@ -0,0 +1,94 @@
+use super::*;
+use crate::page::RestyleHint;
+use html::{HtmlParseOptions, parse_document};
+
+fn page_with_dom(input: &str) -> PageState {
+    let output = parse_document(input, HtmlParseOptions::default()).expect("parse should work");
+    let mut page = PageState::new();
+    page.start_nav("https://example.com/index.html");
+    let _ = page.replace_dom(Box::new(output.document), RestyleHint::document_replaced());
+    let _ = page.reconcile_document_stylesheets();
+    page
+}
+
+fn page_count(pdf: &[u8]) -> usize {
+    String::from_utf8_lossy(pdf).matches("/Type /Page ").count()
+}
+
+/// Small pages so a few paragraphs overflow them.
+const SMALL: PrintOptions = PrintOptions {
+    page_width_pt: 300.0,
+    page_height_pt: 200.0,
+    margin_pt: 20.0,
+};
+
+#[test]
+fn short_documents_print_on_one_page_with_their_text() {
+    let mut page = page_with_dom("<!doctype html><p>Hello (printed) world</p>");
+    let pdf = print_page(&mut page, &PrintOptions::A4).expect("print should work");
+    let text = String::from_utf8_lossy(&pdf);
+
+    assert!(text.starts_with("%PDF-1.4"));
+    assert!(text.trim_end().ends_with("%%EOF"));
+    assert_eq!(page_count(&pdf), 1);
+    for word in ["(Hello) Tj", "(\\(printed\\)) Tj", "(world) Tj"] {
+        assert!(text.contains(word), "missing {word}");
+    }
+    assert!(!text.contains("( ) Tj"), "blank runs are not drawn");
+}
+
+#[test]
+fn long_documents_continue_on_further_pages_without_splitting_lines() {
+    let body = "<p>Paragraph of printed text.</p>".repeat(30);
+    let mut page = page_with_dom(&format!("<!doctype html><body>{body}</body>"));
+    let pdf = print_page(&mut page, &SMALL).expect("print should work");
+    assert!(page_count(&pdf) > 3, "expected several pages");
+
+    let mut primitives = Vec::new();
+    let mut page = page_with_dom(&format!("<!doctype html><body>{body}</body>"));
+    let style_output = page.build_style_phase_output().unwrap().unwrap();
+    let layout_output = layout_document(LayoutPhaseInput::from_style_output(
+        &style_output,
+        SMALL.content_width_px(),
+        &HelveticaMeasurer,
+        None,
+    ));
+    let artifact =
+        PaintArtifact::from_phase_input(PaintPhaseInput::new(&layout_output), &HelveticaMeasurer);
+    collect_primitives(artifact.tree().root(), &mut primitives);
+    let breaks = page_breaks(&primitives, SMALL.content_height_px());
+
+    assert_eq!(breaks.len(), page_count(&pdf));
+    for window in breaks.windows(2) {
+        assert_eq!(window[0].1, window[1].0, "pages must be contiguous");
+    }
+    for (_, bottom) in &breaks {
+        for primitive in &primitives {
+            if let PaintPrimitive::Text(text) = primitive {
+                let (start, end) = (text.rect.y, text.rect.y + text.rect.height);
+                assert!(
+                    !(start < *bottom && end > *bottom),
+                    "line {:?} cut by the break at {bottom}",
+                    text.text
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn empty_documents_still_produce_a_page() {
+    assert_eq!(page_breaks(&[], 100.0), [(0.0, 0.0)]);
+}
+
+#[test]
+fn non_ascii_text_is_win_ansi_encoded() {
+    let mut content = ContentStream::default();
+    content.text(0.0, 0.0, 12.0, [0.0; 3], "café – ✓");
+    let pdf = {
+        let mut document = PdfDocument::new(100.0, 100.0);
+        document.add_page(content);
+        document.finish()
+    };
+    assert!(String::from_utf8_lossy(&pdf).contains("(caf\\351 \\226 ?) Tj"));
+}
@@ -2,7 +2,7 @@
 
 use crate::form_controls::FormControlIndex;
 use crate::input_state::DocumentInputState;
-use crate::page::PageState;
+use crate::page::{LayoutJobRequest, LayoutOffload, PageState};
 use css::{ComputedStyleResolutionError, StylePhaseOutput};
 use egui::Ui;
 use gfx::input::PageAction;
@@ -37,6 +37,9 @@ pub(crate) struct PreparedPageFrame<'a> {
     pub(crate) style_output: StylePhaseOutput<'a>,
     pub(crate) page_background: Option<(u8, u8, u8, u8)>,
     pub(crate) work_plan: RenderWorkPlan,
+    /// Relayout handed to the layout runtime this frame, for the tab to send.
+    pub(crate) layout_job: Option<LayoutJobRequest>,
+    layout_deferred: bool,
     retained_layout_key_seed: RetainedLayoutKeySeed,
     retained_layout_artifact: Option<RetainedLayoutArtifact>,
     retained_paint_key_seed: RetainedPaintArtifactKeySeed,
@@ -45,6 +48,7 @@ pub(crate) struct PreparedPageFrame<'a> {
     style_dirty_before_frame: bool,
     base_url: Option<String>,
     form_controls: FormControlIndex,
+    zoom: f32,
 }
 
 #[derive(Default)]
@@ -67,24 +71,29 @@ impl PhaseReasonAccumulator {
     }
 }
 
-pub(crate) fn prepare_page_frame(
-    page: &mut PageState,
+pub(crate) fn prepare_page_frame<'a>(
+    page: &'a mut PageState,
     pending_work: PendingRenderWork,
-) -> Result<Option<PreparedPageFrame<'_>>, ComputedStyleResolutionError> {
+    offload: Option<LayoutOffload<'_>>,
+) -> Result<Option<PreparedPageFrame<'a>>, ComputedStyleResolutionError> {
     let style_dirty_before_frame = page.style_dirty_for_rendering();
     let base_url = page.base_url.clone();
     let form_controls = page.form_controls.clone();
+    let zoom = page.zoom();
 
-    let prepared_style = match page.prepare_style_phase_for_frame(&pending_work)? {
-        Some(prepared_style) => prepared_style,
-        None => return Ok(None),
-    };
+    let prepared_style =
+        match page.prepare_style_phase_for_frame(&pending_work, offload.as_ref())? {
+            Some(prepared_style) => prepared_style,
+            None => return Ok(None),
+        };
     let page_background = find_page_background_color(&prepared_style.style_output);
 
     Ok(Some(PreparedPageFrame {
         style_output: prepared_style.style_output,
         page_background,
         work_plan: prepared_style.work_plan,
+        layout_job: prepared_style.layout_job,
+        layout_deferred: prepared_style.layout_deferred,
         retained_layout_key_seed: prepared_style.retained_layout_key_seed,
         retained_layout_artifact: prepared_style.retained_layout_artifact,
         retained_paint_key_seed: prepared_style.retained_paint_key_seed,
@@ -93,17 +102,20 @@ pub(crate) fn prepare_page_frame(
         style_dirty_before_frame,
         base_url,
         form_controls,
+        zoom,
     }))
 }
 
 struct BrowserReplacedElementInfo<'a, R> {
     base_url: Option<&'a str>,
     resources: &'a R,
+    /// Page zoom; image intrinsic sizes scale with it like computed lengths.
+    zoom: f32,
 }
 
 impl<R: ImageProvider> layout::ReplacedElementInfoProvider for BrowserReplacedElementInfo<'_, R> {
     fn resolve_image_source(&self, source: &str) -> Option<String> {
-        crate::resources::resolve_image_source(self.base_url, source)
+        weburl::resolve_str(self.base_url, source)
     }
 
     fn intrinsic_for_img(
@@ -113,8 +125,8 @@ impl<R: ImageProvider> layout::ReplacedElementInfoProvider for BrowserReplacedEl
         let url = image.resolved_source()?;
         let (width, height) = self.resources.image_intrinsic_size_px(url)?;
         Some(layout::replaced::intrinsic::IntrinsicSize::from_w_h(
-            Some(width as f32),
-            Some(height as f32),
+            Some(width as f32 * self.zoom),
+            Some(height as f32 * self.zoom),
         ))
     }
 }
@@ -129,6 +141,8 @@ pub(crate) fn execute_prepared_page_frame<R: ImageProvider>(
         style_output,
         page_background: _page_background,
         work_plan,
+        layout_job: _,
+        layout_deferred,
         retained_layout_key_seed,
         retained_layout_artifact,
         retained_paint_key_seed,
@@ -137,10 +151,12 @@ pub(crate) fn execute_prepared_page_frame<R: ImageProvider>(
         style_dirty_before_frame,
         base_url,
         form_controls,
+        zoom,
     } = prepared;
     let replaced_info = BrowserReplacedElementInfo {
         base_url: base_url.as_deref(),
         resources,
+        zoom,
     };
     let repaint_policy = viewport_repaint_policy(&pending_work);
     let viewport_result = execute_viewport_frame(
@@ -165,6 +181,7 @@ pub(crate) fn execute_prepared_page_frame<R: ImageProvider>(
                 work_plan.relayout_execution,
                 super::work_plan::RelayoutExecution::ConservativeDocumentFallback { .. }
             ),
+            deferred: layout_deferred,
         })
         .with_retained_paint(ViewportRetainedPaint {
             retained: retained_paint_artifact.as_ref(),
@@ -219,9 +236,17 @@ pub(crate) fn execute_prepared_page_frame<R: ImageProvider>(
         trace.repaint_execution.scope,
         repaint_execution_scope_from_viewport(viewport_result.repaint_scope)
     );
-    let followup_render_request = viewport_result
-        .requested_followup_render
-        .then(|| render_invalidation_request(RenderInvalidationEntryPoint::InputStateChanged));
+    let followup_render_request = if viewport_result.requested_followup_relayout {
+        // Resized controls reach layout through the replaced-element info,
+        // like decoded image sizes, so they invalidate the same metadata.
+        Some(render_invalidation_request(
+            RenderInvalidationEntryPoint::ResourceStateChanged,
+        ))
+    } else {
+        viewport_result
+            .requested_followup_render
+            .then(|| render_invalidation_request(RenderInvalidationEntryPoint::InputStateChanged))
+    };
 
     OrchestratedFrameOutcome {
         action: viewport_result.action,
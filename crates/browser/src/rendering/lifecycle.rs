@@ -106,6 +106,10 @@ pub enum RetainedLayoutArtifactAction {
     ConservativeDocumentFallback,
     DiscardedForInvalidation,
     MaterializationFailedFallback,
+    /// An older artifact was drawn while the layout runtime works.
+    DeferredToRuntime,
+    /// The layout runtime delivered the artifact.
+    ComputedOffThread,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -630,6 +634,8 @@ fn retained_layout_artifact_action_debug_label(
         RetainedLayoutArtifactAction::MaterializationFailedFallback => {
             "materialization-failed-fallback"
         }
+        RetainedLayoutArtifactAction::DeferredToRuntime => "deferred-to-runtime",
+        RetainedLayoutArtifactAction::ComputedOffThread => "computed-off-thread",
     }
 }
 
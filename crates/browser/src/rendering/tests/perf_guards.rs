@@ -62,7 +62,7 @@ impl FrameHarness {
         pending_work: PendingRenderWork,
         viewport_width: f32,
     ) -> RenderFrameExecutionTrace {
-        let prepared = prepare_page_frame(page, pending_work)
+        let prepared = prepare_page_frame(page, pending_work, None)
             .expect("frame preparation should succeed")
             .expect("document should produce a frame");
         let ctx = Context::default();
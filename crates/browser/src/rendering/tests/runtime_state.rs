@@ -4,7 +4,7 @@ use crate::rendering::*;
 use crate::resources::ResourceManager;
 use egui::{CentralPanel, Context, Pos2, RawInput, Rect, Vec2};
 use gfx::paint::{PaintArtifact, PaintPhaseInput};
-use html::internal::Id;
+use html::{HtmlParseOptions, internal::Id, parse_document};
 use layout::{
     LayoutPhaseInput, RetainedLayoutFallbackReason, RetainedLayoutFrameAction,
     RetainedLayoutFrameResult,
@@ -457,7 +457,7 @@ fn paint_only_style_change_preserves_layout_and_plans_retained_layout_reuse() {
     page.mark_dom_changed_for_tests(RestyleHint::attributes_changed(vec![dirty_id]));
 
     let prepared = page
-        .prepare_style_phase_for_frame(&PendingRenderWork::default())
+        .prepare_style_phase_for_frame(&PendingRenderWork::default(), None)
         .expect("frame preparation should succeed")
         .expect("document should produce a frame");
     assert!(
@@ -523,7 +523,7 @@ fn layout_affecting_style_change_forces_paint_recompute_planning() {
     page.mark_dom_changed_for_tests(RestyleHint::attributes_changed(vec![dirty_id]));
 
     let prepared = page
-        .prepare_style_phase_for_frame(&PendingRenderWork::default())
+        .prepare_style_phase_for_frame(&PendingRenderWork::default(), None)
         .expect("frame preparation should succeed")
         .expect("document should produce a frame");
     assert!(
@@ -576,7 +576,7 @@ fn stacking_order_affecting_style_change_invalidates_retained_paint() {
     page.mark_dom_changed_for_tests(RestyleHint::attributes_changed(vec![dirty_id]));
 
     let prepared = page
-        .prepare_style_phase_for_frame(&PendingRenderWork::default())
+        .prepare_style_phase_for_frame(&PendingRenderWork::default(), None)
         .expect("frame preparation should succeed")
         .expect("document should produce a frame");
     assert!(
@@ -628,7 +628,7 @@ fn retained_paint_recomputes_when_actual_layout_reuse_key_mismatches() {
         .paint_artifacts
         .key
         .expect("seeded retained paint should have a key");
-    let prepared = prepare_page_frame(&mut page, PendingRenderWork::default())
+    let prepared = prepare_page_frame(&mut page, PendingRenderWork::default(), None)
         .expect("frame preparation should succeed")
         .expect("document should produce a frame");
     assert_eq!(
@@ -747,7 +747,7 @@ fn paint_only_style_change_preserves_existing_viewport_layout_dirty_entry() {
     page.mark_dom_changed_for_tests(RestyleHint::attributes_changed(vec![dirty_id]));
 
     let prepared = page
-        .prepare_style_phase_for_frame(&PendingRenderWork::default())
+        .prepare_style_phase_for_frame(&PendingRenderWork::default(), None)
         .expect("frame preparation should succeed")
         .expect("document should produce a frame");
 
@@ -823,7 +823,7 @@ fn layout_affecting_style_change_marks_layout_dirty() {
     page.mark_dom_changed_for_tests(RestyleHint::attributes_changed(vec![dirty_id]));
 
     let prepared = page
-        .prepare_style_phase_for_frame(&PendingRenderWork::default())
+        .prepare_style_phase_for_frame(&PendingRenderWork::default(), None)
         .expect("frame preparation should succeed")
         .expect("document should produce a frame");
     assert!(
@@ -1543,6 +1543,108 @@ fn stylesheet_update_discards_retained_style_artifacts() {
     assert_eq!(refreshed.style_artifacts.stats.discard_count, 1);
 }
 
+#[test]
+fn color_scheme_change_restyles_pages_with_prefers_color_scheme_rules() {
+    let mut page = page_with_dom(concat!(
+        "<!doctype html><html><head><style>p { color: red; } ",
+        "@media (prefers-color-scheme: dark) { p { color: blue; } }",
+        "</style></head><body><p>Hello</p></body></html>",
+    ));
+    let light = style_output_for_test(&mut page);
+    assert_eq!(styled_element_color(light.root(), "p"), (255, 0, 0, 255));
+    drop(light);
+
+    assert!(page.set_color_scheme(css::ColorScheme::Dark).is_some());
+    assert!(page.set_color_scheme(css::ColorScheme::Dark).is_none());
+    let dark = style_output_for_test(&mut page);
+    assert_eq!(styled_element_color(dark.root(), "p"), (0, 0, 255, 255));
+    drop(dark);
+
+    let mut plain = page_with_dom(
+        "<!doctype html><html><head><style>p { color: red; }</style></head><body><p>Hello</p></body></html>",
+    );
+    assert!(plain.set_color_scheme(css::ColorScheme::Dark).is_none());
+}
+
+#[test]
+fn viewport_and_zoom_changes_restyle_only_when_a_breakpoint_is_crossed() {
+    let mut page = page_with_dom(concat!(
+        "<!doctype html><html><head><style>p { color: red; } ",
+        "@media (min-width: 600px) { p { color: blue; } }",
+        "</style></head><body><p>Hello</p></body></html>",
+    ));
+    assert!(page.set_viewport_size(400.0, 300.0).is_none());
+    let narrow = style_output_for_test(&mut page);
+    assert_eq!(styled_element_color(narrow.root(), "p"), (255, 0, 0, 255));
+    drop(narrow);
+
+    assert!(page.set_viewport_size(800.0, 300.0).is_some());
+    let wide = style_output_for_test(&mut page);
+    assert_eq!(styled_element_color(wide.root(), "p"), (0, 0, 255, 255));
+    drop(wide);
+    assert!(page.set_viewport_size(820.0, 310.0).is_none());
+
+    // Zooming in narrows the viewport in CSS pixels.
+    assert!(page.set_zoom(2.0).is_some());
+    let zoomed = style_output_for_test(&mut page);
+    assert_eq!(styled_element_color(zoomed.root(), "p"), (255, 0, 0, 255));
+    drop(zoomed);
+
+    // The environment outlives the document.
+    assert!(page.set_color_scheme(css::ColorScheme::Dark).is_none());
+    assert!(page.set_zoom(1.0).is_some());
+    let output = parse_document(
+        concat!(
+            "<!doctype html><html><head><style>p { color: red; } ",
+            "@media (min-width: 600px) and (prefers-color-scheme: dark) ",
+            "{ p { color: blue; } }</style></head><body><p>Hello</p></body></html>",
+        ),
+        HtmlParseOptions::default(),
+    )
+    .expect("parse should work");
+    page.start_nav("https://example.com/next.html");
+    let _ = page.replace_dom(Box::new(output.document), RestyleHint::document_replaced());
+    let _ = page.reconcile_document_stylesheets();
+    let next = style_output_for_test(&mut page);
+    assert_eq!(styled_element_color(next.root(), "p"), (0, 0, 255, 255));
+}
+
+#[test]
+fn viewport_resize_restyles_pages_that_use_viewport_units() {
+    let mut page = page_with_dom(concat!(
+        "<!doctype html><html><head><style>div { width: 50vw; height: 10vh; }",
+        "</style></head><body><div></div></body></html>",
+    ));
+    let _ = page.set_viewport_size(400.0, 300.0);
+    let narrow = style_output_for_test(&mut page);
+    let div = find_styled_element(narrow.root(), "div").expect("styled div");
+    assert_eq!(
+        div.style.width(),
+        Some(css::LengthPercentage::Length(css::Length::Px(200.0)))
+    );
+    assert_eq!(
+        div.style.height(),
+        Some(css::LengthPercentage::Length(css::Length::Px(30.0)))
+    );
+    drop(narrow);
+
+    assert!(page.set_viewport_size(800.0, 300.0).is_some());
+    let wide = style_output_for_test(&mut page);
+    let div = find_styled_element(wide.root(), "div").expect("styled div");
+    assert_eq!(
+        div.style.width(),
+        Some(css::LengthPercentage::Length(css::Length::Px(400.0)))
+    );
+    drop(wide);
+
+    let mut plain = page_with_dom(
+        "<!doctype html><html><head><style>div { width: 50%; }</style></head><body><div></div></body></html>",
+    );
+    let _ = plain.set_viewport_size(400.0, 300.0);
+    drop(style_output_for_test(&mut plain));
+    assert!(plain.set_viewport_size(800.0, 300.0).is_none());
+}
+
 #[test]
 fn text_mutation_dirties_layout_without_invalidating_computed_style() {
     let mut page = page_with_dom(
@@ -500,7 +500,7 @@ fn prepare_page_frame_carries_derived_work_plan_before_execution() {
         RenderInvalidationEntryPoint::ViewportChanged,
     ));
 
-    let prepared = prepare_page_frame(&mut page, pending_work)
+    let prepared = prepare_page_frame(&mut page, pending_work, None)
         .expect("frame preparation should succeed")
         .expect("document should produce a prepared frame");
     let plan = &prepared.work_plan;
@@ -1,11 +1,11 @@
 use std::collections::HashMap;
-use std::sync::mpsc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use app_api::RepaintHandle;
+use bus::DecodedImage;
 use egui::{ColorImage, TextureHandle, TextureId, TextureOptions};
 use gfx::paint::ImageProvider;
 use tools::common::MAX_IMAGE_BYTES;
-use url::Url;
 
 const MAX_IMAGE_PIXELS: usize = 16_777_216; // 4096 * 4096
 
@@ -44,11 +44,6 @@ pub enum ImageState {
     Error { id: ImageId, error: String },
 }
 
-struct DecodedImage {
-    size_px: [usize; 2],
-    rgba: Vec<u8>,
-}
-
 struct DecodeResult {
     id: ImageId,
     decoded: Result<DecodedImage, String>,
@@ -56,16 +51,65 @@ struct DecodeResult {
 
 enum EntryState {
     Loading,
-    Decoding,
+    /// The response body is with the image runtime; it is kept so the image
+    /// can be saved as-is.
+    Decoding {
+        encoded: Arc<[u8]>,
+    },
     Ready {
         texture: TextureHandle,
         size_px: [usize; 2],
+        encoded: Arc<[u8]>,
+        /// Playback state if the image is animated; `texture` is its first
+        /// frame.
+        animation: Option<Animation>,
     },
     Error {
         error: String,
     },
 }
 
+/// Playback of an animated image: a texture per frame, shown in turn.
+struct Animation {
+    frames: Vec<(TextureHandle, Duration)>,
+    current: usize,
+    /// When the current frame gives way to the next.
+    next_frame_at: Instant,
+    /// Paused images keep their frame until a document requests them again.
+    playing: bool,
+}
+
+impl Animation {
+    fn texture_id(&self) -> TextureId {
+        self.frames[self.current].0.id()
+    }
+
+    fn resume(&mut self, now: Instant) {
+        if !self.playing {
+            self.playing = true;
+            self.next_frame_at = now + self.frames[self.current].1;
+        }
+    }
+
+    /// Move to the frame that shows at `now`.
+    fn advance(&mut self, now: Instant) {
+        let cycle: Duration = self.frames.iter().map(|(_, delay)| *delay).sum();
+        if now < self.next_frame_at || cycle.is_zero() {
+            return;
+        }
+        if now.duration_since(self.next_frame_at) >= cycle {
+            // Frames were not drawn for a whole cycle (say, the tab was in
+            // the background); resume from the next frame instead of
+            // catching up.
+            self.next_frame_at = now;
+        }
+        while self.next_frame_at <= now {
+            self.current = (self.current + 1) % self.frames.len();
+            self.next_frame_at += self.frames[self.current].1;
+        }
+    }
+}
+
 struct ImageEntry {
     url: String,
     state: EntryState,
@@ -83,19 +127,17 @@ pub struct ResourceManager {
     next_image_id: u64,
     image_id_by_url: HashMap<String, ImageId>,
     images: HashMap<ImageId, ImageEntry>,
-    decode_done_rx: mpsc::Receiver<DecodeResult>,
-    decode_done_tx: mpsc::Sender<DecodeResult>,
+    /// Decoded images waiting for their texture upload.
+    decoded: Vec<DecodeResult>,
 }
 
 impl ResourceManager {
     pub fn new() -> Self {
-        let (decode_done_tx, decode_done_rx) = mpsc::channel();
         Self {
             next_image_id: 1,
             image_id_by_url: HashMap::new(),
             images: HashMap::new(),
-            decode_done_rx,
-            decode_done_tx,
+            decoded: Vec::new(),
         }
     }
 
@@ -107,6 +149,17 @@ impl ResourceManager {
         F: FnMut(String),
     {
         if let Some(id) = self.image_id_by_url.get(&url).copied() {
+            if let Some(ImageEntry {
+                state:
+                    EntryState::Ready {
+                        animation: Some(animation),
+                        ..
+                    },
+                ..
+            }) = self.images.get_mut(&id)
+            {
+                animation.resume(Instant::now());
+            }
             return id;
         }
 
@@ -132,10 +185,17 @@ impl ResourceManager {
 
         match &entry.state {
             EntryState::Loading => ImageState::Loading { id },
-            EntryState::Decoding => ImageState::Decoding { id },
-            EntryState::Ready { texture, size_px } => ImageState::Ready(ReadyImage {
+            EntryState::Decoding { .. } => ImageState::Decoding { id },
+            EntryState::Ready {
+                texture,
+                size_px,
+                animation,
+                ..
+            } => ImageState::Ready(ReadyImage {
                 id,
-                texture_id: texture.id(),
+                texture_id: animation
+                    .as_ref()
+                    .map_or_else(|| texture.id(), Animation::texture_id),
                 size_px: *size_px,
             }),
             EntryState::Error { error } => ImageState::Error {
@@ -161,6 +221,15 @@ impl ResourceManager {
         }
     }
 
+    /// Encoded bytes of a decoded image, as received from the network.
+    pub fn encoded_image(&self, url: &str) -> Option<&[u8]> {
+        let id = self.image_id_by_url.get(url).copied()?;
+        match &self.images.get(&id)?.state {
+            EntryState::Ready { encoded, .. } => Some(&encoded[..]),
+            _ => None,
+        }
+    }
+
     pub fn on_network_chunk(&mut self, url: &str, bytes: &[u8]) {
         let Some(id) = self.image_id_by_url.get(url).copied() else {
             return;
@@ -184,7 +253,12 @@ impl ResourceManager {
         entry.bytes.extend_from_slice(bytes);
     }
 
-    pub fn on_network_done(&mut self, url: &str, repaint: Option<RepaintHandle>) {
+    /// Hands the complete response body to `start_decode`, which should send
+    /// a `CoreCommand::DecodeImage` to the image runtime.
+    pub fn on_network_done<F>(&mut self, url: &str, start_decode: F)
+    where
+        F: FnOnce(String, Arc<[u8]>),
+    {
         let Some(id) = self.image_id_by_url.get(url).copied() else {
             return;
         };
@@ -196,18 +270,27 @@ impl ResourceManager {
             return;
         }
 
-        let bytes = std::mem::take(&mut entry.bytes);
-        entry.state = EntryState::Decoding;
+        let encoded: Arc<[u8]> = std::mem::take(&mut entry.bytes).into();
+        entry.state = EntryState::Decoding {
+            encoded: Arc::clone(&encoded),
+        };
+        start_decode(entry.url.clone(), encoded);
+    }
 
-        let tx = self.decode_done_tx.clone();
-        let repaint = repaint.clone();
-        std::thread::spawn(move || {
-            let decoded = decode_image(bytes);
-            let _ = tx.send(DecodeResult { id, decoded });
-            if let Some(r) = repaint {
-                r.request_now();
-            }
-        });
+    /// Takes the image runtime's answer for `url`; the texture is uploaded by
+    /// the next `pump`. Returns false if the image is not waiting for one.
+    pub fn on_image_decoded(&mut self, url: &str, decoded: Result<DecodedImage, String>) -> bool {
+        let Some(id) = self.image_id_by_url.get(url).copied() else {
+            return false;
+        };
+        let Some(entry) = self.images.get(&id) else {
+            return false;
+        };
+        if !matches!(entry.state, EntryState::Decoding { .. }) {
+            return false;
+        }
+        self.decoded.push(DecodeResult { id, decoded });
+        true
     }
 
     pub fn on_network_error(&mut self, url: &str, error: String) {
@@ -227,19 +310,23 @@ impl ResourceManager {
         entry.bytes.clear();
     }
 
-    /// Drains completed decode jobs and uploads textures via egui.
+    /// Uploads textures for decoded images via egui.
     /// Returns true if any resource state changed (useful to request repaint).
     pub fn pump(&mut self, egui_ctx: &egui::Context) -> bool {
         let mut changed = false;
 
-        while let Ok(msg) = self.decode_done_rx.try_recv() {
+        for msg in std::mem::take(&mut self.decoded) {
             let Some(entry) = self.images.get_mut(&msg.id) else {
                 continue;
             };
+            let EntryState::Decoding { encoded } = &entry.state else {
+                continue;
+            };
+            let encoded = Arc::clone(encoded);
 
             match msg.decoded {
                 Ok(decoded) => {
-                    let size = decoded.size_px;
+                    let size = decoded.size;
                     let pixel_count = size[0].saturating_mul(size[1]);
                     if pixel_count == 0 || pixel_count > MAX_IMAGE_PIXELS {
                         entry.state = EntryState::Error {
@@ -258,10 +345,30 @@ impl ResourceManager {
                         image,
                         TextureOptions::LINEAR,
                     );
+                    let animation = decoded.is_animated().then(|| {
+                        let mut frames = vec![(texture.clone(), decoded.delay)];
+                        for (index, frame) in decoded.frames.iter().enumerate() {
+                            let image = ColorImage::from_rgba_unmultiplied(size, &frame.rgba);
+                            let texture = egui_ctx.load_texture(
+                                format!("img:{}#{}", entry.url, index + 1),
+                                image,
+                                TextureOptions::LINEAR,
+                            );
+                            frames.push((texture, frame.delay));
+                        }
+                        Animation {
+                            frames,
+                            current: 0,
+                            next_frame_at: Instant::now() + decoded.delay,
+                            playing: true,
+                        }
+                    });
 
                     entry.state = EntryState::Ready {
                         texture,
                         size_px: size,
+                        encoded,
+                        animation,
                     };
                     changed = true;
                 }
@@ -275,6 +382,38 @@ impl ResourceManager {
         changed
     }
 
+    /// Show the frame of each playing animated image that is due at `now`.
+    /// Returns when the next frame is due, if any image is playing.
+    pub fn advance_animations(&mut self, now: Instant) -> Option<Instant> {
+        self.images
+            .values_mut()
+            .filter_map(|entry| match &mut entry.state {
+                EntryState::Ready {
+                    animation: Some(animation),
+                    ..
+                } if animation.playing => {
+                    animation.advance(now);
+                    Some(animation.next_frame_at)
+                }
+                _ => None,
+            })
+            .min()
+    }
+
+    /// Pause every animated image, as the document showing them is gone;
+    /// [`Self::request_image`] resumes one.
+    pub fn pause_animations(&mut self) {
+        for entry in self.images.values_mut() {
+            if let EntryState::Ready {
+                animation: Some(animation),
+                ..
+            } = &mut entry.state
+            {
+                animation.playing = false;
+            }
+        }
+    }
+
     fn forget_image(&mut self, url: &str) {
         let Some(id) = self.image_id_by_url.remove(url) else {
             return;
@@ -289,21 +428,6 @@ impl Default for ResourceManager {
     }
 }
 
-fn decode_image(bytes: Vec<u8>) -> Result<DecodedImage, String> {
-    if bytes.is_empty() {
-        return Err("empty image response".to_string());
-    }
-
-    let img = image::load_from_memory(&bytes).map_err(|e| e.to_string())?;
-    let rgba = img.to_rgba8();
-    let (w, h) = rgba.dimensions();
-
-    Ok(DecodedImage {
-        size_px: [w as usize, h as usize],
-        rgba: rgba.into_raw(),
-    })
-}
-
 impl ImageProvider for ResourceManager {
     fn image_state_by_url(&self, url: &str) -> gfx::paint::ImageState {
         match ResourceManager::image_state_by_url(self, url) {
@@ -323,80 +447,77 @@ impl ImageProvider for ResourceManager {
     }
 }
 
-pub(crate) fn resolve_image_source(base_url: Option<&str>, source: &str) -> Option<String> {
-    let source = strip_surrounding_html_ascii_whitespace(source);
-    if source.is_empty() {
-        return None;
-    }
-    if let Ok(url) = Url::parse(source) {
-        return Some(url.to_string());
-    }
-    Url::parse(base_url?)
-        .ok()?
-        .join(source)
-        .ok()
-        .map(Into::into)
-}
-
-fn strip_surrounding_html_ascii_whitespace(value: &str) -> &str {
-    value.trim_matches(|character| {
-        matches!(
-            character,
-            '\u{0009}' | '\u{000A}' | '\u{000C}' | '\u{000D}' | '\u{0020}'
-        )
-    })
-}
-
 #[cfg(test)]
 mod tests {
-    use super::{
-        EntryState, ImageState, ResourceManager, resolve_image_source,
-        strip_surrounding_html_ascii_whitespace,
-    };
+    use super::{EntryState, ImageState, ResourceManager};
+    use bus::{AnimationFrame, DecodedImage};
+    use std::time::{Duration, Instant};
     use tools::common::MAX_IMAGE_BYTES;
 
+    fn ready_texture(resources: &ResourceManager, url: &str) -> egui::TextureId {
+        match resources.image_state_by_url(url) {
+            ImageState::Ready(image) => image.texture_id,
+            other => panic!("image not ready: {other:?}"),
+        }
+    }
+
     #[test]
-    fn browser_resource_provider_resolves_typed_image_sources() {
-        assert_eq!(
-            resolve_image_source(
-                Some("https://example.com/docs/page.html"),
-                "\t\n\u{000C}\r ../hero.png \t\n\u{000C}\r"
-            )
-            .as_deref(),
-            Some("https://example.com/hero.png")
-        );
-        assert_eq!(
-            resolve_image_source(
-                Some("https://ignored.example/"),
-                " \thttps://cdn.example/hero.png\r\n"
-            )
-            .as_deref(),
-            Some("https://cdn.example/hero.png")
-        );
-        assert_eq!(resolve_image_source(Some("https://example.com/"), ""), None);
-        assert_eq!(
-            resolve_image_source(Some("https://example.com/"), " \t\n\u{000C}\r"),
-            None
-        );
-        assert_eq!(resolve_image_source(None, "relative.png"), None);
+    fn animated_images_cycle_through_their_frames_as_they_fall_due() {
+        let mut resources = ResourceManager::new();
+        let url = "https://example.com/spinner.gif".to_string();
+        resources.request_image(url.clone(), |_| {});
+        resources.on_network_chunk(&url, b"GIF89a");
+        resources.on_network_done(&url, |_, _| {});
+        let mut image = DecodedImage::still(vec![255; 4], [1, 1]);
+        image.delay = Duration::from_millis(100);
+        image.frames.push(AnimationFrame {
+            rgba: vec![0; 4],
+            delay: Duration::from_millis(50),
+        });
+        assert!(resources.on_image_decoded(&url, Ok(image)));
+
+        let uploaded = Instant::now();
+        assert!(resources.pump(&egui::Context::default()));
+        let first = ready_texture(&resources, &url);
+        let next = resources
+            .advance_animations(uploaded)
+            .expect("animated images schedule their next frame");
+        assert!(next > uploaded && next <= Instant::now() + Duration::from_millis(100));
+        assert_eq!(ready_texture(&resources, &url), first);
+
+        let after_first = resources.advance_animations(next).expect("playback loops");
+        let second = ready_texture(&resources, &url);
+        assert_ne!(second, first, "the second frame is due");
+        assert_eq!(after_first, next + Duration::from_millis(50));
+
+        resources.advance_animations(after_first);
+        assert_eq!(ready_texture(&resources, &url), first, "playback loops");
+
+        resources.pause_animations();
         assert_eq!(
-            resolve_image_source(
-                Some("https://ignored.example/base/"),
-                "https://cdn.example/hero.png"
-            )
-            .as_deref(),
-            Some("https://cdn.example/hero.png")
+            resources.advance_animations(after_first + Duration::from_secs(1)),
+            None,
+            "paused images schedule no frames"
         );
+        resources.request_image(url.clone(), |_| panic!("cached images are not refetched"));
+        assert!(resources.advance_animations(Instant::now()).is_some());
     }
 
     #[test]
-    fn image_source_preprocessing_strips_only_html_ascii_whitespace() {
-        let non_ascii_whitespace = "\u{00A0}https://cdn.example/hero.png\u{00A0}";
-        assert_eq!(
-            strip_surrounding_html_ascii_whitespace(non_ascii_whitespace),
-            non_ascii_whitespace
-        );
-        assert_eq!(resolve_image_source(None, non_ascii_whitespace), None);
+    fn still_images_schedule_no_frames() {
+        let mut resources = ResourceManager::new();
+        let url = "https://example.com/still.png".to_string();
+        resources.request_image(url.clone(), |_| {});
+        resources.on_network_chunk(&url, &[1]);
+        resources.on_network_done(&url, |_, _| {});
+        resources.on_image_decoded(&url, Ok(DecodedImage::still(vec![255; 4], [1, 1])));
+        resources.pump(&egui::Context::default());
+
+        assert!(matches!(
+            resources.image_state_by_url(&url),
+            ImageState::Ready(_)
+        ));
+        assert_eq!(resources.advance_animations(Instant::now()), None);
     }
 
     #[test]
@@ -428,7 +549,7 @@ mod tests {
             ImageState::Error { ref error, .. } if error.contains("exceeded byte limit")
         ));
 
-        resources.on_network_done(&url, None);
+        resources.on_network_done(&url, |_, _| panic!("errored image must not decode"));
         assert!(matches!(
             resources.image_state(image_id),
             ImageState::Error { ref error, .. } if error.contains("exceeded byte limit")
@@ -0,0 +1,143 @@
+//! Animated page scrolling.
+//!
+//! Page input (wheel, scrolling keys, find-in-page, fragment navigation) only
+//! records where the page should end up in `InteractionState::scroll_target`.
+//! The tab steps this animation once per frame to move the viewport there and
+//! asks for repaints until it arrives.
+
+use gfx::input::InteractionState;
+use std::time::{Duration, Instant};
+
+/// How long one animated scroll takes, however far it goes.
+const SCROLL_ANIMATION_DURATION: Duration = Duration::from_millis(150);
+
+/// Delay before the next frame of a running scroll animation.
+pub(crate) const SCROLL_ANIMATION_FRAME: Duration = Duration::from_millis(16);
+
+#[derive(Debug, Clone, Copy)]
+struct ActiveScroll {
+    from: f32,
+    to: f32,
+    started_at: Instant,
+}
+
+impl ActiveScroll {
+    /// Progress in `0.0..=1.0` at `now`.
+    fn progress(&self, now: Instant) -> f32 {
+        let elapsed = now.saturating_duration_since(self.started_at);
+        (elapsed.as_secs_f32() / SCROLL_ANIMATION_DURATION.as_secs_f32()).min(1.0)
+    }
+
+    fn offset_at(&self, now: Instant) -> f32 {
+        // Ease out: move quickly at first, then settle onto the target.
+        let eased = 1.0 - (1.0 - self.progress(now)).powi(3);
+        self.from + (self.to - self.from) * eased
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct ScrollAnimation {
+    active: Option<ActiveScroll>,
+    /// Jump straight to scroll targets instead of animating.
+    instant: bool,
+}
+
+impl ScrollAnimation {
+    pub(crate) fn set_instant(&mut self, instant: bool) {
+        self.instant = instant;
+    }
+
+    pub(crate) fn stop(&mut self) {
+        self.active = None;
+    }
+
+    /// Move the page one frame towards `interaction.scroll_target` by setting
+    /// the offset the viewport applies next. On arrival the target is cleared
+    /// and left as a scroll restore, so a target below the part of the
+    /// document loaded so far is still reached once the rest streams in.
+    pub(crate) fn step(&mut self, interaction: &mut InteractionState, now: Instant) {
+        let Some(to) = interaction.scroll_target else {
+            self.active = None;
+            return;
+        };
+        let active = match self.active {
+            Some(active) if active.to == to => active,
+            // A new target starts from wherever the page is now, including
+            // part way through the previous animation.
+            current => ActiveScroll {
+                from: current.map_or(interaction.scroll_y, |active| active.offset_at(now)),
+                to,
+                started_at: now,
+            },
+        };
+        if self.instant || active.progress(now) >= 1.0 {
+            self.active = None;
+            interaction.scroll_target = None;
+            interaction.pending_scroll_y = Some(to);
+            return;
+        }
+        self.active = Some(active);
+        interaction.pending_scroll_y = Some(active.offset_at(now));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interaction_at(scroll_y: f32, target: f32) -> InteractionState {
+        let mut interaction = InteractionState::default();
+        interaction.scroll_y = scroll_y;
+        interaction.scroll_target = Some(target);
+        interaction
+    }
+
+    #[test]
+    fn scrolls_ease_towards_the_target_and_finish_as_a_restore() {
+        let start = Instant::now();
+        let mut animation = ScrollAnimation::default();
+        let mut interaction = interaction_at(100.0, 500.0);
+
+        animation.step(&mut interaction, start);
+        assert_eq!(interaction.pending_scroll_y, Some(100.0));
+
+        animation.step(&mut interaction, start + SCROLL_ANIMATION_DURATION / 2);
+        let halfway = interaction.pending_scroll_y.unwrap();
+        assert!(halfway > 300.0 && halfway < 500.0, "{halfway}");
+        assert_eq!(interaction.scroll_target, Some(500.0));
+
+        animation.step(&mut interaction, start + SCROLL_ANIMATION_DURATION);
+        assert_eq!(interaction.pending_scroll_y, Some(500.0));
+        assert_eq!(interaction.scroll_target, None);
+    }
+
+    #[test]
+    fn a_new_target_continues_from_the_animated_offset() {
+        let start = Instant::now();
+        let mut animation = ScrollAnimation::default();
+        let mut interaction = interaction_at(0.0, 400.0);
+        animation.step(&mut interaction, start);
+        animation.step(&mut interaction, start + SCROLL_ANIMATION_DURATION / 2);
+        let halfway = interaction.pending_scroll_y.unwrap();
+
+        interaction.scroll_target = Some(800.0);
+        let later = start + SCROLL_ANIMATION_DURATION * 3 / 4;
+        animation.step(&mut interaction, later);
+        let from = interaction.pending_scroll_y.unwrap();
+        assert!(from > halfway && from < 400.0, "{from}");
+
+        animation.step(&mut interaction, later + SCROLL_ANIMATION_DURATION);
+        assert_eq!(interaction.pending_scroll_y, Some(800.0));
+    }
+
+    #[test]
+    fn instant_scrolling_jumps_to_the_target() {
+        let mut animation = ScrollAnimation::default();
+        animation.set_instant(true);
+        let mut interaction = interaction_at(0.0, 250.0);
+
+        animation.step(&mut interaction, Instant::now());
+        assert_eq!(interaction.pending_scroll_y, Some(250.0));
+        assert_eq!(interaction.scroll_target, None);
+    }
+}
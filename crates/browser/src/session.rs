@@ -0,0 +1,216 @@
+//! Session restore: open tabs with their history stacks and scroll offsets.
+//!
+//! The shell snapshots its tabs every frame and hands the snapshot to
+//! [`SessionStore::save`], which writes it to `session.tsv` in the user data
+//! directory when it changed. Writes are throttled because scrolling changes
+//! the snapshot every frame; [`SessionStore::flush`] writes unconditionally
+//! and is used on exit.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::user_data::{escape_field, unescape_field, user_data_dir, write_atomically};
+
+const SESSION_FILE: &str = "session.tsv";
+const SAVE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Navigation state of one tab.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TabSession {
+    pub history: Vec<String>,
+    /// Vertical scroll offset per `history` entry.
+    pub history_scroll: Vec<f32>,
+    pub history_index: usize,
+}
+
+impl TabSession {
+    fn is_valid(&self) -> bool {
+        !self.history.is_empty()
+            && self.history.len() == self.history_scroll.len()
+            && self.history_index < self.history.len()
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Session {
+    pub tabs: Vec<TabSession>,
+    pub active: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct SessionStore {
+    /// Backing file; `None` keeps the store in memory only.
+    path: Option<PathBuf>,
+    saved: Option<Session>,
+    pending: Option<Session>,
+    last_write: Option<Instant>,
+}
+
+impl SessionStore {
+    /// Create a store that never touches disk.
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Store backed by `session.tsv` in the user data directory.
+    pub fn load_default() -> Self {
+        match user_data_dir() {
+            Some(dir) => Self::load(dir.join(SESSION_FILE)),
+            None => Self::in_memory(),
+        }
+    }
+
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: Some(path.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Read the last saved session. Missing or malformed files yield `None`.
+    pub fn restore(&mut self) -> Option<Session> {
+        let text = fs::read_to_string(self.path.as_ref()?).ok()?;
+        let session = parse(&text).filter(|session| !session.tabs.is_empty())?;
+        self.saved = Some(session.clone());
+        Some(session)
+    }
+
+    /// Record `session`, writing it out if it changed and the last write is
+    /// older than the save interval. Returns the delay after which a deferred
+    /// change should be saved, if one is pending.
+    pub fn save(&mut self, session: Session) -> io::Result<Option<Duration>> {
+        if self.saved.as_ref() == Some(&session) {
+            self.pending = None;
+            return Ok(None);
+        }
+        self.pending = Some(session);
+        if let Some(elapsed) = self.last_write.map(|at| at.elapsed())
+            && elapsed < SAVE_INTERVAL
+        {
+            return Ok(Some(SAVE_INTERVAL - elapsed));
+        }
+        self.flush()?;
+        Ok(None)
+    }
+
+    /// Write any pending session immediately.
+    pub fn flush(&mut self) -> io::Result<()> {
+        let Some(session) = self.pending.take() else {
+            return Ok(());
+        };
+        if let Some(path) = &self.path {
+            write_atomically(path, &serialize(&session))?;
+        }
+        self.last_write = Some(Instant::now());
+        self.saved = Some(session);
+        Ok(())
+    }
+}
+
+// Line-based format:
+//   active \t <tab index>
+//   tab \t <history index>
+//   entry \t <scroll y> \t <url>
+// `entry` lines belong to the preceding `tab` line.
+
+fn serialize(session: &Session) -> String {
+    let mut out = format!("active\t{}\n", session.active);
+    for tab in &session.tabs {
+        out.push_str(&format!("tab\t{}\n", tab.history_index));
+        for (url, scroll_y) in tab.history.iter().zip(&tab.history_scroll) {
+            out.push_str(&format!("entry\t{scroll_y}\t{}\n", escape_field(url)));
+        }
+    }
+    out
+}
+
+fn parse(text: &str) -> Option<Session> {
+    let mut session = Session::default();
+    for line in text.lines() {
+        let mut fields = line.split('\t');
+        match fields.next()? {
+            "active" => session.active = fields.next()?.parse().ok()?,
+            "tab" => session.tabs.push(TabSession {
+                history: Vec::new(),
+                history_scroll: Vec::new(),
+                history_index: fields.next()?.parse().ok()?,
+            }),
+            "entry" => {
+                let tab = session.tabs.last_mut()?;
+                let scroll_y: f32 = fields.next()?.parse().ok()?;
+                tab.history_scroll
+                    .push(if scroll_y.is_finite() { scroll_y } else { 0.0 });
+                tab.history.push(unescape_field(fields.next()?));
+            }
+            _ => return None,
+        }
+    }
+    session.tabs.retain(TabSession::is_valid);
+    session.active = session.active.min(session.tabs.len().saturating_sub(1));
+    Some(session)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tab(history: &[&str], scroll: &[f32], index: usize) -> TabSession {
+        TabSession {
+            history: history.iter().map(|url| url.to_string()).collect(),
+            history_scroll: scroll.to_vec(),
+            history_index: index,
+        }
+    }
+
+    #[test]
+    fn serialization_round_trips_tabs_history_and_scroll() {
+        let session = Session {
+            tabs: vec![
+                tab(
+                    &["https://a.test/", "https://a.test/\tnext"],
+                    &[0.0, 120.5],
+                    1,
+                ),
+                tab(&["file:///tmp/x.html"], &[40.0], 0),
+            ],
+            active: 1,
+        };
+        assert_eq!(parse(&serialize(&session)), Some(session));
+    }
+
+    #[test]
+    fn invalid_tabs_are_dropped_and_active_is_clamped() {
+        let parsed = parse("active\t5\ntab\t3\nentry\t0\thttps://a.test/\ntab\t0\n").unwrap();
+        assert!(parsed.tabs.is_empty());
+        assert_eq!(parsed.active, 0);
+        assert_eq!(parse("garbage\n"), None);
+    }
+
+    #[test]
+    fn save_writes_changes_and_restore_reads_them_back() {
+        let dir = std::env::temp_dir().join(format!("borrowser-session-{}", std::process::id()));
+        let path = dir.join(SESSION_FILE);
+        let _ = fs::remove_dir_all(&dir);
+
+        let session = Session {
+            tabs: vec![tab(&["https://a.test/"], &[10.0], 0)],
+            active: 0,
+        };
+        let mut store = SessionStore::load(&path);
+        assert_eq!(store.restore(), None);
+        assert_eq!(store.save(session.clone()).unwrap(), None);
+
+        // A change right after a write is deferred until the interval passes.
+        let mut scrolled = session.clone();
+        scrolled.tabs[0].history_scroll[0] = 50.0;
+        assert!(store.save(scrolled.clone()).unwrap().is_some());
+        assert_eq!(SessionStore::load(&path).restore(), Some(session));
+
+        store.flush().unwrap();
+        assert_eq!(SessionStore::load(&path).restore(), Some(scrolled));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
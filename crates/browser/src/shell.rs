@@ -1,15 +1,39 @@
 use egui::{
     Align2, Color32, Context, CornerRadius, FontId, Frame, Margin, Rect, ScrollArea, Sense,
-    TopBottomPanel, Ui, pos2, scroll_area::ScrollBarVisibility, vec2,
+    Spinner, TextEdit, TopBottomPanel, Ui, gui_zoom::kb_shortcuts, pos2,
+    scroll_area::ScrollBarVisibility, vec2,
 };
+use std::path::PathBuf;
 use std::sync::mpsc;
 
 use app_api::{RepaintHandle, UiApp};
-use bus::{CoreCommand, CoreEvent};
+use bus::{CoreCommand, CoreEvent, ParseLimits};
 use core_types::{BrowserInput, TabId};
-use gfx::ui::toolbar::navigation_widgets;
-
-use crate::tab::Tab;
+use css::ColorScheme;
+use gfx::ui::toolbar::{NavigationState, UrlSuggestion, navigation_widgets};
+
+use crate::bookmarks::{Bookmark, BookmarkStore};
+use crate::history::{HistoryStore, HistoryUpdate, now_secs};
+use crate::network_panel::NetworkPanel;
+use crate::preferences::PreferenceStore;
+use crate::session::{Session, SessionStore};
+use crate::tab::{
+    ABOUT_HISTORY, ABOUT_METRICS, LoadProgress, PageMetrics, Tab, VIEW_SOURCE_PREFIX,
+    about_page_html, elide_end,
+};
+use crate::view::PageAction;
+
+const FIND_QUERY_ID: &str = "find_bar_query";
+const FAVICON_SIZE: f32 = 16.0;
+const URL_SUGGESTION_LIMIT: usize = 6;
+const PROGRESS_BAR_HEIGHT: f32 = 2.0;
+/// Sweeps per second of the bar while the load size is unknown.
+const PROGRESS_SWEEP_RATE: f32 = 0.8;
+
+enum BookmarkBarAction {
+    Open(String),
+    Remove(String),
+}
 
 pub struct ShellApp {
     tabs: Vec<Tab>,
@@ -19,21 +43,107 @@ pub struct ShellApp {
     repaint: Option<RepaintHandle>,
 
     next_tab_id: TabId,
+
+    bookmarks: BookmarkStore,
+    history: HistoryStore,
+    session: SessionStore,
+    preferences: PreferenceStore,
+    /// Open network panel, toggled with F12.
+    network_panel: Option<NetworkPanel>,
+    /// Page scrolls jump instead of animating.
+    instant_scroll: bool,
+    /// Directory every tab records its navigations' runtime events into.
+    record_dir: Option<PathBuf>,
+    /// OS colour scheme, matched by `prefers-color-scheme` in every tab.
+    color_scheme: ColorScheme,
+    /// Bounds every tab parses its documents within.
+    parse_limits: ParseLimits,
 }
 
 impl ShellApp {
     pub fn new() -> Self {
+        let preferences = PreferenceStore::load_default();
+        let instant_scroll = instant_scroll_from_env().unwrap_or(preferences.get().instant_scroll);
         let mut s = Self {
             tabs: Vec::new(),
             active: 0,
             cmd_tx: None,
             repaint: None,
             next_tab_id: 1,
+            bookmarks: BookmarkStore::load_default(),
+            history: HistoryStore::load_default(),
+            session: SessionStore::load_default(),
+            preferences,
+            network_panel: None,
+            instant_scroll,
+            record_dir: std::env::var_os("BORROWSER_RECORD_DIR").map(PathBuf::from),
+            color_scheme: ColorScheme::default(),
+            parse_limits: ParseLimits::default(),
         };
-        s.add_tab();
+        match s.session.restore() {
+            Some(session) => s.restore_tabs(session),
+            None => s.add_tab(),
+        }
         s
     }
 
+    fn restore_tabs(&mut self, session: Session) {
+        for tab_session in session.tabs {
+            self.add_tab();
+            self.active_tab_mut().restore_session(tab_session);
+        }
+        self.active = session.active.min(self.tabs.len() - 1);
+    }
+
+    /// Snapshot all tabs into the session store. Unless `flush` is set, the
+    /// store throttles writes and asks for a later frame to save deferred
+    /// changes.
+    fn save_session(&mut self, flush: bool) {
+        let mut session = Session::default();
+        for (idx, tab) in self.tabs.iter().enumerate() {
+            if idx == self.active {
+                session.active = session.tabs.len();
+            }
+            session.tabs.extend(tab.session_state());
+        }
+        let result = self.session.save(session).and_then(|retry_after| {
+            if flush {
+                self.session.flush().map(|()| None)
+            } else {
+                Ok(retry_after)
+            }
+        });
+        match result {
+            Ok(Some(retry_after)) => {
+                if let Some(repaint) = &self.repaint {
+                    repaint.request_after(retry_after);
+                }
+            }
+            Ok(None) => {}
+            Err(err) => eprintln!("session save error: {err}"),
+        }
+    }
+
+    /// Make page scrolls in every tab jump instead of animating, and remember
+    /// the choice for later sessions.
+    pub fn set_instant_scroll(&mut self, instant: bool) {
+        if let Err(err) = self.preferences.set_instant_scroll(instant) {
+            eprintln!("preferences save error: {err}");
+        }
+        self.instant_scroll = instant;
+        for tab in &mut self.tabs {
+            tab.set_instant_scroll(instant);
+        }
+    }
+
+    /// Parse documents in every tab within `limits`, from the next load on.
+    pub fn set_parse_limits(&mut self, limits: ParseLimits) {
+        self.parse_limits = limits;
+        for tab in &mut self.tabs {
+            tab.set_parse_limits(limits);
+        }
+    }
+
     fn alloc_tab_id(&mut self) -> TabId {
         let id = self.next_tab_id;
         self.next_tab_id = self.next_tab_id.wrapping_add(1);
@@ -50,22 +160,46 @@ impl ShellApp {
         if let Some(rp) = &self.repaint {
             t.set_repaint_handle(rp.clone());
         }
+        t.set_instant_scroll(self.instant_scroll);
+        if let Some(dir) = &self.record_dir {
+            t.set_event_recording(dir.clone());
+        }
+        t.set_color_scheme(self.color_scheme);
+        t.set_parse_limits(self.parse_limits);
+        for entry in self.history.list() {
+            t.mark_visited(&entry.url);
+        }
         self.tabs.push(t);
         self.active = self.tabs.len() - 1;
         self.request_repaint();
     }
 
-    fn close_at(&mut self, idx: usize) {
-        // Cancel any in-flight request for that tab
-        if let (Some(tab), Some(tx)) = (
-            self.tabs.get(idx).filter(|tab| tab.nav_gen > 0),
-            self.cmd_tx.as_ref(),
-        ) {
-            let _ = tx.send(CoreCommand::CancelRequest {
+    /// Open `url` in a new tab, switching to it only if `activate` is set.
+    fn open_in_new_tab(&mut self, url: String, activate: bool) {
+        let previous = self.active;
+        self.add_tab();
+        self.active_tab_mut().navigate_to_new(url);
+        if !activate {
+            self.active = previous;
+        }
+    }
+
+    /// Cancel any in-flight request of the tab at `idx` and stop its runtimes.
+    fn release_tab(&self, idx: usize) {
+        let (Some(tab), Some(tx)) = (self.tabs.get(idx), self.cmd_tx.as_ref()) else {
+            return;
+        };
+        if tab.nav_gen > 0 {
+            let _ = tx.send(CoreCommand::CancelGeneration {
                 tab_id: tab.tab_id,
                 request_id: tab.nav_gen,
             });
         }
+        let _ = tx.send(CoreCommand::TabClosed { tab_id: tab.tab_id });
+    }
+
+    fn close_at(&mut self, idx: usize) {
+        self.release_tab(idx);
 
         // Remove the tab
         let removed_active = idx == self.active;
@@ -91,16 +225,7 @@ impl ShellApp {
             return;
         }
         let idx = self.active;
-
-        if let (Some(tab), Some(tx)) = (
-            self.tabs.get(idx).filter(|tab| tab.nav_gen > 0),
-            self.cmd_tx.as_ref(),
-        ) {
-            let _ = tx.send(CoreCommand::CancelRequest {
-                tab_id: tab.tab_id,
-                request_id: tab.nav_gen,
-            });
-        }
+        self.release_tab(idx);
 
         self.tabs.remove(idx);
         if self.tabs.is_empty() {
@@ -197,12 +322,33 @@ impl ShellApp {
                             close_idx = Some(i);
                         }
 
-                        // ---- 3) Text, clipped to "tab minus close" ----
+                        // ---- 3) Favicon (a spinner while loading), then text clipped to "tab minus close" ----
                         let title = t.display_title();
 
+                        let mut text_left = tab_rect.left() + 12.0;
+                        let icon_rect = Rect::from_min_size(
+                            pos2(text_left, tab_rect.center().y - FAVICON_SIZE / 2.0),
+                            vec2(FAVICON_SIZE, FAVICON_SIZE),
+                        );
+                        if t.is_loading() {
+                            Spinner::new()
+                                .size(FAVICON_SIZE)
+                                .color(vis.widgets.inactive.fg_stroke.color)
+                                .paint_at(ui, icon_rect);
+                            text_left = icon_rect.right() + 6.0;
+                        } else if let Some(texture) = t.favicon() {
+                            ui.painter().image(
+                                texture,
+                                icon_rect,
+                                Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0)),
+                                Color32::WHITE,
+                            );
+                            text_left = icon_rect.right() + 6.0;
+                        }
+
                         // Area reserved for text only
                         let text_rect = Rect::from_min_max(
-                            pos2(tab_rect.left() + 12.0, tab_rect.top()),
+                            pos2(text_left, tab_rect.top()),
                             pos2(close_rect.left() - 4.0, tab_rect.bottom()),
                         );
 
@@ -261,28 +407,100 @@ impl ShellApp {
         }
     }
 
+    fn ui_file_menu(&mut self, ui: &mut Ui) {
+        ui.menu_button("File", |ui| {
+            if ui.button("New tab").clicked() {
+                self.add_tab();
+                ui.close();
+            }
+            if ui.button("Print to PDF").clicked() {
+                self.active_tab_mut().print_to_downloads();
+                ui.close();
+            }
+            if ui.button("History").clicked() {
+                self.open_in_new_tab(ABOUT_HISTORY.to_string(), true);
+                ui.close();
+            }
+            if ui.button("Metrics").clicked() {
+                self.open_in_new_tab(ABOUT_METRICS.to_string(), true);
+                ui.close();
+            }
+            ui.separator();
+            let mut instant_scroll = self.instant_scroll;
+            if ui
+                .checkbox(&mut instant_scroll, "Instant scrolling")
+                .changed()
+            {
+                self.set_instant_scroll(instant_scroll);
+            }
+        });
+    }
+
     fn ui_urlbar(&mut self, ui: &mut Ui) {
         let input = BrowserInput {
             enter_pressed: ui.input(|i| i.key_pressed(egui::Key::Enter)),
         };
 
         let needs_repaint = {
-            let tab = self.active_tab_mut();
-
-            let can_back = tab.history_index > 0;
-            let can_forward = tab.history_index + 1 < tab.history.len();
-
-            let intent = navigation_widgets(ui, &mut tab.url, can_back, can_forward, input);
+            let tab = &mut self.tabs[self.active];
+            let suggestions = url_suggestions(&self.history, tab);
+
+            let state = NavigationState {
+                can_go_back: tab.can_go_back(),
+                can_go_forward: tab.can_go_forward(),
+                is_loading: tab.is_loading(),
+                is_bookmarked: tab
+                    .current_url()
+                    .is_some_and(|url| self.bookmarks.contains(url)),
+                favicon: tab.favicon(),
+                zoom_percent: Some((tab.zoom() * 100.0).round() as u32)
+                    .filter(|&percent| percent != 100),
+                suggestions: &suggestions,
+            };
+
+            let intent = navigation_widgets(ui, &mut tab.url, state, input);
+            let (alt_back, alt_forward) = ui.input_mut(|i| {
+                (
+                    i.consume_key(egui::Modifiers::ALT, egui::Key::ArrowLeft),
+                    i.consume_key(egui::Modifiers::ALT, egui::Key::ArrowRight),
+                )
+            });
 
-            if intent.go_back {
+            if intent.go_back || alt_back {
                 tab.go_back();
             }
-            if intent.go_forward {
+            if intent.go_forward || alt_forward {
                 tab.go_forward();
             }
             if intent.refresh {
                 tab.refresh();
             }
+            if intent.stop {
+                tab.stop();
+            }
+            if intent.toggle_bookmark {
+                toggle_bookmark(&mut self.bookmarks, tab);
+            }
+
+            // Consumed here so egui's own end-of-frame GUI zoom never sees
+            // them; page zoom re-lays out the document instead.
+            let (zoom_in, zoom_out, zoom_reset) = ui.input_mut(|i| {
+                (
+                    i.consume_shortcut(&kb_shortcuts::ZOOM_IN)
+                        | i.consume_shortcut(&kb_shortcuts::ZOOM_IN_SECONDARY),
+                    i.consume_shortcut(&kb_shortcuts::ZOOM_OUT),
+                    i.consume_shortcut(&kb_shortcuts::ZOOM_RESET),
+                )
+            });
+            if zoom_in {
+                tab.zoom_in();
+            }
+            if zoom_out {
+                tab.zoom_out();
+            }
+            if intent.reset_zoom || zoom_reset {
+                tab.reset_zoom();
+            }
 
             if let Some(url) = intent.navigate_to {
                 tab.navigate_to_new(url);
@@ -296,10 +514,197 @@ impl ShellApp {
             self.request_repaint();
         }
     }
+
+    fn ui_bookmarks_bar(&mut self, ui: &mut Ui) {
+        let mut action = None;
+
+        let mut bookmark_button = |ui: &mut Ui, bookmark: &Bookmark| {
+            let resp = ui
+                .button(elide_end(&bookmark.title, 24))
+                .on_hover_text(&bookmark.url);
+            if resp.clicked() {
+                action = Some(BookmarkBarAction::Open(bookmark.url.clone()));
+            }
+            resp.context_menu(|ui| {
+                if ui.button("Remove bookmark").clicked() {
+                    action = Some(BookmarkBarAction::Remove(bookmark.url.clone()));
+                    ui.close();
+                }
+            });
+        };
+
+        ui.horizontal_wrapped(|ui| {
+            for folder in self.bookmarks.folders() {
+                ui.menu_button(format!("📁 {folder}"), |ui| {
+                    for bookmark in self.bookmarks.list() {
+                        if bookmark.folder.as_deref() == Some(folder) {
+                            bookmark_button(ui, bookmark);
+                        }
+                    }
+                });
+            }
+            for bookmark in self.bookmarks.list() {
+                if bookmark.folder.is_none() {
+                    bookmark_button(ui, bookmark);
+                }
+            }
+        });
+
+        match action {
+            Some(BookmarkBarAction::Open(url)) => {
+                self.active_tab_mut().navigate_to_new(url);
+                self.request_repaint();
+            }
+            Some(BookmarkBarAction::Remove(url)) => {
+                if let Err(err) = self.bookmarks.remove(&url) {
+                    eprintln!("bookmark save error: {err}");
+                }
+            }
+            None => {}
+        }
+    }
+}
+
+/// A non-empty `BORROWSER_INSTANT_SCROLL` overrides the saved preference for
+/// this run: `0` animates page scrolls, anything else makes them jump.
+fn instant_scroll_from_env() -> Option<bool> {
+    std::env::var("BORROWSER_INSTANT_SCROLL")
+        .ok()
+        .filter(|value| !value.is_empty())
+        .map(|value| value != "0")
+}
+
+/// History completions for the URL bar, offered once its text was edited
+/// away from the current page's URL.
+fn url_suggestions(history: &HistoryStore, tab: &Tab) -> Vec<UrlSuggestion> {
+    if tab.url.trim().is_empty() || tab.current_url() == Some(tab.url.as_str()) {
+        return Vec::new();
+    }
+    history
+        .suggestions(&tab.url, URL_SUGGESTION_LIMIT)
+        .into_iter()
+        .map(|entry| UrlSuggestion {
+            url: entry.url.clone(),
+            title: entry.title.clone(),
+        })
+        .collect()
+}
+
+fn toggle_bookmark(bookmarks: &mut BookmarkStore, tab: &Tab) {
+    let Some(url) = tab.current_url() else {
+        return;
+    };
+    let result = if bookmarks.contains(url) {
+        bookmarks.remove(url)
+    } else {
+        bookmarks.add(Bookmark {
+            title: tab.page_title().unwrap_or(url).to_string(),
+            url: url.to_string(),
+            folder: None,
+        })
+    };
+    if let Err(err) = result {
+        eprintln!("bookmark save error: {err}");
+    }
+}
+
+/// Thin bar under the toolbar with the active tab's load progress. It sweeps
+/// back and forth while the document size is unknown.
+fn ui_load_progress(ui: &mut Ui, progress: Option<LoadProgress>) {
+    let (rect, _) = ui.allocate_exact_size(
+        vec2(ui.available_width(), PROGRESS_BAR_HEIGHT),
+        Sense::hover(),
+    );
+    let Some(progress) = progress else {
+        return;
+    };
+    let color = ui.visuals().selection.bg_fill;
+    let bar = match progress.fraction() {
+        Some(fraction) => {
+            Rect::from_min_size(rect.min, vec2(rect.width() * fraction, rect.height()))
+        }
+        None => {
+            let width = rect.width() * 0.25;
+            let phase = (ui.input(|i| i.time) as f32 * PROGRESS_SWEEP_RATE).fract();
+            let sweep = 1.0 - (2.0 * phase - 1.0).abs();
+            ui.ctx().request_repaint();
+            Rect::from_min_size(
+                pos2(rect.left() + sweep * (rect.width() - width), rect.top()),
+                vec2(width, rect.height()),
+            )
+        }
+    };
+    ui.painter().rect_filled(bar, CornerRadius::ZERO, color);
+}
+
+fn ui_find_bar(ui: &mut Ui, tab: &mut Tab) {
+    let find = tab.find_mut();
+
+    ui.label("Find:");
+    let resp = ui.add(
+        TextEdit::singleline(find.query_mut())
+            .id(egui::Id::new(FIND_QUERY_ID))
+            .desired_width(220.0)
+            .hint_text("Find in page"),
+    );
+    if resp.changed() {
+        find.query_changed();
+    }
+
+    let (enter, shift, escape) = ui.input(|i| {
+        (
+            i.key_pressed(egui::Key::Enter),
+            i.modifiers.shift,
+            i.key_pressed(egui::Key::Escape),
+        )
+    });
+    if resp.lost_focus() && enter {
+        if shift {
+            find.previous();
+        } else {
+            find.next();
+        }
+        resp.request_focus();
+    }
+
+    let status = match find.active() {
+        Some(active) => format!("{} of {}", active + 1, find.match_count()),
+        None if find.query().is_empty() => String::new(),
+        None => "No matches".to_string(),
+    };
+    ui.label(status);
+
+    if ui.button("▲").on_hover_text("Previous match").clicked() {
+        find.previous();
+    }
+    if ui.button("▼").on_hover_text("Next match").clicked() {
+        find.next();
+    }
+    if ui.button("✖").on_hover_text("Close find bar").clicked() || escape {
+        find.close();
+    }
 }
 
 impl UiApp for ShellApp {
     fn ui(&mut self, ctx: &Context) {
+        let now = now_secs();
+        let pages: Vec<PageMetrics> = if self
+            .tabs
+            .iter()
+            .any(|tab| tab.pending_about_page().is_some())
+        {
+            self.tabs.iter().filter_map(PageMetrics::of).collect()
+        } else {
+            Vec::new()
+        };
+        for tab in &mut self.tabs {
+            tab.pump_icons(ctx);
+            if let Some(url) = tab.pending_about_page() {
+                let html = about_page_html(url, &self.history, &pages, now);
+                tab.show_about_page(html);
+            }
+        }
+
         TopBottomPanel::top("Browser Shell")
             .frame(Frame::new().inner_margin(Margin::symmetric(0, 0)))
             .show(ctx, |ui| {
@@ -323,35 +728,160 @@ impl UiApp for ShellApp {
                         ui.set_min_width(ui.available_width());
                         ui.horizontal(|ui| {
                             ui.spacing_mut().item_spacing.x = 6.0;
+                            self.ui_file_menu(ui);
                             self.ui_urlbar(ui);
                         });
                     });
+                ui_load_progress(ui, self.tabs[self.active].load_progress());
+
+                // ---- Find bar ----
+                let open_find =
+                    ui.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::F));
+                if open_find {
+                    self.active_tab_mut().find_mut().open();
+                    ui.memory_mut(|m| m.request_focus(egui::Id::new(FIND_QUERY_ID)));
+                }
+                if self.tabs[self.active].find().is_open() {
+                    Frame::new()
+                        .fill(Color32::from_rgb(40, 40, 40))
+                        .inner_margin(Margin::symmetric(6, 4))
+                        .show(ui, |ui| {
+                            ui.set_min_width(ui.available_width());
+                            ui.horizontal(|ui| {
+                                ui.spacing_mut().item_spacing.x = 6.0;
+                                ui_find_bar(ui, self.active_tab_mut());
+                            });
+                        });
+                }
+
+                // ---- Bookmarks bar ----
+                if !self.bookmarks.is_empty() {
+                    Frame::new()
+                        .fill(Color32::from_rgb(40, 40, 40))
+                        .inner_margin(Margin::symmetric(6, 4))
+                        .show(ui, |ui| {
+                            ui.set_min_width(ui.available_width());
+                            self.ui_bookmarks_bar(ui);
+                        });
+                }
             });
 
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::H)) {
+            self.open_in_new_tab(ABOUT_HISTORY.to_string(), true);
+        }
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::P)) {
+            self.active_tab_mut().print_to_downloads();
+        }
+
+        // ---- DevTools ----
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::F12)) {
+            self.network_panel = match self.network_panel {
+                Some(_) => None,
+                None => Some(NetworkPanel::default()),
+            };
+        }
+        if let Some(panel) = &mut self.network_panel
+            && !panel.show(ctx, self.tabs[self.active].network_log())
+        {
+            self.network_panel = None;
+        }
+        let toggle_box_overlay = ctx.input_mut(|i| {
+            i.consume_key(
+                egui::Modifiers::COMMAND | egui::Modifiers::SHIFT,
+                egui::Key::D,
+            )
+        });
+        if toggle_box_overlay {
+            self.active_tab_mut().toggle_box_overlay();
+        }
+
         // ---- Page content below ----
-        self.active_tab_mut().ui_content(ctx);
+        match self.active_tab_mut().ui_content(ctx) {
+            Some(PageAction::OpenInNewTab(url)) => self.open_in_new_tab(url, false),
+            Some(PageAction::NavigateInNewTab(url)) => self.open_in_new_tab(url, true),
+            Some(PageAction::ViewSource) => {
+                if let Some(url) = self
+                    .active_tab_mut()
+                    .current_url()
+                    .filter(|url| !url.starts_with(VIEW_SOURCE_PREFIX))
+                    .map(|url| format!("{VIEW_SOURCE_PREFIX}{url}"))
+                {
+                    self.open_in_new_tab(url, true);
+                }
+            }
+            _ => {}
+        }
+
+        self.save_session(false);
     }
 
     fn set_bus_sender(&mut self, tx: mpsc::Sender<CoreCommand>) {
         self.cmd_tx = Some(tx.clone());
         for t in &mut self.tabs {
             t.set_bus_sender(tx.clone());
+            // Tabs restored from the previous session load once commands
+            // can be sent.
+            if t.nav_gen == 0 {
+                t.load_restored_entry();
+            }
         }
     }
 
+    fn on_exit(&mut self) {
+        self.save_session(true);
+    }
+
     fn on_core_event(&mut self, evt: CoreEvent) {
         let sid = match &evt {
-            CoreEvent::NetworkStart { tab_id, .. }
+            CoreEvent::NetworkRedirected { tab_id, .. }
+            | CoreEvent::NetworkConnection { tab_id, .. }
+            | CoreEvent::NetworkStart { tab_id, .. }
             | CoreEvent::NetworkChunk { tab_id, .. }
             | CoreEvent::NetworkDone { tab_id, .. }
             | CoreEvent::NetworkError { tab_id, .. }
-            | CoreEvent::DomUpdate { tab_id, .. }
             | CoreEvent::DomPatchUpdate { tab_id, .. }
+            | CoreEvent::ScriptParsed { tab_id, .. }
+            | CoreEvent::ResourceHint { tab_id, .. }
+            | CoreEvent::ResourceTruncated { tab_id, .. }
+            | CoreEvent::ScriptDone { tab_id, .. }
+            | CoreEvent::CssImport { tab_id, .. }
             | CoreEvent::CssDecodedBlock { tab_id, .. }
-            | CoreEvent::CssSheetDone { tab_id, .. } => *tab_id,
+            | CoreEvent::CssSheetDone { tab_id, .. }
+            | CoreEvent::LayoutReady { tab_id, .. }
+            | CoreEvent::ImageDecoded { tab_id, .. }
+            | CoreEvent::FilesPicked { tab_id, .. }
+            | CoreEvent::TimeoutFired { tab_id, .. }
+            | CoreEvent::Metrics { tab_id, .. }
+            | CoreEvent::RuntimeCrashed { tab_id, .. } => *tab_id,
+        };
+        let Some(tab) = self.tabs.iter_mut().find(|t| t.tab_id == sid) else {
+            return;
+        };
+        tab.on_core_event(evt);
+        for update in tab.take_history_updates() {
+            if let HistoryUpdate::Visit { url } = &update {
+                for tab in &mut self.tabs {
+                    tab.mark_visited(url);
+                }
+            }
+            if let Err(err) = self.history.apply(update) {
+                eprintln!("history save error: {err}");
+            }
+        }
+    }
+
+    fn window_title(&self) -> Option<String> {
+        self.tabs.get(self.active).map(Tab::window_title)
+    }
+
+    fn set_dark_mode(&mut self, dark: bool) {
+        self.color_scheme = if dark {
+            ColorScheme::Dark
+        } else {
+            ColorScheme::Light
         };
-        if let Some(tab) = self.tabs.iter_mut().find(|t| t.tab_id == sid) {
-            tab.on_core_event(evt);
+        for tab in &mut self.tabs {
+            tab.set_color_scheme(self.color_scheme);
         }
     }
 
@@ -0,0 +1,181 @@
+//! Generated documents for the shell's internal `about:` URLs.
+//!
+//! Their content lives in the shell (the history store and the open tabs), so
+//! a tab only marks the page as pending and the shell fills it in with
+//! [`about_page_html`]. The markup goes through the regular
+//! parse/style/layout pipeline, and the search box is a plain GET form that
+//! navigates back to `about:history`.
+//!
+//! Every other `about:` page (`about:blank`, `about:version`, and
+//! `about:crash-test`) is generated by the net crate and fetched like any
+//! document.
+
+use super::Tab;
+use super::error_page::escape_html;
+use super::nav::ABOUT_PREFIX;
+use crate::history::HistoryStore;
+use crate::network_panel::{format_duration, format_size};
+use bus::RuntimeMetrics;
+use url::Url;
+
+/// URL of the browsing history page.
+pub(crate) const ABOUT_HISTORY: &str = "about:history";
+
+/// URL of the page listing the runtime counters of each open tab.
+pub(crate) const ABOUT_METRICS: &str = "about:metrics";
+
+/// Whether the shell generates the `about:` page at `url`.
+pub(super) fn is_shell_page(url: &str) -> bool {
+    Url::parse(url)
+        .is_ok_and(|url| url.scheme() == "about" && matches!(url.path(), "history" | "metrics"))
+}
+
+/// Runtime counters of one tab's current page, as `about:metrics` lists them.
+pub(crate) struct PageMetrics {
+    pub(crate) url: String,
+    pub(crate) title: String,
+    pub(crate) metrics: RuntimeMetrics,
+}
+
+impl PageMetrics {
+    /// The tab's current page, unless it is an internal page itself.
+    pub(crate) fn of(tab: &Tab) -> Option<Self> {
+        let url = tab.current_url()?;
+        if url.starts_with(ABOUT_PREFIX) {
+            return None;
+        }
+        Some(Self {
+            url: url.to_string(),
+            title: tab.page_title().unwrap_or_default().to_string(),
+            metrics: *tab.metrics(),
+        })
+    }
+}
+
+/// Entries listed on the history page, most recent first.
+const HISTORY_PAGE_LIMIT: usize = 500;
+
+const STYLE: &str = "\
+body { background-color: #f4f4f4; color: #303030; margin: 0; padding: 32px 48px; }\
+h1 { font-size: 24px; margin-bottom: 16px; }\
+ul { padding: 0; }\
+li { list-style-type: none; margin-bottom: 12px; }\
+.url { color: #606060; font-size: 13px; }\
+.meta { color: #808080; font-size: 12px; }\
+.empty { color: #808080; }";
+
+/// Markup for the `about:` page at `url`; `now` is in seconds since the Unix
+/// epoch and dates the visits.
+pub(crate) fn about_page_html(
+    url: &str,
+    history: &HistoryStore,
+    pages: &[PageMetrics],
+    now: u64,
+) -> String {
+    let parsed = Url::parse(url).ok();
+    match parsed.as_ref().map(Url::path) {
+        Some("history") => {
+            let query = parsed
+                .as_ref()
+                .and_then(|url| url.query_pairs().find(|(name, _)| name == "q"))
+                .map(|(_, value)| value.into_owned())
+                .unwrap_or_default();
+            history_page_html(&query, history, now)
+        }
+        Some("metrics") => metrics_page_html(pages),
+        _ => format!(
+            "<!DOCTYPE html><html><head><title>Page not found</title><style>{STYLE}</style></head>\
+<body><h1>Page not found</h1><p class=\"empty\">{} is not an internal page.</p></body></html>",
+            escape_html(url)
+        ),
+    }
+}
+
+fn history_page_html(query: &str, history: &HistoryStore, now: u64) -> String {
+    let matches = history.search(query);
+    let mut items = String::new();
+    for entry in matches.iter().take(HISTORY_PAGE_LIMIT) {
+        let url = escape_html(&entry.url);
+        let title = if entry.title.is_empty() {
+            url.clone()
+        } else {
+            escape_html(&entry.title)
+        };
+        let visits = match entry.visit_count {
+            1 => "1 visit".to_string(),
+            count => format!("{count} visits"),
+        };
+        items.push_str(&format!(
+            "<li><a href=\"{url}\">{title}</a><div class=\"url\">{url}</div>\
+<div class=\"meta\">{visits} • {}</div></li>",
+            time_ago(now.saturating_sub(entry.last_visit))
+        ));
+    }
+    let list = if items.is_empty() {
+        let message = if query.trim().is_empty() {
+            "No pages visited yet."
+        } else {
+            "No history entries match your search."
+        };
+        format!("<p class=\"empty\">{message}</p>")
+    } else {
+        format!("<ul>{items}</ul>")
+    };
+    let query = escape_html(query);
+    format!(
+        "<!DOCTYPE html>\
+<html><head><title>History</title><style>{STYLE}</style></head><body>\
+<h1>History</h1>\
+<form action=\"{ABOUT_HISTORY}\" method=\"get\">\
+<input type=\"text\" name=\"q\" value=\"{query}\" placeholder=\"Search history\"> \
+<button type=\"submit\">Search</button>\
+</form>\
+{list}\
+</body></html>"
+    )
+}
+
+fn metrics_page_html(pages: &[PageMetrics]) -> String {
+    let mut items = String::new();
+    for page in pages {
+        let url = escape_html(&page.url);
+        let title = if page.title.is_empty() {
+            url.clone()
+        } else {
+            escape_html(&page.title)
+        };
+        let metrics = &page.metrics;
+        items.push_str(&format!(
+            "<li><a href=\"{url}\">{title}</a><div class=\"url\">{url}</div>\
+<div class=\"meta\">{} fetched • {} tokens • parsed in {} • {} DOM nodes • {} CSS rules</div></li>",
+            format_size(usize::try_from(metrics.bytes_fetched).unwrap_or(usize::MAX)),
+            metrics.tokens_emitted,
+            format_duration(metrics.parse_time),
+            metrics.dom_nodes,
+            metrics.css_rules_applied,
+        ));
+    }
+    let list = if items.is_empty() {
+        "<p class=\"empty\">No pages open.</p>".to_string()
+    } else {
+        format!("<ul>{items}</ul>")
+    };
+    format!(
+        "<!DOCTYPE html>\
+<html><head><title>Metrics</title><style>{STYLE}</style></head><body>\
+<h1>Metrics</h1>\
+{list}\
+</body></html>"
+    )
+}
+
+fn time_ago(elapsed_secs: u64) -> String {
+    let (amount, unit) = match elapsed_secs {
+        0..60 => return "just now".to_string(),
+        60..3_600 => (elapsed_secs / 60, "minute"),
+        3_600..86_400 => (elapsed_secs / 3_600, "hour"),
+        _ => (elapsed_secs / 86_400, "day"),
+    };
+    let plural = if amount == 1 { "" } else { "s" };
+    format!("{amount} {unit}{plural} ago")
+}
@@ -2,7 +2,7 @@ use super::Tab;
 use super::state::StylesheetLoadState;
 use super::status::format_network_error;
 use bus::CoreCommand;
-use core_types::{NetworkErrorKind, NetworkResponseInfo, RequestId, StylesheetSlotId};
+use core_types::{NetError, NetworkResponseInfo, RequestId, ResourceKind, StylesheetSlotId};
 
 impl Tab {
     pub(super) fn on_css_network_start(
@@ -71,9 +71,8 @@ impl Tab {
         &mut self,
         stylesheet_slot_id: StylesheetSlotId,
         url: String,
-        error_kind: NetworkErrorKind,
-        status_code: Option<u16>,
-        error: String,
+        error: NetError,
+        message: String,
         request_id: RequestId,
     ) {
         self.stylesheet_loads.remove(&stylesheet_slot_id);
@@ -85,10 +84,12 @@ impl Tab {
             url: url.clone(),
         });
         let remaining = self.page.pending_count();
-        self.loading = remaining > 0;
+        // A cancelled stylesheet means the whole load was stopped; its
+        // siblings are being cancelled too, so don't report loading again.
+        self.loading = remaining > 0 && error != NetError::Cancelled;
         self.last_status = Some(format!(
             "{} ({} remaining)",
-            format_network_error("stylesheet", &url, error_kind, status_code, &error),
+            format_network_error("stylesheet", &url, error, &message),
             remaining
         ));
         if !self.request_optional_render_work(render_work) {
@@ -96,6 +97,28 @@ impl Tab {
         }
     }
 
+    /// Fetch the sheet an `@import` rule of the sheet in
+    /// `stylesheet_slot_id` refers to.
+    pub(super) fn on_css_import(
+        &mut self,
+        request_id: RequestId,
+        stylesheet_slot_id: StylesheetSlotId,
+        href: String,
+        media: String,
+    ) {
+        if let Some(fetch) = self
+            .page
+            .import_stylesheet(stylesheet_slot_id, &href, &media)
+        {
+            self.send_fetch(
+                request_id,
+                Some(fetch.slot_id),
+                fetch.url,
+                ResourceKind::Css,
+            );
+        }
+    }
+
     pub(super) fn on_css_decoded_block(
         &mut self,
         stylesheet_slot_id: StylesheetSlotId,
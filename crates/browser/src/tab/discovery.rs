@@ -1,13 +1,79 @@
 use super::Tab;
 use bus::CoreCommand;
-use core_types::{RequestId, ResourceKind, StylesheetSlotId};
+use core_types::{RequestId, RequestPayload, ResourceKind, StylesheetSlotId};
 use html::dom_utils::collect_img_srcs;
-use url::Url;
+use weburl::Url;
 
 impl Tab {
     pub(crate) fn discover_resources(&mut self, request_id: RequestId) {
         let _ = self.discover_stylesheets(request_id);
         self.discover_images(request_id);
+        self.discover_icon(request_id);
+    }
+
+    /// Start fetching a resource the parser's preload scanner found before
+    /// it reaches the DOM. The DOM-driven discovery later finds the fetch
+    /// already under way instead of starting another.
+    pub(super) fn on_resource_hint(
+        &mut self,
+        request_id: RequestId,
+        url: String,
+        kind: ResourceKind,
+    ) {
+        match kind {
+            ResourceKind::Css => {
+                if let Some(fetch) = self.page.preload_stylesheet(&url) {
+                    self.send_fetch(request_id, Some(fetch.slot_id), fetch.url, kind);
+                }
+            }
+            ResourceKind::Image => {
+                let Some(abs) = self
+                    .base_url()
+                    .and_then(|base| weburl::resolve(&base, &url))
+                else {
+                    return;
+                };
+                let mut fetch = None;
+                self.resources
+                    .request_image(abs.to_string(), |url| fetch = Some(url));
+                if let Some(url) = fetch {
+                    self.send_fetch(request_id, None, url, kind);
+                }
+            }
+            ResourceKind::Html | ResourceKind::Icon => {}
+        }
+    }
+
+    /// Pick the page icon: the first `<link rel=icon>`, else `/favicon.ico` at
+    /// the document origin, which `<base href>` does not change. Re-evaluated
+    /// per DOM update so a link arriving in a later chunk replaces the
+    /// fallback.
+    fn discover_icon(&mut self, request_id: RequestId) {
+        let url = match self.page.head.icon_href() {
+            Some(href) => self
+                .base_url()
+                .and_then(|base| weburl::resolve(&base, href)),
+            None => self
+                .page
+                .document_url
+                .as_deref()
+                .and_then(weburl::parse)
+                .filter(|document| matches!(document.scheme(), "http" | "https"))
+                .and_then(|document| weburl::resolve(&document, "/favicon.ico")),
+        };
+        let Some(url) = url.map(String::from) else {
+            return;
+        };
+        if self.favicon_url.as_deref() == Some(url.as_str()) {
+            return;
+        }
+        self.favicon_url = Some(url.clone());
+
+        let mut fetch = None;
+        self.icons.request_image(url, |url| fetch = Some(url));
+        if let Some(url) = fetch {
+            self.send_fetch(request_id, None, url, ResourceKind::Icon);
+        }
     }
 
     fn discover_stylesheets(&mut self, request_id: RequestId) -> bool {
@@ -31,49 +97,56 @@ impl Tab {
         let mut srcs = Vec::new();
         collect_img_srcs(dom_ref, &mut srcs);
 
-        let cmd_tx = self.cmd_tx.clone();
-        let tab_id = self.tab_id;
-
-        if srcs.is_empty() {
-            return;
-        }
-
+        let mut fetches = Vec::new();
         for src in srcs {
-            if let Ok(abs) = base.join(&src) {
+            if let Some(abs) = weburl::resolve(&base, &src) {
                 let url = abs.to_string();
-                self.resources.request_image(url, |url| {
-                    if let Some(tx) = &cmd_tx {
-                        let _ = tx.send(CoreCommand::FetchStream {
-                            tab_id,
-                            request_id,
-                            stylesheet_slot_id: None,
-                            url,
-                            kind: ResourceKind::Image,
-                        });
-                    }
-                });
+                self.resources.request_image(url, |url| fetches.push(url));
             }
         }
+        for url in fetches {
+            self.send_fetch(request_id, None, url, ResourceKind::Image);
+        }
     }
 
     fn base_url(&self) -> Option<Url> {
-        let base = self.page.base_url.as_ref()?;
-        Url::parse(base).ok()
+        weburl::parse(self.page.base_url.as_deref()?)
+    }
+
+    pub(super) fn send_fetch(
+        &mut self,
+        request_id: RequestId,
+        stylesheet_slot_id: Option<StylesheetSlotId>,
+        url: String,
+        kind: ResourceKind,
+    ) {
+        self.send_fetch_with_payload(
+            request_id,
+            stylesheet_slot_id,
+            url,
+            kind,
+            RequestPayload::default(),
+        );
     }
 
-    fn send_fetch(
-        &self,
+    /// Like [`Tab::send_fetch`], sending the method, headers, and body of
+    /// `payload`.
+    pub(super) fn send_fetch_with_payload(
+        &mut self,
         request_id: RequestId,
         stylesheet_slot_id: Option<StylesheetSlotId>,
         url: String,
         kind: ResourceKind,
+        payload: RequestPayload,
     ) {
+        self.network_log.record_request(url.clone(), kind);
         self.send_cmd(CoreCommand::FetchStream {
             tab_id: self.tab_id,
             request_id,
             stylesheet_slot_id,
             url,
             kind,
+            payload,
         });
     }
 }
@@ -0,0 +1,99 @@
+//! Generated document shown when the main document could not be loaded.
+//!
+//! The page is plain HTML fed through the regular parse/style/layout
+//! pipeline. Its "Try again" link points at the failed URL; following it
+//! while the error page is shown reloads instead of adding a history entry.
+
+use bus::RuntimeKind;
+use core_types::NetError;
+
+pub(super) fn error_page_html(url: &str, error: NetError, message: &str) -> String {
+    let (title, reason) = describe(url, error);
+    page_html(title, url, &reason, message)
+}
+
+/// Page for a load that failed because a runtime crashed while handling it.
+pub(super) fn runtime_crash_page_html(url: &str, which: RuntimeKind, error: &str) -> String {
+    let reason = format!(
+        "The browser's {} crashed while loading this page.",
+        which.name()
+    );
+    page_html("This page crashed the browser", url, &reason, error)
+}
+
+fn page_html(title: &str, url: &str, reason: &str, error: &str) -> String {
+    let url = escape_html(url);
+    let error = escape_html(error);
+    format!(
+        "<!DOCTYPE html>\
+<html><head><title>{title}</title><style>\
+body {{ background-color: #f4f4f4; color: #303030; margin: 0; padding: 48px; }}\
+h1 {{ font-size: 24px; margin-bottom: 8px; }}\
+.url {{ color: #606060; margin-bottom: 16px; }}\
+.detail {{ color: #808080; font-size: 13px; margin-top: 8px; }}\
+.retry {{ display: inline-block; margin-top: 24px; padding: 8px 16px; background-color: #1a73e8; color: #ffffff; text-decoration: none; }}\
+</style></head><body>\
+<h1>{title}</h1>\
+<p class=\"url\">{url}</p>\
+<p>{reason}</p>\
+<p class=\"detail\">{error}</p>\
+<a class=\"retry\" href=\"{url}\">Try again</a>\
+</body></html>"
+    )
+}
+
+fn describe(url: &str, error: NetError) -> (&'static str, String) {
+    match error {
+        NetError::Dns => (
+            "This site can't be found",
+            "The server's address could not be found. Check the address for typos.".to_string(),
+        ),
+        NetError::Tls => (
+            "This connection isn't secure",
+            "A secure connection to the server could not be set up.".to_string(),
+        ),
+        NetError::Timeout => (
+            "This site took too long to respond",
+            "The server stopped answering before the page finished loading.".to_string(),
+        ),
+        NetError::HttpStatus(code) => (
+            "This page isn't working",
+            format!("The server answered with HTTP {code}."),
+        ),
+        NetError::TooManyRedirects => (
+            "This page isn't working",
+            "The server redirected too many times.".to_string(),
+        ),
+        NetError::Io if url.starts_with("file:") => (
+            "This file can't be opened",
+            "The file does not exist or cannot be accessed.".to_string(),
+        ),
+        NetError::Io => (
+            "This site can't be reached",
+            "The connection to the server failed. Check the address and your connection."
+                .to_string(),
+        ),
+        NetError::TooLarge => (
+            "This page is too large",
+            "The document exceeds the size this browser loads.".to_string(),
+        ),
+        NetError::Cancelled => (
+            "Loading was cancelled",
+            "The request was cancelled before a response arrived.".to_string(),
+        ),
+    }
+}
+
+pub(super) fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
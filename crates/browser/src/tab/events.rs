@@ -1,12 +1,48 @@
 use super::Tab;
 use crate::page::RestyleHint;
-use bus::CoreEvent;
+use bus::{CoreCommand, CoreEvent, InProcess};
 use core_types::ResourceKind;
 use html::{DomPatch, PatchKey};
 
 impl Tab {
     pub fn on_core_event(&mut self, evt: CoreEvent) {
+        self.record_event(&evt);
+
+        if let CoreEvent::NetworkRedirected {
+            tab_id, request_id, ..
+        }
+        | CoreEvent::NetworkConnection {
+            tab_id, request_id, ..
+        }
+        | CoreEvent::NetworkStart {
+            tab_id, request_id, ..
+        }
+        | CoreEvent::NetworkChunk {
+            tab_id, request_id, ..
+        }
+        | CoreEvent::NetworkDone {
+            tab_id, request_id, ..
+        }
+        | CoreEvent::NetworkError {
+            tab_id, request_id, ..
+        } = &evt
+            && self.is_current(*tab_id, *request_id)
+        {
+            self.network_log.record_event(&evt);
+        }
+
         match evt {
+            CoreEvent::NetworkRedirected {
+                tab_id,
+                request_id,
+                stylesheet_slot_id: _,
+                kind: ResourceKind::Html,
+                from: _,
+                to,
+            } if self.is_current(tab_id, request_id) => {
+                self.on_html_network_redirected(to);
+            }
+
             CoreEvent::NetworkStart {
                 tab_id,
                 request_id,
@@ -46,20 +82,12 @@ impl Tab {
                 stylesheet_slot_id: _,
                 kind: ResourceKind::Html,
                 url,
-                error_kind,
-                status_code,
                 error,
+                message,
             } if self.is_current(tab_id, request_id) => {
-                self.on_html_network_error(url, error_kind, status_code, error);
+                self.on_html_network_error(url, error, message, request_id);
             }
 
-            CoreEvent::DomUpdate {
-                tab_id,
-                request_id,
-                dom,
-            } if self.is_current(tab_id, request_id) => {
-                self.on_dom_update(dom, request_id);
-            }
             CoreEvent::DomPatchUpdate {
                 tab_id,
                 request_id,
@@ -75,6 +103,11 @@ impl Tab {
                 }
                 match self.dom_store.apply(handle, from, to, &patches) {
                     Ok(()) => {
+                        self.send_cmd(CoreCommand::DomUpdateApplied {
+                            tab_id,
+                            request_id,
+                            version: to,
+                        });
                         let dirty_attribute_keys = patch_attribute_keys(&patches);
                         let dirty_attribute_nodes = match self
                             .dom_store
@@ -156,18 +189,10 @@ impl Tab {
                 stylesheet_slot_id: Some(stylesheet_slot_id),
                 kind: ResourceKind::Css,
                 url,
-                error_kind,
-                status_code,
                 error,
+                message,
             } if self.is_current(tab_id, request_id) => {
-                self.on_css_network_error(
-                    stylesheet_slot_id,
-                    url,
-                    error_kind,
-                    status_code,
-                    error,
-                    request_id,
-                );
+                self.on_css_network_error(stylesheet_slot_id, url, error, message, request_id);
             }
             CoreEvent::NetworkError {
                 tab_id,
@@ -175,13 +200,93 @@ impl Tab {
                 stylesheet_slot_id: _,
                 kind: ResourceKind::Image,
                 url,
-                error_kind: _,
-                status_code: _,
-                error,
+                error: _,
+                message,
+            } if self.is_current(tab_id, request_id) => {
+                self.on_image_network_error(url, message);
+            }
+
+            CoreEvent::NetworkChunk {
+                tab_id,
+                request_id,
+                stylesheet_slot_id: _,
+                kind: ResourceKind::Icon,
+                url,
+                bytes,
+            } if self.is_current(tab_id, request_id) => {
+                self.on_icon_network_chunk(url, bytes);
+            }
+            CoreEvent::NetworkDone {
+                tab_id,
+                request_id,
+                stylesheet_slot_id: _,
+                kind: ResourceKind::Icon,
+                response,
+                ..
+            } if self.is_current(tab_id, request_id) => {
+                self.on_icon_network_done(response.requested_url);
+            }
+            CoreEvent::NetworkError {
+                tab_id,
+                request_id,
+                stylesheet_slot_id: _,
+                kind: ResourceKind::Icon,
+                url,
+                error: _,
+                message,
             } if self.is_current(tab_id, request_id) => {
-                self.on_image_network_error(url, error);
+                self.on_icon_network_error(url, message);
             }
 
+            CoreEvent::ImageDecoded {
+                tab_id,
+                request_id,
+                kind,
+                url,
+                image,
+            } if self.is_current(tab_id, request_id) => {
+                self.on_image_decoded(kind, url, image);
+            }
+
+            CoreEvent::ScriptParsed {
+                tab_id,
+                request_id,
+                source,
+            } if self.is_current(tab_id, request_id) => {
+                self.on_script_parsed(source);
+            }
+            CoreEvent::ResourceHint {
+                tab_id,
+                request_id,
+                url,
+                kind,
+            } if self.is_current(tab_id, request_id) => {
+                self.on_resource_hint(request_id, url, kind);
+            }
+            CoreEvent::ResourceTruncated {
+                tab_id,
+                request_id,
+                limit,
+            } if self.is_current(tab_id, request_id) => {
+                self.on_resource_truncated(limit);
+            }
+            CoreEvent::ScriptDone {
+                tab_id,
+                request_id,
+                result,
+            } if self.is_current(tab_id, request_id) => {
+                self.on_script_done(result);
+            }
+
+            CoreEvent::CssImport {
+                tab_id,
+                request_id,
+                stylesheet_slot_id,
+                href,
+                media,
+            } if self.is_current(tab_id, request_id) => {
+                self.on_css_import(request_id, stylesheet_slot_id, href, media);
+            }
             CoreEvent::CssDecodedBlock {
                 tab_id,
                 request_id,
@@ -200,6 +305,42 @@ impl Tab {
                 self.on_css_sheet_done(stylesheet_slot_id, url);
             }
 
+            CoreEvent::LayoutReady {
+                tab_id,
+                request_id,
+                key,
+                layout,
+            } if self.is_current(tab_id, request_id) => {
+                self.on_layout_ready(key, layout.map(InProcess::into_inner));
+            }
+
+            CoreEvent::FilesPicked {
+                tab_id,
+                request_id,
+                input_id,
+                paths,
+            } if self.is_current(tab_id, request_id) => {
+                self.on_files_picked(input_id, paths);
+            }
+
+            CoreEvent::RuntimeCrashed {
+                tab_id,
+                request_id,
+                which,
+                error,
+            } if self.is_current(tab_id, request_id) => {
+                self.on_runtime_crashed(which, error);
+            }
+
+            CoreEvent::Metrics {
+                tab_id,
+                request_id,
+                which: _,
+                metrics,
+            } if self.is_current(tab_id, request_id) => {
+                self.metrics.merge(&metrics);
+            }
+
             _ => {}
         }
     }
@@ -209,7 +350,9 @@ fn patch_attribute_keys(patches: &[DomPatch]) -> Vec<PatchKey> {
     patches
         .iter()
         .filter_map(|patch| match patch {
-            DomPatch::SetAttributes { key, .. } => Some(*key),
+            DomPatch::SetAttributes { key, .. }
+            | DomPatch::SetAttribute { key, .. }
+            | DomPatch::RemoveAttribute { key, .. } => Some(*key),
             _ => None,
         })
         .collect()
@@ -0,0 +1,32 @@
+use std::path::PathBuf;
+
+use super::Tab;
+use crate::rendering::{RenderInvalidationEntryPoint, render_invalidation_request};
+use bus::CoreCommand;
+use html::internal::Id;
+
+impl Tab {
+    /// Ask the platform to show the native file picker for a file input.
+    pub(super) fn pick_files(&self, input_id: Id) {
+        let Some(multiple) = self
+            .page
+            .form_controls
+            .file_input_accepts_multiple(input_id)
+        else {
+            return;
+        };
+        self.send_cmd(CoreCommand::PickFiles {
+            tab_id: self.tab_id,
+            request_id: self.nav_gen,
+            input_id,
+            multiple,
+        });
+    }
+
+    pub(super) fn on_files_picked(&mut self, input_id: Id, paths: Vec<PathBuf>) {
+        self.document_input.set_chosen_files(input_id, paths);
+        self.request_render_work(render_invalidation_request(
+            RenderInvalidationEntryPoint::InputStateChanged,
+        ));
+    }
+}
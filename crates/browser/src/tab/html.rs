@@ -1,12 +1,29 @@
 use super::Tab;
+use super::VIEW_SOURCE_PREFIX;
+use super::error_page::{error_page_html, runtime_crash_page_html};
+use super::nav::ABOUT_PREFIX;
 use super::state::DocumentLoadState;
-use super::status::{format_network_error, response_summary};
+use super::status::{format_network_error, response_summary, truncation_warning};
+use crate::history::HistoryUpdate;
 use crate::page::RestyleHint;
-use bus::CoreCommand;
-use core_types::{NetworkErrorKind, NetworkResponseInfo, RequestId};
+use bus::{CoreCommand, ParseLimit, RuntimeKind};
+use core_types::{NetError, NetworkResponseInfo, RequestId};
 use html::Node;
 
+/// Markup the escaped source of a `view-source:` document is streamed into.
+const VIEW_SOURCE_PROLOGUE: &[u8] = b"<!DOCTYPE html><pre>";
+
 impl Tab {
+    pub(super) fn on_html_network_redirected(&mut self, to: String) {
+        self.last_status = Some(format!("Redirected to {to}"));
+        self.url = if self.view_source {
+            format!("{VIEW_SOURCE_PREFIX}{to}")
+        } else {
+            to
+        };
+        self.poke_redraw();
+    }
+
     pub(super) fn on_html_network_start(
         &mut self,
         response: NetworkResponseInfo,
@@ -16,10 +33,14 @@ impl Tab {
         self.dom_handle = None;
         self.document_load = DocumentLoadState {
             response: Some(response.clone()),
-            bytes_received: 0,
+            ..DocumentLoadState::default()
         };
         self.stylesheet_loads.clear();
-        self.url = response.final_url.clone();
+        self.url = if self.view_source {
+            format!("{VIEW_SOURCE_PREFIX}{}", response.final_url)
+        } else {
+            response.final_url.clone()
+        };
         self.page.start_nav(response.display_url());
         self.clear_render_orchestration_state();
         self.loading = true;
@@ -30,7 +51,16 @@ impl Tab {
         self.send_cmd(CoreCommand::ParseHtmlStart {
             tab_id: self.tab_id,
             request_id,
+            content_type: response.content_type.clone(),
+            limits: self.parse_limits,
         });
+        if self.view_source {
+            self.send_cmd(CoreCommand::ParseHtmlChunk {
+                tab_id: self.tab_id,
+                request_id,
+                bytes: VIEW_SOURCE_PROLOGUE.to_vec(),
+            });
+        }
         self.poke_redraw();
     }
 
@@ -39,6 +69,11 @@ impl Tab {
             .document_load
             .bytes_received
             .saturating_add(bytes.len());
+        let bytes = if self.view_source {
+            escape_source_bytes(&bytes)
+        } else {
+            bytes
+        };
         self.send_cmd(CoreCommand::ParseHtmlChunk {
             tab_id: self.tab_id,
             request_id,
@@ -52,9 +87,13 @@ impl Tab {
         bytes_received: usize,
         request_id: RequestId,
     ) {
+        // A document cut off at the byte limit was recorded when it was.
+        let cut_off = self.document_load.complete;
         self.document_load = DocumentLoadState {
             response: Some(response.clone()),
             bytes_received,
+            complete: true,
+            ..std::mem::take(&mut self.document_load)
         };
         self.send_cmd(CoreCommand::ParseHtmlDone {
             tab_id: self.tab_id,
@@ -64,29 +103,125 @@ impl Tab {
             "Document response complete • {}",
             response_summary(&response, bytes_received)
         ));
+        if !cut_off {
+            self.record_history_visit(response.final_url);
+        }
         self.poke_redraw();
     }
 
+    /// The parser dropped part of the document at one of its limits. Input
+    /// cut off at the byte limit ends the document: the parser has finished
+    /// it and the rest of the download is stopped.
+    pub(super) fn on_resource_truncated(&mut self, limit: ParseLimit) {
+        self.document_load.truncated.get_or_insert(limit);
+        if limit == ParseLimit::Bytes && !self.document_load.complete {
+            self.document_load.complete = true;
+            if let Some(response) = &self.document_load.response {
+                self.record_history_visit(response.final_url.clone());
+            }
+        }
+        self.poke_redraw();
+    }
+
+    /// A warning for the page when the parser could not show all of it.
+    pub(super) fn truncation_warning(&self) -> Option<String> {
+        let limit = self.document_load.truncated?;
+        Some(truncation_warning(limit, &self.parse_limits))
+    }
+
+    fn record_history_visit(&mut self, final_url: String) {
+        if self.view_source || final_url.starts_with(ABOUT_PREFIX) {
+            return;
+        }
+        self.history_updates.push(HistoryUpdate::Visit {
+            url: final_url.clone(),
+        });
+        self.history_visit = Some((final_url, String::new()));
+        self.report_history_title();
+    }
+
     pub(super) fn on_html_network_error(
         &mut self,
         url: String,
-        error_kind: NetworkErrorKind,
-        status_code: Option<u16>,
-        error: String,
+        error: NetError,
+        message: String,
+        request_id: RequestId,
     ) {
+        if self.document_load.complete {
+            // The parser read all of the document it will and stopped the
+            // download.
+            return;
+        }
         self.loading = false;
-        self.last_status = Some(format_network_error(
-            "document",
-            &url,
-            error_kind,
-            status_code,
-            &error,
-        ));
+        self.last_status = Some(format_network_error("document", &url, error, &message));
+        // A partially received document stays on screen; a failure before any
+        // response replaces the previous page with an error page.
+        if error != NetError::Cancelled && self.document_load.response.is_none() {
+            self.show_error_page(error_page_html(&url, error, &message), request_id);
+        }
+        self.poke_redraw();
+    }
+
+    /// A runtime panicked on a command of the current navigation and lost
+    /// its state for it.
+    ///
+    /// Network and stylesheet crashes reload the page once. A parser crash
+    /// would recur on the same bytes, so it shows an error page right away,
+    /// as does a second crash on the same URL.
+    pub(super) fn on_runtime_crashed(&mut self, which: RuntimeKind, error: String) {
+        let url = self.current_url().unwrap_or(&self.url).to_string();
+        if self.error_page {
+            self.loading = false;
+            self.last_status = Some(format!("The {} crashed: {error}", which.name()));
+            self.poke_redraw();
+            return;
+        }
+        if which != RuntimeKind::Parse && self.crash_reloaded_url.as_deref() != Some(&url) {
+            self.crash_reloaded_url = Some(url);
+            self.refresh();
+            self.last_status = Some(format!("The {} crashed; reloading …", which.name()));
+            return;
+        }
+
+        // Leave the broken load behind under a fresh request id so its
+        // remaining network events are ignored.
+        self.send_cmd(CoreCommand::CancelGeneration {
+            tab_id: self.tab_id,
+            request_id: self.nav_gen,
+        });
+        self.nav_gen = self.nav_gen.wrapping_add(1);
+        self.loading = false;
+        self.last_status = Some(format!("The {} crashed: {error}", which.name()));
+        self.show_error_page(runtime_crash_page_html(&url, which, &error), self.nav_gen);
         self.poke_redraw();
     }
 
-    pub(super) fn on_dom_update(&mut self, dom: Box<Node>, request_id: RequestId) {
-        self.on_dom_update_with_restyle(dom, request_id, RestyleHint::document_replaced());
+    fn show_error_page(&mut self, html: String, request_id: RequestId) {
+        self.error_page = true;
+        self.show_generated_page(html, request_id);
+    }
+
+    /// Parse `html` as the document of `request_id` without fetching anything.
+    pub(super) fn show_generated_page(&mut self, html: String, request_id: RequestId) {
+        self.dom_store.clear();
+        self.dom_handle = None;
+        self.stylesheet_loads.clear();
+        self.clear_render_orchestration_state();
+        self.send_cmd(CoreCommand::ParseHtmlStart {
+            tab_id: self.tab_id,
+            request_id,
+            content_type: Some("text/html; charset=utf-8".to_string()),
+            limits: self.parse_limits,
+        });
+        self.send_cmd(CoreCommand::ParseHtmlChunk {
+            tab_id: self.tab_id,
+            request_id,
+            bytes: html.into_bytes(),
+        });
+        self.send_cmd(CoreCommand::ParseHtmlDone {
+            tab_id: self.tab_id,
+            request_id,
+        });
     }
 
     pub(super) fn on_dom_update_with_restyle(
@@ -97,6 +232,7 @@ impl Tab {
     ) {
         let render_work = self.page.replace_dom(dom, restyle_hint);
         self.page.update_head_metadata();
+        self.report_history_title();
         self.page
             .seed_input_values_from_dom(&mut self.document_input.input_values);
         self.page.update_visible_text_cache();
@@ -105,6 +241,12 @@ impl Tab {
 
         let pending = self.page.pending_count();
         self.loading = pending > 0;
+        self.document_load.subresources = self.document_load.subresources.max(pending);
+        if self.error_page {
+            // Keep the network error as the status.
+            self.request_render_work(render_work);
+            return;
+        }
         let response = self.document_load.response.as_ref();
         let base = if pending > 0 {
             format!("Document parsed • fetching {pending} stylesheet(s)")
@@ -120,4 +262,42 @@ impl Tab {
         });
         self.request_render_work(render_work);
     }
+
+    /// Queue a title update for the visited document once its `<title>` is
+    /// known or whenever it changes.
+    fn report_history_title(&mut self) {
+        let Some((url, reported)) = &mut self.history_visit else {
+            return;
+        };
+        let Some(title) = self
+            .page
+            .head
+            .title
+            .as_deref()
+            .map(str::trim)
+            .filter(|title| !title.is_empty() && title != reported)
+        else {
+            return;
+        };
+        *reported = title.to_string();
+        self.history_updates.push(HistoryUpdate::Title {
+            url: url.clone(),
+            title: title.to_string(),
+        });
+    }
+}
+
+/// Escape markup-significant bytes so the HTML parser yields the source as
+/// text. Both are ASCII, so chunk boundaries inside multi-byte UTF-8
+/// sequences are left intact.
+fn escape_source_bytes(bytes: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(bytes.len());
+    for &byte in bytes {
+        match byte {
+            b'&' => out.extend_from_slice(b"&amp;"),
+            b'<' => out.extend_from_slice(b"&lt;"),
+            _ => out.push(byte),
+        }
+    }
+    out
 }
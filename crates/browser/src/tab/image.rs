@@ -1,5 +1,14 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use bus::{CoreCommand, DecodedImage};
+use core_types::ResourceKind;
+
 use super::Tab;
 use crate::rendering::{RenderInvalidationEntryPoint, render_invalidation_request};
+use crate::user_data::{downloads_dir, sanitize_file_name, unused_file_path};
 
 impl Tab {
     pub(super) fn on_image_network_chunk(&mut self, url: String, bytes: Vec<u8>) {
@@ -7,7 +16,21 @@ impl Tab {
     }
 
     pub(super) fn on_image_network_done(&mut self, url: String) {
-        self.resources.on_network_done(&url, self.repaint.clone());
+        let decode = self.image_decoder(ResourceKind::Image);
+        self.resources.on_network_done(&url, decode);
+    }
+
+    pub(super) fn on_icon_network_chunk(&mut self, url: String, bytes: Vec<u8>) {
+        self.icons.on_network_chunk(&url, &bytes);
+    }
+
+    pub(super) fn on_icon_network_done(&mut self, url: String) {
+        let decode = self.image_decoder(ResourceKind::Icon);
+        self.icons.on_network_done(&url, decode);
+    }
+
+    pub(super) fn on_icon_network_error(&mut self, url: String, error: String) {
+        self.icons.on_network_error(&url, error);
     }
 
     pub(super) fn on_image_network_error(&mut self, url: String, error: String) {
@@ -16,4 +39,73 @@ impl Tab {
             RenderInvalidationEntryPoint::ResourceStateChanged,
         ));
     }
+
+    /// Sends a fetched image body to the image runtime for decoding.
+    fn image_decoder(&self, kind: ResourceKind) -> impl FnOnce(String, Arc<[u8]>) + use<> {
+        let cmd_tx = self.cmd_tx.clone();
+        let (tab_id, request_id) = (self.tab_id, self.nav_gen);
+        move |url, bytes| {
+            if let Some(tx) = cmd_tx {
+                let _ = tx.send(CoreCommand::DecodeImage {
+                    tab_id,
+                    request_id,
+                    kind,
+                    url,
+                    bytes,
+                });
+            }
+        }
+    }
+
+    pub(super) fn on_image_decoded(
+        &mut self,
+        kind: ResourceKind,
+        url: String,
+        image: Result<DecodedImage, String>,
+    ) {
+        let images = match kind {
+            ResourceKind::Icon => &mut self.icons,
+            _ => &mut self.resources,
+        };
+        // The texture is uploaded when the next frame pumps the images.
+        if images.on_image_decoded(&url, image) {
+            self.poke_redraw();
+        }
+    }
+
+    /// Save a loaded image of this page to the downloads directory and
+    /// report the outcome in the status line.
+    pub(super) fn save_image(&mut self, url: &str) {
+        self.last_status = Some(match self.write_image_to_downloads(url) {
+            Ok(path) => format!("Saved image to {}", path.display()),
+            Err(err) => format!("Could not save image: {err}"),
+        });
+        self.poke_redraw();
+    }
+
+    fn write_image_to_downloads(&self, url: &str) -> io::Result<PathBuf> {
+        let bytes = self
+            .resources
+            .encoded_image(url)
+            .ok_or_else(|| io::Error::other("image is not loaded"))?;
+        let dir = downloads_dir().ok_or_else(|| io::Error::other("no downloads directory"))?;
+        fs::create_dir_all(&dir)?;
+        let path = unused_file_path(&dir, &image_file_name(url));
+        fs::write(&path, bytes)?;
+        Ok(path)
+    }
+}
+
+/// File name for a saved image: the last URL path segment with characters
+/// that are unsafe in file names replaced.
+fn image_file_name(url: &str) -> String {
+    let segment = url::Url::parse(url).ok().and_then(|url| {
+        url.path_segments()
+            .and_then(|mut segments| segments.rfind(|s| !s.is_empty()))
+            .map(str::to_owned)
+    });
+    match segment {
+        Some(segment) => sanitize_file_name(&segment),
+        None => "image".to_string(),
+    }
 }
@@ -0,0 +1,72 @@
+use super::Tab;
+use crate::page::{LayoutJobRequest, LayoutOffload};
+use bus::{CoreCommand, InProcess, LayoutJob};
+use egui::Context;
+use gfx::EguiTextMeasurer;
+use html::Node;
+use layout::{RetainedLayoutArtifact, RetainedLayoutKey, TextMeasurer};
+
+impl Tab {
+    /// Send a relayout the page handed off to the layout runtime, with a
+    /// snapshot of the DOM and a measurer for the fonts the page paints with.
+    pub(super) fn send_layout_job(&mut self, ctx: &Context, request: LayoutJobRequest) {
+        match self.materialize_dom() {
+            Ok(dom) => self.send_cmd(CoreCommand::LayoutDocument {
+                tab_id: self.tab_id,
+                request_id: self.nav_gen,
+                job: InProcess::new(Box::new(
+                    request.into_job(dom, Box::new(EguiTextMeasurer::new(ctx))),
+                )),
+            }),
+            // Lay it out in the frame instead.
+            Err(error) => {
+                let _ = self.page.finish_layout_job(request.key(), Err(error));
+            }
+        }
+    }
+
+    /// A layout job for the current document at `viewport_width`, with the
+    /// stylesheets, images, and resized controls the page would lay out with.
+    pub fn layout_job(
+        &self,
+        viewport_width: f32,
+        measurer: Box<dyn TextMeasurer + Send>,
+    ) -> Result<LayoutJob, String> {
+        let offload = LayoutOffload {
+            viewport_width,
+            resources: &self.resources,
+            resized_controls: &self.document_input.interaction.resized_controls,
+        };
+        let request = self
+            .page
+            .snapshot_layout_job(&offload)
+            .ok_or_else(|| "no document".to_string())?;
+        Ok(request.into_job(self.materialize_dom()?, measurer))
+    }
+
+    fn materialize_dom(&self) -> Result<Box<Node>, String> {
+        self.dom_handle
+            .ok_or_else(|| "no DOM handle".to_string())
+            .and_then(|handle| {
+                self.dom_store
+                    .materialize(handle)
+                    .map_err(|err| format!("{err:?}"))
+            })
+    }
+
+    pub(super) fn on_layout_ready(
+        &mut self,
+        key: RetainedLayoutKey,
+        layout: Result<Box<RetainedLayoutArtifact>, String>,
+    ) {
+        if self.page.finish_layout_job(key, layout) {
+            self.poke_redraw();
+        }
+    }
+
+    /// Whether the page still has to be laid out, in the frame or by the
+    /// layout runtime.
+    pub(crate) fn layout_dirty(&self) -> bool {
+        self.page.layout_dirty()
+    }
+}
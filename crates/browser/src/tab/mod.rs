@@ -11,18 +11,30 @@
 //!   There is no cross-tab sharing of DOM, resources, or input state; any
 //!   shared work must go through the bus/runtime layers.
 
+mod about_page;
 mod css;
 mod discovery;
 mod dom_style;
+mod error_page;
 mod events;
+mod file_input;
 mod html;
 mod image;
+mod layout_job;
 mod nav;
+mod print;
+mod progress;
+mod recording;
+mod script;
 mod state;
 mod status;
 #[cfg(test)]
 mod tests;
 mod ui;
 
+pub(crate) use self::about_page::{ABOUT_HISTORY, ABOUT_METRICS, PageMetrics, about_page_html};
+pub(crate) use self::nav::VIEW_SOURCE_PREFIX;
+pub use self::progress::LoadProgress;
 pub use self::state::Tab;
 pub use dom_style::{inherited_color, page_background};
+pub(crate) use ui::elide_end;
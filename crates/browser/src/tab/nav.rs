@@ -1,7 +1,15 @@
 use super::Tab;
-use bus::CoreCommand;
-use core_types::ResourceKind;
-use url::Url;
+use super::about_page::is_shell_page;
+use crate::history::HistoryUpdate;
+use crate::session::TabSession;
+use bus::{CoreCommand, RuntimeMetrics};
+use core_types::{RequestPayload, ResourceKind};
+use gfx::input::FormSubmission;
+
+/// URL prefix that shows a document's source instead of rendering it.
+pub(crate) const VIEW_SOURCE_PREFIX: &str = "view-source:";
+/// Scheme of internal pages, generated by the shell or by the net crate.
+pub(super) const ABOUT_PREFIX: &str = "about:";
 
 impl Tab {
     // -- Navigation Methods ---
@@ -17,52 +25,160 @@ impl Tab {
                 return;
             }
         };
+        if self.error_page && self.current_url() == Some(url.as_str()) {
+            // "Try again" on an error page, or re-entering the failed URL.
+            self.refresh();
+            return;
+        }
         let current_url = self.url.clone();
         self.url = url.clone();
 
         if self.is_same_document_navigation_with(&current_url, &url) {
-            self.history.truncate(self.history_index + 1);
-            self.history.push(url.clone());
-            self.history_index = self.history.len() - 1;
+            self.scroll_to_fragment_of(&url);
+            self.push_history(url);
             self.poke_redraw();
             return;
         }
 
-        // record to history (truncate forward branch)
-        self.history.truncate(self.history_index + 1);
-        self.history.push(url.clone());
-        self.history_index = self.history.len() - 1;
-
+        self.push_history(url.clone());
         self.start_fetch(url);
     }
 
+    /// Navigate to the response of a form submission. History keeps only
+    /// the URL, so reloading a POST result or going back to it fetches the
+    /// URL with GET.
+    pub fn submit_form(&mut self, submission: FormSubmission) {
+        let FormSubmission { url, payload } = submission;
+        if payload == RequestPayload::default() {
+            self.navigate_to_new(url);
+            return;
+        }
+        self.url = url.clone();
+        self.push_history(url.clone());
+        self.start_fetch_with_payload(url, payload);
+    }
+
+    /// URL of the current history entry (unaffected by edits in the URL bar).
+    pub fn current_url(&self) -> Option<&str> {
+        self.history.get(self.history_index).map(String::as_str)
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        self.history_index > 0
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        self.history_index + 1 < self.history.len()
+    }
+
     pub fn go_back(&mut self) {
-        if self.history_index > 0 {
-            self.history_index -= 1;
-            let url = self.history[self.history_index].clone();
-            self.load_current(url);
+        if self.can_go_back() {
+            self.go_to_history_entry(self.history_index - 1);
         }
     }
 
     pub fn go_forward(&mut self) {
-        if self.history_index + 1 < self.history.len() {
-            self.history_index += 1;
-            let url = self.history[self.history_index].clone();
-            self.load_current(url);
+        if self.can_go_forward() {
+            self.go_to_history_entry(self.history_index + 1);
         }
     }
 
     pub fn refresh(&mut self) {
         if let Some(url) = self.history.get(self.history_index).cloned() {
+            let scroll_y = self.document_input.interaction.scroll_y;
             self.url = url.clone();
             self.start_fetch(url);
+            if scroll_y > 0.0 {
+                self.document_input.interaction.pending_scroll_y = Some(scroll_y);
+            }
+        }
+    }
+
+    /// Abort the current document load and all of its subresources.
+    ///
+    /// Subresource fetches share the navigation's request id, so a single
+    /// `CancelRequest` stops them all; whatever was already parsed stays on
+    /// screen.
+    pub fn stop(&mut self) {
+        if !self.loading {
+            return;
+        }
+        self.send_cmd(CoreCommand::CancelRequest {
+            tab_id: self.tab_id,
+            request_id: self.nav_gen,
+        });
+        self.loading = false;
+        self.last_status = Some("Stopped".to_string());
+        self.poke_redraw();
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.loading
+    }
+
+    /// URL of the current `about:` page while it waits for its markup.
+    pub fn pending_about_page(&self) -> Option<&str> {
+        self.current_url().filter(|_| self.about_page_pending)
+    }
+
+    /// Show `html` as the pending `about:` page.
+    pub fn show_about_page(&mut self, html: String) {
+        if !self.about_page_pending {
+            return;
+        }
+        self.about_page_pending = false;
+        self.show_generated_page(html, self.nav_gen);
+    }
+
+    /// History updates since the last call, oldest first.
+    pub fn take_history_updates(&mut self) -> Vec<HistoryUpdate> {
+        std::mem::take(&mut self.history_updates)
+    }
+
+    /// Snapshot of this tab's navigation state for session restore, or `None`
+    /// if nothing was ever loaded.
+    pub fn session_state(&self) -> Option<TabSession> {
+        if self.history.is_empty() {
+            return None;
+        }
+        let mut history_scroll = self.history_scroll.clone();
+        if let Some(slot) = history_scroll.get_mut(self.history_index) {
+            // A scroll still under way or not applied yet owns the offset.
+            *slot = self.document_input.interaction.scroll_destination();
+        }
+        Some(TabSession {
+            history: self.history.clone(),
+            history_scroll,
+            history_index: self.history_index,
+        })
+    }
+
+    /// Adopt a saved history stack. Nothing is fetched until
+    /// [`Tab::load_restored_entry`] runs, which needs the bus sender.
+    pub fn restore_session(&mut self, session: TabSession) {
+        self.history = session.history;
+        self.history_scroll = session.history_scroll;
+        self.history_scroll.resize(self.history.len(), 0.0);
+        self.history_index = session
+            .history_index
+            .min(self.history.len().saturating_sub(1));
+    }
+
+    /// Fetch the current entry of a restored tab and queue its scroll offset.
+    pub fn load_restored_entry(&mut self) {
+        if self.history_index < self.history.len() {
+            self.load_history_entry(self.history_index);
         }
     }
 
     // -- Internal Helpers ---
     fn start_fetch(&mut self, url: String) {
+        self.start_fetch_with_payload(url, RequestPayload::default());
+    }
+
+    fn start_fetch_with_payload(&mut self, url: String, payload: RequestPayload) {
         if self.nav_gen > 0 {
-            self.send_cmd(CoreCommand::CancelRequest {
+            self.send_cmd(CoreCommand::CancelGeneration {
                 tab_id: self.tab_id,
                 request_id: self.nav_gen,
             });
@@ -70,33 +186,109 @@ impl Tab {
         self.document_input.clear_for_navigation();
         self.nav_gen = self.nav_gen.wrapping_add(1);
         let request_id = self.nav_gen;
+        let url = match url.strip_prefix(VIEW_SOURCE_PREFIX) {
+            Some(inner) => {
+                self.view_source = true;
+                inner.to_string()
+            }
+            None => {
+                self.view_source = false;
+                self.scroll_to_fragment_of(&url);
+                url
+            }
+        };
 
         self.loading = true;
+        self.error_page = false;
+        self.history_visit = None;
+        self.favicon_url = None;
+        self.resources.pause_animations();
+        self.icons.pause_animations();
+        self.network_log.clear();
+        self.metrics = RuntimeMetrics::default();
         self.document_load = Default::default();
         self.stylesheet_loads.clear();
         self.last_status = Some(format!("Fetching {url} …"));
         self.page.start_nav(&url);
         self.clear_render_orchestration_state();
 
-        self.send_cmd(CoreCommand::FetchStream {
-            tab_id: self.tab_id,
-            request_id,
-            stylesheet_slot_id: None,
-            url,
-            kind: ResourceKind::Html,
-        });
+        self.about_page_pending = is_shell_page(&url);
+        if self.about_page_pending {
+            self.last_status = None;
+            if let Some(recorder) = &mut self.recorder {
+                recorder.stop();
+            }
+        } else {
+            self.start_recording(request_id, &url);
+            self.send_fetch_with_payload(request_id, None, url, ResourceKind::Html, payload);
+        }
         self.poke_redraw();
     }
 
-    fn load_current(&mut self, url: String) {
+    /// Record `url` as the new current entry, dropping any forward branch.
+    fn push_history(&mut self, url: String) {
+        self.remember_scroll();
+        self.history.truncate(self.history_index + 1);
+        self.history_scroll.truncate(self.history_index + 1);
+        self.history.push(url);
+        self.history_scroll.push(0.0);
+        self.history_index = self.history.len() - 1;
+    }
+
+    fn remember_scroll(&mut self) {
+        if let Some(slot) = self.history_scroll.get_mut(self.history_index) {
+            *slot = self.document_input.interaction.scroll_y;
+        }
+    }
+
+    fn go_to_history_entry(&mut self, index: usize) {
+        self.remember_scroll();
+        self.load_history_entry(index);
+    }
+
+    fn load_history_entry(&mut self, index: usize) {
+        self.history_index = index;
+        let url = self.history[index].clone();
+        let scroll_y = self.history_scroll.get(index).copied().unwrap_or(0.0);
+        let fetched = self.load_current(url.clone());
+        let interaction = &mut self.document_input.interaction;
+        if scroll_y > 0.0 {
+            // `start_fetch` cleared interaction state; queue the restore after
+            // it. The saved offset wins over the entry's fragment.
+            interaction.scroll_target = None;
+            interaction.pending_scroll_y = Some(scroll_y);
+            interaction.pending_fragment = None;
+        } else if !fetched {
+            // The document stays, scrolled wherever the other entry left it:
+            // go to this entry's fragment, or its top.
+            interaction.scroll_target = None;
+            interaction.pending_fragment = None;
+            self.scroll_to_fragment_of(&url);
+            let interaction = &mut self.document_input.interaction;
+            if interaction.pending_fragment.is_none() {
+                interaction.pending_scroll_y = Some(0.0);
+            }
+        }
+    }
+
+    /// Returns `true` if a new document fetch was started.
+    fn load_current(&mut self, url: String) -> bool {
         // do NOT touch history; just fetch the given URL
         if self.is_same_document_navigation(&url) {
             self.url = url;
             self.poke_redraw();
-            return;
+            return false;
         }
         self.url = url.clone();
         self.start_fetch(url);
+        true
+    }
+
+    /// Queue scrolling to the fragment of `url`, if it has one.
+    fn scroll_to_fragment_of(&mut self, url: &str) {
+        if let Some(fragment) = weburl::parse(url).as_ref().and_then(weburl::fragment_id) {
+            self.document_input.interaction.pending_fragment = Some(fragment);
+        }
     }
 
     fn normalize_url(&mut self, url: &str) -> Result<String, &'static str> {
@@ -105,8 +297,21 @@ impl Tab {
             return Err("Cannot navigate to an empty URL");
         }
 
+        if let Some(inner) = trimmed.strip_prefix(VIEW_SOURCE_PREFIX) {
+            if inner.starts_with(VIEW_SOURCE_PREFIX) {
+                return Err("Cannot view the source of a source view");
+            }
+            if inner.starts_with(ABOUT_PREFIX) {
+                return Err("Cannot view the source of an internal page");
+            }
+            return self
+                .normalize_url(inner)
+                .map(|url| format!("{VIEW_SOURCE_PREFIX}{url}"));
+        }
+
         // Already a full URL with scheme we support
-        if trimmed.starts_with("http://")
+        if trimmed.starts_with(ABOUT_PREFIX)
+            || trimmed.starts_with("http://")
             || trimmed.starts_with("https://")
             || trimmed.starts_with("file://")
         {
@@ -126,17 +331,11 @@ impl Tab {
             return false;
         }
 
-        let Ok(current) = Url::parse(current_url) else {
-            return false;
-        };
-        let Ok(next) = Url::parse(next_url) else {
+        let (Some(current), Some(next)) = (weburl::parse(current_url), weburl::parse(next_url))
+        else {
             return false;
         };
 
-        current.scheme() == next.scheme()
-            && current.host_str() == next.host_str()
-            && current.port_or_known_default() == next.port_or_known_default()
-            && current.path() == next.path()
-            && current.query() == next.query()
+        weburl::without_fragment(&current) == weburl::without_fragment(&next)
     }
 }
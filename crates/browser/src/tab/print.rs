@@ -0,0 +1,42 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use super::Tab;
+use crate::print::{PrintError, PrintOptions, print_page};
+use crate::user_data::{downloads_dir, sanitize_file_name, unused_file_path};
+
+impl Tab {
+    /// Lay the current document out on pages and render them as a PDF.
+    pub fn print_pdf(&mut self, options: &PrintOptions) -> Result<Vec<u8>, PrintError> {
+        print_page(&mut self.page, options)
+    }
+
+    /// Print the current document to a PDF in the downloads directory and
+    /// report the outcome in the status line.
+    pub fn print_to_downloads(&mut self) {
+        self.last_status = Some(match self.write_pdf_to_downloads() {
+            Ok(path) => format!("Saved PDF to {}", path.display()),
+            Err(err) => format!("Could not print: {err}"),
+        });
+        self.poke_redraw();
+    }
+
+    /// Status line text, e.g. the outcome of the last load.
+    pub fn status(&self) -> Option<&str> {
+        self.last_status.as_deref()
+    }
+
+    fn write_pdf_to_downloads(&mut self) -> Result<PathBuf, PrintError> {
+        let pdf = self.print_pdf(&PrintOptions::default())?;
+        let dir = downloads_dir().ok_or_else(|| io::Error::other("no downloads directory"))?;
+        fs::create_dir_all(&dir)?;
+        let name = match self.page_title() {
+            Some(title) => sanitize_file_name(title),
+            None => "page".to_string(),
+        };
+        let path = unused_file_path(&dir, &format!("{name}.pdf"));
+        fs::write(&path, pdf)?;
+        Ok(path)
+    }
+}
@@ -0,0 +1,99 @@
+use super::Tab;
+
+/// Share of the progress bar the document body fills; the stylesheets it
+/// links fill the rest.
+const DOCUMENT_SHARE: f32 = 0.7;
+
+/// How far the current page load has come.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LoadProgress {
+    /// Document body bytes received so far.
+    pub document_bytes: usize,
+    /// Document body size the response announced, if any.
+    pub document_length: Option<u64>,
+    /// The whole document body has arrived.
+    pub document_complete: bool,
+    /// Stylesheets the page still waits for.
+    pub pending_subresources: usize,
+    /// Most stylesheets the page has waited for at once during this load.
+    pub subresources: usize,
+}
+
+impl LoadProgress {
+    /// Share of the load that is done, from 0 to 1, or `None` while the
+    /// document body is still arriving without an announced size.
+    pub fn fraction(&self) -> Option<f32> {
+        let document = if self.document_complete {
+            1.0
+        } else {
+            let length = self.document_length.filter(|length| *length > 0)?;
+            (self.document_bytes as f64 / length as f64).min(1.0) as f32
+        };
+        let subresources = if self.subresources == 0 {
+            1.0
+        } else {
+            let pending = self.pending_subresources.min(self.subresources);
+            1.0 - pending as f32 / self.subresources as f32
+        };
+        Some(DOCUMENT_SHARE * document + (1.0 - DOCUMENT_SHARE) * subresources)
+    }
+}
+
+impl Tab {
+    /// Progress of the page load, or `None` once the tab is not loading.
+    pub fn load_progress(&self) -> Option<LoadProgress> {
+        if !self.loading {
+            return None;
+        }
+        let load = &self.document_load;
+        let pending_subresources = self.page.pending_count();
+        Some(LoadProgress {
+            document_bytes: load.bytes_received,
+            document_length: load
+                .response
+                .as_ref()
+                .and_then(|response| response.content_length),
+            document_complete: load.complete,
+            pending_subresources,
+            subresources: load.subresources.max(pending_subresources),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LoadProgress;
+
+    fn assert_fraction(progress: LoadProgress, expected: f32) {
+        let fraction = progress.fraction().expect("known progress");
+        assert!(
+            (fraction - expected).abs() < 1e-6,
+            "{fraction} != {expected}"
+        );
+    }
+
+    #[test]
+    fn fraction_covers_document_bytes_then_pending_stylesheets() {
+        let streaming = LoadProgress {
+            document_bytes: 500,
+            document_length: Some(1000),
+            ..LoadProgress::default()
+        };
+        assert_fraction(streaming, 0.65);
+
+        let unknown_length = LoadProgress {
+            document_bytes: 500,
+            ..LoadProgress::default()
+        };
+        assert_eq!(unknown_length.fraction(), None);
+
+        let fetching_styles = LoadProgress {
+            document_bytes: 1000,
+            document_complete: true,
+            pending_subresources: 3,
+            subresources: 4,
+            ..LoadProgress::default()
+        };
+        assert_fraction(fetching_styles, 0.775);
+    }
+}
@@ -0,0 +1,49 @@
+use super::Tab;
+use bus::{CoreEvent, EventRecorder, Recording};
+use core_types::RequestId;
+use std::path::PathBuf;
+
+impl Tab {
+    /// Record the runtime events of every later navigation into a file in
+    /// `dir`, for [`Tab::replay`].
+    pub fn set_event_recording(&mut self, dir: impl Into<PathBuf>) {
+        self.recorder = Some(EventRecorder::new(dir));
+    }
+
+    /// Load a recorded navigation by feeding its events to the tab instead
+    /// of fetching and parsing the page. The tab must have no bus sender,
+    /// or the runtimes would load the page a second time alongside it.
+    pub fn replay(&mut self, recording: Recording) {
+        self.navigate_to_new(recording.url.clone());
+        let (tab_id, request_id) = (self.tab_id, self.nav_gen);
+        for evt in recording.into_events(tab_id, request_id) {
+            self.on_core_event(evt);
+        }
+    }
+
+    pub(super) fn start_recording(&mut self, request_id: RequestId, url: &str) {
+        let Some(recorder) = &mut self.recorder else {
+            return;
+        };
+        if let Err(err) = recorder.start(self.tab_id, request_id, url) {
+            eprintln!("event recording error: {err}");
+            recorder.stop();
+        }
+    }
+
+    pub(super) fn record_event(&mut self, evt: &CoreEvent) {
+        let Some((tab_id, request_id)) = evt.request() else {
+            return;
+        };
+        if !self.is_current(tab_id, request_id) {
+            return;
+        }
+        let Some(recorder) = &mut self.recorder else {
+            return;
+        };
+        if let Err(err) = recorder.record(evt) {
+            eprintln!("event recording error: {err}");
+            recorder.stop();
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use bus::CoreCommand;
+
+use super::Tab;
+
+impl Tab {
+    /// The parser stopped at the end of a script; the patches before it are
+    /// applied, so the script runs against the document parsed so far.
+    pub(super) fn on_script_parsed(&mut self, source: String) {
+        self.send_cmd(CoreCommand::RunScript {
+            tab_id: self.tab_id,
+            request_id: self.nav_gen,
+            source,
+        });
+    }
+
+    /// A script failing does not stop the page: parsing resumes either way.
+    pub(super) fn on_script_done(&mut self, result: Result<(), String>) {
+        if let Err(err) = result {
+            eprintln!("script error: {err}");
+        }
+        self.send_cmd(CoreCommand::ParseHtmlResume {
+            tab_id: self.tab_id,
+            request_id: self.nav_gen,
+        });
+    }
+}
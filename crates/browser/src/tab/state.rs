@@ -1,11 +1,14 @@
-use crate::dom_store::DomStore;
+use crate::history::HistoryUpdate;
 use crate::input_state::DocumentInputState;
+use crate::network_log::NetworkLog;
 use crate::page::PageState;
 use crate::rendering::{PendingRenderWork, RenderFrameExecutionTrace, RenderInvalidationRequest};
 use crate::resources::ResourceManager;
 use app_api::RepaintHandle;
-use bus::CoreCommand;
+use bus::{CoreCommand, EventRecorder, ParseLimit, ParseLimits, RuntimeMetrics};
 use core_types::{DomHandle, NetworkResponseInfo, RequestId, StylesheetSlotId, TabId};
+use css::ColorScheme;
+use dom_store::DomStore;
 use std::collections::HashMap;
 use std::sync::mpsc;
 
@@ -13,6 +16,13 @@ use std::sync::mpsc;
 pub(super) struct DocumentLoadState {
     pub(super) response: Option<NetworkResponseInfo>,
     pub(super) bytes_received: usize,
+    /// The whole document body has arrived, or as much of it as the parse
+    /// limits let the parser read.
+    pub(super) complete: bool,
+    /// The first parse limit the document ran into.
+    pub(super) truncated: Option<ParseLimit>,
+    /// Most stylesheets the page has waited for at once.
+    pub(super) subresources: usize,
 }
 
 #[derive(Clone, Debug)]
@@ -28,14 +38,45 @@ pub struct Tab {
     pub history: Vec<String>,
     pub history_index: usize,
     pub nav_gen: RequestId,
+    /// Last vertical scroll offset of each `history` entry, kept in lockstep
+    /// with `history` so back/forward can restore where the user left off.
+    pub(super) history_scroll: Vec<f32>,
 
     pub(super) loading: bool,
     pub(super) last_status: Option<String>,
     pub(super) document_load: DocumentLoadState,
+    /// Bounds documents are parsed within.
+    pub(super) parse_limits: ParseLimits,
+    /// The current document is shown as escaped source text.
+    pub(super) view_source: bool,
+    /// The current document is a generated error page for a failed load.
+    pub(super) error_page: bool,
+    /// The current document is an `about:` page whose markup the shell has
+    /// not supplied yet.
+    pub(super) about_page_pending: bool,
+    /// URL the current document was recorded under in the browsing history,
+    /// and the title last reported for it.
+    pub(super) history_visit: Option<(String, String)>,
+    /// History updates the shell has not collected yet.
+    pub(super) history_updates: Vec<HistoryUpdate>,
+    /// URL last reloaded because a runtime crashed while loading it; a
+    /// second crash on it shows an error page instead of looping.
+    pub(super) crash_reloaded_url: Option<String>,
     pub(super) stylesheet_loads: HashMap<StylesheetSlotId, StylesheetLoadState>,
+    /// Requests of the current navigation, for the network panel.
+    pub(super) network_log: NetworkLog,
+    /// Runtime counters of the current navigation, for `about:metrics`.
+    pub(super) metrics: RuntimeMetrics,
+    /// Writes the runtime events of each navigation to disk, if enabled.
+    pub(super) recorder: Option<EventRecorder>,
 
     pub(super) page: PageState,
     pub(super) resources: ResourceManager,
+    /// Page icons, kept apart from document images so icon loads never
+    /// invalidate rendering. Survives navigation as a per-tab icon cache.
+    pub(super) icons: ResourceManager,
+    /// Icon URL chosen for the current document.
+    pub(super) favicon_url: Option<String>,
     pub(super) repaint: Option<RepaintHandle>,
     pub(super) cmd_tx: Option<mpsc::Sender<CoreCommand>>,
     pub(super) document_input: DocumentInputState,
@@ -53,12 +94,25 @@ impl Tab {
             history: Vec::new(),
             history_index: 0,
             nav_gen: 0,
+            history_scroll: Vec::new(),
             loading: false,
             last_status: None,
             document_load: DocumentLoadState::default(),
+            parse_limits: ParseLimits::default(),
+            view_source: false,
+            error_page: false,
+            about_page_pending: false,
+            history_visit: None,
+            history_updates: Vec::new(),
+            crash_reloaded_url: None,
             stylesheet_loads: HashMap::new(),
+            network_log: NetworkLog::default(),
+            metrics: RuntimeMetrics::default(),
+            recorder: None,
             page: PageState::new(),
             resources: ResourceManager::new(),
+            icons: ResourceManager::new(),
+            favicon_url: None,
             repaint: None,
             cmd_tx: None,
             document_input: DocumentInputState::default(),
@@ -77,6 +131,28 @@ impl Tab {
         self.repaint = Some(h);
     }
 
+    /// Jump straight to scroll targets instead of animating page scrolls.
+    pub fn set_instant_scroll(&mut self, instant: bool) {
+        self.document_input.scroll_animation.set_instant(instant);
+    }
+
+    /// Parse documents within `limits`, from the next load on.
+    pub fn set_parse_limits(&mut self, limits: ParseLimits) {
+        self.parse_limits = limits;
+    }
+
+    /// Match `prefers-color-scheme` media queries against `color_scheme`.
+    pub fn set_color_scheme(&mut self, color_scheme: ColorScheme) {
+        let render_work = self.page.set_color_scheme(color_scheme);
+        let _ = self.request_optional_render_work(render_work);
+    }
+
+    /// Restyle links to `url` as visited, now and in documents loaded later.
+    pub fn mark_visited(&mut self, url: &str) {
+        let render_work = self.page.mark_visited(url);
+        let _ = self.request_optional_render_work(render_work);
+    }
+
     pub(super) fn is_current(&self, tab_id: TabId, request_id: RequestId) -> bool {
         tab_id == self.tab_id && request_id == self.nav_gen
     }
@@ -1,4 +1,5 @@
-use core_types::{NetworkErrorKind, NetworkResponseInfo};
+use bus::{ParseLimit, ParseLimits};
+use core_types::{NetError, NetworkResponseInfo};
 
 pub(super) fn response_summary(response: &NetworkResponseInfo, bytes_received: usize) -> String {
     let mut parts = Vec::new();
@@ -27,27 +28,43 @@ pub(super) fn response_summary(response: &NetworkResponseInfo, bytes_received: u
 pub(super) fn format_network_error(
     resource_label: &str,
     url: &str,
-    error_kind: NetworkErrorKind,
-    status_code: Option<u16>,
-    error: &str,
+    error: NetError,
+    message: &str,
 ) -> String {
-    match error_kind {
-        NetworkErrorKind::Cancelled => format!("Cancelled {resource_label} load: {url}"),
-        NetworkErrorKind::HttpStatus => match status_code {
-            Some(status_code) => {
-                format!("HTTP {status_code} while loading {resource_label}: {url}")
-            }
-            None => format!("HTTP error while loading {resource_label}: {url}"),
-        },
-        NetworkErrorKind::Transport => {
-            format!("Transport error loading {resource_label}: {url} ({error})")
+    match error {
+        NetError::Cancelled => format!("Cancelled {resource_label} load: {url}"),
+        NetError::HttpStatus(status_code) => {
+            format!("HTTP {status_code} while loading {resource_label}: {url}")
         }
-        NetworkErrorKind::LocalFile => {
-            format!("Local file error loading {resource_label}: {url} ({error})")
+        NetError::TooManyRedirects => {
+            format!("Too many redirects loading {resource_label}: {url} ({message})")
         }
-        NetworkErrorKind::Read => format!("Read error loading {resource_label}: {url} ({error})"),
-        NetworkErrorKind::ResourceLimit => {
-            format!("Resource limit loading {resource_label}: {url} ({error})")
+        NetError::Dns => format!("DNS error loading {resource_label}: {url} ({message})"),
+        NetError::Tls => format!("TLS error loading {resource_label}: {url} ({message})"),
+        NetError::Timeout => {
+            format!("Timed out loading {resource_label}: {url} ({message})")
         }
+        NetError::Io => format!("I/O error loading {resource_label}: {url} ({message})"),
+        NetError::TooLarge => {
+            format!("Resource limit loading {resource_label}: {url} ({message})")
+        }
+    }
+}
+
+/// The banner shown over a page the parser stopped short of.
+pub(super) fn truncation_warning(limit: ParseLimit, limits: &ParseLimits) -> String {
+    match limit {
+        ParseLimit::Bytes => format!(
+            "This page is larger than {} bytes; only the first {} bytes are shown.",
+            limits.max_bytes, limits.max_bytes
+        ),
+        ParseLimit::Nodes => format!(
+            "This page has more than {} nodes; only the first {} are shown.",
+            limits.max_nodes, limits.max_nodes
+        ),
+        ParseLimit::Depth => format!(
+            "This page nests elements more than {} deep; deeper content is not shown.",
+            limits.max_depth
+        ),
     }
 }
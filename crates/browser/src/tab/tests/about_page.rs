@@ -0,0 +1,63 @@
+use super::super::about_page::{ABOUT_HISTORY, ABOUT_METRICS, PageMetrics, about_page_html};
+use crate::history::HistoryStore;
+use bus::RuntimeMetrics;
+use std::time::Duration;
+
+fn store() -> HistoryStore {
+    let mut store = HistoryStore::in_memory();
+    store.record_visit("https://a.test/", 1_000).unwrap();
+    store.set_title("https://a.test/", "Alpha <page>").unwrap();
+    store.record_visit("https://b.test/", 4_000).unwrap();
+    store.record_visit("https://b.test/", 4_600).unwrap();
+    store
+}
+
+#[test]
+fn history_page_lists_entries_with_visit_counts_and_escaped_titles() {
+    let html = about_page_html(ABOUT_HISTORY, &store(), &[], 4_600);
+    assert!(html.contains("<a href=\"https://a.test/\">Alpha &lt;page&gt;</a>"));
+    assert!(html.contains("1 visit • 1 hour ago"));
+    assert!(html.contains("<a href=\"https://b.test/\">https://b.test/</a>"));
+    assert!(html.contains("2 visits • just now"));
+    assert!(html.find("b.test").unwrap() < html.find("a.test").unwrap());
+}
+
+#[test]
+fn history_page_filters_by_the_search_query() {
+    let html = about_page_html("about:history?q=alpha", &store(), &[], 4_600);
+    assert!(html.contains("value=\"alpha\""));
+    assert!(html.contains("https://a.test/"));
+    assert!(!html.contains("https://b.test/"));
+
+    let html = about_page_html("about:history?q=zzz", &store(), &[], 4_600);
+    assert!(html.contains("No history entries match your search."));
+}
+
+#[test]
+fn unknown_about_pages_say_so() {
+    let html = about_page_html("about:nope", &store(), &[], 0);
+    assert!(html.contains("about:nope is not an internal page."));
+}
+
+#[test]
+fn metrics_page_lists_the_counters_of_each_open_page() {
+    let pages = [PageMetrics {
+        url: "https://a.test/".to_string(),
+        title: "Alpha <page>".to_string(),
+        metrics: RuntimeMetrics {
+            bytes_fetched: 2048,
+            tokens_emitted: 120,
+            parse_time: Duration::from_millis(4),
+            css_rules_applied: 9,
+            dom_nodes: 42,
+        },
+    }];
+    let html = about_page_html(ABOUT_METRICS, &store(), &pages, 0);
+    assert!(html.contains("<a href=\"https://a.test/\">Alpha &lt;page&gt;</a>"));
+    assert!(
+        html.contains("2.0 KB fetched • 120 tokens • parsed in 4 ms • 42 DOM nodes • 9 CSS rules")
+    );
+
+    let html = about_page_html(ABOUT_METRICS, &store(), &[], 0);
+    assert!(html.contains("No pages open."));
+}
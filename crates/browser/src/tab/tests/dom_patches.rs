@@ -510,3 +510,30 @@ fn empty_dom_patch_batch_does_not_trigger_restyle() {
     );
     assert_eq!(current_element_color(&mut tab, "p"), (255, 0, 0, 255));
 }
+
+#[test]
+fn applied_patch_updates_are_reported_back_to_the_parser() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut tab = Tab::new(1);
+    tab.set_bus_sender(tx);
+    tab.nav_gen = 4;
+    tab.page.start_nav("https://example.com/");
+
+    tab.on_core_event(CoreEvent::DomPatchUpdate {
+        tab_id: tab.tab_id,
+        request_id: 4,
+        handle: DomHandle(40),
+        from: DomVersion::INITIAL,
+        to: DomVersion(1),
+        patches: initial_patch_document("p { color: black; }", None),
+    });
+
+    assert!(rx.try_iter().any(|cmd| matches!(
+        cmd,
+        bus::CoreCommand::DomUpdateApplied {
+            tab_id: 1,
+            request_id: 4,
+            version: DomVersion(1),
+        }
+    )));
+}
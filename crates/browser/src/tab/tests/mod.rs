@@ -1,6 +1,8 @@
+mod about_page;
 mod dom_patches;
 mod navigation;
 mod network;
+mod recording;
 mod rendering;
 mod style_cache;
 mod stylesheets;
@@ -1,9 +1,14 @@
 use super::super::Tab;
+use super::support::{current_element_color_by_id, dom_patch_update, find_dom_element};
+use crate::history::HistoryUpdate;
 use crate::rendering::{RenderInvalidationEntryPoint, render_invalidation_request};
-use bus::CoreEvent;
-use core_types::{NetworkResponseInfo, ResourceKind};
+use bus::{CoreCommand, CoreEvent};
+use core_types::{NetworkResponseInfo, RequestPayload, ResourceKind};
 use egui::Context;
+use gfx::input::FormSubmission;
 use html::{HtmlParseOptions, parse_document};
+use std::path::PathBuf;
+use std::sync::mpsc;
 
 #[test]
 fn redirected_document_response_updates_tab_base_url_and_status() {
@@ -16,6 +21,8 @@ fn redirected_document_response_updates_tab_base_url_and_status() {
         final_url: final_url.clone(),
         status_code: Some(200),
         content_type: Some("text/html; charset=utf-8".to_string()),
+        content_length: None,
+        headers: Vec::new(),
     };
 
     tab.on_core_event(CoreEvent::NetworkStart {
@@ -40,11 +47,7 @@ fn redirected_document_response_updates_tab_base_url_and_status() {
         response,
         bytes_received: 63,
     });
-    tab.on_core_event(CoreEvent::DomUpdate {
-        tab_id: tab.tab_id,
-        request_id: 1,
-        dom: Box::new(output.document),
-    });
+    tab.on_core_event(dom_patch_update(&tab, 1, output.document));
 
     assert_eq!(tab.page.base_url.as_deref(), Some(final_url.as_str()));
     assert!(
@@ -57,6 +60,38 @@ fn redirected_document_response_updates_tab_base_url_and_status() {
     );
 }
 
+#[test]
+fn document_redirects_update_the_url_bar_before_the_response_starts() {
+    let mut tab = Tab::new(1);
+    tab.nav_gen = 1;
+    tab.url = "http://example.com/".to_string();
+
+    tab.on_core_event(CoreEvent::NetworkRedirected {
+        tab_id: tab.tab_id,
+        request_id: 1,
+        stylesheet_slot_id: None,
+        kind: ResourceKind::Html,
+        from: "http://example.com/".to_string(),
+        to: "https://example.com/".to_string(),
+    });
+    assert_eq!(tab.url, "https://example.com/");
+    assert_eq!(
+        tab.last_status.as_deref(),
+        Some("Redirected to https://example.com/")
+    );
+
+    // Hops of a stale navigation are ignored.
+    tab.on_core_event(CoreEvent::NetworkRedirected {
+        tab_id: tab.tab_id,
+        request_id: 0,
+        stylesheet_slot_id: None,
+        kind: ResourceKind::Html,
+        from: "https://example.com/".to_string(),
+        to: "https://stale.example/".to_string(),
+    });
+    assert_eq!(tab.url, "https://example.com/");
+}
+
 #[test]
 fn starting_new_navigation_clears_pending_render_work_and_last_trace() {
     let mut tab = Tab::new(1);
@@ -69,14 +104,12 @@ fn starting_new_navigation_clears_pending_render_work_and_last_trace() {
     )
     .expect("parse should succeed");
 
-    tab.on_core_event(CoreEvent::DomUpdate {
-        tab_id: tab.tab_id,
-        request_id: 32,
-        dom: Box::new(output.document),
-    });
+    tab.on_core_event(dom_patch_update(&tab, 32, output.document));
 
     let ctx = Context::default();
-    let _ = ctx.run(egui::RawInput::default(), |ctx| tab.ui_content(ctx));
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        tab.ui_content(ctx);
+    });
 
     assert!(tab.pending_render_work.is_empty());
     assert!(
@@ -94,3 +127,437 @@ fn starting_new_navigation_clears_pending_render_work_and_last_trace() {
     assert!(tab.pending_render_work.is_empty());
     assert!(tab.last_render_trace.is_none());
 }
+
+#[test]
+fn back_and_forward_refetch_entry_and_restore_its_scroll_offset() {
+    let (tx, rx) = mpsc::channel();
+    let mut tab = Tab::new(1);
+    tab.set_bus_sender(tx);
+
+    tab.navigate_to_new("https://example.com/a".to_string());
+    tab.document_input.interaction.scroll_y = 120.0;
+    tab.navigate_to_new("https://example.com/b".to_string());
+    assert_eq!(tab.document_input.interaction.pending_scroll_y, None);
+    tab.document_input.interaction.scroll_y = 40.0;
+    let _ = rx.try_iter().count();
+
+    let nav_gen_before = tab.nav_gen;
+    tab.go_back();
+
+    assert_eq!(tab.url, "https://example.com/a");
+    assert_eq!(tab.history_index, 0);
+    assert_eq!(tab.nav_gen, nav_gen_before + 1);
+    assert_eq!(tab.document_input.interaction.pending_scroll_y, Some(120.0));
+    let commands: Vec<_> = rx.try_iter().collect();
+    assert!(matches!(
+        commands.as_slice(),
+        [
+            CoreCommand::CancelGeneration { request_id: cancelled, .. },
+            CoreCommand::FetchStream { request_id, url, .. },
+        ] if *cancelled == nav_gen_before
+            && *request_id == tab.nav_gen
+            && url == "https://example.com/a"
+    ));
+
+    tab.go_forward();
+    assert_eq!(tab.url, "https://example.com/b");
+    assert_eq!(tab.document_input.interaction.pending_scroll_y, Some(40.0));
+    assert!(!tab.can_go_forward());
+
+    tab.go_back();
+    tab.navigate_to_new("https://example.com/c".to_string());
+    assert!(!tab.can_go_forward());
+    assert_eq!(
+        tab.history,
+        ["https://example.com/a", "https://example.com/c"]
+    );
+}
+
+#[test]
+fn reload_refetches_with_new_request_id_and_stop_cancels_it() {
+    let (tx, rx) = mpsc::channel();
+    let mut tab = Tab::new(1);
+    tab.set_bus_sender(tx);
+
+    tab.navigate_to_new("https://example.com/".to_string());
+    tab.document_input.interaction.scroll_y = 64.0;
+    let first = tab.nav_gen;
+    let _ = rx.try_iter().count();
+
+    tab.refresh();
+    assert_eq!(tab.nav_gen, first + 1);
+    assert_eq!(tab.document_input.interaction.pending_scroll_y, Some(64.0));
+    assert!(tab.is_loading());
+    let _ = rx.try_iter().count();
+
+    tab.stop();
+    assert!(!tab.is_loading());
+    assert_eq!(tab.last_status.as_deref(), Some("Stopped"));
+    let commands: Vec<_> = rx.try_iter().collect();
+    assert!(matches!(
+        commands.as_slice(),
+        [CoreCommand::CancelRequest { request_id, .. }] if *request_id == tab.nav_gen
+    ));
+
+    // Stopping an idle tab is a no-op.
+    tab.stop();
+    assert_eq!(rx.try_iter().count(), 0);
+}
+
+#[test]
+fn session_state_round_trips_into_a_fresh_tab_and_loads_current_entry() {
+    let (tx, rx) = mpsc::channel();
+    let mut tab = Tab::new(1);
+    tab.set_bus_sender(tx.clone());
+    assert_eq!(tab.session_state(), None);
+
+    tab.navigate_to_new("https://example.com/a".to_string());
+    tab.document_input.interaction.scroll_y = 120.0;
+    tab.navigate_to_new("https://example.com/b".to_string());
+    tab.document_input.interaction.scroll_y = 40.0;
+    tab.go_back();
+    let session = tab.session_state().expect("tab has history");
+    assert_eq!(session.history_index, 0);
+    // The pending restore of entry 0 wins over the not-yet-applied scroll.
+    assert_eq!(session.history_scroll, [120.0, 40.0]);
+
+    let mut restored = Tab::new(2);
+    restored.restore_session(session.clone());
+    restored.set_bus_sender(tx);
+    let _ = rx.try_iter().count();
+    restored.load_restored_entry();
+
+    assert_eq!(restored.url, "https://example.com/a");
+    assert!(restored.can_go_forward());
+    assert_eq!(
+        restored.document_input.interaction.pending_scroll_y,
+        Some(120.0)
+    );
+    assert_eq!(restored.session_state(), Some(session));
+    let commands: Vec<_> = rx.try_iter().collect();
+    assert!(matches!(
+        commands.as_slice(),
+        [CoreCommand::FetchStream { tab_id: 2, url, .. }] if url == "https://example.com/a"
+    ));
+}
+
+#[test]
+fn post_submissions_fetch_with_their_payload_and_reload_with_get() {
+    let (tx, rx) = mpsc::channel();
+    let mut tab = Tab::new(1);
+    tab.set_bus_sender(tx);
+    tab.navigate_to_new("https://example.com/form".to_string());
+
+    let payload = net::urlencoded_form([("q", "1")]);
+    tab.submit_form(FormSubmission {
+        url: "https://example.com/form".to_string(),
+        payload: payload.clone(),
+    });
+
+    // Posting to the page's own URL is a new navigation, not a fragment jump.
+    assert_eq!(tab.url, "https://example.com/form");
+    assert!(tab.can_go_back());
+    let fetches: Vec<_> = rx
+        .try_iter()
+        .filter_map(|cmd| match cmd {
+            CoreCommand::FetchStream { payload, .. } => Some(payload),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(fetches, [RequestPayload::default(), payload]);
+
+    tab.refresh();
+    assert!(rx.try_iter().any(|cmd| matches!(
+        cmd,
+        CoreCommand::FetchStream { payload, .. } if payload == RequestPayload::default()
+    )));
+}
+
+#[test]
+fn fragment_navigation_queues_scroll_without_refetching_same_document() {
+    let (tx, rx) = mpsc::channel();
+    let mut tab = Tab::new(1);
+    tab.set_bus_sender(tx);
+
+    tab.navigate_to_new("https://example.com/doc#intro".to_string());
+    assert_eq!(
+        tab.document_input.interaction.pending_fragment.as_deref(),
+        Some("intro")
+    );
+    let _ = rx.try_iter().count();
+    let nav_gen_before = tab.nav_gen;
+
+    tab.document_input.interaction.pending_fragment = None;
+    tab.navigate_to_new("https://example.com/doc#caf%C3%A9".to_string());
+
+    assert_eq!(tab.nav_gen, nav_gen_before);
+    assert_eq!(rx.try_iter().count(), 0);
+    assert_eq!(
+        tab.document_input.interaction.pending_fragment.as_deref(),
+        Some("café")
+    );
+    assert!(tab.can_go_back());
+}
+
+#[test]
+fn picked_files_fill_the_file_input_until_the_next_navigation() {
+    let (tx, rx) = mpsc::channel();
+    let mut tab = Tab::new(1);
+    tab.set_bus_sender(tx);
+    tab.nav_gen = 4;
+    tab.page.start_nav("https://example.com/");
+    let output = parse_document(
+        "<!doctype html><input type=file multiple>",
+        HtmlParseOptions::default(),
+    )
+    .expect("parse should succeed");
+    tab.on_core_event(dom_patch_update(&tab, 4, output.document));
+    let input_id = find_dom_element(tab.page.dom.as_deref().unwrap(), "input")
+        .unwrap()
+        .id();
+
+    tab.pick_files(input_id);
+    match rx.try_iter().last() {
+        Some(CoreCommand::PickFiles {
+            tab_id: 1,
+            request_id: 4,
+            input_id: id,
+            multiple: true,
+        }) => assert_eq!(id, input_id),
+        other => panic!("expected a file picker request, got {other:?}"),
+    }
+
+    let picked = |request_id, paths: &[&str]| CoreEvent::FilesPicked {
+        tab_id: 1,
+        request_id,
+        input_id,
+        paths: paths.iter().map(PathBuf::from).collect(),
+    };
+    tab.on_core_event(picked(3, &["/tmp/stale.txt"]));
+    assert!(tab.document_input.chosen_files(input_id).is_empty());
+
+    tab.on_core_event(picked(4, &["/tmp/a.txt", "/tmp/b.png"]));
+    assert_eq!(tab.document_input.chosen_files(input_id).len(), 2);
+    assert_eq!(
+        tab.document_input.input_values.get(input_id),
+        Some("2 files")
+    );
+    tab.on_core_event(picked(4, &["/home/me/report.pdf"]));
+    assert_eq!(
+        tab.document_input.input_values.get(input_id),
+        Some("report.pdf")
+    );
+
+    tab.navigate_to_new("https://example.com/next".to_string());
+    assert!(tab.document_input.chosen_files(input_id).is_empty());
+}
+
+#[test]
+fn completed_document_loads_report_a_history_visit_and_its_title_once() {
+    let mut tab = Tab::new(1);
+    tab.nav_gen = 1;
+    let response = NetworkResponseInfo {
+        requested_url: "https://example.com".to_string(),
+        final_url: "https://example.com/landing".to_string(),
+        status_code: Some(200),
+        content_type: Some("text/html".to_string()),
+        content_length: None,
+        headers: Vec::new(),
+    };
+    let dom_update = |tab: &Tab| {
+        let output = parse_document(
+            "<!doctype html><title> Landing </title><p>Hi</p>",
+            HtmlParseOptions::default(),
+        )
+        .expect("parse should succeed");
+        dom_patch_update(tab, 1, output.document)
+    };
+
+    tab.on_core_event(dom_update(&tab));
+    assert!(tab.take_history_updates().is_empty());
+
+    tab.on_core_event(CoreEvent::NetworkDone {
+        tab_id: 1,
+        request_id: 1,
+        stylesheet_slot_id: None,
+        kind: ResourceKind::Html,
+        response,
+        bytes_received: 40,
+    });
+    tab.on_core_event(dom_update(&tab));
+    let url = "https://example.com/landing".to_string();
+    assert_eq!(
+        tab.take_history_updates(),
+        [
+            HistoryUpdate::Visit { url: url.clone() },
+            HistoryUpdate::Title {
+                url,
+                title: "Landing".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn shell_about_pages_wait_for_the_shell_instead_of_fetching() {
+    let (tx, rx) = mpsc::channel();
+    let mut tab = Tab::new(1);
+    tab.set_bus_sender(tx);
+
+    tab.navigate_to_new(" about:history?q=rust ".to_string());
+    assert_eq!(tab.pending_about_page(), Some("about:history?q=rust"));
+    assert!(
+        !rx.try_iter()
+            .any(|cmd| matches!(cmd, CoreCommand::FetchStream { .. })),
+        "shell pages are never fetched"
+    );
+
+    tab.show_about_page("<!doctype html><title>History</title>".to_string());
+    assert_eq!(tab.pending_about_page(), None);
+    let commands: Vec<_> = rx.try_iter().collect();
+    assert!(matches!(
+        commands.as_slice(),
+        [
+            CoreCommand::ParseHtmlStart { .. },
+            CoreCommand::ParseHtmlChunk { .. },
+            CoreCommand::ParseHtmlDone { .. },
+        ]
+    ));
+
+    tab.navigate_to_new("view-source:about:history".to_string());
+    assert_eq!(tab.current_url(), Some("about:history?q=rust"));
+}
+
+#[test]
+fn other_about_pages_are_fetched_like_documents() {
+    let (tx, rx) = mpsc::channel();
+    let mut tab = Tab::new(1);
+    tab.set_bus_sender(tx);
+
+    tab.navigate_to_new("about:version".to_string());
+    assert_eq!(tab.pending_about_page(), None);
+    let commands: Vec<_> = rx.try_iter().collect();
+    assert!(matches!(
+        commands.as_slice(),
+        [CoreCommand::FetchStream { url, kind: ResourceKind::Html, .. }] if url == "about:version"
+    ));
+
+    let response = NetworkResponseInfo {
+        requested_url: "about:version".to_string(),
+        final_url: "about:version".to_string(),
+        status_code: None,
+        content_type: Some("text/html; charset=utf-8".to_string()),
+        content_length: Some(0),
+        headers: Vec::new(),
+    };
+    tab.on_core_event(CoreEvent::NetworkDone {
+        tab_id: tab.tab_id,
+        request_id: tab.nav_gen,
+        stylesheet_slot_id: None,
+        kind: ResourceKind::Html,
+        response,
+        bytes_received: 0,
+    });
+    assert!(
+        tab.take_history_updates().is_empty(),
+        "internal pages stay out of the browsing history"
+    );
+}
+
+#[test]
+fn window_title_follows_the_page_title_and_loading_state() {
+    let mut tab = Tab::new(1);
+    assert_eq!(tab.window_title(), "Borrowser");
+
+    tab.navigate_to_new("https://example.com/".to_string());
+    assert_eq!(tab.window_title(), "example.com (loading…) — Borrowser");
+
+    let output = parse_document(
+        "<!doctype html><title> Example Domain </title>",
+        HtmlParseOptions::default(),
+    )
+    .expect("parse should succeed");
+    tab.on_core_event(dom_patch_update(&tab, tab.nav_gen, output.document));
+    assert!(!tab.is_loading());
+    assert_eq!(tab.window_title(), "Example Domain — Borrowser");
+}
+
+#[test]
+fn visiting_a_link_target_restyles_links_to_it_as_visited() {
+    let mut tab = Tab::new(1);
+    tab.nav_gen = 6;
+    tab.page.start_nav("https://example.com/docs/");
+    let output = parse_document(
+        "<a id=next href=' next.html'>next</a><a id=home href='/'>home</a>",
+        HtmlParseOptions::default(),
+    )
+    .expect("parse should succeed");
+    tab.on_core_event(dom_patch_update(&tab, 6, output.document));
+    let link_id = |tab: &Tab, id| {
+        let dom = tab.page.dom.as_deref().expect("document");
+        html::query::get_element_by_id(dom, id)
+            .expect("link element")
+            .id()
+    };
+    let (next, home) = (link_id(&tab, "next"), link_id(&tab, "home"));
+    let link = (0, 0, 238, 255);
+    let visited = (85, 26, 139, 255);
+    assert_eq!(current_element_color_by_id(&mut tab, next), link);
+    assert_eq!(current_element_color_by_id(&mut tab, home), link);
+
+    assert!(
+        tab.page
+            .mark_visited("https://example.com/elsewhere")
+            .is_none(),
+        "a URL no link points at restyles nothing"
+    );
+    assert!(
+        tab.page
+            .mark_visited("HTTPS://EXAMPLE.COM/docs/next.html")
+            .is_some()
+    );
+    assert_eq!(current_element_color_by_id(&mut tab, next), visited);
+    assert_eq!(current_element_color_by_id(&mut tab, home), link);
+
+    // Visited URLs outlive the document.
+    tab.page.start_nav("https://example.com/");
+    let output = parse_document("<a id=home href='/'>home</a>", HtmlParseOptions::default())
+        .expect("parse should succeed");
+    tab.mark_visited("https://example.com/");
+    tab.on_core_event(dom_patch_update(&tab, 6, output.document));
+    let home = link_id(&tab, "home");
+    assert_eq!(current_element_color_by_id(&mut tab, home), visited);
+}
+
+#[test]
+fn same_document_back_and_forward_restore_offset_or_fragment() {
+    let (tx, rx) = mpsc::channel();
+    let mut tab = Tab::new(1);
+    tab.set_bus_sender(tx);
+
+    tab.navigate_to_new("https://example.com/doc#intro".to_string());
+    tab.document_input.interaction.pending_fragment = None;
+    tab.document_input.interaction.scroll_y = 300.0;
+    tab.navigate_to_new("https://example.com/doc#end".to_string());
+    tab.document_input.interaction.pending_fragment = None;
+    tab.document_input.interaction.scroll_y = 900.0;
+    let _ = rx.try_iter().count();
+    let nav_gen_before = tab.nav_gen;
+
+    tab.go_back();
+    assert_eq!(tab.nav_gen, nav_gen_before);
+    assert_eq!(rx.try_iter().count(), 0);
+    assert_eq!(tab.document_input.interaction.pending_scroll_y, Some(300.0));
+    assert_eq!(tab.document_input.interaction.pending_fragment, None);
+
+    tab.go_forward();
+    assert_eq!(tab.document_input.interaction.pending_scroll_y, Some(900.0));
+
+    // An entry left at the top goes to its fragment.
+    tab.history_scroll[0] = 0.0;
+    tab.go_back();
+    assert_eq!(
+        tab.document_input.interaction.pending_fragment.as_deref(),
+        Some("intro")
+    );
+}
@@ -1,7 +1,15 @@
 use super::super::Tab;
-use bus::{CoreCommand, CoreEvent};
+use super::support::dom_patch_update;
+use crate::history::HistoryUpdate;
+use crate::network_log::NetworkRequestState;
+use app_api::Repaint;
+use bus::{
+    AnimationFrame, CoreCommand, CoreEvent, DecodedImage, ParseLimit, ParseLimits, RuntimeKind,
+    RuntimeMetrics,
+};
 use core_types::{NetworkResponseInfo, ResourceKind};
-use std::sync::mpsc;
+use std::sync::{Arc, Mutex, mpsc};
+use std::time::Duration;
 
 #[test]
 fn css_with_html_content_type_is_not_forwarded_to_css_runtime() {
@@ -18,6 +26,8 @@ fn css_with_html_content_type_is_not_forwarded_to_css_runtime() {
         final_url: url.clone(),
         status_code: Some(404),
         content_type: Some("text/html".to_string()),
+        content_length: None,
+        headers: Vec::new(),
     };
 
     tab.on_core_event(CoreEvent::NetworkStart {
@@ -71,8 +81,15 @@ fn css_with_html_content_type_is_not_forwarded_to_css_runtime() {
 }
 
 #[test]
-fn resource_limit_document_error_surfaces_status_and_stops_loading() {
+fn document_cut_off_at_the_byte_limit_warns_and_counts_as_loaded() {
     let mut tab = Tab::new(1);
+    let (tx, rx) = mpsc::channel();
+    tab.set_bus_sender(tx);
+    let limits = ParseLimits {
+        max_bytes: 2048,
+        ..ParseLimits::default()
+    };
+    tab.set_parse_limits(limits);
     tab.nav_gen = 3;
 
     let response = NetworkResponseInfo {
@@ -80,6 +97,8 @@ fn resource_limit_document_error_surfaces_status_and_stops_loading() {
         final_url: "https://example.com".to_string(),
         status_code: Some(200),
         content_type: Some("text/html".to_string()),
+        content_length: None,
+        headers: Vec::new(),
     };
 
     tab.on_core_event(CoreEvent::NetworkStart {
@@ -89,26 +108,46 @@ fn resource_limit_document_error_surfaces_status_and_stops_loading() {
         kind: ResourceKind::Html,
         response,
     });
+    assert!(
+        rx.try_iter().any(|cmd| matches!(
+            cmd,
+            CoreCommand::ParseHtmlStart { limits: sent, .. } if sent == limits
+        )),
+        "the parse should start with the tab's limits"
+    );
+    assert_eq!(tab.truncation_warning(), None);
+
+    tab.on_core_event(CoreEvent::ResourceTruncated {
+        tab_id: tab.tab_id,
+        request_id: 3,
+        limit: ParseLimit::Bytes,
+    });
+    let warning = tab.truncation_warning().expect("truncation warning");
+    assert!(warning.contains("2048 bytes"), "{warning}");
+    assert!(matches!(
+        &tab.take_history_updates()[..],
+        [HistoryUpdate::Visit { url }] if url == "https://example.com"
+    ));
+
+    // The parser stopped the download; that is not a failed load.
     tab.on_core_event(CoreEvent::NetworkError {
         tab_id: tab.tab_id,
         request_id: 3,
         stylesheet_slot_id: None,
         kind: ResourceKind::Html,
         url: "https://example.com".to_string(),
-        error_kind: core_types::NetworkErrorKind::ResourceLimit,
-        status_code: Some(200),
-        error: "html response exceeded byte limit of 10485760 bytes".to_string(),
+        error: core_types::NetError::Cancelled,
+        message: "cancelled".to_string(),
     });
-
     assert!(
-        tab.last_status
+        !tab.last_status
             .as_deref()
             .unwrap_or_default()
-            .contains("Resource limit loading document"),
-        "expected resource-limit document status, got {:?}",
+            .contains("Cancelled"),
+        "unexpected status {:?}",
         tab.last_status
     );
-    assert!(!tab.loading, "document load should stop after limit error");
+    assert!(tab.truncation_warning().is_some());
 }
 
 #[test]
@@ -126,6 +165,8 @@ fn stylesheet_resource_limit_aborts_partial_css_and_clears_pending_state() {
         final_url: url.clone(),
         status_code: Some(200),
         content_type: Some("text/css".to_string()),
+        content_length: None,
+        headers: Vec::new(),
     };
 
     tab.on_core_event(CoreEvent::NetworkStart {
@@ -149,9 +190,8 @@ fn stylesheet_resource_limit_aborts_partial_css_and_clears_pending_state() {
         stylesheet_slot_id: Some(slot_id),
         kind: ResourceKind::Css,
         url: url.clone(),
-        error_kind: core_types::NetworkErrorKind::ResourceLimit,
-        status_code: Some(200),
-        error: "css response exceeded byte limit of 2097152 bytes".to_string(),
+        error: core_types::NetError::TooLarge,
+        message: "css response exceeded byte limit of 2097152 bytes".to_string(),
     });
 
     let queued = rx.try_iter().collect::<Vec<_>>();
@@ -199,6 +239,8 @@ fn decoded_css_for_aborted_stylesheet_slot_is_ignored() {
         final_url: url.clone(),
         status_code: Some(200),
         content_type: Some("text/css".to_string()),
+        content_length: None,
+        headers: Vec::new(),
     };
 
     tab.on_core_event(CoreEvent::NetworkStart {
@@ -214,9 +256,8 @@ fn decoded_css_for_aborted_stylesheet_slot_is_ignored() {
         stylesheet_slot_id: Some(slot_id),
         kind: ResourceKind::Css,
         url: url.clone(),
-        error_kind: core_types::NetworkErrorKind::ResourceLimit,
-        status_code: Some(200),
-        error: "css response exceeded byte limit".to_string(),
+        error: core_types::NetError::TooLarge,
+        message: "css response exceeded byte limit".to_string(),
     });
 
     assert!(
@@ -239,3 +280,649 @@ fn decoded_css_for_aborted_stylesheet_slot_is_ignored() {
         "late decoded CSS for an aborted slot must not attach"
     );
 }
+
+fn icon_fetches(rx: &mpsc::Receiver<CoreCommand>) -> Vec<String> {
+    rx.try_iter()
+        .filter_map(|cmd| match cmd {
+            CoreCommand::FetchStream {
+                kind: ResourceKind::Icon,
+                url,
+                ..
+            } => Some(url),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn favicon_uses_link_icon_or_falls_back_to_origin_favicon_ico() {
+    let (tx, rx) = mpsc::channel();
+    let mut tab = Tab::new(1);
+    tab.set_bus_sender(tx);
+    tab.nav_gen = 3;
+    tab.page.start_nav("https://example.com/docs/page.html");
+
+    let dom = |html: &str| {
+        html::parse_document(html, html::HtmlParseOptions::default())
+            .expect("parse should succeed")
+            .document
+    };
+    tab.on_core_event(dom_patch_update(
+        &tab,
+        3,
+        dom("<!doctype html><title>x</title><p>no icon yet"),
+    ));
+    assert_eq!(icon_fetches(&rx), ["https://example.com/favicon.ico"]);
+
+    tab.on_core_event(dom_patch_update(
+        &tab,
+        3,
+        dom("<!doctype html><link rel=\"shortcut icon\" href=\"img/fav.png\"><p>icon"),
+    ));
+    assert_eq!(icon_fetches(&rx), ["https://example.com/docs/img/fav.png"]);
+
+    // Re-discovery with the same icon does not refetch, and icon failures
+    // never reach the document's image cache.
+    tab.on_core_event(dom_patch_update(
+        &tab,
+        3,
+        dom("<!doctype html><link rel=\"shortcut icon\" href=\"img/fav.png\"><p>icon!"),
+    ));
+    assert!(icon_fetches(&rx).is_empty());
+    tab.on_core_event(CoreEvent::NetworkError {
+        tab_id: tab.tab_id,
+        request_id: 3,
+        stylesheet_slot_id: None,
+        kind: ResourceKind::Icon,
+        url: "https://example.com/docs/img/fav.png".to_string(),
+        error: core_types::NetError::HttpStatus(404),
+        message: "not found".to_string(),
+    });
+    assert_eq!(tab.favicon(), None);
+    assert!(matches!(
+        tab.resources
+            .image_state_by_url("https://example.com/docs/img/fav.png"),
+        crate::resources::ImageState::Missing
+    ));
+}
+
+#[test]
+fn base_href_redirects_subresources_but_not_the_favicon_fallback() {
+    let (tx, rx) = mpsc::channel();
+    let mut tab = Tab::new(1);
+    tab.set_bus_sender(tx);
+    tab.nav_gen = 3;
+    tab.page.start_nav("https://example.com/docs/page.html");
+
+    tab.on_core_event(dom_patch_update(
+        &tab,
+        3,
+        html::parse_document(
+            "<!doctype html><base href=' https://cdn.example/assets/ '>\
+             <link rel=stylesheet href=site.css><img src=logo.png>",
+            html::HtmlParseOptions::default(),
+        )
+        .expect("parse should succeed")
+        .document,
+    ));
+
+    let fetches: Vec<_> = rx
+        .try_iter()
+        .filter_map(|cmd| match cmd {
+            CoreCommand::FetchStream { kind, url, .. } => Some((kind, url)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        tab.page.base_url.as_deref(),
+        Some("https://cdn.example/assets/")
+    );
+    for expected in [
+        (ResourceKind::Css, "https://cdn.example/assets/site.css"),
+        (ResourceKind::Image, "https://cdn.example/assets/logo.png"),
+        (ResourceKind::Icon, "https://example.com/favicon.ico"),
+    ] {
+        assert!(
+            fetches
+                .iter()
+                .any(|(kind, url)| (*kind, url.as_str()) == expected),
+            "missing fetch {expected:?} in {fetches:?}"
+        );
+    }
+}
+
+#[test]
+fn fetched_icons_decode_in_the_image_runtime_and_upload_on_the_next_pump() {
+    let (tx, rx) = mpsc::channel();
+    let mut tab = Tab::new(1);
+    tab.set_bus_sender(tx);
+    tab.nav_gen = 3;
+    tab.page.start_nav("https://example.com/");
+    tab.on_core_event(dom_patch_update(
+        &tab,
+        3,
+        html::parse_document("<p>icon", html::HtmlParseOptions::default())
+            .expect("parse should succeed")
+            .document,
+    ));
+    let url = "https://example.com/favicon.ico".to_string();
+    assert_eq!(icon_fetches(&rx), std::slice::from_ref(&url));
+
+    tab.on_core_event(CoreEvent::NetworkChunk {
+        tab_id: tab.tab_id,
+        request_id: 3,
+        stylesheet_slot_id: None,
+        kind: ResourceKind::Icon,
+        url: url.clone(),
+        bytes: b"icon bytes".to_vec(),
+    });
+    tab.on_core_event(CoreEvent::NetworkDone {
+        tab_id: tab.tab_id,
+        request_id: 3,
+        stylesheet_slot_id: None,
+        kind: ResourceKind::Icon,
+        response: NetworkResponseInfo {
+            requested_url: url.clone(),
+            final_url: url.clone(),
+            status_code: Some(200),
+            content_type: Some("image/x-icon".to_string()),
+            content_length: None,
+            headers: Vec::new(),
+        },
+        bytes_received: 10,
+    });
+    let decode = rx.try_iter().find_map(|cmd| match cmd {
+        CoreCommand::DecodeImage {
+            tab_id: 1,
+            request_id: 3,
+            kind: ResourceKind::Icon,
+            url,
+            bytes,
+        } => Some((url, bytes)),
+        _ => None,
+    });
+    let (decode_url, bytes) = decode.expect("icon body should go to the image runtime");
+    assert_eq!(decode_url, url);
+    assert_eq!(&bytes[..], b"icon bytes");
+
+    tab.on_core_event(CoreEvent::ImageDecoded {
+        tab_id: tab.tab_id,
+        request_id: 3,
+        kind: ResourceKind::Icon,
+        url: url.clone(),
+        image: Ok(DecodedImage::still(vec![255; 4], [1, 1])),
+    });
+    assert_eq!(tab.favicon(), None, "textures upload when the icons pump");
+    tab.pump_icons(&egui::Context::default());
+    assert!(tab.favicon().is_some());
+    assert!(matches!(
+        tab.resources.image_state_by_url(&url),
+        crate::resources::ImageState::Missing
+    ));
+}
+
+#[derive(Default)]
+struct RecordingRepaint(Mutex<Vec<Duration>>);
+
+impl Repaint for RecordingRepaint {
+    fn request_now(&self) {}
+
+    fn request_after(&self, duration: Duration) {
+        self.0.lock().unwrap().push(duration);
+    }
+}
+
+#[test]
+fn animated_icons_play_in_the_tab_strip() {
+    let (tx, _rx) = mpsc::channel();
+    let repaint = Arc::new(RecordingRepaint::default());
+    let mut tab = Tab::new(1);
+    tab.set_bus_sender(tx);
+    tab.set_repaint_handle(repaint.clone());
+    tab.nav_gen = 3;
+    tab.page.start_nav("https://example.com/");
+    tab.on_core_event(dom_patch_update(
+        &tab,
+        3,
+        html::parse_document("<p>icon", html::HtmlParseOptions::default())
+            .expect("parse should succeed")
+            .document,
+    ));
+    let url = "https://example.com/favicon.ico".to_string();
+    tab.icons.on_network_chunk(&url, b"GIF89a");
+    tab.icons.on_network_done(&url, |_, _| {});
+
+    let mut image = DecodedImage::still(vec![255; 4], [1, 1]);
+    image.delay = Duration::from_millis(1);
+    image.frames.push(AnimationFrame {
+        rgba: vec![0; 4],
+        delay: Duration::from_secs(60),
+    });
+    tab.on_core_event(CoreEvent::ImageDecoded {
+        tab_id: tab.tab_id,
+        request_id: 3,
+        kind: ResourceKind::Icon,
+        url,
+        image: Ok(image),
+    });
+    tab.pump_icons(&egui::Context::default());
+    let first = tab.favicon().expect("icon uploaded");
+    assert!(
+        !repaint.0.lock().unwrap().is_empty(),
+        "the window comes back for the next frame"
+    );
+
+    std::thread::sleep(Duration::from_millis(5));
+    tab.pump_icons(&egui::Context::default());
+    assert_ne!(tab.favicon(), Some(first), "the second frame is due");
+}
+
+#[test]
+fn view_source_fetches_inner_url_and_streams_escaped_source() {
+    let mut tab = Tab::new(1);
+    let (tx, rx) = mpsc::channel();
+    tab.set_bus_sender(tx);
+
+    tab.navigate_to_new("view-source:example.com/a".to_string());
+    assert_eq!(tab.current_url(), Some("view-source:https://example.com/a"));
+    let fetched = rx.try_iter().find_map(|cmd| match cmd {
+        CoreCommand::FetchStream { url, .. } => Some(url),
+        _ => None,
+    });
+    assert_eq!(fetched.as_deref(), Some("https://example.com/a"));
+
+    let url = "https://example.com/a".to_string();
+    let response = NetworkResponseInfo {
+        requested_url: url.clone(),
+        final_url: url.clone(),
+        status_code: Some(200),
+        content_type: Some("text/html".to_string()),
+        content_length: None,
+        headers: Vec::new(),
+    };
+    tab.on_core_event(CoreEvent::NetworkStart {
+        tab_id: tab.tab_id,
+        request_id: tab.nav_gen,
+        stylesheet_slot_id: None,
+        kind: ResourceKind::Html,
+        response,
+    });
+    tab.on_core_event(CoreEvent::NetworkChunk {
+        tab_id: tab.tab_id,
+        request_id: tab.nav_gen,
+        stylesheet_slot_id: None,
+        kind: ResourceKind::Html,
+        url,
+        bytes: b"<p>a & b</p>".to_vec(),
+    });
+
+    assert_eq!(tab.url, "view-source:https://example.com/a");
+    let parsed = rx
+        .try_iter()
+        .filter_map(|cmd| match cmd {
+            CoreCommand::ParseHtmlChunk { bytes, .. } => Some(bytes),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .concat();
+    assert_eq!(
+        String::from_utf8(parsed).unwrap(),
+        "<!DOCTYPE html><pre>&lt;p>a &amp; b&lt;/p>"
+    );
+}
+
+#[test]
+fn network_log_tracks_current_navigation_requests_only() {
+    let mut tab = Tab::new(1);
+    let (tx, _rx) = mpsc::channel();
+    tab.set_bus_sender(tx);
+
+    tab.navigate_to_new("https://example.com/old".to_string());
+    tab.navigate_to_new("https://example.com/new".to_string());
+    let urls = |tab: &Tab| {
+        tab.network_log()
+            .requests()
+            .iter()
+            .map(|request| request.url.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(urls(&tab), vec!["https://example.com/new".to_string()]);
+
+    let response = |url: &str| NetworkResponseInfo {
+        requested_url: url.to_string(),
+        final_url: url.to_string(),
+        status_code: Some(200),
+        content_type: Some("text/html".to_string()),
+        content_length: None,
+        headers: Vec::new(),
+    };
+    // A late event from the previous navigation is ignored.
+    tab.on_core_event(CoreEvent::NetworkStart {
+        tab_id: tab.tab_id,
+        request_id: tab.nav_gen - 1,
+        stylesheet_slot_id: None,
+        kind: ResourceKind::Html,
+        response: response("https://example.com/new"),
+    });
+    assert_eq!(
+        tab.network_log().requests()[0].state,
+        NetworkRequestState::Pending
+    );
+
+    tab.on_core_event(CoreEvent::NetworkStart {
+        tab_id: tab.tab_id,
+        request_id: tab.nav_gen,
+        stylesheet_slot_id: None,
+        kind: ResourceKind::Html,
+        response: response("https://example.com/new"),
+    });
+    assert_eq!(tab.network_log().requests()[0].status_code, Some(200));
+    assert_eq!(
+        tab.network_log().requests()[0].state,
+        NetworkRequestState::Receiving
+    );
+}
+
+#[test]
+fn document_dns_error_renders_error_page_and_retry_refetches() {
+    let mut tab = Tab::new(1);
+    let (tx, rx) = mpsc::channel();
+    tab.set_bus_sender(tx);
+
+    tab.navigate_to_new("https://unreachable.test/".to_string());
+    let failed_gen = tab.nav_gen;
+    rx.try_iter().for_each(drop);
+
+    tab.on_core_event(CoreEvent::NetworkError {
+        tab_id: tab.tab_id,
+        request_id: failed_gen,
+        stylesheet_slot_id: None,
+        kind: ResourceKind::Html,
+        url: "https://unreachable.test/".to_string(),
+        error: core_types::NetError::Dns,
+        message: "dns error: <no such host>".to_string(),
+    });
+
+    let queued = rx.try_iter().collect::<Vec<_>>();
+    assert!(matches!(
+        queued.first(),
+        Some(CoreCommand::ParseHtmlStart { request_id, .. }) if *request_id == failed_gen
+    ));
+    assert!(matches!(
+        queued.last(),
+        Some(CoreCommand::ParseHtmlDone { .. })
+    ));
+    let page = queued
+        .iter()
+        .find_map(|cmd| match cmd {
+            CoreCommand::ParseHtmlChunk { bytes, .. } => Some(String::from_utf8_lossy(bytes)),
+            _ => None,
+        })
+        .expect("error page markup");
+    assert!(page.contains("This site can't be found"));
+    assert!(page.contains("dns error: &lt;no such host&gt;"));
+    assert!(page.contains("href=\"https://unreachable.test/\""));
+    assert!(!tab.loading);
+
+    tab.navigate_to_new("https://unreachable.test/".to_string());
+    assert_eq!(tab.history.len(), 1, "retry must not add a history entry");
+    assert_eq!(tab.nav_gen, failed_gen.wrapping_add(1));
+    assert!(rx.try_iter().any(|cmd| matches!(
+        cmd,
+        CoreCommand::FetchStream { url, .. } if url == "https://unreachable.test/"
+    )));
+}
+
+#[test]
+fn document_error_pages_name_the_cause_of_the_failure() {
+    for (error, title, status) in [
+        (
+            core_types::NetError::Tls,
+            "This connection isn't secure",
+            "TLS error loading document",
+        ),
+        (
+            core_types::NetError::Timeout,
+            "This site took too long to respond",
+            "Timed out loading document",
+        ),
+        (
+            core_types::NetError::HttpStatus(503),
+            "This page isn't working",
+            "HTTP 503 while loading document",
+        ),
+    ] {
+        let mut tab = Tab::new(1);
+        let (tx, rx) = mpsc::channel();
+        tab.set_bus_sender(tx);
+        tab.navigate_to_new("https://example.com/".to_string());
+        rx.try_iter().for_each(drop);
+
+        tab.on_core_event(CoreEvent::NetworkError {
+            tab_id: tab.tab_id,
+            request_id: tab.nav_gen,
+            stylesheet_slot_id: None,
+            kind: ResourceKind::Html,
+            url: "https://example.com/".to_string(),
+            error,
+            message: "failed".to_string(),
+        });
+
+        let page = rx
+            .try_iter()
+            .find_map(|cmd| match cmd {
+                CoreCommand::ParseHtmlChunk { bytes, .. } => {
+                    Some(String::from_utf8_lossy(&bytes).into_owned())
+                }
+                _ => None,
+            })
+            .expect("error page markup");
+        assert!(page.contains(title), "{error:?}: {page}");
+        assert!(
+            tab.last_status
+                .as_deref()
+                .is_some_and(|s| s.starts_with(status)),
+            "{error:?}: {:?}",
+            tab.last_status
+        );
+    }
+}
+
+#[test]
+fn runtime_crashes_reload_once_then_show_an_error_page() {
+    let mut tab = Tab::new(1);
+    let (tx, rx) = mpsc::channel();
+    tab.set_bus_sender(tx);
+
+    tab.navigate_to_new("https://example.com/".to_string());
+    let first_gen = tab.nav_gen;
+    rx.try_iter().for_each(drop);
+
+    let crash = |tab: &Tab| CoreEvent::RuntimeCrashed {
+        tab_id: tab.tab_id,
+        request_id: tab.nav_gen,
+        which: RuntimeKind::Css,
+        error: "index out of bounds".to_string(),
+    };
+
+    tab.on_core_event(crash(&tab));
+    assert_eq!(tab.nav_gen, first_gen.wrapping_add(1));
+    assert!(tab.is_loading());
+    assert!(rx.try_iter().any(|cmd| matches!(
+        cmd,
+        CoreCommand::FetchStream { url, request_id, .. }
+            if url == "https://example.com/" && request_id == tab.nav_gen
+    )));
+
+    tab.on_core_event(crash(&tab));
+    let error_gen = tab.nav_gen;
+    assert_eq!(error_gen, first_gen.wrapping_add(2));
+    assert!(!tab.is_loading());
+    let queued = rx.try_iter().collect::<Vec<_>>();
+    assert!(
+        !queued
+            .iter()
+            .any(|cmd| matches!(cmd, CoreCommand::FetchStream { .. })),
+        "a second crash must not reload again"
+    );
+    let page = queued
+        .iter()
+        .find_map(|cmd| match cmd {
+            CoreCommand::ParseHtmlChunk {
+                request_id, bytes, ..
+            } if *request_id == error_gen => Some(String::from_utf8_lossy(bytes)),
+            _ => None,
+        })
+        .expect("crash page markup");
+    assert!(page.contains("stylesheet crashed"));
+    assert!(page.contains("index out of bounds"));
+
+    // A crash while showing the error page only updates the status.
+    tab.on_core_event(crash(&tab));
+    assert_eq!(tab.nav_gen, error_gen);
+    assert_eq!(rx.try_iter().count(), 0);
+}
+
+#[test]
+fn load_progress_follows_document_bytes_until_loading_ends() {
+    let mut tab = Tab::new(1);
+    tab.nav_gen = 4;
+    assert_eq!(tab.load_progress(), None);
+
+    let response = NetworkResponseInfo {
+        requested_url: "https://example.com".to_string(),
+        final_url: "https://example.com".to_string(),
+        status_code: Some(200),
+        content_type: Some("text/html".to_string()),
+        content_length: Some(40),
+        headers: Vec::new(),
+    };
+    tab.on_core_event(CoreEvent::NetworkStart {
+        tab_id: tab.tab_id,
+        request_id: 4,
+        stylesheet_slot_id: None,
+        kind: ResourceKind::Html,
+        response: response.clone(),
+    });
+    tab.on_core_event(CoreEvent::NetworkChunk {
+        tab_id: tab.tab_id,
+        request_id: 4,
+        stylesheet_slot_id: None,
+        kind: ResourceKind::Html,
+        url: "https://example.com".to_string(),
+        bytes: vec![b' '; 10],
+    });
+    let progress = tab.load_progress().expect("loading");
+    assert_eq!(progress.document_bytes, 10);
+    assert_eq!(progress.document_length, Some(40));
+    assert!(!progress.document_complete);
+
+    tab.on_core_event(CoreEvent::NetworkDone {
+        tab_id: tab.tab_id,
+        request_id: 4,
+        stylesheet_slot_id: None,
+        kind: ResourceKind::Html,
+        response,
+        bytes_received: 40,
+    });
+    let progress = tab.load_progress().expect("still parsing");
+    assert!(progress.document_complete);
+    assert_eq!(progress.fraction(), Some(1.0));
+
+    tab.on_core_event(dom_patch_update(
+        &tab,
+        4,
+        html::Node::Document {
+            id: html::internal::Id(1),
+            doctype: None,
+            children: Vec::new(),
+        },
+    ));
+    assert_eq!(tab.load_progress(), None);
+}
+
+#[test]
+fn parsed_scripts_run_in_the_script_runtime_and_resume_the_parse_when_done() {
+    let (tx, rx) = mpsc::channel();
+    let mut tab = Tab::new(1);
+    tab.set_bus_sender(tx);
+    tab.nav_gen = 6;
+
+    tab.on_core_event(CoreEvent::ScriptParsed {
+        tab_id: tab.tab_id,
+        request_id: 5,
+        source: "stale()".to_string(),
+    });
+    tab.on_core_event(CoreEvent::ScriptParsed {
+        tab_id: tab.tab_id,
+        request_id: 6,
+        source: "run()".to_string(),
+    });
+    let commands: Vec<_> = rx.try_iter().collect();
+    assert!(
+        matches!(
+            commands.as_slice(),
+            [CoreCommand::RunScript { tab_id: 1, request_id: 6, source }] if source == "run()"
+        ),
+        "unexpected commands: {commands:?}"
+    );
+
+    tab.on_core_event(CoreEvent::ScriptDone {
+        tab_id: tab.tab_id,
+        request_id: 6,
+        result: Err("ReferenceError: run is not defined".to_string()),
+    });
+    let commands: Vec<_> = rx.try_iter().collect();
+    assert!(
+        matches!(
+            commands.as_slice(),
+            [CoreCommand::ParseHtmlResume {
+                tab_id: 1,
+                request_id: 6
+            }]
+        ),
+        "unexpected commands: {commands:?}"
+    );
+}
+
+#[test]
+fn metrics_of_the_current_navigation_are_merged() {
+    let mut tab = Tab::new(1);
+    tab.nav_gen = 4;
+
+    let sample = |request_id, which, metrics| CoreEvent::Metrics {
+        tab_id: 1,
+        request_id,
+        which,
+        metrics,
+    };
+    tab.on_core_event(sample(
+        3,
+        RuntimeKind::Net,
+        RuntimeMetrics {
+            bytes_fetched: 1000,
+            ..RuntimeMetrics::default()
+        },
+    ));
+    tab.on_core_event(sample(
+        4,
+        RuntimeKind::Net,
+        RuntimeMetrics {
+            bytes_fetched: 300,
+            ..RuntimeMetrics::default()
+        },
+    ));
+    tab.on_core_event(sample(
+        4,
+        RuntimeKind::Parse,
+        RuntimeMetrics {
+            tokens_emitted: 25,
+            dom_nodes: 10,
+            ..RuntimeMetrics::default()
+        },
+    ));
+
+    assert_eq!(tab.metrics().bytes_fetched, 300);
+    assert_eq!(tab.metrics().tokens_emitted, 25);
+    assert_eq!(tab.metrics().dom_nodes, 10);
+}
@@ -0,0 +1,82 @@
+use super::super::Tab;
+use super::support::{find_dom_element, two_paragraph_patch_document};
+use bus::{CoreEvent, Recording};
+use core_types::{DomHandle, DomVersion, NetworkResponseInfo, ResourceKind};
+
+fn load_page(tab: &mut Tab, url: &str) {
+    tab.navigate_to_new(url.to_string());
+    let (tab_id, request_id) = (tab.tab_id, tab.nav_gen);
+    let response = NetworkResponseInfo {
+        requested_url: url.to_string(),
+        final_url: url.to_string(),
+        status_code: Some(200),
+        content_type: Some("text/html".to_string()),
+        content_length: None,
+        headers: Vec::new(),
+    };
+    let mut patches = two_paragraph_patch_document("p {\n\tcolor: red;\n}");
+    let rest = patches.split_off(9);
+
+    tab.on_core_event(CoreEvent::NetworkStart {
+        tab_id,
+        request_id,
+        stylesheet_slot_id: None,
+        kind: ResourceKind::Html,
+        response: response.clone(),
+    });
+    tab.on_core_event(CoreEvent::NetworkChunk {
+        tab_id,
+        request_id,
+        stylesheet_slot_id: None,
+        kind: ResourceKind::Html,
+        url: url.to_string(),
+        bytes: b"<p>First<p>Second".to_vec(),
+    });
+    tab.on_core_event(CoreEvent::DomPatchUpdate {
+        tab_id,
+        request_id,
+        handle: DomHandle(1),
+        from: DomVersion::INITIAL,
+        to: DomVersion(1),
+        patches,
+    });
+    tab.on_core_event(CoreEvent::DomPatchUpdate {
+        tab_id,
+        request_id,
+        handle: DomHandle(1),
+        from: DomVersion(1),
+        to: DomVersion(2),
+        patches: rest,
+    });
+    tab.on_core_event(CoreEvent::NetworkDone {
+        tab_id,
+        request_id,
+        stylesheet_slot_id: None,
+        kind: ResourceKind::Html,
+        response,
+        bytes_received: 17,
+    });
+}
+
+#[test]
+fn replaying_a_recorded_navigation_rebuilds_the_same_document() {
+    let dir = std::env::temp_dir().join(format!("borrowser-tab-recording-{}", std::process::id()));
+    let mut recorded = Tab::new(4);
+    recorded.set_event_recording(&dir);
+    load_page(&mut recorded, "https://example.com/");
+    let path = dir.join(format!("tab4-nav{}.events", recorded.nav_gen));
+    let recording = Recording::load(&path).expect("read recording");
+    let _ = std::fs::remove_dir_all(&dir);
+    assert_eq!(recording.url, "https://example.com/");
+    assert_eq!(recording.len(), 5);
+
+    let mut replayed = Tab::new(9);
+    replayed.replay(recording);
+
+    let dom = replayed.page.dom.as_deref().expect("replayed document");
+    assert!(find_dom_element(dom, "p").is_some());
+    let original = recorded.page.dom.as_deref().expect("recorded document");
+    assert_eq!(format!("{dom:?}"), format!("{original:?}"));
+    assert_eq!(replayed.page.pending_count(), 0);
+    assert!(!replayed.loading);
+}
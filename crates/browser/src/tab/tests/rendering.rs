@@ -1,10 +1,14 @@
 use super::super::Tab;
+use super::support::{dom_patch_update, initial_patch_document};
 use crate::rendering::{
     RenderInvalidationEntryPoint, RenderPhaseExecutionKind, RenderRebuildTrigger, RenderingPhase,
+    RetainedLayoutArtifactAction,
 };
-use bus::CoreEvent;
+use bus::{CoreCommand, CoreEvent, InProcess};
+use core_types::{DomHandle, DomVersion};
 use egui::Context;
-use html::{HtmlParseOptions, parse_document};
+use html::{DomPatch, HtmlParseOptions, PatchKey, parse_document};
+use std::sync::mpsc;
 
 #[test]
 fn ui_content_consumes_pending_render_work_through_explicit_orchestration_path() {
@@ -18,11 +22,7 @@ fn ui_content_consumes_pending_render_work_through_explicit_orchestration_path()
     )
     .expect("parse should succeed");
 
-    tab.on_core_event(CoreEvent::DomUpdate {
-        tab_id: tab.tab_id,
-        request_id: 31,
-        dom: Box::new(output.document),
-    });
+    tab.on_core_event(dom_patch_update(&tab, 31, output.document));
 
     assert_eq!(
         tab.pending_render_work
@@ -37,7 +37,9 @@ fn ui_content_consumes_pending_render_work_through_explicit_orchestration_path()
     );
 
     let ctx = Context::default();
-    let _ = ctx.run(egui::RawInput::default(), |ctx| tab.ui_content(ctx));
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        tab.ui_content(ctx);
+    });
 
     assert!(tab.pending_render_work.is_empty());
     let trace = tab
@@ -68,3 +70,189 @@ fn ui_content_consumes_pending_render_work_through_explicit_orchestration_path()
         ]
     );
 }
+
+fn painted_font_sizes(tab: &Tab) -> Vec<f32> {
+    fn collect(node: &gfx::paint::PaintNode, out: &mut Vec<f32>) {
+        for primitive in node.primitives().iter().chain(node.post_primitives()) {
+            if let gfx::paint::PaintPrimitive::Text(text) = primitive {
+                out.push(text.font_size_px);
+            }
+        }
+        for child in node.children() {
+            collect(child, out);
+        }
+    }
+    let mut sizes = Vec::new();
+    let artifact = tab
+        .page
+        .retained_paint_artifact()
+        .expect("frame should retain a paint artifact");
+    collect(artifact.tree().root(), &mut sizes);
+    sizes
+}
+
+#[test]
+fn zoom_relays_out_document_with_scaled_lengths_and_survives_navigation() {
+    let mut tab = Tab::new(1);
+    tab.nav_gen = 5;
+    tab.page.start_nav("https://example.com/");
+    let output = parse_document(
+        "<!doctype html><html><head><style>p { font-size: 10px; }</style></head><body><p>Hello</p></body></html>",
+        HtmlParseOptions::default(),
+    )
+    .expect("parse should succeed");
+    tab.on_core_event(dom_patch_update(&tab, 5, output.document));
+
+    let ctx = Context::default();
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        tab.ui_content(ctx);
+    });
+    assert_eq!(painted_font_sizes(&tab), vec![10.0]);
+
+    tab.zoom_in();
+    tab.zoom_in();
+    tab.zoom_in();
+    assert_eq!(tab.zoom(), 1.5);
+    assert!(
+        tab.pending_render_work
+            .requests()
+            .iter()
+            .any(|request| request.entry_point == RenderInvalidationEntryPoint::ViewportChanged)
+    );
+
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        tab.ui_content(ctx);
+    });
+    let trace = tab
+        .last_render_trace
+        .as_ref()
+        .expect("ui frame should store an orchestration trace");
+    assert_eq!(trace.layout.kind, RenderPhaseExecutionKind::Requested);
+    assert_eq!(painted_font_sizes(&tab), vec![15.0]);
+
+    tab.page.start_nav("https://example.com/next");
+    assert_eq!(tab.zoom(), 1.5);
+
+    tab.reset_zoom();
+    assert_eq!(tab.zoom(), 1.0);
+    tab.zoom_out();
+    assert_eq!(tab.zoom(), 0.9);
+}
+
+fn paragraph_patches(first_key: u32, count: u32) -> Vec<DomPatch> {
+    (0..count)
+        .flat_map(|i| {
+            let p = PatchKey(first_key + 2 * i);
+            let text = PatchKey(first_key + 2 * i + 1);
+            [
+                DomPatch::CreateElement {
+                    key: p,
+                    name: html::internal::html_name("p"),
+                    attributes: Vec::new(),
+                },
+                DomPatch::CreateText {
+                    key: text,
+                    text: format!("paragraph-{i}"),
+                },
+                DomPatch::AppendChild {
+                    parent: p,
+                    child: text,
+                },
+                DomPatch::AppendChild {
+                    parent: PatchKey(6),
+                    child: p,
+                },
+            ]
+        })
+        .collect()
+}
+
+#[test]
+fn large_document_relayouts_run_in_the_layout_runtime_while_the_old_layout_is_drawn() {
+    let mut tab = Tab::new(1);
+    tab.nav_gen = 7;
+    tab.page.start_nav("https://example.com/");
+    let (cmd_tx, cmd_rx) = mpsc::channel();
+    tab.set_bus_sender(cmd_tx);
+    let handle = DomHandle(470);
+    let mut patches = initial_patch_document("p { margin: 0; }", None);
+    patches.extend(paragraph_patches(100, 1_000));
+    tab.on_core_event(CoreEvent::DomPatchUpdate {
+        tab_id: tab.tab_id,
+        request_id: 7,
+        handle,
+        from: DomVersion::INITIAL,
+        to: DomVersion(1),
+        patches,
+    });
+
+    // The first layout happens in the frame: there is nothing to draw yet.
+    let ctx = Context::default();
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        tab.ui_content(ctx);
+    });
+    assert_eq!(painted_font_sizes(&tab).len(), 1_000);
+    assert!(
+        !cmd_rx
+            .try_iter()
+            .any(|cmd| matches!(cmd, CoreCommand::LayoutDocument { .. }))
+    );
+
+    tab.on_core_event(CoreEvent::DomPatchUpdate {
+        tab_id: tab.tab_id,
+        request_id: 7,
+        handle,
+        from: DomVersion(1),
+        to: DomVersion(2),
+        patches: paragraph_patches(5_000, 1),
+    });
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        tab.ui_content(ctx);
+    });
+    let job = cmd_rx
+        .try_iter()
+        .find_map(|cmd| match cmd {
+            CoreCommand::LayoutDocument {
+                tab_id: 1,
+                request_id: 7,
+                job,
+            } => Some(job.into_inner()),
+            _ => None,
+        })
+        .expect("relayout should go to the layout runtime");
+    assert_eq!(
+        tab.page
+            .retained_render_state_debug_snapshot()
+            .layout_artifacts
+            .last_action,
+        RetainedLayoutArtifactAction::DeferredToRuntime
+    );
+    assert!(tab.page.layout_dirty());
+    assert_eq!(painted_font_sizes(&tab).len(), 1_000);
+
+    let layout = runtime_layout::lay_out(&job).expect("layout runtime lays the job out");
+    tab.on_core_event(CoreEvent::LayoutReady {
+        tab_id: tab.tab_id,
+        request_id: 7,
+        key: job.key,
+        layout: Ok(InProcess::new(Box::new(layout))),
+    });
+    assert!(!tab.page.layout_dirty());
+
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        tab.ui_content(ctx);
+    });
+    assert_eq!(
+        tab.page
+            .retained_render_state_debug_snapshot()
+            .layout_artifacts
+            .last_action,
+        RetainedLayoutArtifactAction::Reused
+    );
+    assert_eq!(painted_font_sizes(&tab).len(), 1_001);
+    assert!(
+        !cmd_rx
+            .try_iter()
+            .any(|cmd| matches!(cmd, CoreCommand::LayoutDocument { .. }))
+    );
+}
@@ -1,14 +1,14 @@
 use super::super::Tab;
 use super::support::{
-    current_element_color_by_id, find_styled_node_id, initial_patch_document,
+    current_element_color_by_id, dom_patch_update, find_styled_node_id, initial_patch_document,
     two_paragraph_patch_document,
 };
-use crate::page::StyleRecalcKind;
+use crate::page::{InteractionTargets, StyleRecalcKind};
 use crate::rendering::RetainedStyleArtifactAction;
 use bus::CoreEvent;
 use core_types::{DomHandle, DomVersion};
 use css::ComputedStyleReuseStats;
-use html::{DomPatch, PatchKey, internal::Id};
+use html::{DomPatch, HtmlParseOptions, PatchKey, internal::Id, parse_document};
 
 #[test]
 fn attribute_mutation_without_existing_style_cache_falls_back_to_full_recompute() {
@@ -140,3 +140,98 @@ fn clean_style_cache_reuses_computed_document_without_recompute() {
         1
     );
 }
+
+#[test]
+fn interaction_targets_restyle_only_elements_entering_or_leaving_a_state() {
+    let mut tab = Tab::new(1);
+    tab.nav_gen = 31;
+    tab.page.start_nav("https://example.com/index.html");
+    let output = parse_document(
+        "<style>div:hover { color: red; } p:hover { color: blue; } \
+         input:focus { color: green; } div:active { color: gray; }</style>\
+         <div id=box><p id=para>text</p></div><input id=field>",
+        HtmlParseOptions::default(),
+    )
+    .expect("parse should succeed");
+    tab.on_core_event(dom_patch_update(&tab, 31, output.document));
+    let element_id = |tab: &Tab, id| {
+        let dom = tab.page.dom.as_deref().expect("document");
+        html::query::get_element_by_id(dom, id)
+            .expect("element")
+            .id()
+    };
+    let (div, p, input) = (
+        element_id(&tab, "box"),
+        element_id(&tab, "para"),
+        element_id(&tab, "field"),
+    );
+    let text = tab.page.dom.as_deref().and_then(|dom| {
+        html::query::get_element_by_id(dom, "para")?
+            .children()?
+            .first()
+            .map(html::Node::id)
+    });
+    let black = (0, 0, 0, 255);
+    assert_eq!(current_element_color_by_id(&mut tab, p), black);
+
+    // Hovering the paragraph's text hovers the paragraph and its ancestors.
+    let hover_text = InteractionTargets {
+        hover: text,
+        ..InteractionTargets::default()
+    };
+    assert!(tab.page.set_interaction_targets(hover_text).is_some());
+    assert_eq!(current_element_color_by_id(&mut tab, div), (255, 0, 0, 255));
+    assert_eq!(current_element_color_by_id(&mut tab, p), (0, 0, 255, 255));
+    assert!(
+        tab.page.set_interaction_targets(hover_text).is_none(),
+        "unchanged targets restyle nothing"
+    );
+
+    // Moving from the text to its paragraph changes no element's state.
+    let hover_p = InteractionTargets {
+        hover: Some(p),
+        ..InteractionTargets::default()
+    };
+    assert!(tab.page.set_interaction_targets(hover_p).is_none());
+
+    let press_div = InteractionTargets {
+        active: Some(div),
+        ..hover_p
+    };
+    assert!(tab.page.set_interaction_targets(press_div).is_some());
+    assert_eq!(
+        current_element_color_by_id(&mut tab, div),
+        (128, 128, 128, 255)
+    );
+
+    // Moving the pointer to the input keeps `html` and `body` hovered, so the
+    // elements before the div keep their computed styles.
+    let hover_input = InteractionTargets {
+        hover: Some(input),
+        ..press_div
+    };
+    assert!(tab.page.set_interaction_targets(hover_input).is_some());
+    assert_eq!(
+        current_element_color_by_id(&mut tab, p),
+        (128, 128, 128, 255),
+        "the paragraph is no longer hovered and inherits the pressed div's color"
+    );
+    assert!(matches!(
+        tab.page.last_style_recalc(),
+        Some(StyleRecalcKind::IncrementalSuffix {
+            reused_prefix_len, ..
+        }) if reused_prefix_len > 0
+    ));
+
+    let focus_input = InteractionTargets {
+        focus: Some(input),
+        ..InteractionTargets::default()
+    };
+    assert!(tab.page.set_interaction_targets(focus_input).is_some());
+    assert_eq!(current_element_color_by_id(&mut tab, div), black);
+    assert_eq!(current_element_color_by_id(&mut tab, p), black);
+    assert_eq!(
+        current_element_color_by_id(&mut tab, input),
+        (0, 128, 0, 255)
+    );
+}
@@ -1,5 +1,7 @@
 use super::super::Tab;
-use super::support::{current_element_color, find_dom_element, find_styled_element};
+use super::support::{
+    current_element_color, dom_patch_update, find_dom_element, find_styled_element,
+};
 use bus::{CoreCommand, CoreEvent};
 use core_types::ResourceKind;
 use css::build_style_tree_with_stylesheets;
@@ -18,11 +20,7 @@ fn inline_styles_are_attached_and_computed_during_initial_document_load() {
     )
     .expect("parse should succeed");
 
-    tab.on_core_event(CoreEvent::DomUpdate {
-        tab_id: tab.tab_id,
-        request_id: 11,
-        dom: Box::new(output.document),
-    });
+    tab.on_core_event(dom_patch_update(&tab, 11, output.document));
 
     let dom = tab.page.dom.as_deref().expect("dom installed");
     let styled = build_style_tree_with_stylesheets(dom, tab.page.css_stylesheets())
@@ -97,11 +95,7 @@ fn split_text_style_element_is_concatenated_without_synthetic_newlines() {
         )],
     });
 
-    tab.on_core_event(CoreEvent::DomUpdate {
-        tab_id: tab.tab_id,
-        request_id: 15,
-        dom,
-    });
+    tab.on_core_event(dom_patch_update(&tab, 15, *dom));
 
     let dom = tab.page.dom.as_deref().expect("dom installed");
     let styled = build_style_tree_with_stylesheets(dom, tab.page.css_stylesheets())
@@ -124,11 +118,7 @@ fn repeated_dom_updates_reconcile_inline_styles_without_duplicate_slots() {
 
     for _ in 0..2 {
         let output = parse_document(html, HtmlParseOptions::default()).expect("parse succeeds");
-        tab.on_core_event(CoreEvent::DomUpdate {
-            tab_id: tab.tab_id,
-            request_id: 14,
-            dom: Box::new(output.document),
-        });
+        tab.on_core_event(dom_patch_update(&tab, 14, output.document));
     }
 
     assert_eq!(
@@ -163,11 +153,7 @@ fn external_stylesheets_keep_document_order_when_network_arrives_out_of_order()
     )
     .expect("parse should succeed");
 
-    tab.on_core_event(CoreEvent::DomUpdate {
-        tab_id: tab.tab_id,
-        request_id: 12,
-        dom: Box::new(output.document),
-    });
+    tab.on_core_event(dom_patch_update(&tab, 12, output.document));
 
     let queued = rx.try_iter().collect::<Vec<_>>();
     let mut css_fetches = queued
@@ -241,11 +227,7 @@ fn duplicate_same_url_stylesheets_keep_distinct_document_slots() {
     )
     .expect("parse should succeed");
 
-    tab.on_core_event(CoreEvent::DomUpdate {
-        tab_id: tab.tab_id,
-        request_id: 13,
-        dom: Box::new(output.document),
-    });
+    tab.on_core_event(dom_patch_update(&tab, 13, output.document));
 
     let slots = rx
         .try_iter()
@@ -281,11 +263,7 @@ fn duplicate_same_url_stylesheets_participate_as_separate_cascade_slots() {
     )
     .expect("parse should succeed");
 
-    tab.on_core_event(CoreEvent::DomUpdate {
-        tab_id: tab.tab_id,
-        request_id: 16,
-        dom: Box::new(output.document),
-    });
+    tab.on_core_event(dom_patch_update(&tab, 16, output.document));
 
     let slots = rx
         .try_iter()
@@ -346,11 +324,7 @@ fn decoded_css_for_removed_stylesheet_slot_is_ignored() {
         HtmlParseOptions::default(),
     )
     .expect("parse should succeed");
-    tab.on_core_event(CoreEvent::DomUpdate {
-        tab_id: tab.tab_id,
-        request_id: 17,
-        dom: Box::new(with_link.document),
-    });
+    tab.on_core_event(dom_patch_update(&tab, 17, with_link.document));
 
     let (removed_slot, removed_url) = rx
         .try_iter()
@@ -370,11 +344,7 @@ fn decoded_css_for_removed_stylesheet_slot_is_ignored() {
         HtmlParseOptions::default(),
     )
     .expect("parse should succeed");
-    tab.on_core_event(CoreEvent::DomUpdate {
-        tab_id: tab.tab_id,
-        request_id: 17,
-        dom: Box::new(without_link.document),
-    });
+    tab.on_core_event(dom_patch_update(&tab, 17, without_link.document));
 
     tab.on_core_event(CoreEvent::CssDecodedBlock {
         tab_id: tab.tab_id,
@@ -413,11 +383,7 @@ fn external_stylesheet_arrival_invalidates_cached_computed_style() {
     )
     .expect("parse should succeed");
 
-    tab.on_core_event(CoreEvent::DomUpdate {
-        tab_id: tab.tab_id,
-        request_id: 24,
-        dom: Box::new(output.document),
-    });
+    tab.on_core_event(dom_patch_update(&tab, 24, output.document));
 
     let (slot_id, url) = rx
         .try_iter()
@@ -453,3 +419,163 @@ fn external_stylesheet_arrival_invalidates_cached_computed_style() {
     assert_eq!(current_element_color(&mut tab, "p"), (255, 0, 0, 255));
     assert!(!tab.page.style_dirty());
 }
+
+#[test]
+fn hinted_stylesheets_are_fetched_early_and_apply_once_their_link_arrives() {
+    let mut tab = Tab::new(1);
+    let (tx, rx) = mpsc::channel();
+    tab.set_bus_sender(tx);
+    tab.nav_gen = 16;
+    tab.page.start_nav("https://example.com/dir/index.html");
+
+    for _ in 0..2 {
+        tab.on_core_event(CoreEvent::ResourceHint {
+            tab_id: tab.tab_id,
+            request_id: 16,
+            url: "a.css".to_string(),
+            kind: ResourceKind::Css,
+        });
+    }
+    tab.on_core_event(CoreEvent::ResourceHint {
+        tab_id: tab.tab_id,
+        request_id: 16,
+        url: "hero.png".to_string(),
+        kind: ResourceKind::Image,
+    });
+    let fetches = rx
+        .try_iter()
+        .filter_map(|cmd| match cmd {
+            CoreCommand::FetchStream {
+                stylesheet_slot_id,
+                url,
+                kind,
+                ..
+            } => Some((stylesheet_slot_id, url, kind)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let [
+        (Some(slot_id), css_url, ResourceKind::Css),
+        (None, image_url, ResourceKind::Image),
+    ] = fetches.as_slice()
+    else {
+        panic!("expected one stylesheet and one image fetch, got {fetches:?}");
+    };
+    assert_eq!(css_url, "https://example.com/dir/a.css");
+    assert_eq!(image_url, "https://example.com/dir/hero.png");
+
+    tab.on_core_event(CoreEvent::CssDecodedBlock {
+        tab_id: tab.tab_id,
+        request_id: 16,
+        stylesheet_slot_id: *slot_id,
+        url: css_url.clone(),
+        css_block: "p { color: green; }".to_string(),
+    });
+    assert!(
+        tab.page.css_stylesheets().is_empty(),
+        "a preload applies only once the DOM references it"
+    );
+
+    let output = parse_document(
+        "<link rel=stylesheet href=a.css><p>Hello</p><img src=hero.png>",
+        HtmlParseOptions::default(),
+    )
+    .expect("parse should succeed");
+    tab.on_core_event(dom_patch_update(&tab, 16, output.document));
+
+    assert!(
+        !rx.try_iter().any(|cmd| matches!(
+            cmd,
+            CoreCommand::FetchStream {
+                kind: ResourceKind::Css | ResourceKind::Image,
+                ..
+            }
+        )),
+        "the DOM adopts the hinted fetches instead of starting new ones"
+    );
+    assert_eq!(current_element_color(&mut tab, "p"), (0, 128, 0, 255));
+}
+
+#[test]
+fn imported_stylesheets_are_fetched_and_cascade_ahead_of_their_importer() {
+    let mut tab = Tab::new(1);
+    let (tx, rx) = mpsc::channel();
+    tab.set_bus_sender(tx);
+    tab.nav_gen = 18;
+    tab.page.start_nav("https://example.com/index.html");
+    let css_fetches = |rx: &mpsc::Receiver<CoreCommand>| {
+        rx.try_iter()
+            .filter_map(|cmd| match cmd {
+                CoreCommand::FetchStream {
+                    stylesheet_slot_id: Some(slot_id),
+                    url,
+                    kind: ResourceKind::Css,
+                    ..
+                } => Some((slot_id, url)),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let output = parse_document(
+        "<link rel=stylesheet href=\"css/site.css\"><p>Hello <em>there</em></p>",
+        HtmlParseOptions::default(),
+    )
+    .expect("parse should succeed");
+    tab.on_core_event(dom_patch_update(&tab, 18, output.document));
+    let [(site_slot, site_url)] = css_fetches(&rx).try_into().expect("one site.css fetch");
+    assert_eq!(site_url, "https://example.com/css/site.css");
+
+    for (href, media) in [("base.css", ""), ("print.css", "print")] {
+        tab.on_core_event(CoreEvent::CssImport {
+            tab_id: tab.tab_id,
+            request_id: 18,
+            stylesheet_slot_id: site_slot,
+            href: href.to_string(),
+            media: media.to_string(),
+        });
+    }
+    let [(base_slot, base_url), (print_slot, print_url)] =
+        css_fetches(&rx).try_into().expect("one fetch per import");
+    assert_eq!(base_url, "https://example.com/css/base.css");
+    assert_eq!(print_url, "https://example.com/css/print.css");
+
+    // An import of a sheet further up the chain would loop and is ignored.
+    for href in ["site.css", "../fonts/fonts.css"] {
+        tab.on_core_event(CoreEvent::CssImport {
+            tab_id: tab.tab_id,
+            request_id: 18,
+            stylesheet_slot_id: base_slot,
+            href: href.to_string(),
+            media: String::new(),
+        });
+    }
+    let [(_, fonts_url)] = css_fetches(&rx).try_into().expect("one nested fetch");
+    assert_eq!(fonts_url, "https://example.com/fonts/fonts.css");
+    assert_eq!(tab.page.pending_count(), 4);
+
+    for (slot, url, css_block) in [
+        (
+            site_slot,
+            site_url,
+            "@import \"base.css\"; p { color: blue; }",
+        ),
+        (
+            base_slot,
+            base_url,
+            "p { color: red; } em { color: green; }",
+        ),
+        (print_slot, print_url, "em { color: black; }"),
+    ] {
+        tab.on_core_event(CoreEvent::CssDecodedBlock {
+            tab_id: tab.tab_id,
+            request_id: 18,
+            stylesheet_slot_id: slot,
+            url,
+            css_block: css_block.to_string(),
+        });
+    }
+    assert_eq!(tab.page.css_stylesheets().len(), 2);
+    assert_eq!(current_element_color(&mut tab, "p"), (0, 0, 255, 255));
+    assert_eq!(current_element_color(&mut tab, "em"), (0, 128, 0, 255));
+}
@@ -1,6 +1,8 @@
 use super::super::Tab;
+use bus::CoreEvent;
+use core_types::{DomHandle, DomVersion, RequestId};
 use css::StyledNode;
-use html::{DomPatch, Node, PatchKey, internal::Id};
+use html::{DomDiffState, DomPatch, Node, PatchKey, diff_from_empty, internal::Id};
 use layout::TextMeasurer;
 
 pub(super) fn find_styled_element<'a>(
@@ -69,6 +71,23 @@ pub(super) fn find_styled_node_id<'a>(
         .find_map(|child| find_styled_node_id(child, want))
 }
 
+/// Deliver `dom` the way the parse runtime does: one patch batch that builds
+/// the whole document, under a handle the tab has not seen yet. Nodes without
+/// an id are numbered first, as parsed documents come back unnumbered.
+pub(super) fn dom_patch_update(tab: &Tab, request_id: RequestId, mut dom: Node) -> CoreEvent {
+    html::internal::assign_missing_full_model_ids_for_test(&mut dom);
+    let handle = DomHandle(tab.dom_handle.map_or(1, |handle| handle.0 + 1));
+    CoreEvent::DomPatchUpdate {
+        tab_id: tab.tab_id,
+        request_id,
+        handle,
+        from: DomVersion::INITIAL,
+        to: DomVersion::INITIAL.next(),
+        patches: diff_from_empty(&dom, &mut DomDiffState::new())
+            .expect("test documents should diff into patches"),
+    }
+}
+
 pub(super) fn initial_patch_document(
     style_text: &str,
     body_element: Option<&str>,
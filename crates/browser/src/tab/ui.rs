@@ -1,36 +1,103 @@
 use super::Tab;
+use crate::network_log::NetworkLog;
+use crate::page::InteractionTargets;
 use crate::rendering::{RenderInvalidationEntryPoint, render_invalidation_request};
-use crate::view::content;
-use egui::Context;
+use crate::resources::ImageState;
+use crate::scroll_animation::SCROLL_ANIMATION_FRAME;
+use crate::view::{PageAction, content};
+use bus::RuntimeMetrics;
+use egui::{Context, TextureId};
+use gfx::find::FindState;
+use std::time::Instant;
+
+const APP_NAME: &str = "Borrowser";
+
+/// Zoom steps used by Ctrl +/−, matching common browser presets.
+const ZOOM_LEVELS: [f32; 13] = [
+    0.3, 0.5, 0.67, 0.8, 0.9, 1.0, 1.1, 1.25, 1.5, 1.75, 2.0, 2.5, 3.0,
+];
 
 impl Tab {
-    pub fn ui_content(&mut self, ctx: &Context) {
-        // Drain completed decode jobs and upload textures before painting.
+    /// Render the page. Actions that reach beyond this tab (opening other
+    /// tabs) are returned for the shell to handle.
+    pub fn ui_content(&mut self, ctx: &Context) -> Option<PageAction> {
+        // Upload textures for images the image runtime decoded before painting.
         if self.resources.pump(ctx) {
             self.request_render_work(render_invalidation_request(
                 RenderInvalidationEntryPoint::ResourceStateChanged,
             ));
         }
 
+        // Animated images show the frame that is due and come back for the
+        // next one.
+        let now = Instant::now();
+        let next_frame_at = self.resources.advance_animations(now);
+        self.request_frame_at(next_frame_at, now);
+
+        let input = &mut self.document_input;
+        input.scroll_animation.step(&mut input.interaction, now);
+
+        let warning = self.truncation_warning();
         let pending_work = std::mem::take(&mut self.pending_render_work);
+        let paint_span =
+            tracing::info_span!("paint", tab_id = self.tab_id, request_id = self.nav_gen).entered();
         let outcome = content(
             ctx,
             &mut self.page,
             &mut self.document_input,
             &self.resources,
             self.last_status.as_ref(),
+            warning.as_deref(),
             self.loading,
             pending_work,
+            self.cmd_tx.is_some() && self.dom_handle.is_some(),
         );
+        drop(paint_span);
         self.last_render_trace = outcome.trace;
+        if let Some(request) = outcome.layout_job {
+            self.send_layout_job(ctx, request);
+        }
+        // A restore the finished page is too short for would be retried every
+        // frame; settle for the bottom of the page instead.
+        if !self.loading && !self.layout_dirty() {
+            self.document_input.interaction.clamp_pending_scroll();
+        }
+        // Keep frames coming while a scroll animation has somewhere to go.
+        if self.document_input.interaction.scroll_target.is_some()
+            && let Some(repaint) = &self.repaint
+        {
+            repaint.request_after(SCROLL_ANIMATION_FRAME);
+        }
         if let Some(request) = outcome.followup_render_request {
             self.request_render_work(request);
         }
-        if let Some(action) = outcome.action {
-            match action {
-                crate::view::PageAction::Navigate(url) => self.navigate_to_new(url),
+        let interaction = &self.document_input.interaction;
+        let targets = InteractionTargets::from_interaction(interaction);
+        let viewport_size = interaction
+            .last_viewport_width
+            .zip(interaction.last_viewport_height);
+        let restyle = self.page.set_interaction_targets(targets);
+        self.request_optional_render_work(restyle);
+        if let Some((width, height)) = viewport_size {
+            let restyle = self.page.set_viewport_size(width, height);
+            self.request_optional_render_work(restyle);
+        }
+        if let Some(factor) = self.document_input.interaction.pinch_zoom.take() {
+            self.pinch_zoom(factor);
+        }
+        match outcome.action? {
+            PageAction::Navigate(url) => self.navigate_to_new(url),
+            PageAction::Submit(submission) => self.submit_form(submission),
+            PageAction::Reload => self.refresh(),
+            PageAction::SaveImage(url) => self.save_image(&url),
+            PageAction::PickFiles(input_id) => self.pick_files(input_id),
+            action @ (PageAction::OpenInNewTab(_)
+            | PageAction::NavigateInNewTab(_)
+            | PageAction::ViewSource) => {
+                return Some(action);
             }
         }
+        None
     }
 
     /// Derive a human-friendly label from the URL:
@@ -82,6 +149,112 @@ impl Tab {
         String::new()
     }
 
+    /// Upload decoded page icons as textures, and show the due frame of an
+    /// animated one. Called for every tab, not just the active one, so
+    /// background tabs show their icon too.
+    pub fn pump_icons(&mut self, ctx: &Context) {
+        self.icons.pump(ctx);
+        let now = Instant::now();
+        let next_frame_at = self.icons.advance_animations(now);
+        self.request_frame_at(next_frame_at, now);
+    }
+
+    /// Have the window draw again when the next animation frame is due.
+    fn request_frame_at(&self, next_frame_at: Option<Instant>, now: Instant) {
+        if let (Some(next_frame_at), Some(repaint)) = (next_frame_at, &self.repaint) {
+            repaint.request_after(next_frame_at.saturating_duration_since(now));
+        }
+    }
+
+    /// Texture of the current document's icon, once it has decoded.
+    pub fn favicon(&self) -> Option<TextureId> {
+        match self.icons.image_state_by_url(self.favicon_url.as_deref()?) {
+            ImageState::Ready(image) => Some(image.texture_id),
+            _ => None,
+        }
+    }
+
+    pub fn find(&self) -> &FindState {
+        &self.document_input.interaction.find
+    }
+
+    pub fn find_mut(&mut self) -> &mut FindState {
+        &mut self.document_input.interaction.find
+    }
+
+    /// Requests of the current navigation.
+    pub fn network_log(&self) -> &NetworkLog {
+        &self.network_log
+    }
+
+    /// Runtime counters of the current navigation.
+    pub fn metrics(&self) -> &RuntimeMetrics {
+        &self.metrics
+    }
+
+    /// Toggle the layout debug overlay for the hovered box.
+    pub fn toggle_box_overlay(&mut self) {
+        let interaction = &mut self.document_input.interaction;
+        interaction.box_overlay = !interaction.box_overlay;
+    }
+
+    /// Current page zoom factor (`1.0` is 100%).
+    pub fn zoom(&self) -> f32 {
+        self.page.zoom()
+    }
+
+    /// Step to the next larger zoom level.
+    pub fn zoom_in(&mut self) {
+        let current = self.zoom();
+        let next = ZOOM_LEVELS
+            .iter()
+            .copied()
+            .find(|&level| level > current + f32::EPSILON)
+            .unwrap_or(ZOOM_LEVELS[ZOOM_LEVELS.len() - 1]);
+        self.set_zoom(next);
+    }
+
+    /// Step to the next smaller zoom level.
+    pub fn zoom_out(&mut self) {
+        let current = self.zoom();
+        let next = ZOOM_LEVELS
+            .iter()
+            .rev()
+            .copied()
+            .find(|&level| level < current - f32::EPSILON)
+            .unwrap_or(ZOOM_LEVELS[0]);
+        self.set_zoom(next);
+    }
+
+    pub fn reset_zoom(&mut self) {
+        self.set_zoom(1.0);
+    }
+
+    /// Scale the zoom by a pinch gesture's `factor`, within the range of the
+    /// zoom steps.
+    fn pinch_zoom(&mut self, factor: f32) {
+        let min = ZOOM_LEVELS[0];
+        let max = ZOOM_LEVELS[ZOOM_LEVELS.len() - 1];
+        // Whole percents, so a resting pinch does not re-lay out every frame.
+        let zoom = ((self.zoom() * factor).clamp(min, max) * 100.0).round() / 100.0;
+        self.set_zoom(zoom);
+    }
+
+    fn set_zoom(&mut self, zoom: f32) {
+        let request = self.page.set_zoom(zoom);
+        self.request_optional_render_work(request);
+    }
+
+    /// The document's `<title>`, if it has a non-blank one.
+    pub fn page_title(&self) -> Option<&str> {
+        self.page
+            .head
+            .title
+            .as_deref()
+            .map(str::trim)
+            .filter(|title| !title.is_empty())
+    }
+
     pub fn display_title(&self) -> String {
         // 1) Prefer <title> from head
         if let Some(title) = self.page.head.title.as_ref() {
@@ -115,9 +288,24 @@ impl Tab {
 
         "New Tab".to_string()
     }
+
+    /// Title for the browser window while this tab is active: the page title
+    /// (or its URL while untitled), marked while the page is still loading.
+    pub fn window_title(&self) -> String {
+        let label = match self.page_title() {
+            Some(title) => title.to_string(),
+            None => self.url_label(),
+        };
+        match (label.is_empty(), self.loading) {
+            (true, false) => APP_NAME.to_string(),
+            (true, true) => format!("Loading… — {APP_NAME}"),
+            (false, false) => format!("{label} — {APP_NAME}"),
+            (false, true) => format!("{label} (loading…) — {APP_NAME}"),
+        }
+    }
 }
 
-fn elide_end(text: &str, max_chars: usize) -> String {
+pub(crate) fn elide_end(text: &str, max_chars: usize) -> String {
     let chars: Vec<char> = text.chars().collect();
     if chars.len() <= max_chars {
         return text.to_owned();
@@ -0,0 +1,130 @@
+//! Per-user data and download directories, and the small line-based file
+//! helpers shared by the shell's persisted stores (bookmarks, history,
+//! session).
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Resolve the per-user data directory for Borrowser.
+///
+/// `BORROWSER_DATA_DIR` overrides the platform default.
+pub fn user_data_dir() -> Option<PathBuf> {
+    if let Some(dir) = non_empty_env("BORROWSER_DATA_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+
+    let base = if cfg!(target_os = "windows") {
+        non_empty_env("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        non_empty_env("HOME").map(|home| Path::new(&home).join("Library/Application Support"))
+    } else {
+        non_empty_env("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| non_empty_env("HOME").map(|home| Path::new(&home).join(".local/share")))
+    };
+    base.map(|base| base.join("borrowser"))
+}
+
+/// Resolve the directory saved files go to.
+///
+/// `BORROWSER_DOWNLOAD_DIR` overrides the platform default.
+pub fn downloads_dir() -> Option<PathBuf> {
+    if let Some(dir) = non_empty_env("BORROWSER_DOWNLOAD_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+    if !cfg!(target_os = "windows")
+        && !cfg!(target_os = "macos")
+        && let Some(dir) = non_empty_env("XDG_DOWNLOAD_DIR")
+    {
+        return Some(PathBuf::from(dir));
+    }
+    let home = if cfg!(target_os = "windows") {
+        non_empty_env("USERPROFILE")
+    } else {
+        non_empty_env("HOME")
+    };
+    home.map(|home| Path::new(&home).join("Downloads"))
+}
+
+/// First path in `dir` named `file_name` that does not exist yet, adding a
+/// ` (n)` suffix before the extension when needed.
+pub(crate) fn unused_file_path(dir: &Path, file_name: &str) -> PathBuf {
+    let candidate = dir.join(file_name);
+    if !candidate.exists() {
+        return candidate;
+    }
+    let (stem, extension) = match file_name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem, Some(extension)),
+        _ => (file_name, None),
+    };
+    (1..)
+        .map(|n| match extension {
+            Some(extension) => dir.join(format!("{stem} ({n}).{extension}")),
+            None => dir.join(format!("{stem} ({n})")),
+        })
+        .find(|path| !path.exists())
+        .expect("unbounded candidate sequence")
+}
+
+/// `name` with characters that are unsafe in file names replaced by `_`.
+pub(crate) fn sanitize_file_name(name: &str) -> String {
+    name.chars()
+        .map(|ch| match ch {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            ch if ch.is_control() => '_',
+            ch => ch,
+        })
+        .collect()
+}
+
+fn non_empty_env(key: &str) -> Option<String> {
+    std::env::var(key).ok().filter(|value| !value.is_empty())
+}
+
+/// Write `contents` to `path` via a sibling temp file so a crash mid-write
+/// never leaves a truncated file behind. Creates the parent directory.
+pub(crate) fn write_atomically(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    fs::write(&tmp, contents)?;
+    fs::rename(&tmp, path)
+}
+
+/// Escape tabs, newlines and backslashes so `field` fits in one TSV cell.
+pub(crate) fn escape_field(field: &str) -> String {
+    let mut out = String::with_capacity(field.len());
+    for ch in field.chars() {
+        match ch {
+            '\\' => out.push_str("\\\\"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// Inverse of [`escape_field`].
+pub(crate) fn unescape_field(field: &str) -> String {
+    let mut out = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            out.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => out.push('\t'),
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
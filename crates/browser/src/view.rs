@@ -1,5 +1,5 @@
 use crate::input_state::DocumentInputState;
-use crate::page::PageState;
+use crate::page::{LayoutJobRequest, LayoutOffload, PageState};
 use crate::rendering::{
     OrchestratedFrameOutcome, PendingRenderWork, execute_prepared_page_frame, prepare_page_frame,
 };
@@ -14,26 +14,27 @@ pub(crate) struct ViewContentOutcome {
     pub(crate) action: Option<PageAction>,
     pub(crate) followup_render_request: Option<crate::rendering::RenderInvalidationRequest>,
     pub(crate) trace: Option<crate::rendering::RenderFrameExecutionTrace>,
+    pub(crate) layout_job: Option<LayoutJobRequest>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn content(
     ctx: &Context,
     page: &mut PageState,
     input_state: &mut DocumentInputState,
     resources: &ResourceManager,
     status: Option<&String>,
+    warning: Option<&str>,
     loading: bool,
     pending_work: PendingRenderWork,
+    offload_layout: bool,
 ) -> ViewContentOutcome {
     if page.dom.is_none() {
         let visuals = ctx.style().visuals.clone();
         CentralPanel::default()
             .frame(Frame::default().fill(visuals.panel_fill))
             .show(ctx, |ui| {
-                if loading {
-                    ui.label("⏳ Loading…");
-                }
-                if let Some(s) = status {
+                if let Some(s) = overlay_status(loading, status.map(String::as_str)) {
                     ui.label(s);
                 }
             });
@@ -41,10 +42,23 @@ pub(crate) fn content(
             action: None,
             followup_render_request: None,
             trace: None,
+            layout_job: None,
         };
     }
 
-    let prepared_frame = match prepare_page_frame(page, pending_work) {
+    show_warning_banner(ctx, warning);
+
+    // Relayouts can go to the layout runtime once the page has a width.
+    let offload = input_state
+        .interaction
+        .last_viewport_width
+        .filter(|_| offload_layout)
+        .map(|viewport_width| LayoutOffload {
+            viewport_width,
+            resources,
+            resized_controls: &input_state.interaction.resized_controls,
+        });
+    let mut prepared_frame = match prepare_page_frame(page, pending_work, offload) {
         Ok(Some(prepared_frame)) => prepared_frame,
         Ok(None) => {
             show_status_overlay(ctx, loading, status.map(|status| status.as_str()));
@@ -52,6 +66,7 @@ pub(crate) fn content(
                 action: None,
                 followup_render_request: None,
                 trace: None,
+                layout_job: None,
             };
         }
         Err(error) => {
@@ -66,9 +81,11 @@ pub(crate) fn content(
                 action: None,
                 followup_render_request: None,
                 trace: None,
+                layout_job: None,
             };
         }
     };
+    let layout_job = prepared_frame.layout_job.take();
     let base_fill = if let Some((r, g, b, a)) = prepared_frame.page_background {
         Color32::from_rgba_unmultiplied(r, g, b, a)
     } else {
@@ -94,18 +111,19 @@ pub(crate) fn content(
         page.record_paint_frame_result(retained_paint_result);
     }
     show_status_overlay(ctx, loading, status.map(|status| status.as_str()));
+    show_link_status(ctx, input_state.interaction.hover_href.as_deref());
     ViewContentOutcome {
         action,
         followup_render_request,
         trace: Some(trace),
+        layout_job,
     }
 }
 
 fn show_status_overlay(ctx: &Context, loading: bool, status: Option<&str>) {
-    let lines = overlay_lines(loading, status);
-    if lines.is_empty() {
+    let Some(status) = overlay_status(loading, status) else {
         return;
-    }
+    };
 
     Area::new(Id::new("page_status_overlay"))
         .order(Order::Foreground)
@@ -119,41 +137,84 @@ fn show_status_overlay(ctx: &Context, loading: bool, status: Option<&str>) {
                 .inner_margin(Margin::same(8))
                 .show(ui, |ui| {
                     ui.set_max_width(420.0);
-                    for (idx, line) in lines.iter().enumerate() {
-                        let text = if idx == 0 && loading {
-                            RichText::new(line).strong().color(Color32::WHITE)
-                        } else {
-                            RichText::new(line).color(Color32::WHITE)
-                        };
-                        ui.label(text);
-                    }
+                    ui.label(RichText::new(status).color(Color32::WHITE));
                 });
         });
 }
 
-fn overlay_lines(loading: bool, status: Option<&str>) -> Vec<String> {
-    let mut lines = Vec::new();
-    if loading {
-        lines.push("Loading…".to_string());
-    }
-    if let Some(status) = status {
-        lines.push(status.to_string());
-    }
-    lines
+/// Top banner warning that the page is not shown in full.
+fn show_warning_banner(ctx: &Context, warning: Option<&str>) {
+    let Some(warning) = warning else {
+        return;
+    };
+
+    Area::new(Id::new("page_warning_banner"))
+        .order(Order::Foreground)
+        .anchor(Align2::CENTER_TOP, vec2(0.0, 8.0))
+        .interactable(false)
+        .show(ctx, |ui| {
+            Frame::new()
+                .fill(Color32::from_rgb(255, 243, 205))
+                .stroke(Stroke::new(1.0, Color32::from_rgb(204, 153, 0)))
+                .corner_radius(CornerRadius::same(6))
+                .inner_margin(Margin::symmetric(12, 6))
+                .show(ui, |ui| {
+                    ui.set_max_width(ctx.screen_rect().width() * 0.8);
+                    ui.label(RichText::new(warning).color(Color32::from_rgb(102, 77, 0)));
+                });
+        });
+}
+
+/// Bottom-left status bubble with the hovered link's destination.
+fn show_link_status(ctx: &Context, href: Option<&str>) {
+    let Some(href) = href else {
+        return;
+    };
+
+    Area::new(Id::new("page_link_status"))
+        .order(Order::Foreground)
+        .anchor(Align2::LEFT_BOTTOM, vec2(0.0, 0.0))
+        .interactable(false)
+        .show(ctx, |ui| {
+            Frame::new()
+                .fill(Color32::from_black_alpha(208))
+                .stroke(Stroke::new(1.0, Color32::from_white_alpha(40)))
+                .corner_radius(CornerRadius {
+                    ne: 6,
+                    ..CornerRadius::ZERO
+                })
+                .inner_margin(Margin::symmetric(8, 4))
+                .show(ui, |ui| {
+                    ui.set_max_width(ctx.screen_rect().width() * 0.6);
+                    ui.add(
+                        egui::Label::new(RichText::new(href).color(Color32::WHITE).small())
+                            .truncate(),
+                    );
+                });
+        });
+}
+
+/// Status text to overlay on the page. While the page loads, the shell's
+/// progress bar and tab spinner report progress instead.
+fn overlay_status(loading: bool, status: Option<&str>) -> Option<&str> {
+    status.filter(|_| !loading)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::overlay_lines;
+    use super::overlay_status;
 
     #[test]
-    fn overlay_lines_include_loading_and_status() {
-        let lines = overlay_lines(true, Some("Document parsed • HTTP 200"));
-        assert_eq!(lines, vec!["Loading…", "Document parsed • HTTP 200"]);
+    fn overlay_status_shows_the_outcome_once_loading_ends() {
+        assert_eq!(
+            overlay_status(false, Some("Document parsed • HTTP 200")),
+            Some("Document parsed • HTTP 200")
+        );
     }
 
     #[test]
-    fn overlay_lines_are_empty_without_loading_or_status() {
-        assert!(overlay_lines(false, None).is_empty());
+    fn overlay_status_is_hidden_while_loading_or_without_status() {
+        assert_eq!(overlay_status(true, Some("Loading document")), None);
+        assert_eq!(overlay_status(false, None), None);
     }
 }
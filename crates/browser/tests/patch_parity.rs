@@ -1,5 +1,5 @@
-use browser::dom_store::DomStore;
 use core_types::{DomHandle, DomVersion};
+use dom_store::DomStore;
 use html::dom_snapshot::{DomSnapshotOptions, compare_dom};
 use html::golden_corpus::fixtures;
 use html::{DomDiffState, DomPatch, HtmlParseOptions, HtmlParser, Node, diff_dom_with_state};
@@ -0,0 +1,52 @@
+//! Pixels the image decoding runtime hands back to the UI thread.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+
+/// A decoded image as unmultiplied RGBA8, row by row.
+#[derive(Serialize, Deserialize)]
+pub struct DecodedImage {
+    /// The pixels of the first frame, which is all a still image has.
+    pub rgba: Vec<u8>,
+    /// Width and height in pixels.
+    pub size: [usize; 2],
+    /// How long the first frame shows if the image is animated.
+    pub delay: Duration,
+    /// The frames of an animated image after the first, each `size` large;
+    /// empty for a still image. Playback loops.
+    pub frames: Vec<AnimationFrame>,
+}
+
+/// One frame of an animated image, composited onto the whole canvas.
+#[derive(Serialize, Deserialize)]
+pub struct AnimationFrame {
+    pub rgba: Vec<u8>,
+    /// How long the frame shows before the next.
+    pub delay: Duration,
+}
+
+impl DecodedImage {
+    /// A still image of `size` pixels.
+    pub fn still(rgba: Vec<u8>, size: [usize; 2]) -> Self {
+        Self {
+            rgba,
+            size,
+            delay: Duration::ZERO,
+            frames: Vec::new(),
+        }
+    }
+
+    pub fn is_animated(&self) -> bool {
+        !self.frames.is_empty()
+    }
+}
+
+impl fmt::Debug for DecodedImage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DecodedImage")
+            .field("size", &self.size)
+            .field("frames", &(1 + self.frames.len()))
+            .finish_non_exhaustive()
+    }
+}
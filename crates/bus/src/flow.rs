@@ -0,0 +1,195 @@
+//! Backpressure for streamed HTML documents.
+//!
+//! Document bytes travel from a fetch thread through the UI thread to the
+//! tab's parse runtime, and every hop is an unbounded queue. Instead of
+//! bounding the queues, which would let a slow parser stall the router or
+//! the UI for every tab, a document stream is flow controlled end to end:
+//! the fetch thread takes credit for each chunk before sending it and the
+//! parse runtime gives the credit back once it has consumed the chunk. At
+//! most [`HTML_CHUNK_WINDOW_BYTES`] of a document are in flight, so a fast
+//! network simply waits for a slow parser.
+//!
+//! Only fetch threads ever block, and never on a queue. A window closes when
+//! the request is cancelled, its tab closes, or the parse runtime drops the
+//! document, and closing wakes a waiting fetch thread. Streams without an
+//! open window are never held back. The parse runtime can also stop a
+//! window once it has read all of a document it will, which tells the fetch
+//! thread to end the download.
+
+use core_types::{RequestId, TabId};
+use std::collections::HashMap;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+
+/// Document bytes a request may have sent but the parse runtime not yet
+/// consumed.
+pub const HTML_CHUNK_WINDOW_BYTES: usize = 1024 * 1024;
+
+/// Per-request credit windows shared by the network and parse runtimes.
+#[derive(Clone, Debug, Default)]
+pub struct ChunkCredits {
+    shared: Arc<Shared>,
+}
+
+#[derive(Debug, Default)]
+struct Shared {
+    windows: Mutex<HashMap<(TabId, RequestId), Window>>,
+    released: Condvar,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Window {
+    /// Bytes sent but not yet consumed.
+    Open { in_flight: usize },
+    /// The consumer wants no more of the stream.
+    Stopped,
+}
+
+impl ChunkCredits {
+    /// Start flow control for a document stream.
+    pub fn open(&self, tab_id: TabId, request_id: RequestId) {
+        self.windows()
+            .insert((tab_id, request_id), Window::Open { in_flight: 0 });
+    }
+
+    /// Take credit for a chunk of `bytes`, waiting while the window is full.
+    ///
+    /// A chunk larger than the window passes once nothing else is in flight.
+    /// Returns at once if the window is or gets closed. Returns `false` if
+    /// the consumer stopped the stream, in which case the chunk and the rest
+    /// of the stream should not be sent.
+    pub fn acquire(&self, tab_id: TabId, request_id: RequestId, bytes: usize) -> bool {
+        let key = (tab_id, request_id);
+        let mut windows = self.windows();
+        loop {
+            match windows.get(&key) {
+                None => return true,
+                Some(Window::Stopped) => {
+                    windows.remove(&key);
+                    return false;
+                }
+                Some(&Window::Open { in_flight }) => {
+                    if in_flight == 0 || in_flight + bytes <= HTML_CHUNK_WINDOW_BYTES {
+                        let in_flight = in_flight + bytes;
+                        windows.insert(key, Window::Open { in_flight });
+                        return true;
+                    }
+                }
+            }
+            windows = self
+                .shared
+                .released
+                .wait(windows)
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+    }
+
+    /// Give back credit for `bytes` the consumer is done with.
+    pub fn release(&self, tab_id: TabId, request_id: RequestId, bytes: usize) {
+        if let Some(Window::Open { in_flight }) = self.windows().get_mut(&(tab_id, request_id)) {
+            *in_flight = in_flight.saturating_sub(bytes);
+            self.shared.released.notify_all();
+        }
+    }
+
+    /// The consumer needs no more of the stream: the producer's next
+    /// [`acquire`](Self::acquire) returns `false`. Does nothing once the
+    /// window is closed.
+    pub fn stop(&self, tab_id: TabId, request_id: RequestId) {
+        if let Some(window) = self.windows().get_mut(&(tab_id, request_id)) {
+            *window = Window::Stopped;
+            self.shared.released.notify_all();
+        }
+    }
+
+    /// Stop flow control for a stream; its producer no longer waits.
+    pub fn close(&self, tab_id: TabId, request_id: RequestId) {
+        if self.windows().remove(&(tab_id, request_id)).is_some() {
+            self.shared.released.notify_all();
+        }
+    }
+
+    /// Close every window of a tab.
+    pub fn close_tab(&self, tab_id: TabId) {
+        self.windows().retain(|&(tab, _), _| tab != tab_id);
+        self.shared.released.notify_all();
+    }
+
+    fn windows(&self) -> MutexGuard<'_, HashMap<(TabId, RequestId), Window>> {
+        // The map stays consistent across a panic while it is held.
+        self.shared
+            .windows
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChunkCredits, HTML_CHUNK_WINDOW_BYTES};
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    fn acquire_in_background(credits: &ChunkCredits, bytes: usize) -> mpsc::Receiver<()> {
+        let (done_tx, done_rx) = mpsc::channel();
+        let credits = credits.clone();
+        thread::spawn(move || {
+            credits.acquire(1, 2, bytes);
+            let _ = done_tx.send(());
+        });
+        done_rx
+    }
+
+    #[test]
+    fn acquire_waits_for_release_once_the_window_is_full() {
+        let credits = ChunkCredits::default();
+        credits.open(1, 2);
+        credits.acquire(1, 2, HTML_CHUNK_WINDOW_BYTES);
+
+        let done = acquire_in_background(&credits, 10);
+        assert!(done.recv_timeout(Duration::from_millis(50)).is_err());
+
+        credits.release(1, 2, 10);
+        done.recv_timeout(Duration::from_secs(5))
+            .expect("released credit should let the producer continue");
+    }
+
+    #[test]
+    fn closing_the_window_wakes_a_waiting_producer() {
+        let credits = ChunkCredits::default();
+        credits.open(1, 2);
+        credits.acquire(1, 2, HTML_CHUNK_WINDOW_BYTES);
+
+        let done = acquire_in_background(&credits, 10);
+        assert!(done.recv_timeout(Duration::from_millis(50)).is_err());
+
+        credits.close_tab(1);
+        done.recv_timeout(Duration::from_secs(5))
+            .expect("closing the window should not leave the producer waiting");
+
+        // Streams without a window are not flow controlled.
+        assert!(credits.acquire(1, 2, 2 * HTML_CHUNK_WINDOW_BYTES));
+        assert!(credits.acquire(1, 2, 2 * HTML_CHUNK_WINDOW_BYTES));
+    }
+
+    #[test]
+    fn stopping_the_window_tells_a_waiting_producer_to_end_the_stream() {
+        let credits = ChunkCredits::default();
+        credits.open(1, 2);
+        assert!(credits.acquire(1, 2, HTML_CHUNK_WINDOW_BYTES));
+
+        let (done_tx, done_rx) = mpsc::channel();
+        let producer = credits.clone();
+        thread::spawn(move || {
+            let _ = done_tx.send(producer.acquire(1, 2, 10));
+        });
+        assert!(done_rx.recv_timeout(Duration::from_millis(50)).is_err());
+
+        credits.stop(1, 2);
+        assert_eq!(done_rx.recv_timeout(Duration::from_secs(5)), Ok(false));
+
+        // The window is gone once the producer has seen the stop.
+        credits.release(1, 2, HTML_CHUNK_WINDOW_BYTES);
+        assert!(credits.acquire(1, 2, 10));
+    }
+}
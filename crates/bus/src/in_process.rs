@@ -0,0 +1,65 @@
+//! Payloads that stay in the process that sent them.
+//!
+//! Bus messages serialize so that runtimes can later move into processes of
+//! their own, but a few payloads cannot leave the UI process: a layout job
+//! measures text with the UI's fonts, layout results and legacy DOM
+//! snapshots are trees the UI keeps using in place, and a shutdown
+//! acknowledgement is a channel. [`InProcess`] marks them. Serializing or
+//! deserializing one is an error, so a message that carries such a payload
+//! can only travel over an in-process channel, and a failed attempt leaves
+//! the payload where it was.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de, ser};
+use std::fmt;
+
+/// A payload that only travels within this process.
+pub struct InProcess<T>(T);
+
+impl<T> InProcess<T> {
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// The payload.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for InProcess<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T> Serialize for InProcess<T> {
+    fn serialize<S: Serializer>(&self, _serializer: S) -> Result<S::Ok, S::Error> {
+        Err(ser::Error::custom(
+            "in-process payload cannot be serialized",
+        ))
+    }
+}
+
+impl<'de, T> Deserialize<'de> for InProcess<T> {
+    fn deserialize<D: Deserializer<'de>>(_deserializer: D) -> Result<Self, D::Error> {
+        Err(de::Error::custom(
+            "in-process payload cannot be deserialized",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InProcess;
+
+    #[test]
+    fn serializing_a_payload_fails_and_leaves_it_in_place() {
+        let payload = InProcess::new(vec![1, 2, 3]);
+        assert!(serde_json::to_string(&payload).is_err());
+        assert!(serde_json::to_string(&payload).is_err());
+        assert_eq!(format!("{payload:?}"), "[1, 2, 3]");
+        assert_eq!(payload.into_inner(), [1, 2, 3]);
+
+        assert!(serde_json::from_str::<InProcess<Vec<i32>>>("[1, 2, 3]").is_err());
+    }
+}
@@ -0,0 +1,65 @@
+//! Inputs for laying a document out off the UI thread.
+
+use css::{
+    CascadeOrigin, MediaEnvironment, SelectorElementStates, StylesheetCascadeInput, StylesheetParse,
+};
+use html::{ElementNamespace, Node, internal::Id};
+use layout::{RetainedLayoutKey, TextMeasurer};
+use std::collections::HashMap;
+use std::fmt;
+
+/// A snapshot of everything style and layout read for one document.
+pub struct LayoutJob {
+    pub dom: Box<Node>,
+    /// Stylesheets in cascade order, user agent sheet first.
+    pub stylesheets: Vec<LayoutStylesheet>,
+    /// Element states selectors match against, such as visited links.
+    pub element_states: SelectorElementStates,
+    pub viewport_width: f32,
+    /// Environment the stylesheets' `@media` rules were evaluated against;
+    /// viewport units resolve against its viewport.
+    pub media: MediaEnvironment,
+    /// Page zoom applied to computed lengths.
+    pub zoom: f32,
+    /// Key the resulting layout is retained under.
+    pub key: RetainedLayoutKey,
+    /// Resolved image URL for each exact `src` value in the document.
+    pub image_sources: HashMap<String, String>,
+    /// Decoded image sizes in pixels, by resolved URL.
+    pub image_sizes: HashMap<String, (u32, u32)>,
+    /// Border-box sizes the user dragged resizable controls to.
+    pub resized_controls: HashMap<Id, (f32, f32)>,
+    /// Measures text with the fonts the UI thread paints with.
+    pub measurer: Box<dyn TextMeasurer + Send>,
+}
+
+impl fmt::Debug for LayoutJob {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LayoutJob")
+            .field("stylesheets", &self.stylesheets.len())
+            .field("viewport_width", &self.viewport_width)
+            .field("zoom", &self.zoom)
+            .field("key", &self.key)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LayoutStylesheet {
+    pub origin: CascadeOrigin,
+    pub stylesheet: StylesheetParse,
+}
+
+impl LayoutStylesheet {
+    pub fn cascade_input(&self) -> StylesheetCascadeInput<'_> {
+        match self.origin {
+            CascadeOrigin::UserAgent => StylesheetCascadeInput::user_agent_for_namespace(
+                &self.stylesheet,
+                ElementNamespace::Html,
+            ),
+            CascadeOrigin::User | CascadeOrigin::Author => {
+                StylesheetCascadeInput::new(self.origin, &self.stylesheet)
+            }
+        }
+    }
+}
@@ -1,11 +1,41 @@
 use core_types::{
-    DomHandle, DomVersion, NetworkErrorKind, NetworkResponseInfo, ResourceKind, StylesheetSlotId,
-    TabId,
+    DomHandle, DomVersion, NetError, NetworkResponseInfo, RequestPayload, ResourceKind,
+    StylesheetSlotId, TabId,
 };
-use html::{DomPatch, Node};
+use html::{DomPatch, internal::Id};
+use layout::{RetainedLayoutArtifact, RetainedLayoutKey};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
 use std::sync::mpsc::{Receiver, Sender};
+use std::time::Duration;
 
-#[derive(Debug)]
+mod decoded_image;
+mod flow;
+mod in_process;
+mod layout_job;
+mod metrics;
+mod parse_limits;
+mod recording;
+mod supervise;
+#[cfg(test)]
+mod tests;
+
+pub use decoded_image::{AnimationFrame, DecodedImage};
+pub use flow::{ChunkCredits, HTML_CHUNK_WINDOW_BYTES};
+pub use in_process::InProcess;
+pub use layout_job::{LayoutJob, LayoutStylesheet};
+pub use metrics::RuntimeMetrics;
+pub use parse_limits::{ParseLimit, ParseLimits};
+pub use recording::{EventRecorder, Recording, RecordingError};
+pub use supervise::run_supervised;
+
+/// Commands to the runtimes.
+///
+/// Commands and events serialize, so a runtime can later run in a process
+/// of its own. Payloads that cannot leave the UI process are wrapped in
+/// [`InProcess`], and the messages carrying them fail to serialize.
+#[derive(Debug, Serialize, Deserialize)]
 pub enum CoreCommand {
     // Network requests
     FetchStream {
@@ -14,15 +44,28 @@ pub enum CoreCommand {
         stylesheet_slot_id: Option<StylesheetSlotId>,
         url: String,
         kind: ResourceKind,
+        /// Method, headers, and body. Subresources are always fetched with a
+        /// plain GET; only documents send anything else.
+        payload: RequestPayload,
     },
     CancelRequest {
         tab_id: TabId,
         request_id: u64,
     },
+    /// The tab left `request_id` and every older request behind: fetches
+    /// are cancelled and every runtime drops what it holds for them.
+    CancelGeneration {
+        tab_id: TabId,
+        request_id: u64,
+    },
     // HTML Parser
     ParseHtmlStart {
         tab_id: TabId,
         request_id: u64,
+        /// `Content-Type` the document was served with; its `charset`
+        /// picks the encoding the bytes are decoded in.
+        content_type: Option<String>,
+        limits: ParseLimits,
     },
     ParseHtmlChunk {
         tab_id: TabId,
@@ -33,6 +76,18 @@ pub enum CoreCommand {
         tab_id: TabId,
         request_id: u64,
     },
+    // Continue parsing after the script the parser stopped at has run
+    ParseHtmlResume {
+        tab_id: TabId,
+        request_id: u64,
+    },
+    // UI -> HTML Parser: the tab applied the patch update up to `version`;
+    // the parser backs off while the UI falls behind
+    DomUpdateApplied {
+        tab_id: TabId,
+        request_id: u64,
+        version: DomVersion,
+    },
     // CSS stylesheet runtime
     CssChunk {
         tab_id: TabId,
@@ -53,11 +108,79 @@ pub enum CoreCommand {
         stylesheet_slot_id: StylesheetSlotId,
         url: String,
     },
+    // Style and layout runtime
+    LayoutDocument {
+        tab_id: TabId,
+        request_id: u64,
+        job: InProcess<Box<LayoutJob>>,
+    },
+    // Image decoding runtime
+    DecodeImage {
+        tab_id: TabId,
+        request_id: u64,
+        kind: ResourceKind,
+        url: String,
+        bytes: Arc<[u8]>,
+    },
+    // Script runtime
+    RunScript {
+        tab_id: TabId,
+        request_id: u64,
+        source: String,
+    },
+    // Native file dialog
+    PickFiles {
+        tab_id: TabId,
+        request_id: u64,
+        input_id: Id,
+        multiple: bool,
+    },
+    // Timer runtime: fire `TimeoutFired` with `token` after `delay`. Tokens
+    // are chosen by the caller; setting a token again restarts its timer.
+    SetTimeout {
+        tab_id: TabId,
+        token: u64,
+        delay: Duration,
+    },
+    ClearTimeout {
+        tab_id: TabId,
+        token: u64,
+    },
+    // Tab lifecycle: stops the tab's parse, css, layout, image, and script
+    // runtimes and clears its timers
+    TabClosed {
+        tab_id: TabId,
+    },
+    /// The app is exiting: each runtime handles the commands queued before
+    /// this one, sends on `ack`, and stops.
+    Shutdown {
+        ack: InProcess<Sender<()>>,
+    },
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub enum CoreEvent {
     // Network -> UI
+    /// A request was redirected from `from` to `to`; its events continue
+    /// under the same request id.
+    NetworkRedirected {
+        tab_id: TabId,
+        request_id: u64,
+        stylesheet_slot_id: Option<StylesheetSlotId>,
+        kind: ResourceKind,
+        from: String,
+        to: String,
+    },
+    /// Diagnostics: a request of the fetch went to `origin` over a pooled
+    /// keep-alive connection if `reused`, or else over a new one.
+    NetworkConnection {
+        tab_id: TabId,
+        request_id: u64,
+        stylesheet_slot_id: Option<StylesheetSlotId>,
+        kind: ResourceKind,
+        origin: String,
+        reused: bool,
+    },
     NetworkStart {
         tab_id: TabId,
         request_id: u64,
@@ -87,17 +210,11 @@ pub enum CoreEvent {
         stylesheet_slot_id: Option<StylesheetSlotId>,
         kind: ResourceKind,
         url: String,
-        error_kind: NetworkErrorKind,
-        status_code: Option<u16>,
-        error: String,
+        error: NetError,
+        /// The underlying failure, worded for logs and the status bar.
+        message: String,
     },
 
-    // HTML Parser -> UI (legacy snapshot path)
-    DomUpdate {
-        tab_id: TabId,
-        request_id: u64,
-        dom: Box<Node>,
-    },
     // HTML Parser -> UI (patch stream)
     DomPatchUpdate {
         tab_id: TabId,
@@ -107,8 +224,42 @@ pub enum CoreEvent {
         to: DomVersion,
         patches: Vec<DomPatch>,
     },
+    // HTML Parser -> UI: parsing stopped at the end of a script and waits
+    // for `ParseHtmlResume`. Patches up to the script arrive first.
+    ScriptParsed {
+        tab_id: TabId,
+        request_id: u64,
+        source: String,
+    },
+    // HTML Parser -> UI: the preload scanner saw a stylesheet or image
+    // reference ahead of the tree builder. `url` is the attribute value as
+    // written; the tab resolves it and may fetch it before the DOM has it.
+    ResourceHint {
+        tab_id: TabId,
+        request_id: u64,
+        url: String,
+        kind: ResourceKind,
+    },
+    // HTML Parser -> UI: the document ran into one of its `ParseLimits`.
+    // The parse ended there as if the input had, and later chunks are
+    // ignored.
+    ResourceTruncated {
+        tab_id: TabId,
+        request_id: u64,
+        limit: ParseLimit,
+    },
 
     // CSS stylesheet runtime -> UI
+    // The sheet in `stylesheet_slot_id` has an `@import` of `href`, as
+    // written, under the media query list `media`. Sent in rule order, ahead
+    // of the sheet's decoded block.
+    CssImport {
+        tab_id: TabId,
+        request_id: u64,
+        stylesheet_slot_id: StylesheetSlotId,
+        href: String,
+        media: String,
+    },
     // Carries fully decoded stylesheet text for downstream css::syntax parsing.
     CssDecodedBlock {
         tab_id: TabId,
@@ -123,6 +274,265 @@ pub enum CoreEvent {
         stylesheet_slot_id: StylesheetSlotId,
         url: String,
     },
+
+    // Style and layout runtime -> UI; `Err` carries the style error.
+    LayoutReady {
+        tab_id: TabId,
+        request_id: u64,
+        key: RetainedLayoutKey,
+        layout: Result<InProcess<Box<RetainedLayoutArtifact>>, String>,
+    },
+
+    // Image decoding runtime -> UI; `Err` carries the decoder error.
+    ImageDecoded {
+        tab_id: TabId,
+        request_id: u64,
+        kind: ResourceKind,
+        url: String,
+        image: Result<DecodedImage, String>,
+    },
+
+    // Script runtime -> UI; `Err` carries the script error.
+    ScriptDone {
+        tab_id: TabId,
+        request_id: u64,
+        result: Result<(), String>,
+    },
+
+    // Native file dialog -> UI; not sent when the dialog is cancelled.
+    FilesPicked {
+        tab_id: TabId,
+        request_id: u64,
+        input_id: Id,
+        paths: Vec<PathBuf>,
+    },
+
+    // Timer runtime -> UI: the timeout set with `token` elapsed.
+    TimeoutFired {
+        tab_id: TabId,
+        token: u64,
+    },
+
+    // Runtime -> UI: counters a runtime measured for this request, sampled
+    // once per fetch, parse, or layout job.
+    Metrics {
+        tab_id: TabId,
+        request_id: u64,
+        which: RuntimeKind,
+        metrics: RuntimeMetrics,
+    },
+
+    // Runtime -> UI: a runtime panicked while handling a command of this
+    // request and restarted with empty state.
+    RuntimeCrashed {
+        tab_id: TabId,
+        request_id: u64,
+        which: RuntimeKind,
+        error: String,
+    },
+}
+
+/// The runtimes behind the bus.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RuntimeKind {
+    Net,
+    Parse,
+    Css,
+    Layout,
+    Image,
+    Script,
+}
+
+impl RuntimeKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            RuntimeKind::Net => "network",
+            RuntimeKind::Parse => "HTML parser",
+            RuntimeKind::Css => "stylesheet",
+            RuntimeKind::Layout => "layout engine",
+            RuntimeKind::Image => "image decoder",
+            RuntimeKind::Script => "script engine",
+        }
+    }
+}
+
+impl CoreEvent {
+    /// Tab and request the event belongs to, if it belongs to a request.
+    pub fn request(&self) -> Option<(TabId, u64)> {
+        match self {
+            CoreEvent::NetworkStart {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::NetworkRedirected {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::NetworkConnection {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::NetworkChunk {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::NetworkDone {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::NetworkError {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::DomPatchUpdate {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::ScriptParsed {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::ResourceHint {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::ResourceTruncated {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::CssImport {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::CssDecodedBlock {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::CssSheetDone {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::LayoutReady {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::ImageDecoded {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::ScriptDone {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::FilesPicked {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::Metrics {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::RuntimeCrashed {
+                tab_id, request_id, ..
+            } => Some((*tab_id, *request_id)),
+            CoreEvent::TimeoutFired { .. } => None,
+        }
+    }
+
+    /// Mutable access to the tab and request the event belongs to.
+    pub(crate) fn request_mut(&mut self) -> Option<(&mut TabId, &mut u64)> {
+        match self {
+            CoreEvent::NetworkStart {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::NetworkRedirected {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::NetworkConnection {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::NetworkChunk {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::NetworkDone {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::NetworkError {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::DomPatchUpdate {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::ScriptParsed {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::ResourceHint {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::ResourceTruncated {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::CssImport {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::CssDecodedBlock {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::CssSheetDone {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::LayoutReady {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::ImageDecoded {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::ScriptDone {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::FilesPicked {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::Metrics {
+                tab_id, request_id, ..
+            }
+            | CoreEvent::RuntimeCrashed {
+                tab_id, request_id, ..
+            } => Some((tab_id, request_id)),
+            CoreEvent::TimeoutFired { .. } => None,
+        }
+    }
+}
+
+impl CoreCommand {
+    /// Tab and request the command belongs to, if it belongs to a request.
+    pub fn request(&self) -> Option<(TabId, u64)> {
+        match self {
+            CoreCommand::FetchStream {
+                tab_id, request_id, ..
+            }
+            | CoreCommand::CancelRequest { tab_id, request_id }
+            | CoreCommand::CancelGeneration { tab_id, request_id }
+            | CoreCommand::ParseHtmlStart {
+                tab_id, request_id, ..
+            }
+            | CoreCommand::ParseHtmlChunk {
+                tab_id, request_id, ..
+            }
+            | CoreCommand::ParseHtmlDone { tab_id, request_id }
+            | CoreCommand::ParseHtmlResume { tab_id, request_id }
+            | CoreCommand::DomUpdateApplied {
+                tab_id, request_id, ..
+            }
+            | CoreCommand::CssChunk {
+                tab_id, request_id, ..
+            }
+            | CoreCommand::CssDone {
+                tab_id, request_id, ..
+            }
+            | CoreCommand::CssAbort {
+                tab_id, request_id, ..
+            }
+            | CoreCommand::LayoutDocument {
+                tab_id, request_id, ..
+            }
+            | CoreCommand::DecodeImage {
+                tab_id, request_id, ..
+            }
+            | CoreCommand::RunScript {
+                tab_id, request_id, ..
+            }
+            | CoreCommand::PickFiles {
+                tab_id, request_id, ..
+            } => Some((*tab_id, *request_id)),
+            CoreCommand::SetTimeout { .. }
+            | CoreCommand::ClearTimeout { .. }
+            | CoreCommand::TabClosed { .. }
+            | CoreCommand::Shutdown { .. } => None,
+        }
+    }
 }
 
 pub struct Bus {
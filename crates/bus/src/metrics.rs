@@ -0,0 +1,72 @@
+//! Load counters the runtimes report per navigation.
+//!
+//! Each runtime samples what it measured for a request and sends it with
+//! `CoreEvent::Metrics`; the UI folds the samples of a navigation together
+//! with [`RuntimeMetrics::merge`].
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Counters for one request; fields a runtime does not measure stay zero.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuntimeMetrics {
+    /// Response body bytes received.
+    pub bytes_fetched: u64,
+    /// Tokens the HTML tokenizer emitted.
+    pub tokens_emitted: u64,
+    /// Time the parse runtime spent parsing the document.
+    pub parse_time: Duration,
+    /// Author and user stylesheet rules the cascade applied.
+    pub css_rules_applied: u64,
+    /// Nodes the parser created.
+    pub dom_nodes: u64,
+}
+
+impl RuntimeMetrics {
+    /// Fold `other` into `self`. Counters add up; sizes, which later samples
+    /// restate, keep the largest value seen.
+    pub fn merge(&mut self, other: &RuntimeMetrics) {
+        self.bytes_fetched = self.bytes_fetched.saturating_add(other.bytes_fetched);
+        self.tokens_emitted = self.tokens_emitted.saturating_add(other.tokens_emitted);
+        self.parse_time = self.parse_time.saturating_add(other.parse_time);
+        self.css_rules_applied = self.css_rules_applied.max(other.css_rules_applied);
+        self.dom_nodes = self.dom_nodes.max(other.dom_nodes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RuntimeMetrics;
+    use std::time::Duration;
+
+    #[test]
+    fn merge_adds_counters_and_keeps_the_largest_sizes() {
+        let mut total = RuntimeMetrics {
+            bytes_fetched: 100,
+            css_rules_applied: 4,
+            ..RuntimeMetrics::default()
+        };
+        total.merge(&RuntimeMetrics {
+            bytes_fetched: 50,
+            tokens_emitted: 7,
+            parse_time: Duration::from_millis(3),
+            dom_nodes: 12,
+            ..RuntimeMetrics::default()
+        });
+        total.merge(&RuntimeMetrics {
+            css_rules_applied: 2,
+            ..RuntimeMetrics::default()
+        });
+
+        assert_eq!(
+            total,
+            RuntimeMetrics {
+                bytes_fetched: 150,
+                tokens_emitted: 7,
+                parse_time: Duration::from_millis(3),
+                css_rules_applied: 4,
+                dom_nodes: 12,
+            }
+        );
+    }
+}
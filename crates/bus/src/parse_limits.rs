@@ -0,0 +1,47 @@
+//! Bounds on the work one document parse may do.
+
+use serde::{Deserialize, Serialize};
+
+/// Limits the parse runtime applies to one document, sent with
+/// `ParseHtmlStart`.
+///
+/// A document that runs into one is parsed up to that point, finished as if
+/// its input had ended there, and reported with `ResourceTruncated`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParseLimits {
+    /// Document bytes the parser reads; the rest of the response is dropped.
+    pub max_bytes: usize,
+    /// DOM nodes the tree builder creates.
+    pub max_nodes: usize,
+    /// Depth of nested open elements.
+    pub max_depth: usize,
+}
+
+impl Default for ParseLimits {
+    fn default() -> Self {
+        let tree_builder = html::HtmlTreeBuilderLimits::default();
+        Self {
+            max_bytes: 10 * 1024 * 1024,
+            max_nodes: tree_builder.max_nodes_created,
+            max_depth: tree_builder.max_open_elements_depth,
+        }
+    }
+}
+
+/// The bound of [`ParseLimits`] a document ran into.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ParseLimit {
+    Bytes,
+    Nodes,
+    Depth,
+}
+
+impl ParseLimit {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ParseLimit::Bytes => "bytes",
+            ParseLimit::Nodes => "nodes",
+            ParseLimit::Depth => "depth",
+        }
+    }
+}
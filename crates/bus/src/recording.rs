@@ -0,0 +1,418 @@
+//! Recordings of the events a tab receives for one navigation.
+//!
+//! An [`EventRecorder`] writes the runtime events of a navigation to a text
+//! file as they arrive; [`Recording`] reads such a file back so the events
+//! can be fed into a tab again without the network and parse runtimes.
+//! Replaying the same recording applies the same chunks and patches in the
+//! same order, which turns streaming bugs that depend on where chunk
+//! boundaries fell into deterministic tests.
+//!
+//! The file is line-based: a header, the navigation URL as a JSON string,
+//! then one event per line in the bus's serde form, as JSON. `LayoutReady`
+//! is not recorded, since the tab lays the document out again on replay,
+//! and neither is `FilesPicked`, which carries a user's choice rather than
+//! a runtime result. `TimeoutFired` belongs to no navigation.
+
+use crate::CoreEvent;
+use core_types::{RequestId, TabId};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+const HEADER: &str = "borrowser-events 2";
+
+/// Writes one file per navigation into a directory.
+pub struct EventRecorder {
+    dir: PathBuf,
+    out: Option<BufWriter<File>>,
+}
+
+impl EventRecorder {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            out: None,
+        }
+    }
+
+    /// Finish the current recording and start one for the navigation of
+    /// `tab_id` to `url`. Returns the path of the new file.
+    pub fn start(
+        &mut self,
+        tab_id: TabId,
+        request_id: RequestId,
+        url: &str,
+    ) -> io::Result<PathBuf> {
+        self.out = None;
+        fs::create_dir_all(&self.dir)?;
+        let path = self.dir.join(format!("tab{tab_id}-nav{request_id}.events"));
+        let mut out = BufWriter::new(File::create(&path)?);
+        writeln!(out, "{HEADER}")?;
+        writeln!(
+            out,
+            "{}",
+            serde_json::to_string(url).map_err(io::Error::other)?
+        )?;
+        out.flush()?;
+        self.out = Some(out);
+        Ok(path)
+    }
+
+    /// Append `evt` to the current recording. Events that are not recorded
+    /// and events arriving before [`EventRecorder::start`] are skipped.
+    pub fn record(&mut self, evt: &CoreEvent) -> io::Result<()> {
+        let Some(out) = self.out.as_mut() else {
+            return Ok(());
+        };
+        if !is_recorded(evt) {
+            return Ok(());
+        }
+        let line = serde_json::to_string(evt).map_err(io::Error::other)?;
+        writeln!(out, "{line}")?;
+        // Flush per event so a crash keeps everything that led up to it.
+        out.flush()
+    }
+
+    /// Finish the current recording.
+    pub fn stop(&mut self) {
+        self.out = None;
+    }
+}
+
+/// A navigation read back from an [`EventRecorder`] file.
+#[derive(Debug)]
+pub struct Recording {
+    pub url: String,
+    events: Vec<CoreEvent>,
+}
+
+impl Recording {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RecordingError> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn parse(text: &str) -> Result<Self, RecordingError> {
+        let mut lines = text.lines().enumerate().map(|(idx, line)| (idx + 1, line));
+        match lines.next() {
+            Some((_, HEADER)) => {}
+            _ => return Err(format_error(1, "not an event recording")),
+        }
+        let url = match lines.next() {
+            Some((number, line)) => {
+                serde_json::from_str(line).map_err(|err| format_error(number, err.to_string()))?
+            }
+            None => return Err(format_error(2, "missing url")),
+        };
+
+        let events = lines
+            .map(|(number, line)| {
+                serde_json::from_str(line).map_err(|err| format_error(number, err.to_string()))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { url, events })
+    }
+
+    /// Number of recorded events.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// The recorded events, addressed to `request_id` of `tab_id` instead
+    /// of the tab and navigation they were recorded for.
+    pub fn into_events(
+        self,
+        tab_id: TabId,
+        request_id: RequestId,
+    ) -> impl Iterator<Item = CoreEvent> {
+        self.events.into_iter().map(move |mut evt| {
+            if let Some((tab, request)) = evt.request_mut() {
+                *tab = tab_id;
+                *request = request_id;
+            }
+            evt
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum RecordingError {
+    Io(io::Error),
+    /// Line `line` (1-based) could not be read.
+    Format {
+        line: usize,
+        message: String,
+    },
+}
+
+impl fmt::Display for RecordingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => err.fmt(f),
+            Self::Format { line, message } => write!(f, "line {line}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for RecordingError {}
+
+impl From<io::Error> for RecordingError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+fn format_error(line: usize, message: impl Into<String>) -> RecordingError {
+    RecordingError::Format {
+        line,
+        message: message.into(),
+    }
+}
+
+fn is_recorded(evt: &CoreEvent) -> bool {
+    evt.request().is_some()
+        && !matches!(
+            evt,
+            CoreEvent::LayoutReady { .. }
+                | CoreEvent::FilesPicked { .. }
+                | CoreEvent::TimeoutFired { .. }
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EventRecorder, Recording, RecordingError};
+    use crate::{AnimationFrame, CoreEvent, DecodedImage, ParseLimit, RuntimeKind, RuntimeMetrics};
+    use core_types::{DomHandle, DomVersion, NetworkResponseInfo, ResourceKind, StylesheetSlotId};
+    use html::internal::{expanded_name, html_name, qualified_attribute};
+    use html::{AttributeNamespace, DomPatch, ElementNamespace, PatchKey, SourceSpan};
+    use std::time::Duration;
+
+    fn response(url: &str) -> NetworkResponseInfo {
+        NetworkResponseInfo {
+            requested_url: url.to_string(),
+            final_url: url.to_string(),
+            status_code: Some(200),
+            content_type: None,
+            content_length: Some(12),
+            headers: vec![
+                ("content-type".to_string(), "text/html".to_string()),
+                ("refresh".to_string(), "0; url=/next\tpage".to_string()),
+            ],
+        }
+    }
+
+    fn sample_events() -> Vec<CoreEvent> {
+        let url = "https://example.com/a\tb";
+        vec![
+            CoreEvent::NetworkStart {
+                tab_id: 3,
+                request_id: 9,
+                stylesheet_slot_id: None,
+                kind: ResourceKind::Html,
+                response: response(url),
+            },
+            CoreEvent::NetworkRedirected {
+                tab_id: 3,
+                request_id: 9,
+                stylesheet_slot_id: None,
+                kind: ResourceKind::Html,
+                from: "https://example.com/old".to_string(),
+                to: url.to_string(),
+            },
+            CoreEvent::NetworkConnection {
+                tab_id: 3,
+                request_id: 9,
+                stylesheet_slot_id: None,
+                kind: ResourceKind::Html,
+                origin: "https://example.com".to_string(),
+                reused: false,
+            },
+            CoreEvent::NetworkChunk {
+                tab_id: 3,
+                request_id: 9,
+                stylesheet_slot_id: None,
+                kind: ResourceKind::Html,
+                url: url.to_string(),
+                bytes: vec![0, 0x3c, 0xff, b'\n'],
+            },
+            CoreEvent::DomPatchUpdate {
+                tab_id: 3,
+                request_id: 9,
+                handle: DomHandle(4),
+                from: DomVersion::INITIAL,
+                to: DomVersion(1),
+                patches: vec![
+                    DomPatch::Clear,
+                    DomPatch::CreateDocument {
+                        key: PatchKey(1),
+                        doctype: Some("\\N".to_string()),
+                    },
+                    DomPatch::CreateElement {
+                        key: PatchKey(2),
+                        name: expanded_name(ElementNamespace::Svg, "svg"),
+                        attributes: vec![
+                            qualified_attribute(AttributeNamespace::None, "viewBox", "0 0 1 1"),
+                            qualified_attribute(AttributeNamespace::Xmlns, "xmlns", "x"),
+                            qualified_attribute(AttributeNamespace::XLink, "href", "#a"),
+                        ],
+                    },
+                    DomPatch::CreateText {
+                        key: PatchKey(3),
+                        text: "line\none\\".to_string(),
+                    },
+                    DomPatch::CreateElement {
+                        key: PatchKey(4),
+                        name: html_name("p"),
+                        attributes: Vec::new(),
+                    },
+                    DomPatch::AppendChild {
+                        parent: PatchKey(1),
+                        child: PatchKey(2),
+                    },
+                    DomPatch::SetAttribute {
+                        key: PatchKey(2),
+                        attribute: qualified_attribute(AttributeNamespace::Xml, "lang", "\t"),
+                    },
+                    DomPatch::RemoveAttribute {
+                        key: PatchKey(2),
+                        name: qualified_attribute(AttributeNamespace::XLink, "href", "")
+                            .name()
+                            .clone(),
+                    },
+                    DomPatch::SetSourceSpan {
+                        key: PatchKey(2),
+                        span: SourceSpan { start: 5, end: 17 },
+                    },
+                ],
+            },
+            CoreEvent::ResourceHint {
+                tab_id: 3,
+                request_id: 9,
+                url: "s.css?a\tb".to_string(),
+                kind: ResourceKind::Css,
+            },
+            CoreEvent::ResourceTruncated {
+                tab_id: 3,
+                request_id: 9,
+                limit: ParseLimit::Depth,
+            },
+            CoreEvent::CssImport {
+                tab_id: 3,
+                request_id: 9,
+                stylesheet_slot_id: StylesheetSlotId(1),
+                href: "base.css".to_string(),
+                media: "screen and (min-width: 600px)".to_string(),
+            },
+            CoreEvent::CssDecodedBlock {
+                tab_id: 3,
+                request_id: 9,
+                stylesheet_slot_id: StylesheetSlotId(1),
+                url: "https://example.com/s.css".to_string(),
+                css_block: "p {\n  color: red;\n}".to_string(),
+            },
+            CoreEvent::ScriptDone {
+                tab_id: 3,
+                request_id: 9,
+                result: Err("boom".to_string()),
+            },
+            CoreEvent::Metrics {
+                tab_id: 3,
+                request_id: 9,
+                which: RuntimeKind::Parse,
+                metrics: RuntimeMetrics {
+                    tokens_emitted: 5,
+                    parse_time: Duration::from_nanos(1234),
+                    dom_nodes: 4,
+                    ..RuntimeMetrics::default()
+                },
+            },
+        ]
+    }
+
+    #[test]
+    fn recorded_events_read_back_unchanged() {
+        let dir = std::env::temp_dir().join(format!("borrowser-recording-{}", std::process::id()));
+        let mut recorder = EventRecorder::new(&dir);
+        let path = recorder
+            .start(3, 9, "https://example.com/a\tb")
+            .expect("start recording");
+        for evt in sample_events() {
+            recorder.record(&evt).expect("record event");
+        }
+        recorder.stop();
+
+        let recording = Recording::load(&path).expect("read recording");
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(recording.url, "https://example.com/a\tb");
+        let expected: Vec<String> = sample_events()
+            .iter()
+            .map(|evt| format!("{evt:?}"))
+            .collect();
+        let replayed: Vec<String> = recording
+            .into_events(3, 9)
+            .map(|evt| format!("{evt:?}"))
+            .collect();
+        assert_eq!(replayed, expected);
+    }
+
+    #[test]
+    fn animated_images_read_back_with_every_frame() {
+        let mut image = DecodedImage::still(vec![1, 2, 3, 4], [1, 1]);
+        image.delay = Duration::from_millis(100);
+        image.frames.push(AnimationFrame {
+            rgba: vec![5, 6, 7, 8],
+            delay: Duration::from_millis(40),
+        });
+        let evt = CoreEvent::ImageDecoded {
+            tab_id: 3,
+            request_id: 9,
+            kind: ResourceKind::Image,
+            url: "https://example.com/a.gif".to_string(),
+            image: Ok(image),
+        };
+        let mut text = String::from("borrowser-events 2\n\"https://example.com/\"\n");
+        text.push_str(&serde_json::to_string(&evt).expect("decoded images serialize"));
+
+        let recording = Recording::parse(&text).expect("parse recording");
+        let mut events = recording.into_events(3, 9);
+        let Some(CoreEvent::ImageDecoded {
+            image: Ok(image), ..
+        }) = events.next()
+        else {
+            panic!("expected the decoded image");
+        };
+        assert_eq!(image.rgba, [1, 2, 3, 4]);
+        assert_eq!(image.delay, Duration::from_millis(100));
+        assert_eq!(image.frames.len(), 1);
+        assert_eq!(image.frames[0].rgba, [5, 6, 7, 8]);
+        assert_eq!(image.frames[0].delay, Duration::from_millis(40));
+    }
+
+    #[test]
+    fn replayed_events_are_addressed_to_the_replaying_tab() {
+        let text = concat!(
+            "borrowser-events 2\n\"https://example.com/\"\n",
+            r#"{"ScriptParsed":{"tab_id":3,"request_id":9,"source":"x()"}}"#,
+        );
+        let recording = Recording::parse(text).expect("parse recording");
+        let events: Vec<_> = recording
+            .into_events(1, 2)
+            .map(|evt| evt.request())
+            .collect();
+        assert_eq!(events, vec![Some((1, 2))]);
+    }
+
+    #[test]
+    fn malformed_lines_report_their_line_number() {
+        let text = "borrowser-events 2\n\"x\"\n{\"NetworkChunk\":{\"tab_id\":1}}\n";
+        match Recording::parse(text) {
+            Err(RecordingError::Format { line, .. }) => assert_eq!(line, 3),
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+}
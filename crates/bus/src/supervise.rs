@@ -0,0 +1,144 @@
+//! Panic recovery for runtime command loops.
+
+use crate::{CoreCommand, CoreEvent, RuntimeKind};
+use std::any::Any;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::mpsc::{Receiver, Sender};
+
+/// Handle commands from `cmd_rx` with `handle` until the channel disconnects.
+///
+/// A panic inside `handle` does not end the loop: the state is rebuilt with
+/// `init`, as if the runtime had been started again, and a
+/// `CoreEvent::RuntimeCrashed` is sent for the request whose command
+/// panicked. Work the dropped state held for other requests is lost.
+///
+/// `CoreCommand::Shutdown` ends the loop after the commands queued before it;
+/// the state is dropped before the shutdown is acknowledged.
+pub fn run_supervised<S>(
+    which: RuntimeKind,
+    cmd_rx: &Receiver<CoreCommand>,
+    evt_tx: &Sender<CoreEvent>,
+    mut init: impl FnMut() -> S,
+    mut handle: impl FnMut(&mut S, CoreCommand),
+) {
+    let mut state = init();
+    while let Ok(cmd) = cmd_rx.recv() {
+        if let CoreCommand::Shutdown { ack } = cmd {
+            drop(state);
+            let _ = ack.into_inner().send(());
+            return;
+        }
+        let request = cmd.request();
+        let result = catch_unwind(AssertUnwindSafe(|| handle(&mut state, cmd)));
+        let Err(payload) = result else {
+            continue;
+        };
+        state = init();
+        if let Some((tab_id, request_id)) = request {
+            let _ = evt_tx.send(CoreEvent::RuntimeCrashed {
+                tab_id,
+                request_id,
+                which,
+                error: panic_message(payload.as_ref()),
+            });
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run_supervised;
+    use crate::{CoreCommand, CoreEvent, InProcess, RuntimeKind};
+    use std::sync::mpsc;
+
+    fn parse_chunk(request_id: u64, bytes: &[u8]) -> CoreCommand {
+        CoreCommand::ParseHtmlChunk {
+            tab_id: 3,
+            request_id,
+            bytes: bytes.to_vec(),
+        }
+    }
+
+    #[test]
+    fn panicking_commands_report_a_crash_and_restart_with_fresh_state() {
+        let (cmd_tx, cmd_rx) = mpsc::channel();
+        let (evt_tx, evt_rx) = mpsc::channel();
+        let mut seen = Vec::new();
+
+        cmd_tx.send(parse_chunk(1, b"a")).unwrap();
+        cmd_tx.send(parse_chunk(2, b"boom")).unwrap();
+        cmd_tx.send(parse_chunk(3, b"b")).unwrap();
+        drop(cmd_tx);
+
+        run_supervised(
+            RuntimeKind::Parse,
+            &cmd_rx,
+            &evt_tx,
+            Vec::new,
+            |handled: &mut Vec<u64>, cmd| {
+                let CoreCommand::ParseHtmlChunk {
+                    request_id, bytes, ..
+                } = cmd
+                else {
+                    return;
+                };
+                handled.push(request_id);
+                assert_ne!(bytes, b"boom", "bad chunk");
+                seen.push(handled.clone());
+            },
+        );
+
+        assert_eq!(seen, [vec![1], vec![3]]);
+        let events: Vec<_> = evt_rx.try_iter().collect();
+        assert!(matches!(
+            events.as_slice(),
+            [CoreEvent::RuntimeCrashed {
+                tab_id: 3,
+                request_id: 2,
+                which: RuntimeKind::Parse,
+                error,
+            }] if error.contains("bad chunk")
+        ));
+    }
+
+    #[test]
+    fn shutdown_acknowledges_after_the_commands_queued_before_it() {
+        let (cmd_tx, cmd_rx) = mpsc::channel();
+        let (evt_tx, _evt_rx) = mpsc::channel();
+        let (ack_tx, ack_rx) = mpsc::channel();
+        let mut seen = Vec::new();
+
+        cmd_tx.send(parse_chunk(1, b"a")).unwrap();
+        cmd_tx
+            .send(CoreCommand::Shutdown {
+                ack: InProcess::new(ack_tx),
+            })
+            .unwrap();
+        cmd_tx.send(parse_chunk(2, b"b")).unwrap();
+
+        run_supervised(
+            RuntimeKind::Parse,
+            &cmd_rx,
+            &evt_tx,
+            || (),
+            |_, cmd| {
+                if let CoreCommand::ParseHtmlChunk { request_id, .. } = cmd {
+                    seen.push(request_id);
+                }
+            },
+        );
+
+        assert_eq!(seen, [1]);
+        assert_eq!(ack_rx.try_recv(), Ok(()));
+    }
+}
@@ -0,0 +1,433 @@
+use super::{CoreCommand, CoreEvent, DecodedImage, InProcess, LayoutJob, RuntimeKind};
+use crate::{ParseLimit, ParseLimits, RuntimeMetrics};
+use core_types::{
+    DomHandle, DomVersion, HttpMethod, NetError, NetworkResponseInfo, RequestPayload, ResourceKind,
+    StylesheetSlotId,
+};
+use css::{ComputedStyle, MediaEnvironment, SelectorElementStates};
+use html::internal::Id;
+use html::{DomPatch, Node, PatchKey};
+use layout::{RetainedLayoutKey, RetainedLayoutKeySeed, TextMeasurer};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::time::Duration;
+
+struct FixedMeasurer;
+
+impl TextMeasurer for FixedMeasurer {
+    fn measure(&self, text: &str, _style: &ComputedStyle) -> f32 {
+        text.chars().count() as f32 * 8.0
+    }
+
+    fn line_height(&self, _style: &ComputedStyle) -> f32 {
+        16.0
+    }
+}
+
+fn key() -> RetainedLayoutKey {
+    RetainedLayoutKeySeed {
+        identity_domain: 1,
+        layout_input_generation: 2,
+        layout_style_generation: 3,
+        text_measurement_generation: 4,
+        replaced_metadata_generation: 5,
+    }
+    .for_viewport_width(400.0)
+}
+
+fn document() -> Node {
+    Node::Document {
+        id: Id(1),
+        doctype: Some("html".to_string()),
+        children: vec![Node::Text {
+            id: Id(2),
+            text: "Hello".to_string(),
+        }],
+    }
+}
+
+fn response() -> NetworkResponseInfo {
+    NetworkResponseInfo {
+        requested_url: "http://example.test/".to_string(),
+        final_url: "http://example.test/index.html".to_string(),
+        status_code: Some(200),
+        content_type: Some("text/html".to_string()),
+        content_length: Some(12),
+        headers: Vec::new(),
+    }
+}
+
+/// One command of every kind; `command_kind` makes sure none is missing.
+fn commands() -> Vec<CoreCommand> {
+    let url = || "http://example.test/a.css".to_string();
+    vec![
+        CoreCommand::FetchStream {
+            tab_id: 1,
+            request_id: 2,
+            stylesheet_slot_id: None,
+            url: "http://example.test/submit".to_string(),
+            kind: ResourceKind::Html,
+            payload: RequestPayload {
+                method: HttpMethod::Post,
+                headers: vec![("Content-Type".to_string(), "text/plain".to_string())],
+                body: b"a=1".to_vec(),
+            },
+        },
+        CoreCommand::CancelRequest {
+            tab_id: 1,
+            request_id: 2,
+        },
+        CoreCommand::CancelGeneration {
+            tab_id: 1,
+            request_id: 2,
+        },
+        CoreCommand::ParseHtmlStart {
+            tab_id: 1,
+            request_id: 2,
+            content_type: Some("text/html; charset=windows-1252".to_string()),
+            limits: ParseLimits::default(),
+        },
+        CoreCommand::ParseHtmlChunk {
+            tab_id: 1,
+            request_id: 2,
+            bytes: b"<p>hi".to_vec(),
+        },
+        CoreCommand::ParseHtmlDone {
+            tab_id: 1,
+            request_id: 2,
+        },
+        CoreCommand::ParseHtmlResume {
+            tab_id: 1,
+            request_id: 2,
+        },
+        CoreCommand::DomUpdateApplied {
+            tab_id: 1,
+            request_id: 2,
+            version: DomVersion(4),
+        },
+        CoreCommand::CssChunk {
+            tab_id: 1,
+            request_id: 2,
+            stylesheet_slot_id: StylesheetSlotId(3),
+            url: url(),
+            bytes: b"p {".to_vec(),
+        },
+        CoreCommand::CssDone {
+            tab_id: 1,
+            request_id: 2,
+            stylesheet_slot_id: StylesheetSlotId(3),
+            url: url(),
+        },
+        CoreCommand::CssAbort {
+            tab_id: 1,
+            request_id: 2,
+            stylesheet_slot_id: StylesheetSlotId(3),
+            url: url(),
+        },
+        CoreCommand::LayoutDocument {
+            tab_id: 1,
+            request_id: 2,
+            job: InProcess::new(Box::new(LayoutJob {
+                dom: Box::new(document()),
+                stylesheets: Vec::new(),
+                element_states: SelectorElementStates::default(),
+                viewport_width: 400.0,
+                media: MediaEnvironment::default(),
+                zoom: 1.5,
+                key: key(),
+                image_sources: HashMap::new(),
+                image_sizes: HashMap::new(),
+                resized_controls: HashMap::new(),
+                measurer: Box::new(FixedMeasurer),
+            })),
+        },
+        CoreCommand::DecodeImage {
+            tab_id: 1,
+            request_id: 2,
+            kind: ResourceKind::Image,
+            url: "http://example.test/a.png".to_string(),
+            bytes: b"\x89PNG".as_slice().into(),
+        },
+        CoreCommand::RunScript {
+            tab_id: 1,
+            request_id: 2,
+            source: "let x = 1;".to_string(),
+        },
+        CoreCommand::PickFiles {
+            tab_id: 1,
+            request_id: 2,
+            input_id: Id(7),
+            multiple: true,
+        },
+        CoreCommand::SetTimeout {
+            tab_id: 1,
+            token: 9,
+            delay: Duration::from_millis(250),
+        },
+        CoreCommand::ClearTimeout {
+            tab_id: 1,
+            token: 9,
+        },
+        CoreCommand::TabClosed { tab_id: 1 },
+        CoreCommand::Shutdown {
+            ack: InProcess::new(mpsc::channel().0),
+        },
+    ]
+}
+
+fn command_kind(cmd: &CoreCommand) -> usize {
+    match cmd {
+        CoreCommand::FetchStream { .. } => 0,
+        CoreCommand::CancelRequest { .. } => 1,
+        CoreCommand::CancelGeneration { .. } => 2,
+        CoreCommand::ParseHtmlStart { .. } => 3,
+        CoreCommand::ParseHtmlChunk { .. } => 4,
+        CoreCommand::ParseHtmlDone { .. } => 5,
+        CoreCommand::ParseHtmlResume { .. } => 6,
+        CoreCommand::DomUpdateApplied { .. } => 7,
+        CoreCommand::CssChunk { .. } => 8,
+        CoreCommand::CssDone { .. } => 9,
+        CoreCommand::CssAbort { .. } => 10,
+        CoreCommand::LayoutDocument { .. } => 11,
+        CoreCommand::DecodeImage { .. } => 12,
+        CoreCommand::RunScript { .. } => 13,
+        CoreCommand::PickFiles { .. } => 14,
+        CoreCommand::SetTimeout { .. } => 15,
+        CoreCommand::ClearTimeout { .. } => 16,
+        CoreCommand::TabClosed { .. } => 17,
+        CoreCommand::Shutdown { .. } => 18,
+    }
+}
+
+/// One event of every kind; `event_kind` makes sure none is missing.
+fn events() -> Vec<CoreEvent> {
+    let url = || "http://example.test/a.css".to_string();
+    vec![
+        CoreEvent::NetworkStart {
+            tab_id: 1,
+            request_id: 2,
+            stylesheet_slot_id: None,
+            kind: ResourceKind::Html,
+            response: response(),
+        },
+        CoreEvent::NetworkChunk {
+            tab_id: 1,
+            request_id: 2,
+            stylesheet_slot_id: Some(StylesheetSlotId(3)),
+            kind: ResourceKind::Css,
+            url: url(),
+            bytes: b"p {".to_vec(),
+        },
+        CoreEvent::NetworkDone {
+            tab_id: 1,
+            request_id: 2,
+            stylesheet_slot_id: None,
+            kind: ResourceKind::Html,
+            response: response(),
+            bytes_received: 12,
+        },
+        CoreEvent::NetworkError {
+            tab_id: 1,
+            request_id: 2,
+            stylesheet_slot_id: None,
+            kind: ResourceKind::Image,
+            url: url(),
+            error: NetError::HttpStatus(404),
+            message: "not found".to_string(),
+        },
+        CoreEvent::DomPatchUpdate {
+            tab_id: 1,
+            request_id: 2,
+            handle: DomHandle(5),
+            from: DomVersion(0),
+            to: DomVersion(1),
+            patches: vec![
+                DomPatch::Clear,
+                DomPatch::CreateDocument {
+                    key: PatchKey(1),
+                    doctype: Some("html".to_string()),
+                },
+            ],
+        },
+        CoreEvent::ScriptParsed {
+            tab_id: 1,
+            request_id: 2,
+            source: "let x = 1;".to_string(),
+        },
+        CoreEvent::CssDecodedBlock {
+            tab_id: 1,
+            request_id: 2,
+            stylesheet_slot_id: StylesheetSlotId(3),
+            url: url(),
+            css_block: "p { color: red }".to_string(),
+        },
+        CoreEvent::CssSheetDone {
+            tab_id: 1,
+            request_id: 2,
+            stylesheet_slot_id: StylesheetSlotId(3),
+            url: url(),
+        },
+        CoreEvent::LayoutReady {
+            tab_id: 1,
+            request_id: 2,
+            key: key(),
+            layout: Err("style failed".to_string()),
+        },
+        CoreEvent::ImageDecoded {
+            tab_id: 1,
+            request_id: 2,
+            kind: ResourceKind::Image,
+            url: "http://example.test/a.png".to_string(),
+            image: Ok(DecodedImage::still(vec![255; 8], [2, 1])),
+        },
+        CoreEvent::ScriptDone {
+            tab_id: 1,
+            request_id: 2,
+            result: Err("ReferenceError".to_string()),
+        },
+        CoreEvent::FilesPicked {
+            tab_id: 1,
+            request_id: 2,
+            input_id: Id(7),
+            paths: vec![PathBuf::from("/tmp/a.txt")],
+        },
+        CoreEvent::TimeoutFired {
+            tab_id: 1,
+            token: 9,
+        },
+        CoreEvent::Metrics {
+            tab_id: 1,
+            request_id: 2,
+            which: RuntimeKind::Parse,
+            metrics: RuntimeMetrics {
+                bytes_fetched: 10,
+                tokens_emitted: 11,
+                parse_time: Duration::from_micros(12),
+                css_rules_applied: 13,
+                dom_nodes: 14,
+            },
+        },
+        CoreEvent::RuntimeCrashed {
+            tab_id: 1,
+            request_id: 2,
+            which: RuntimeKind::Layout,
+            error: "panicked".to_string(),
+        },
+        CoreEvent::NetworkRedirected {
+            tab_id: 1,
+            request_id: 2,
+            stylesheet_slot_id: None,
+            kind: ResourceKind::Html,
+            from: "http://example.test/".to_string(),
+            to: "https://example.test/".to_string(),
+        },
+        CoreEvent::NetworkConnection {
+            tab_id: 1,
+            request_id: 2,
+            stylesheet_slot_id: None,
+            kind: ResourceKind::Css,
+            origin: "https://example.test".to_string(),
+            reused: true,
+        },
+        CoreEvent::ResourceHint {
+            tab_id: 1,
+            request_id: 2,
+            url: "a.png".to_string(),
+            kind: ResourceKind::Image,
+        },
+        CoreEvent::ResourceTruncated {
+            tab_id: 1,
+            request_id: 2,
+            limit: ParseLimit::Bytes,
+        },
+        CoreEvent::CssImport {
+            tab_id: 1,
+            request_id: 2,
+            stylesheet_slot_id: StylesheetSlotId(3),
+            href: "base.css".to_string(),
+            media: "print".to_string(),
+        },
+    ]
+}
+
+fn event_kind(event: &CoreEvent) -> usize {
+    match event {
+        CoreEvent::NetworkStart { .. } => 0,
+        CoreEvent::NetworkChunk { .. } => 1,
+        CoreEvent::NetworkDone { .. } => 2,
+        CoreEvent::NetworkError { .. } => 3,
+        CoreEvent::DomPatchUpdate { .. } => 4,
+        CoreEvent::ScriptParsed { .. } => 5,
+        CoreEvent::CssDecodedBlock { .. } => 6,
+        CoreEvent::CssSheetDone { .. } => 7,
+        CoreEvent::LayoutReady { .. } => 8,
+        CoreEvent::ImageDecoded { .. } => 9,
+        CoreEvent::ScriptDone { .. } => 10,
+        CoreEvent::FilesPicked { .. } => 11,
+        CoreEvent::TimeoutFired { .. } => 12,
+        CoreEvent::Metrics { .. } => 13,
+        CoreEvent::RuntimeCrashed { .. } => 14,
+        CoreEvent::NetworkRedirected { .. } => 15,
+        CoreEvent::NetworkConnection { .. } => 16,
+        CoreEvent::ResourceHint { .. } => 17,
+        CoreEvent::ResourceTruncated { .. } => 18,
+        CoreEvent::CssImport { .. } => 19,
+    }
+}
+
+/// Send every message through JSON and compare what comes back with the
+/// original; messages with in-process payloads refuse to serialize and keep
+/// their payload.
+fn assert_round_trips<T: Serialize + DeserializeOwned + Debug>(
+    messages: Vec<T>,
+    kind: fn(&T) -> usize,
+    in_process: &[usize],
+) {
+    let kinds: Vec<usize> = messages.iter().map(kind).collect();
+    assert_eq!(kinds, (0..kinds.len()).collect::<Vec<_>>());
+
+    for message in messages {
+        let expected = format!("{message:?}");
+        match serde_json::to_string(&message) {
+            Ok(json) => {
+                assert!(!in_process.contains(&kind(&message)), "{expected}");
+                let back: T = serde_json::from_str(&json).expect("deserialize");
+                assert_eq!(format!("{back:?}"), expected);
+            }
+            Err(_) => {
+                assert!(in_process.contains(&kind(&message)), "{expected}");
+                assert_eq!(format!("{message:?}"), expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn every_command_survives_a_serialization_round_trip() {
+    assert_round_trips(commands(), command_kind, &[11, 18]);
+}
+
+#[test]
+fn every_event_survives_a_serialization_round_trip() {
+    assert_round_trips(events(), event_kind, &[]);
+}
+
+#[test]
+fn an_in_process_payload_stays_with_its_message() {
+    let Some(cmd) = commands()
+        .into_iter()
+        .find(|cmd| matches!(cmd, CoreCommand::LayoutDocument { .. }))
+    else {
+        panic!("the command fixtures include a layout job");
+    };
+    assert!(serde_json::to_string(&cmd).is_err());
+    assert!(serde_json::to_string(&cmd).is_err());
+    let CoreCommand::LayoutDocument { job, .. } = cmd else {
+        unreachable!();
+    };
+    assert_eq!(job.into_inner().viewport_width, 400.0);
+}
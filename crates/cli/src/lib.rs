@@ -0,0 +1,114 @@
+//! Pages through the engine without a window.
+//!
+//! `borrowser-cli` loads a URL or a local file over the runtime bus, the way
+//! a tab in the window would, runs it through style and layout, and dumps
+//! what one phase made of it: the DOM, the computed styles, the layout
+//! fragment tree, or the rendered page.
+
+use bus::{CoreCommand, CoreEvent};
+use core_types::TabId;
+use egui::ColorImage;
+use html::dom_snapshot::{DomSnapshot, DomSnapshotOptions};
+use layout_test_support::fragment_tree_lines;
+use std::fs;
+use std::path::Path;
+use std::sync::mpsc::{Receiver, Sender};
+use std::time::Duration;
+use url::Url;
+
+/// What to dump of a loaded page.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dump {
+    /// The document tree as a DOM snapshot.
+    Dom,
+    /// The computed style of every element.
+    Style,
+    /// The layout fragment tree: block boxes, line boxes, and fragments.
+    Layout,
+    /// The page rendered as the window would show it.
+    Png,
+}
+
+/// A dump: text for the tree dumps, pixels for [`Dump::Png`].
+pub enum Output {
+    Text(String),
+    Image(ColorImage),
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Options {
+    /// Viewport size in pixels; layout only uses the width.
+    pub viewport: [usize; 2],
+    /// How long to wait for the page to load.
+    pub timeout: Duration,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            viewport: [800, 600],
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+/// URL of a page argument: an absolute URL as given, or else a path to a
+/// local file.
+pub fn page_url(arg: &str) -> Result<String, String> {
+    // A single letter is a Windows drive, not a scheme.
+    if let Ok(url) = Url::parse(arg)
+        && url.scheme().len() > 1
+    {
+        return Ok(url.into());
+    }
+    let absolute = fs::canonicalize(Path::new(arg)).map_err(|err| format!("{arg}: {err}"))?;
+    Url::from_file_path(&absolute)
+        .map(Into::into)
+        .map_err(|()| format!("{arg}: not a file path"))
+}
+
+/// Load `url` in a new tab `tab_id` over the runtime bus and dump it.
+pub fn dump_url(
+    url: &str,
+    dump: Dump,
+    tab_id: TabId,
+    cmd_tx: Sender<CoreCommand>,
+    evt_rx: &Receiver<CoreEvent>,
+    options: &Options,
+) -> Result<Output, String> {
+    if dump == Dump::Png {
+        return browser::headless::render_url(
+            url,
+            tab_id,
+            cmd_tx,
+            evt_rx,
+            options.viewport,
+            options.timeout,
+        )
+        .map(Output::Image);
+    }
+
+    let job = browser::headless::load_layout_job(
+        url,
+        tab_id,
+        cmd_tx,
+        evt_rx,
+        options.viewport[0] as f32,
+        options.timeout,
+    )?;
+    let text = match dump {
+        Dump::Dom => DomSnapshot::new(&job.dom, DomSnapshotOptions::default()).to_string() + "\n",
+        Dump::Style => {
+            runtime_layout::style_and_lay_out(&job, |computed, _| computed.to_debug_snapshot())
+                .map_err(|err| err.to_string())?
+        }
+        Dump::Layout => runtime_layout::style_and_lay_out(&job, |_, output| {
+            let mut text = fragment_tree_lines(output.root(), job.measurer.as_ref()).join("\n");
+            text.push('\n');
+            text
+        })
+        .map_err(|err| err.to_string())?,
+        Dump::Png => unreachable!("rendered above"),
+    };
+    Ok(Output::Text(text))
+}
@@ -0,0 +1,78 @@
+use borrowser_cli::{Dump, Options, Output, dump_url, page_url};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: borrowser-cli (--dom | --style | --layout | --png <output.png>)
+                    [--width <px>] [--height <px>] <url or file>";
+
+struct Args {
+    dump: Dump,
+    png: Option<PathBuf>,
+    options: Options,
+    page: String,
+}
+
+fn parse_args(args: &[String]) -> Option<Args> {
+    let mut dump = None;
+    let mut png = None;
+    let mut options = Options::default();
+    let mut page = None;
+    let mut args = args.iter().map(String::as_str);
+    while let Some(arg) = args.next() {
+        match arg {
+            "--dom" => dump = Some(Dump::Dom),
+            "--style" => dump = Some(Dump::Style),
+            "--layout" => dump = Some(Dump::Layout),
+            "--png" => {
+                dump = Some(Dump::Png);
+                png = Some(PathBuf::from(args.next()?));
+            }
+            "--width" => options.viewport[0] = args.next()?.parse().ok()?,
+            "--height" => options.viewport[1] = args.next()?.parse().ok()?,
+            _ if arg.starts_with("--") || page.is_some() => return None,
+            _ => page = Some(arg.to_string()),
+        }
+    }
+    Some(Args {
+        dump: dump?,
+        png,
+        options,
+        page: page?,
+    })
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let Some(args) = parse_args(&args) else {
+        eprintln!("{USAGE}");
+        return ExitCode::FAILURE;
+    };
+    let url = match page_url(&args.page) {
+        Ok(url) => url,
+        Err(err) => {
+            eprintln!("could not open {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let (cmd_tx, evt_rx, runtimes) = platform::start_runtimes();
+    let output = dump_url(&url, args.dump, 1, cmd_tx, &evt_rx, &args.options);
+    runtimes.shutdown(platform::SHUTDOWN_TIMEOUT);
+    let written = output.and_then(|output| match (output, &args.png) {
+        (Output::Text(text), _) => {
+            print!("{text}");
+            Ok(())
+        }
+        (Output::Image(image), Some(path)) => {
+            reftest::runner::write_png(path, &image).map_err(|err| err.to_string())
+        }
+        (Output::Image(_), None) => Err("no output path for the image".to_string()),
+    });
+    match written {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("could not dump {url}: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
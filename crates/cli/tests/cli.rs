@@ -0,0 +1,103 @@
+use borrowser_cli::{Dump, Options, Output, dump_url, page_url};
+use std::fs;
+
+const PAGE: &str = "<!doctype html>
+<html><head><style>
+body { margin: 0; }
+div { display: block; width: 40px; height: 30px; background-color: green; }
+</style></head>
+<body><div id=box>hi</div></body></html>";
+
+#[test]
+fn page_arguments_are_urls_or_local_files() {
+    assert_eq!(
+        page_url("https://example.com/a").as_deref(),
+        Ok("https://example.com/a")
+    );
+
+    let dir = tempfile::tempdir().expect("scratch dir");
+    let path = dir.path().join("page.html");
+    fs::write(&path, PAGE).expect("page");
+    let url = page_url(path.to_str().expect("utf-8 path")).expect("file URL");
+    assert!(
+        url.starts_with("file://") && url.ends_with("/page.html"),
+        "{url}"
+    );
+
+    assert!(page_url(dir.path().join("missing.html").to_str().unwrap()).is_err());
+}
+
+#[test]
+fn every_dump_runs_the_page_through_the_pipeline() {
+    let dir = tempfile::tempdir().expect("scratch dir");
+    let path = dir.path().join("page.html");
+    fs::write(&path, PAGE).expect("page");
+    let url = page_url(path.to_str().expect("utf-8 path")).expect("file URL");
+
+    let (cmd_tx, evt_rx, runtimes) = platform::start_runtimes();
+    let options = Options {
+        viewport: [200, 100],
+        ..Options::default()
+    };
+    let dump = |dump, tab_id| {
+        dump_url(&url, dump, tab_id, cmd_tx.clone(), &evt_rx, &options)
+            .unwrap_or_else(|err| panic!("{dump:?} dump failed: {err}"))
+    };
+    let text = |output| match output {
+        Output::Text(text) => text,
+        Output::Image(_) => panic!("expected a text dump"),
+    };
+
+    let dom = text(dump(Dump::Dom, 1));
+    assert!(dom.starts_with("#dom-snapshot-v2\n"), "{dom}");
+    assert!(
+        dom.contains("local=\"div\" attrs=[{ns=none prefix=- local=\"id\" value=\"box\"}]"),
+        "{dom}"
+    );
+    assert!(dom.contains("\"hi\""), "{dom}");
+
+    let style = text(dump(Dump::Style, 2));
+    assert!(style.contains("computed-document-style"), "{style}");
+    assert!(style.contains("name=\"div\""), "{style}");
+
+    let layout = text(dump(Dump::Layout, 3));
+    assert!(
+        layout.starts_with("document x=0.00 y=0.00 w=200.00"),
+        "{layout}"
+    );
+    assert!(
+        layout.contains("div x=0.00 y=0.00 w=40.00 h=30.00"),
+        "{layout}"
+    );
+
+    let Output::Image(image) = dump(Dump::Png, 4) else {
+        panic!("expected an image");
+    };
+    runtimes.shutdown(platform::SHUTDOWN_TIMEOUT);
+    assert_eq!(image.size, [200, 100]);
+    let green = egui::Color32::from_rgb(0, 128, 0);
+    assert!(
+        image.pixels.contains(&green),
+        "no green box in the rendering"
+    );
+}
+
+#[test]
+fn internal_pages_load_through_the_runtimes() {
+    let (cmd_tx, evt_rx, runtimes) = platform::start_runtimes();
+    let options = Options::default();
+    let dom =
+        |url: &str, tab_id| dump_url(url, Dump::Dom, tab_id, cmd_tx.clone(), &evt_rx, &options);
+
+    let Ok(Output::Text(version)) = dom("about:version", 1) else {
+        panic!("about:version did not load");
+    };
+    let crash = dom("about:crash-test", 2);
+    runtimes.shutdown(platform::SHUTDOWN_TIMEOUT);
+
+    assert!(version.contains("\"Borrowser "), "{version}");
+    match crash {
+        Err(err) => assert!(err.contains("crashed"), "{err}"),
+        Ok(_) => panic!("about:crash-test loaded"),
+    }
+}
@@ -6,12 +6,14 @@ pub type RequestId = u64;
 /// This is deliberately distinct from URL or cache identity: two stylesheet
 /// links with the same URL still occupy two cascade-order slots.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StylesheetSlotId(pub u64);
 
 /// Stable identity for a live document owned by a parse session.
 ///
 /// Handles are created by the owning subsystem; `0` is reserved and must not be used.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DomHandle(pub u64);
 
 /// Monotonic version for a document identified by a `DomHandle`.
@@ -19,6 +21,7 @@ pub struct DomHandle(pub u64);
 /// The initial version is `0`; the first mutation produces `1` and versions
 /// are expected to increment by exactly 1 per patch in the current model.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DomVersion(pub u64);
 
 impl DomVersion {
@@ -45,11 +48,15 @@ pub struct BrowserInput {
     pub enter_pressed: bool,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ResourceKind {
     Html,
     Css,
     Image,
+    /// Page icon (`<link rel=icon>` or `/favicon.ico`); decoded like an image
+    /// but owned by the browser shell rather than the document.
+    Icon,
 }
 
 impl ResourceKind {
@@ -58,23 +65,57 @@ impl ResourceKind {
             Self::Html => "html",
             Self::Css => "css",
             Self::Image => "image",
+            Self::Icon => "icon",
         }
     }
 
     pub const fn role_str(self) -> &'static str {
         match self {
             Self::Html => "top-level",
-            Self::Css | Self::Image => "subresource",
+            Self::Css | Self::Image | Self::Icon => "subresource",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HttpMethod {
+    #[default]
+    Get,
+    Post,
+}
+
+impl HttpMethod {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Get => "GET",
+            Self::Post => "POST",
         }
     }
 }
 
+/// What a request sends besides its URL. The default is a plain GET.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RequestPayload {
+    pub method: HttpMethod,
+    /// Extra request headers, in the order they are sent.
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NetworkResponseInfo {
     pub requested_url: String,
     pub final_url: String,
     pub status_code: Option<u16>,
     pub content_type: Option<String>,
+    /// Body size the response announced, if any.
+    pub content_length: Option<u64>,
+    /// The HTTP response headers with lowercase names, grouped by name in the
+    /// order the names first appeared. Empty for `file:` and `about:` URLs.
+    pub headers: Vec<(String, String)>,
 }
 
 impl NetworkResponseInfo {
@@ -89,14 +130,44 @@ impl NetworkResponseInfo {
     pub fn was_redirected(&self) -> bool {
         self.final_url != self.requested_url
     }
+
+    /// The first value of header `name`, matched case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
 }
 
+/// Why a fetch failed.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum NetworkErrorKind {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NetError {
+    /// The host name didn't resolve.
+    Dns,
+    /// No secure connection could be set up, e.g. because the server's
+    /// certificate isn't trusted.
+    Tls,
+    /// Connecting or waiting for the next bytes took longer than the
+    /// client's timeout.
+    Timeout,
+    /// The server answered with an error status and no body worth showing.
+    HttpStatus(u16),
+    /// The server kept redirecting past the client's redirect limit.
+    TooManyRedirects,
+    /// The connection or the local file failed some other way.
+    Io,
     Cancelled,
-    Transport,
-    HttpStatus,
-    LocalFile,
-    Read,
-    ResourceLimit,
+    /// The body outgrew the byte limit for its resource kind.
+    TooLarge,
+}
+
+impl NetError {
+    pub fn status_code(self) -> Option<u16> {
+        match self {
+            Self::HttpStatus(code) => Some(code),
+            _ => None,
+        }
+    }
 }
@@ -197,6 +197,12 @@ fn supported_property_metadata_matches_current_subset_contract() {
             InitialStyleValue::PositionStatic,
             CascadePropertyLengthSignPolicy::NotLength,
         ),
+        (
+            CascadePropertyId::Resize,
+            CascadeInheritance::NotInherited,
+            InitialStyleValue::ResizeNone,
+            CascadePropertyLengthSignPolicy::NotLength,
+        ),
         (
             CascadePropertyId::TextDecorationLine,
             CascadeInheritance::NotInherited,
@@ -20,7 +20,7 @@ use self::rule_inputs::{
 use super::contract::resolve_cascade_style_from_rule_inputs;
 use super::document::{ResolvedDocumentStyle, ResolvedElementStyle};
 use crate::model;
-use crate::selectors::{SelectorDomIndex, SelectorMatchingContext};
+use crate::selectors::{SelectorDomIndex, SelectorElementStates, SelectorMatchingContext};
 use html::{Node, internal::Id};
 use std::collections::BTreeMap;
 
@@ -58,6 +58,7 @@ pub fn resolve_document_styles_from_cascade_inputs(
     try_resolve_document_styles_from_cascade_inputs_with_limits(
         root,
         sheets,
+        &SelectorElementStates::default(),
         &StyleResolutionLimits::default(),
     )
 }
@@ -96,16 +97,20 @@ pub fn try_resolve_document_styles_with_limits(
     Ok(ResolvedDocumentStyle::new(entries))
 }
 
+/// Like [`resolve_document_styles_from_cascade_inputs`], with explicit limits
+/// and the element states (such as visited links) that state pseudo-classes
+/// match against.
 pub fn try_resolve_document_styles_from_cascade_inputs_with_limits(
     root: &Node,
     sheets: &[StylesheetCascadeInput<'_>],
+    element_states: &SelectorElementStates,
     limits: &StyleResolutionLimits,
 ) -> Result<ResolvedDocumentStyle, StyleResolutionError> {
     validate_representation_limits(limits)?;
     enforce_stylesheet_input_limits(sheets, limits)?;
     count_styled_elements_bounded(root, limits.max_styled_elements_per_document)?;
 
-    let index = SelectorDomIndex::from_root(root);
+    let index = SelectorDomIndex::from_root(root).with_element_states(element_states);
     let context = SelectorMatchingContext::with_limits(&index, limits.selector_matching);
     let mut entries = Vec::with_capacity(index.len());
     let mut styles_by_element = BTreeMap::new();
@@ -146,6 +151,7 @@ pub fn try_resolve_document_styles_incremental_suffix_with_limits(
     try_resolve_document_styles_incremental_suffix_from_cascade_inputs_with_limits(
         root,
         &inputs,
+        &SelectorElementStates::default(),
         previous,
         dirty_node_ids,
         limits,
@@ -155,6 +161,7 @@ pub fn try_resolve_document_styles_incremental_suffix_with_limits(
 pub fn try_resolve_document_styles_incremental_suffix_from_cascade_inputs_with_limits(
     root: &Node,
     sheets: &[StylesheetCascadeInput<'_>],
+    element_states: &SelectorElementStates,
     previous: &ResolvedDocumentStyle,
     dirty_node_ids: &[Id],
     limits: &StyleResolutionLimits,
@@ -167,7 +174,7 @@ pub fn try_resolve_document_styles_incremental_suffix_from_cascade_inputs_with_l
         return Ok(None);
     }
 
-    let index = SelectorDomIndex::from_root(root);
+    let index = SelectorDomIndex::from_root(root).with_element_states(element_states);
     if previous.entries().len() != index.len() {
         return Ok(None);
     }
@@ -326,33 +326,61 @@ fn resolve_document_styles_rejects_invalid_outline_shorthand_atomically() {
 }
 
 #[test]
-fn resolve_document_styles_keeps_border_shorthand_unsupported() {
-    let stylesheets = vec![stylesheet("div { border: 1px solid red; }")];
+fn resolve_document_styles_expands_border_shorthand_to_every_side() {
+    let stylesheets = vec![stylesheet(
+        "div { border: 1px solid red; border-left: none; border-width: 2px 3px; }",
+    )];
     let dom = element("div", Vec::new(), Vec::new());
 
     let resolved = resolve_document_styles(&dom, &stylesheets).expect("resolved document style");
     let style = resolved.entries()[0].style();
+    let winner = |property| {
+        style
+            .get(property)
+            .and_then(|entry| entry.winner())
+            .and_then(|winner| winner.value.to_css_text())
+    };
 
     assert_eq!(
-        style
-            .get(CascadePropertyId::BorderTopColor)
-            .expect("border-top-color")
-            .source(),
-        &ResolvedValueSource::Initial(crate::InitialStyleValue::TransparentColor)
+        winner(CascadePropertyId::BorderTopColor).as_deref(),
+        Some("red")
     );
     assert_eq!(
-        style
-            .get(CascadePropertyId::BorderTopStyle)
-            .expect("border-top-style")
-            .source(),
-        &ResolvedValueSource::Initial(crate::InitialStyleValue::BorderStyleNone)
+        winner(CascadePropertyId::BorderTopStyle).as_deref(),
+        Some("solid")
     );
     assert_eq!(
-        style
-            .get(CascadePropertyId::BorderTopWidth)
-            .expect("border-top-width")
-            .source(),
-        &ResolvedValueSource::Initial(crate::InitialStyleValue::ZeroPx)
+        winner(CascadePropertyId::BorderTopWidth).as_deref(),
+        Some("2px")
+    );
+    assert_eq!(
+        winner(CascadePropertyId::BorderRightWidth).as_deref(),
+        Some("3px")
+    );
+    assert_eq!(
+        winner(CascadePropertyId::BorderBottomStyle).as_deref(),
+        Some("solid")
+    );
+    assert_eq!(
+        winner(CascadePropertyId::BorderLeftStyle).as_deref(),
+        Some("none")
+    );
+    assert_eq!(
+        winner(CascadePropertyId::BorderLeftWidth).as_deref(),
+        Some("3px")
+    );
+    assert!(
+        matches!(
+            style
+                .get(CascadePropertyId::BorderLeftColor)
+                .expect("border-left-color")
+                .source(),
+            ResolvedValueSource::CssWideKeyword(crate::CssWideResolvedSource::Initial {
+                initial: crate::InitialStyleValue::TransparentColor,
+                ..
+            })
+        ),
+        "border-left resets the color it omits"
     );
 }
 
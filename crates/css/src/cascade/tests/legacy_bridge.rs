@@ -36,3 +36,171 @@ fn attach_styles_clears_legacy_projection_when_style_resolution_hits_limits() {
     };
     assert!(element.style().is_empty());
 }
+
+#[test]
+fn attach_styles_matches_rules_through_every_combinator() {
+    let stylesheets = vec![stylesheet(
+        "nav ul li a { color: red; }
+         nav > li { color: green; }
+         h1 + p { color: blue; }
+         h1 ~ span { color: gray; }",
+    )];
+    let mut dom = element(
+        "body",
+        Vec::new(),
+        vec![
+            element(
+                "nav",
+                Vec::new(),
+                vec![element(
+                    "ul",
+                    Vec::new(),
+                    vec![element(
+                        "li",
+                        Vec::new(),
+                        vec![element("a", Vec::new(), Vec::new())],
+                    )],
+                )],
+            ),
+            element("h1", Vec::new(), Vec::new()),
+            element("p", Vec::new(), Vec::new()),
+            element("span", Vec::new(), Vec::new()),
+            element("p", Vec::new(), Vec::new()),
+        ],
+    );
+
+    attach_styles(&mut dom, &stylesheets);
+
+    assert_eq!(projected_color(&dom, &[0, 0, 0, 0]), Some("red"));
+    assert_eq!(projected_color(&dom, &[0, 0, 0]), None);
+    assert_eq!(projected_color(&dom, &[2]), Some("blue"));
+    assert_eq!(projected_color(&dom, &[3]), Some("gray"));
+    assert_eq!(projected_color(&dom, &[4]), None);
+}
+
+#[test]
+fn attach_styles_matches_class_id_and_attribute_selectors() {
+    let stylesheets = vec![stylesheet(
+        ".card.featured { color: red; }
+         #main { color: green; }
+         [hidden] { color: blue; }
+         [lang=en] { color: gray; }
+         [rel~=nofollow] { color: white; }
+         [href^=\"https:\"] { color: black; }",
+    )];
+    let mut dom = element(
+        "body",
+        Vec::new(),
+        vec![
+            element(
+                "div",
+                vec![("class", Some("a card b featured"))],
+                Vec::new(),
+            ),
+            element("div", vec![("class", Some("card"))], Vec::new()),
+            element("div", vec![("id", Some("main"))], Vec::new()),
+            element("div", vec![("hidden", None)], Vec::new()),
+            element("p", vec![("lang", Some("en"))], Vec::new()),
+            element("p", vec![("lang", Some("en-GB"))], Vec::new()),
+            element("a", vec![("rel", Some("external nofollow"))], Vec::new()),
+            element("a", vec![("rel", Some("nofollowed"))], Vec::new()),
+            element(
+                "a",
+                vec![("href", Some("https://example.com/"))],
+                Vec::new(),
+            ),
+            element("a", vec![("href", Some("http://example.com/"))], Vec::new()),
+        ],
+    );
+
+    attach_styles(&mut dom, &stylesheets);
+
+    let colors = (0..10)
+        .map(|index| projected_color(&dom, &[index]))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        colors,
+        [
+            Some("red"),
+            None,
+            Some("green"),
+            Some("blue"),
+            Some("gray"),
+            None,
+            Some("white"),
+            None,
+            Some("black"),
+            None,
+        ]
+    );
+}
+
+#[test]
+fn attach_styles_orders_winners_by_importance_specificity_and_inline_style() {
+    let stylesheets = vec![stylesheet(
+        "#lead { color: red; }
+         p.intro { color: green; }
+         p { color: blue; }
+         .note { color: gray !important; }",
+    )];
+    let mut dom = element(
+        "body",
+        Vec::new(),
+        vec![
+            element(
+                "p",
+                vec![("id", Some("lead")), ("class", Some("intro"))],
+                Vec::new(),
+            ),
+            element("p", vec![("class", Some("intro"))], Vec::new()),
+            element("p", vec![("style", Some("color: white"))], Vec::new()),
+            element(
+                "p",
+                vec![("class", Some("note")), ("style", Some("color: white"))],
+                Vec::new(),
+            ),
+        ],
+    );
+
+    attach_styles(&mut dom, &stylesheets);
+
+    assert_eq!(projected_color(&dom, &[0]), Some("red"));
+    assert_eq!(projected_color(&dom, &[1]), Some("green"));
+    assert_eq!(projected_color(&dom, &[2]), Some("white"));
+    assert_eq!(projected_color(&dom, &[3]), Some("gray"));
+}
+
+#[test]
+fn attach_styles_stripes_table_rows_with_structural_pseudo_classes() {
+    let stylesheets = vec![stylesheet(
+        "tr:nth-child(even) { color: gray; }
+         tr:first-child { color: red; }
+         tr:last-child { color: blue; }",
+    )];
+    let row = || element("tr", Vec::new(), Vec::new());
+    let mut dom = element("tbody", Vec::new(), vec![row(), row(), row(), row(), row()]);
+
+    attach_styles(&mut dom, &stylesheets);
+
+    let colors = (0..5)
+        .map(|index| projected_color(&dom, &[index]))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        colors,
+        [Some("red"), Some("gray"), None, Some("gray"), Some("blue")]
+    );
+}
+
+fn projected_color<'a>(root: &'a html::Node, path: &[usize]) -> Option<&'a str> {
+    let node = path.iter().fold(root, |node, &index| {
+        &node.children().expect("element has children")[index]
+    });
+    let html::Node::Element { element } = node else {
+        panic!("expected element");
+    };
+    element
+        .style()
+        .iter()
+        .find(|(name, _)| name == "color")
+        .map(|(_, value)| value.as_str())
+}
@@ -47,6 +47,7 @@ fn resolved_document_style_debug_snapshot_is_stable() {
             "    padding-right: initial(0px)\n",
             "    padding-top: initial(0px)\n",
             "    position: initial(static)\n",
+            "    resize: initial(none)\n",
             "    text-decoration-line: initial(none)\n",
             "    width: initial(auto)\n",
             "    z-index: initial(auto)\n",
@@ -114,6 +115,7 @@ fn document_style_resolution_debug_snapshot_covers_override_inheritance_and_defa
             "    padding-right: initial(0px)\n",
             "    padding-top: initial(0px)\n",
             "    position: initial(static)\n",
+            "    resize: initial(none)\n",
             "    text-decoration-line: initial(none)\n",
             "    width: initial(auto)\n",
             "    z-index: initial(auto)\n",
@@ -165,6 +167,7 @@ fn document_style_resolution_debug_snapshot_covers_override_inheritance_and_defa
             "    padding-right: initial(0px)\n",
             "    padding-top: initial(0px)\n",
             "    position: initial(static)\n",
+            "    resize: initial(none)\n",
             "    text-decoration-line: initial(none)\n",
             "    width: initial(auto)\n",
             "    z-index: initial(auto)\n",
@@ -3,7 +3,7 @@ use std::collections::BTreeMap;
 use crate::{
     PropertyId, property_registry,
     values::{
-        BorderStyle, Display, Length, LengthPercentage, OutlineStyle, Overflow, Position,
+        BorderStyle, Display, Length, LengthPercentage, OutlineStyle, Overflow, Position, Resize,
         TextDecorationLine, ZIndex,
     },
 };
@@ -112,6 +112,7 @@ impl ComputedStyleBuilder {
             display: expect_display(&self.entries, PropertyId::Display),
             overflow: expect_overflow(&self.entries, PropertyId::Overflow),
             position: expect_position(&self.entries, PropertyId::Position),
+            resize: expect_resize(&self.entries, PropertyId::Resize),
             z_index: expect_z_index(&self.entries, PropertyId::ZIndex),
             text_decoration_line: expect_text_decoration_line(
                 &self.entries,
@@ -207,6 +208,21 @@ fn expect_display(entries: &BTreeMap<PropertyId, ComputedValue>, property: Prope
     }
 }
 
+fn expect_resize(entries: &BTreeMap<PropertyId, ComputedValue>, property: PropertyId) -> Resize {
+    match entries.get(&property).copied() {
+        Some(ComputedValue::Resize(resize)) => resize,
+        Some(other) => unreachable!(
+            "property '{}' expected resize computed value, got {:?}",
+            property.name(),
+            other.discriminant()
+        ),
+        None => unreachable!(
+            "property '{}' missing after completeness check",
+            property.name()
+        ),
+    }
+}
+
 fn expect_overflow(
     entries: &BTreeMap<PropertyId, ComputedValue>,
     property: PropertyId,
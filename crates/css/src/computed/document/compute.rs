@@ -3,11 +3,14 @@
 use std::collections::BTreeMap;
 
 use crate::{
+    MediaEnvironment,
     cascade::{
-        ResolvedDocumentStyle, StyleResolutionLimits, try_resolve_document_styles_with_limits,
+        ResolvedDocumentStyle, ResolvedStyle, ResolvedStyleEntry, StyleResolutionLimits,
+        try_resolve_document_styles_with_limits,
     },
     model,
     selectors::{SelectorDomIndex, SelectorMatchingContext},
+    values::CssLengthUnit,
 };
 use html::Node;
 
@@ -35,27 +38,35 @@ pub fn compute_document_styles_with_limits(
     compute_document_styles_from_resolved_styles(root, &resolved)
 }
 
+/// Computes document-level styles with viewport units resolved against
+/// `media`, reporting how often the pass reused a computed style.
 pub fn compute_document_styles_from_resolved_styles_with_reuse_stats(
     root: &Node,
     resolved_styles: &ResolvedDocumentStyle,
+    media: &MediaEnvironment,
 ) -> Result<ComputedDocumentStyleWithStats, ComputedStyleResolutionError> {
-    compute_document_styles_from_resolved_styles_pass(root, resolved_styles, None, 0)
+    compute_document_styles_from_resolved_styles_pass(root, resolved_styles, media, None, 0)
         .map(|computed| computed.expect("full computed style pass cannot miss prefix validation"))
 }
 
 /// Computes document-level styles from an already materialized structured
-/// cascade result.
+/// cascade result, in the empty viewport of `MediaEnvironment::default()`.
 pub fn compute_document_styles_from_resolved_styles(
     root: &Node,
     resolved_styles: &ResolvedDocumentStyle,
 ) -> Result<ComputedDocumentStyle, ComputedStyleResolutionError> {
-    compute_document_styles_from_resolved_styles_with_reuse_stats(root, resolved_styles)
-        .map(|computed| computed.computed)
+    compute_document_styles_from_resolved_styles_with_reuse_stats(
+        root,
+        resolved_styles,
+        &MediaEnvironment::default(),
+    )
+    .map(|computed| computed.computed)
 }
 
 pub(super) fn compute_document_styles_from_resolved_styles_pass(
     root: &Node,
     resolved_styles: &ResolvedDocumentStyle,
+    media: &MediaEnvironment,
     previous_computed: Option<&ComputedDocumentStyle>,
     reused_prefix_len: usize,
 ) -> Result<Option<ComputedDocumentStyleWithStats>, ComputedStyleResolutionError> {
@@ -72,7 +83,9 @@ pub(super) fn compute_document_styles_from_resolved_styles_pass(
 
     let mut computed_by_element = BTreeMap::new();
     let mut entries = Vec::with_capacity(index.len());
-    let mut reuse_cache = ComputedStyleReuseCache::default();
+    let mut reuse_cache = ComputedStyleReuseCache::new(media);
+    let mut root_style = None;
+    let mut viewport_dependent = false;
 
     for (element_index, element) in index.elements().enumerate() {
         let resolved = resolved_styles
@@ -104,6 +117,7 @@ pub(super) fn compute_document_styles_from_resolved_styles_pass(
                 )?),
                 None => None,
             };
+        viewport_dependent |= uses_viewport_units(resolved.style());
 
         if element_index < reused_prefix_len {
             let previous = previous_computed
@@ -117,13 +131,22 @@ pub(super) fn compute_document_styles_from_resolved_styles_pass(
             }
 
             reuse_cache.seed(resolved.style(), parent_style, *previous.style());
+            root_style.get_or_insert(*previous.style());
             computed_by_element.insert(element, *previous.style());
             entries.push(previous.clone());
             continue;
         }
 
-        let style = reuse_cache.lookup_or_compute(resolved.style(), parent_style)?;
+        // `rem` resolves against the first element, the document root. An
+        // element without a parent stands in as its own root, which keeps
+        // the reuse cache key `(resolved style, parent)` sufficient.
+        let style = reuse_cache.lookup_or_compute(
+            resolved.style(),
+            parent_style,
+            root_style.as_ref().filter(|_| parent_style.is_some()),
+        )?;
 
+        root_style.get_or_insert(style);
         computed_by_element.insert(element, style);
         entries.push(ComputedElementStyle::new(
             element,
@@ -134,7 +157,22 @@ pub(super) fn compute_document_styles_from_resolved_styles_pass(
     }
 
     Ok(Some(ComputedDocumentStyleWithStats {
-        computed: ComputedDocumentStyle::new(entries),
+        computed: ComputedDocumentStyle::new(entries, viewport_dependent),
         reuse_stats: reuse_cache.stats(),
     }))
 }
+
+/// Whether a winning declaration in `style` uses a viewport-relative length.
+fn uses_viewport_units(style: &ResolvedStyle) -> bool {
+    style
+        .entries()
+        .iter()
+        .filter_map(ResolvedStyleEntry::winner)
+        .filter_map(|winner| winner.value.parsed())
+        .any(|specified| {
+            specified
+                .value()
+                .length_unit()
+                .is_some_and(CssLengthUnit::is_viewport_relative)
+        })
+}
@@ -1,12 +1,14 @@
 //! Incremental suffix recomputation for document-level computed styles.
 
 use crate::{
+    MediaEnvironment,
     cascade::{
         ResolvedDocumentStyle, StyleResolutionLimits, StylesheetCascadeInput,
         try_resolve_document_styles_incremental_suffix_from_cascade_inputs_with_limits,
         try_resolve_document_styles_incremental_suffix_with_limits,
     },
     model,
+    selectors::SelectorElementStates,
 };
 use html::{Node, internal::Id};
 
@@ -32,6 +34,7 @@ pub fn compute_document_styles_incremental_suffix_with_limits(
     previous_computed: &ComputedDocumentStyle,
     dirty_node_ids: &[Id],
     limits: &StyleResolutionLimits,
+    media: &MediaEnvironment,
 ) -> Result<Option<IncrementalComputedDocumentStyle>, ComputedStyleResolutionError> {
     let Some(resolved) = try_resolve_document_styles_incremental_suffix_with_limits(
         root,
@@ -48,6 +51,7 @@ pub fn compute_document_styles_incremental_suffix_with_limits(
     let Some(computed) = compute_document_styles_from_resolved_styles_incremental_suffix(
         root,
         &resolved.resolved,
+        media,
         previous_computed,
         resolved.stats.reused_prefix_len,
     )?
@@ -65,18 +69,22 @@ pub fn compute_document_styles_incremental_suffix_with_limits(
     }))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn compute_document_styles_incremental_suffix_from_cascade_inputs_with_limits(
     root: &Node,
     sheets: &[StylesheetCascadeInput<'_>],
+    element_states: &SelectorElementStates,
     previous_resolved: &ResolvedDocumentStyle,
     previous_computed: &ComputedDocumentStyle,
     dirty_node_ids: &[Id],
     limits: &StyleResolutionLimits,
+    media: &MediaEnvironment,
 ) -> Result<Option<IncrementalComputedDocumentStyle>, ComputedStyleResolutionError> {
     let Some(resolved) =
         try_resolve_document_styles_incremental_suffix_from_cascade_inputs_with_limits(
             root,
             sheets,
+            element_states,
             previous_resolved,
             dirty_node_ids,
             limits,
@@ -89,6 +97,7 @@ pub fn compute_document_styles_incremental_suffix_from_cascade_inputs_with_limit
     let Some(computed) = compute_document_styles_from_resolved_styles_incremental_suffix(
         root,
         &resolved.resolved,
+        media,
         previous_computed,
         resolved.stats.reused_prefix_len,
     )?
@@ -106,15 +115,19 @@ pub fn compute_document_styles_incremental_suffix_from_cascade_inputs_with_limit
     }))
 }
 
+/// `previous_computed` must have been computed against the same `media`; its
+/// prefix is reused as is.
 fn compute_document_styles_from_resolved_styles_incremental_suffix(
     root: &Node,
     resolved_styles: &ResolvedDocumentStyle,
+    media: &MediaEnvironment,
     previous_computed: &ComputedDocumentStyle,
     reused_prefix_len: usize,
 ) -> Result<Option<ComputedDocumentStyleWithStats>, ComputedStyleResolutionError> {
     compute_document_styles_from_resolved_styles_pass(
         root,
         resolved_styles,
+        media,
         Some(previous_computed),
         reused_prefix_len,
     )
@@ -1,16 +1,17 @@
 //! Single-element computed-style materialization.
 
 use crate::{
-    PropertyId, PropertyInheritance,
+    MediaEnvironment, PropertyId, PropertyInheritance,
     cascade::{CssWideResolvedSource, ResolvedStyle, ResolvedValueSource},
     property_registry,
+    values::Length,
 };
 
 use super::{
     super::{
         builder::ComputedStyleBuilder,
         style::ComputedStyle,
-        value::{ComputedValue, normalize_specified_value},
+        value::{ComputedValue, ComputedValueContext},
     },
     error::ComputedStyleResolutionError,
 };
@@ -20,17 +21,95 @@ use super::{
 /// Rejected invalid declarations do not appear in `ResolvedStyle` winners.
 /// Fallback is therefore applied by the cascade source carried in each entry:
 /// another valid winner, inheritance, or the property's initial/default value.
+///
+/// The element is treated as the root of an empty viewport; see
+/// [`compute_style_from_resolved_style_in_environment`].
 pub fn compute_style_from_resolved_style(
     resolved_style: &ResolvedStyle,
     parent_style: Option<&ComputedStyle>,
 ) -> Result<ComputedStyle, ComputedStyleResolutionError> {
-    let mut builder = ComputedStyleBuilder::new();
+    compute_style_from_resolved_style_in_environment(
+        resolved_style,
+        parent_style,
+        None,
+        &MediaEnvironment::default(),
+    )
+}
 
+/// Materializes one element's computed style, resolving relative values.
+///
+/// `em` resolves against the element's computed font size, or the parent's
+/// for `font-size` itself, as do `font-size` percentages. `rem` resolves against the font size of
+/// `root_style`; pass `None` for the root element, whose own font size stands
+/// in except in `font-size`, where `rem` means the initial value. `vw` and
+/// `vh` resolve against the viewport of `media`. `currentColor` resolves
+/// against the element's computed `color`, or the parent's in `color` itself.
+/// Percentages are not accepted in margins or padding yet; such declarations
+/// are dropped as invalid before they get here.
+pub fn compute_style_from_resolved_style_in_environment(
+    resolved_style: &ResolvedStyle,
+    parent_style: Option<&ComputedStyle>,
+    root_style: Option<&ComputedStyle>,
+    media: &MediaEnvironment,
+) -> Result<ComputedStyle, ComputedStyleResolutionError> {
+    let initial = ComputedValueContext::for_media(media);
+    let font_size_context = ComputedValueContext {
+        font_size: parent_style.map_or(initial.font_size, font_size_px),
+        root_font_size: root_style.map_or(initial.root_font_size, font_size_px),
+        ..initial
+    };
+    let font_size = computed_value_from_resolved_property(
+        PropertyId::FontSize,
+        resolved_style,
+        parent_style,
+        &font_size_context,
+    );
+    // A failed font size or color is reported in registry order below, like
+    // any other property, so the values computed before it fall back to the
+    // initial font size or color.
+    let own_font_size = match font_size {
+        Ok(ComputedValue::Length(Length::Px(px))) => px,
+        _ => initial.font_size,
+    };
+    let color_context = ComputedValueContext {
+        font_size: own_font_size,
+        root_font_size: root_style.map_or(own_font_size, font_size_px),
+        current_color: parent_style.map_or(initial.current_color, ComputedStyle::color),
+        ..initial
+    };
+    let color = computed_value_from_resolved_property(
+        PropertyId::Color,
+        resolved_style,
+        parent_style,
+        &color_context,
+    );
+    let own_color = match color {
+        Ok(ComputedValue::Color(color)) => color,
+        _ => initial.current_color,
+    };
+    let context = ComputedValueContext {
+        current_color: own_color,
+        ..color_context
+    };
+    let mut font_size = Some(font_size);
+    let mut color = Some(color);
+
+    let mut builder = ComputedStyleBuilder::new();
     for property in property_registry().ids() {
-        let entry = resolved_style
-            .get(property)
-            .ok_or(ComputedStyleResolutionError::MissingResolvedProperty { property })?;
-        let value = computed_value_from_resolved_source(property, entry.source(), parent_style)?;
+        let value = match property {
+            PropertyId::FontSize => font_size
+                .take()
+                .expect("font-size appears once in the property registry")?,
+            PropertyId::Color => color
+                .take()
+                .expect("color appears once in the property registry")?,
+            _ => computed_value_from_resolved_property(
+                property,
+                resolved_style,
+                parent_style,
+                &context,
+            )?,
+        };
         builder
             .record(property, value)
             .map_err(ComputedStyleResolutionError::Build)?;
@@ -39,10 +118,28 @@ pub fn compute_style_from_resolved_style(
     builder.build().map_err(ComputedStyleResolutionError::Build)
 }
 
+fn font_size_px(style: &ComputedStyle) -> f32 {
+    let Length::Px(px) = style.font_size();
+    px
+}
+
+fn computed_value_from_resolved_property(
+    property: PropertyId,
+    resolved_style: &ResolvedStyle,
+    parent_style: Option<&ComputedStyle>,
+    context: &ComputedValueContext,
+) -> Result<ComputedValue, ComputedStyleResolutionError> {
+    let entry = resolved_style
+        .get(property)
+        .ok_or(ComputedStyleResolutionError::MissingResolvedProperty { property })?;
+    computed_value_from_resolved_source(property, entry.source(), parent_style, context)
+}
+
 fn computed_value_from_resolved_source(
     property: PropertyId,
     source: &ResolvedValueSource,
     parent_style: Option<&ComputedStyle>,
+    context: &ComputedValueContext,
 ) -> Result<ComputedValue, ComputedStyleResolutionError> {
     match source {
         ResolvedValueSource::Winner(winner) => {
@@ -57,7 +154,7 @@ fn computed_value_from_resolved_source(
                 });
             }
 
-            normalize_specified_value(specified)
+            ComputedValue::from_specified_in_context(specified, context)
                 .map_err(ComputedStyleResolutionError::Normalization)
         }
         ResolvedValueSource::Inherited => {
@@ -24,7 +24,9 @@ pub use incremental::{
     compute_document_styles_incremental_suffix_from_cascade_inputs_with_limits,
     compute_document_styles_incremental_suffix_with_limits,
 };
-pub use materialize::compute_style_from_resolved_style;
+pub use materialize::{
+    compute_style_from_resolved_style, compute_style_from_resolved_style_in_environment,
+};
 pub use model::{
     ComputedDocumentStyle, ComputedDocumentStyleWithStats, ComputedElementStyle,
     ComputedStyleReuseStats,
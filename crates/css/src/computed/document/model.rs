@@ -50,17 +50,27 @@ impl ComputedElementStyle {
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct ComputedDocumentStyle {
     pub(in crate::computed) entries: Vec<ComputedElementStyle>,
+    pub(in crate::computed) viewport_dependent: bool,
 }
 
 impl ComputedDocumentStyle {
-    pub(super) fn new(entries: Vec<ComputedElementStyle>) -> Self {
-        Self { entries }
+    pub(super) fn new(entries: Vec<ComputedElementStyle>, viewport_dependent: bool) -> Self {
+        Self {
+            entries,
+            viewport_dependent,
+        }
     }
 
     pub fn entries(&self) -> &[ComputedElementStyle] {
         &self.entries
     }
 
+    /// Whether any computed value was resolved from a `vw` or `vh` length,
+    /// so resizing the viewport needs a new style pass.
+    pub fn depends_on_viewport(&self) -> bool {
+        self.viewport_dependent
+    }
+
     pub fn get(&self, element: SelectorDomElementId) -> Option<&ComputedElementStyle> {
         self.entries
             .iter()
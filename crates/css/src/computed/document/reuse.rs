@@ -1,28 +1,37 @@
 //! Pass-local computed-style reuse cache.
 
-use crate::cascade::ResolvedStyle;
+use crate::{MediaEnvironment, cascade::ResolvedStyle};
 
 use super::{
     super::style::ComputedStyle, error::ComputedStyleResolutionError,
-    materialize::compute_style_from_resolved_style, model::ComputedStyleReuseStats,
+    materialize::compute_style_from_resolved_style_in_environment, model::ComputedStyleReuseStats,
 };
 
 // Pass-local cache for computed-style materialization.
 //
-// Reuse is valid only while `compute_style_from_resolved_style(...)` is a pure
-// function of `(ResolvedStyle, Option<ComputedStyle parent>)`. If future
-// computed-value logic depends on additional environment inputs such as
-// viewport units, font metrics, writing mode context, visited-link privacy
-// state, container queries, or media/device state, those inputs must either be
-// added to this cache key or this reuse path must be disabled for affected
-// properties.
-#[derive(Default)]
-pub(super) struct ComputedStyleReuseCache {
+// Reuse is valid only while materialization is a pure function of
+// `(ResolvedStyle, Option<ComputedStyle parent>)`. The media environment that
+// viewport units resolve against and the root style that `rem` resolves
+// against are fixed for the whole pass, so they stay out of the key. If future
+// computed-value logic depends on additional inputs that vary within a pass,
+// such as font metrics, writing mode context, visited-link privacy state, or
+// container queries, those inputs must either be added to this cache key or
+// this reuse path must be disabled for affected properties.
+pub(super) struct ComputedStyleReuseCache<'a> {
+    media: &'a MediaEnvironment,
     entries: Vec<ComputedStyleReuseEntry>,
     stats: ComputedStyleReuseStats,
 }
 
-impl ComputedStyleReuseCache {
+impl<'a> ComputedStyleReuseCache<'a> {
+    pub(super) fn new(media: &'a MediaEnvironment) -> Self {
+        Self {
+            media,
+            entries: Vec::new(),
+            stats: ComputedStyleReuseStats::default(),
+        }
+    }
+
     pub(super) fn seed(
         &mut self,
         resolved_style: &ResolvedStyle,
@@ -49,6 +58,7 @@ impl ComputedStyleReuseCache {
         &mut self,
         resolved_style: &ResolvedStyle,
         parent_style: Option<&ComputedStyle>,
+        root_style: Option<&ComputedStyle>,
     ) -> Result<ComputedStyle, ComputedStyleResolutionError> {
         let parent = parent_style.copied();
         if let Some(entry) = self
@@ -61,7 +71,12 @@ impl ComputedStyleReuseCache {
         }
 
         self.stats.misses = self.stats.misses.saturating_add(1);
-        let computed = compute_style_from_resolved_style(resolved_style, parent_style)?;
+        let computed = compute_style_from_resolved_style_in_environment(
+            resolved_style,
+            parent_style,
+            root_style,
+            self.media,
+        )?;
         self.entries.push(ComputedStyleReuseEntry {
             resolved: resolved_style.clone(),
             parent,
@@ -1,8 +1,8 @@
 use crate::{
     PropertyId, PropertyInheritance, model, property_registry,
-    specified::parse_specified_value,
+    specified::{SpecifiedDeclarationValue, parse_specified_declaration_value},
     syntax::ParseOptions,
-    values::{Display, Length},
+    values::{CssWideKeyword, Display, Length},
 };
 
 use html::Node;
@@ -21,6 +21,11 @@ use super::{
 /// Assumptions:
 /// - `specified` already reflects cascade (author + inline etc.)
 ///
+/// Inherited properties start from the parent's value and the others from
+/// their initial value. `inherit`, `initial` and `unset` resolve the way the
+/// cascade resolves them, with the initial value standing in for a missing
+/// parent.
+///
 /// This remains a compatibility step for callers that still provide the
 /// DOM-attached string vector. Supported declarations are still resolved through
 /// the property registry, specified-value parser, computed-value normalizer, and
@@ -37,7 +42,7 @@ pub fn compute_style(
     let mut has_valid_color_decl = false;
 
     for (name, value) in specified {
-        let Some((property, value)) = legacy_computed_declaration(name, value) else {
+        let Some((property, value)) = legacy_computed_declaration(name, value, parent) else {
             continue;
         };
 
@@ -79,15 +84,44 @@ fn legacy_base_computed_style(parent: Option<&ComputedStyle>) -> ComputedStyle {
     })
 }
 
-fn legacy_computed_declaration(name: &str, value: &str) -> Option<(PropertyId, ComputedValue)> {
+fn legacy_computed_declaration(
+    name: &str,
+    value: &str,
+    parent: Option<&ComputedStyle>,
+) -> Option<(PropertyId, ComputedValue)> {
     let name = name.trim().to_ascii_lowercase();
     let property = property_registry().lookup_id(&name)?;
     let declaration_value = legacy_declaration_value(property, value)?;
-    let specified = parse_specified_value(property, &declaration_value).ok()?;
-    let computed = normalize_specified_value(&specified).ok()?;
+    let computed = match parse_specified_declaration_value(property, &declaration_value).ok()? {
+        SpecifiedDeclarationValue::Property(specified) => {
+            normalize_specified_value(&specified).ok()?
+        }
+        SpecifiedDeclarationValue::CssWideKeyword { value, .. } => {
+            legacy_css_wide_keyword_value(property, value.keyword(), parent)?
+        }
+    };
     Some((property, computed))
 }
 
+fn legacy_css_wide_keyword_value(
+    property: PropertyId,
+    keyword: CssWideKeyword,
+    parent: Option<&ComputedStyle>,
+) -> Option<ComputedValue> {
+    let inherit = match keyword {
+        CssWideKeyword::Initial => false,
+        CssWideKeyword::Inherit => true,
+        CssWideKeyword::Unset => property.metadata().inheritance == PropertyInheritance::Inherited,
+        // Rejected by the specified-value parser before reaching here.
+        CssWideKeyword::Revert | CssWideKeyword::RevertLayer => return None,
+    };
+
+    Some(match parent {
+        Some(parent) if inherit => parent.get(property).value(),
+        _ => ComputedValue::from_initial(property),
+    })
+}
+
 fn legacy_declaration_value(property: PropertyId, value: &str) -> Option<model::DeclarationValue> {
     // Bridge-only parser adapter: this routes DOM-attached `(property, value)`
     // pairs through the real model parser so the legacy path does not grow a
@@ -245,7 +279,7 @@ pub fn build_style_tree<'a>(
             // temporary HTML/UA default-display bridge.
             let has_display_decl = style.iter().any(|(name, value)| {
                 matches!(
-                    legacy_computed_declaration(name, value),
+                    legacy_computed_declaration(name, value, parent_style),
                     Some((PropertyId::Display, _))
                 )
             });
@@ -37,7 +37,7 @@ pub use document::{
     compute_document_styles_from_resolved_styles_with_reuse_stats,
     compute_document_styles_incremental_suffix_from_cascade_inputs_with_limits,
     compute_document_styles_incremental_suffix_with_limits, compute_document_styles_with_limits,
-    compute_style_from_resolved_style,
+    compute_style_from_resolved_style, compute_style_from_resolved_style_in_environment,
 };
 pub use format::computed_value_debug_snapshot;
 pub use impact::{
@@ -54,8 +54,9 @@ pub use style_tree::{
     build_style_tree_with_stylesheets,
 };
 pub use value::{
-    ComputedValue, ComputedValueDiscriminant, ComputedValueNormalizationError,
-    ComputedValueNormalizationErrorKind, normalize_specified_value,
+    ComputedValue, ComputedValueContext, ComputedValueDiscriminant,
+    ComputedValueNormalizationError, ComputedValueNormalizationErrorKind,
+    normalize_specified_value,
 };
 
 #[cfg(test)]
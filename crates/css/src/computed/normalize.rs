@@ -6,38 +6,34 @@ use crate::{
         SpecifiedLengthPercentageOrNone, SpecifiedZIndexValue,
     },
     values::{
-        BorderStyle, CssLengthPercentageValue, CssLengthValue, CssPercentageValue, Display, Length,
-        LengthPercentage, OutlineStyle, Percentage, TextDecorationLine, ZIndex,
+        BorderStyle, CssLengthPercentageValue, CssLengthUnit, CssLengthValue, CssPercentageValue,
+        Display, Length, LengthPercentage, OutlineStyle, Percentage, TextDecorationLine, ZIndex,
     },
 };
 
-use super::value::{ComputedValueNormalizationError, ComputedValueNormalizationErrorKind};
+use super::value::{
+    ComputedValueContext, ComputedValueNormalizationError, ComputedValueNormalizationErrorKind,
+};
 
-pub(super) fn normalize_color(color: &SpecifiedColor) -> (u8, u8, u8, u8) {
+pub(super) fn normalize_color(
+    color: &SpecifiedColor,
+    context: &ComputedValueContext,
+) -> (u8, u8, u8, u8) {
     match color.syntax() {
-        SpecifiedColorSyntax::Keyword(keyword) => normalize_color_keyword(*keyword),
+        SpecifiedColorSyntax::Keyword(keyword) => normalize_color_keyword(*keyword, context),
         SpecifiedColorSyntax::Hex(hex) => hex.rgba(),
+        SpecifiedColorSyntax::Function(function) => function.rgba(),
     }
 }
 
-pub(super) fn normalize_color_keyword(keyword: SpecifiedColorKeyword) -> (u8, u8, u8, u8) {
+fn normalize_color_keyword(
+    keyword: SpecifiedColorKeyword,
+    context: &ComputedValueContext,
+) -> (u8, u8, u8, u8) {
     match keyword {
-        SpecifiedColorKeyword::Black => (0, 0, 0, 255),
-        SpecifiedColorKeyword::Blue => (0, 0, 255, 255),
-        SpecifiedColorKeyword::Cyan => (0, 255, 255, 255),
-        SpecifiedColorKeyword::Gray => (128, 128, 128, 255),
-        SpecifiedColorKeyword::Green => (0, 128, 0, 255),
-        SpecifiedColorKeyword::Magenta => (255, 0, 255, 255),
-        SpecifiedColorKeyword::Maroon => (128, 0, 0, 255),
-        SpecifiedColorKeyword::Navy => (0, 0, 128, 255),
-        SpecifiedColorKeyword::Olive => (128, 128, 0, 255),
-        SpecifiedColorKeyword::Purple => (128, 0, 128, 255),
-        SpecifiedColorKeyword::Red => (255, 0, 0, 255),
-        SpecifiedColorKeyword::Silver => (192, 192, 192, 255),
-        SpecifiedColorKeyword::Teal => (0, 128, 128, 255),
+        SpecifiedColorKeyword::Named(named) => named.rgba(),
         SpecifiedColorKeyword::Transparent => (0, 0, 0, 0),
-        SpecifiedColorKeyword::White => (255, 255, 255, 255),
-        SpecifiedColorKeyword::Yellow => (255, 255, 0, 255),
+        SpecifiedColorKeyword::CurrentColor => context.current_color,
     }
 }
 
@@ -85,26 +81,67 @@ pub(super) fn normalize_z_index(value: &SpecifiedZIndexValue) -> ZIndex {
 pub(super) fn normalize_length(
     property: PropertyId,
     length: &SpecifiedLength,
+    context: &ComputedValueContext,
 ) -> Result<Length, ComputedValueNormalizationError> {
-    normalize_css_length(property, length.value())
+    normalize_css_length(property, length.value(), context)
 }
 
 fn normalize_css_length(
     property: PropertyId,
     length: &CssLengthValue,
+    context: &ComputedValueContext,
+) -> Result<Length, ComputedValueNormalizationError> {
+    let number = length.numeric_value();
+    let px = match length.unit() {
+        CssLengthUnit::Px | CssLengthUnit::UnitlessZero => number,
+        CssLengthUnit::Em => number * f64::from(context.font_size),
+        CssLengthUnit::Rem => number * f64::from(context.root_font_size),
+        CssLengthUnit::Vw => number * f64::from(context.viewport_width) / 100.0,
+        CssLengthUnit::Vh => number * f64::from(context.viewport_height) / 100.0,
+    };
+    normalize_scaled_px(property, px)
+}
+
+fn normalize_scaled_px(
+    property: PropertyId,
+    px: f64,
 ) -> Result<Length, ComputedValueNormalizationError> {
-    let value = normalize_px_scalar(property, length.numeric_value())?;
+    // Scaling a validated scalar can still overflow `f64`.
+    if !px.is_finite() {
+        return Err(ComputedValueNormalizationError::new(
+            property,
+            ComputedValueNormalizationErrorKind::LengthOutOfRange,
+        ));
+    }
+    let value = normalize_px_scalar(property, px)?;
 
     Ok(Length::Px(value))
 }
 
+/// Resolves a length-percentage to CSS px, taking percentages of the context
+/// font size. In `font-size` that is the parent's, as it is for `em`.
+pub(super) fn normalize_length_percentage_against_font_size(
+    property: PropertyId,
+    value: &SpecifiedLengthPercentage,
+    context: &ComputedValueContext,
+) -> Result<Length, ComputedValueNormalizationError> {
+    match value.value() {
+        CssLengthPercentageValue::Length(length) => normalize_css_length(property, length, context),
+        CssLengthPercentageValue::Percentage(percentage) => normalize_scaled_px(
+            property,
+            percentage.numeric_value() * f64::from(context.font_size) / 100.0,
+        ),
+    }
+}
+
 pub(super) fn normalize_length_percentage_or_auto(
     property: PropertyId,
     value: &SpecifiedLengthPercentageOrAuto,
+    context: &ComputedValueContext,
 ) -> Result<Option<LengthPercentage>, ComputedValueNormalizationError> {
     match value {
         SpecifiedLengthPercentageOrAuto::LengthPercentage(value) => {
-            normalize_length_percentage(property, value).map(Some)
+            normalize_length_percentage(property, value, context).map(Some)
         }
         SpecifiedLengthPercentageOrAuto::Auto { .. } => Ok(None),
     }
@@ -113,10 +150,11 @@ pub(super) fn normalize_length_percentage_or_auto(
 pub(super) fn normalize_length_percentage_or_none(
     property: PropertyId,
     value: &SpecifiedLengthPercentageOrNone,
+    context: &ComputedValueContext,
 ) -> Result<Option<LengthPercentage>, ComputedValueNormalizationError> {
     match value {
         SpecifiedLengthPercentageOrNone::LengthPercentage(value) => {
-            normalize_length_percentage(property, value).map(Some)
+            normalize_length_percentage(property, value, context).map(Some)
         }
         SpecifiedLengthPercentageOrNone::None { .. } => Ok(None),
     }
@@ -125,10 +163,11 @@ pub(super) fn normalize_length_percentage_or_none(
 pub(super) fn normalize_length_percentage(
     property: PropertyId,
     value: &SpecifiedLengthPercentage,
+    context: &ComputedValueContext,
 ) -> Result<LengthPercentage, ComputedValueNormalizationError> {
     match value.value() {
         CssLengthPercentageValue::Length(length) => {
-            normalize_css_length(property, length).map(LengthPercentage::Length)
+            normalize_css_length(property, length, context).map(LengthPercentage::Length)
         }
         CssLengthPercentageValue::Percentage(percentage) => {
             normalize_css_percentage(property, percentage).map(LengthPercentage::Percentage)
@@ -5,7 +5,7 @@ use crate::{
     cascade::ResolvedStyle,
     property_registry,
     values::{
-        BorderStyle, Display, Length, LengthPercentage, OutlineStyle, Overflow, Position,
+        BorderStyle, Display, Length, LengthPercentage, OutlineStyle, Overflow, Position, Resize,
         TextDecorationLine, ZIndex,
     },
 };
@@ -175,6 +175,9 @@ pub struct ComputedStyle {
     /// CSS `position` keyword after computed-value resolution.
     pub(super) position: Position,
 
+    /// CSS `resize` keyword; only form controls currently honor it.
+    pub(super) resize: Resize,
+
     /// CSS `z-index` value after computed-value resolution.
     pub(super) z_index: ZIndex,
 
@@ -215,6 +218,7 @@ impl ComputedStyle {
             display: Display::Inline,
             overflow: Overflow::Visible,
             position: Position::Static,
+            resize: Resize::None,
             z_index: ZIndex::Auto,
             text_decoration_line: TextDecorationLine::None,
             width: None,
@@ -283,6 +287,11 @@ impl ComputedStyle {
         self.position
     }
 
+    /// Returns the computed `resize` keyword.
+    pub fn resize(&self) -> Resize {
+        self.resize
+    }
+
     /// Returns the computed `z-index` value.
     pub fn z_index(&self) -> ZIndex {
         self.z_index
@@ -313,6 +322,62 @@ impl ComputedStyle {
         self.max_width
     }
 
+    /// Returns a copy of this style with every absolute length scaled by
+    /// `factor` (font size, margins, padding, borders, outline and sizes).
+    ///
+    /// This implements page zoom: scaling computed lengths before layout makes
+    /// the document lay out as if the viewport were `1 / factor` as wide,
+    /// while text is still shaped at its final size. Percentages and colors
+    /// are unchanged.
+    pub fn zoomed(&self, factor: f32) -> Self {
+        let scale = |px: f32| px * factor;
+        let scale_length = |Length::Px(px): Length| Length::Px(scale(px));
+        let scale_lp = |value: Option<LengthPercentage>| {
+            value.map(|value| match value {
+                LengthPercentage::Length(length) => LengthPercentage::Length(scale_length(length)),
+                percentage @ LengthPercentage::Percentage(_) => percentage,
+            })
+        };
+        let scale_side = |side: BorderSide| BorderSide {
+            width: scale(side.width),
+            ..side
+        };
+        let m = self.box_metrics;
+
+        Self {
+            font_size: scale_length(self.font_size),
+            box_metrics: BoxMetrics {
+                margin_top: scale(m.margin_top),
+                margin_right: scale(m.margin_right),
+                margin_bottom: scale(m.margin_bottom),
+                margin_left: scale(m.margin_left),
+                padding_top: scale(m.padding_top),
+                padding_right: scale(m.padding_right),
+                padding_bottom: scale(m.padding_bottom),
+                padding_left: scale(m.padding_left),
+                border_top: scale(m.border_top),
+                border_right: scale(m.border_right),
+                border_bottom: scale(m.border_bottom),
+                border_left: scale(m.border_left),
+            },
+            border_edges: BorderEdges {
+                top: scale_side(self.border_edges.top),
+                right: scale_side(self.border_edges.right),
+                bottom: scale_side(self.border_edges.bottom),
+                left: scale_side(self.border_edges.left),
+            },
+            outline: Outline {
+                width: scale(self.outline.width),
+                ..self.outline
+            },
+            width: scale_lp(self.width),
+            height: scale_lp(self.height),
+            min_width: scale_lp(self.min_width),
+            max_width: scale_lp(self.max_width),
+            ..*self
+        }
+    }
+
     /// Returns a copy of this style with one computed property replaced.
     ///
     /// This keeps ad hoc updates behind the same property-kind and totality
@@ -389,6 +454,7 @@ impl ComputedStyle {
             PropertyId::OutlineStyle => ComputedValue::OutlineStyle(self.outline.style),
             PropertyId::OutlineWidth => ComputedValue::Length(Length::Px(self.outline.width)),
             PropertyId::Position => ComputedValue::Position(self.position),
+            PropertyId::Resize => ComputedValue::Resize(self.resize),
             PropertyId::ZIndex => ComputedValue::ZIndex(self.z_index),
             PropertyId::PaddingBottom => {
                 ComputedValue::Length(Length::Px(self.box_metrics.padding_bottom))
@@ -36,6 +36,16 @@ impl<'a> StylePhaseOutput<'a> {
         self.root
     }
 
+    /// Apply page zoom to every styled node (see [`ComputedStyle::zoomed`]).
+    ///
+    /// A factor of exactly `1.0` leaves the output untouched.
+    pub fn with_zoom(mut self, factor: f32) -> Self {
+        if factor != 1.0 {
+            zoom_styled_node(&mut self.root, factor);
+        }
+        self
+    }
+
     /// Stable debug snapshot for the style-to-layout phase boundary.
     pub fn to_debug_snapshot(&self) -> String {
         let mut out = String::new();
@@ -66,6 +76,13 @@ pub struct StyledNode<'a> {
     pub children: Vec<StyledNode<'a>>,
 }
 
+fn zoom_styled_node(node: &mut StyledNode<'_>, factor: f32) {
+    node.style = node.style.zoomed(factor);
+    for child in &mut node.children {
+        zoom_styled_node(child, factor);
+    }
+}
+
 fn count_styled_nodes(node: &StyledNode<'_>) -> usize {
     1 + node
         .children
@@ -1,8 +1,8 @@
 use super::support::*;
 use super::*;
 use crate::{
-    ComputedDocumentStyleInvalidationImpact, StyleResolutionError, StyleResolutionLimit,
-    StyleResolutionLimits,
+    ComputedDocumentStyleInvalidationImpact, MediaEnvironment, StyleResolutionError,
+    StyleResolutionLimit, StyleResolutionLimits,
 };
 
 #[test]
@@ -338,6 +338,7 @@ fn computed_document_style_snapshot_is_deterministic() {
             "  padding-right: 0px\n",
             "  padding-top: 0px\n",
             "  position: static\n",
+            "  resize: none\n",
             "  text-decoration-line: none\n",
             "  width: 12px\n",
             "  z-index: auto\n",
@@ -374,6 +375,7 @@ fn computed_document_style_snapshot_is_deterministic() {
             "  padding-right: 0px\n",
             "  padding-top: 0px\n",
             "  position: static\n",
+            "  resize: none\n",
             "  text-decoration-line: none\n",
             "  width: auto\n",
             "  z-index: auto\n",
@@ -412,8 +414,12 @@ fn compute_document_styles_reuses_identical_resolved_styles_with_same_parent() {
     );
     let resolved = resolve_document_styles(&dom, &stylesheets).expect("resolved document style");
 
-    let computed = compute_document_styles_from_resolved_styles_with_reuse_stats(&dom, &resolved)
-        .expect("computed document");
+    let computed = compute_document_styles_from_resolved_styles_with_reuse_stats(
+        &dom,
+        &resolved,
+        &MediaEnvironment::default(),
+    )
+    .expect("computed document");
 
     assert_eq!(computed.computed.entries().len(), 4);
     assert_eq!(
@@ -455,8 +461,12 @@ fn computed_style_reuse_does_not_cross_different_parent_computed_styles() {
     );
     let resolved = resolve_document_styles(&dom, &stylesheets).expect("resolved document style");
 
-    let computed = compute_document_styles_from_resolved_styles_with_reuse_stats(&dom, &resolved)
-        .expect("computed document");
+    let computed = compute_document_styles_from_resolved_styles_with_reuse_stats(
+        &dom,
+        &resolved,
+        &MediaEnvironment::default(),
+    )
+    .expect("computed document");
 
     assert_eq!(computed.computed.entries().len(), 5);
     let first_p = computed.computed.entries()[2].style();
@@ -471,6 +481,153 @@ fn computed_style_reuse_does_not_cross_different_parent_computed_styles() {
     );
 }
 
+#[test]
+fn compute_document_styles_resolve_relative_lengths_against_fonts_and_viewport() {
+    let stylesheets = vec![stylesheet(concat!(
+        "html { font-size: 2em; margin-left: 1rem; }",
+        "section { font-size: 1.5em; width: 10em; padding-top: 2rem; }",
+        "p { font-size: 1rem; height: 25vh; max-width: 50vw; }",
+    ))];
+    let dom = element(
+        "html",
+        Vec::new(),
+        vec![element(
+            "section",
+            Vec::new(),
+            vec![element("p", Vec::new(), Vec::new())],
+        )],
+    );
+    let resolved = resolve_document_styles(&dom, &stylesheets).expect("resolved document style");
+    let media = MediaEnvironment::default().with_viewport_size(800.0, 600.0);
+
+    let computed =
+        compute_document_styles_from_resolved_styles_with_reuse_stats(&dom, &resolved, &media)
+            .expect("computed document")
+            .computed;
+
+    let [html, section, p] = computed.entries() else {
+        panic!("expected three computed elements");
+    };
+    // `em` in the root's font-size and `rem` in its other properties.
+    assert_eq!(html.style().font_size(), Length::Px(32.0));
+    assert_eq!(html.style().box_metrics().margin_left, 32.0);
+    // `em` in font-size reads the parent; elsewhere it reads the element.
+    assert_eq!(section.style().font_size(), Length::Px(48.0));
+    assert_eq!(
+        section.style().width(),
+        Some(LengthPercentage::Length(Length::Px(480.0)))
+    );
+    assert_eq!(section.style().box_metrics().padding_top, 64.0);
+    assert_eq!(p.style().font_size(), Length::Px(32.0));
+    assert_eq!(
+        p.style().height(),
+        Some(LengthPercentage::Length(Length::Px(150.0)))
+    );
+    assert_eq!(
+        p.style().max_width(),
+        Some(LengthPercentage::Length(Length::Px(400.0)))
+    );
+    assert!(computed.depends_on_viewport());
+    assert!(
+        !compute_document_styles_from_resolved_styles(
+            &dom,
+            &resolve_document_styles(&dom, &[stylesheet("section { width: 10em; }")],)
+                .expect("resolved document style")
+        )
+        .expect("computed document")
+        .depends_on_viewport()
+    );
+}
+
+#[test]
+fn compute_document_styles_resolve_current_color_against_the_element_color() {
+    let stylesheets = vec![stylesheet(concat!(
+        "html { color: rgb(0 0 255 / 50%); }",
+        "section { color: currentColor; background-color: currentColor; }",
+        "p { color: hsl(0, 100%, 50%); background-color: currentcolor; }",
+    ))];
+    let dom = element(
+        "html",
+        Vec::new(),
+        vec![element(
+            "section",
+            Vec::new(),
+            vec![element("p", Vec::new(), Vec::new())],
+        )],
+    );
+    let resolved = resolve_document_styles(&dom, &stylesheets).expect("resolved document style");
+
+    let computed =
+        compute_document_styles_from_resolved_styles(&dom, &resolved).expect("computed document");
+
+    let [html, section, p] = computed.entries() else {
+        panic!("expected three computed elements");
+    };
+    assert_eq!(html.style().color(), (0, 0, 255, 128));
+    // `currentColor` in `color` reads the parent; elsewhere it reads the
+    // element's own color.
+    assert_eq!(section.style().color(), (0, 0, 255, 128));
+    assert_eq!(section.style().background_color(), (0, 0, 255, 128));
+    assert_eq!(p.style().color(), (255, 0, 0, 255));
+    assert_eq!(p.style().background_color(), (255, 0, 0, 255));
+}
+
+#[test]
+fn percentage_margins_and_padding_are_dropped_as_invalid() {
+    // Percentages in these properties are not supported yet: the
+    // declarations are invalid, so an earlier declaration or the initial
+    // value applies instead.
+    let stylesheets = vec![stylesheet(concat!(
+        "html { font-size: 20px; }",
+        "section { margin-left: 3px; margin-left: 10%; padding-top: 5%; }",
+    ))];
+    let dom = element(
+        "html",
+        Vec::new(),
+        vec![element("section", Vec::new(), Vec::new())],
+    );
+    let resolved = resolve_document_styles(&dom, &stylesheets).expect("resolved document style");
+
+    let computed =
+        compute_document_styles_from_resolved_styles(&dom, &resolved).expect("computed document");
+
+    let [_, section] = computed.entries() else {
+        panic!("expected two computed elements");
+    };
+    assert_eq!(section.style().box_metrics().margin_left, 3.0);
+    assert_eq!(section.style().box_metrics().padding_top, 0.0);
+}
+
+#[test]
+fn percentage_font_sizes_resolve_against_the_parent_font_size() {
+    let stylesheets = vec![stylesheet(concat!(
+        "html { font-size: 20px; }",
+        "section { font-size: 50%; margin-left: 2em; }",
+        "p { font-size: 300%; }",
+    ))];
+    let dom = element(
+        "html",
+        Vec::new(),
+        vec![element(
+            "section",
+            Vec::new(),
+            vec![element("p", Vec::new(), Vec::new())],
+        )],
+    );
+    let resolved = resolve_document_styles(&dom, &stylesheets).expect("resolved document style");
+
+    let computed =
+        compute_document_styles_from_resolved_styles(&dom, &resolved).expect("computed document");
+
+    let [_, section, p] = computed.entries() else {
+        panic!("expected three computed elements");
+    };
+    assert_eq!(section.style().font_size(), Length::Px(10.0));
+    // `em` in other properties reads the resolved percentage font size.
+    assert_eq!(section.style().box_metrics().margin_left, 20.0);
+    assert_eq!(p.style().font_size(), Length::Px(30.0));
+}
+
 #[test]
 fn compute_style_from_resolved_style_rejects_normalization_failures() {
     let stylesheets = vec![stylesheet("div { width: 1e39px; }")];
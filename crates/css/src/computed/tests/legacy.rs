@@ -90,6 +90,66 @@ fn legacy_compute_style_ignores_invalid_link_color_for_ua_fallback() {
     assert_eq!(valid.color(), (255, 0, 0, 255));
 }
 
+#[test]
+fn legacy_compute_style_resolves_css_wide_keywords_against_the_parent() {
+    let declarations = |pairs: &[(&str, &str)]| {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect::<Vec<_>>()
+    };
+    let parent = compute_style(
+        Some("div"),
+        &declarations(&[
+            ("color", "red"),
+            ("font-size", "20px"),
+            ("width", "100px"),
+            ("padding-left", "7px"),
+        ]),
+        None,
+    );
+
+    let defaults = compute_style(Some("div"), &[], Some(&parent));
+    assert_eq!(defaults.color(), (255, 0, 0, 255));
+    assert_eq!(defaults.font_size(), Length::Px(20.0));
+    assert_eq!(defaults.width(), None);
+    assert_eq!(defaults.box_metrics().padding_left, 0.0);
+
+    let keywords = compute_style(
+        Some("div"),
+        &declarations(&[
+            ("color", "initial"),
+            ("font-size", "unset"),
+            ("width", "inherit"),
+            ("padding-left", "unset"),
+        ]),
+        Some(&parent),
+    );
+    assert_eq!(keywords.color(), (0, 0, 0, 255));
+    assert_eq!(keywords.font_size(), Length::Px(20.0));
+    assert_eq!(
+        keywords.width(),
+        Some(LengthPercentage::Length(Length::Px(100.0)))
+    );
+    assert_eq!(keywords.box_metrics().padding_left, 0.0);
+
+    let root = compute_style(
+        Some("div"),
+        &declarations(&[("width", "inherit"), ("color", "revert")]),
+        None,
+    );
+    assert_eq!(root.width(), None);
+    assert_eq!(root.color(), (0, 0, 0, 255));
+
+    // An explicit keyword is an authored color, so the link default yields.
+    let link = compute_style(
+        Some("a"),
+        &declarations(&[("color", "inherit")]),
+        Some(&parent),
+    );
+    assert_eq!(link.color(), (255, 0, 0, 255));
+}
+
 #[test]
 fn legacy_build_style_tree_ignores_invalid_display_for_default_bridge() {
     let dom = html::internal::node_element_from_parts(
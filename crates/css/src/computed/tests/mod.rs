@@ -15,7 +15,7 @@ use crate::{
     resolve_cascade_style_from_rule_inputs, resolve_document_styles, resolve_initial_style,
     values::{
         BorderStyle, Display, Length, LengthPercentage, OutlineStyle, Overflow, Percentage,
-        Position, TextDecorationLine, ZIndex,
+        Position, Resize, TextDecorationLine, ZIndex,
     },
 };
 use html::{Node, internal::Id};
@@ -209,7 +209,7 @@ fn computed_value_normalization_preserves_ad5_representative_boundaries() {
             PropertyId::FontSize,
             "font-size: 20px",
             PropertyComputedValueKind::AbsoluteLength,
-            SpecifiedToComputedConversionRule::AbsoluteLengthToCssPx,
+            SpecifiedToComputedConversionRule::LengthPercentageToCssPxAgainstFontSize,
             ComputedValue::Length(Length::Px(20.0)),
         ),
         (
@@ -286,6 +286,7 @@ fn computed_value_normalization_matches_property_metadata_for_supported_subset()
         (PropertyId::PaddingRight, "padding-right: 1px"),
         (PropertyId::PaddingTop, "padding-top: 1px"),
         (PropertyId::Position, "position: static"),
+        (PropertyId::Resize, "resize: both"),
         (
             PropertyId::TextDecorationLine,
             "text-decoration-line: underline",
@@ -49,6 +49,7 @@ fn computed_style_initial_snapshot_is_total_and_canonical() {
             "  padding-right: 0px\n",
             "  padding-top: 0px\n",
             "  position: static\n",
+            "  resize: none\n",
             "  text-decoration-line: none\n",
             "  width: auto\n",
             "  z-index: auto\n",
@@ -507,6 +508,7 @@ fn computed_style_get_round_trips_all_builder_supported_properties_losslessly()
             PropertyId::Position,
             ComputedValue::Position(Position::Sticky),
         ),
+        (PropertyId::Resize, ComputedValue::Resize(Resize::Vertical)),
         (
             PropertyId::TextDecorationLine,
             ComputedValue::TextDecorationLine(TextDecorationLine::Underline),
@@ -144,3 +144,39 @@ fn template_host_is_styled_while_template_contents_are_excluded() {
         "active style-tree construction must exclude fragment descendants"
     );
 }
+
+#[test]
+fn style_phase_output_zoom_scales_lengths_but_not_percentages() {
+    let stylesheets = vec![stylesheet(
+        "div { font-size: 10px; margin-left: 4px; border-top-width: 2px; border-top-style: solid; width: 50%; } \
+         span { width: 30px; outline-width: 1px; outline-style: solid; }",
+    )];
+    let dom = element(
+        "div",
+        Vec::new(),
+        vec![element("span", Vec::new(), Vec::new())],
+    );
+
+    let styled = build_style_tree_with_stylesheets(&dom, &stylesheets).expect("styled document");
+    let zoomed = StylePhaseOutput::new(styled).with_zoom(2.0);
+    let root = zoomed.root();
+
+    assert_eq!(root.style.font_size(), Length::Px(20.0));
+    assert_eq!(root.style.box_metrics().margin_left, 8.0);
+    assert_eq!(root.style.box_metrics().border_top, 4.0);
+    assert_eq!(root.style.border_edges().top.width, 4.0);
+    assert_eq!(
+        root.style.width(),
+        Some(LengthPercentage::Percentage(
+            Percentage::from_fraction(0.5).expect("valid percentage")
+        ))
+    );
+
+    let span = &root.children[0].style;
+    assert_eq!(span.font_size(), Length::Px(20.0));
+    assert_eq!(
+        span.width(),
+        Some(LengthPercentage::Length(Length::Px(60.0)))
+    );
+    assert_eq!(span.outline().width, 2.0);
+}
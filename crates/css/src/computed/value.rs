@@ -1,8 +1,8 @@
 use crate::{
-    InitialStyleValue, PropertyComputedValueKind, PropertyId,
+    InitialStyleValue, MediaEnvironment, PropertyComputedValueKind, PropertyId,
     specified::{SpecifiedPropertyValue, SpecifiedValue},
     values::{
-        BorderStyle, Display, Length, LengthPercentage, OutlineStyle, Overflow, Position,
+        BorderStyle, Display, Length, LengthPercentage, OutlineStyle, Overflow, Position, Resize,
         TextDecorationLine, ZIndex,
     },
 };
@@ -11,8 +11,9 @@ use super::{
     format::{display_keyword, format_length},
     normalize::{
         normalize_border_style, normalize_color, normalize_display, normalize_length,
-        normalize_length_percentage_or_auto, normalize_length_percentage_or_none,
-        normalize_outline_style, normalize_text_decoration_line, normalize_z_index,
+        normalize_length_percentage_against_font_size, normalize_length_percentage_or_auto,
+        normalize_length_percentage_or_none, normalize_outline_style,
+        normalize_text_decoration_line, normalize_z_index,
     },
 };
 
@@ -26,6 +27,7 @@ pub enum ComputedValue {
     Display(Display),
     Overflow(Overflow),
     Position(Position),
+    Resize(Resize),
     ZIndex(ZIndex),
     Length(Length),
     LengthPercentageOrAuto(Option<LengthPercentage>),
@@ -42,6 +44,7 @@ impl ComputedValue {
             Self::Display(_) => ComputedValueDiscriminant::Display,
             Self::Overflow(_) => ComputedValueDiscriminant::Overflow,
             Self::Position(_) => ComputedValueDiscriminant::Position,
+            Self::Resize(_) => ComputedValueDiscriminant::Resize,
             Self::ZIndex(_) => ComputedValueDiscriminant::ZIndex,
             Self::Length(_) => ComputedValueDiscriminant::Length,
             Self::LengthPercentageOrAuto(_) => ComputedValueDiscriminant::LengthPercentageOrAuto,
@@ -56,15 +59,16 @@ impl ComputedValue {
             InitialStyleValue::TextDecorationLineNone => {
                 Self::TextDecorationLine(TextDecorationLine::None)
             }
-            InitialStyleValue::ColorBlack => Self::Color((0, 0, 0, 255)),
+            InitialStyleValue::ColorBlack => Self::Color(INITIAL_COLOR),
             InitialStyleValue::TransparentColor => Self::Color((0, 0, 0, 0)),
             InitialStyleValue::DisplayInline => Self::Display(Display::Inline),
-            InitialStyleValue::FontSizePx16 => Self::Length(Length::Px(16.0)),
+            InitialStyleValue::FontSizePx16 => Self::Length(Length::Px(INITIAL_FONT_SIZE_PX)),
             InitialStyleValue::ZeroPx => Self::Length(Length::Px(0.0)),
             InitialStyleValue::AutoKeyword => Self::LengthPercentageOrAuto(None),
             InitialStyleValue::NoneKeyword => Self::LengthPercentageOrNone(None),
             InitialStyleValue::OverflowVisible => Self::Overflow(Overflow::Visible),
             InitialStyleValue::PositionStatic => Self::Position(Position::Static),
+            InitialStyleValue::ResizeNone => Self::Resize(Resize::None),
             InitialStyleValue::ZIndexAuto => Self::ZIndex(ZIndex::Auto),
         }
     }
@@ -74,9 +78,19 @@ impl ComputedValue {
     ///
     /// This performs canonical value conversion only. It does not apply
     /// inheritance, initial/default fallback, layout-dependent resolution, or
-    /// UA/HTML bridge defaults.
+    /// UA/HTML bridge defaults. Relative lengths and `currentColor` resolve
+    /// against `ComputedValueContext::default()`.
     pub fn from_specified(
         specified: &SpecifiedPropertyValue,
+    ) -> Result<Self, ComputedValueNormalizationError> {
+        Self::from_specified_in_context(specified, &ComputedValueContext::default())
+    }
+
+    /// Like [`Self::from_specified`], resolving `em`, `rem`, `vw` and `vh`
+    /// lengths and `currentColor` against `context`.
+    pub fn from_specified_in_context(
+        specified: &SpecifiedPropertyValue,
+        context: &ComputedValueContext,
     ) -> Result<Self, ComputedValueNormalizationError> {
         let property = specified.property();
         let value = match specified.value() {
@@ -89,7 +103,7 @@ impl ComputedValue {
             SpecifiedValue::TextDecorationLine(text_decoration_line) => Self::TextDecorationLine(
                 normalize_text_decoration_line(text_decoration_line.keyword()),
             ),
-            SpecifiedValue::Color(color) => Self::Color(normalize_color(color)),
+            SpecifiedValue::Color(color) => Self::Color(normalize_color(color, context)),
             SpecifiedValue::Display(display) => Self::Display(normalize_display(display.keyword())),
             SpecifiedValue::Overflow(overflow) => {
                 Self::Overflow(normalize_overflow(overflow.keyword()))
@@ -97,14 +111,20 @@ impl ComputedValue {
             SpecifiedValue::Position(position) => {
                 Self::Position(normalize_position(position.keyword()))
             }
+            SpecifiedValue::Resize(resize) => Self::Resize(normalize_resize(resize.keyword())),
             SpecifiedValue::ZIndex(z_index) => Self::ZIndex(normalize_z_index(z_index.value())),
-            SpecifiedValue::Length(length) => Self::Length(normalize_length(property, length)?),
-            SpecifiedValue::LengthPercentageOrAuto(value) => {
-                Self::LengthPercentageOrAuto(normalize_length_percentage_or_auto(property, value)?)
-            }
-            SpecifiedValue::LengthPercentageOrNone(value) => {
-                Self::LengthPercentageOrNone(normalize_length_percentage_or_none(property, value)?)
+            SpecifiedValue::Length(length) => {
+                Self::Length(normalize_length(property, length, context)?)
             }
+            SpecifiedValue::LengthPercentage(value) => Self::Length(
+                normalize_length_percentage_against_font_size(property, value, context)?,
+            ),
+            SpecifiedValue::LengthPercentageOrAuto(value) => Self::LengthPercentageOrAuto(
+                normalize_length_percentage_or_auto(property, value, context)?,
+            ),
+            SpecifiedValue::LengthPercentageOrNone(value) => Self::LengthPercentageOrNone(
+                normalize_length_percentage_or_none(property, value, context)?,
+            ),
         };
 
         let expected = property.metadata().computed_value;
@@ -132,6 +152,7 @@ impl ComputedValue {
             Self::Display(display) => display_keyword(display).to_string(),
             Self::Overflow(overflow) => overflow_keyword(overflow).to_string(),
             Self::Position(position) => position_keyword(position).to_string(),
+            Self::Resize(resize) => resize_keyword(resize).to_string(),
             Self::ZIndex(z_index) => z_index_debug_label(z_index),
             Self::Length(length) => format_length(length),
             Self::LengthPercentageOrAuto(Some(value)) => format_length_percentage(value),
@@ -142,6 +163,50 @@ impl ComputedValue {
     }
 }
 
+/// Element state that relative values resolve against during computed-value
+/// normalization. Sizes are in CSS px.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ComputedValueContext {
+    /// Font size `em` resolves against.
+    pub font_size: f32,
+    /// Root element font size `rem` resolves against.
+    pub root_font_size: f32,
+    /// Viewport width `vw` resolves against.
+    pub viewport_width: f32,
+    /// Viewport height `vh` resolves against.
+    pub viewport_height: f32,
+    /// Color `currentColor` resolves against.
+    pub current_color: (u8, u8, u8, u8),
+}
+
+impl ComputedValueContext {
+    /// Initial font sizes and color with the viewport of `media`.
+    pub fn for_media(media: &MediaEnvironment) -> Self {
+        Self {
+            viewport_width: media.viewport_width,
+            viewport_height: media.viewport_height,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for ComputedValueContext {
+    /// Initial `font-size` for both font sizes, initial `color`, and the
+    /// empty viewport of `MediaEnvironment::default()`.
+    fn default() -> Self {
+        Self {
+            font_size: INITIAL_FONT_SIZE_PX,
+            root_font_size: INITIAL_FONT_SIZE_PX,
+            viewport_width: 0.0,
+            viewport_height: 0.0,
+            current_color: INITIAL_COLOR,
+        }
+    }
+}
+
+pub(super) const INITIAL_FONT_SIZE_PX: f32 = 16.0;
+const INITIAL_COLOR: (u8, u8, u8, u8) = (0, 0, 0, 255);
+
 /// Error returned when a parsed specified value cannot be normalized into the
 /// computed-value contract for its property.
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -205,6 +270,7 @@ pub enum ComputedValueDiscriminant {
     Display,
     Overflow,
     Position,
+    Resize,
     ZIndex,
     Length,
     LengthPercentageOrAuto,
@@ -221,6 +287,7 @@ impl ComputedValueDiscriminant {
             Self::Display => "display",
             Self::Overflow => "overflow",
             Self::Position => "position",
+            Self::Resize => "resize",
             Self::ZIndex => "z-index",
             Self::Length => "length",
             Self::LengthPercentageOrAuto => "length-percentage-or-auto",
@@ -248,6 +315,7 @@ pub(super) fn computed_value_discriminant(
         PropertyComputedValueKind::DisplayKeyword => ComputedValueDiscriminant::Display,
         PropertyComputedValueKind::OverflowKeyword => ComputedValueDiscriminant::Overflow,
         PropertyComputedValueKind::PositionKeyword => ComputedValueDiscriminant::Position,
+        PropertyComputedValueKind::ResizeKeyword => ComputedValueDiscriminant::Resize,
         PropertyComputedValueKind::ZIndex => ComputedValueDiscriminant::ZIndex,
         PropertyComputedValueKind::AbsoluteLength => ComputedValueDiscriminant::Length,
         PropertyComputedValueKind::LengthPercentageOrAuto => {
@@ -320,6 +388,24 @@ fn position_keyword(position: Position) -> &'static str {
     }
 }
 
+fn normalize_resize(keyword: crate::SpecifiedResizeKeyword) -> Resize {
+    match keyword {
+        crate::SpecifiedResizeKeyword::None => Resize::None,
+        crate::SpecifiedResizeKeyword::Both => Resize::Both,
+        crate::SpecifiedResizeKeyword::Horizontal => Resize::Horizontal,
+        crate::SpecifiedResizeKeyword::Vertical => Resize::Vertical,
+    }
+}
+
+fn resize_keyword(resize: Resize) -> &'static str {
+    match resize {
+        Resize::None => "none",
+        Resize::Both => "both",
+        Resize::Horizontal => "horizontal",
+        Resize::Vertical => "vertical",
+    }
+}
+
 fn z_index_debug_label(z_index: ZIndex) -> String {
     match z_index {
         ZIndex::Auto => "auto".to_string(),
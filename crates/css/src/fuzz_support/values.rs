@@ -12,6 +12,8 @@ const DISPLAY_VALUES: &[&str] = &[
 
 const OVERFLOW_VALUES: &[&str] = &["visible", "hidden", "clip", "scroll", "auto", "overlay"];
 
+const RESIZE_VALUES: &[&str] = &["none", "both", "horizontal", "vertical", "block"];
+
 const POSITION_VALUES: &[&str] = &[
     "static", "relative", "absolute", "fixed", "sticky", "center",
 ];
@@ -30,8 +32,9 @@ const COLOR_VALUES: &[&str] = &[
     "#112233",
     "#00ff00",
     "transparent",
+    "hsl(120 50% 25% / 0.5)",
     "#12",
-    "rgb(1,2,3)",
+    "lab(1 2 3)",
 ];
 
 pub(crate) fn synthesized_supported_stylesheet_suite(bytes: &[u8], raw_css: &str) -> Vec<String> {
@@ -45,13 +48,13 @@ pub(crate) fn synthesized_supported_stylesheet_suite(bytes: &[u8], raw_css: &str
              section > span.label {{ background-color: {}; padding-left: {}; }}\n\
              section + aside.note {{ max-width: {}; margin-top: {}; }}\n\
              [data-kind=\"promo\"] {{ min-width: {}; }}",
-            cursor.choose_str(&COLOR_VALUES[..4]),
+            cursor.choose_str(&COLOR_VALUES[..5]),
             supported_absolute_length_value(&mut cursor, false),
             supported_auto_length_value(&mut cursor),
             cursor.choose_str(&DISPLAY_VALUES[..5]),
             cursor.choose_str(&OVERFLOW_VALUES[..5]),
             cursor.choose_str(&POSITION_VALUES[..5]),
-            cursor.choose_str(&COLOR_VALUES[..4]),
+            cursor.choose_str(&COLOR_VALUES[..5]),
             supported_absolute_length_value(&mut cursor, false),
             supported_none_length_value(&mut cursor),
             supported_absolute_length_value(&mut cursor, true),
@@ -112,9 +115,9 @@ fn synthesized_value_for_property(
         }
         PropertySpecifiedValueKind::Color => {
             if valid_bias {
-                cursor.choose_str(&COLOR_VALUES[..4]).to_string()
+                cursor.choose_str(&COLOR_VALUES[..5]).to_string()
             } else {
-                cursor.choose_str(&COLOR_VALUES[3..]).to_string()
+                cursor.choose_str(&COLOR_VALUES[4..]).to_string()
             }
         }
         PropertySpecifiedValueKind::DisplayKeyword => {
@@ -138,6 +141,13 @@ fn synthesized_value_for_property(
                 cursor.choose_str(&POSITION_VALUES[5..]).to_string()
             }
         }
+        PropertySpecifiedValueKind::ResizeKeyword => {
+            if valid_bias {
+                cursor.choose_str(&RESIZE_VALUES[..4]).to_string()
+            } else {
+                cursor.choose_str(&RESIZE_VALUES[4..]).to_string()
+            }
+        }
         PropertySpecifiedValueKind::ZIndex => {
             if valid_bias {
                 cursor
@@ -153,6 +163,10 @@ fn synthesized_value_for_property(
             cursor,
             property.metadata().length_sign == PropertyLengthSignPolicy::AllowNegative,
         ),
+        PropertySpecifiedValueKind::LengthPercentage => length_percentage_value(
+            cursor,
+            property.metadata().length_sign == PropertyLengthSignPolicy::AllowNegative,
+        ),
         PropertySpecifiedValueKind::LengthPercentageOrAuto => {
             if valid_bias && cursor.next_bool() {
                 "auto".to_string()
@@ -163,7 +177,7 @@ fn synthesized_value_for_property(
                 )
             } else {
                 cursor
-                    .choose_str(&["1em", "1e39px", "-1px", "bogus"])
+                    .choose_str(&["1ex", "1e39px", "-1px", "bogus"])
                     .to_string()
             }
         }
@@ -177,7 +191,7 @@ fn synthesized_value_for_property(
                 )
             } else {
                 cursor
-                    .choose_str(&["1em", "1e39px", "-1px", "bogus"])
+                    .choose_str(&["1ex", "1e39px", "-1px", "bogus"])
                     .to_string()
             }
         }
@@ -204,7 +218,7 @@ fn absolute_length_value(cursor: &mut ByteCursor<'_>, allow_negative: bool) -> S
         0 => format!("{magnitude}px"),
         1 if allow_negative => format!("-{magnitude}px"),
         1 => "0".to_string(),
-        2 => "1em".to_string(),
+        2 => "1ex".to_string(),
         _ => "1e39px".to_string(),
     }
 }
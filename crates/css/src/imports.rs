@@ -0,0 +1,196 @@
+//! `@import` discovery on stylesheet text.
+//!
+//! Like `@media`, imports are resolved outside the model: the loader lists the
+//! `@import` rules at the head of a sheet, fetches what they reference and
+//! cascades each imported sheet ahead of the rules of the sheet importing it.
+//! Only the rules before the first other rule count; an `@import` after a
+//! style rule is invalid and ignored, as it is in other browsers.
+
+use crate::syntax::{CssInput, CssToken, CssTokenKind, tokenize_str};
+
+/// One `@import` rule at the head of a stylesheet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StylesheetImport {
+    /// The imported URL as written, unresolved.
+    pub href: String,
+    /// Media query list after the URL, or empty when the import is
+    /// unconditional.
+    pub media: String,
+}
+
+/// The `@import` rules of `css`, in source order.
+pub fn stylesheet_imports(css: &str) -> Vec<StylesheetImport> {
+    let mut imports = Vec::new();
+    if !css
+        .as_bytes()
+        .windows(b"@import".len())
+        .any(|window| window.eq_ignore_ascii_case(b"@import"))
+    {
+        return imports;
+    }
+    let tokenization = tokenize_str(css);
+    let input = &tokenization.input;
+    let mut tokens = tokenization.tokens.as_slice();
+    loop {
+        tokens = skip_insignificant(tokens);
+        let [first, rest @ ..] = tokens else {
+            break;
+        };
+        let CssTokenKind::AtKeyword(name) = &first.kind else {
+            break;
+        };
+        let Some(name) = name.resolve(input) else {
+            break;
+        };
+        let Some(end) = statement_end(rest) else {
+            // A block ends the import prelude; nothing after it can import.
+            break;
+        };
+        if name.eq_ignore_ascii_case("import") {
+            if let Some(import) = parse_import(input, &rest[..end]) {
+                imports.push(import);
+            }
+        } else if !name.eq_ignore_ascii_case("charset") && !name.eq_ignore_ascii_case("layer") {
+            break;
+        }
+        tokens = rest.get(end + 1..).unwrap_or_default();
+    }
+    imports
+}
+
+fn skip_insignificant(tokens: &[CssToken]) -> &[CssToken] {
+    let start = tokens
+        .iter()
+        .position(|token| {
+            !matches!(
+                token.kind,
+                CssTokenKind::Whitespace
+                    | CssTokenKind::Comment(_)
+                    | CssTokenKind::Cdo
+                    | CssTokenKind::Cdc
+            )
+        })
+        .unwrap_or(tokens.len());
+    &tokens[start..]
+}
+
+/// Index of the `;` ending a statement at-rule, or the end of the input for
+/// one left unterminated. `None` when the rule has a block.
+fn statement_end(tokens: &[CssToken]) -> Option<usize> {
+    for (index, token) in tokens.iter().enumerate() {
+        match token.kind {
+            CssTokenKind::Semicolon => return Some(index),
+            CssTokenKind::LeftCurlyBracket => return None,
+            CssTokenKind::Eof => return Some(index),
+            _ => {}
+        }
+    }
+    Some(tokens.len())
+}
+
+/// Read `url(...)`, `url("...")` or `"..."` followed by an optional media
+/// query list.
+fn parse_import(input: &CssInput, prelude: &[CssToken]) -> Option<StylesheetImport> {
+    let prelude = skip_insignificant(prelude);
+    let (href, rest) = match prelude {
+        [
+            CssToken {
+                kind: CssTokenKind::Url(href) | CssTokenKind::String(href),
+                ..
+            },
+            rest @ ..,
+        ] => (href.resolve(input)?, rest),
+        [
+            CssToken {
+                kind: CssTokenKind::Function(function),
+                ..
+            },
+            rest @ ..,
+        ] if function
+            .resolve(input)
+            .is_some_and(|function| function.eq_ignore_ascii_case("url")) =>
+        {
+            let [
+                CssToken {
+                    kind: CssTokenKind::String(href),
+                    ..
+                },
+                rest @ ..,
+            ] = skip_insignificant(rest)
+            else {
+                return None;
+            };
+            let [
+                CssToken {
+                    kind: CssTokenKind::RightParenthesis,
+                    ..
+                },
+                rest @ ..,
+            ] = skip_insignificant(rest)
+            else {
+                return None;
+            };
+            (href.resolve(input)?, rest)
+        }
+        _ => return None,
+    };
+    let media = match (rest.first(), rest.last()) {
+        (Some(first), Some(last)) => input.as_str().get(first.span.start..last.span.end)?.trim(),
+        _ => "",
+    };
+    Some(StylesheetImport {
+        href: href.into_owned(),
+        media: media.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{StylesheetImport, stylesheet_imports};
+
+    fn import(href: &str, media: &str) -> StylesheetImport {
+        StylesheetImport {
+            href: href.to_string(),
+            media: media.to_string(),
+        }
+    }
+
+    #[test]
+    fn imports_at_the_head_of_the_sheet_are_listed_in_order() {
+        let css = concat!(
+            "@charset \"utf-8\";\n",
+            "<!-- @import url(base.css);\n",
+            "/* theme */ @IMPORT \"theme.css\" screen and (prefers-color-scheme: dark);\n",
+            "@layer reset;\n",
+            "@import url( 'print.css' ) print;\n",
+            "@import 42;\n",
+            "p { color: red; }\n",
+            "@import url(late.css);\n",
+        );
+        assert_eq!(
+            stylesheet_imports(css),
+            [
+                import("base.css", ""),
+                import("theme.css", "screen and (prefers-color-scheme: dark)"),
+                import("print.css", "print"),
+            ]
+        );
+    }
+
+    #[test]
+    fn imports_stop_at_the_first_block() {
+        assert_eq!(
+            stylesheet_imports("@media print { } @import url(a.css);"),
+            []
+        );
+        assert_eq!(
+            stylesheet_imports("@import url(a.css) { } @import url(b.css);"),
+            []
+        );
+        assert_eq!(
+            stylesheet_imports("@import url(a.css)"),
+            [import("a.css", "")]
+        );
+        assert_eq!(stylesheet_imports("p { color: red; }"), []);
+    }
+}
@@ -15,6 +15,8 @@ mod fuzz_support;
 
 pub mod cascade;
 pub mod computed;
+pub mod imports;
+pub mod media;
 pub mod model;
 pub mod properties;
 pub mod selectors;
@@ -62,17 +64,21 @@ pub use computed::{
     ComputedDocumentStyleInvalidationImpact, ComputedDocumentStyleWithStats, ComputedElementStyle,
     ComputedStyleBuildError, ComputedStyleBuilder, ComputedStyleEntry,
     ComputedStyleInvalidationImpact, ComputedStyleResolutionError, ComputedStyleReuseStats,
-    ComputedValue, ComputedValueDiscriminant, ComputedValueNormalizationError,
-    ComputedValueNormalizationErrorKind, IncrementalComputedDocumentStyle, StylePhaseOutput,
-    build_style_tree_from_computed_styles, build_style_tree_with_stylesheets,
-    compute_document_styles, compute_document_styles_from_resolved_styles,
+    ComputedValue, ComputedValueContext, ComputedValueDiscriminant,
+    ComputedValueNormalizationError, ComputedValueNormalizationErrorKind,
+    IncrementalComputedDocumentStyle, StylePhaseOutput, build_style_tree_from_computed_styles,
+    build_style_tree_with_stylesheets, compute_document_styles,
+    compute_document_styles_from_resolved_styles,
     compute_document_styles_from_resolved_styles_with_reuse_stats,
     compute_document_styles_incremental_suffix_from_cascade_inputs_with_limits,
     compute_document_styles_incremental_suffix_with_limits, compute_document_styles_with_limits,
-    compute_style_from_resolved_style, computed_value_debug_snapshot, normalize_specified_value,
+    compute_style_from_resolved_style, compute_style_from_resolved_style_in_environment,
+    computed_value_debug_snapshot, normalize_specified_value,
     property_invalidation_classification_debug_snapshot,
 };
 pub use computed::{ComputedStyle, StyledNode, build_style_tree, compute_style};
+pub use imports::{StylesheetImport, stylesheet_imports};
+pub use media::{ColorScheme, MediaEnvironment, media_query_list_matches, resolve_media_rules};
 pub use model::{
     AtRule, AtRuleBlock, Declaration, DeclarationBlock, DeclarationValue, ImportantAnnotation,
     PreservedBlock, PreservedComponentList, PropertyName, PropertyNameKind, Rule, StyleRule,
@@ -95,12 +101,13 @@ pub use selectors::{
     AncestorElements, AttributeExistsSelector, AttributeMatchSelector, AttributeMatcher,
     AttributeSelector, AttributeValue, ClassSelector, Combinator, CombinedSelector,
     ComplexSelector, CompoundSelector, IdSelector, InvalidSelectorList, InvalidSelectorReason,
-    MatchedSelector, NamedTypeSelector, PreviousSiblingElements, SelectorDomElementId,
-    SelectorDomElementIter, SelectorDomIndex, SelectorIdent, SelectorList,
-    SelectorListMatchBuilder, SelectorListMatchOutcome, SelectorListParseResult, SelectorMatchDom,
-    SelectorMatchability, SelectorMatchingContext, SelectorMatchingLimitError,
-    SelectorMatchingLimits, SelectorNamespaceConstraint, SelectorString, SelectorStructureError,
-    Specificity, SubclassSelector, TypeSelector, UniversalSelector, UnsupportedSelectorFeature,
+    MatchedSelector, NamedTypeSelector, NthIndex, PreviousSiblingElements, PseudoClass,
+    PseudoClassSelector, SelectorDomElementId, SelectorDomElementIter, SelectorDomIndex,
+    SelectorElementStates, SelectorIdent, SelectorList, SelectorListMatchBuilder,
+    SelectorListMatchOutcome, SelectorListParseResult, SelectorMatchDom, SelectorMatchability,
+    SelectorMatchingContext, SelectorMatchingLimitError, SelectorMatchingLimits,
+    SelectorNamespaceConstraint, SelectorString, SelectorStructureError, Specificity,
+    SubclassSelector, TypeSelector, UniversalSelector, UnsupportedSelectorFeature,
     UnsupportedSelectorHandling, UnsupportedSelectorList, parse_selector_list,
     parse_selector_list_with_limits, serialize_selector_list_for_snapshot,
     serialize_selector_parse_result_for_snapshot,
@@ -108,16 +115,17 @@ pub use selectors::{
 pub use specified::{
     ExpandedLonghandDeclaration, ShorthandExpansion, ShorthandExpansionError,
     ShorthandExpansionErrorKind, SpecifiedBorderStyle, SpecifiedBorderStyleKeyword, SpecifiedColor,
-    SpecifiedColorKeyword, SpecifiedColorSyntax, SpecifiedDeclarationValue, SpecifiedDisplay,
-    SpecifiedDisplayKeyword, SpecifiedHexColor, SpecifiedLength, SpecifiedLengthPercentage,
-    SpecifiedLengthPercentageOrAuto, SpecifiedLengthPercentageOrNone, SpecifiedLengthUnit,
-    SpecifiedOutlineStyle, SpecifiedOutlineStyleKeyword, SpecifiedOverflow,
+    SpecifiedColorFunction, SpecifiedColorKeyword, SpecifiedColorSyntax, SpecifiedDeclarationValue,
+    SpecifiedDisplay, SpecifiedDisplayKeyword, SpecifiedHexColor, SpecifiedLength,
+    SpecifiedLengthPercentage, SpecifiedLengthPercentageOrAuto, SpecifiedLengthPercentageOrNone,
+    SpecifiedLengthUnit, SpecifiedOutlineStyle, SpecifiedOutlineStyleKeyword, SpecifiedOverflow,
     SpecifiedOverflowKeyword, SpecifiedPercentage, SpecifiedPosition, SpecifiedPositionKeyword,
-    SpecifiedPropertyValue, SpecifiedTextDecorationLine, SpecifiedTextDecorationLineKeyword,
-    SpecifiedValue, SpecifiedValueLimits, SpecifiedValueParseError, SpecifiedValueParseErrorKind,
-    SpecifiedZIndex, SpecifiedZIndexValue, expand_shorthand_declaration,
-    parse_specified_declaration_value, parse_specified_declaration_value_with_limits,
-    parse_specified_value, parse_specified_value_with_limits, shorthand_expansion_debug_snapshot,
+    SpecifiedPropertyValue, SpecifiedResize, SpecifiedResizeKeyword, SpecifiedTextDecorationLine,
+    SpecifiedTextDecorationLineKeyword, SpecifiedValue, SpecifiedValueLimits,
+    SpecifiedValueParseError, SpecifiedValueParseErrorKind, SpecifiedZIndex, SpecifiedZIndexValue,
+    expand_shorthand_declaration, parse_specified_declaration_value,
+    parse_specified_declaration_value_with_limits, parse_specified_value,
+    parse_specified_value_with_limits, shorthand_expansion_debug_snapshot,
 };
 
 // Explicit syntax-layer surface for parser/tokenizer work and syntax tests.
@@ -161,9 +169,10 @@ pub use syntax::Declaration as CompatDeclaration;
 pub use syntax::serialize_compat_stylesheet_for_snapshot;
 
 pub use values::{
-    BorderStyle, CssColorKeyword, CssColorSyntax, CssColorValue, CssFunctionValue, CssHexColor,
-    CssIntegerValue, CssKeywordValue, CssLengthPercentageValue, CssLengthUnit, CssLengthValue,
-    CssNumberScalar, CssNumberValue, CssPercentageValue, CssStringValue, CssUrlValue,
-    CssWideKeyword, CssWideKeywordValue, Display, Length, LengthPercentage, OutlineStyle, Overflow,
-    Percentage, Position, TextDecorationLine, ZIndex, parse_color, parse_length,
+    BorderStyle, CssColorFunction, CssColorFunctionName, CssColorKeyword, CssColorSyntax,
+    CssColorValue, CssFunctionValue, CssHexColor, CssIntegerValue, CssKeywordValue,
+    CssLengthPercentageValue, CssLengthUnit, CssLengthValue, CssNamedColor, CssNumberScalar,
+    CssNumberValue, CssPercentageValue, CssStringValue, CssUrlValue, CssWideKeyword,
+    CssWideKeywordValue, Display, Length, LengthPercentage, OutlineStyle, Overflow, Percentage,
+    Position, Resize, TextDecorationLine, ZIndex, parse_color, parse_length,
 };
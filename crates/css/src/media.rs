@@ -0,0 +1,633 @@
+//! `@media` evaluation against the host environment.
+//!
+//! The model keeps at-rules uninterpreted, so media rules are resolved on the
+//! stylesheet text before it is parsed: a top-level `@media` block whose query
+//! list matches the [`MediaEnvironment`] is unwrapped in place and every other
+//! `@media` block is dropped. Media types, `width`, `height` (with their
+//! `min-`/`max-` prefixes and the range syntax), `orientation` and
+//! `prefers-color-scheme` are understood; a query using any other feature
+//! never matches.
+
+use crate::syntax::{CssInput, CssToken, CssTokenKind, CssTokenText, tokenize_str};
+use std::borrow::Cow;
+
+/// Colour scheme the user prefers, as reported by the operating system.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ColorScheme {
+    #[default]
+    Light,
+    Dark,
+}
+
+/// Host state that media queries are evaluated against.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MediaEnvironment {
+    pub color_scheme: ColorScheme,
+    /// Width of the viewport in CSS pixels.
+    pub viewport_width: f32,
+    /// Height of the viewport in CSS pixels.
+    pub viewport_height: f32,
+}
+
+impl MediaEnvironment {
+    pub fn with_color_scheme(color_scheme: ColorScheme) -> Self {
+        Self {
+            color_scheme,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_viewport_size(self, width: f32, height: f32) -> Self {
+        Self {
+            viewport_width: width,
+            viewport_height: height,
+            ..self
+        }
+    }
+}
+
+/// Font size `em` and `rem` resolve against in media queries: the initial
+/// value, since no element is involved.
+const MEDIA_FONT_SIZE_PX: f32 = 16.0;
+
+/// Rewrite `css` with its top-level `@media` rules resolved for `env`.
+///
+/// Returns the input unchanged when it has no `@media` rules.
+pub fn resolve_media_rules<'a>(css: &'a str, env: &MediaEnvironment) -> Cow<'a, str> {
+    if !contains_ignore_ascii_case(css, "@media") {
+        return Cow::Borrowed(css);
+    }
+    let tokenization = tokenize_str(css);
+    if tokenization.stats.hit_limit {
+        return Cow::Borrowed(css);
+    }
+    let mut tokens = tokenization.tokens.as_slice();
+    if let [rest @ .., last] = tokens
+        && last.kind == CssTokenKind::Eof
+    {
+        tokens = rest;
+    }
+
+    let mut out = String::with_capacity(css.len());
+    resolve_block(&tokenization.input, tokens, 0, css.len(), env, &mut out);
+    Cow::Owned(out)
+}
+
+/// Whether a comma-separated media query list matches `env`.
+///
+/// An empty list matches, as it does on `@media {}`.
+pub fn media_query_list_matches(query_list: &str, env: &MediaEnvironment) -> bool {
+    let tokenization = tokenize_str(query_list);
+    query_list_matches(&tokenization.input, &tokenization.tokens, env)
+}
+
+/// Copy the bytes `start..end` of the input to `out`, resolving the `@media`
+/// rules among `tokens` at brace depth zero.
+fn resolve_block(
+    input: &CssInput,
+    tokens: &[CssToken],
+    start: usize,
+    end: usize,
+    env: &MediaEnvironment,
+    out: &mut String,
+) {
+    let css = input.as_str();
+    let mut copied = start;
+    let mut depth = 0usize;
+    let mut index = 0;
+    while index < tokens.len() {
+        let token = &tokens[index];
+        match &token.kind {
+            CssTokenKind::LeftCurlyBracket => depth += 1,
+            CssTokenKind::RightCurlyBracket => depth = depth.saturating_sub(1),
+            CssTokenKind::AtKeyword(name)
+                if depth == 0
+                    && name
+                        .resolve(input)
+                        .is_some_and(|name| name.eq_ignore_ascii_case("media")) =>
+            {
+                let rule = MediaRule::find(tokens, index, end);
+                out.push_str(&css[copied..token.span.start]);
+                if let Some(body) = rule.body
+                    && query_list_matches(input, &tokens[index + 1..rule.prelude_end], env)
+                {
+                    let open = &tokens[rule.prelude_end];
+                    resolve_block(
+                        input,
+                        &tokens[rule.prelude_end + 1..body.close],
+                        open.span.end,
+                        body.content_end,
+                        env,
+                        out,
+                    );
+                }
+                copied = rule.end;
+                index = rule.next;
+                continue;
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+    out.push_str(&css[copied..end]);
+}
+
+/// Token layout of one `@media` rule.
+struct MediaRule {
+    /// Index of the `{` or `;` ending the prelude, or the token count.
+    prelude_end: usize,
+    body: Option<MediaBody>,
+    /// Index of the first token after the rule.
+    next: usize,
+    /// Byte offset just past the rule.
+    end: usize,
+}
+
+struct MediaBody {
+    /// Index of the closing `}`, or the token count if it is missing.
+    close: usize,
+    /// Byte offset where the block contents end.
+    content_end: usize,
+}
+
+impl MediaRule {
+    /// Locate the rule whose at-keyword is `tokens[at]`; `end` is the byte
+    /// offset where unterminated rules stop.
+    fn find(tokens: &[CssToken], at: usize, end: usize) -> Self {
+        let mut nesting = 0usize;
+        let mut index = at + 1;
+        while index < tokens.len() {
+            match tokens[index].kind {
+                CssTokenKind::Function(_)
+                | CssTokenKind::LeftParenthesis
+                | CssTokenKind::LeftSquareBracket => nesting += 1,
+                CssTokenKind::RightParenthesis | CssTokenKind::RightSquareBracket => {
+                    nesting = nesting.saturating_sub(1);
+                }
+                CssTokenKind::Semicolon if nesting == 0 => {
+                    // `@media screen;` has no block and is dropped whole.
+                    return Self {
+                        prelude_end: index,
+                        body: None,
+                        next: index + 1,
+                        end: tokens[index].span.end,
+                    };
+                }
+                CssTokenKind::LeftCurlyBracket if nesting == 0 => {
+                    return Self::with_body(tokens, index, end);
+                }
+                _ => {}
+            }
+            index += 1;
+        }
+        Self {
+            prelude_end: tokens.len(),
+            body: None,
+            next: tokens.len(),
+            end,
+        }
+    }
+
+    fn with_body(tokens: &[CssToken], open: usize, end: usize) -> Self {
+        let mut depth = 0usize;
+        for (index, token) in tokens.iter().enumerate().skip(open + 1) {
+            match token.kind {
+                CssTokenKind::LeftCurlyBracket => depth += 1,
+                CssTokenKind::RightCurlyBracket if depth == 0 => {
+                    return Self {
+                        prelude_end: open,
+                        body: Some(MediaBody {
+                            close: index,
+                            content_end: token.span.start,
+                        }),
+                        next: index + 1,
+                        end: token.span.end,
+                    };
+                }
+                CssTokenKind::RightCurlyBracket => depth -= 1,
+                _ => {}
+            }
+        }
+        Self {
+            prelude_end: open,
+            body: Some(MediaBody {
+                close: tokens.len(),
+                content_end: end,
+            }),
+            next: tokens.len(),
+            end,
+        }
+    }
+}
+
+/// Prelude token with whitespace and comments already skipped.
+#[derive(Debug, PartialEq)]
+enum Term<'a> {
+    Ident(Cow<'a, str>),
+    /// A length, in CSS pixels.
+    Length(f32),
+    Delim(char),
+    Colon,
+    Open,
+    Close,
+    Comma,
+    Other,
+}
+
+fn query_list_matches(input: &CssInput, tokens: &[CssToken], env: &MediaEnvironment) -> bool {
+    let terms: Vec<Term<'_>> = tokens
+        .iter()
+        .filter_map(|token| match &token.kind {
+            CssTokenKind::Whitespace | CssTokenKind::Comment(_) | CssTokenKind::Eof => None,
+            CssTokenKind::Ident(text) => Some(text.resolve(input).map_or(Term::Other, Term::Ident)),
+            CssTokenKind::Number(number) => Some(
+                resolve_number(input, &number.repr)
+                    .filter(|&value| value == 0.0)
+                    .map_or(Term::Other, Term::Length),
+            ),
+            CssTokenKind::Dimension(dimension) => Some(
+                length_px(input, &dimension.number.repr, &dimension.unit)
+                    .map_or(Term::Other, Term::Length),
+            ),
+            CssTokenKind::Delim(delim) => Some(Term::Delim(*delim)),
+            CssTokenKind::Colon => Some(Term::Colon),
+            CssTokenKind::LeftParenthesis => Some(Term::Open),
+            CssTokenKind::RightParenthesis => Some(Term::Close),
+            CssTokenKind::Comma => Some(Term::Comma),
+            _ => Some(Term::Other),
+        })
+        .collect();
+    if terms.is_empty() {
+        return true;
+    }
+    terms
+        .split(|term| *term == Term::Comma)
+        .any(|query| query_matches(query, env).unwrap_or(false))
+}
+
+/// Evaluate one media query; `None` means it is malformed or uses something
+/// unsupported, which never matches.
+fn query_matches(query: &[Term<'_>], env: &MediaEnvironment) -> Option<bool> {
+    let mut rest = query;
+    let mut negated = false;
+    let mut requires_type = false;
+    if let [Term::Ident(word), tail @ ..] = rest
+        && (word.eq_ignore_ascii_case("not") || word.eq_ignore_ascii_case("only"))
+    {
+        negated = word.eq_ignore_ascii_case("not");
+        // `only` must be followed by a media type; `not` may negate a bare
+        // condition.
+        requires_type = !negated;
+        rest = tail;
+    }
+
+    let mut matches = true;
+    if let [Term::Ident(media_type), tail @ ..] = rest {
+        matches = media_type_matches(media_type)?;
+        rest = tail;
+        if let [Term::Ident(and), tail @ ..] = rest
+            && and.eq_ignore_ascii_case("and")
+            && !tail.is_empty()
+        {
+            rest = tail;
+        } else if !rest.is_empty() {
+            return None;
+        }
+    } else if requires_type {
+        return None;
+    }
+
+    while !rest.is_empty() {
+        let [Term::Open, tail @ ..] = rest else {
+            return None;
+        };
+        let close = tail.iter().position(|term| *term == Term::Close)?;
+        matches &= feature_matches(&tail[..close], env)?;
+        let tail = &tail[close + 1..];
+        rest = match tail {
+            [] => tail,
+            [Term::Ident(and), tail @ ..]
+                if and.eq_ignore_ascii_case("and") && !tail.is_empty() =>
+            {
+                tail
+            }
+            _ => return None,
+        };
+    }
+    Some(matches != negated)
+}
+
+fn media_type_matches(media_type: &str) -> Option<bool> {
+    const RESERVED: [&str; 5] = ["not", "only", "and", "or", "layer"];
+    if RESERVED
+        .iter()
+        .any(|word| media_type.eq_ignore_ascii_case(word))
+    {
+        return None;
+    }
+    Some(media_type.eq_ignore_ascii_case("all") || media_type.eq_ignore_ascii_case("screen"))
+}
+
+/// Evaluate the terms between the parentheses of one media feature.
+fn feature_matches(feature: &[Term<'_>], env: &MediaEnvironment) -> Option<bool> {
+    match feature {
+        [Term::Ident(name)] => boolean_feature_matches(name, env),
+        [Term::Ident(name), Term::Colon, value] => plain_feature_matches(name, value, env),
+        _ => range_feature_matches(feature, env),
+    }
+}
+
+/// `(name)`: whether the feature has a value other than zero or `none`.
+fn boolean_feature_matches(name: &str, env: &MediaEnvironment) -> Option<bool> {
+    if let Some(size) = viewport_dimension(name, env) {
+        return Some(size != 0.0);
+    }
+    // Some preference and some orientation always apply.
+    (name.eq_ignore_ascii_case("prefers-color-scheme") || name.eq_ignore_ascii_case("orientation"))
+        .then_some(true)
+}
+
+/// `(name: value)`, including the `min-` and `max-` forms of dimensions.
+fn plain_feature_matches(name: &str, value: &Term<'_>, env: &MediaEnvironment) -> Option<bool> {
+    if name.eq_ignore_ascii_case("prefers-color-scheme") {
+        let Term::Ident(value) = value else {
+            return None;
+        };
+        let wanted = if value.eq_ignore_ascii_case("light") {
+            ColorScheme::Light
+        } else if value.eq_ignore_ascii_case("dark") {
+            ColorScheme::Dark
+        } else {
+            return None;
+        };
+        return Some(env.color_scheme == wanted);
+    }
+    if name.eq_ignore_ascii_case("orientation") {
+        let Term::Ident(value) = value else {
+            return None;
+        };
+        // A square viewport counts as portrait.
+        let portrait = env.viewport_height >= env.viewport_width;
+        return if value.eq_ignore_ascii_case("portrait") {
+            Some(portrait)
+        } else if value.eq_ignore_ascii_case("landscape") {
+            Some(!portrait)
+        } else {
+            None
+        };
+    }
+
+    let Term::Length(wanted) = *value else {
+        return None;
+    };
+    let (comparison, dimension) = match name.get(..4) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("min-") => {
+            (Comparison::GreaterOrEqual, &name[4..])
+        }
+        Some(prefix) if prefix.eq_ignore_ascii_case("max-") => {
+            (Comparison::LessOrEqual, &name[4..])
+        }
+        _ => (Comparison::Equal, name),
+    };
+    Some(comparison.holds(viewport_dimension(dimension, env)?, wanted))
+}
+
+/// `(width >= 600px)`, `(600px <= width)` and `(400px < width < 800px)`.
+fn range_feature_matches(feature: &[Term<'_>], env: &MediaEnvironment) -> Option<bool> {
+    match feature {
+        [Term::Ident(name), rest @ ..] => {
+            let (comparison, [Term::Length(value)]) = comparison(rest)? else {
+                return None;
+            };
+            Some(comparison.holds(viewport_dimension(name, env)?, *value))
+        }
+        [Term::Length(low), rest @ ..] => {
+            let (first, rest) = comparison(rest)?;
+            let [Term::Ident(name), rest @ ..] = rest else {
+                return None;
+            };
+            let size = viewport_dimension(name, env)?;
+            if rest.is_empty() {
+                return Some(first.holds(*low, size));
+            }
+            let (second, [Term::Length(high)]) = comparison(rest)? else {
+                return None;
+            };
+            // Both comparisons of a bounded range must point the same way.
+            if first.ascending().is_none() || first.ascending() != second.ascending() {
+                return None;
+            }
+            Some(first.holds(*low, size) && second.holds(size, *high))
+        }
+        _ => None,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Comparison {
+    Less,
+    LessOrEqual,
+    Equal,
+    GreaterOrEqual,
+    Greater,
+}
+
+impl Comparison {
+    fn holds(self, left: f32, right: f32) -> bool {
+        match self {
+            Self::Less => left < right,
+            Self::LessOrEqual => left <= right,
+            Self::Equal => left == right,
+            Self::GreaterOrEqual => left >= right,
+            Self::Greater => left > right,
+        }
+    }
+
+    /// Whether the comparison reads as `<`, as `>`, or neither for `=`.
+    fn ascending(self) -> Option<bool> {
+        match self {
+            Self::Less | Self::LessOrEqual => Some(true),
+            Self::Equal => None,
+            Self::GreaterOrEqual | Self::Greater => Some(false),
+        }
+    }
+}
+
+/// Split a leading `<`, `<=`, `=`, `>=` or `>` off `terms`.
+fn comparison<'t, 'a>(terms: &'t [Term<'a>]) -> Option<(Comparison, &'t [Term<'a>])> {
+    match terms {
+        [Term::Delim('<'), Term::Delim('='), rest @ ..] => Some((Comparison::LessOrEqual, rest)),
+        [Term::Delim('<'), rest @ ..] => Some((Comparison::Less, rest)),
+        [Term::Delim('='), rest @ ..] => Some((Comparison::Equal, rest)),
+        [Term::Delim('>'), Term::Delim('='), rest @ ..] => Some((Comparison::GreaterOrEqual, rest)),
+        [Term::Delim('>'), rest @ ..] => Some((Comparison::Greater, rest)),
+        _ => None,
+    }
+}
+
+fn viewport_dimension(name: &str, env: &MediaEnvironment) -> Option<f32> {
+    if name.eq_ignore_ascii_case("width") {
+        Some(env.viewport_width)
+    } else if name.eq_ignore_ascii_case("height") {
+        Some(env.viewport_height)
+    } else {
+        None
+    }
+}
+
+fn resolve_number(input: &CssInput, repr: &CssTokenText) -> Option<f32> {
+    repr.resolve(input)?
+        .parse::<f32>()
+        .ok()
+        .filter(|value| value.is_finite())
+}
+
+/// A `px`, `em` or `rem` dimension in CSS pixels.
+fn length_px(input: &CssInput, repr: &CssTokenText, unit: &CssTokenText) -> Option<f32> {
+    let value = resolve_number(input, repr)?;
+    let unit = unit.resolve(input)?;
+    if unit.eq_ignore_ascii_case("px") {
+        Some(value)
+    } else if unit.eq_ignore_ascii_case("em") || unit.eq_ignore_ascii_case("rem") {
+        Some(value * MEDIA_FONT_SIZE_PX)
+    } else {
+        None
+    }
+}
+
+fn contains_ignore_ascii_case(haystack: &str, needle: &str) -> bool {
+    haystack
+        .as_bytes()
+        .windows(needle.len())
+        .any(|window| window.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ColorScheme, MediaEnvironment, media_query_list_matches, resolve_media_rules};
+    use std::borrow::Cow;
+
+    const LIGHT: MediaEnvironment = MediaEnvironment {
+        color_scheme: ColorScheme::Light,
+        viewport_width: 0.0,
+        viewport_height: 0.0,
+    };
+    const DARK: MediaEnvironment = MediaEnvironment {
+        color_scheme: ColorScheme::Dark,
+        viewport_width: 0.0,
+        viewport_height: 0.0,
+    };
+
+    #[test]
+    fn color_scheme_queries_follow_the_environment() {
+        for (query, light, dark) in [
+            ("(prefers-color-scheme: dark)", false, true),
+            ("(PREFERS-COLOR-SCHEME: Light)", true, false),
+            ("(prefers-color-scheme)", true, true),
+            ("not all and (prefers-color-scheme: dark)", true, false),
+            ("not (prefers-color-scheme: dark)", true, false),
+            ("only screen and (prefers-color-scheme: dark)", false, true),
+            ("print, (prefers-color-scheme: dark)", false, true),
+            ("screen", true, true),
+            ("", true, true),
+            ("print", false, false),
+            ("(min-width: 600px)", false, false),
+            ("(min-resolution: 2dppx)", false, false),
+            ("not (prefers-color-scheme: sepia)", false, false),
+            ("screen and", false, false),
+        ] {
+            assert_eq!(media_query_list_matches(query, &LIGHT), light, "{query:?}");
+            assert_eq!(media_query_list_matches(query, &DARK), dark, "{query:?}");
+        }
+    }
+
+    #[test]
+    fn viewport_queries_compare_against_the_viewport_size() {
+        let narrow = LIGHT.with_viewport_size(480.0, 800.0);
+        let wide = LIGHT.with_viewport_size(1280.0, 720.0);
+        for (query, on_narrow, on_wide) in [
+            ("(min-width: 600px)", false, true),
+            ("(max-width: 600px)", true, false),
+            ("(MAX-WIDTH: 30em)", true, false),
+            ("(width: 480px)", true, false),
+            ("(min-height: 750px)", true, false),
+            (
+                "screen and (min-width: 40rem) and (max-width: 1280px)",
+                false,
+                true,
+            ),
+            ("(width >= 600px)", false, true),
+            ("(600px > width)", true, false),
+            ("(400px < width <= 480px)", true, false),
+            ("(500px <= width < 1500px)", false, true),
+            ("(height < 720px)", false, false),
+            ("(orientation: portrait)", true, false),
+            ("(orientation: landscape)", false, true),
+            ("(width)", true, true),
+            ("(orientation)", true, true),
+            ("not (min-width: 600px)", true, false),
+            ("(max-width: 600px), (min-height: 700px)", true, true),
+            // Malformed or unsupported forms never match.
+            ("(min-width: 600)", false, false),
+            ("(min-width: 50%)", false, false),
+            ("(min-width >= 600px)", false, false),
+            ("(400px < width > 300px)", false, false),
+            ("(400px = width = 480px)", false, false),
+            ("(orientation: sideways)", false, false),
+        ] {
+            assert_eq!(
+                media_query_list_matches(query, &narrow),
+                on_narrow,
+                "{query:?}"
+            );
+            assert_eq!(media_query_list_matches(query, &wide), on_wide, "{query:?}");
+        }
+        assert!(media_query_list_matches("(max-width: 0)", &LIGHT));
+    }
+
+    #[test]
+    fn matching_media_blocks_are_unwrapped_and_others_dropped() {
+        let css = concat!(
+            "p { color: black; }\n",
+            "@media (prefers-color-scheme: dark) { p { color: white; } ",
+            "@media print { p { color: gray; } } }\n",
+            "@media (prefers-color-scheme: light) { a { color: blue; } }\n",
+            "@supports (display: grid) { @media print { b { x: y; } } }\n",
+            "@media screen;\n",
+        );
+
+        assert_eq!(
+            resolve_media_rules(css, &DARK),
+            concat!(
+                "p { color: black; }\n",
+                " p { color: white; }  \n",
+                "\n",
+                "@supports (display: grid) { @media print { b { x: y; } } }\n",
+                "\n",
+            )
+        );
+        assert_eq!(
+            resolve_media_rules(css, &LIGHT),
+            concat!(
+                "p { color: black; }\n",
+                "\n",
+                " a { color: blue; } \n",
+                "@supports (display: grid) { @media print { b { x: y; } } }\n",
+                "\n",
+            )
+        );
+    }
+
+    #[test]
+    fn stylesheets_without_media_rules_are_borrowed() {
+        let css = "p { color: red; }";
+        assert!(matches!(resolve_media_rules(css, &DARK), Cow::Borrowed(_)));
+
+        let unterminated = "a {} @media (prefers-color-scheme: dark) { a { color: red; }";
+        assert_eq!(
+            resolve_media_rules(unterminated, &DARK),
+            "a {}  a { color: red; }"
+        );
+        assert_eq!(resolve_media_rules(unterminated, &LIGHT), "a {} ");
+    }
+}
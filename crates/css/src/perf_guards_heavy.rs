@@ -1,5 +1,5 @@
 use crate::{
-    ParseOptions, compute_document_styles_from_resolved_styles_with_reuse_stats,
+    MediaEnvironment, ParseOptions, compute_document_styles_from_resolved_styles_with_reuse_stats,
     parse_stylesheet_with_options, perf_fixtures, resolve_document_styles,
 };
 
@@ -25,8 +25,12 @@ fn perf_guard_large_css_style_resolution_remains_bounded_and_reusable() {
 
     let resolved =
         resolve_document_styles(&dom, &sheets).expect("heavy style resolution should work");
-    let computed = compute_document_styles_from_resolved_styles_with_reuse_stats(&dom, &resolved)
-        .expect("heavy computed style materialization should work");
+    let computed = compute_document_styles_from_resolved_styles_with_reuse_stats(
+        &dom,
+        &resolved,
+        &MediaEnvironment::default(),
+    )
+    .expect("heavy computed style materialization should work");
     let entries = perf_fixtures::representative_element_count(HEAVY_BLOCKS);
 
     assert_eq!(resolved.entries().len(), entries);
@@ -32,6 +32,7 @@ pub enum SpecifiedToComputedConversionRule {
     ColorToRgba,
     KeywordToComputedEnum,
     AbsoluteLengthToCssPx,
+    LengthPercentageToCssPxAgainstFontSize,
     LengthPercentageOrAutoPreservingPercentages,
     LengthPercentageOrNonePreservingPercentages,
     ZIndexAutoOrInteger,
@@ -43,6 +44,9 @@ impl SpecifiedToComputedConversionRule {
             Self::ColorToRgba => "color-to-rgba",
             Self::KeywordToComputedEnum => "keyword-to-computed-enum",
             Self::AbsoluteLengthToCssPx => "absolute-length-to-css-px",
+            Self::LengthPercentageToCssPxAgainstFontSize => {
+                "length-percentage-to-css-px-against-font-size"
+            }
             Self::LengthPercentageOrAutoPreservingPercentages => {
                 "length-percentage-or-auto-preserving-percentages"
             }
@@ -158,13 +162,20 @@ fn specified_to_computed_conversion_rule(
         | (
             PropertySpecifiedValueKind::PositionKeyword,
             PropertyComputedValueKind::PositionKeyword,
-        ) => SpecifiedToComputedConversionRule::KeywordToComputedEnum,
+        )
+        | (PropertySpecifiedValueKind::ResizeKeyword, PropertyComputedValueKind::ResizeKeyword) => {
+            SpecifiedToComputedConversionRule::KeywordToComputedEnum
+        }
         (PropertySpecifiedValueKind::ZIndex, PropertyComputedValueKind::ZIndex) => {
             SpecifiedToComputedConversionRule::ZIndexAutoOrInteger
         }
         (PropertySpecifiedValueKind::AbsoluteLength, PropertyComputedValueKind::AbsoluteLength) => {
             SpecifiedToComputedConversionRule::AbsoluteLengthToCssPx
         }
+        (
+            PropertySpecifiedValueKind::LengthPercentage,
+            PropertyComputedValueKind::AbsoluteLength,
+        ) => SpecifiedToComputedConversionRule::LengthPercentageToCssPxAgainstFontSize,
         (
             PropertySpecifiedValueKind::LengthPercentageOrAuto,
             PropertyComputedValueKind::LengthPercentageOrAuto,
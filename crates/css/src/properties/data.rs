@@ -6,7 +6,7 @@ use super::{
     },
 };
 
-pub(super) const PROPERTY_REGISTRATION_DATA: [PropertyRegistration; 35] = [
+pub(super) const PROPERTY_REGISTRATION_DATA: [PropertyRegistration; 36] = [
     PropertyRegistration::new(
         PropertyId::BackgroundColor,
         "background-color",
@@ -162,7 +162,7 @@ pub(super) const PROPERTY_REGISTRATION_DATA: [PropertyRegistration; 35] = [
         "font-size",
         PropertyMetadata::inherited(
             InitialStyleValue::FontSizePx16,
-            PropertySpecifiedValueKind::AbsoluteLength,
+            PropertySpecifiedValueKind::LengthPercentage,
             PropertyComputedValueKind::AbsoluteLength,
             PropertyInvalidationImpact::inherited_text_metrics_layout_paint(),
         ),
@@ -331,6 +331,16 @@ pub(super) const PROPERTY_REGISTRATION_DATA: [PropertyRegistration; 35] = [
             PropertyInvalidationImpact::layout_paint_order_paint(),
         ),
     ),
+    PropertyRegistration::new(
+        PropertyId::Resize,
+        "resize",
+        PropertyMetadata::not_inherited(
+            InitialStyleValue::ResizeNone,
+            PropertySpecifiedValueKind::ResizeKeyword,
+            PropertyComputedValueKind::ResizeKeyword,
+            PropertyInvalidationImpact::paint_only(),
+        ),
+    ),
     PropertyRegistration::new(
         PropertyId::TextDecorationLine,
         "text-decoration-line",
@@ -363,7 +373,7 @@ pub(super) const PROPERTY_REGISTRATION_DATA: [PropertyRegistration; 35] = [
     ),
 ];
 
-pub(super) const PROPERTY_LOOKUP_BY_NAME: [PropertyNameLookupEntry; 35] = [
+pub(super) const PROPERTY_LOOKUP_BY_NAME: [PropertyNameLookupEntry; 36] = [
     PropertyNameLookupEntry::new("background-color", PropertyId::BackgroundColor),
     PropertyNameLookupEntry::new("border-bottom-color", PropertyId::BorderBottomColor),
     PropertyNameLookupEntry::new("border-bottom-style", PropertyId::BorderBottomStyle),
@@ -396,6 +406,7 @@ pub(super) const PROPERTY_LOOKUP_BY_NAME: [PropertyNameLookupEntry; 35] = [
     PropertyNameLookupEntry::new("padding-right", PropertyId::PaddingRight),
     PropertyNameLookupEntry::new("padding-top", PropertyId::PaddingTop),
     PropertyNameLookupEntry::new("position", PropertyId::Position),
+    PropertyNameLookupEntry::new("resize", PropertyId::Resize),
     PropertyNameLookupEntry::new("text-decoration-line", PropertyId::TextDecorationLine),
     PropertyNameLookupEntry::new("width", PropertyId::Width),
     PropertyNameLookupEntry::new("z-index", PropertyId::ZIndex),
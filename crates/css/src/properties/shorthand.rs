@@ -9,15 +9,53 @@ use std::fmt::Write;
 /// longhands.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Ord, PartialOrd)]
 pub enum ShorthandId {
+    Background,
+    Border,
+    BorderBottom,
+    BorderColor,
+    BorderLeft,
+    BorderRight,
+    BorderStyle,
+    BorderTop,
+    BorderWidth,
+    Font,
+    Margin,
     Outline,
+    Padding,
 }
 
 impl ShorthandId {
-    pub const ALL: [Self; 1] = [Self::Outline];
+    pub const ALL: [Self; 13] = [
+        Self::Background,
+        Self::Border,
+        Self::BorderBottom,
+        Self::BorderColor,
+        Self::BorderLeft,
+        Self::BorderRight,
+        Self::BorderStyle,
+        Self::BorderTop,
+        Self::BorderWidth,
+        Self::Font,
+        Self::Margin,
+        Self::Outline,
+        Self::Padding,
+    ];
 
     pub const fn as_index(self) -> usize {
         match self {
-            Self::Outline => 0,
+            Self::Background => 0,
+            Self::Border => 1,
+            Self::BorderBottom => 2,
+            Self::BorderColor => 3,
+            Self::BorderLeft => 4,
+            Self::BorderRight => 5,
+            Self::BorderStyle => 6,
+            Self::BorderTop => 7,
+            Self::BorderWidth => 8,
+            Self::Font => 9,
+            Self::Margin => 10,
+            Self::Outline => 11,
+            Self::Padding => 12,
         }
     }
 
@@ -160,22 +198,145 @@ pub fn shorthand_registry_debug_snapshot() -> String {
     output
 }
 
+const BACKGROUND_LONGHANDS: [PropertyId; 1] = [PropertyId::BackgroundColor];
+
+const BORDER_LONGHANDS: [PropertyId; 12] = [
+    PropertyId::BorderTopColor,
+    PropertyId::BorderTopStyle,
+    PropertyId::BorderTopWidth,
+    PropertyId::BorderRightColor,
+    PropertyId::BorderRightStyle,
+    PropertyId::BorderRightWidth,
+    PropertyId::BorderBottomColor,
+    PropertyId::BorderBottomStyle,
+    PropertyId::BorderBottomWidth,
+    PropertyId::BorderLeftColor,
+    PropertyId::BorderLeftStyle,
+    PropertyId::BorderLeftWidth,
+];
+
+const BORDER_BOTTOM_LONGHANDS: [PropertyId; 3] = [
+    PropertyId::BorderBottomColor,
+    PropertyId::BorderBottomStyle,
+    PropertyId::BorderBottomWidth,
+];
+
+const BORDER_COLOR_LONGHANDS: [PropertyId; 4] = [
+    PropertyId::BorderTopColor,
+    PropertyId::BorderRightColor,
+    PropertyId::BorderBottomColor,
+    PropertyId::BorderLeftColor,
+];
+
+const BORDER_LEFT_LONGHANDS: [PropertyId; 3] = [
+    PropertyId::BorderLeftColor,
+    PropertyId::BorderLeftStyle,
+    PropertyId::BorderLeftWidth,
+];
+
+const BORDER_RIGHT_LONGHANDS: [PropertyId; 3] = [
+    PropertyId::BorderRightColor,
+    PropertyId::BorderRightStyle,
+    PropertyId::BorderRightWidth,
+];
+
+const BORDER_STYLE_LONGHANDS: [PropertyId; 4] = [
+    PropertyId::BorderTopStyle,
+    PropertyId::BorderRightStyle,
+    PropertyId::BorderBottomStyle,
+    PropertyId::BorderLeftStyle,
+];
+
+const BORDER_TOP_LONGHANDS: [PropertyId; 3] = [
+    PropertyId::BorderTopColor,
+    PropertyId::BorderTopStyle,
+    PropertyId::BorderTopWidth,
+];
+
+const BORDER_WIDTH_LONGHANDS: [PropertyId; 4] = [
+    PropertyId::BorderTopWidth,
+    PropertyId::BorderRightWidth,
+    PropertyId::BorderBottomWidth,
+    PropertyId::BorderLeftWidth,
+];
+
+const FONT_LONGHANDS: [PropertyId; 1] = [PropertyId::FontSize];
+
+const MARGIN_LONGHANDS: [PropertyId; 4] = [
+    PropertyId::MarginTop,
+    PropertyId::MarginRight,
+    PropertyId::MarginBottom,
+    PropertyId::MarginLeft,
+];
+
 const OUTLINE_LONGHANDS: [PropertyId; 3] = [
     PropertyId::OutlineColor,
     PropertyId::OutlineStyle,
     PropertyId::OutlineWidth,
 ];
 
-const SHORTHAND_REGISTRATION_DATA: [ShorthandRegistration; 1] = [ShorthandRegistration::new(
-    ShorthandId::Outline,
-    "outline",
-    &OUTLINE_LONGHANDS,
-)];
+const PADDING_LONGHANDS: [PropertyId; 4] = [
+    PropertyId::PaddingTop,
+    PropertyId::PaddingRight,
+    PropertyId::PaddingBottom,
+    PropertyId::PaddingLeft,
+];
+
+const SHORTHAND_REGISTRATION_DATA: [ShorthandRegistration; 13] = [
+    ShorthandRegistration::new(ShorthandId::Background, "background", &BACKGROUND_LONGHANDS),
+    ShorthandRegistration::new(ShorthandId::Border, "border", &BORDER_LONGHANDS),
+    ShorthandRegistration::new(
+        ShorthandId::BorderBottom,
+        "border-bottom",
+        &BORDER_BOTTOM_LONGHANDS,
+    ),
+    ShorthandRegistration::new(
+        ShorthandId::BorderColor,
+        "border-color",
+        &BORDER_COLOR_LONGHANDS,
+    ),
+    ShorthandRegistration::new(
+        ShorthandId::BorderLeft,
+        "border-left",
+        &BORDER_LEFT_LONGHANDS,
+    ),
+    ShorthandRegistration::new(
+        ShorthandId::BorderRight,
+        "border-right",
+        &BORDER_RIGHT_LONGHANDS,
+    ),
+    ShorthandRegistration::new(
+        ShorthandId::BorderStyle,
+        "border-style",
+        &BORDER_STYLE_LONGHANDS,
+    ),
+    ShorthandRegistration::new(ShorthandId::BorderTop, "border-top", &BORDER_TOP_LONGHANDS),
+    ShorthandRegistration::new(
+        ShorthandId::BorderWidth,
+        "border-width",
+        &BORDER_WIDTH_LONGHANDS,
+    ),
+    ShorthandRegistration::new(ShorthandId::Font, "font", &FONT_LONGHANDS),
+    ShorthandRegistration::new(ShorthandId::Margin, "margin", &MARGIN_LONGHANDS),
+    ShorthandRegistration::new(ShorthandId::Outline, "outline", &OUTLINE_LONGHANDS),
+    ShorthandRegistration::new(ShorthandId::Padding, "padding", &PADDING_LONGHANDS),
+];
 
-const SHORTHAND_LOOKUP_BY_NAME: [ShorthandNameLookupEntry; 1] = [ShorthandNameLookupEntry::new(
-    "outline",
-    ShorthandId::Outline,
-)];
+const SHORTHAND_LOOKUP_BY_NAME: [ShorthandNameLookupEntry; 13] = [
+    ShorthandNameLookupEntry::new("background", ShorthandId::Background),
+    ShorthandNameLookupEntry::new("border", ShorthandId::Border),
+    ShorthandNameLookupEntry::new("border-bottom", ShorthandId::BorderBottom),
+    ShorthandNameLookupEntry::new("border-color", ShorthandId::BorderColor),
+    ShorthandNameLookupEntry::new("border-left", ShorthandId::BorderLeft),
+    ShorthandNameLookupEntry::new("border-right", ShorthandId::BorderRight),
+    ShorthandNameLookupEntry::new("border-style", ShorthandId::BorderStyle),
+    ShorthandNameLookupEntry::new("border-top", ShorthandId::BorderTop),
+    ShorthandNameLookupEntry::new("border-width", ShorthandId::BorderWidth),
+    ShorthandNameLookupEntry::new("font", ShorthandId::Font),
+    ShorthandNameLookupEntry::new("margin", ShorthandId::Margin),
+    ShorthandNameLookupEntry::new("outline", ShorthandId::Outline),
+    ShorthandNameLookupEntry::new("padding", ShorthandId::Padding),
+];
 
 static SHORTHAND_REGISTRY: ShorthandRegistry =
     ShorthandRegistry::new(&SHORTHAND_REGISTRATION_DATA, &SHORTHAND_LOOKUP_BY_NAME);
@@ -170,7 +170,7 @@ fn property_registry_entries_are_total_canonical_and_metadata_backed() {
             PropertyId::FontSize,
             PropertyInheritance::Inherited,
             InitialStyleValue::FontSizePx16,
-            PropertySpecifiedValueKind::AbsoluteLength,
+            PropertySpecifiedValueKind::LengthPercentage,
             PropertyComputedValueKind::AbsoluteLength,
             PropertyLengthSignPolicy::NonNegative,
             PropertyInvalidationImpact::inherited_text_metrics_layout_paint(),
@@ -319,6 +319,15 @@ fn property_registry_entries_are_total_canonical_and_metadata_backed() {
             PropertyLengthSignPolicy::NotLength,
             PropertyInvalidationImpact::layout_paint_order_paint(),
         ),
+        (
+            PropertyId::Resize,
+            PropertyInheritance::NotInherited,
+            InitialStyleValue::ResizeNone,
+            PropertySpecifiedValueKind::ResizeKeyword,
+            PropertyComputedValueKind::ResizeKeyword,
+            PropertyLengthSignPolicy::NotLength,
+            PropertyInvalidationImpact::paint_only(),
+        ),
         (
             PropertyId::TextDecorationLine,
             PropertyInheritance::NotInherited,
@@ -518,6 +527,7 @@ fn property_registry_invalidation_impact_is_explicit_for_every_supported_longhan
         PropertyId::OutlineColor,
         PropertyId::OutlineStyle,
         PropertyId::OutlineWidth,
+        PropertyId::Resize,
         PropertyId::TextDecorationLine,
     ];
     let layout_and_paint = [
@@ -651,7 +661,7 @@ fn property_value_boundaries_classify_representative_conversion_rules() {
     );
     assert_eq!(
         conversion_for(PropertyId::FontSize),
-        SpecifiedToComputedConversionRule::AbsoluteLengthToCssPx
+        SpecifiedToComputedConversionRule::LengthPercentageToCssPxAgainstFontSize
     );
     assert_eq!(
         conversion_for(PropertyId::Width),
@@ -762,17 +772,7 @@ fn unsupported_flex_properties_are_not_registered_for_cascade_or_computed_style(
 #[test]
 fn unsupported_shorthands_are_not_registered_for_cascade_or_computed_style() {
     let registry = property_registry();
-    let unsupported_shorthand_properties = [
-        "background",
-        "border",
-        "border-color",
-        "border-style",
-        "border-width",
-        "font",
-        "margin",
-        "padding",
-        "text-decoration",
-    ];
+    let unsupported_shorthand_properties = ["flex", "inset", "list-style", "text-decoration"];
 
     for name in unsupported_shorthand_properties {
         assert_eq!(PropertyId::from_name(name), None, "{name}");
@@ -809,7 +809,7 @@ fn shorthand_registry_keeps_outline_separate_from_supported_longhands() {
     );
     assert_eq!(PropertyId::from_name("outline"), None);
     assert_eq!(property_registry().lookup("outline"), None);
-    assert_eq!(registry.lookup("border"), None);
+    assert_eq!(registry.lookup("text-decoration"), None);
 }
 
 #[test]
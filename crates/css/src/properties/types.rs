@@ -38,13 +38,14 @@ pub enum PropertyId {
     PaddingRight,
     PaddingTop,
     Position,
+    Resize,
     TextDecorationLine,
     Width,
     ZIndex,
 }
 
 impl PropertyId {
-    pub const ALL: [Self; 35] = [
+    pub const ALL: [Self; 36] = [
         Self::BackgroundColor,
         Self::BorderBottomColor,
         Self::BorderBottomStyle,
@@ -77,6 +78,7 @@ impl PropertyId {
         Self::PaddingRight,
         Self::PaddingTop,
         Self::Position,
+        Self::Resize,
         Self::TextDecorationLine,
         Self::Width,
         Self::ZIndex,
@@ -116,9 +118,10 @@ impl PropertyId {
             Self::PaddingRight => 29,
             Self::PaddingTop => 30,
             Self::Position => 31,
-            Self::TextDecorationLine => 32,
-            Self::Width => 33,
-            Self::ZIndex => 34,
+            Self::Resize => 32,
+            Self::TextDecorationLine => 33,
+            Self::Width => 34,
+            Self::ZIndex => 35,
         }
     }
 
@@ -218,8 +221,10 @@ const fn default_length_sign_policy(
         | PropertySpecifiedValueKind::DisplayKeyword
         | PropertySpecifiedValueKind::OverflowKeyword
         | PropertySpecifiedValueKind::PositionKeyword
+        | PropertySpecifiedValueKind::ResizeKeyword
         | PropertySpecifiedValueKind::ZIndex => PropertyLengthSignPolicy::NotLength,
         PropertySpecifiedValueKind::AbsoluteLength
+        | PropertySpecifiedValueKind::LengthPercentage
         | PropertySpecifiedValueKind::LengthPercentageOrAuto
         | PropertySpecifiedValueKind::LengthPercentageOrNone => {
             PropertyLengthSignPolicy::NonNegative
@@ -257,8 +262,10 @@ pub enum PropertySpecifiedValueKind {
     DisplayKeyword,
     OverflowKeyword,
     PositionKeyword,
+    ResizeKeyword,
     ZIndex,
     AbsoluteLength,
+    LengthPercentage,
     LengthPercentageOrAuto,
     LengthPercentageOrNone,
 }
@@ -273,8 +280,10 @@ impl PropertySpecifiedValueKind {
             Self::DisplayKeyword => "display-keyword",
             Self::OverflowKeyword => "overflow-keyword",
             Self::PositionKeyword => "position-keyword",
+            Self::ResizeKeyword => "resize-keyword",
             Self::ZIndex => "z-index",
             Self::AbsoluteLength => "absolute-length",
+            Self::LengthPercentage => "length-percentage",
             Self::LengthPercentageOrAuto => "length-percentage-or-auto",
             Self::LengthPercentageOrNone => "length-percentage-or-none",
         }
@@ -295,6 +304,7 @@ pub enum PropertyComputedValueKind {
     DisplayKeyword,
     OverflowKeyword,
     PositionKeyword,
+    ResizeKeyword,
     ZIndex,
     AbsoluteLength,
     LengthPercentageOrAuto,
@@ -311,6 +321,7 @@ impl PropertyComputedValueKind {
             Self::DisplayKeyword => "display-keyword",
             Self::OverflowKeyword => "overflow-keyword",
             Self::PositionKeyword => "position-keyword",
+            Self::ResizeKeyword => "resize-keyword",
             Self::ZIndex => "z-index",
             Self::AbsoluteLength => "absolute-length",
             Self::LengthPercentageOrAuto => "length-percentage-or-auto",
@@ -537,6 +548,7 @@ pub enum InitialStyleValue {
     NoneKeyword,
     OverflowVisible,
     PositionStatic,
+    ResizeNone,
     TextDecorationLineNone,
     ZIndexAuto,
 }
@@ -555,6 +567,7 @@ impl InitialStyleValue {
             Self::NoneKeyword => "none",
             Self::OverflowVisible => "visible",
             Self::PositionStatic => "static",
+            Self::ResizeNone => "none",
             Self::TextDecorationLineNone => "none",
             Self::ZIndexAuto => "auto",
         }
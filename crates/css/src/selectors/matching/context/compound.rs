@@ -5,7 +5,7 @@ use super::attributes::{
 use super::dom::SelectorMatchDom;
 use crate::selectors::{
     AttributeMatchSelector, AttributeMatcher, AttributeSelector, ClassSelector, CompoundSelector,
-    IdSelector, SubclassSelector, TypeSelector,
+    IdSelector, PseudoClass, PseudoClassSelector, SubclassSelector, TypeSelector,
 };
 
 impl<D: SelectorMatchDom> SelectorMatchingContext<'_, D> {
@@ -80,6 +80,32 @@ impl<D: SelectorMatchDom> SelectorMatchingContext<'_, D> {
             SubclassSelector::Attribute(selector) => {
                 self.matches_attribute_selector(element, selector)
             }
+            SubclassSelector::PseudoClass(selector) => {
+                self.matches_pseudo_class_selector(element, selector)
+            }
+        }
+    }
+
+    pub fn matches_pseudo_class_selector(
+        &self,
+        element: D::ElementId,
+        selector: &PseudoClassSelector,
+    ) -> bool {
+        match selector.pseudo_class() {
+            PseudoClass::AnyLink => self.is_link(element),
+            PseudoClass::Link => self.is_link(element) && !self.is_visited_link(element),
+            PseudoClass::Visited => self.is_link(element) && self.is_visited_link(element),
+            PseudoClass::Hover => self.is_hovered(element),
+            PseudoClass::Focus => self.is_focused(element),
+            PseudoClass::Active => self.is_active(element),
+            PseudoClass::FirstChild => self.previous_sibling_element(element).is_none(),
+            PseudoClass::LastChild => self.next_sibling_element(element).is_none(),
+            PseudoClass::OnlyChild => {
+                self.previous_sibling_element(element).is_none()
+                    && self.next_sibling_element(element).is_none()
+            }
+            PseudoClass::NthChild(nth) => nth.matches(self.sibling_position(element)),
+            PseudoClass::NthLastChild(nth) => nth.matches(self.sibling_position_from_end(element)),
         }
     }
 
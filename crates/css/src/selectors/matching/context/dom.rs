@@ -7,7 +7,7 @@ use std::hash::Hash;
 ///
 /// The selector engine only relies on:
 /// - element parent traversal
-/// - previous element sibling traversal
+/// - previous and next element sibling traversal
 /// - canonical element names
 /// - deterministic attribute presence/value queries
 ///
@@ -29,6 +29,29 @@ pub trait SelectorMatchDom {
     /// Text/comment/document siblings are skipped.
     fn previous_sibling_element(&self, element: Self::ElementId) -> Option<Self::ElementId>;
 
+    /// Returns the nearest following element sibling of `element`, if any.
+    ///
+    /// Text/comment/document siblings are skipped.
+    fn next_sibling_element(&self, element: Self::ElementId) -> Option<Self::ElementId>;
+
+    /// Returns the 1-based position of `element` among its element siblings,
+    /// for `:nth-child()`.
+    ///
+    /// The default walks the previous-sibling axis; providers that record
+    /// positions should answer directly.
+    fn sibling_position(&self, element: Self::ElementId) -> usize {
+        std::iter::successors(Some(element), |&sibling| {
+            self.previous_sibling_element(sibling)
+        })
+        .count()
+    }
+
+    /// Returns the 1-based position of `element` among its element siblings,
+    /// counting from the last, for `:nth-last-child()`.
+    fn sibling_position_from_end(&self, element: Self::ElementId) -> usize {
+        std::iter::successors(Some(element), |&sibling| self.next_sibling_element(sibling)).count()
+    }
+
     /// Returns the canonical element name exposed to selector matching.
     ///
     /// DOM providers are responsible for exposing a canonical element-name
@@ -74,4 +97,33 @@ pub trait SelectorMatchDom {
         self.attribute_value(element, "class")
             .is_some_and(|value| class_list_contains(value, want))
     }
+
+    /// Returns whether `element` is a link whose destination the user has
+    /// visited.
+    ///
+    /// Visited state is history, not tree data, so DOM providers that have
+    /// none report every link as unvisited.
+    fn is_visited_link(&self, element: Self::ElementId) -> bool {
+        let _ = element;
+        false
+    }
+
+    /// Returns whether the pointer is over `element` or one of its
+    /// descendants. Providers without pointer state report `false`.
+    fn is_hovered(&self, element: Self::ElementId) -> bool {
+        let _ = element;
+        false
+    }
+
+    /// Returns whether `element` has keyboard focus.
+    fn is_focused(&self, element: Self::ElementId) -> bool {
+        let _ = element;
+        false
+    }
+
+    /// Returns whether `element` or one of its descendants is being pressed.
+    fn is_active(&self, element: Self::ElementId) -> bool {
+        let _ = element;
+        false
+    }
 }
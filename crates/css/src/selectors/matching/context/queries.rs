@@ -15,6 +15,18 @@ impl<'a, D: SelectorMatchDom> SelectorMatchingContext<'a, D> {
         self.dom.previous_sibling_element(element)
     }
 
+    pub fn next_sibling_element(&self, element: D::ElementId) -> Option<D::ElementId> {
+        self.dom.next_sibling_element(element)
+    }
+
+    pub fn sibling_position(&self, element: D::ElementId) -> usize {
+        self.dom.sibling_position(element)
+    }
+
+    pub fn sibling_position_from_end(&self, element: D::ElementId) -> usize {
+        self.dom.sibling_position_from_end(element)
+    }
+
     /// Returns nearest-first ancestor elements, excluding `element` itself.
     pub fn ancestor_elements(&self, element: D::ElementId) -> AncestorElements<'a, D> {
         AncestorElements {
@@ -76,4 +88,28 @@ impl<'a, D: SelectorMatchDom> SelectorMatchingContext<'a, D> {
     pub fn element_has_class(&self, element: D::ElementId, want: &str) -> bool {
         self.dom.element_has_class(element, want)
     }
+
+    /// Returns whether `element` is a hyperlink: an HTML `a` or `area` with an
+    /// `href` attribute.
+    pub fn is_link(&self, element: D::ElementId) -> bool {
+        self.element_namespace(element) == html::ElementNamespace::Html
+            && matches!(self.element_name(element), "a" | "area")
+            && self.has_attribute(element, "href")
+    }
+
+    pub fn is_visited_link(&self, element: D::ElementId) -> bool {
+        self.dom.is_visited_link(element)
+    }
+
+    pub fn is_hovered(&self, element: D::ElementId) -> bool {
+        self.dom.is_hovered(element)
+    }
+
+    pub fn is_focused(&self, element: D::ElementId) -> bool {
+        self.dom.is_focused(element)
+    }
+
+    pub fn is_active(&self, element: D::ElementId) -> bool {
+        self.dom.is_active(element)
+    }
 }
@@ -1,4 +1,5 @@
 use super::context::SelectorMatchDom;
+use super::element_states::SelectorElementStates;
 use html::{ElementNamespace, Node, ParserCreatedAttribute, internal::Id};
 use std::fmt::Write;
 
@@ -19,6 +20,11 @@ struct IndexedElement<'a> {
     attributes: &'a [ParserCreatedAttribute],
     parent: Option<SelectorDomElementId>,
     previous_sibling: Option<SelectorDomElementId>,
+    next_sibling: Option<SelectorDomElementId>,
+    /// 1-based position among element siblings, from the first and from the
+    /// last.
+    sibling_position: usize,
+    sibling_position_from_end: usize,
 }
 
 /// Deterministic element-only DOM index built from an owned `html::Node` tree.
@@ -31,6 +37,7 @@ struct IndexedElement<'a> {
 ///   children into the surrounding traversal frame
 pub struct SelectorDomIndex<'a> {
     elements: Vec<IndexedElement<'a>>,
+    element_states: Option<&'a SelectorElementStates>,
 }
 
 impl<'a> SelectorDomIndex<'a> {
@@ -60,6 +67,9 @@ impl<'a> SelectorDomIndex<'a> {
                     attributes,
                     parent: None,
                     previous_sibling: None,
+                    next_sibling: None,
+                    sibling_position: 0,
+                    sibling_position_from_end: 0,
                 });
                 stack.push(ChildFrame {
                     parent_element: Some(root_id),
@@ -103,6 +113,9 @@ impl<'a> SelectorDomIndex<'a> {
                         attributes,
                         parent: frame.parent_element,
                         previous_sibling: frame.last_child_element,
+                        next_sibling: None,
+                        sibling_position: 0,
+                        sibling_position_from_end: 0,
                     });
                     frame.last_child_element = Some(element_id);
                     push_frame = Some(ChildFrame {
@@ -133,7 +146,18 @@ impl<'a> SelectorDomIndex<'a> {
             }
         }
 
-        Self { elements }
+        link_following_siblings(&mut elements);
+        Self {
+            elements,
+            element_states: None,
+        }
+    }
+
+    /// Answers state pseudo-classes such as `:visited` and `:hover` from
+    /// `states`. Without this every element is in its initial state.
+    pub fn with_element_states(mut self, states: &'a SelectorElementStates) -> Self {
+        self.element_states = Some(states);
+        self
     }
 
     pub fn len(&self) -> usize {
@@ -185,6 +209,18 @@ impl SelectorMatchDom for SelectorDomIndex<'_> {
         self.record(element).previous_sibling
     }
 
+    fn next_sibling_element(&self, element: Self::ElementId) -> Option<Self::ElementId> {
+        self.record(element).next_sibling
+    }
+
+    fn sibling_position(&self, element: Self::ElementId) -> usize {
+        self.record(element).sibling_position
+    }
+
+    fn sibling_position_from_end(&self, element: Self::ElementId) -> usize {
+        self.record(element).sibling_position_from_end
+    }
+
     fn element_name(&self, element: Self::ElementId) -> &str {
         self.record(element).name
     }
@@ -218,6 +254,26 @@ impl SelectorMatchDom for SelectorDomIndex<'_> {
             })
             .map(ParserCreatedAttribute::value)
     }
+
+    fn is_visited_link(&self, element: Self::ElementId) -> bool {
+        self.element_states
+            .is_some_and(|states| states.is_visited_link(self.record(element).node_id))
+    }
+
+    fn is_hovered(&self, element: Self::ElementId) -> bool {
+        self.element_states
+            .is_some_and(|states| states.is_hovered(self.record(element).node_id))
+    }
+
+    fn is_focused(&self, element: Self::ElementId) -> bool {
+        self.element_states
+            .is_some_and(|states| states.is_focused(self.record(element).node_id))
+    }
+
+    fn is_active(&self, element: Self::ElementId) -> bool {
+        self.element_states
+            .is_some_and(|states| states.is_active(self.record(element).node_id))
+    }
 }
 
 /// Document-order iterator over [`SelectorDomElementId`] values.
@@ -282,6 +338,28 @@ pub(crate) fn write_selector_dom_snapshot_body(
     }
 }
 
+/// Fill in the following-sibling links and sibling positions from the
+/// previous-sibling links. A previous sibling always precedes its sibling in
+/// document order, and a next sibling always follows it.
+fn link_following_siblings(elements: &mut [IndexedElement<'_>]) {
+    let slot = |id: SelectorDomElementId| usize::try_from(id.0 - 1).expect("element index");
+    for index in 0..elements.len() {
+        let previous = elements[index].previous_sibling.map(slot);
+        elements[index].sibling_position =
+            previous.map_or(1, |previous| elements[previous].sibling_position + 1);
+        if let Some(previous) = previous {
+            elements[previous].next_sibling = Some(SelectorDomElementId(
+                (index + 1).try_into().expect("element id"),
+            ));
+        }
+    }
+    for index in (0..elements.len()).rev() {
+        elements[index].sibling_position_from_end = elements[index]
+            .next_sibling
+            .map_or(1, |next| elements[slot(next)].sibling_position_from_end + 1);
+    }
+}
+
 struct ChildFrame<'a> {
     parent_element: Option<SelectorDomElementId>,
     children: &'a [Node],
@@ -0,0 +1,69 @@
+use html::internal::Id;
+use std::collections::HashSet;
+
+/// Element state that pseudo-classes depend on but the DOM tree does not
+/// record, keyed by DOM node id.
+///
+/// Whether a link's destination has been visited comes from the embedder's
+/// history; hover, focus and press state come from its input handling. The
+/// default value reports every element in its initial state, so every link
+/// matches `:link` and nothing matches `:visited`, `:hover`, `:focus` or
+/// `:active`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SelectorElementStates {
+    visited_links: HashSet<Id>,
+    hovered: HashSet<Id>,
+    active: HashSet<Id>,
+    focused: Option<Id>,
+}
+
+impl SelectorElementStates {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that the link element `node` points at a visited URL.
+    pub fn insert_visited_link(&mut self, node: Id) {
+        self.visited_links.insert(node);
+    }
+
+    pub fn is_visited_link(&self, node: Id) -> bool {
+        self.visited_links.contains(&node)
+    }
+
+    /// Records that `node` matches `:hover`. The embedder inserts the element
+    /// under the pointer and each of its ancestors.
+    pub fn insert_hovered(&mut self, node: Id) {
+        self.hovered.insert(node);
+    }
+
+    pub fn is_hovered(&self, node: Id) -> bool {
+        self.hovered.contains(&node)
+    }
+
+    /// Records that `node` matches `:active`. The embedder inserts the
+    /// pressed element and each of its ancestors.
+    pub fn insert_active(&mut self, node: Id) {
+        self.active.insert(node);
+    }
+
+    pub fn is_active(&self, node: Id) -> bool {
+        self.active.contains(&node)
+    }
+
+    /// Sets the element that matches `:focus`.
+    pub fn set_focused(&mut self, node: Option<Id>) {
+        self.focused = node;
+    }
+
+    pub fn is_focused(&self, node: Id) -> bool {
+        self.focused == Some(node)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.visited_links.is_empty()
+            && self.hovered.is_empty()
+            && self.active.is_empty()
+            && self.focused.is_none()
+    }
+}
@@ -81,6 +81,21 @@ fn selector_dom_index_previous_sibling_skips_non_elements() {
     assert_eq!(index.previous_sibling_element(ids[1]), None);
     assert_eq!(index.previous_sibling_element(ids[2]), Some(ids[1]));
     assert_eq!(index.previous_sibling_element(ids[3]), Some(ids[2]));
+
+    assert_eq!(index.next_sibling_element(ids[0]), None);
+    assert_eq!(index.next_sibling_element(ids[1]), Some(ids[2]));
+    assert_eq!(index.next_sibling_element(ids[2]), Some(ids[3]));
+    assert_eq!(index.next_sibling_element(ids[3]), None);
+    assert_eq!(
+        ids[1..]
+            .iter()
+            .map(|&id| (
+                index.sibling_position(id),
+                index.sibling_position_from_end(id)
+            ))
+            .collect::<Vec<_>>(),
+        [(1, 3), (2, 2), (3, 1)]
+    );
 }
 
 #[test]
@@ -1,11 +1,14 @@
 use super::super::{
-    MatchedSelector, SelectorDomIndex, SelectorMatchability, SelectorMatchingContext,
+    MatchedSelector, SelectorDomIndex, SelectorElementStates, SelectorMatchability,
+    SelectorMatchingContext,
 };
 use super::support::{
-    attribute_exists_selector, attribute_match_selector, class_selector, doc, element, id_selector,
-    ident_value, named_type_selector, parse_selector_result, parsed_single_selector, string_value,
-    universal_type_selector,
+    attribute_exists_selector, attribute_match_selector, class_selector, comment, doc, element,
+    id_selector, ident_value, named_type_selector, parse_selector_result, parsed_single_selector,
+    string_value, text, universal_type_selector,
 };
+use html::internal::Id;
+
 use crate::selectors::{
     AttributeMatcher, InvalidSelectorReason, Specificity, SubclassSelector,
     UnsupportedSelectorFeature,
@@ -231,3 +234,160 @@ fn matching_context_match_selector_list_preserves_non_matchable_parse_states() {
     );
     assert!(!invalid_outcome.matched_any());
 }
+
+#[test]
+fn matching_context_matches_link_pseudo_classes_against_element_states() {
+    let link = |id: u32, name: &str, href: Option<&str>| {
+        let attributes = std::iter::once(("id", name))
+            .chain(href.map(|href| ("href", href)))
+            .map(|(name, value)| html::internal::unqualified_attribute(name, value))
+            .collect();
+        let tag = if name == "map" { "area" } else { "a" };
+        html::internal::node_element_from_parts(
+            Id(id),
+            html::internal::expanded_name(html::ElementNamespace::Html, tag),
+            attributes,
+            Vec::new(),
+            Vec::new(),
+        )
+    };
+    let dom = doc(vec![
+        link(1, "seen", Some("/a")),
+        link(2, "fresh", Some("/b")),
+        link(3, "anchor", None),
+        link(4, "map", Some("/a")),
+        element(
+            "div",
+            vec![("id", Some("div")), ("href", Some("/a"))],
+            Vec::new(),
+        ),
+    ]);
+    let mut states = SelectorElementStates::new();
+    for id in [1, 4, 0] {
+        states.insert_visited_link(Id(id));
+    }
+
+    let matching = |states: Option<&SelectorElementStates>, selector: &str| {
+        let index = SelectorDomIndex::from_root(&dom);
+        let index = match states {
+            Some(states) => index.with_element_states(states),
+            None => index,
+        };
+        let context = SelectorMatchingContext::new(&index);
+        let selector = parsed_single_selector(selector);
+        index
+            .elements()
+            .filter(|&element| context.matches_compound_selector(element, selector.head()))
+            .filter_map(|element| context.attribute_value(element, "id").map(str::to_string))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        matching(Some(&states), ":any-link"),
+        ["seen", "fresh", "map"]
+    );
+    assert_eq!(matching(Some(&states), ":link"), ["fresh"]);
+    assert_eq!(matching(Some(&states), ":visited"), ["seen", "map"]);
+    assert_eq!(matching(None, ":link"), ["seen", "fresh", "map"]);
+    assert!(matching(None, ":visited").is_empty());
+}
+
+#[test]
+fn matching_context_matches_interaction_pseudo_classes_against_element_states() {
+    let node = |id: u32, name: &str, children: Vec<html::Node>| {
+        html::internal::node_element_from_parts(
+            Id(id),
+            html::internal::expanded_name(html::ElementNamespace::Html, name),
+            vec![html::internal::unqualified_attribute("id", name)],
+            Vec::new(),
+            children,
+        )
+    };
+    let dom = doc(vec![node(
+        1,
+        "main",
+        vec![
+            node(2, "button", vec![node(3, "span", Vec::new())]),
+            node(4, "input", Vec::new()),
+        ],
+    )]);
+    let mut states = SelectorElementStates::new();
+    for id in [1, 2, 3] {
+        states.insert_hovered(Id(id));
+    }
+    for id in [1, 2] {
+        states.insert_active(Id(id));
+    }
+    states.set_focused(Some(Id(4)));
+
+    let matching = |states: Option<&SelectorElementStates>, selector: &str| {
+        let index = SelectorDomIndex::from_root(&dom);
+        let index = match states {
+            Some(states) => index.with_element_states(states),
+            None => index,
+        };
+        let context = SelectorMatchingContext::new(&index);
+        let selector = parsed_single_selector(selector);
+        index
+            .elements()
+            .filter(|&element| context.matches_compound_selector(element, selector.head()))
+            .filter_map(|element| context.attribute_value(element, "id").map(str::to_string))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        matching(Some(&states), ":hover"),
+        ["main", "button", "span"]
+    );
+    assert_eq!(matching(Some(&states), ":active"), ["main", "button"]);
+    assert_eq!(matching(Some(&states), ":focus"), ["input"]);
+    assert_eq!(matching(Some(&states), "button:hover:active"), ["button"]);
+    assert!(!states.is_empty());
+    for pseudo_class in [":hover", ":focus", ":active"] {
+        assert!(matching(None, pseudo_class).is_empty());
+    }
+}
+
+#[test]
+fn matching_context_matches_structural_pseudo_classes_by_element_position() {
+    let item = |id: &str| element("li", vec![("id", Some(id))], Vec::new());
+    let dom = doc(vec![element(
+        "ul",
+        vec![("id", Some("list"))],
+        vec![
+            text("gap"),
+            item("a"),
+            comment("skipped"),
+            item("b"),
+            item("c"),
+            text("gap"),
+            item("d"),
+            item("e"),
+            element(
+                "ol",
+                vec![("id", Some("nested"))],
+                vec![element("li", vec![("id", Some("only"))], Vec::new())],
+            ),
+        ],
+    )]);
+    let index = SelectorDomIndex::from_root(&dom);
+    let context = SelectorMatchingContext::new(&index);
+    let matching = |selector: &str| {
+        let selector = parsed_single_selector(selector);
+        index
+            .elements()
+            .filter(|&element| context.matches_compound_selector(element, selector.head()))
+            .filter_map(|element| context.attribute_value(element, "id").map(str::to_string))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(matching("li:first-child"), ["a", "only"]);
+    assert_eq!(matching(":last-child"), ["list", "nested", "only"]);
+    assert_eq!(matching(":only-child"), ["list", "only"]);
+    assert_eq!(matching("li:nth-child(odd)"), ["a", "c", "e", "only"]);
+    assert_eq!(matching("li:nth-child(even)"), ["b", "d"]);
+    assert_eq!(matching("li:nth-child(-n+2)"), ["a", "b", "only"]);
+    assert_eq!(matching("li:nth-child(3)"), ["c"]);
+    assert_eq!(matching("li:nth-last-child(2)"), ["e"]);
+    assert_eq!(matching("li:nth-last-child(n+5)"), ["a", "b"]);
+}
@@ -194,13 +194,13 @@ fn selector_matching_debug_snapshot_is_stable_for_unsupported_selector_cases() {
 
     assert_matching_debug_snapshot(
         dom,
-        ":hover",
+        ":target",
         concat!(
             "version: 1\n",
             "selector-matching\n",
             "selectors:\n",
             "  result: unsupported\n",
-            "  span: @0..7\n",
+            "  span: @0..8\n",
             "  feature[0]: pseudo-class\n",
             "dom:\n",
             "  elements: 1\n",
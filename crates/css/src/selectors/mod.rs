@@ -52,7 +52,8 @@ pub use attribute::{
 };
 pub use complex::{Combinator, CombinedSelector, ComplexSelector, CompoundSelector};
 pub use simple::{
-    ClassSelector, IdSelector, NamedTypeSelector, SubclassSelector, TypeSelector, UniversalSelector,
+    ClassSelector, IdSelector, NamedTypeSelector, NthIndex, PseudoClass, PseudoClassSelector,
+    SubclassSelector, TypeSelector, UniversalSelector,
 };
 pub use specificity::Specificity;
 pub use validation::SelectorStructureError;
@@ -61,9 +62,9 @@ pub use values::{SelectorIdent, SelectorString};
 // Matching contract
 pub use matching::{
     AncestorElements, MatchedSelector, PreviousSiblingElements, SelectorDomElementId,
-    SelectorDomElementIter, SelectorDomIndex, SelectorListMatchBuilder, SelectorListMatchOutcome,
-    SelectorMatchDom, SelectorMatchability, SelectorMatchingContext, SelectorMatchingLimitError,
-    SelectorMatchingLimits, SelectorNamespaceConstraint,
+    SelectorDomElementIter, SelectorDomIndex, SelectorElementStates, SelectorListMatchBuilder,
+    SelectorListMatchOutcome, SelectorMatchDom, SelectorMatchability, SelectorMatchingContext,
+    SelectorMatchingLimitError, SelectorMatchingLimits, SelectorNamespaceConstraint,
 };
 
 pub use parser::{parse_selector_list, parse_selector_list_with_limits};
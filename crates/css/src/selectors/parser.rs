@@ -1,13 +1,14 @@
 pub(super) use super::{
     AttributeExistsSelector, AttributeMatchSelector, AttributeMatcher, AttributeSelector,
     AttributeValue, ClassSelector, Combinator, CombinedSelector, ComplexSelector, CompoundSelector,
-    IdSelector, InvalidSelectorList, InvalidSelectorReason, SelectorIdent, SelectorList,
-    SelectorListParseResult, SelectorString, SelectorStructureError, SubclassSelector,
-    TypeSelector, UnsupportedSelectorFeature, UnsupportedSelectorList,
+    IdSelector, InvalidSelectorList, InvalidSelectorReason, NthIndex, PseudoClass,
+    PseudoClassSelector, SelectorIdent, SelectorList, SelectorListParseResult, SelectorString,
+    SelectorStructureError, SubclassSelector, TypeSelector, UnsupportedSelectorFeature,
+    UnsupportedSelectorList,
 };
 pub(super) use crate::syntax::{
-    CssBlockKind, CssComponentValue, CssHashKind, CssInput, CssSpan, CssToken, CssTokenKind,
-    CssTokenText,
+    CssBlockKind, CssComponentValue, CssFunction, CssHashKind, CssInput, CssSpan, CssToken,
+    CssTokenKind, CssTokenText,
 };
 
 mod attribute;
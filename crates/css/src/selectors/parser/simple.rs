@@ -2,9 +2,9 @@ use super::convert::{map_structure_error, selector_ident_from_text};
 use super::segment::{ParsedSimpleSelector, SegmentParseError, SegmentParser};
 use super::spans::{component_list_span, span_from_bounds};
 use super::{
-    ClassSelector, CssBlockKind, CssComponentValue, CssHashKind, CssInput, CssToken, CssTokenKind,
-    CssTokenText, IdSelector, InvalidSelectorReason, SubclassSelector, TypeSelector,
-    UnsupportedSelectorFeature,
+    ClassSelector, CssBlockKind, CssComponentValue, CssFunction, CssHashKind, CssInput, CssToken,
+    CssTokenKind, CssTokenText, IdSelector, InvalidSelectorReason, NthIndex, PseudoClass,
+    PseudoClassSelector, SubclassSelector, TypeSelector, UnsupportedSelectorFeature,
 };
 
 impl<'a> SegmentParser<'a> {
@@ -187,17 +187,45 @@ impl<'a> SegmentParser<'a> {
 
         let end_span = match self.current_value() {
             Some(CssComponentValue::PreservedToken(CssToken {
-                kind: CssTokenKind::Ident(_),
+                kind: CssTokenKind::Ident(name),
                 span,
             })) => {
                 self.index += 1;
                 if !is_double_colon {
+                    let selector_span =
+                        span_from_bounds(first_colon_span, *span).unwrap_or(first_colon_span);
+                    if let Some(pseudo_class) = name
+                        .resolve(self.input)
+                        .as_deref()
+                        .and_then(PseudoClass::from_name)
+                    {
+                        return Ok(ParsedSimpleSelector::Subclass {
+                            span: selector_span,
+                            selector: SubclassSelector::PseudoClass(PseudoClassSelector::new(
+                                selector_span,
+                                pseudo_class,
+                            )),
+                        });
+                    }
                     features.push(UnsupportedSelectorFeature::PseudoClass);
                 }
                 *span
             }
             Some(CssComponentValue::Function(function)) => {
                 self.index += 1;
+                if !is_double_colon
+                    && let Some(pseudo_class) = nth_pseudo_class(self.input, function)
+                {
+                    let selector_span = span_from_bounds(first_colon_span, function.span)
+                        .unwrap_or(first_colon_span);
+                    return Ok(ParsedSimpleSelector::Subclass {
+                        span: selector_span,
+                        selector: SubclassSelector::PseudoClass(PseudoClassSelector::new(
+                            selector_span,
+                            pseudo_class,
+                        )),
+                    });
+                }
                 if is_double_colon {
                     features.push(UnsupportedSelectorFeature::PseudoElement);
                 } else {
@@ -226,3 +254,78 @@ impl<'a> SegmentParser<'a> {
 fn function_name_is_forgiving_list(input: &CssInput, name: &CssTokenText) -> bool {
     matches!(name.resolve(input).as_deref(), Some("is") | Some("where"))
 }
+
+/// `:nth-child()` or `:nth-last-child()` with a plain `An+B` argument. The
+/// `of S` form is left unsupported.
+fn nth_pseudo_class(input: &CssInput, function: &CssFunction) -> Option<PseudoClass> {
+    let name = function.name.resolve(input)?;
+    let make = if name.eq_ignore_ascii_case("nth-child") {
+        PseudoClass::NthChild
+    } else if name.eq_ignore_ascii_case("nth-last-child") {
+        PseudoClass::NthLastChild
+    } else {
+        return None;
+    };
+
+    let mut text = String::new();
+    for value in &function.value {
+        match value {
+            CssComponentValue::PreservedToken(CssToken {
+                kind: CssTokenKind::Whitespace,
+                ..
+            }) => text.push(' '),
+            CssComponentValue::PreservedToken(CssToken {
+                kind: CssTokenKind::Comment(_),
+                ..
+            }) => {}
+            CssComponentValue::PreservedToken(token) => text.push_str(input.slice(token.span)?),
+            _ => return None,
+        }
+    }
+    parse_nth_index(&text).map(make)
+}
+
+/// Parses the `An+B` microsyntax: `odd`, `even`, an integer, or `A n` with an
+/// optional integer `A` and an optional signed `B`. Whitespace may surround
+/// the sign of `B` but nothing else.
+fn parse_nth_index(text: &str) -> Option<NthIndex> {
+    let text = text
+        .trim_matches(|character: char| matches!(character, ' ' | '\t' | '\n' | '\r' | '\u{c}'))
+        .to_ascii_lowercase();
+    match text.as_str() {
+        "odd" => return Some(NthIndex::new(2, 1)),
+        "even" => return Some(NthIndex::new(2, 0)),
+        _ => {}
+    }
+    let Some(n) = text.find('n') else {
+        return parse_integer(&text).map(|b| NthIndex::new(0, b));
+    };
+    let a = match &text[..n] {
+        "" | "+" => 1,
+        "-" => -1,
+        a => parse_integer(a)?,
+    };
+    let rest = text[n + 1..].trim_start();
+    if rest.is_empty() {
+        return Some(NthIndex::new(a, 0));
+    }
+    let (negative, digits) = match rest.split_at(1) {
+        ("+", digits) => (false, digits.trim_start()),
+        ("-", digits) => (true, digits.trim_start()),
+        _ => return None,
+    };
+    if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    let b: i32 = digits.parse().ok()?;
+    Some(NthIndex::new(a, if negative { -b } else { b }))
+}
+
+/// An optionally signed integer with no whitespace.
+fn parse_integer(text: &str) -> Option<i32> {
+    let digits = text.strip_prefix(['+', '-']).unwrap_or(text);
+    if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
+}
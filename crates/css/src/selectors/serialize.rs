@@ -1,7 +1,7 @@
 use super::{
     AttributeMatcher, AttributeSelector, AttributeValue, Combinator, ComplexSelector,
-    CompoundSelector, InvalidSelectorReason, SelectorList, SelectorListParseResult, Specificity,
-    SubclassSelector, TypeSelector, UnsupportedSelectorFeature,
+    CompoundSelector, InvalidSelectorReason, PseudoClass, SelectorList, SelectorListParseResult,
+    Specificity, SubclassSelector, TypeSelector, UnsupportedSelectorFeature,
 };
 use crate::syntax::CssSpan;
 use std::fmt::Write;
@@ -168,6 +168,15 @@ fn type_selector_snapshot(selector: &TypeSelector) -> String {
     }
 }
 
+fn pseudo_class_snapshot(pseudo_class: PseudoClass) -> String {
+    match pseudo_class {
+        PseudoClass::NthChild(nth) | PseudoClass::NthLastChild(nth) => {
+            format!("{}({}n{:+})", pseudo_class.name(), nth.a(), nth.b())
+        }
+        _ => pseudo_class.name().to_string(),
+    }
+}
+
 fn subclass_selector_snapshot(selector: &SubclassSelector) -> String {
     match selector {
         SubclassSelector::Id(selector) => format!(
@@ -183,6 +192,11 @@ fn subclass_selector_snapshot(selector: &SubclassSelector) -> String {
             span_label(selector.name().span()),
         ),
         SubclassSelector::Attribute(selector) => attribute_selector_snapshot(selector),
+        SubclassSelector::PseudoClass(selector) => format!(
+            "pseudo-class({}) node={}",
+            pseudo_class_snapshot(selector.pseudo_class()),
+            span_label(Some(selector.span())),
+        ),
     }
 }
 
@@ -90,6 +90,7 @@ pub enum SubclassSelector {
     Id(IdSelector),
     Class(ClassSelector),
     Attribute(AttributeSelector),
+    PseudoClass(PseudoClassSelector),
 }
 
 impl SubclassSelector {
@@ -98,6 +99,7 @@ impl SubclassSelector {
             Self::Id(selector) => selector.span(),
             Self::Class(selector) => selector.span(),
             Self::Attribute(selector) => selector.span(),
+            Self::PseudoClass(selector) => selector.span(),
         }
     }
 
@@ -106,6 +108,7 @@ impl SubclassSelector {
             Self::Id(selector) => selector.specificity(),
             Self::Class(selector) => selector.specificity(),
             Self::Attribute(selector) => selector.specificity(),
+            Self::PseudoClass(selector) => selector.specificity(),
         }
     }
 }
@@ -161,3 +164,126 @@ impl ClassSelector {
         Specificity::CLASS
     }
 }
+
+/// Pseudo-classes the matcher understands. Any other pseudo-class keeps the
+/// selector unsupported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PseudoClass {
+    /// `:link`, a link whose destination has not been visited.
+    Link,
+    /// `:visited`
+    Visited,
+    /// `:any-link`, a link in either state.
+    AnyLink,
+    /// `:hover`, the element under the pointer or one of its ancestors.
+    Hover,
+    /// `:focus`, the element that has keyboard focus.
+    Focus,
+    /// `:active`, the element being pressed or one of its ancestors.
+    Active,
+    /// `:first-child`
+    FirstChild,
+    /// `:last-child`
+    LastChild,
+    /// `:only-child`
+    OnlyChild,
+    /// `:nth-child(An+B)`, counting element siblings from the first.
+    NthChild(NthIndex),
+    /// `:nth-last-child(An+B)`, counting element siblings from the last.
+    NthLastChild(NthIndex),
+}
+
+impl PseudoClass {
+    /// Looks up a non-functional pseudo-class by name, ASCII
+    /// case-insensitively.
+    pub fn from_name(name: &str) -> Option<Self> {
+        [
+            Self::Link,
+            Self::Visited,
+            Self::AnyLink,
+            Self::Hover,
+            Self::Focus,
+            Self::Active,
+            Self::FirstChild,
+            Self::LastChild,
+            Self::OnlyChild,
+        ]
+        .into_iter()
+        .find(|pseudo_class| pseudo_class.name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Link => "link",
+            Self::Visited => "visited",
+            Self::AnyLink => "any-link",
+            Self::Hover => "hover",
+            Self::Focus => "focus",
+            Self::Active => "active",
+            Self::FirstChild => "first-child",
+            Self::LastChild => "last-child",
+            Self::OnlyChild => "only-child",
+            Self::NthChild(_) => "nth-child",
+            Self::NthLastChild(_) => "nth-last-child",
+        }
+    }
+}
+
+/// The `An+B` argument of `:nth-child()` and `:nth-last-child()`: the
+/// 1-based positions `A*n + B` for every integer `n >= 0`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NthIndex {
+    a: i32,
+    b: i32,
+}
+
+impl NthIndex {
+    pub const fn new(a: i32, b: i32) -> Self {
+        Self { a, b }
+    }
+
+    pub fn a(self) -> i32 {
+        self.a
+    }
+
+    pub fn b(self) -> i32 {
+        self.b
+    }
+
+    /// Returns whether the 1-based `position` is one of the selected ones.
+    pub fn matches(self, position: usize) -> bool {
+        let Ok(position) = i64::try_from(position) else {
+            return false;
+        };
+        let offset = position - i64::from(self.b);
+        match i64::from(self.a) {
+            0 => offset == 0,
+            a => offset % a == 0 && offset / a >= 0,
+        }
+    }
+}
+
+/// `:visited`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PseudoClassSelector {
+    span: CssSpan,
+    pseudo_class: PseudoClass,
+}
+
+impl PseudoClassSelector {
+    pub fn new(span: CssSpan, pseudo_class: PseudoClass) -> Self {
+        Self { span, pseudo_class }
+    }
+
+    pub fn span(&self) -> CssSpan {
+        self.span
+    }
+
+    pub fn pseudo_class(&self) -> PseudoClass {
+        self.pseudo_class
+    }
+
+    pub fn specificity(&self) -> Specificity {
+        Specificity::CLASS
+    }
+}
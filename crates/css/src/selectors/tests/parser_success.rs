@@ -1,4 +1,4 @@
-use super::super::Combinator;
+use super::super::{Combinator, PseudoClass, SubclassSelector};
 use super::support::{parse_selector_result, parsed_selector_list};
 
 #[test]
@@ -43,3 +43,101 @@ fn parser_distinguishes_comments_from_descendant_whitespace() {
         Combinator::Descendant
     );
 }
+
+#[test]
+fn parser_builds_ir_for_link_pseudo_classes() {
+    let result = parse_selector_result("a:LINK, a:visited:any-link");
+
+    assert_eq!(
+        result.to_debug_snapshot(),
+        concat!(
+            "version: 1\n",
+            "selector-parse\n",
+            "result: parsed\n",
+            "span: @0..27\n",
+            "selector[0] @0..6 specificity=(0,1,1)\n",
+            "  compound[0] @0..6 specificity=(0,1,1)\n",
+            "    - type(\"a\") node=@0..1 name=@0..1\n",
+            "    - pseudo-class(link) node=@1..6\n",
+            "selector[1] @8..26 specificity=(0,2,1)\n",
+            "  compound[0] @8..26 specificity=(0,2,1)\n",
+            "    - type(\"a\") node=@8..9 name=@8..9\n",
+            "    - pseudo-class(visited) node=@9..17\n",
+            "    - pseudo-class(any-link) node=@17..26\n",
+        )
+    );
+}
+
+#[test]
+fn parser_builds_ir_for_interaction_pseudo_classes() {
+    let result = parse_selector_result("a:hover, input:FOCUS:active");
+
+    assert_eq!(
+        result.to_debug_snapshot(),
+        concat!(
+            "version: 1\n",
+            "selector-parse\n",
+            "result: parsed\n",
+            "span: @0..28\n",
+            "selector[0] @0..7 specificity=(0,1,1)\n",
+            "  compound[0] @0..7 specificity=(0,1,1)\n",
+            "    - type(\"a\") node=@0..1 name=@0..1\n",
+            "    - pseudo-class(hover) node=@1..7\n",
+            "selector[1] @9..27 specificity=(0,2,1)\n",
+            "  compound[0] @9..27 specificity=(0,2,1)\n",
+            "    - type(\"input\") node=@9..14 name=@9..14\n",
+            "    - pseudo-class(focus) node=@14..20\n",
+            "    - pseudo-class(active) node=@20..27\n",
+        )
+    );
+}
+
+#[test]
+fn parser_builds_ir_for_structural_pseudo_classes() {
+    let result = parse_selector_result("li:first-child:last-child, tr:NTH-CHILD(2n+1):only-child");
+
+    assert_eq!(
+        result.to_debug_snapshot(),
+        concat!(
+            "version: 1\n",
+            "selector-parse\n",
+            "result: parsed\n",
+            "span: @0..57\n",
+            "selector[0] @0..25 specificity=(0,2,1)\n",
+            "  compound[0] @0..25 specificity=(0,2,1)\n",
+            "    - type(\"li\") node=@0..2 name=@0..2\n",
+            "    - pseudo-class(first-child) node=@2..14\n",
+            "    - pseudo-class(last-child) node=@14..25\n",
+            "selector[1] @27..56 specificity=(0,2,1)\n",
+            "  compound[0] @27..56 specificity=(0,2,1)\n",
+            "    - type(\"tr\") node=@27..29 name=@27..29\n",
+            "    - pseudo-class(nth-child(2n+1)) node=@29..45\n",
+            "    - pseudo-class(only-child) node=@45..56\n",
+        )
+    );
+}
+
+#[test]
+fn parser_reads_nth_arguments_in_every_an_plus_b_form() {
+    let nth = |argument: &str| {
+        let list = parsed_selector_list(&format!(":nth-last-child({argument})"));
+        let [SubclassSelector::PseudoClass(selector)] = list.selectors()[0].head().subclasses()
+        else {
+            panic!("expected one pseudo-class in {argument:?}");
+        };
+        let PseudoClass::NthLastChild(nth) = selector.pseudo_class() else {
+            panic!("expected :nth-last-child in {argument:?}");
+        };
+        (nth.a(), nth.b())
+    };
+
+    assert_eq!(nth("odd"), (2, 1));
+    assert_eq!(nth(" EVEN "), (2, 0));
+    assert_eq!(nth("7"), (0, 7));
+    assert_eq!(nth("-3"), (0, -3));
+    assert_eq!(nth("n"), (1, 0));
+    assert_eq!(nth("-n+3"), (-1, 3));
+    assert_eq!(nth("+n - 2"), (1, -2));
+    assert_eq!(nth("3n /* step */ + 1"), (3, 1));
+    assert_eq!(nth("-2N- 1"), (-2, -1));
+}
@@ -42,7 +42,7 @@ fn parser_reports_unsupported_selector_features_without_string_splitting() {
 
 #[test]
 fn unsupported_selectors_do_not_corrupt_surrounding_selector_parsing() {
-    let pseudo_tail = unsupported_selector("div:hover.class > span");
+    let pseudo_tail = unsupported_selector("div:target.class > span");
     let column = unsupported_selector("div || span");
     let nesting = unsupported_selector("& > main.card");
 
@@ -83,3 +83,22 @@ fn unsupported_feature_aggregation_is_stable_across_selector_lists() {
         )
     );
 }
+
+#[test]
+fn nth_child_arguments_outside_an_plus_b_stay_unsupported() {
+    for selector in [
+        "li:nth-child(2n of .x)",
+        "li:nth-child(2 n)",
+        "li:nth-child(+ 2n)",
+        "li:nth-child(2n1)",
+        "li:nth-child(n + -1)",
+        "li:nth-child()",
+        "li:nth-of-type(2)",
+    ] {
+        assert_eq!(
+            unsupported_selector(selector).features(),
+            &[UnsupportedSelectorFeature::FunctionalPseudoClass],
+            "{selector}"
+        );
+    }
+}
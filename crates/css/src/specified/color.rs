@@ -1,13 +1,16 @@
 use crate::{
-    model::{ValueComponent, ValueToken},
+    model::{ValueComponent, ValueFunction, ValueSymbol, ValueToken},
     properties::PropertyId,
-    values::{CssColorValue, CssHexColor},
+    values::{
+        ColorFunctionArgument, CssColorFunction, CssColorFunctionName, CssColorKeyword,
+        CssColorValue, CssHexColor, angle_to_degrees, color_function_rgba,
+    },
 };
 
 use super::{
-    core::{keyword_value, resolve_text, unsupported_component_error},
+    core::{keyword_value, number_value_from_parts, resolve_text, unsupported_component_error},
     error::{SpecifiedValueParseError, SpecifiedValueParseErrorKind, error},
-    value::{SpecifiedColor, SpecifiedColorKeyword, SpecifiedColorSyntax},
+    value::{SpecifiedColor, SpecifiedColorSyntax},
 };
 
 pub(super) fn parse_color(
@@ -15,7 +18,7 @@ pub(super) fn parse_color(
     component: &ValueComponent,
 ) -> Result<SpecifiedColor, SpecifiedValueParseError> {
     if let Some(keyword) = keyword_value(property, component)? {
-        let Some(keyword_value) = parse_color_keyword(keyword.canonical()) else {
+        let Some(keyword_value) = CssColorKeyword::from_css_keyword(keyword.canonical()) else {
             return Err(error(
                 property,
                 SpecifiedValueParseErrorKind::UnsupportedColorKeyword,
@@ -27,6 +30,15 @@ pub(super) fn parse_color(
         });
     }
 
+    if let ValueComponent::Function(function) = component {
+        return Ok(SpecifiedColor {
+            value: CssColorValue::new(
+                function.span(),
+                SpecifiedColorSyntax::Function(parse_color_function(property, function)?),
+            ),
+        });
+    }
+
     let ValueComponent::Token(token) = component else {
         return Err(unsupported_component_error(property, component));
     };
@@ -47,28 +59,98 @@ pub(super) fn parse_color(
     })
 }
 
-fn parse_color_keyword(keyword: &str) -> Option<SpecifiedColorKeyword> {
-    match keyword {
-        "black" => Some(SpecifiedColorKeyword::Black),
-        "blue" => Some(SpecifiedColorKeyword::Blue),
-        "cyan" => Some(SpecifiedColorKeyword::Cyan),
-        "gray" | "grey" => Some(SpecifiedColorKeyword::Gray),
-        "green" => Some(SpecifiedColorKeyword::Green),
-        "magenta" => Some(SpecifiedColorKeyword::Magenta),
-        "maroon" => Some(SpecifiedColorKeyword::Maroon),
-        "navy" => Some(SpecifiedColorKeyword::Navy),
-        "olive" => Some(SpecifiedColorKeyword::Olive),
-        "purple" => Some(SpecifiedColorKeyword::Purple),
-        "red" => Some(SpecifiedColorKeyword::Red),
-        "silver" => Some(SpecifiedColorKeyword::Silver),
-        "teal" => Some(SpecifiedColorKeyword::Teal),
-        "transparent" => Some(SpecifiedColorKeyword::Transparent),
-        "white" => Some(SpecifiedColorKeyword::White),
-        "yellow" => Some(SpecifiedColorKeyword::Yellow),
-        _ => None,
+/// Parses `rgb()`, `rgba()`, `hsl()` and `hsla()` in both the legacy
+/// comma-separated and the space-separated `r g b / alpha` syntax.
+fn parse_color_function(
+    property: PropertyId,
+    function: &ValueFunction,
+) -> Result<CssColorFunction, SpecifiedValueParseError> {
+    let name = resolve_text(property, &function.name)?;
+    if CssColorFunctionName::from_css_name(name).is_none() {
+        return Err(error(
+            property,
+            SpecifiedValueParseErrorKind::UnsupportedFunction,
+        ));
     }
+    let invalid = || error(property, SpecifiedValueParseErrorKind::InvalidColorFunction);
+
+    let mut arguments = Vec::with_capacity(4);
+    let mut separators = Vec::with_capacity(3);
+    let mut expect_argument = true;
+    for component in &function.components {
+        let ValueComponent::Token(token) = component else {
+            return Err(invalid());
+        };
+        let separator = match token {
+            ValueToken::Whitespace { .. } | ValueToken::Comment { .. } => continue,
+            ValueToken::Symbol {
+                kind: ValueSymbol::Comma,
+                ..
+            } => Some(','),
+            ValueToken::Delim { value: '/', .. } => Some('/'),
+            _ => None,
+        };
+        match separator {
+            Some(_) if expect_argument => return Err(invalid()),
+            Some(separator) => {
+                separators.push(separator);
+                expect_argument = true;
+            }
+            None => {
+                if !expect_argument {
+                    separators.push(' ');
+                }
+                arguments.push(color_function_argument(property, token)?);
+                expect_argument = false;
+            }
+        }
+    }
+
+    let legacy = match separators.as_slice() {
+        [',', ','] | [',', ',', ','] => true,
+        [' ', ' '] | [' ', ' ', '/'] => false,
+        _ => return Err(invalid()),
+    };
+    if expect_argument {
+        return Err(invalid());
+    }
+    let Some((name, rgba)) = color_function_rgba(name, legacy, &arguments) else {
+        return Err(invalid());
+    };
+
+    Ok(CssColorFunction::new(name, rgba))
 }
 
+fn color_function_argument(
+    property: PropertyId,
+    token: &ValueToken,
+) -> Result<ColorFunctionArgument, SpecifiedValueParseError> {
+    match token {
+        ValueToken::Number { span, kind, text } => {
+            let number = number_value_from_parts(property, *span, *kind, text)?;
+            Ok(ColorFunctionArgument::Number(number.numeric_value()))
+        }
+        ValueToken::Percentage { span, kind, text } => {
+            let number = number_value_from_parts(property, *span, *kind, text)?;
+            Ok(ColorFunctionArgument::Percentage(number.numeric_value()))
+        }
+        ValueToken::Dimension {
+            span,
+            kind,
+            number,
+            unit,
+        } => {
+            let number = number_value_from_parts(property, *span, *kind, number)?;
+            angle_to_degrees(number.numeric_value(), resolve_text(property, unit)?)
+                .map(ColorFunctionArgument::Degrees)
+                .ok_or_else(|| error(property, SpecifiedValueParseErrorKind::InvalidColorFunction))
+        }
+        _ => Err(error(
+            property,
+            SpecifiedValueParseErrorKind::InvalidColorFunction,
+        )),
+    }
+}
 fn parse_hex_color_digits(
     property: PropertyId,
     digits: &str,
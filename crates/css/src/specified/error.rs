@@ -47,6 +47,7 @@ pub enum SpecifiedValueParseErrorKind {
     UnresolvedTokenText,
     UnsupportedColorKeyword,
     InvalidHexColor,
+    InvalidColorFunction,
     UnsupportedDisplayKeyword,
     UnsupportedOverflowKeyword,
     UnsupportedPositionKeyword,
@@ -74,6 +75,7 @@ impl SpecifiedValueParseErrorKind {
             Self::UnresolvedTokenText => "unresolved-token-text",
             Self::UnsupportedColorKeyword => "unsupported-color-keyword",
             Self::InvalidHexColor => "invalid-hex-color",
+            Self::InvalidColorFunction => "invalid-color-function",
             Self::UnsupportedDisplayKeyword => "unsupported-display-keyword",
             Self::UnsupportedOverflowKeyword => "unsupported-overflow-keyword",
             Self::UnsupportedPositionKeyword => "unsupported-position-keyword",
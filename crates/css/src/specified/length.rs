@@ -94,16 +94,16 @@ pub(super) fn parse_length(
             let numeric_value = number.numeric_value();
             reject_negative_if_needed(property, numeric_value)?;
 
-            let unit = resolve_text(property, unit)?.to_ascii_lowercase();
-            if unit != "px" {
-                return Err(error(
-                    property,
-                    SpecifiedValueParseErrorKind::UnsupportedLengthUnit,
-                ));
-            }
+            let unit =
+                CssLengthUnit::from_unit_text(resolve_text(property, unit)?).ok_or_else(|| {
+                    error(
+                        property,
+                        SpecifiedValueParseErrorKind::UnsupportedLengthUnit,
+                    )
+                })?;
 
             Ok(SpecifiedLength {
-                value: CssLengthValue::new(number, CssLengthUnit::Px),
+                value: CssLengthValue::new(number, unit),
             })
         }
         ValueToken::Number { span, kind, text } => {
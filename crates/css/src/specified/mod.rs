@@ -17,6 +17,7 @@ mod outline;
 mod overflow;
 mod parse;
 mod position;
+mod resize;
 mod shorthand;
 mod text_decoration;
 mod value;
@@ -33,14 +34,14 @@ pub use shorthand::{
     ShorthandExpansionErrorKind, expand_shorthand_declaration, shorthand_expansion_debug_snapshot,
 };
 pub use value::{
-    SpecifiedBorderStyle, SpecifiedBorderStyleKeyword, SpecifiedColor, SpecifiedColorKeyword,
-    SpecifiedColorSyntax, SpecifiedDeclarationValue, SpecifiedDisplay, SpecifiedDisplayKeyword,
-    SpecifiedHexColor, SpecifiedLength, SpecifiedLengthPercentage, SpecifiedLengthPercentageOrAuto,
-    SpecifiedLengthPercentageOrNone, SpecifiedLengthUnit, SpecifiedOutlineStyle,
-    SpecifiedOutlineStyleKeyword, SpecifiedOverflow, SpecifiedOverflowKeyword, SpecifiedPercentage,
-    SpecifiedPosition, SpecifiedPositionKeyword, SpecifiedPropertyValue,
-    SpecifiedTextDecorationLine, SpecifiedTextDecorationLineKeyword, SpecifiedValue,
-    SpecifiedZIndex, SpecifiedZIndexValue,
+    SpecifiedBorderStyle, SpecifiedBorderStyleKeyword, SpecifiedColor, SpecifiedColorFunction,
+    SpecifiedColorKeyword, SpecifiedColorSyntax, SpecifiedDeclarationValue, SpecifiedDisplay,
+    SpecifiedDisplayKeyword, SpecifiedHexColor, SpecifiedLength, SpecifiedLengthPercentage,
+    SpecifiedLengthPercentageOrAuto, SpecifiedLengthPercentageOrNone, SpecifiedLengthUnit,
+    SpecifiedOutlineStyle, SpecifiedOutlineStyleKeyword, SpecifiedOverflow,
+    SpecifiedOverflowKeyword, SpecifiedPercentage, SpecifiedPosition, SpecifiedPositionKeyword,
+    SpecifiedPropertyValue, SpecifiedResize, SpecifiedResizeKeyword, SpecifiedTextDecorationLine,
+    SpecifiedTextDecorationLineKeyword, SpecifiedValue, SpecifiedZIndex, SpecifiedZIndexValue,
 };
 
 #[cfg(test)]
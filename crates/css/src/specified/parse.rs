@@ -9,10 +9,14 @@ use super::{
     css_wide::parse_supported_css_wide_keyword,
     display::parse_display,
     error::{SpecifiedValueParseError, SpecifiedValueParseErrorKind, error},
-    length::{parse_length, parse_length_percentage_or_auto, parse_length_percentage_or_none},
+    length::{
+        parse_length, parse_length_percentage, parse_length_percentage_or_auto,
+        parse_length_percentage_or_none,
+    },
     outline::parse_outline_style,
     overflow::parse_overflow,
     position::parse_position,
+    resize::parse_resize,
     text_decoration::parse_text_decoration_line,
     value::{SpecifiedDeclarationValue, SpecifiedPropertyValue, SpecifiedValue},
     z_index::parse_z_index,
@@ -97,12 +101,18 @@ fn parse_specified_value_component(
         PropertySpecifiedValueKind::PositionKeyword => {
             SpecifiedValue::Position(parse_position(property, component)?)
         }
+        PropertySpecifiedValueKind::ResizeKeyword => {
+            SpecifiedValue::Resize(parse_resize(property, component)?)
+        }
         PropertySpecifiedValueKind::ZIndex => {
             SpecifiedValue::ZIndex(parse_z_index(property, component)?)
         }
         PropertySpecifiedValueKind::AbsoluteLength => {
             SpecifiedValue::Length(parse_length(property, component)?)
         }
+        PropertySpecifiedValueKind::LengthPercentage => {
+            SpecifiedValue::LengthPercentage(parse_length_percentage(property, component)?)
+        }
         PropertySpecifiedValueKind::LengthPercentageOrAuto => {
             SpecifiedValue::LengthPercentageOrAuto(parse_length_percentage_or_auto(
                 property, component,
@@ -0,0 +1,34 @@
+use crate::{model::ValueComponent, properties::PropertyId};
+
+use super::{
+    core::{keyword_value, unsupported_component_error},
+    error::{SpecifiedValueParseError, SpecifiedValueParseErrorKind, error},
+    value::{SpecifiedResize, SpecifiedResizeKeyword},
+};
+
+pub(super) fn parse_resize(
+    property: PropertyId,
+    component: &ValueComponent,
+) -> Result<SpecifiedResize, SpecifiedValueParseError> {
+    let Some(keyword) = keyword_value(property, component)? else {
+        return Err(unsupported_component_error(property, component));
+    };
+
+    let resize_keyword = match keyword.canonical() {
+        "none" => SpecifiedResizeKeyword::None,
+        "both" => SpecifiedResizeKeyword::Both,
+        "horizontal" => SpecifiedResizeKeyword::Horizontal,
+        "vertical" => SpecifiedResizeKeyword::Vertical,
+        _ => {
+            return Err(error(
+                property,
+                SpecifiedValueParseErrorKind::UnsupportedKeyword,
+            ));
+        }
+    };
+
+    Ok(SpecifiedResize {
+        span: keyword.span(),
+        keyword: resize_keyword,
+    })
+}
@@ -1,6 +1,7 @@
 use crate::{
-    model::{DeclarationValue, ValueComponent, ValueText, ValueToken},
+    model::{DeclarationValue, ValueComponent, ValueSymbol, ValueText, ValueToken},
     properties::{PropertyId, ShorthandId},
+    syntax::CssSpan,
     values::CssWideKeyword,
 };
 use std::fmt::Write;
@@ -11,8 +12,6 @@ use super::{
     parse::{parse_specified_declaration_value, parse_specified_value},
 };
 
-const DEFAULT_EXPANSION_ORDER: u16 = 0;
-
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ShorthandExpansion {
     shorthand: ShorthandId,
@@ -132,9 +131,24 @@ pub fn expand_shorthand_declaration(
     shorthand: ShorthandId,
     value: &DeclarationValue,
 ) -> Result<ShorthandExpansion, ShorthandExpansionError> {
-    match shorthand {
-        ShorthandId::Outline => expand_outline(value),
-    }
+    let components = non_trivia_components(shorthand, value)?;
+    let values = match css_wide_shorthand_value(shorthand, &components)? {
+        Some(css_wide) => vec![css_wide; shorthand.longhands().len()],
+        None => shorthand_longhand_values(shorthand, &components, value.span())?,
+    };
+
+    Ok(ShorthandExpansion::new(
+        shorthand,
+        shorthand
+            .longhands()
+            .iter()
+            .zip(values)
+            .zip(0u16..)
+            .map(|((&property, value), expansion_order)| {
+                ExpandedLonghandDeclaration::new(property, value, expansion_order)
+            })
+            .collect(),
+    ))
 }
 
 /// Deterministic debug snapshot for one supported shorthand expansion attempt.
@@ -218,120 +232,76 @@ fn append_shorthand_expansion_error(output: &mut String, error: &ShorthandExpans
     }
 }
 
-fn expand_outline(value: &DeclarationValue) -> Result<ShorthandExpansion, ShorthandExpansionError> {
-    let components = non_trivia_components(ShorthandId::Outline, value)?;
-    if let Some(css_wide) = css_wide_shorthand_value(&components)? {
-        return Ok(outline_expansion(
-            value,
-            css_wide.clone(),
-            css_wide.clone(),
-            css_wide,
-        ));
-    }
-
-    let mut outline = OutlineExpansionParts::default();
-    for component in components {
-        let slot = classify_outline_component(component)?;
-        outline.record(slot, component_value(component))?;
+/// Split one shorthand value into its longhand values, in the order of
+/// `shorthand.longhands()`.
+fn shorthand_longhand_values(
+    shorthand: ShorthandId,
+    components: &[&ValueComponent],
+    span: CssSpan,
+) -> Result<Vec<DeclarationValue>, ShorthandExpansionError> {
+    let longhands = shorthand.longhands();
+    match shorthand {
+        ShorthandId::Outline
+        | ShorthandId::BorderTop
+        | ShorthandId::BorderRight
+        | ShorthandId::BorderBottom
+        | ShorthandId::BorderLeft => expand_line(shorthand, longhands, components, span),
+        ShorthandId::Border => {
+            // Every side takes the same line, so classify against the top
+            // side's longhands and repeat the result.
+            let line = expand_line(shorthand, &longhands[..3], components, span)?;
+            Ok(line.iter().cycle().take(longhands.len()).cloned().collect())
+        }
+        ShorthandId::BorderColor
+        | ShorthandId::BorderStyle
+        | ShorthandId::BorderWidth
+        | ShorthandId::Margin
+        | ShorthandId::Padding => expand_box(shorthand, longhands, components),
+        ShorthandId::Background => expand_background(components, span),
+        ShorthandId::Font => expand_font(components),
     }
-
-    Ok(outline_expansion(
-        value,
-        outline
-            .color
-            .unwrap_or_else(|| initial_reset_value(value.span())),
-        outline
-            .style
-            .unwrap_or_else(|| initial_reset_value(value.span())),
-        outline
-            .width
-            .unwrap_or_else(|| initial_reset_value(value.span())),
-    ))
-}
-
-fn outline_expansion(
-    value: &DeclarationValue,
-    color: DeclarationValue,
-    style: DeclarationValue,
-    width: DeclarationValue,
-) -> ShorthandExpansion {
-    let _ = value;
-    ShorthandExpansion::new(
-        ShorthandId::Outline,
-        vec![
-            ExpandedLonghandDeclaration::new(
-                PropertyId::OutlineColor,
-                color,
-                DEFAULT_EXPANSION_ORDER,
-            ),
-            ExpandedLonghandDeclaration::new(PropertyId::OutlineStyle, style, 1),
-            ExpandedLonghandDeclaration::new(PropertyId::OutlineWidth, width, 2),
-        ],
-    )
-}
-
-#[derive(Default)]
-struct OutlineExpansionParts {
-    color: Option<DeclarationValue>,
-    style: Option<DeclarationValue>,
-    width: Option<DeclarationValue>,
 }
 
-impl OutlineExpansionParts {
-    fn record(
-        &mut self,
-        slot: OutlineExpansionSlot,
-        value: DeclarationValue,
-    ) -> Result<(), ShorthandExpansionError> {
-        let target = match slot {
-            OutlineExpansionSlot::Color => &mut self.color,
-            OutlineExpansionSlot::Style => &mut self.style,
-            OutlineExpansionSlot::Width => &mut self.width,
-        };
-        if target.is_some() {
+/// `<color> || <style> || <width>` in any order, for `outline` and the border
+/// sides. `longhands` lists the color, style and width longhands in that
+/// order; omitted components reset to `initial`.
+fn expand_line(
+    shorthand: ShorthandId,
+    longhands: &[PropertyId],
+    components: &[&ValueComponent],
+    span: CssSpan,
+) -> Result<Vec<DeclarationValue>, ShorthandExpansionError> {
+    let mut line: [Option<DeclarationValue>; 3] = Default::default();
+    for &component in components {
+        let slot = classify_line_component(shorthand, longhands, component)?;
+        if line[slot].is_some() {
             return Err(ShorthandExpansionError::new(
-                ShorthandId::Outline,
+                shorthand,
                 ShorthandExpansionErrorKind::DuplicateComponent {
-                    property: slot.property(),
+                    property: longhands[slot],
                 },
             ));
         }
-
-        *target = Some(value);
-        Ok(())
+        line[slot] = Some(component_value(component));
     }
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum OutlineExpansionSlot {
-    Color,
-    Style,
-    Width,
-}
 
-impl OutlineExpansionSlot {
-    fn property(self) -> PropertyId {
-        match self {
-            Self::Color => PropertyId::OutlineColor,
-            Self::Style => PropertyId::OutlineStyle,
-            Self::Width => PropertyId::OutlineWidth,
-        }
-    }
+    Ok(line
+        .into_iter()
+        .map(|value| value.unwrap_or_else(|| initial_reset_value(span)))
+        .collect())
 }
 
-fn classify_outline_component(
+/// Index into `longhands` of the only line longhand accepting `component`.
+fn classify_line_component(
+    shorthand: ShorthandId,
+    longhands: &[PropertyId],
     component: &ValueComponent,
-) -> Result<OutlineExpansionSlot, ShorthandExpansionError> {
-    let candidates = [
-        (OutlineExpansionSlot::Color, PropertyId::OutlineColor),
-        (OutlineExpansionSlot::Style, PropertyId::OutlineStyle),
-        (OutlineExpansionSlot::Width, PropertyId::OutlineWidth),
-    ];
+) -> Result<usize, ShorthandExpansionError> {
     let value = component_value(component);
     let mut matches = Vec::new();
     let mut errors = Vec::new();
 
-    for (slot, property) in candidates {
+    for (slot, &property) in longhands.iter().enumerate() {
         match parse_specified_value(property, &value) {
             Ok(_) => matches.push(slot),
             Err(error) => errors.push((property, error.kind())),
@@ -340,47 +310,261 @@ fn classify_outline_component(
 
     match matches.as_slice() {
         [slot] => Ok(*slot),
-        [] => Err(shorthand_component_error(component, &errors)),
+        [] => {
+            let [color, _, width] = longhands else {
+                unreachable!("line shorthands expand into color, style and width");
+            };
+            let preferred = match component {
+                ValueComponent::Token(ValueToken::Hash { .. }) | ValueComponent::Function(_) => {
+                    Some(*color)
+                }
+                ValueComponent::Token(ValueToken::Dimension { .. } | ValueToken::Number { .. }) => {
+                    Some(*width)
+                }
+                _ => None,
+            };
+            Err(shorthand_component_error(shorthand, preferred, &errors))
+        }
         _ => Err(ShorthandExpansionError::new(
-            ShorthandId::Outline,
+            shorthand,
             ShorthandExpansionErrorKind::AmbiguousComponent,
         )),
     }
 }
 
-fn shorthand_component_error(
-    component: &ValueComponent,
-    errors: &[(PropertyId, SpecifiedValueParseErrorKind)],
-) -> ShorthandExpansionError {
-    let preferred = match component {
-        ValueComponent::Token(ValueToken::Hash { .. }) => PropertyId::OutlineColor,
-        ValueComponent::Token(ValueToken::Dimension { .. } | ValueToken::Number { .. }) => {
-            PropertyId::OutlineWidth
-        }
-        ValueComponent::Function(_) => PropertyId::OutlineColor,
+/// One to four values assigned to the top, right, bottom and left longhands
+/// the way `margin` and `border-color` assign them.
+fn expand_box(
+    shorthand: ShorthandId,
+    longhands: &[PropertyId],
+    components: &[&ValueComponent],
+) -> Result<Vec<DeclarationValue>, ShorthandExpansionError> {
+    let sides = match *components {
+        [all] => [all, all, all, all],
+        [vertical, horizontal] => [vertical, horizontal, vertical, horizontal],
+        [top, horizontal, bottom] => [top, horizontal, bottom, horizontal],
+        [top, right, bottom, left] => [top, right, bottom, left],
         _ => {
-            return ShorthandExpansionError::new(
-                ShorthandId::Outline,
+            return Err(ShorthandExpansionError::new(
+                shorthand,
                 ShorthandExpansionErrorKind::UnsupportedComponent,
-            );
+            ));
         }
     };
 
-    if let Some((property, kind)) = errors
+    longhands
         .iter()
-        .find(|(property, _)| *property == preferred)
-        .copied()
-    {
+        .zip(sides)
+        .map(|(&property, component)| {
+            let value = component_value(component);
+            parse_specified_value(property, &value).map_err(|error| {
+                ShorthandExpansionError::new(
+                    shorthand,
+                    ShorthandExpansionErrorKind::LonghandValueRejected {
+                        property,
+                        kind: error.kind(),
+                    },
+                )
+            })?;
+            Ok(value)
+        })
+        .collect()
+}
+
+/// `background` sets only `background-color`; the one other component
+/// accepted is `none`, the initial background image.
+fn expand_background(
+    components: &[&ValueComponent],
+    span: CssSpan,
+) -> Result<Vec<DeclarationValue>, ShorthandExpansionError> {
+    let shorthand = ShorthandId::Background;
+    let mut color = None;
+    let mut image = false;
+    for &component in components {
+        if !image && ident_is(component, "none") {
+            image = true;
+            continue;
+        }
+        let value = component_value(component);
+        match parse_specified_value(PropertyId::BackgroundColor, &value) {
+            Ok(_) if color.is_some() => {
+                return Err(ShorthandExpansionError::new(
+                    shorthand,
+                    ShorthandExpansionErrorKind::DuplicateComponent {
+                        property: PropertyId::BackgroundColor,
+                    },
+                ));
+            }
+            Ok(_) => color = Some(value),
+            Err(error) => {
+                let preferred = matches!(
+                    component,
+                    ValueComponent::Token(ValueToken::Hash { .. }) | ValueComponent::Function(_)
+                )
+                .then_some(PropertyId::BackgroundColor);
+                return Err(shorthand_component_error(
+                    shorthand,
+                    preferred,
+                    &[(PropertyId::BackgroundColor, error.kind())],
+                ));
+            }
+        }
+    }
+
+    Ok(vec![color.unwrap_or_else(|| initial_reset_value(span))])
+}
+
+/// Keywords and numbers that may precede the size in `font`: font style,
+/// variant, weight and stretch. None of them has a longhand yet.
+const FONT_PREFIX_KEYWORDS: [&str; 15] = [
+    "normal",
+    "italic",
+    "oblique",
+    "small-caps",
+    "bold",
+    "bolder",
+    "lighter",
+    "ultra-condensed",
+    "extra-condensed",
+    "condensed",
+    "semi-condensed",
+    "semi-expanded",
+    "expanded",
+    "extra-expanded",
+    "ultra-expanded",
+];
+
+/// `[<style> || <variant> || <weight> || <stretch>]? <size> [/ <line-height>]?
+/// <family>#`. Only the size has a longhand; the rest is checked for shape
+/// and dropped.
+fn expand_font(
+    components: &[&ValueComponent],
+) -> Result<Vec<DeclarationValue>, ShorthandExpansionError> {
+    let shorthand = ShorthandId::Font;
+    let unsupported = || {
+        ShorthandExpansionError::new(shorthand, ShorthandExpansionErrorKind::UnsupportedComponent)
+    };
+
+    let prefix = components
+        .iter()
+        .take_while(|component| is_font_prefix_component(component))
+        .count();
+    if prefix > 4 {
+        return Err(unsupported());
+    }
+    let [size, rest @ ..] = &components[prefix..] else {
+        return Err(unsupported());
+    };
+    let size = component_value(size);
+    if let Err(error) = parse_specified_value(PropertyId::FontSize, &size) {
+        let preferred = matches!(
+            components[prefix],
+            ValueComponent::Token(
+                ValueToken::Dimension { .. }
+                    | ValueToken::Percentage { .. }
+                    | ValueToken::Number { .. }
+            )
+        )
+        .then_some(PropertyId::FontSize);
+        return Err(shorthand_component_error(
+            shorthand,
+            preferred,
+            &[(PropertyId::FontSize, error.kind())],
+        ));
+    }
+
+    let family = match rest {
+        [
+            ValueComponent::Token(ValueToken::Delim { value: '/', .. }),
+            line_height,
+            family @ ..,
+        ] if is_font_line_height_component(line_height) => family,
+        family => family,
+    };
+    if !is_font_family_list(family) {
+        return Err(unsupported());
+    }
+
+    Ok(vec![size])
+}
+
+fn is_font_prefix_component(component: &ValueComponent) -> bool {
+    match component {
+        ValueComponent::Token(ValueToken::Number { text, .. }) => text
+            .text
+            .as_deref()
+            .and_then(|text| text.parse::<f32>().ok())
+            .is_some_and(|weight| (1.0..=1000.0).contains(&weight)),
+        _ => FONT_PREFIX_KEYWORDS
+            .iter()
+            .any(|keyword| ident_is(component, keyword)),
+    }
+}
+
+fn is_font_line_height_component(component: &ValueComponent) -> bool {
+    matches!(
+        component,
+        ValueComponent::Token(
+            ValueToken::Number { .. }
+                | ValueToken::Percentage { .. }
+                | ValueToken::Dimension { .. }
+        )
+    ) || ident_is(component, "normal")
+}
+
+/// Comma-separated family names, each a string or a run of identifiers.
+fn is_font_family_list(components: &[&ValueComponent]) -> bool {
+    !components.is_empty()
+        && components
+            .split(|component| {
+                matches!(
+                    component,
+                    ValueComponent::Token(ValueToken::Symbol {
+                        kind: ValueSymbol::Comma,
+                        ..
+                    })
+                )
+            })
+            .all(|family| match family {
+                [ValueComponent::Token(ValueToken::String { .. })] => true,
+                names => {
+                    !names.is_empty()
+                        && names.iter().all(|name| {
+                            matches!(name, ValueComponent::Token(ValueToken::Ident { .. }))
+                        })
+                }
+            })
+}
+
+fn ident_is(component: &ValueComponent, keyword: &str) -> bool {
+    matches!(
+        component,
+        ValueComponent::Token(ValueToken::Ident { text, .. })
+            if text
+                .text
+                .as_deref()
+                .is_some_and(|text| text.eq_ignore_ascii_case(keyword))
+    )
+}
+
+fn shorthand_component_error(
+    shorthand: ShorthandId,
+    preferred: Option<PropertyId>,
+    errors: &[(PropertyId, SpecifiedValueParseErrorKind)],
+) -> ShorthandExpansionError {
+    if let Some((property, kind)) = preferred.and_then(|preferred| {
+        errors
+            .iter()
+            .find(|(property, _)| *property == preferred)
+            .copied()
+    }) {
         return ShorthandExpansionError::new(
-            ShorthandId::Outline,
+            shorthand,
             ShorthandExpansionErrorKind::LonghandValueRejected { property, kind },
         );
     }
 
-    ShorthandExpansionError::new(
-        ShorthandId::Outline,
-        ShorthandExpansionErrorKind::UnsupportedComponent,
-    )
+    ShorthandExpansionError::new(shorthand, ShorthandExpansionErrorKind::UnsupportedComponent)
 }
 
 fn non_trivia_components(
@@ -411,6 +595,7 @@ fn non_trivia_components(
 }
 
 fn css_wide_shorthand_value(
+    shorthand: ShorthandId,
     components: &[&ValueComponent],
 ) -> Result<Option<DeclarationValue>, ShorthandExpansionError> {
     let [component] = components else {
@@ -428,7 +613,7 @@ fn css_wide_shorthand_value(
 
     if !keyword.is_supported_for_current_cascade() {
         return Err(ShorthandExpansionError::new(
-            ShorthandId::Outline,
+            shorthand,
             ShorthandExpansionErrorKind::UnsupportedCssWideKeyword,
         ));
     }
@@ -446,7 +631,7 @@ fn component_value(component: &ValueComponent) -> DeclarationValue {
     }
 }
 
-fn initial_reset_value(span: crate::syntax::CssSpan) -> DeclarationValue {
+fn initial_reset_value(span: CssSpan) -> DeclarationValue {
     DeclarationValue {
         span,
         components: vec![ValueComponent::Token(ValueToken::Ident {
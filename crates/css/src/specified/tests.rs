@@ -2,9 +2,10 @@ use super::{
     SpecifiedBorderStyleKeyword, SpecifiedColorKeyword, SpecifiedColorSyntax,
     SpecifiedDisplayKeyword, SpecifiedLengthPercentageOrAuto, SpecifiedLengthUnit,
     SpecifiedOutlineStyleKeyword, SpecifiedOverflowKeyword, SpecifiedPositionKeyword,
-    SpecifiedTextDecorationLineKeyword, SpecifiedValue, SpecifiedValueLimits,
-    SpecifiedValueParseErrorKind, SpecifiedZIndexValue, expand_shorthand_declaration,
-    parse_specified_declaration_value, parse_specified_value, parse_specified_value_with_limits,
+    SpecifiedResizeKeyword, SpecifiedTextDecorationLineKeyword, SpecifiedValue,
+    SpecifiedValueLimits, SpecifiedValueParseErrorKind, SpecifiedZIndexValue,
+    expand_shorthand_declaration, parse_specified_declaration_value, parse_specified_value,
+    parse_specified_value_with_limits,
 };
 use crate::{
     CssLengthPercentageValue, CssWideKeyword, ParseOptions, PropertyId, PropertySpecifiedValueKind,
@@ -67,10 +68,11 @@ fn parses_representative_property_aware_specified_values() {
     let SpecifiedValue::Color(color) = color.value() else {
         panic!("expected color");
     };
-    assert_eq!(
-        color.syntax(),
-        &SpecifiedColorSyntax::Keyword(SpecifiedColorKeyword::Red)
-    );
+    let SpecifiedColorSyntax::Keyword(SpecifiedColorKeyword::Named(named)) = color.syntax() else {
+        panic!("expected named color");
+    };
+    assert_eq!(named.name(), "red");
+    assert_eq!(named.rgba(), (255, 0, 0, 255));
 
     let background = parse(PropertyId::BackgroundColor, "background-color: #Aa00FF");
     assert_eq!(background.to_css_text(), "#aa00ff");
@@ -140,6 +142,13 @@ fn parses_representative_property_aware_specified_values() {
     assert_eq!(position.keyword(), SpecifiedPositionKeyword::Relative);
     assert_eq!(position.to_css_text(), "relative");
 
+    let resize = parse(PropertyId::Resize, "resize: Vertical");
+    let SpecifiedValue::Resize(resize) = resize.value() else {
+        panic!("expected resize");
+    };
+    assert_eq!(resize.keyword(), SpecifiedResizeKeyword::Vertical);
+    assert_eq!(resize.to_css_text(), "vertical");
+
     let z_index = parse(PropertyId::ZIndex, "z-index: -3");
     let SpecifiedValue::ZIndex(z_index) = z_index.value() else {
         panic!("expected z-index");
@@ -264,11 +273,7 @@ fn expands_supported_css_wide_outline_shorthand_to_all_longhands() {
 
 #[test]
 fn rejects_invalid_outline_shorthand_atomically_before_expansion() {
-    for declaration in [
-        "outline: 1px 2px",
-        "outline: dashed",
-        "outline: rgb(1, 2, 3)",
-    ] {
+    for declaration in ["outline: 1px 2px", "outline: dashed", "outline: lab(1 2 3)"] {
         let error = match expand_shorthand_declaration(
             ShorthandId::Outline,
             &declaration_value(declaration),
@@ -293,6 +298,124 @@ fn rejects_unsupported_css_wide_outline_shorthand_with_dedicated_error() {
     );
 }
 
+fn expanded_css_texts(shorthand: ShorthandId, declaration: &str) -> Vec<(PropertyId, String)> {
+    expand_shorthand_declaration(shorthand, &declaration_value(declaration))
+        .unwrap_or_else(|error| panic!("{declaration}: {error}"))
+        .longhands()
+        .iter()
+        .map(|longhand| (longhand.property(), expanded_value_css_text(longhand)))
+        .collect()
+}
+
+#[test]
+fn expands_box_shorthands_by_value_count() {
+    let sides = |declaration| {
+        expanded_css_texts(ShorthandId::Margin, declaration)
+            .into_iter()
+            .map(|(_, text)| text)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(sides("margin: 4px"), ["4px", "4px", "4px", "4px"]);
+    assert_eq!(sides("margin: 8px 16px"), ["8px", "16px", "8px", "16px"]);
+    assert_eq!(sides("margin: 1px 2px 3px"), ["1px", "2px", "3px", "2px"]);
+    assert_eq!(
+        sides("margin: 1px 2px 3px 4px"),
+        ["1px", "2px", "3px", "4px"]
+    );
+    assert_eq!(
+        expanded_css_texts(ShorthandId::Padding, "padding: 0 2px")
+            .into_iter()
+            .map(|(property, _)| property)
+            .collect::<Vec<_>>(),
+        [
+            PropertyId::PaddingTop,
+            PropertyId::PaddingRight,
+            PropertyId::PaddingBottom,
+            PropertyId::PaddingLeft,
+        ]
+    );
+
+    for (shorthand, declaration) in [
+        (ShorthandId::Margin, "margin: 1px 2px 3px 4px 5px"),
+        (ShorthandId::Padding, "padding: 1px red"),
+        (ShorthandId::BorderStyle, "border-style: solid 2px"),
+    ] {
+        let error = expand_shorthand_declaration(shorthand, &declaration_value(declaration))
+            .expect_err("invalid box shorthand must be rejected");
+        assert_eq!(error.shorthand(), shorthand, "{declaration}");
+    }
+}
+
+#[test]
+fn expands_border_shorthand_onto_every_side() {
+    let longhands = expanded_css_texts(ShorthandId::Border, "border: 1px solid #ccc");
+
+    assert_eq!(longhands.len(), 12);
+    for (property, text) in &longhands {
+        let expected = match property.name().rsplit('-').next() {
+            Some("color") => "#ccc",
+            Some("style") => "solid",
+            _ => "1px",
+        };
+        assert_eq!(text, expected, "{}", property.name());
+    }
+    assert_eq!(
+        expanded_css_texts(ShorthandId::BorderLeft, "border-left: red"),
+        [
+            (PropertyId::BorderLeftColor, "red".to_string()),
+            (PropertyId::BorderLeftStyle, "initial".to_string()),
+            (PropertyId::BorderLeftWidth, "initial".to_string()),
+        ]
+    );
+    assert_eq!(
+        expand_shorthand_declaration(
+            ShorthandId::Border,
+            &declaration_value("border: solid none")
+        )
+        .expect_err("border with two styles must be rejected")
+        .kind(),
+        &ShorthandExpansionErrorKind::DuplicateComponent {
+            property: PropertyId::BorderTopStyle
+        }
+    );
+}
+
+#[test]
+fn expands_background_and_font_into_their_supported_longhands() {
+    assert_eq!(
+        expanded_css_texts(ShorthandId::Background, "background: none #fff"),
+        [(PropertyId::BackgroundColor, "#fff".to_string())]
+    );
+    assert_eq!(
+        expanded_css_texts(ShorthandId::Background, "background: none"),
+        [(PropertyId::BackgroundColor, "initial".to_string())]
+    );
+    assert_eq!(
+        expanded_css_texts(
+            ShorthandId::Font,
+            "font: italic bold 12px/1.5 \"Helvetica Neue\", Arial, sans-serif"
+        ),
+        [(PropertyId::FontSize, "12px".to_string())]
+    );
+    assert_eq!(
+        expanded_css_texts(ShorthandId::Font, "font: 20px serif"),
+        [(PropertyId::FontSize, "20px".to_string())]
+    );
+
+    for (shorthand, declaration) in [
+        (ShorthandId::Background, "background: url(tile.png) red"),
+        (ShorthandId::Background, "background: red blue"),
+        (ShorthandId::Font, "font: 12px"),
+        (ShorthandId::Font, "font: bold serif"),
+        (ShorthandId::Font, "font: menu"),
+    ] {
+        let error = expand_shorthand_declaration(shorthand, &declaration_value(declaration))
+            .expect_err("invalid shorthand must be rejected");
+        assert_eq!(error.shorthand(), shorthand, "{declaration}");
+    }
+}
+
 #[test]
 fn parses_unitless_zero_as_specified_length_without_computing_it() {
     let width = parse(PropertyId::Width, "width: 0");
@@ -376,9 +499,33 @@ fn rejects_values_that_do_not_match_the_property_specified_shape() {
         SpecifiedValueParseErrorKind::NonZeroUnitlessLength
     );
     assert_eq!(
-        parse_error(PropertyId::Color, "color: rgb(1, 2, 3)"),
+        parse_error(PropertyId::Color, "color: lab(50% 40 60)"),
         SpecifiedValueParseErrorKind::UnsupportedFunction
     );
+    assert_eq!(
+        parse_error(PropertyId::Color, "color: rgb(1, 2)"),
+        SpecifiedValueParseErrorKind::InvalidColorFunction
+    );
+    assert_eq!(
+        parse_error(PropertyId::Color, "color: rgb(1, 2 3)"),
+        SpecifiedValueParseErrorKind::InvalidColorFunction
+    );
+    assert_eq!(
+        parse_error(PropertyId::Color, "color: rgb(1, 2, 50%)"),
+        SpecifiedValueParseErrorKind::InvalidColorFunction
+    );
+    assert_eq!(
+        parse_error(PropertyId::Color, "color: hsl(120, 100, 50%)"),
+        SpecifiedValueParseErrorKind::InvalidColorFunction
+    );
+    assert_eq!(
+        parse_error(PropertyId::Color, "color: rgb(1 2 3 /)"),
+        SpecifiedValueParseErrorKind::InvalidColorFunction
+    );
+    assert_eq!(
+        parse_error(PropertyId::Color, "color: hotpinkish"),
+        SpecifiedValueParseErrorKind::UnsupportedColorKeyword
+    );
     assert_eq!(
         parse_error(PropertyId::Color, "color: #abcd"),
         SpecifiedValueParseErrorKind::InvalidHexColor
@@ -395,6 +542,10 @@ fn rejects_values_that_do_not_match_the_property_specified_shape() {
         parse_error(PropertyId::Position, "position: center"),
         SpecifiedValueParseErrorKind::UnsupportedPositionKeyword
     );
+    assert_eq!(
+        parse_error(PropertyId::Resize, "resize: block"),
+        SpecifiedValueParseErrorKind::UnsupportedKeyword
+    );
     assert_eq!(
         parse_error(PropertyId::ZIndex, "z-index: 1.5"),
         SpecifiedValueParseErrorKind::InvalidInteger
@@ -423,6 +574,100 @@ fn rejects_values_that_do_not_match_the_property_specified_shape() {
     );
 }
 
+#[test]
+fn parses_font_and_viewport_relative_length_units() {
+    for (declaration, unit, css_text) in [
+        ("width: 1.5em", SpecifiedLengthUnit::Em, "1.5em"),
+        ("width: 2REM", SpecifiedLengthUnit::Rem, "2rem"),
+        ("width: 50vw", SpecifiedLengthUnit::Vw, "50vw"),
+        ("width: 10vh", SpecifiedLengthUnit::Vh, "10vh"),
+    ] {
+        let width = parse(PropertyId::Width, declaration);
+        assert_eq!(width.value().length_unit(), Some(unit), "{declaration}");
+        assert_eq!(width.to_css_text(), css_text);
+    }
+
+    let font_size = parse(PropertyId::FontSize, "font-size: 1.25rem");
+    assert!(matches!(
+        font_size.value(),
+        SpecifiedValue::LengthPercentage(_)
+    ));
+    let unit = font_size.value().length_unit().expect("length unit");
+    assert_eq!(unit, SpecifiedLengthUnit::Rem);
+    assert!(!unit.is_viewport_relative());
+    let font_size = parse(PropertyId::FontSize, "font-size: 125%");
+    assert_eq!(font_size.value().length_unit(), None);
+    assert_eq!(font_size.to_css_text(), "125%");
+    assert!(SpecifiedLengthUnit::Vh.is_viewport_relative());
+    assert_eq!(
+        parse(PropertyId::Width, "width: 50%").value().length_unit(),
+        None
+    );
+}
+
+#[test]
+fn parses_extended_color_keywords_and_functions() {
+    let css_text_and_rgba = |property, input| {
+        let value = parse(property, input);
+        let SpecifiedValue::Color(color) = value.value() else {
+            panic!("expected color for {input}");
+        };
+        let rgba = match color.syntax() {
+            SpecifiedColorSyntax::Keyword(SpecifiedColorKeyword::Named(named)) => {
+                Some(named.rgba())
+            }
+            SpecifiedColorSyntax::Function(function) => Some(function.rgba()),
+            _ => None,
+        };
+        (value.to_css_text(), rgba)
+    };
+
+    assert_eq!(
+        css_text_and_rgba(PropertyId::Color, "color: RebeccaPurple"),
+        ("rebeccapurple".to_string(), Some((102, 51, 153, 255)))
+    );
+    assert_eq!(
+        css_text_and_rgba(PropertyId::Color, "color: rgb(255, 0, 0)"),
+        ("rgb(255, 0, 0)".to_string(), Some((255, 0, 0, 255)))
+    );
+    assert_eq!(
+        css_text_and_rgba(PropertyId::Color, "color: rgba(100%, 50%, 0%, 0.5)"),
+        (
+            "rgba(255, 128, 0, 0.5)".to_string(),
+            Some((255, 128, 0, 128))
+        )
+    );
+    assert_eq!(
+        css_text_and_rgba(PropertyId::Color, "color: rgb(255 0 0 / 50%)"),
+        ("rgba(255, 0, 0, 0.5)".to_string(), Some((255, 0, 0, 128)))
+    );
+    assert_eq!(
+        css_text_and_rgba(PropertyId::Color, "color: RGB(300 -5 12.4)"),
+        ("rgb(255, 0, 12)".to_string(), Some((255, 0, 12, 255)))
+    );
+    assert_eq!(
+        css_text_and_rgba(PropertyId::Color, "color: hsl(120, 100%, 25%)"),
+        ("rgb(0, 128, 0)".to_string(), Some((0, 128, 0, 255)))
+    );
+    assert_eq!(
+        css_text_and_rgba(PropertyId::Color, "color: hsla(0.5turn 100% 50% / 0.25)"),
+        (
+            "rgba(0, 255, 255, 0.25)".to_string(),
+            Some((0, 255, 255, 64))
+        )
+    );
+
+    let current = parse(PropertyId::BorderTopColor, "border-top-color: currentColor");
+    assert_eq!(current.to_css_text(), "currentcolor");
+    let SpecifiedValue::Color(current) = current.value() else {
+        panic!("expected color");
+    };
+    assert_eq!(
+        current.syntax(),
+        &SpecifiedColorSyntax::Keyword(SpecifiedColorKeyword::CurrentColor)
+    );
+}
+
 #[test]
 fn rejects_unsupported_or_malformed_core_value_categories_deterministically() {
     assert_eq!(
@@ -438,7 +683,7 @@ fn rejects_unsupported_or_malformed_core_value_categories_deterministically() {
         SpecifiedValueParseErrorKind::UnsupportedString
     );
     assert_eq!(
-        parse_error(PropertyId::Width, "width: 1em"),
+        parse_error(PropertyId::Width, "width: 1ex"),
         SpecifiedValueParseErrorKind::UnsupportedLengthUnit
     );
     assert_eq!(
@@ -507,6 +752,7 @@ fn supported_property_metadata_matches_emitted_specified_value_kinds() {
         (PropertyId::PaddingRight, "padding-right: 1px"),
         (PropertyId::PaddingTop, "padding-top: 1px"),
         (PropertyId::Position, "position: static"),
+        (PropertyId::Resize, "resize: both"),
         (
             PropertyId::TextDecorationLine,
             "text-decoration-line: underline",
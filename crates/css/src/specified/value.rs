@@ -3,9 +3,9 @@ use crate::{
     properties::{PropertyId, PropertySpecifiedValueKind},
     syntax::CssSpan,
     values::{
-        CssColorKeyword, CssColorSyntax, CssColorValue, CssHexColor, CssIntegerValue,
-        CssLengthPercentageValue, CssLengthUnit, CssLengthValue, CssPercentageValue,
-        CssWideKeywordValue,
+        CssColorFunction, CssColorKeyword, CssColorSyntax, CssColorValue, CssHexColor,
+        CssIntegerValue, CssLengthPercentageValue, CssLengthUnit, CssLengthValue,
+        CssPercentageValue, CssWideKeywordValue,
     },
 };
 
@@ -120,8 +120,10 @@ pub enum SpecifiedValue {
     Display(SpecifiedDisplay),
     Overflow(SpecifiedOverflow),
     Position(SpecifiedPosition),
+    Resize(SpecifiedResize),
     ZIndex(SpecifiedZIndex),
     Length(SpecifiedLength),
+    LengthPercentage(SpecifiedLengthPercentage),
     LengthPercentageOrAuto(SpecifiedLengthPercentageOrAuto),
     LengthPercentageOrNone(SpecifiedLengthPercentageOrNone),
 }
@@ -136,8 +138,10 @@ impl SpecifiedValue {
             Self::Display(_) => PropertySpecifiedValueKind::DisplayKeyword,
             Self::Overflow(_) => PropertySpecifiedValueKind::OverflowKeyword,
             Self::Position(_) => PropertySpecifiedValueKind::PositionKeyword,
+            Self::Resize(_) => PropertySpecifiedValueKind::ResizeKeyword,
             Self::ZIndex(_) => PropertySpecifiedValueKind::ZIndex,
             Self::Length(_) => PropertySpecifiedValueKind::AbsoluteLength,
+            Self::LengthPercentage(_) => PropertySpecifiedValueKind::LengthPercentage,
             Self::LengthPercentageOrAuto(_) => PropertySpecifiedValueKind::LengthPercentageOrAuto,
             Self::LengthPercentageOrNone(_) => PropertySpecifiedValueKind::LengthPercentageOrNone,
         }
@@ -152,13 +156,46 @@ impl SpecifiedValue {
             Self::Display(display) => display.span(),
             Self::Overflow(overflow) => overflow.span(),
             Self::Position(position) => position.span(),
+            Self::Resize(resize) => resize.span(),
             Self::ZIndex(z_index) => z_index.span(),
             Self::Length(length) => length.span(),
+            Self::LengthPercentage(value) => value.span(),
             Self::LengthPercentageOrAuto(value) => value.span(),
             Self::LengthPercentageOrNone(value) => value.span(),
         }
     }
 
+    /// Unit of the length this value carries, if it carries one.
+    pub fn length_unit(&self) -> Option<SpecifiedLengthUnit> {
+        let value = match self {
+            Self::Length(length) => return Some(length.unit()),
+            Self::LengthPercentage(value)
+            | Self::LengthPercentageOrAuto(SpecifiedLengthPercentageOrAuto::LengthPercentage(
+                value,
+            ))
+            | Self::LengthPercentageOrNone(SpecifiedLengthPercentageOrNone::LengthPercentage(
+                value,
+            )) => value,
+            Self::BorderStyle(_)
+            | Self::OutlineStyle(_)
+            | Self::TextDecorationLine(_)
+            | Self::Color(_)
+            | Self::Display(_)
+            | Self::Overflow(_)
+            | Self::Position(_)
+            | Self::Resize(_)
+            | Self::ZIndex(_)
+            | Self::LengthPercentageOrAuto(SpecifiedLengthPercentageOrAuto::Auto { .. })
+            | Self::LengthPercentageOrNone(SpecifiedLengthPercentageOrNone::None { .. }) => {
+                return None;
+            }
+        };
+        match value.value() {
+            CssLengthPercentageValue::Length(length) => Some(length.unit()),
+            CssLengthPercentageValue::Percentage(_) => None,
+        }
+    }
+
     pub fn to_css_text(&self) -> String {
         match self {
             Self::BorderStyle(border_style) => border_style.to_css_text().to_string(),
@@ -170,8 +207,10 @@ impl SpecifiedValue {
             Self::Display(display) => display.to_css_text().to_string(),
             Self::Overflow(overflow) => overflow.to_css_text().to_string(),
             Self::Position(position) => position.to_css_text().to_string(),
+            Self::Resize(resize) => resize.to_css_text().to_string(),
             Self::ZIndex(z_index) => z_index.to_css_text(),
             Self::Length(length) => length.to_css_text(),
+            Self::LengthPercentage(value) => value.to_css_text(),
             Self::LengthPercentageOrAuto(value) => value.to_css_text(),
             Self::LengthPercentageOrNone(value) => value.to_css_text(),
         }
@@ -235,6 +274,7 @@ impl SpecifiedColor {
 
 pub type SpecifiedColorSyntax = CssColorSyntax;
 pub type SpecifiedColorKeyword = CssColorKeyword;
+pub type SpecifiedColorFunction = CssColorFunction;
 pub type SpecifiedHexColor = CssHexColor;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -362,6 +402,45 @@ impl SpecifiedPositionKeyword {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpecifiedResize {
+    pub(super) span: CssSpan,
+    pub(super) keyword: SpecifiedResizeKeyword,
+}
+
+impl SpecifiedResize {
+    pub fn span(&self) -> CssSpan {
+        self.span
+    }
+
+    pub fn keyword(&self) -> SpecifiedResizeKeyword {
+        self.keyword
+    }
+
+    pub fn to_css_text(&self) -> &'static str {
+        self.keyword.as_css_keyword()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpecifiedResizeKeyword {
+    None,
+    Both,
+    Horizontal,
+    Vertical,
+}
+
+impl SpecifiedResizeKeyword {
+    pub fn as_css_keyword(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Both => "both",
+            Self::Horizontal => "horizontal",
+            Self::Vertical => "vertical",
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SpecifiedZIndex {
     pub(super) value: SpecifiedZIndexValue,
@@ -1,5 +1,9 @@
 use crate::syntax::{CssNumericKind, CssSpan};
 
+mod color;
+
+pub(crate) use color::{ColorFunctionArgument, angle_to_degrees, color_function_rgba};
+
 /// CSS identifier or keyword value after model-layer token extraction.
 ///
 /// This is a reusable CSS-owned primitive. It is not a syntax token and it is
@@ -199,9 +203,43 @@ impl CssIntegerValue {
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum CssLengthUnit {
     Px,
+    /// Relative to the element's font size; for `font-size` itself, the
+    /// parent's.
+    Em,
+    /// Relative to the root element's font size.
+    Rem,
+    /// One percent of the viewport width.
+    Vw,
+    /// One percent of the viewport height.
+    Vh,
     UnitlessZero,
 }
 
+impl CssLengthUnit {
+    /// Resolves `unit`, matched ASCII case-insensitively, to a supported unit.
+    pub fn from_unit_text(unit: &str) -> Option<Self> {
+        [Self::Px, Self::Em, Self::Rem, Self::Vw, Self::Vh]
+            .into_iter()
+            .find(|candidate| unit.eq_ignore_ascii_case(candidate.to_css_text()))
+    }
+
+    pub fn to_css_text(self) -> &'static str {
+        match self {
+            Self::Px => "px",
+            Self::Em => "em",
+            Self::Rem => "rem",
+            Self::Vw => "vw",
+            Self::Vh => "vh",
+            Self::UnitlessZero => "",
+        }
+    }
+
+    /// Whether lengths in this unit change with the viewport size.
+    pub fn is_viewport_relative(self) -> bool {
+        matches!(self, Self::Vw | Self::Vh)
+    }
+}
+
 /// CSS length primitive after unit and scalar validation.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct CssLengthValue {
@@ -235,10 +273,7 @@ impl CssLengthValue {
     }
 
     pub fn to_css_text(&self) -> String {
-        match self.unit {
-            CssLengthUnit::Px => format!("{}px", self.number.repr()),
-            CssLengthUnit::UnitlessZero => self.number.repr().to_string(),
-        }
+        format!("{}{}", self.number.repr(), self.unit.to_css_text())
     }
 }
 
@@ -300,50 +335,60 @@ impl CssLengthPercentageValue {
     }
 }
 
-/// Supported color keyword subset.
+/// Supported color keywords.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum CssColorKeyword {
-    Black,
-    Blue,
-    Cyan,
-    Gray,
-    Green,
-    Magenta,
-    Maroon,
-    Navy,
-    Olive,
-    Purple,
-    Red,
-    Silver,
-    Teal,
+    Named(CssNamedColor),
     Transparent,
-    White,
-    Yellow,
+    /// `currentColor`, resolved against the element's `color` during
+    /// computed-value normalization.
+    CurrentColor,
 }
 
 impl CssColorKeyword {
+    /// Looks up an ASCII-lowercase color keyword.
+    pub fn from_css_keyword(keyword: &str) -> Option<Self> {
+        match keyword {
+            "transparent" => Some(Self::Transparent),
+            "currentcolor" => Some(Self::CurrentColor),
+            _ => CssNamedColor::from_name(keyword).map(Self::Named),
+        }
+    }
+
     pub fn as_css_keyword(self) -> &'static str {
         match self {
-            Self::Black => "black",
-            Self::Blue => "blue",
-            Self::Cyan => "cyan",
-            Self::Gray => "gray",
-            Self::Green => "green",
-            Self::Magenta => "magenta",
-            Self::Maroon => "maroon",
-            Self::Navy => "navy",
-            Self::Olive => "olive",
-            Self::Purple => "purple",
-            Self::Red => "red",
-            Self::Silver => "silver",
-            Self::Teal => "teal",
+            Self::Named(named) => named.name(),
             Self::Transparent => "transparent",
-            Self::White => "white",
-            Self::Yellow => "yellow",
+            Self::CurrentColor => "currentcolor",
         }
     }
 }
 
+/// One of the CSS named colors, such as `rebeccapurple`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CssNamedColor {
+    index: usize,
+}
+
+impl CssNamedColor {
+    /// Looks up an ASCII-lowercase color name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        color::NAMED_COLORS
+            .binary_search_by(|(candidate, _)| (*candidate).cmp(name))
+            .ok()
+            .map(|index| Self { index })
+    }
+
+    pub fn name(self) -> &'static str {
+        color::NAMED_COLORS[self.index].0
+    }
+
+    pub fn rgba(self) -> (u8, u8, u8, u8) {
+        let (r, g, b) = color::NAMED_COLORS[self.index].1;
+        (r, g, b, 255)
+    }
+}
+
 /// Supported hex color primitive.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct CssHexColor {
@@ -365,11 +410,65 @@ impl CssHexColor {
     }
 }
 
+/// Supported color functions; `rgba()` and `hsla()` are aliases of `rgb()`
+/// and `hsl()` that keep their authored name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CssColorFunctionName {
+    Rgb,
+    Rgba,
+    Hsl,
+    Hsla,
+}
+
+impl CssColorFunctionName {
+    /// Looks up a function name case-insensitively.
+    pub fn from_css_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "rgb" => Some(Self::Rgb),
+            "rgba" => Some(Self::Rgba),
+            "hsl" => Some(Self::Hsl),
+            "hsla" => Some(Self::Hsla),
+            _ => None,
+        }
+    }
+
+    pub fn as_css_name(self) -> &'static str {
+        match self {
+            Self::Rgb => "rgb",
+            Self::Rgba => "rgba",
+            Self::Hsl => "hsl",
+            Self::Hsla => "hsla",
+        }
+    }
+}
+
+/// `rgb()`/`hsl()` color primitive, converted to sRGB channels at parse time.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CssColorFunction {
+    name: CssColorFunctionName,
+    rgba: (u8, u8, u8, u8),
+}
+
+impl CssColorFunction {
+    pub fn new(name: CssColorFunctionName, rgba: (u8, u8, u8, u8)) -> Self {
+        Self { name, rgba }
+    }
+
+    pub fn name(&self) -> CssColorFunctionName {
+        self.name
+    }
+
+    pub fn rgba(&self) -> (u8, u8, u8, u8) {
+        self.rgba
+    }
+}
+
 /// Supported color syntaxes in Borrowser's current CSS value subset.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum CssColorSyntax {
     Keyword(CssColorKeyword),
     Hex(CssHexColor),
+    Function(CssColorFunction),
 }
 
 /// CSS color primitive after current-subset validation.
@@ -396,6 +495,7 @@ impl CssColorValue {
         match &self.syntax {
             CssColorSyntax::Keyword(keyword) => keyword.as_css_keyword().to_string(),
             CssColorSyntax::Hex(hex) => format!("#{}", hex.digits()),
+            CssColorSyntax::Function(function) => color::serialize_rgba(function.rgba()),
         }
     }
 }
@@ -470,8 +570,8 @@ impl CssFunctionValue {
     }
 }
 
-/// CSS Length value, currently only supports `px`,
-/// but keep this extensible for `em`, `rem`, `pt`, etc.
+/// Computed CSS length. Relative units such as `em` and `vw` are resolved to
+/// `px` during computed-value normalization.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Length {
     Px(f32),
@@ -568,6 +668,15 @@ pub enum Position {
     Sticky,
 }
 
+/// CSS `resize` keyword; `block`/`inline` are not supported yet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Resize {
+    None,
+    Both,
+    Horizontal,
+    Vertical,
+}
+
 /// CSS `z-index` value for the current stacking subset.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ZIndex {
@@ -575,6 +684,11 @@ pub enum ZIndex {
     Integer(i32),
 }
 
+/// Parses a color from inline-style text: hex, named colors,
+/// `transparent`, and `rgb()`/`hsl()` functions.
+///
+/// `currentColor` returns `None`, since it needs the element's computed
+/// `color`; callers walking ancestors for `color` get inheritance from that.
 pub fn parse_color(value: &str) -> Option<(u8, u8, u8, u8)> {
     let s = value.trim().to_ascii_lowercase();
     // HEX
@@ -592,25 +706,15 @@ pub fn parse_color(value: &str) -> Option<(u8, u8, u8, u8)> {
         }
     }
 
-    let named = match s.as_str() {
-        "black" => (0, 0, 0, 255),
-        "blue" => (0, 0, 255, 255),
-        "cyan" => (0, 255, 255, 255),
-        "gray" | "grey" => (128, 128, 128, 255),
-        "green" => (0, 128, 0, 255),
-        "magenta" => (255, 0, 255, 255),
-        "maroon" => (128, 0, 0, 255),
-        "navy" => (0, 0, 128, 255),
-        "olive" => (128, 128, 0, 255),
-        "purple" => (128, 0, 128, 255),
-        "red" => (255, 0, 0, 255),
-        "silver" => (192, 192, 192, 255),
-        "teal" => (0, 128, 128, 255),
-        "white" => (255, 255, 255, 255),
-        "yellow" => (255, 255, 0, 255),
-        _ => return None,
-    };
-    Some(named)
+    if s.ends_with(')') {
+        return color::parse_color_function_text(&s);
+    }
+
+    match CssColorKeyword::from_css_keyword(&s)? {
+        CssColorKeyword::Named(named) => Some(named.rgba()),
+        CssColorKeyword::Transparent => Some((0, 0, 0, 0)),
+        CssColorKeyword::CurrentColor => None,
+    }
 }
 
 /// Parse a `font-size` value into a Length.
@@ -0,0 +1,357 @@
+//! Color conversions shared by the specified-value parser and the legacy
+//! string-based [`super::parse_color`].
+
+use super::CssColorFunctionName;
+
+/// CSS Color 4 named colors, sorted by name, as opaque sRGB channels.
+pub(crate) const NAMED_COLORS: &[(&str, (u8, u8, u8))] = &[
+    ("aliceblue", (240, 248, 255)),
+    ("antiquewhite", (250, 235, 215)),
+    ("aqua", (0, 255, 255)),
+    ("aquamarine", (127, 255, 212)),
+    ("azure", (240, 255, 255)),
+    ("beige", (245, 245, 220)),
+    ("bisque", (255, 228, 196)),
+    ("black", (0, 0, 0)),
+    ("blanchedalmond", (255, 235, 205)),
+    ("blue", (0, 0, 255)),
+    ("blueviolet", (138, 43, 226)),
+    ("brown", (165, 42, 42)),
+    ("burlywood", (222, 184, 135)),
+    ("cadetblue", (95, 158, 160)),
+    ("chartreuse", (127, 255, 0)),
+    ("chocolate", (210, 105, 30)),
+    ("coral", (255, 127, 80)),
+    ("cornflowerblue", (100, 149, 237)),
+    ("cornsilk", (255, 248, 220)),
+    ("crimson", (220, 20, 60)),
+    ("cyan", (0, 255, 255)),
+    ("darkblue", (0, 0, 139)),
+    ("darkcyan", (0, 139, 139)),
+    ("darkgoldenrod", (184, 134, 11)),
+    ("darkgray", (169, 169, 169)),
+    ("darkgreen", (0, 100, 0)),
+    ("darkgrey", (169, 169, 169)),
+    ("darkkhaki", (189, 183, 107)),
+    ("darkmagenta", (139, 0, 139)),
+    ("darkolivegreen", (85, 107, 47)),
+    ("darkorange", (255, 140, 0)),
+    ("darkorchid", (153, 50, 204)),
+    ("darkred", (139, 0, 0)),
+    ("darksalmon", (233, 150, 122)),
+    ("darkseagreen", (143, 188, 143)),
+    ("darkslateblue", (72, 61, 139)),
+    ("darkslategray", (47, 79, 79)),
+    ("darkslategrey", (47, 79, 79)),
+    ("darkturquoise", (0, 206, 209)),
+    ("darkviolet", (148, 0, 211)),
+    ("deeppink", (255, 20, 147)),
+    ("deepskyblue", (0, 191, 255)),
+    ("dimgray", (105, 105, 105)),
+    ("dimgrey", (105, 105, 105)),
+    ("dodgerblue", (30, 144, 255)),
+    ("firebrick", (178, 34, 34)),
+    ("floralwhite", (255, 250, 240)),
+    ("forestgreen", (34, 139, 34)),
+    ("fuchsia", (255, 0, 255)),
+    ("gainsboro", (220, 220, 220)),
+    ("ghostwhite", (248, 248, 255)),
+    ("gold", (255, 215, 0)),
+    ("goldenrod", (218, 165, 32)),
+    ("gray", (128, 128, 128)),
+    ("green", (0, 128, 0)),
+    ("greenyellow", (173, 255, 47)),
+    ("grey", (128, 128, 128)),
+    ("honeydew", (240, 255, 240)),
+    ("hotpink", (255, 105, 180)),
+    ("indianred", (205, 92, 92)),
+    ("indigo", (75, 0, 130)),
+    ("ivory", (255, 255, 240)),
+    ("khaki", (240, 230, 140)),
+    ("lavender", (230, 230, 250)),
+    ("lavenderblush", (255, 240, 245)),
+    ("lawngreen", (124, 252, 0)),
+    ("lemonchiffon", (255, 250, 205)),
+    ("lightblue", (173, 216, 230)),
+    ("lightcoral", (240, 128, 128)),
+    ("lightcyan", (224, 255, 255)),
+    ("lightgoldenrodyellow", (250, 250, 210)),
+    ("lightgray", (211, 211, 211)),
+    ("lightgreen", (144, 238, 144)),
+    ("lightgrey", (211, 211, 211)),
+    ("lightpink", (255, 182, 193)),
+    ("lightsalmon", (255, 160, 122)),
+    ("lightseagreen", (32, 178, 170)),
+    ("lightskyblue", (135, 206, 250)),
+    ("lightslategray", (119, 136, 153)),
+    ("lightslategrey", (119, 136, 153)),
+    ("lightsteelblue", (176, 196, 222)),
+    ("lightyellow", (255, 255, 224)),
+    ("lime", (0, 255, 0)),
+    ("limegreen", (50, 205, 50)),
+    ("linen", (250, 240, 230)),
+    ("magenta", (255, 0, 255)),
+    ("maroon", (128, 0, 0)),
+    ("mediumaquamarine", (102, 205, 170)),
+    ("mediumblue", (0, 0, 205)),
+    ("mediumorchid", (186, 85, 211)),
+    ("mediumpurple", (147, 112, 219)),
+    ("mediumseagreen", (60, 179, 113)),
+    ("mediumslateblue", (123, 104, 238)),
+    ("mediumspringgreen", (0, 250, 154)),
+    ("mediumturquoise", (72, 209, 204)),
+    ("mediumvioletred", (199, 21, 133)),
+    ("midnightblue", (25, 25, 112)),
+    ("mintcream", (245, 255, 250)),
+    ("mistyrose", (255, 228, 225)),
+    ("moccasin", (255, 228, 181)),
+    ("navajowhite", (255, 222, 173)),
+    ("navy", (0, 0, 128)),
+    ("oldlace", (253, 245, 230)),
+    ("olive", (128, 128, 0)),
+    ("olivedrab", (107, 142, 35)),
+    ("orange", (255, 165, 0)),
+    ("orangered", (255, 69, 0)),
+    ("orchid", (218, 112, 214)),
+    ("palegoldenrod", (238, 232, 170)),
+    ("palegreen", (152, 251, 152)),
+    ("paleturquoise", (175, 238, 238)),
+    ("palevioletred", (219, 112, 147)),
+    ("papayawhip", (255, 239, 213)),
+    ("peachpuff", (255, 218, 185)),
+    ("peru", (205, 133, 63)),
+    ("pink", (255, 192, 203)),
+    ("plum", (221, 160, 221)),
+    ("powderblue", (176, 224, 230)),
+    ("purple", (128, 0, 128)),
+    ("rebeccapurple", (102, 51, 153)),
+    ("red", (255, 0, 0)),
+    ("rosybrown", (188, 143, 143)),
+    ("royalblue", (65, 105, 225)),
+    ("saddlebrown", (139, 69, 19)),
+    ("salmon", (250, 128, 114)),
+    ("sandybrown", (244, 164, 96)),
+    ("seagreen", (46, 139, 87)),
+    ("seashell", (255, 245, 238)),
+    ("sienna", (160, 82, 45)),
+    ("silver", (192, 192, 192)),
+    ("skyblue", (135, 206, 235)),
+    ("slateblue", (106, 90, 205)),
+    ("slategray", (112, 128, 144)),
+    ("slategrey", (112, 128, 144)),
+    ("snow", (255, 250, 250)),
+    ("springgreen", (0, 255, 127)),
+    ("steelblue", (70, 130, 180)),
+    ("tan", (210, 180, 140)),
+    ("teal", (0, 128, 128)),
+    ("thistle", (216, 191, 216)),
+    ("tomato", (255, 99, 71)),
+    ("turquoise", (64, 224, 208)),
+    ("violet", (238, 130, 238)),
+    ("wheat", (245, 222, 179)),
+    ("white", (255, 255, 255)),
+    ("whitesmoke", (245, 245, 245)),
+    ("yellow", (255, 255, 0)),
+    ("yellowgreen", (154, 205, 50)),
+];
+
+/// One numeric argument of an `rgb()` or `hsl()` color function.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum ColorFunctionArgument {
+    Number(f64),
+    /// Percentage as authored, so `50%` is `50.0`.
+    Percentage(f64),
+    /// Hue angle already converted to degrees.
+    Degrees(f64),
+}
+
+/// Converts an angle dimension to degrees; `None` for non-angle units.
+pub(crate) fn angle_to_degrees(value: f64, unit: &str) -> Option<f64> {
+    match unit.to_ascii_lowercase().as_str() {
+        "deg" => Some(value),
+        "grad" => Some(value * 0.9),
+        "rad" => Some(value.to_degrees()),
+        "turn" => Some(value * 360.0),
+        _ => None,
+    }
+}
+
+/// Resolves a color function call from its name and arguments, or `None` if
+/// the name is not a supported color function or the arguments do not fit it.
+///
+/// `legacy` is the comma-separated syntax, where `rgb()` channels may not mix
+/// numbers and percentages. Out-of-range values are clamped.
+pub(crate) fn color_function_rgba(
+    name: &str,
+    legacy: bool,
+    arguments: &[ColorFunctionArgument],
+) -> Option<(CssColorFunctionName, (u8, u8, u8, u8))> {
+    let name = CssColorFunctionName::from_css_name(name)?;
+    let (channels, alpha) = match arguments {
+        [r, g, b] => ([*r, *g, *b], None),
+        [r, g, b, a] => ([*r, *g, *b], Some(*a)),
+        _ => return None,
+    };
+    let hsl = matches!(name, CssColorFunctionName::Hsl | CssColorFunctionName::Hsla);
+    channels_rgba(hsl, legacy, channels, alpha).map(|rgba| (name, rgba))
+}
+
+/// Parses a color function from authored text such as `rgb(255 0 0 / 50%)`.
+pub(super) fn parse_color_function_text(text: &str) -> Option<(u8, u8, u8, u8)> {
+    let (name, rest) = text.split_once('(')?;
+    let arguments = rest.strip_suffix(')')?;
+    let legacy = arguments.contains(',');
+
+    let mut parsed = Vec::with_capacity(4);
+    if legacy {
+        for argument in arguments.split(',') {
+            parsed.push(parse_argument_text(argument.trim())?);
+        }
+    } else {
+        let (channels, alpha) = match arguments.split_once('/') {
+            Some((channels, alpha)) => (channels, Some(alpha.trim())),
+            None => (arguments, None),
+        };
+        for argument in channels.split_ascii_whitespace() {
+            parsed.push(parse_argument_text(argument)?);
+        }
+        if let Some(alpha) = alpha {
+            if parsed.len() != 3 {
+                return None;
+            }
+            parsed.push(parse_argument_text(alpha)?);
+        }
+    }
+
+    color_function_rgba(name.trim(), legacy, &parsed).map(|(_, rgba)| rgba)
+}
+
+fn parse_argument_text(text: &str) -> Option<ColorFunctionArgument> {
+    let number = |text: &str| text.parse::<f64>().ok().filter(|value| value.is_finite());
+    if let Some(percentage) = text.strip_suffix('%') {
+        return number(percentage).map(ColorFunctionArgument::Percentage);
+    }
+    if let Some(value) = number(text) {
+        return Some(ColorFunctionArgument::Number(value));
+    }
+
+    let unit_start = text.find(|ch: char| ch.is_ascii_alphabetic())?;
+    let (value, unit) = text.split_at(unit_start);
+    angle_to_degrees(number(value)?, unit).map(ColorFunctionArgument::Degrees)
+}
+
+fn channels_rgba(
+    hsl: bool,
+    legacy: bool,
+    channels: [ColorFunctionArgument; 3],
+    alpha: Option<ColorFunctionArgument>,
+) -> Option<(u8, u8, u8, u8)> {
+    let alpha = match alpha {
+        None => 1.0,
+        Some(ColorFunctionArgument::Number(value)) => value,
+        Some(ColorFunctionArgument::Percentage(value)) => value / 100.0,
+        Some(ColorFunctionArgument::Degrees(_)) => return None,
+    };
+    let a = unit_to_channel(alpha);
+
+    if hsl {
+        let hue = match channels[0] {
+            ColorFunctionArgument::Number(value) | ColorFunctionArgument::Degrees(value) => value,
+            ColorFunctionArgument::Percentage(_) => return None,
+        };
+        let [
+            ColorFunctionArgument::Percentage(saturation),
+            ColorFunctionArgument::Percentage(lightness),
+        ] = [channels[1], channels[2]]
+        else {
+            return None;
+        };
+        let (r, g, b) = hsl_to_rgb(hue, saturation / 100.0, lightness / 100.0);
+        return Some((r, g, b, a));
+    }
+
+    if legacy
+        && channels
+            .iter()
+            .any(|channel| std::mem::discriminant(channel) != std::mem::discriminant(&channels[0]))
+    {
+        return None;
+    }
+
+    let mut rgb = [0; 3];
+    for (channel, argument) in rgb.iter_mut().zip(channels) {
+        *channel = match argument {
+            ColorFunctionArgument::Number(value) => unit_to_channel(value / 255.0),
+            ColorFunctionArgument::Percentage(value) => unit_to_channel(value / 100.0),
+            ColorFunctionArgument::Degrees(_) => return None,
+        };
+    }
+    Some((rgb[0], rgb[1], rgb[2], a))
+}
+
+/// Serializes an sRGB color the way CSSOM does: `rgb(r, g, b)` when opaque,
+/// otherwise `rgba(r, g, b, alpha)` with the shortest alpha that round-trips.
+pub(crate) fn serialize_rgba((r, g, b, a): (u8, u8, u8, u8)) -> String {
+    if a == 255 {
+        return format!("rgb({r}, {g}, {b})");
+    }
+
+    let alpha = f64::from(a) / 255.0;
+    let mut text = format!("{:.2}", alpha);
+    if unit_to_channel(text.parse().unwrap_or(alpha)) != a {
+        text = format!("{:.3}", alpha);
+    }
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    format!("rgba({r}, {g}, {b}, {text})")
+}
+
+fn unit_to_channel(value: f64) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+fn hsl_to_rgb(hue: f64, saturation: f64, lightness: f64) -> (u8, u8, u8) {
+    let hue = hue.rem_euclid(360.0);
+    let saturation = saturation.clamp(0.0, 1.0);
+    let lightness = lightness.clamp(0.0, 1.0);
+
+    let channel = |n: f64| {
+        let k = (n + hue / 30.0) % 12.0;
+        let a = saturation * lightness.min(1.0 - lightness);
+        lightness - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+    };
+
+    (
+        unit_to_channel(channel(0.0)),
+        unit_to_channel(channel(8.0)),
+        unit_to_channel(channel(4.0)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::parse_color, NAMED_COLORS};
+
+    #[test]
+    fn named_colors_are_sorted_for_lookup() {
+        assert!(NAMED_COLORS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn parse_color_accepts_named_colors_and_color_functions() {
+        for (input, expected) in [
+            ("RebeccaPurple", Some((102, 51, 153, 255))),
+            ("transparent", Some((0, 0, 0, 0))),
+            ("currentColor", None),
+            ("rgb(255, 0, 0)", Some((255, 0, 0, 255))),
+            ("rgba(0, 0, 255, 0.5)", Some((0, 0, 255, 128))),
+            ("rgb(255 0 0 / 50%)", Some((255, 0, 0, 128))),
+            ("rgb(100%, 0, 0)", None),
+            ("hsl(120, 100%, 25%)", Some((0, 128, 0, 255))),
+            ("hsla(240deg 100% 50% / 0.25)", Some((0, 0, 255, 64))),
+            ("hsl(120 100% 25% /)", None),
+            ("lab(50% 40 60)", None),
+        ] {
+            assert_eq!(parse_color(input), expected, "{input}");
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use super::error::DomPatchError;
 use html::PatchKey;
 use html::internal::{Id, ParserCreatedFragmentKind};
-use html::{ExpandedElementName, ParserCreatedAttribute};
+use html::{ExpandedElementName, ParserCreatedAttribute, QualifiedAttributeName, SourceSpan};
 use std::collections::{HashMap, HashSet};
 
 #[derive(Clone)]
@@ -463,6 +463,79 @@ impl DomArena {
         }
     }
 
+    pub(crate) fn set_attribute(
+        &mut self,
+        key: PatchKey,
+        attribute: &ParserCreatedAttribute,
+    ) -> Result<(), DomPatchError> {
+        self.debug_check_invariants();
+        let index = *self.live.get(&key).ok_or(DomPatchError::MissingKey(key))?;
+        let actual = self.nodes[index].kind_name();
+        let NodeKind::Element { attributes, .. } = &mut self.nodes[index].kind else {
+            return Err(DomPatchError::WrongNodeKind {
+                key,
+                expected: "Element",
+                actual,
+            });
+        };
+        match attributes
+            .iter_mut()
+            .find(|slot| slot.name().same_expanded_name(attribute.name()))
+        {
+            Some(slot) => slot.clone_from(attribute),
+            None => attributes.push(attribute.clone()),
+        }
+        self.debug_check_invariants();
+        Ok(())
+    }
+
+    pub(crate) fn remove_attribute(
+        &mut self,
+        key: PatchKey,
+        name: &QualifiedAttributeName,
+    ) -> Result<(), DomPatchError> {
+        self.debug_check_invariants();
+        let index = *self.live.get(&key).ok_or(DomPatchError::MissingKey(key))?;
+        let actual = self.nodes[index].kind_name();
+        let NodeKind::Element { attributes, .. } = &mut self.nodes[index].kind else {
+            return Err(DomPatchError::WrongNodeKind {
+                key,
+                expected: "Element",
+                actual,
+            });
+        };
+        let position = attributes
+            .iter()
+            .position(|slot| slot.name().same_expanded_name(name))
+            .ok_or_else(|| DomPatchError::MissingAttribute {
+                key,
+                name: name.local_name().to_string(),
+            })?;
+        attributes.remove(position);
+        self.debug_check_invariants();
+        Ok(())
+    }
+
+    pub(crate) fn set_source_span(
+        &mut self,
+        key: PatchKey,
+        span: SourceSpan,
+    ) -> Result<(), DomPatchError> {
+        self.debug_check_invariants();
+        let index = *self.live.get(&key).ok_or(DomPatchError::MissingKey(key))?;
+        let actual = self.nodes[index].kind_name();
+        let NodeKind::Element { source_span, .. } = &mut self.nodes[index].kind else {
+            return Err(DomPatchError::WrongNodeKind {
+                key,
+                expected: "Element",
+                actual,
+            });
+        };
+        *source_span = Some(span);
+        self.debug_check_invariants();
+        Ok(())
+    }
+
     pub(crate) fn set_text(&mut self, key: PatchKey, text: &str) -> Result<(), DomPatchError> {
         self.debug_check_invariants();
         let index = *self.live.get(&key).ok_or(DomPatchError::MissingKey(key))?;
@@ -636,6 +709,7 @@ pub(crate) enum NodeKind {
         name: ExpandedElementName,
         attributes: Vec<ParserCreatedAttribute>,
         template_contents: Option<PatchKey>,
+        source_span: Option<SourceSpan>,
     },
     DocumentFragment {
         kind: ParserCreatedFragmentKind,
@@ -130,6 +130,7 @@ impl DomDoc {
                         name: name.clone(),
                         attributes: attributes.clone(),
                         template_contents: None,
+                        source_span: None,
                     },
                 )?;
             }
@@ -188,6 +189,18 @@ impl DomDoc {
                 self.ensure_live(*key)?;
                 self.arena.set_attributes(*key, attributes)?;
             }
+            DomPatch::SetAttribute { key, attribute } => {
+                self.ensure_live(*key)?;
+                self.arena.set_attribute(*key, attribute)?;
+            }
+            DomPatch::RemoveAttribute { key, name } => {
+                self.ensure_live(*key)?;
+                self.arena.remove_attribute(*key, name)?;
+            }
+            DomPatch::SetSourceSpan { key, span } => {
+                self.ensure_live(*key)?;
+                self.arena.set_source_span(*key, *span)?;
+            }
             DomPatch::SetText { key, text } => {
                 self.ensure_live(*key)?;
                 self.arena.set_text(*key, text)?;
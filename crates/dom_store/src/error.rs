@@ -23,6 +23,10 @@ pub enum DomPatchError {
         actual: &'static str,
     },
     InvalidParent(PatchKey),
+    MissingAttribute {
+        key: PatchKey,
+        name: String,
+    },
     // Retained for legacy/non-HTML5-capable appliers that may still reject
     // reparenting outright. Strict HTML5-capable appliers should instead
     // support legal moves and use `IllegalMove` only for forbidden cases.
@@ -0,0 +1,21 @@
+//! # dom_store
+//!
+//! Retained DOM documents built from the parser's `DomPatch` stream.
+//!
+//! A [`DomStore`] keeps one arena-backed document per [`core_types::DomHandle`].
+//! Each batch is applied transactionally against the handle's current
+//! [`core_types::DomVersion`]: nodes are addressed by their stable `PatchKey`,
+//! and a failed batch leaves the previous document untouched. Consumers read
+//! the document back as an `html::Node` tree.
+
+mod arena;
+mod document;
+mod error;
+mod materialize;
+mod store;
+
+pub use error::DomPatchError;
+pub use store::DomStore;
+
+#[cfg(test)]
+mod tests;
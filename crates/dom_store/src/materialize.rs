@@ -44,8 +44,9 @@ impl DomArena {
                 name,
                 attributes,
                 template_contents,
+                source_span,
             } => {
-                if let Some(contents) = template_contents {
+                let mut node = if let Some(contents) = template_contents {
                     let (contents_id, contents_children) = self.materialize_fragment(*contents)?;
                     if !name.is(html::ElementNamespace::Html, "template") {
                         return Err(DomPatchError::Protocol(
@@ -69,7 +70,11 @@ impl DomArena {
                         Vec::new(),
                         children,
                     )
+                };
+                if let Some(element) = node.element_mut() {
+                    element.set_source_span(*source_span);
                 }
+                node
             }
             NodeKind::DocumentFragment { .. } => {
                 return Err(DomPatchError::Protocol(
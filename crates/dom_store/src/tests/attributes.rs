@@ -0,0 +1,162 @@
+use super::super::{DomPatchError, DomStore};
+use super::support::{
+    VersionSteps, apply_ok, assert_failed_apply_is_atomic, materialized_dom_lines,
+    new_store_with_handle,
+};
+use core_types::DomHandle;
+use html::internal::{html_name, qualified_attribute};
+use html::{AttributeNamespace, DomPatch, PatchKey};
+
+fn bootstrap_element(store: &mut DomStore, h: DomHandle) -> VersionSteps {
+    let mut versions = VersionSteps::new();
+    apply_ok(
+        store,
+        h,
+        &mut versions,
+        &[
+            DomPatch::CreateDocument {
+                key: PatchKey(1),
+                doctype: None,
+            },
+            DomPatch::CreateElement {
+                key: PatchKey(2),
+                name: html_name("div"),
+                attributes: vec![
+                    qualified_attribute(AttributeNamespace::None, "id", "a"),
+                    qualified_attribute(AttributeNamespace::None, "lang", "nl"),
+                    qualified_attribute(AttributeNamespace::None, "class", "x"),
+                ],
+            },
+            DomPatch::CreateText {
+                key: PatchKey(3),
+                text: "t".to_string(),
+            },
+            DomPatch::AppendChild {
+                parent: PatchKey(1),
+                child: PatchKey(2),
+            },
+            DomPatch::AppendChild {
+                parent: PatchKey(2),
+                child: PatchKey(3),
+            },
+        ],
+        "bootstrap apply",
+    );
+    versions
+}
+
+fn element_line(store: &DomStore, h: DomHandle) -> String {
+    materialized_dom_lines(store, h)[1].clone()
+}
+
+#[test]
+fn set_attribute_replaces_in_place_and_appends_new_names() {
+    let (mut store, h) = new_store_with_handle(60);
+    let mut versions = bootstrap_element(&mut store, h);
+
+    apply_ok(
+        &mut store,
+        h,
+        &mut versions,
+        &[
+            DomPatch::SetAttribute {
+                key: PatchKey(2),
+                attribute: qualified_attribute(AttributeNamespace::None, "lang", "en"),
+            },
+            DomPatch::SetAttribute {
+                key: PatchKey(2),
+                attribute: qualified_attribute(AttributeNamespace::Xml, "lang", "fr"),
+            },
+        ],
+        "single-attribute updates should apply",
+    );
+    assert_eq!(
+        element_line(&store, h),
+        "  <ns=html local=\"div\" attrs=[\
+         ns=none prefix=none local=\"id\" value=\"a\", \
+         ns=none prefix=none local=\"lang\" value=\"en\", \
+         ns=none prefix=none local=\"class\" value=\"x\", \
+         ns=xml prefix=xml local=\"lang\" value=\"fr\"]>"
+    );
+}
+
+#[test]
+fn remove_attribute_preserves_the_order_of_the_rest() {
+    let (mut store, h) = new_store_with_handle(61);
+    let mut versions = bootstrap_element(&mut store, h);
+
+    apply_ok(
+        &mut store,
+        h,
+        &mut versions,
+        &[
+            DomPatch::RemoveAttribute {
+                key: PatchKey(2),
+                name: qualified_attribute(AttributeNamespace::None, "lang", "")
+                    .name()
+                    .clone(),
+            },
+            DomPatch::SetAttribute {
+                key: PatchKey(2),
+                attribute: qualified_attribute(AttributeNamespace::None, "lang", "de"),
+            },
+        ],
+        "remove then re-add should apply",
+    );
+    assert_eq!(
+        element_line(&store, h),
+        "  <ns=html local=\"div\" attrs=[\
+         ns=none prefix=none local=\"id\" value=\"a\", \
+         ns=none prefix=none local=\"class\" value=\"x\", \
+         ns=none prefix=none local=\"lang\" value=\"de\"]>"
+    );
+}
+
+#[test]
+fn single_attribute_patches_reject_missing_names_and_non_elements_atomically() {
+    let (mut store, h) = new_store_with_handle(62);
+    let versions = bootstrap_element(&mut store, h);
+    let (from, to) = versions.next_pair();
+
+    let err = assert_failed_apply_is_atomic(
+        &mut store,
+        h,
+        from,
+        to,
+        &[
+            DomPatch::SetAttribute {
+                key: PatchKey(2),
+                attribute: qualified_attribute(AttributeNamespace::None, "id", "b"),
+            },
+            DomPatch::RemoveAttribute {
+                key: PatchKey(2),
+                name: qualified_attribute(AttributeNamespace::Xml, "lang", "")
+                    .name()
+                    .clone(),
+            },
+        ],
+    );
+    assert!(matches!(
+        err,
+        DomPatchError::MissingAttribute { key: PatchKey(2), ref name } if name == "lang"
+    ));
+
+    let err = assert_failed_apply_is_atomic(
+        &mut store,
+        h,
+        from,
+        to,
+        &[DomPatch::SetAttribute {
+            key: PatchKey(3),
+            attribute: qualified_attribute(AttributeNamespace::None, "id", "b"),
+        }],
+    );
+    assert!(matches!(
+        err,
+        DomPatchError::WrongNodeKind {
+            key: PatchKey(3),
+            expected: "Element",
+            ..
+        }
+    ));
+}
@@ -2,9 +2,11 @@ mod support;
 
 mod aaa_runtime;
 mod atomicity;
+mod attributes;
 mod lifecycle;
 mod movement;
 mod processing_instruction;
 mod protocol;
+mod source_spans;
 mod structure;
 mod template;
@@ -0,0 +1,133 @@
+use super::super::DomPatchError;
+use super::support::{
+    VersionSteps, apply_ok, assert_failed_apply_is_atomic, new_store_with_handle,
+};
+use html::internal::html_name;
+use html::{DomPatch, Node, PatchKey, SourceSpan};
+
+fn element_spans(node: &Node, out: &mut Vec<(String, Option<SourceSpan>)>) {
+    if let Node::Element { element } = node {
+        out.push((element.name().to_string(), element.source_span()));
+    }
+    for child in node.children().unwrap_or_default() {
+        element_spans(child, out);
+    }
+}
+
+#[test]
+fn source_spans_survive_materialization_including_template_hosts() {
+    let (mut store, h) = new_store_with_handle(70);
+    let mut versions = VersionSteps::new();
+
+    apply_ok(
+        &mut store,
+        h,
+        &mut versions,
+        &[
+            DomPatch::CreateDocument {
+                key: PatchKey(1),
+                doctype: None,
+            },
+            DomPatch::CreateElement {
+                key: PatchKey(2),
+                name: html_name("div"),
+                attributes: Vec::new(),
+            },
+            DomPatch::SetSourceSpan {
+                key: PatchKey(2),
+                span: SourceSpan { start: 0, end: 5 },
+            },
+            DomPatch::CreateElement {
+                key: PatchKey(3),
+                name: html_name("template"),
+                attributes: Vec::new(),
+            },
+            DomPatch::SetSourceSpan {
+                key: PatchKey(3),
+                span: SourceSpan { start: 5, end: 15 },
+            },
+            DomPatch::CreateTemplateContents {
+                host: PatchKey(3),
+                contents: PatchKey(4),
+            },
+            DomPatch::CreateElement {
+                key: PatchKey(5),
+                name: html_name("span"),
+                attributes: Vec::new(),
+            },
+            DomPatch::AppendChild {
+                parent: PatchKey(1),
+                child: PatchKey(2),
+            },
+            DomPatch::AppendChild {
+                parent: PatchKey(2),
+                child: PatchKey(3),
+            },
+            DomPatch::AppendChild {
+                parent: PatchKey(2),
+                child: PatchKey(5),
+            },
+        ],
+        "span patches should apply",
+    );
+
+    let mut spans = Vec::new();
+    element_spans(store.get_current(h).expect("current dom"), &mut spans);
+    assert_eq!(
+        spans,
+        [
+            ("div".to_string(), Some(SourceSpan { start: 0, end: 5 })),
+            (
+                "template".to_string(),
+                Some(SourceSpan { start: 5, end: 15 })
+            ),
+            ("span".to_string(), None),
+        ]
+    );
+}
+
+#[test]
+fn source_span_on_a_non_element_is_rejected_atomically() {
+    let (mut store, h) = new_store_with_handle(71);
+    let mut versions = VersionSteps::new();
+    apply_ok(
+        &mut store,
+        h,
+        &mut versions,
+        &[
+            DomPatch::CreateDocument {
+                key: PatchKey(1),
+                doctype: None,
+            },
+            DomPatch::CreateText {
+                key: PatchKey(2),
+                text: "t".to_string(),
+            },
+            DomPatch::AppendChild {
+                parent: PatchKey(1),
+                child: PatchKey(2),
+            },
+        ],
+        "bootstrap apply",
+    );
+    let (from, to) = versions.next_pair();
+
+    let err = assert_failed_apply_is_atomic(
+        &mut store,
+        h,
+        from,
+        to,
+        &[DomPatch::SetSourceSpan {
+            key: PatchKey(2),
+            span: SourceSpan { start: 0, end: 1 },
+        }],
+    );
+    assert!(matches!(
+        err,
+        DomPatchError::WrongNodeKind {
+            key: PatchKey(2),
+            expected: "Element",
+            ..
+        }
+    ));
+}
@@ -0,0 +1,93 @@
+//! Debug overlay that highlights the box model of the hovered layout box:
+//! border (orange), padding (green) and content (blue), plus its size.
+
+use crate::text_control::find_layout_box_by_id;
+use egui::{Color32, CornerRadius, FontId, Painter, Pos2, Rect, Vec2};
+use html::internal::Id;
+use layout::{LayoutBox, Rectangle, content_height, content_x_and_width, content_y};
+
+const BORDER_FILL: Color32 = Color32::from_rgba_premultiplied(120, 72, 20, 120);
+const PADDING_FILL: Color32 = Color32::from_rgba_premultiplied(68, 104, 40, 120);
+const CONTENT_FILL: Color32 = Color32::from_rgba_premultiplied(44, 82, 120, 120);
+const LABEL_FILL: Color32 = Color32::from_rgb(36, 36, 36);
+
+/// Paint the overlay for `hovered` on top of the page.
+pub(crate) fn paint_box_overlay(
+    painter: &Painter,
+    origin: Pos2,
+    layout_root: &LayoutBox<'_, '_>,
+    hovered: Option<Id>,
+) {
+    let Some(lb) = hovered.and_then(|id| find_layout_box_by_id(layout_root, id)) else {
+        return;
+    };
+    if lb.rect.width <= 0.0 || lb.rect.height <= 0.0 {
+        return;
+    }
+
+    let to_screen = |r: Rectangle| {
+        Rect::from_min_size(
+            Pos2::new(origin.x + r.x, origin.y + r.y),
+            Vec2::new(r.width.max(0.0), r.height.max(0.0)),
+        )
+    };
+    let bm = lb.style.box_metrics();
+    let border_box = to_screen(lb.rect);
+    let padding_box = to_screen(Rectangle {
+        x: lb.rect.x + bm.border_left,
+        y: lb.rect.y + bm.border_top,
+        width: lb.rect.width - bm.border_left - bm.border_right,
+        height: lb.rect.height - bm.border_top - bm.border_bottom,
+    });
+    let (content_x, content_width) = content_x_and_width(lb.style, lb.rect.x, lb.rect.width);
+    let content_box = to_screen(Rectangle {
+        x: content_x,
+        y: content_y(lb.style, lb.rect.y),
+        width: content_width,
+        height: content_height(lb.style, lb.rect.height),
+    });
+
+    fill_ring(painter, border_box, padding_box, BORDER_FILL);
+    fill_ring(painter, padding_box, content_box, PADDING_FILL);
+    painter.rect_filled(content_box, CornerRadius::ZERO, CONTENT_FILL);
+
+    let label = format!("{} × {}", fmt_px(lb.rect.width), fmt_px(lb.rect.height));
+    let galley = painter.layout_no_wrap(label, FontId::monospace(11.0), Color32::WHITE);
+    let label_rect = Rect::from_min_size(
+        border_box.left_bottom() + Vec2::new(0.0, 2.0),
+        galley.size() + Vec2::new(8.0, 4.0),
+    );
+    painter.rect_filled(label_rect, CornerRadius::same(2), LABEL_FILL);
+    painter.galley(label_rect.min + Vec2::new(4.0, 2.0), galley, Color32::WHITE);
+}
+
+/// Fill the area between `outer` and `inner` without overlapping `inner`,
+/// so translucent layers keep their own color.
+fn fill_ring(painter: &Painter, outer: Rect, inner: Rect, color: Color32) {
+    let inner = inner.intersect(outer);
+    let strips = [
+        Rect::from_min_max(outer.min, Pos2::new(outer.max.x, inner.min.y)),
+        Rect::from_min_max(Pos2::new(outer.min.x, inner.max.y), outer.max),
+        Rect::from_min_max(
+            Pos2::new(outer.min.x, inner.min.y),
+            Pos2::new(inner.min.x, inner.max.y),
+        ),
+        Rect::from_min_max(
+            Pos2::new(inner.max.x, inner.min.y),
+            Pos2::new(outer.max.x, inner.max.y),
+        ),
+    ];
+    for strip in strips {
+        if strip.width() > 0.0 && strip.height() > 0.0 {
+            painter.rect_filled(strip, CornerRadius::ZERO, color);
+        }
+    }
+}
+
+fn fmt_px(value: f32) -> String {
+    if value.fract().abs() < 0.01 {
+        format!("{value:.0}")
+    } else {
+        format!("{value:.1}")
+    }
+}
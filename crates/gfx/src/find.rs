@@ -0,0 +1,371 @@
+//! Find-in-page over painted text.
+//!
+//! Matching runs over the text primitives of a [`PaintArtifact`], i.e. the
+//! inline text fragments layout actually placed. Fragments are joined in paint
+//! order so a query can span words (layout emits one fragment per word or
+//! space) and lines. Each match maps back to one highlight rectangle per
+//! fragment it touches, in layout coordinates.
+
+use layout::Rectangle;
+
+use crate::paint::{PaintArtifact, PaintNode, PaintPrimitive};
+
+/// Document-scoped find bar state.
+#[derive(Debug, Default)]
+pub struct FindState {
+    open: bool,
+    query: String,
+    active: usize,
+    match_count: usize,
+    scroll_to_active: bool,
+}
+
+impl FindState {
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    /// Close the find bar and drop its highlights.
+    pub fn close(&mut self) {
+        self.open = false;
+        self.match_count = 0;
+        self.active = 0;
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Mutable access for the find bar text field. Call
+    /// [`FindState::query_changed`] after edits.
+    pub fn query_mut(&mut self) -> &mut String {
+        &mut self.query
+    }
+
+    /// Restart from the first match after the query was edited.
+    pub fn query_changed(&mut self) {
+        self.active = 0;
+        self.scroll_to_active = true;
+    }
+
+    /// Returns `true` if highlights should be computed this frame.
+    pub fn is_searching(&self) -> bool {
+        self.open && !self.query.is_empty()
+    }
+
+    /// Number of matches found in the last painted frame.
+    pub fn match_count(&self) -> usize {
+        self.match_count
+    }
+
+    /// Index of the active match, if any.
+    pub fn active(&self) -> Option<usize> {
+        (self.match_count > 0).then_some(self.active)
+    }
+
+    pub fn next(&mut self) {
+        if self.match_count > 0 {
+            self.active = (self.active + 1) % self.match_count;
+            self.scroll_to_active = true;
+        }
+    }
+
+    pub fn previous(&mut self) {
+        if self.match_count > 0 {
+            self.active = (self.active + self.match_count - 1) % self.match_count;
+            self.scroll_to_active = true;
+        }
+    }
+
+    /// Record the match count for the current frame, clamping the active index.
+    pub fn set_match_count(&mut self, count: usize) {
+        self.match_count = count;
+        if self.active >= count {
+            self.active = 0;
+        }
+    }
+
+    /// Returns `true` once after the active match changed and the viewport
+    /// should scroll to it.
+    pub fn take_scroll_request(&mut self) -> bool {
+        std::mem::take(&mut self.scroll_to_active) && self.match_count > 0
+    }
+
+    /// Matches from the previous document are meaningless; keep the query and
+    /// open state so the bar survives navigation.
+    pub fn clear_for_navigation(&mut self) {
+        self.active = 0;
+        self.match_count = 0;
+        self.scroll_to_active = self.is_searching();
+    }
+}
+
+/// One occurrence of the query, covering one or more fragments.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FindMatch {
+    pub rects: Vec<Rectangle>,
+}
+
+impl FindMatch {
+    /// Bounding box of all rectangles of this match.
+    pub fn bounds(&self) -> Rectangle {
+        let mut rects = self.rects.iter();
+        let Some(first) = rects.next() else {
+            return Rectangle {
+                x: 0.0,
+                y: 0.0,
+                width: 0.0,
+                height: 0.0,
+            };
+        };
+        let (mut x0, mut y0) = (first.x, first.y);
+        let (mut x1, mut y1) = (first.x + first.width, first.y + first.height);
+        for r in rects {
+            x0 = x0.min(r.x);
+            y0 = y0.min(r.y);
+            x1 = x1.max(r.x + r.width);
+            y1 = y1.max(r.y + r.height);
+        }
+        Rectangle {
+            x: x0,
+            y: y0,
+            width: x1 - x0,
+            height: y1 - y0,
+        }
+    }
+}
+
+struct Segment<'a> {
+    text: &'a str,
+    rect: Rectangle,
+    font_size_px: f32,
+}
+
+/// Find case-insensitive, non-overlapping occurrences of `query`.
+///
+/// `measure(text, font_size_px)` returns the painted width of `text`; it is
+/// only called for matches that start or end inside a fragment.
+pub fn find_text_matches(
+    artifact: &PaintArtifact,
+    query: &str,
+    measure: &dyn Fn(&str, f32) -> f32,
+) -> Vec<FindMatch> {
+    let needle: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+    if needle.is_empty() {
+        return Vec::new();
+    }
+
+    let mut segments = Vec::new();
+    collect_segments(artifact.tree().root(), &mut segments);
+
+    // Flattened haystack: (lowercased char, segment index, byte offset in the
+    // segment). Synthetic separators between lines carry no segment.
+    let mut haystack: Vec<(char, Option<(usize, usize)>)> = Vec::new();
+    for (idx, segment) in segments.iter().enumerate() {
+        if let Some(prev) = idx.checked_sub(1).map(|i| &segments[i])
+            && (prev.rect.y - segment.rect.y).abs() > 0.5
+            && !prev.text.ends_with(char::is_whitespace)
+            && !segment.text.starts_with(char::is_whitespace)
+        {
+            haystack.push((' ', None));
+        }
+        for (offset, ch) in segment.text.char_indices() {
+            for lower in ch.to_lowercase() {
+                haystack.push((lower, Some((idx, offset))));
+            }
+        }
+    }
+
+    let mut matches = Vec::new();
+    let mut start = 0;
+    while start + needle.len() <= haystack.len() {
+        let is_match = haystack[start..start + needle.len()]
+            .iter()
+            .zip(&needle)
+            .all(|((hay, _), want)| hay == want || (hay.is_whitespace() && want.is_whitespace()));
+        if !is_match {
+            start += 1;
+            continue;
+        }
+        let end = start + needle.len();
+        let rects = match_rects(&segments, &haystack[start..end], measure);
+        if !rects.is_empty() {
+            matches.push(FindMatch { rects });
+        }
+        start = end;
+    }
+    matches
+}
+
+fn collect_segments<'a>(node: &'a PaintNode, out: &mut Vec<Segment<'a>>) {
+    let texts = |primitives: &'a [PaintPrimitive]| {
+        primitives.iter().filter_map(|primitive| match primitive {
+            PaintPrimitive::Text(text) => Some(Segment {
+                text: &text.text,
+                rect: text.rect,
+                font_size_px: text.font_size_px,
+            }),
+            _ => None,
+        })
+    };
+    out.extend(texts(node.primitives()));
+    for child in node.children() {
+        collect_segments(child, out);
+    }
+    out.extend(texts(node.post_primitives()));
+}
+
+fn match_rects(
+    segments: &[Segment<'_>],
+    span: &[(char, Option<(usize, usize)>)],
+    measure: &dyn Fn(&str, f32) -> f32,
+) -> Vec<Rectangle> {
+    let mut rects = Vec::new();
+    let mut i = 0;
+    while i < span.len() {
+        let Some((seg_idx, first_offset)) = span[i].1 else {
+            i += 1;
+            continue;
+        };
+        // Extend over consecutive chars of the same segment.
+        let mut last_offset = first_offset;
+        while i < span.len() && span[i].1.is_some_and(|(idx, _)| idx == seg_idx) {
+            last_offset = span[i].1.map_or(last_offset, |(_, offset)| offset);
+            i += 1;
+        }
+
+        let segment = &segments[seg_idx];
+        let text = segment.text;
+        let end = last_offset + text[last_offset..].chars().next().map_or(0, char::len_utf8);
+        let x0 = if first_offset == 0 {
+            0.0
+        } else {
+            measure(&text[..first_offset], segment.font_size_px)
+        };
+        let x1 = if end == text.len() {
+            segment.rect.width
+        } else {
+            measure(&text[..end], segment.font_size_px)
+        };
+        rects.push(Rectangle {
+            x: segment.rect.x + x0,
+            y: segment.rect.y,
+            width: (x1 - x0).max(0.0),
+            height: segment.rect.height,
+        });
+    }
+    rects
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paint::PaintPhaseInput;
+    use css::{ComputedStyle, Length};
+    use html::{Node, internal::Id};
+    use layout::{LayoutPhaseInput, TextMeasurer};
+
+    struct TestMeasurer;
+
+    impl TextMeasurer for TestMeasurer {
+        fn measure(&self, text: &str, _style: &ComputedStyle) -> f32 {
+            text.chars().count() as f32 * 8.0
+        }
+
+        fn line_height(&self, style: &ComputedStyle) -> f32 {
+            let Length::Px(px) = style.font_size();
+            px * 1.2
+        }
+    }
+
+    fn measure(text: &str, _font_size_px: f32) -> f32 {
+        text.chars().count() as f32 * 8.0
+    }
+
+    fn matches_for(text: &str, width: &str, query: &str) -> Vec<FindMatch> {
+        let dom = Node::Document {
+            id: Id(1),
+            doctype: None,
+            children: vec![html::internal::node_element_from_parts(
+                Id(2),
+                html::internal::html_name("div"),
+                Vec::new(),
+                vec![
+                    ("display".to_string(), "block".to_string()),
+                    ("width".to_string(), width.to_string()),
+                ],
+                vec![Node::Text {
+                    id: Id(3),
+                    text: text.to_string(),
+                }],
+            )],
+        };
+        let styled = css::build_style_tree(&dom, None);
+        let layout =
+            layout::layout_document(LayoutPhaseInput::new(&styled, 500.0, &TestMeasurer, None));
+        let artifact =
+            PaintArtifact::from_phase_input(PaintPhaseInput::new(&layout), &TestMeasurer);
+        find_text_matches(&artifact, query, &measure)
+    }
+
+    #[test]
+    fn matches_are_case_insensitive_and_non_overlapping() {
+        // "bANAnA bandANA": the second "ana" in "banana" overlaps the first.
+        let found = matches_for("Banana bandana", "400px", "ANA");
+        assert_eq!(found.len(), 2);
+        assert!(matches_for("Banana", "400px", "").is_empty());
+        assert!(matches_for("Banana", "400px", "kiwi").is_empty());
+    }
+
+    #[test]
+    fn partial_word_match_is_measured_within_its_fragment() {
+        let found = matches_for("Hello world", "400px", "llo");
+        assert_eq!(found.len(), 1);
+        let [rect] = found[0].rects.as_slice() else {
+            panic!("expected one rect, got {:?}", found[0].rects);
+        };
+        let whole = matches_for("Hello world", "400px", "hello")[0].rects[0];
+        assert_eq!(rect.x - whole.x, 16.0);
+        assert_eq!(rect.width, 24.0);
+    }
+
+    #[test]
+    fn query_spanning_words_and_wrapped_lines_yields_one_match() {
+        // 60px fits "brave " but not "brave new" on one line.
+        let found = matches_for("brave new world", "60px", "brave new");
+        assert_eq!(found.len(), 1);
+        let rects = &found[0].rects;
+        assert!(rects.len() >= 2, "{rects:?}");
+        let bounds = found[0].bounds();
+        assert!(
+            bounds.height > rects[0].height,
+            "match should span two lines"
+        );
+    }
+
+    #[test]
+    fn active_match_cycles_and_requests_scroll() {
+        let mut state = FindState::default();
+        state.open();
+        state.query_mut().push('a');
+        state.query_changed();
+        state.set_match_count(3);
+        assert!(state.take_scroll_request());
+        assert!(!state.take_scroll_request());
+
+        state.previous();
+        assert_eq!(state.active(), Some(2));
+        state.next();
+        assert_eq!(state.active(), Some(0));
+        assert!(state.take_scroll_request());
+
+        state.set_match_count(0);
+        assert_eq!(state.active(), None);
+        state.close();
+        assert!(!state.is_searching());
+    }
+}
@@ -0,0 +1,167 @@
+//! Resolve a URL fragment (`#section`) to the element it scrolls to.
+
+use html::Node;
+use layout::inline::{InlineAction, InlineFragment};
+use layout::{BoxKind, LayoutBox, Rectangle, TextMeasurer, layout_inline_for_paint};
+
+/// Layout y coordinate of the target of `fragment`: the first element whose
+/// `id` matches, otherwise the first `<a name>` that matches. An empty
+/// fragment or an unmatched `top` targets the document start.
+pub(crate) fn fragment_target_y(
+    root: &LayoutBox<'_, '_>,
+    fragment: &str,
+    measurer: &dyn TextMeasurer,
+) -> Option<f32> {
+    if fragment.is_empty() {
+        return Some(0.0);
+    }
+
+    let mut path = Vec::new();
+    let found = find_path(root, &mut path, &|node| node.attr("id") == Some(fragment))
+        || find_path(root, &mut path, &|node| {
+            is_html_anchor(node) && node.attr("name") == Some(fragment)
+        });
+    if !found {
+        return fragment.eq_ignore_ascii_case("top").then_some(0.0);
+    }
+
+    let target = *path.last()?;
+    if target.kind == BoxKind::Block {
+        return Some(target.rect.y);
+    }
+
+    // Inline-level boxes have no standalone geometry; find the target's first
+    // fragment in the inline layout of its containing block.
+    let block = path
+        .iter()
+        .rev()
+        .skip(1)
+        .find(|lb| lb.kind == BoxKind::Block)?;
+    let (content_x, content_width) = block.content_x_and_width();
+    let content_rect = Rectangle {
+        x: content_x,
+        y: block.content_y(),
+        width: content_width,
+        height: block.content_height(),
+    };
+    let target_id = target.node_id();
+    let first_fragment_y = layout_inline_for_paint(measurer, content_rect, block)
+        .iter()
+        .flat_map(|line| &line.fragments)
+        .find(|frag| {
+            let (action, layout) = match &frag.kind {
+                InlineFragment::Text { action, .. } => (action, None),
+                InlineFragment::Box { action, layout, .. }
+                | InlineFragment::Replaced { action, layout, .. } => (action, *layout),
+            };
+            matches!(action, Some(InlineAction { target, .. }) if *target == target_id)
+                || layout.is_some_and(|lb| lb.node_id() == target_id)
+        })
+        .map(|frag| frag.paint_rect.rect().y);
+    Some(first_fragment_y.unwrap_or(content_rect.y))
+}
+
+/// Depth-first search in document order; on success `path` holds the boxes
+/// from `node` down to the match.
+fn find_path<'a, 'layout, 'dom>(
+    node: &'a LayoutBox<'layout, 'dom>,
+    path: &mut Vec<&'a LayoutBox<'layout, 'dom>>,
+    matches: &dyn Fn(&Node) -> bool,
+) -> bool {
+    path.push(node);
+    if node.direct_node_id().is_some() && matches(node.node.node) {
+        return true;
+    }
+    for child in &node.children {
+        if find_path(child, path, matches) {
+            return true;
+        }
+    }
+    path.pop();
+    false
+}
+
+fn is_html_anchor(node: &Node) -> bool {
+    matches!(
+        node,
+        Node::Element { element }
+            if element.namespace() == html::ElementNamespace::Html && element.name() == "a"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EguiTextMeasurer;
+    use css::build_style_tree;
+    use egui::Context;
+    use html::internal::Id;
+
+    fn elem(id: u32, name: &str, attributes: &[(&str, &str)], children: Vec<Node>) -> Node {
+        html::internal::node_element_from_parts(
+            Id(id),
+            html::internal::html_name(name),
+            attributes
+                .iter()
+                .map(|(name, value)| html::internal::unqualified_attribute(name, *value))
+                .collect(),
+            Vec::new(),
+            children,
+        )
+    }
+
+    fn text(id: u32, value: &str) -> Node {
+        Node::Text {
+            id: Id(id),
+            text: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn resolves_ids_then_anchor_names_then_top() {
+        let ctx = Context::default();
+        let _ = ctx.run(Default::default(), |_| {});
+        let measurer = EguiTextMeasurer::new(&ctx);
+
+        let dom = Node::Document {
+            id: Id(0),
+            doctype: None,
+            children: vec![elem(
+                1,
+                "body",
+                &[],
+                vec![
+                    elem(2, "p", &[], vec![text(3, "intro")]),
+                    elem(4, "h2", &[("id", "usage")], vec![text(5, "Usage")]),
+                    elem(
+                        6,
+                        "p",
+                        &[],
+                        vec![
+                            text(7, "see "),
+                            elem(8, "a", &[("name", "notes")], vec![text(9, "notes")]),
+                        ],
+                    ),
+                ],
+            )],
+        };
+        let style_root = build_style_tree(&dom, None);
+        let layout_root = layout::layout_block_tree(&style_root, 600.0, &measurer, None);
+        let heading = crate::text_control::find_layout_box_by_id(&layout_root, Id(4)).unwrap();
+        let notes_block = crate::text_control::find_layout_box_by_id(&layout_root, Id(6)).unwrap();
+
+        assert_eq!(
+            fragment_target_y(&layout_root, "usage", &measurer),
+            Some(heading.rect.y)
+        );
+        let notes = fragment_target_y(&layout_root, "notes", &measurer).unwrap();
+        assert!(
+            notes >= notes_block.rect.y && notes < notes_block.rect.y + notes_block.rect.height,
+            "anchor y {notes} outside its paragraph {:?}",
+            notes_block.rect
+        );
+        assert!(heading.rect.y > 0.0);
+        assert_eq!(fragment_target_y(&layout_root, "TOP", &measurer), Some(0.0));
+        assert_eq!(fragment_target_y(&layout_root, "missing", &measurer), None);
+    }
+}
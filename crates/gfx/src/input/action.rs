@@ -1,4 +1,28 @@
+use core_types::RequestPayload;
+use html::internal::Id;
+
+/// A form submitted by a button click or implicit submission.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FormSubmission {
+    /// Resolved action URL; a GET submission carries its data in the query.
+    pub url: String,
+    /// Method and body to send; a plain GET for GET forms.
+    pub payload: RequestPayload,
+}
+
 #[derive(Clone, Debug)]
 pub enum PageAction {
     Navigate(String),
+    /// Navigate to the response of a form submission.
+    Submit(FormSubmission),
+    /// Open the URL in a new tab, leaving the current page in place.
+    OpenInNewTab(String),
+    /// Follow a `target="_blank"` link in a new, focused tab.
+    NavigateInNewTab(String),
+    /// Save the already-loaded image with this resolved URL to disk.
+    SaveImage(String),
+    /// Open the native file picker for the file input with this id.
+    PickFiles(Id),
+    Reload,
+    ViewSource,
 }
@@ -1,6 +1,11 @@
+use super::{TouchState, clamp_scroll};
+use crate::find::FindState;
+use crate::selection::PageSelection;
 use crate::textarea::TextareaState;
+use css::Resize;
 use html::internal::Id;
 use layout::{HitKind, Rectangle};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ActiveTarget {
@@ -12,20 +17,87 @@ pub struct ActiveTarget {
 pub struct InputDragState {
     pub input_id: Id,
     pub rect: Rectangle,
+    /// Auto-scroll distance built up while the pointer is past a textarea
+    /// edge, spent one line at a time.
+    pub autoscroll_carry: f32,
+}
+
+/// Drag of a textarea's resize grip.
+#[derive(Debug, Clone, Copy)]
+pub struct ControlResizeState {
+    pub control_id: Id,
+    /// Axes the control's `resize` value lets the drag change.
+    pub resize: Resize,
+    /// Pointer position (layout coordinates) where the drag started.
+    pub start_pos: (f32, f32),
+    /// Border-box size of the control when the drag started.
+    pub start_size: (f32, f32),
+}
+
+/// What the page context menu was opened over. Empty means the page
+/// background.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContextMenuTarget {
+    /// Resolved destination of the link under the pointer.
+    pub link: Option<String>,
+    /// Resolved source of the image under the pointer.
+    pub image: Option<String>,
+    /// Selection of the focused text control under the pointer, or the page
+    /// selection when opened elsewhere.
+    pub selected_text: Option<String>,
+}
+
+impl ContextMenuTarget {
+    pub fn is_page_background(&self) -> bool {
+        self.link.is_none() && self.image.is_none() && self.selected_text.is_none()
+    }
 }
 
 #[derive(Default, Debug)]
 pub struct InteractionState {
     pub hover: Option<Id>,
     pub hover_kind: Option<HitKind>,
+    /// Resolved destination of the hovered link, for the status bar.
+    pub hover_href: Option<String>,
+    /// Target of the last secondary click, shown by the context menu.
+    pub context_menu: Option<ContextMenuTarget>,
     pub active: Option<ActiveTarget>,
     pub focused_node_id: Option<Id>,
     pub focused_kind: Option<HitKind>,
     pub input_drag: Option<InputDragState>,
+    pub control_resize: Option<ControlResizeState>,
+    /// Border-box sizes the user gave controls through their resize grip,
+    /// fed back into layout as overrides.
+    pub resized_controls: HashMap<Id, (f32, f32)>,
     pub focused_input_rect: Option<Rectangle>,
     pub(crate) textarea: TextareaState,
     pub last_viewport_width: Option<f32>,
+    /// Height of the scroll area the page is shown in, as of the last frame.
+    pub last_viewport_height: Option<f32>,
     pub last_layout_root_size: Option<(f32, f32)>,
+    /// Vertical scroll offset of the page viewport as of the last frame.
+    pub scroll_y: f32,
+    /// Scroll offset to apply once the document is tall enough (e.g. after
+    /// history navigation). Cleared when reached or when the user scrolls
+    /// or drags the scrollbar.
+    pub pending_scroll_y: Option<f32>,
+    /// Where an animated scroll (wheel, scrolling keys, find, fragment) is
+    /// heading. The browser moves `pending_scroll_y` towards it frame by
+    /// frame and clears it on arrival.
+    pub scroll_target: Option<f32>,
+    /// URL fragment to scroll into view once its target element is laid out.
+    /// Cleared when resolved or when the user scrolls.
+    pub pending_fragment: Option<String>,
+    pub find: FindState,
+    /// Page text selected by dragging the pointer.
+    pub page_selection: PageSelection,
+    /// Debug overlay showing the box model of the hovered layout box. Kept
+    /// across navigations.
+    pub box_overlay: bool,
+    pub(crate) touch: TouchState,
+    /// Zoom factor, relative to the current zoom, that a pinch on the page
+    /// asked for this frame. Taken by the browser, which owns page zoom.
+    pub pinch_zoom: Option<f32>,
 }
 
 impl InteractionState {
@@ -43,14 +115,49 @@ impl InteractionState {
         self.textarea.clear_focus();
     }
 
+    /// Scroll offset the page is settling at: the target of a running
+    /// animation or pending restore, else the current offset.
+    pub fn scroll_destination(&self) -> f32 {
+        self.scroll_target
+            .or(self.pending_scroll_y)
+            .unwrap_or(self.scroll_y)
+    }
+
+    /// Clamp a pending scroll restore to the scroll range of the last laid
+    /// out document. Called once the document is complete, so a restore past
+    /// its end settles at the bottom instead of being retried every frame.
+    pub fn clamp_pending_scroll(&mut self) {
+        let (Some(offset), Some(viewport_height), Some(_)) = (
+            self.pending_scroll_y,
+            self.last_viewport_height,
+            self.last_layout_root_size,
+        ) else {
+            return;
+        };
+        self.pending_scroll_y = Some(clamp_scroll(self, viewport_height, offset));
+    }
+
     pub fn clear_for_navigation(&mut self) {
         self.hover = None;
         self.hover_kind = None;
+        self.hover_href = None;
+        self.context_menu = None;
         self.active = None;
         self.clear_focus();
         self.input_drag = None;
+        self.control_resize = None;
+        self.resized_controls.clear();
         self.textarea.clear_for_navigation();
         self.last_viewport_width = None;
+        self.last_viewport_height = None;
         self.last_layout_root_size = None;
+        self.scroll_y = 0.0;
+        self.pending_scroll_y = None;
+        self.scroll_target = None;
+        self.pending_fragment = None;
+        self.find.clear_for_navigation();
+        self.page_selection.clear();
+        self.touch.stop_coasting();
+        self.pinch_zoom = None;
     }
 }
@@ -2,12 +2,19 @@ mod action;
 mod interaction;
 mod route;
 mod store;
+mod touch;
 
-pub use action::PageAction;
-pub use interaction::{ActiveTarget, InputDragState, InteractionState};
+pub use action::{FormSubmission, PageAction};
+pub use interaction::{
+    ActiveTarget, ContextMenuTarget, ControlResizeState, InputDragState, InteractionState,
+};
 pub use route::FormControlHandler;
-pub(crate) use route::{FrameInputCtx, route_frame_input};
+pub(crate) use route::{
+    FrameInputCtx, clamp_scroll, drag_autoscroll_delta, keyboard_scroll_target, route_frame_input,
+    wheel_scroll_target,
+};
 pub use store::{InputValueStore, SelectionRange, from_input_id, to_input_id};
+pub(crate) use touch::{TouchState, pinch_zoom_factor, touch_scroll_target};
 
 // Re-export the core InputStore trait for routing abstraction
 pub use input_core::InputStore;
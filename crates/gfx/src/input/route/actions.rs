@@ -1,5 +1,4 @@
 use super::super::{InteractionState, PageAction, to_input_id};
-use crate::util::resolve_relative_url;
 use input_core::InputStore;
 use layout::HitKind;
 use layout::hit_test::HitResult;
@@ -9,9 +8,12 @@ pub(super) struct ActivationResult {
     pub(super) request_repaint: bool,
 }
 
+/// Act on a click released over `hit`. With `open_in_background`, a link
+/// opens in a background tab and the page keeps its focus.
 pub(super) fn activate_release_target<S: InputStore + ?Sized, F>(
     hit: HitResult,
     base_url: Option<&str>,
+    open_in_background: bool,
     input_values: &mut S,
     form_controls: &F,
     interaction: &mut InteractionState,
@@ -20,11 +22,16 @@ where
     F: super::FormControlHandler<S>,
 {
     match hit.kind {
+        HitKind::Link if open_in_background => ActivationResult {
+            action: link_url(&hit, base_url).map(PageAction::OpenInNewTab),
+            request_repaint: false,
+        },
         HitKind::Link => {
-            let action = hit
-                .href
-                .as_deref()
-                .and_then(|href| resolve_relative_url(base_url, href).map(PageAction::Navigate));
+            let action = link_url(&hit, base_url).map(if hit.opens_new_tab {
+                PageAction::NavigateInNewTab
+            } else {
+                PageAction::Navigate
+            });
             interaction.clear_focus();
             ActivationResult {
                 action,
@@ -53,7 +60,16 @@ where
         HitKind::Button => {
             interaction.clear_focus();
             ActivationResult {
-                action: None,
+                action: form_controls
+                    .form_submission(input_values, to_input_id(hit.node_id), base_url)
+                    .map(PageAction::Submit),
+                request_repaint: true,
+            }
+        }
+        HitKind::FileInput => {
+            interaction.clear_focus();
+            ActivationResult {
+                action: Some(PageAction::PickFiles(hit.node_id)),
                 request_repaint: true,
             }
         }
@@ -67,6 +83,16 @@ where
     }
 }
 
+/// Resolved destination of a link hit.
+pub(super) fn link_url(hit: &HitResult, base_url: Option<&str>) -> Option<String> {
+    if hit.kind != HitKind::Link {
+        return None;
+    }
+    hit.href
+        .as_deref()
+        .and_then(|href| weburl::resolve_str(base_url, href))
+}
+
 pub(super) fn activate_checkbox<S: InputStore + ?Sized>(
     input_values: &mut S,
     node_id: html::internal::Id,
@@ -0,0 +1,122 @@
+use super::super::{ContextMenuTarget, InteractionState, PageAction, to_input_id};
+use super::pointer::{PointerCtx, hit_at_pointer, pointer_pos};
+use egui::{Popup, Response, SetOpenCommand};
+use input_core::InputStore;
+use layout::HitKind;
+use layout::hit_test::HitResult;
+
+/// Classify what a secondary click landed on so the context menu can offer
+/// target-aware items.
+pub(super) fn capture_context_target<S: InputStore + ?Sized>(
+    ctx: PointerCtx<'_, '_, '_>,
+    base_url: Option<&str>,
+    input_values: &S,
+    interaction: &mut InteractionState,
+) -> bool {
+    let PointerCtx {
+        ui,
+        resp,
+        content_rect,
+        origin,
+        layout_root,
+        measurer,
+    } = ctx;
+    if !ui.input(|i| i.pointer.secondary_clicked()) || !resp.hovered() {
+        return false;
+    }
+    if !pointer_pos(resp, ui, true).is_some_and(|pos| content_rect.contains(pos)) {
+        return false;
+    }
+
+    let hit = hit_at_pointer(resp, ui, content_rect, origin, layout_root, measurer, true);
+    interaction.context_menu = Some(context_target_for_hit(
+        hit.as_ref(),
+        base_url,
+        input_values,
+        interaction,
+    ));
+    true
+}
+
+fn context_target_for_hit<S: InputStore + ?Sized>(
+    hit: Option<&HitResult>,
+    base_url: Option<&str>,
+    input_values: &S,
+    interaction: &InteractionState,
+) -> ContextMenuTarget {
+    let Some(hit) = hit else {
+        return ContextMenuTarget::default();
+    };
+
+    let link = hit
+        .href
+        .as_deref()
+        .filter(|_| hit.kind == HitKind::Link)
+        .and_then(|href| weburl::resolve_str(base_url, href));
+    let selected_text = if hit.kind == HitKind::Input {
+        (interaction.focused_node_id == Some(hit.node_id))
+            .then(|| input_values.copy_selection(to_input_id(hit.node_id)))
+            .flatten()
+    } else {
+        interaction
+            .page_selection
+            .selected_text()
+            .map(str::to_owned)
+    };
+
+    ContextMenuTarget {
+        link,
+        image: hit.image_src.clone(),
+        selected_text,
+    }
+}
+
+/// Show the page context menu for the captured target. Copy items write to
+/// the clipboard directly; everything else is returned as a page action.
+pub(super) fn show_context_menu(
+    resp: &Response,
+    opened: bool,
+    interaction: &InteractionState,
+) -> Option<PageAction> {
+    let target = interaction.context_menu.as_ref()?;
+    let mut action = None;
+
+    Popup::menu(resp)
+        .open_memory(opened.then_some(SetOpenCommand::Bool(true)))
+        .at_pointer_fixed()
+        .show(|ui| {
+            if let Some(link) = &target.link {
+                if ui.button("Open link in new tab").clicked() {
+                    action = Some(PageAction::OpenInNewTab(link.clone()));
+                }
+                if ui.button("Copy link address").clicked() {
+                    ui.ctx().copy_text(link.clone());
+                }
+            }
+            if let Some(image) = &target.image {
+                if target.link.is_some() {
+                    ui.separator();
+                }
+                if ui.button("Save image").clicked() {
+                    action = Some(PageAction::SaveImage(image.clone()));
+                }
+                if ui.button("Copy image address").clicked() {
+                    ui.ctx().copy_text(image.clone());
+                }
+            }
+            if let Some(text) = &target.selected_text
+                && ui.button("Copy").clicked()
+            {
+                ui.ctx().copy_text(text.clone());
+            }
+            if target.is_page_background() {
+                if ui.button("Reload").clicked() {
+                    action = Some(PageAction::Reload);
+                }
+                if ui.button("View page source").clicked() {
+                    action = Some(PageAction::ViewSource);
+                }
+            }
+        });
+    action
+}
@@ -11,6 +11,7 @@ pub(super) struct HoverCtx<'a, 'layout, 'dom> {
     pub(super) layout_root: &'a LayoutBox<'layout, 'dom>,
     pub(super) measurer: &'a EguiTextMeasurer,
     pub(super) layout_changed: bool,
+    pub(super) base_url: Option<&'a str>,
     pub(super) interaction: &'a mut InteractionState,
 }
 
@@ -23,6 +24,7 @@ pub(super) fn update_hover_and_cursor(ctx: HoverCtx<'_, '_, '_>) {
         layout_root,
         measurer,
         layout_changed,
+        base_url,
         interaction,
     } = ctx;
     // Hover hit-testing can be expensive (inline layout), so only recompute when needed.
@@ -48,9 +50,15 @@ pub(super) fn update_hover_and_cursor(ctx: HoverCtx<'_, '_, '_>) {
     if hover_needs_update {
         interaction.hover = hover_hit.as_ref().map(|h| h.node_id);
         interaction.hover_kind = hover_hit.as_ref().map(|h| h.kind);
+        interaction.hover_href = hover_hit
+            .as_ref()
+            .filter(|h| h.kind == HitKind::Link)
+            .and_then(|h| h.href.as_deref())
+            .and_then(|href| weburl::resolve_str(base_url, href));
     } else if hover_pos.is_none() {
         interaction.hover = None;
         interaction.hover_kind = None;
+        interaction.hover_href = None;
     }
 
     if let Some(kind) = hover_hit
@@ -64,7 +72,11 @@ pub(super) fn update_hover_and_cursor(ctx: HoverCtx<'_, '_, '_>) {
 
 fn apply_cursor_icon(ui: &mut Ui, kind: HitKind) {
     match kind {
-        HitKind::Link | HitKind::Checkbox | HitKind::Radio | HitKind::Button => {
+        HitKind::Link
+        | HitKind::Checkbox
+        | HitKind::Radio
+        | HitKind::FileInput
+        | HitKind::Button => {
             ui.output_mut(|o| o.cursor_icon = CursorIcon::PointingHand);
         }
         HitKind::Input => {
@@ -1,4 +1,5 @@
-use super::super::{InteractionState, to_input_id};
+use super::super::{InteractionState, PageAction, to_input_id};
+use super::actions::ActivationResult;
 use crate::EguiTextMeasurer;
 use crate::text_control::consume_focus_nav_keys;
 use egui::{Event, Key, Ui};
@@ -12,12 +13,16 @@ pub(super) fn handle_focused_keyboard_input<
     ui: &mut Ui,
     layout_root: &LayoutBox<'_, '_>,
     measurer: &EguiTextMeasurer,
+    base_url: Option<&str>,
     input_values: &mut S,
     form_controls: &F,
     interaction: &mut InteractionState,
-) -> bool {
+) -> ActivationResult {
     let Some(focus_id) = interaction.focused_node_id else {
-        return false;
+        return ActivationResult {
+            action: None,
+            request_repaint: false,
+        };
     };
 
     let mut value_changed = false;
@@ -31,6 +36,8 @@ pub(super) fn handle_focused_keyboard_input<
     let is_textarea = matches!(focused_replaced_kind, Some(ReplacedKind::TextArea));
 
     let mut enter_pressed = false;
+    let mut copied_text = None;
+    let mut submit_requested = false;
     let mut saw_text_newline = false;
     let activation_rect = interaction
         .focused_input_rect
@@ -57,7 +64,14 @@ pub(super) fn handle_focused_keyboard_input<
         for evt in &i.events {
             match focused_kind {
                 Some(HitKind::Input) => match evt {
-                    Event::Text(t) => {
+                    Event::Copy => {
+                        copied_text = input_values.copy_selection(to_input_id(focus_id));
+                    }
+                    Event::Cut => {
+                        copied_text = input_values.cut_selection(to_input_id(focus_id));
+                        value_changed |= copied_text.is_some();
+                    }
+                    Event::Text(t) | Event::Paste(t) => {
                         if is_textarea {
                             interaction.textarea.clear_preferred_x();
                             saw_text_newline |= t.contains('\n') || t.contains('\r');
@@ -98,6 +112,8 @@ pub(super) fn handle_focused_keyboard_input<
                                     );
                             }
                         } else {
+                            // Enter in a single-line field submits its form.
+                            submit_requested |= *key == Key::Enter;
                             let (value, caret) = super::text_input::handle_key_event(
                                 input_values,
                                 focus_id,
@@ -177,6 +193,10 @@ pub(super) fn handle_focused_keyboard_input<
         }
     });
 
+    if let Some(text) = copied_text {
+        ui.ctx().copy_text(text);
+    }
+
     if is_textarea && enter_pressed && !saw_text_newline {
         interaction.textarea.clear_preferred_x();
         input_values.insert_text_multiline(to_input_id(focus_id), "\n");
@@ -218,5 +238,16 @@ pub(super) fn handle_focused_keyboard_input<
         }
     }
 
-    changed
+    let action = if submit_requested {
+        form_controls
+            .form_submission(input_values, to_input_id(focus_id), base_url)
+            .map(PageAction::Submit)
+    } else {
+        None
+    };
+
+    ActivationResult {
+        action,
+        request_repaint: changed,
+    }
 }
@@ -1,12 +1,19 @@
 mod actions;
+mod context_menu;
 mod focus;
 mod hover;
 mod keyboard;
 mod pointer;
+mod resize;
+mod scroll;
+mod selection;
 mod text_input;
 mod textarea;
 mod types;
 
+pub(crate) use scroll::{
+    autoscroll_dt, clamp_scroll, drag_autoscroll_delta, keyboard_scroll_target, wheel_scroll_target,
+};
 pub use types::FormControlHandler;
 pub(crate) use types::FrameInputCtx;
 
@@ -24,6 +31,8 @@ mod tests;
 pub(crate) struct FrameInputResult {
     pub(crate) action: Option<PageAction>,
     pub(crate) requested_followup_render: bool,
+    /// A control was resized, so layout must run again.
+    pub(crate) requested_followup_relayout: bool,
 }
 
 pub(crate) fn route_frame_input<S: InputStore + ?Sized, F: FormControlHandler<S>>(
@@ -38,6 +47,7 @@ pub(crate) fn route_frame_input<S: InputStore + ?Sized, F: FormControlHandler<S>
         measurer,
         layout_changed,
         fragment_rects,
+        page_text,
         base_url,
         input_values,
         form_controls,
@@ -55,23 +65,11 @@ pub(crate) fn route_frame_input<S: InputStore + ?Sized, F: FormControlHandler<S>
         layout_root,
         measurer,
         layout_changed,
+        base_url,
         interaction,
     });
 
-    request_repaint |= pointer::handle_pointer_press(
-        pointer::PointerCtx {
-            ui,
-            resp: &resp,
-            content_rect,
-            origin,
-            layout_root,
-            measurer,
-        },
-        input_values,
-        interaction,
-    );
-
-    request_repaint |= pointer::handle_pointer_drag(
+    let control_resize = resize::handle_control_resize(
         pointer::PointerCtx {
             ui,
             resp: &resp,
@@ -80,13 +78,86 @@ pub(crate) fn route_frame_input<S: InputStore + ?Sized, F: FormControlHandler<S>
             layout_root,
             measurer,
         },
-        layout_changed,
         fragment_rects,
-        input_values,
         interaction,
     );
 
-    let release = pointer::handle_pointer_release(
+    let mut action = None;
+    if !control_resize.consumed {
+        request_repaint |= pointer::handle_pointer_press(
+            pointer::PointerCtx {
+                ui,
+                resp: &resp,
+                content_rect,
+                origin,
+                layout_root,
+                measurer,
+            },
+            input_values,
+            interaction,
+        );
+
+        request_repaint |= pointer::handle_pointer_drag(
+            pointer::PointerCtx {
+                ui,
+                resp: &resp,
+                content_rect,
+                origin,
+                layout_root,
+                measurer,
+            },
+            layout_changed,
+            fragment_rects,
+            input_values,
+            interaction,
+        );
+
+        if let Some(page_text) = page_text {
+            selection::handle_page_selection(
+                pointer::PointerCtx {
+                    ui,
+                    resp: &resp,
+                    content_rect,
+                    origin,
+                    layout_root,
+                    measurer,
+                },
+                page_text,
+                interaction,
+            );
+        }
+
+        let release = pointer::handle_pointer_release(
+            pointer::PointerCtx {
+                ui,
+                resp: &resp,
+                content_rect,
+                origin,
+                layout_root,
+                measurer,
+            },
+            base_url,
+            input_values,
+            form_controls,
+            interaction,
+        );
+        request_repaint |= release.request_repaint;
+        action = release.action.or_else(|| {
+            pointer::handle_middle_click(
+                pointer::PointerCtx {
+                    ui,
+                    resp: &resp,
+                    content_rect,
+                    origin,
+                    layout_root,
+                    measurer,
+                },
+                base_url,
+            )
+        });
+    }
+
+    let context_menu_opened = context_menu::capture_context_target(
         pointer::PointerCtx {
             ui,
             resp: &resp,
@@ -97,29 +168,34 @@ pub(crate) fn route_frame_input<S: InputStore + ?Sized, F: FormControlHandler<S>
         },
         base_url,
         input_values,
-        form_controls,
         interaction,
     );
-    request_repaint |= release.request_repaint;
-    let action = release.action;
+    let menu_action = context_menu::show_context_menu(&resp, context_menu_opened, interaction);
+    action = action.or(menu_action);
 
     if let Some(egui_focus_id) =
         focus::maintain_egui_focus_bridge(ui, content_rect, origin, layout_root, interaction)
         && ui.memory(|mem| mem.has_focus(egui_focus_id))
     {
-        request_repaint |= keyboard::handle_focused_keyboard_input(
+        let keyboard = keyboard::handle_focused_keyboard_input(
             ui,
             layout_root,
             measurer,
+            base_url,
             input_values,
             form_controls,
             interaction,
         );
+        request_repaint |= keyboard.request_repaint;
+        action = action.or(keyboard.action);
     }
 
+    selection::handle_page_copy(ui, interaction);
+
     FrameInputResult {
         action,
         requested_followup_render: request_repaint,
+        requested_followup_relayout: control_resize.resized,
     }
 }
 
@@ -1,10 +1,11 @@
 use super::super::{ActiveTarget, InputDragState, InteractionState, PageAction};
+use super::scroll::{AUTOSCROLL_RATE, autoscroll_dt};
 use super::{FragmentRects, focus};
 use crate::EguiTextMeasurer;
-use egui::{Pos2, Rect, Response, Ui};
+use egui::{PointerButton, Pos2, Rect, Response, Ui};
 use input_core::InputStore;
 use layout::{
-    HitKind, LayoutBox, ReplacedKind,
+    HitKind, LayoutBox, ReplacedKind, TextMeasurer,
     hit_test::{HitResult, hit_test},
 };
 
@@ -98,6 +99,7 @@ pub(super) fn handle_pointer_press<S: InputStore + ?Sized>(
             interaction.input_drag = Some(InputDragState {
                 input_id: hit.node_id,
                 rect: hit.fragment_rect,
+                autoscroll_carry: 0.0,
             });
         }
         return true;
@@ -181,6 +183,9 @@ pub(super) fn handle_pointer_drag<S: InputStore + ?Sized>(
             true
         }
         Some(ReplacedKind::TextArea) => {
+            let autoscroll = textarea_autoscroll(ui, interaction, ly - rect.y, rect.height, {
+                measurer.line_height(lb.style)
+            });
             super::textarea::drag_selection(
                 input_values,
                 interaction,
@@ -188,6 +193,7 @@ pub(super) fn handle_pointer_drag<S: InputStore + ?Sized>(
                     input_id: drag_input_id,
                     local_x,
                     local_y,
+                    autoscroll,
                     viewport_width: rect.width,
                     viewport_height: rect.height,
                     style: lb.style,
@@ -200,6 +206,64 @@ pub(super) fn handle_pointer_drag<S: InputStore + ?Sized>(
     }
 }
 
+/// Distance past the visible text a textarea drag selection should reach this
+/// frame: whole lines, at a speed that grows with how far the pointer is
+/// above or below the control. Zero while the pointer is level with it.
+fn textarea_autoscroll(
+    ui: &Ui,
+    interaction: &mut InteractionState,
+    pointer_y: f32,
+    height: f32,
+    line_h: f32,
+) -> f32 {
+    let Some(drag) = interaction.input_drag.as_mut() else {
+        return 0.0;
+    };
+    let past_edge = if pointer_y < 0.0 {
+        pointer_y
+    } else {
+        (pointer_y - height).max(0.0)
+    };
+    if past_edge == 0.0 || line_h <= 0.0 {
+        drag.autoscroll_carry = 0.0;
+        return 0.0;
+    }
+    // Keep frames coming while the pointer rests outside the control.
+    ui.ctx().request_repaint();
+    drag.autoscroll_carry += past_edge * AUTOSCROLL_RATE * autoscroll_dt(ui);
+    let lines = (drag.autoscroll_carry / line_h).trunc();
+    drag.autoscroll_carry -= lines * line_h;
+    lines * line_h
+}
+
+/// A middle click on a link opens it in a background tab. Nothing else on
+/// the page reacts to the middle button, so focus, selection and scroll
+/// stay as they are.
+pub(super) fn handle_middle_click(
+    ctx: PointerCtx<'_, '_, '_>,
+    base_url: Option<&str>,
+) -> Option<PageAction> {
+    let PointerCtx {
+        ui,
+        resp,
+        content_rect,
+        origin,
+        layout_root,
+        measurer,
+    } = ctx;
+    if !ui.input(|i| i.pointer.button_clicked(PointerButton::Middle)) {
+        return None;
+    }
+    // The page response does not count as hovered for the middle button, so
+    // make sure no popup covers the page instead.
+    let pos = pointer_pos(resp, ui, true)?;
+    if ui.ctx().layer_id_at(pos) != Some(ui.layer_id()) {
+        return None;
+    }
+    let hit = hit_at_pointer(resp, ui, content_rect, origin, layout_root, measurer, true)?;
+    super::actions::link_url(&hit, base_url).map(PageAction::OpenInNewTab)
+}
+
 pub(super) fn handle_pointer_release<S: InputStore + ?Sized, F: super::FormControlHandler<S>>(
     ctx: PointerCtx<'_, '_, '_>,
     base_url: Option<&str>,
@@ -259,9 +323,11 @@ pub(super) fn handle_pointer_release<S: InputStore + ?Sized, F: super::FormContr
                     was_active.is_some_and(|a| a.id == hit.node_id && a.kind == hit.kind);
 
                 if down_matches_up {
+                    let open_in_background = ui.input(|i| i.modifiers.command);
                     let activation = super::actions::activate_release_target(
                         hit,
                         base_url,
+                        open_in_background,
                         input_values,
                         form_controls,
                         interaction,
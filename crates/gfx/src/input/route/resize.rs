@@ -0,0 +1,113 @@
+use super::super::{ControlResizeState, InteractionState};
+use super::FragmentRects;
+use super::pointer::{PointerCtx, hit_at_pointer, pointer_pos};
+use crate::textarea::{resize_grip_contains, resized_control_size};
+use css::Resize;
+use egui::{CursorIcon, Ui};
+use html::internal::Id;
+use layout::{HitKind, LayoutBox, ReplacedKind};
+
+pub(super) struct ControlResizeOutcome {
+    /// The pointer gesture belongs to a resize grip; regular pointer handling
+    /// must not see it.
+    pub(super) consumed: bool,
+    /// A control's size changed, so the page needs another layout pass.
+    pub(super) resized: bool,
+}
+
+/// Start, continue or finish a drag of a textarea's resize grip, and show
+/// the resize cursor over grips.
+pub(super) fn handle_control_resize(
+    ctx: PointerCtx<'_, '_, '_>,
+    fragment_rects: &FragmentRects,
+    interaction: &mut InteractionState,
+) -> ControlResizeOutcome {
+    let PointerCtx {
+        ui,
+        resp,
+        content_rect,
+        origin,
+        layout_root,
+        measurer,
+    } = ctx;
+    let local_pos = pointer_pos(resp, ui, true).map(|pos| (pos.x - origin.x, pos.y - origin.y));
+
+    if let Some(drag) = interaction.control_resize {
+        let mut resized = false;
+        if ui.input(|i| i.pointer.primary_down()) {
+            if let Some((x, y)) = local_pos {
+                let delta = (x - drag.start_pos.0, y - drag.start_pos.1);
+                let size = resized_control_size(drag.resize, drag.start_size, delta);
+                if interaction.resized_controls.get(&drag.control_id) != Some(&size) {
+                    interaction.resized_controls.insert(drag.control_id, size);
+                    resized = true;
+                }
+            }
+            set_resize_cursor(ui, drag.resize);
+        } else {
+            interaction.control_resize = None;
+        }
+        return ControlResizeOutcome {
+            consumed: true,
+            resized,
+        };
+    }
+
+    if ui.input(|i| i.pointer.primary_pressed()) {
+        let grip = hit_at_pointer(resp, ui, content_rect, origin, layout_root, measurer, true)
+            .filter(|hit| hit.kind == HitKind::Input)
+            .and_then(|hit| {
+                let resize = textarea_resize(layout_root, hit.node_id)?;
+                let (x, y) = (hit.fragment_rect.x, hit.fragment_rect.y);
+                let pos = (x + hit.local_pos.0, y + hit.local_pos.1);
+                resize_grip_contains(hit.fragment_rect, pos).then_some(ControlResizeState {
+                    control_id: hit.node_id,
+                    resize,
+                    start_pos: pos,
+                    start_size: (hit.fragment_rect.width, hit.fragment_rect.height),
+                })
+            });
+        if let Some(drag) = grip {
+            interaction.control_resize = Some(drag);
+            interaction.active = None;
+            interaction.input_drag = None;
+            set_resize_cursor(ui, drag.resize);
+            return ControlResizeOutcome {
+                consumed: true,
+                resized: false,
+            };
+        }
+    }
+
+    // Hover only needs the painted rect of the control already under the pointer.
+    if let (Some(id), Some(pos)) = (interaction.hover, local_pos)
+        && interaction.hover_kind == Some(HitKind::Input)
+        && let Some(resize) = textarea_resize(layout_root, id)
+        && let Some(rect) = fragment_rects.borrow().get(&id).copied()
+        && resize_grip_contains(rect, pos)
+    {
+        set_resize_cursor(ui, resize);
+    }
+
+    ControlResizeOutcome {
+        consumed: false,
+        resized: false,
+    }
+}
+
+/// The `resize` value of the textarea `id`, if it can be resized at all.
+fn textarea_resize(layout_root: &LayoutBox<'_, '_>, id: Id) -> Option<Resize> {
+    crate::text_control::find_layout_box_by_id(layout_root, id)
+        .filter(|lb| matches!(lb.replaced, Some(ReplacedKind::TextArea)))
+        .map(|lb| lb.style.resize())
+        .filter(|resize| *resize != Resize::None)
+}
+
+fn set_resize_cursor(ui: &mut Ui, resize: Resize) {
+    let icon = match resize {
+        Resize::Horizontal => CursorIcon::ResizeHorizontal,
+        Resize::Vertical => CursorIcon::ResizeVertical,
+        Resize::Both | Resize::None => CursorIcon::ResizeNwSe,
+    };
+    ui.output_mut(|o| o.cursor_icon = icon);
+}
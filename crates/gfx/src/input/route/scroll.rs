@@ -0,0 +1,131 @@
+use super::super::InteractionState;
+use egui::{Key, Modifiers, Rect, Ui};
+
+/// Distance scrolled by one arrow key press.
+const ARROW_SCROLL_STEP: f32 = 40.0;
+
+/// Fraction of the viewport scrolled by one page step; the rest stays on
+/// screen so the reader keeps their place.
+const PAGE_SCROLL_FRACTION: f32 = 0.875;
+
+/// Band along the top and bottom of the viewport where a selection drag
+/// starts scrolling the page.
+const AUTOSCROLL_EDGE: f32 = 24.0;
+
+/// Auto-scroll speed, in pixels per second for every pixel the pointer is
+/// past the start of the edge band.
+pub(crate) const AUTOSCROLL_RATE: f32 = 10.0;
+
+/// New page scroll offset requested by scrolling keys this frame, measured
+/// from where any running scroll animation is heading.
+///
+/// Keys only scroll the page while neither a page control nor a browser
+/// widget (address bar, find bar) has keyboard focus. `viewport_height` is the
+/// visible height of the page; the offset is clamped so the last laid-out
+/// line can reach the bottom of the viewport but not beyond.
+pub(crate) fn keyboard_scroll_target(
+    ui: &mut Ui,
+    interaction: &InteractionState,
+    viewport_height: f32,
+) -> Option<f32> {
+    if interaction.focused_node_id.is_some() || ui.memory(|mem| mem.focused().is_some()) {
+        return None;
+    }
+
+    let page = viewport_height * PAGE_SCROLL_FRACTION;
+    // Shift+Space must be checked before Space, which also matches it.
+    let delta = ui.input_mut(|i| {
+        if i.consume_key(Modifiers::SHIFT, Key::Space)
+            || i.consume_key(Modifiers::NONE, Key::PageUp)
+        {
+            Some(-page)
+        } else if i.consume_key(Modifiers::NONE, Key::Space)
+            || i.consume_key(Modifiers::NONE, Key::PageDown)
+        {
+            Some(page)
+        } else if i.consume_key(Modifiers::NONE, Key::ArrowUp) {
+            Some(-ARROW_SCROLL_STEP)
+        } else if i.consume_key(Modifiers::NONE, Key::ArrowDown) {
+            Some(ARROW_SCROLL_STEP)
+        } else if i.consume_key(Modifiers::NONE, Key::Home) {
+            Some(f32::NEG_INFINITY)
+        } else if i.consume_key(Modifiers::NONE, Key::End) {
+            Some(f32::INFINITY)
+        } else {
+            None
+        }
+    })?;
+
+    Some(clamp_scroll(
+        interaction,
+        viewport_height,
+        interaction.scroll_destination() + delta,
+    ))
+}
+
+/// New page scroll offset requested by the mouse wheel or trackpad this frame.
+///
+/// The page scroll area ignores the wheel itself so that wheel steps animate
+/// like every other page scroll; only wheel input over the page counts.
+pub(crate) fn wheel_scroll_target(
+    ui: &Ui,
+    interaction: &InteractionState,
+    viewport_height: f32,
+) -> Option<f32> {
+    if !ui.ui_contains_pointer() {
+        return None;
+    }
+    // The delta says how the content moves, the opposite of the offset.
+    let delta = ui.input(|i| i.raw_scroll_delta.y);
+    if delta == 0.0 {
+        return None;
+    }
+    Some(clamp_scroll(
+        interaction,
+        viewport_height,
+        interaction.scroll_destination() - delta,
+    ))
+}
+
+/// How far the page should scroll this frame while a selection is dragged
+/// (in the page or a text control) with the pointer near or past the top or
+/// bottom of `viewport`. The speed grows with the distance into the edge band.
+pub(crate) fn drag_autoscroll_delta(
+    ui: &Ui,
+    interaction: &InteractionState,
+    viewport: Rect,
+) -> Option<f32> {
+    let dragging = interaction.page_selection.is_dragging() || interaction.input_drag.is_some();
+    if !dragging || !ui.input(|i| i.pointer.primary_down()) {
+        return None;
+    }
+    let y = ui.input(|i| i.pointer.latest_pos())?.y;
+    let past_edge = if y < viewport.top() + AUTOSCROLL_EDGE {
+        y - (viewport.top() + AUTOSCROLL_EDGE)
+    } else if y > viewport.bottom() - AUTOSCROLL_EDGE {
+        y - (viewport.bottom() - AUTOSCROLL_EDGE)
+    } else {
+        return None;
+    };
+    Some(past_edge * AUTOSCROLL_RATE * autoscroll_dt(ui))
+}
+
+/// Frame time used to advance auto-scrolling, capped so a stalled frame does
+/// not jump far.
+pub(crate) fn autoscroll_dt(ui: &Ui) -> f32 {
+    ui.input(|i| i.stable_dt).min(0.1)
+}
+
+/// Offset of the page scrolled to `offset`, kept between the top and the
+/// point where the last laid-out line reaches the bottom of the viewport.
+pub(crate) fn clamp_scroll(
+    interaction: &InteractionState,
+    viewport_height: f32,
+    offset: f32,
+) -> f32 {
+    let document_height = interaction
+        .last_layout_root_size
+        .map_or(0.0, |(_, height)| height);
+    let max_scroll = (document_height - viewport_height).max(0.0);
+    offset.clamp(0.0, max_scroll)
+}
@@ -0,0 +1,89 @@
+use super::super::InteractionState;
+use super::pointer::{PointerCtx, hit_at_pointer, pointer_pos};
+use crate::paint::PaintArtifact;
+use crate::selection::text_position_at;
+use crate::util::painted_text_width;
+use egui::{Event, Ui};
+use layout::HitKind;
+
+/// Start, extend or finish a page text selection from this frame's primary
+/// button. The viewport paints the selection over the page afterwards, so
+/// changes need no followup render.
+///
+/// A press on page text or background starts a new selection; a press on a
+/// form control only drops the old one, and a command-press on a link keeps
+/// it. While the button is held, the moving end follows the pointer even
+/// outside the page, snapping to the nearest painted text.
+pub(super) fn handle_page_selection(
+    ctx: PointerCtx<'_, '_, '_>,
+    page_text: &PaintArtifact,
+    interaction: &mut InteractionState,
+) {
+    let PointerCtx {
+        ui,
+        resp,
+        content_rect,
+        origin,
+        layout_root,
+        measurer,
+    } = ctx;
+    let (pressed, down, released) = ui.input(|i| {
+        (
+            i.pointer.primary_pressed(),
+            i.pointer.primary_down(),
+            i.pointer.primary_released(),
+        )
+    });
+    let measure = |text: &str, font_size_px: f32| painted_text_width(ui.ctx(), text, font_size_px);
+    let position_at = |pos: egui::Pos2| {
+        text_position_at(page_text, (pos.x - origin.x, pos.y - origin.y), &measure)
+    };
+
+    let on_page = pointer_pos(resp, ui, true).is_some_and(|pos| content_rect.contains(pos));
+    let hit = (pressed && on_page)
+        .then(|| hit_at_pointer(resp, ui, content_rect, origin, layout_root, measurer, true))
+        .flatten();
+    // Command-clicking a link opens it in the background; keep the selection.
+    let background_link = hit.as_ref().is_some_and(|hit| hit.kind == HitKind::Link)
+        && ui.input(|i| i.modifiers.command);
+    if pressed && on_page && !background_link {
+        interaction.page_selection.clear();
+        let on_control = hit.is_some_and(|hit| {
+            matches!(
+                hit.kind,
+                HitKind::Input
+                    | HitKind::Checkbox
+                    | HitKind::Radio
+                    | HitKind::FileInput
+                    | HitKind::Button
+            )
+        });
+        if !on_control && let Some(position) = pointer_pos(resp, ui, true).and_then(position_at) {
+            interaction.page_selection.start(position);
+        }
+    } else if down
+        && interaction.page_selection.is_dragging()
+        && let Some(position) = ui.input(|i| i.pointer.latest_pos()).and_then(position_at)
+    {
+        interaction.page_selection.extend(position, page_text);
+    }
+
+    if released {
+        interaction.page_selection.finish_drag();
+    }
+}
+
+/// Put the page selection on the clipboard when the user copies while
+/// neither a page control nor a browser widget has keyboard focus. Focused
+/// fields copy their own selection instead.
+pub(super) fn handle_page_copy(ui: &Ui, interaction: &InteractionState) {
+    if interaction.focused_node_id.is_some() || ui.memory(|mem| mem.focused().is_some()) {
+        return;
+    }
+    if !ui.input(|i| i.events.iter().any(|evt| matches!(evt, Event::Copy))) {
+        return;
+    }
+    if let Some(text) = interaction.page_selection.selected_text() {
+        ui.ctx().copy_text(text.to_owned());
+    }
+}
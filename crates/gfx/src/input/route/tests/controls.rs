@@ -188,3 +188,249 @@ fn checkbox_and_radio_activate_on_mouse_and_space() {
     });
     assert!(store.is_checked(to_input_id(Id(3))));
 }
+
+#[test]
+fn enter_in_text_field_and_button_click_submit_the_form() {
+    let ctx = Context::default();
+    init_context(&ctx);
+    let measurer = EguiTextMeasurer::new(&ctx);
+
+    let dom = doc(vec![elem(
+        1,
+        "form",
+        Vec::new(),
+        Vec::new(),
+        vec![
+            input_text(2),
+            elem(
+                3,
+                "button",
+                Vec::new(),
+                style_inline_block(),
+                vec![text(4, "Go")],
+            ),
+        ],
+    )]);
+    let style_root = build_style_tree(&dom, None);
+    let layout_root = layout::layout_block_tree(&style_root, 400.0, &measurer, None);
+    let content_size = Vec2::new(400.0, layout_root.rect.height.max(200.0));
+    let origin = content_origin(&ctx, content_size);
+
+    let pos_input = pos_in_rect(
+        origin,
+        find_fragment_rect_for_node(&layout_root, &measurer, Id(2)).unwrap(),
+        2.0,
+        2.0,
+    );
+    let pos_button = pos_center(
+        origin,
+        find_fragment_rect_for_node(&layout_root, &measurer, Id(3)).unwrap(),
+    );
+
+    let mut store = Store::new();
+    store.ensure_initial(to_input_id(Id(2)), String::new());
+    let mut interaction = InteractionState::default();
+    let form_controls = TestFormControls;
+
+    let click = |pos, store: &mut Store, interaction: &mut InteractionState| {
+        let mut action = None;
+        for pressed in [true, false] {
+            action = run_frame(FrameRun {
+                ctx: &ctx,
+                raw_input: raw_input(vec![
+                    Event::PointerMoved(pos),
+                    Event::PointerButton {
+                        pos,
+                        button: PointerButton::Primary,
+                        pressed,
+                        modifiers: Modifiers::NONE,
+                    },
+                ]),
+                layout_root: &layout_root,
+                measurer: &measurer,
+                base_url: None,
+                input_values: store,
+                form_controls: &form_controls,
+                interaction,
+                content_size,
+                layout_changed: false,
+            });
+        }
+        action
+    };
+
+    assert!(click(pos_input, &mut store, &mut interaction).is_none());
+    assert_eq!(interaction.focused_node_id, Some(Id(2)));
+    let action = run_frame(FrameRun {
+        ctx: &ctx,
+        raw_input: raw_input(vec![Event::Key {
+            key: egui::Key::Enter,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers: Modifiers::NONE,
+        }]),
+        layout_root: &layout_root,
+        measurer: &measurer,
+        base_url: None,
+        input_values: &mut store,
+        form_controls: &form_controls,
+        interaction: &mut interaction,
+        content_size,
+        layout_changed: false,
+    });
+    match action {
+        Some(PageAction::Submit(submission)) => {
+            assert_eq!(submission.url, "https://example.com/submit?by=2")
+        }
+        other => panic!("expected Enter to submit, got {other:?}"),
+    }
+
+    match click(pos_button, &mut store, &mut interaction) {
+        Some(PageAction::Submit(submission)) => {
+            assert_eq!(submission.url, "https://example.com/submit?by=3")
+        }
+        other => panic!("expected the button to submit, got {other:?}"),
+    }
+}
+
+#[test]
+fn dragging_a_textarea_resize_grip_resizes_along_allowed_axes() {
+    let ctx = Context::default();
+    init_context(&ctx);
+    let measurer = EguiTextMeasurer::new(&ctx);
+
+    let textarea = |id, resize: &str| {
+        let mut style = style_inline_block();
+        style.push(("resize".to_string(), resize.to_string()));
+        elem(id, "textarea", Vec::new(), style, Vec::new())
+    };
+    let dom = doc(vec![elem(
+        1,
+        "div",
+        Vec::new(),
+        Vec::new(),
+        vec![
+            textarea(2, "both"),
+            textarea(3, "vertical"),
+            textarea(4, "none"),
+        ],
+    )]);
+    let style_root = build_style_tree(&dom, None);
+    let layout_root = layout::layout_block_tree(&style_root, 1000.0, &measurer, None);
+    let content_size = Vec2::new(1000.0, layout_root.rect.height.max(200.0));
+    let origin = content_origin(&ctx, content_size);
+
+    let mut store = Store::new();
+    let mut interaction = InteractionState::default();
+    let form_controls = TestFormControls;
+
+    let drag = |id: u32, store: &mut Store, interaction: &mut InteractionState| {
+        let rect = find_fragment_rect_for_node(&layout_root, &measurer, Id(id)).unwrap();
+        let start = pos_in_rect(origin, rect, rect.width - 3.0, rect.height - 3.0);
+        let end = start + Vec2::new(30.0, 20.0);
+        for (pos, pressed) in [(start, Some(true)), (end, None), (end, Some(false))] {
+            let mut events = vec![Event::PointerMoved(pos)];
+            if let Some(pressed) = pressed {
+                events.push(Event::PointerButton {
+                    pos,
+                    button: PointerButton::Primary,
+                    pressed,
+                    modifiers: Modifiers::NONE,
+                });
+            }
+            run_frame(FrameRun {
+                ctx: &ctx,
+                raw_input: raw_input(events),
+                layout_root: &layout_root,
+                measurer: &measurer,
+                base_url: None,
+                input_values: store,
+                form_controls: &form_controls,
+                interaction,
+                content_size,
+                layout_changed: false,
+            });
+        }
+        (rect.width, rect.height)
+    };
+
+    let (width, height) = drag(2, &mut store, &mut interaction);
+    assert_eq!(
+        interaction.resized_controls.get(&Id(2)),
+        Some(&(width + 30.0, height + 20.0))
+    );
+    assert!(interaction.control_resize.is_none());
+    assert_eq!(interaction.focused_node_id, None);
+
+    let (width, height) = drag(3, &mut store, &mut interaction);
+    assert_eq!(
+        interaction.resized_controls.get(&Id(3)),
+        Some(&(width, height + 20.0))
+    );
+
+    drag(4, &mut store, &mut interaction);
+    assert_eq!(interaction.resized_controls.get(&Id(4)), None);
+    assert_eq!(interaction.focused_node_id, Some(Id(4)));
+}
+
+#[test]
+fn clicking_a_file_input_requests_the_file_picker() {
+    let ctx = Context::default();
+    init_context(&ctx);
+    let measurer = EguiTextMeasurer::new(&ctx);
+
+    let dom = doc(vec![elem(
+        1,
+        "div",
+        Vec::new(),
+        Vec::new(),
+        vec![elem(
+            2,
+            "input",
+            vec![("type", Some("File"))],
+            style_inline_block(),
+            Vec::new(),
+        )],
+    )]);
+    let style_root = build_style_tree(&dom, None);
+    let layout_root = layout::layout_block_tree(&style_root, 400.0, &measurer, None);
+    let content_size = Vec2::new(400.0, layout_root.rect.height.max(200.0));
+    let origin = content_origin(&ctx, content_size);
+    let pos = pos_center(
+        origin,
+        find_fragment_rect_for_node(&layout_root, &measurer, Id(2)).unwrap(),
+    );
+
+    let mut store = Store::new();
+    let mut interaction = InteractionState::default();
+    let form_controls = TestFormControls;
+
+    let mut action = None;
+    for pressed in [true, false] {
+        action = run_frame(FrameRun {
+            ctx: &ctx,
+            raw_input: raw_input(vec![
+                Event::PointerMoved(pos),
+                Event::PointerButton {
+                    pos,
+                    button: PointerButton::Primary,
+                    pressed,
+                    modifiers: Modifiers::NONE,
+                },
+            ]),
+            layout_root: &layout_root,
+            measurer: &measurer,
+            base_url: None,
+            input_values: &mut store,
+            form_controls: &form_controls,
+            interaction: &mut interaction,
+            content_size,
+            layout_changed: false,
+        });
+    }
+    match action {
+        Some(PageAction::PickFiles(id)) => assert_eq!(id, Id(2)),
+        other => panic!("expected the file picker to open, got {other:?}"),
+    }
+}
@@ -10,6 +10,7 @@ use layout::{LayoutBox, Rectangle, TextMeasurer, content_height, content_x_and_w
 use std::cell::RefCell;
 use std::collections::HashMap;
 
+use crate::input::FormSubmission;
 pub(super) use crate::input::to_input_id;
 
 pub(super) struct TestFormControls;
@@ -18,6 +19,18 @@ impl<S: InputStore + ?Sized> FormControlHandler<S> for TestFormControls {
     fn on_radio_clicked(&self, store: &mut S, radio_id: InputId) -> bool {
         store.set_checked(radio_id, true)
     }
+
+    fn form_submission(
+        &self,
+        _store: &S,
+        trigger: InputId,
+        _base_url: Option<&str>,
+    ) -> Option<FormSubmission> {
+        Some(FormSubmission {
+            url: format!("https://example.com/submit?by={}", trigger.as_raw()),
+            payload: Default::default(),
+        })
+    }
 }
 
 pub(super) fn doc(children: Vec<Node>) -> Node {
@@ -165,6 +178,8 @@ pub(super) fn run_frame<S: InputStore + ?Sized, F: FormControlHandler<S>>(
             let origin = content_rect.min;
             let fragment_rects: RefCell<HashMap<Id, Rectangle>> = RefCell::new(HashMap::new());
 
+            // Touch pans are tracked before input routing, as in the viewport.
+            let _ = crate::input::touch_scroll_target(ui, interaction, content_rect);
             let result = route_frame_input(FrameInputCtx {
                 ui,
                 resp,
@@ -174,6 +189,7 @@ pub(super) fn run_frame<S: InputStore + ?Sized, F: FormControlHandler<S>>(
                 measurer,
                 layout_changed,
                 fragment_rects: &fragment_rects,
+                page_text: None,
                 base_url,
                 input_values,
                 form_controls,
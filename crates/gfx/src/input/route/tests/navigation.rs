@@ -1,8 +1,13 @@
 use super::super::*;
 use super::helpers::*;
+use crate::input::ContextMenuTarget;
 use crate::text_measurer::EguiTextMeasurer;
 use css::build_style_tree;
-use egui::{Context, Event, Modifiers, PointerButton, Vec2};
+use egui::{
+    Context, Event, Modifiers, PointerButton, Pos2, RawInput, TouchDeviceId, TouchId, TouchPhase,
+    Vec2,
+};
+use std::cell::RefCell;
 
 #[test]
 fn link_click_clears_focus_and_returns_navigation() {
@@ -120,3 +125,517 @@ fn link_click_clears_focus_and_returns_navigation() {
         _ => panic!("expected PageAction::Navigate"),
     }
 }
+
+#[test]
+fn a_touch_pan_starting_on_a_link_does_not_follow_it() {
+    let ctx = Context::default();
+    init_context(&ctx);
+    let measurer = EguiTextMeasurer::new(&ctx);
+
+    let dom = doc(vec![elem(
+        1,
+        "div",
+        Vec::new(),
+        Vec::new(),
+        vec![link(2, "https://example.com/next", vec![text(3, "next")])],
+    )]);
+    let style_root = build_style_tree(&dom, None);
+    let layout_root = layout::layout_block_tree(&style_root, 600.0, &measurer, None);
+    let content_size = Vec2::new(600.0, layout_root.rect.height.max(200.0));
+    let origin = content_origin(&ctx, content_size);
+    let link_rect = find_link_fragment_rect(&layout_root, &measurer, Id(2)).unwrap();
+    let link_pos = pos_in_rect(origin, link_rect, 1.0, 1.0);
+    let panned = link_pos + Vec2::new(0.0, 60.0);
+
+    let mut store = Store::new();
+    let mut interaction = InteractionState::default();
+    let form_controls = TestFormControls;
+    let touch = |phase, pos| Event::Touch {
+        device_id: TouchDeviceId(0),
+        id: TouchId(1),
+        phase,
+        pos,
+        force: None,
+    };
+    let frames = [
+        vec![
+            touch(TouchPhase::Start, link_pos),
+            Event::PointerMoved(link_pos),
+            Event::PointerButton {
+                pos: link_pos,
+                button: PointerButton::Primary,
+                pressed: true,
+                modifiers: Modifiers::NONE,
+            },
+        ],
+        vec![touch(TouchPhase::Move, panned), Event::PointerMoved(panned)],
+        // The page moved with the finger, so the link is under it again.
+        vec![
+            touch(TouchPhase::End, link_pos),
+            Event::PointerMoved(link_pos),
+            Event::PointerButton {
+                pos: link_pos,
+                button: PointerButton::Primary,
+                pressed: false,
+                modifiers: Modifiers::NONE,
+            },
+        ],
+    ];
+
+    let actions: Vec<_> = frames
+        .into_iter()
+        .map(|events| {
+            run_frame(FrameRun {
+                ctx: &ctx,
+                raw_input: raw_input(events),
+                layout_root: &layout_root,
+                measurer: &measurer,
+                base_url: None,
+                input_values: &mut store,
+                form_controls: &form_controls,
+                interaction: &mut interaction,
+                content_size,
+                layout_changed: false,
+            })
+        })
+        .collect();
+
+    assert!(actions.iter().all(Option::is_none), "{actions:?}");
+}
+
+#[test]
+fn target_blank_link_click_requests_a_new_tab() {
+    let ctx = Context::default();
+    init_context(&ctx);
+    let measurer = EguiTextMeasurer::new(&ctx);
+
+    let dom = doc(vec![elem(
+        1,
+        "div",
+        Vec::new(),
+        Vec::new(),
+        vec![elem(
+            2,
+            "a",
+            vec![("href", Some("/docs")), ("target", Some("_BLANK"))],
+            style_inline(),
+            vec![text(3, "docs")],
+        )],
+    )]);
+    let style_root = build_style_tree(&dom, None);
+    let layout_root = layout::layout_block_tree(&style_root, 600.0, &measurer, None);
+    let content_size = Vec2::new(600.0, layout_root.rect.height.max(200.0));
+    let origin = content_origin(&ctx, content_size);
+    let link_rect = find_link_fragment_rect(&layout_root, &measurer, Id(2)).unwrap();
+    let link_pos = pos_in_rect(origin, link_rect, 1.0, 1.0);
+
+    let mut store = Store::new();
+    let mut interaction = InteractionState::default();
+    let form_controls = TestFormControls;
+    let mut action = None;
+    for pressed in [true, false] {
+        action = run_frame(FrameRun {
+            ctx: &ctx,
+            raw_input: raw_input(vec![
+                Event::PointerMoved(link_pos),
+                Event::PointerButton {
+                    pos: link_pos,
+                    button: PointerButton::Primary,
+                    pressed,
+                    modifiers: Modifiers::NONE,
+                },
+            ]),
+            layout_root: &layout_root,
+            measurer: &measurer,
+            base_url: Some("https://example.com/index.html"),
+            input_values: &mut store,
+            form_controls: &form_controls,
+            interaction: &mut interaction,
+            content_size,
+            layout_changed: false,
+        });
+    }
+
+    match action {
+        Some(PageAction::NavigateInNewTab(url)) => assert_eq!(url, "https://example.com/docs"),
+        other => panic!("expected PageAction::NavigateInNewTab, got {other:?}"),
+    }
+}
+
+#[test]
+fn command_and_middle_clicks_open_links_in_background_tabs_and_keep_focus() {
+    let ctx = Context::default();
+    init_context(&ctx);
+    let measurer = EguiTextMeasurer::new(&ctx);
+
+    let dom = doc(vec![elem(
+        1,
+        "div",
+        Vec::new(),
+        Vec::new(),
+        vec![
+            input_text(2),
+            link(3, "https://example.com/next", vec![text(4, "next")]),
+        ],
+    )]);
+    let style_root = build_style_tree(&dom, None);
+    let layout_root = layout::layout_block_tree(&style_root, 600.0, &measurer, None);
+    let content_size = Vec2::new(600.0, layout_root.rect.height.max(200.0));
+    let origin = content_origin(&ctx, content_size);
+
+    let input_rect = find_fragment_rect_for_node(&layout_root, &measurer, Id(2)).unwrap();
+    let input_pos = pos_in_rect(origin, input_rect, 2.0, 2.0);
+    let link_rect = find_link_fragment_rect(&layout_root, &measurer, Id(3)).unwrap();
+    let link_pos = pos_in_rect(origin, link_rect, 1.0, 1.0);
+
+    let mut store = Store::new();
+    store.ensure_initial(to_input_id(Id(2)), "hello".to_string());
+    let mut interaction = InteractionState::default();
+    let form_controls = TestFormControls;
+
+    let mut click = |pos: Pos2, button: PointerButton, modifiers: Modifiers| {
+        let mut action = None;
+        for pressed in [true, false] {
+            action = run_frame(FrameRun {
+                ctx: &ctx,
+                raw_input: RawInput {
+                    modifiers,
+                    ..raw_input(vec![
+                        Event::PointerMoved(pos),
+                        Event::PointerButton {
+                            pos,
+                            button,
+                            pressed,
+                            modifiers,
+                        },
+                    ])
+                },
+                layout_root: &layout_root,
+                measurer: &measurer,
+                base_url: None,
+                input_values: &mut store,
+                form_controls: &form_controls,
+                interaction: &mut interaction,
+                content_size,
+                layout_changed: false,
+            });
+        }
+        action
+    };
+
+    assert!(click(input_pos, PointerButton::Primary, Modifiers::NONE).is_none());
+    for (button, modifiers) in [
+        (PointerButton::Middle, Modifiers::NONE),
+        (PointerButton::Primary, Modifiers::COMMAND),
+    ] {
+        match click(link_pos, button, modifiers) {
+            Some(PageAction::OpenInNewTab(url)) => assert_eq!(url, "https://example.com/next"),
+            other => panic!("expected PageAction::OpenInNewTab for {button:?}, got {other:?}"),
+        }
+    }
+    assert_eq!(interaction.focused_node_id, Some(Id(2)));
+}
+
+#[test]
+fn hovering_a_link_exposes_its_resolved_destination_until_hover_ends() {
+    let ctx = Context::default();
+    init_context(&ctx);
+    let measurer = EguiTextMeasurer::new(&ctx);
+
+    let dom = doc(vec![elem(
+        1,
+        "div",
+        Vec::new(),
+        Vec::new(),
+        vec![
+            link(2, "../next?q=1", vec![text(3, "next")]),
+            text(4, " plain text"),
+        ],
+    )]);
+    let style_root = build_style_tree(&dom, None);
+    let layout_root = layout::layout_block_tree(&style_root, 600.0, &measurer, None);
+    let content_size = Vec2::new(600.0, layout_root.rect.height.max(200.0));
+    let origin = content_origin(&ctx, content_size);
+
+    let link_rect = find_link_fragment_rect(&layout_root, &measurer, Id(2)).unwrap();
+    let link_pos = pos_in_rect(origin, link_rect, 1.0, 1.0);
+    let past_link = pos_in_rect(origin, link_rect, link_rect.width + 40.0, 1.0);
+
+    let mut store = Store::new();
+    let mut interaction = InteractionState::default();
+    let form_controls = TestFormControls;
+    let mut hover = |pos, interaction: &mut InteractionState| {
+        run_frame(FrameRun {
+            ctx: &ctx,
+            raw_input: raw_input(vec![Event::PointerMoved(pos)]),
+            layout_root: &layout_root,
+            measurer: &measurer,
+            base_url: Some("https://example.com/docs/page.html"),
+            input_values: &mut store,
+            form_controls: &form_controls,
+            interaction,
+            content_size,
+            layout_changed: false,
+        });
+    };
+
+    // Hover is only recomputed once the pointer has moved.
+    hover(past_link, &mut interaction);
+    hover(link_pos, &mut interaction);
+    assert_eq!(
+        interaction.hover_href.as_deref(),
+        Some("https://example.com/next?q=1")
+    );
+
+    hover(past_link, &mut interaction);
+    assert_eq!(interaction.hover_href, None);
+}
+
+struct ResolvingImages;
+
+impl layout::ReplacedElementInfoProvider for ResolvingImages {
+    fn resolve_image_source(&self, source: &str) -> Option<String> {
+        Some(format!("https://example.com/img/{source}"))
+    }
+
+    fn intrinsic_for_img(
+        &self,
+        _image: &layout::ImagePresentation,
+    ) -> Option<layout::replaced::intrinsic::IntrinsicSize> {
+        Some(layout::replaced::intrinsic::IntrinsicSize::from_w_h(
+            Some(40.0),
+            Some(30.0),
+        ))
+    }
+}
+
+#[test]
+fn secondary_click_captures_context_menu_target() {
+    let ctx = Context::default();
+    init_context(&ctx);
+    let measurer = EguiTextMeasurer::new(&ctx);
+
+    let dom = doc(vec![elem(
+        1,
+        "div",
+        Vec::new(),
+        Vec::new(),
+        vec![link(
+            2,
+            "/next",
+            vec![elem(
+                3,
+                "img",
+                vec![("src", Some("cat.png"))],
+                style_inline_block(),
+                Vec::new(),
+            )],
+        )],
+    )]);
+    let style_root = build_style_tree(&dom, None);
+    let layout_root =
+        layout::layout_block_tree(&style_root, 600.0, &measurer, Some(&ResolvingImages));
+    let content_size = Vec2::new(600.0, layout_root.rect.height.max(200.0));
+    let origin = content_origin(&ctx, content_size);
+
+    let image_rect = find_link_fragment_rect(&layout_root, &measurer, Id(2)).unwrap();
+    let image_pos = pos_in_rect(origin, image_rect, 2.0, 2.0);
+    let background_pos = pos_in_rect(origin, image_rect, 400.0, 2.0);
+
+    let mut store = Store::new();
+    let mut interaction = InteractionState::default();
+    let form_controls = TestFormControls;
+    let mut secondary_click = |pos, interaction: &mut InteractionState| {
+        for pressed in [true, false] {
+            let action = run_frame(FrameRun {
+                ctx: &ctx,
+                raw_input: raw_input(vec![
+                    Event::PointerMoved(pos),
+                    Event::PointerButton {
+                        pos,
+                        button: PointerButton::Secondary,
+                        pressed,
+                        modifiers: Modifiers::NONE,
+                    },
+                ]),
+                layout_root: &layout_root,
+                measurer: &measurer,
+                base_url: Some("https://example.com/docs/page.html"),
+                input_values: &mut store,
+                form_controls: &form_controls,
+                interaction,
+                content_size,
+                layout_changed: false,
+            });
+            assert!(action.is_none(), "secondary clicks never navigate");
+        }
+    };
+
+    secondary_click(image_pos, &mut interaction);
+    assert_eq!(
+        interaction.context_menu,
+        Some(ContextMenuTarget {
+            link: Some("https://example.com/next".to_string()),
+            image: Some("https://example.com/img/cat.png".to_string()),
+            selected_text: None,
+        })
+    );
+
+    // The first click outside the open menu only dismisses it.
+    secondary_click(background_pos, &mut interaction);
+    secondary_click(background_pos, &mut interaction);
+    assert!(
+        interaction
+            .context_menu
+            .as_ref()
+            .is_some_and(ContextMenuTarget::is_page_background)
+    );
+}
+
+#[test]
+fn pending_scroll_restores_clamp_to_the_laid_out_document() {
+    let mut interaction = InteractionState {
+        pending_scroll_y: Some(5000.0),
+        ..InteractionState::default()
+    };
+    // Nothing laid out yet: keep waiting for the document to grow.
+    interaction.clamp_pending_scroll();
+    assert_eq!(interaction.pending_scroll_y, Some(5000.0));
+
+    interaction.last_viewport_height = Some(400.0);
+    interaction.last_layout_root_size = Some((800.0, 1000.0));
+    interaction.clamp_pending_scroll();
+    assert_eq!(interaction.pending_scroll_y, Some(600.0));
+
+    interaction.pending_scroll_y = Some(250.0);
+    interaction.clamp_pending_scroll();
+    assert_eq!(interaction.pending_scroll_y, Some(250.0));
+}
+
+#[test]
+fn scrolling_keys_move_the_page_by_viewport_steps_within_the_document() {
+    let ctx = Context::default();
+    init_context(&ctx);
+
+    let key = |key, modifiers| Event::Key {
+        key,
+        physical_key: None,
+        pressed: true,
+        repeat: false,
+        modifiers,
+    };
+    let scroll = |event, interaction: &mut InteractionState| {
+        let target = RefCell::new(None);
+        let _ = ctx.run(raw_input(vec![event]), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                *target.borrow_mut() = keyboard_scroll_target(ui, interaction, 400.0);
+            });
+        });
+        if let Some(target) = target.into_inner() {
+            interaction.scroll_y = target;
+        }
+        interaction.scroll_y
+    };
+
+    let mut interaction = InteractionState {
+        last_layout_root_size: Some((800.0, 1000.0)),
+        ..InteractionState::default()
+    };
+    assert_eq!(
+        scroll(key(egui::Key::ArrowDown, Modifiers::NONE), &mut interaction),
+        40.0
+    );
+    assert_eq!(
+        scroll(key(egui::Key::PageDown, Modifiers::NONE), &mut interaction),
+        390.0
+    );
+    assert_eq!(
+        scroll(key(egui::Key::Space, Modifiers::NONE), &mut interaction),
+        600.0
+    );
+    assert_eq!(
+        scroll(key(egui::Key::Space, Modifiers::SHIFT), &mut interaction),
+        250.0
+    );
+    assert_eq!(
+        scroll(key(egui::Key::Home, Modifiers::NONE), &mut interaction),
+        0.0
+    );
+    assert_eq!(
+        scroll(key(egui::Key::ArrowUp, Modifiers::NONE), &mut interaction),
+        0.0
+    );
+    assert_eq!(
+        scroll(key(egui::Key::End, Modifiers::NONE), &mut interaction),
+        600.0
+    );
+
+    // A focused page control keeps its keys.
+    interaction.scroll_y = 0.0;
+    interaction.focused_node_id = Some(Id(1));
+    assert_eq!(
+        scroll(key(egui::Key::PageDown, Modifiers::NONE), &mut interaction),
+        0.0
+    );
+    // Steps taken while a scroll animation runs start from where it heads.
+    interaction.focused_node_id = None;
+    interaction.scroll_target = Some(100.0);
+    assert_eq!(
+        scroll(key(egui::Key::ArrowDown, Modifiers::NONE), &mut interaction),
+        140.0
+    );
+}
+
+#[test]
+fn selection_drags_near_the_viewport_edges_scroll_by_distance() {
+    let ctx = Context::default();
+    init_context(&ctx);
+
+    let frame = |events: Vec<Event>, interaction: &InteractionState| {
+        let delta = RefCell::new(None);
+        let _ = ctx.run(raw_input(events), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                let viewport = ui.available_rect_before_wrap();
+                *delta.borrow_mut() =
+                    Some((viewport, drag_autoscroll_delta(ui, interaction, viewport)));
+            });
+        });
+        delta.into_inner().unwrap()
+    };
+    let press = |pos| {
+        vec![
+            Event::PointerMoved(pos),
+            Event::PointerButton {
+                pos,
+                button: PointerButton::Primary,
+                pressed: true,
+                modifiers: Modifiers::NONE,
+            },
+        ]
+    };
+
+    let mut interaction = InteractionState::default();
+    let (viewport, delta) = frame(press(Pos2::new(100.0, 300.0)), &interaction);
+    assert_eq!(delta, None, "not dragging a selection");
+
+    interaction
+        .page_selection
+        .start(crate::selection::TextPosition {
+            node_id: Id(1),
+            offset: 0,
+        });
+    let at_y = |y| vec![Event::PointerMoved(Pos2::new(100.0, y))];
+    assert_eq!(frame(at_y(300.0), &interaction).1, None);
+
+    let near_bottom = frame(at_y(viewport.bottom() - 14.0), &interaction)
+        .1
+        .unwrap();
+    let past_bottom = frame(at_y(viewport.bottom() + 6.0), &interaction)
+        .1
+        .unwrap();
+    assert!(near_bottom > 0.0);
+    assert!((past_bottom - near_bottom * 3.0).abs() < 0.01);
+
+    let near_top = frame(at_y(viewport.top() + 4.0), &interaction).1.unwrap();
+    assert!((near_top + near_bottom * 2.0).abs() < 0.01);
+}
@@ -87,3 +87,72 @@ fn drag_selection_updates_caret_and_selection() {
     assert_eq!(caret, expected_end);
     assert_eq!(selection, Some(expected_sel));
 }
+
+#[test]
+fn cut_and_paste_edit_the_focused_text_field() {
+    let ctx = Context::default();
+    init_context(&ctx);
+    let measurer = EguiTextMeasurer::new(&ctx);
+
+    let dom = doc(vec![elem(
+        1,
+        "div",
+        Vec::new(),
+        Vec::new(),
+        vec![input_text(2)],
+    )]);
+    let style_root = build_style_tree(&dom, None);
+    let layout_root = layout::layout_block_tree(&style_root, 500.0, &measurer, None);
+    let content_size = Vec2::new(500.0, layout_root.rect.height.max(200.0));
+    let origin = content_origin(&ctx, content_size);
+    let pos = pos_in_rect(
+        origin,
+        find_fragment_rect_for_node(&layout_root, &measurer, Id(2)).unwrap(),
+        2.0,
+        2.0,
+    );
+
+    let mut store = Store::new();
+    let id = to_input_id(Id(2));
+    store.ensure_initial(id, "hello world".to_string());
+    let mut interaction = InteractionState::default();
+    let form_controls = TestFormControls;
+
+    let mut frame = |events: Vec<Event>, store: &mut Store| {
+        run_frame(FrameRun {
+            ctx: &ctx,
+            raw_input: raw_input(events),
+            layout_root: &layout_root,
+            measurer: &measurer,
+            base_url: None,
+            input_values: store,
+            form_controls: &form_controls,
+            interaction: &mut interaction,
+            content_size,
+            layout_changed: false,
+        });
+    };
+    for pressed in [true, false] {
+        frame(
+            vec![
+                Event::PointerMoved(pos),
+                Event::PointerButton {
+                    pos,
+                    button: PointerButton::Primary,
+                    pressed,
+                    modifiers: Modifiers::NONE,
+                },
+            ],
+            &mut store,
+        );
+    }
+
+    store.set_caret(id, 5, false);
+    store.set_caret(id, 11, true);
+    frame(vec![Event::Cut], &mut store);
+    assert_eq!(store.get(id), Some("hello"));
+
+    // Single-line fields drop the newlines of pasted text.
+    frame(vec![Event::Paste(" big\nworld".to_string())], &mut store);
+    assert_eq!(store.get(id), Some("hello bigworld"));
+}
@@ -12,6 +12,9 @@ pub(super) struct TextareaDragParams<'a> {
     pub(super) input_id: html::internal::Id,
     pub(super) local_x: f32,
     pub(super) local_y: f32,
+    /// Distance past the top (negative) or bottom of the visible text to
+    /// select to instead of `local_y`, when auto-scrolling.
+    pub(super) autoscroll: f32,
     pub(super) viewport_width: f32,
     pub(super) viewport_height: f32,
     pub(super) style: &'a css::ComputedStyle,
@@ -70,12 +73,13 @@ pub(super) fn drag_selection<S: InputStore + ?Sized>(
         input_id,
         local_x,
         local_y,
+        autoscroll,
         viewport_width,
         viewport_height,
         style,
     } = params;
     interaction.textarea.clear_preferred_x();
-    let (pad_l, pad_r, pad_t, _pad_b) = input_text_padding(style);
+    let (pad_l, pad_r, pad_t, pad_b) = input_text_padding(style);
     let available_text_w = (viewport_width - pad_l - pad_r).max(0.0);
     let lines = interaction.textarea.ensure_layout_cache(
         &*input_values,
@@ -91,8 +95,15 @@ pub(super) fn drag_selection<S: InputStore + ?Sized>(
             .map(|(v, _c, _sel, _sx, sy)| (v, sy))
             .unwrap_or(("", 0.0));
 
-        let y_in_viewport = (local_y - pad_t).max(0.0);
-        let y_in_text = y_in_viewport + scroll_y;
+        let y_in_viewport = if autoscroll < 0.0 {
+            autoscroll
+        } else if autoscroll > 0.0 {
+            // From the last visible line.
+            (viewport_height - pad_t - pad_b - 1.0).max(0.0) + autoscroll
+        } else {
+            (local_y - pad_t).max(0.0)
+        };
+        let y_in_text = (y_in_viewport + scroll_y).max(0.0);
         let line_h = measurer.line_height(style);
         let line_idx = textarea_line_index_from_y(lines, y_in_text, line_h);
         let x_in_viewport = (local_x - pad_l).max(0.0);
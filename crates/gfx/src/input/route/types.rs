@@ -1,5 +1,6 @@
-use super::super::InteractionState;
+use super::super::{FormSubmission, InteractionState};
 use crate::EguiTextMeasurer;
+use crate::paint::PaintArtifact;
 use egui::{Pos2, Rect, Ui};
 use html::internal::Id;
 use input_core::{InputId, InputStore};
@@ -13,6 +14,15 @@ use std::collections::HashMap;
 /// Implementors are responsible for converting `html::internal::Id` to `InputId` as needed.
 pub trait FormControlHandler<S: InputStore + ?Sized> {
     fn on_radio_clicked(&self, store: &mut S, radio_id: InputId) -> bool;
+
+    /// Submission of the form `trigger` submits: a click on a submit button,
+    /// or Enter in a text field. `None` if nothing should be submitted.
+    fn form_submission(
+        &self,
+        store: &S,
+        trigger: InputId,
+        base_url: Option<&str>,
+    ) -> Option<FormSubmission>;
 }
 
 pub(crate) struct FrameInputCtx<'a, 'layout, 'dom, S: InputStore + ?Sized, F> {
@@ -24,6 +34,8 @@ pub(crate) struct FrameInputCtx<'a, 'layout, 'dom, S: InputStore + ?Sized, F> {
     pub measurer: &'a EguiTextMeasurer,
     pub layout_changed: bool,
     pub fragment_rects: &'a RefCell<HashMap<Id, Rectangle>>,
+    /// Painted text of this frame, for page text selection.
+    pub page_text: Option<&'a PaintArtifact>,
     pub base_url: Option<&'a str>,
     pub input_values: &'a mut S,
     pub form_controls: &'a F,
@@ -12,7 +12,9 @@
 //! the exported `to_input_id()` function.
 
 use html::internal::Id;
-use input_core::{InputId, InputValueStore as CoreInputValueStore};
+use input_core::{
+    DisplayState, InputConstraints, InputId, InputValueStore as CoreInputValueStore, Validity,
+};
 
 // Re-export SelectionRange directly since it has no Id dependency
 pub use input_core::SelectionRange;
@@ -49,6 +51,43 @@ impl InputValueStore {
         self.inner.get_state(to_input_id(id))
     }
 
+    /// Get the full state tuple in display coordinates.
+    ///
+    /// For masked inputs the string is the mask and caret/selection are byte
+    /// offsets into it; otherwise this matches [`get_state`](Self::get_state).
+    #[inline]
+    pub fn get_display_state(&self, id: Id) -> Option<DisplayState<'_>> {
+        self.inner.get_display_state(to_input_id(id))
+    }
+
+    /// Returns `true` if this input's value is displayed masked.
+    #[inline]
+    pub fn is_masked(&self, id: Id) -> bool {
+        self.inner.is_masked(to_input_id(id))
+    }
+
+    /// Enable or disable masked display for an input.
+    ///
+    /// Returns `true` if the flag actually changed.
+    #[inline]
+    pub fn set_masked(&mut self, id: Id, masked: bool) -> bool {
+        self.inner.set_masked(to_input_id(id), masked)
+    }
+
+    /// Set the validation constraints for an input and recompute its validity.
+    ///
+    /// Passing empty constraints removes them. Returns the new validity.
+    #[inline]
+    pub fn set_constraints(&mut self, id: Id, constraints: InputConstraints) -> Validity {
+        self.inner.set_constraints(to_input_id(id), constraints)
+    }
+
+    /// Returns the cached validity for an input.
+    #[inline]
+    pub fn validity(&self, id: Id) -> Validity {
+        self.inner.validity(to_input_id(id))
+    }
+
     /// Monotonic revision counter for the input's value.
     ///
     /// Increments on any text change. Useful for cache invalidation.
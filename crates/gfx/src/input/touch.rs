@@ -0,0 +1,244 @@
+//! Touch gestures on the page: one-finger panning with momentum and
+//! two-finger pinch zoom.
+//!
+//! Only touches that start on the page take part, so egui keeps handling
+//! touches on browser chrome on its own. egui also turns the first finger into
+//! pointer input, which still taps links and controls; a finger that moves
+//! past [`PAN_SLOP`] pans instead.
+
+use super::InteractionState;
+use super::route::{autoscroll_dt, clamp_scroll};
+use egui::{Event, Pos2, Rect, TouchId, TouchPhase, Ui};
+use std::collections::BTreeMap;
+
+/// Distance a finger travels before the page starts panning.
+const PAN_SLOP: f32 = 8.0;
+
+/// Share of the coasting velocity kept after one second.
+const MOMENTUM_RETAINED_PER_SECOND: f32 = 0.05;
+
+/// Coasting stops below this speed, in pixels per second.
+const MOMENTUM_MIN_SPEED: f32 = 30.0;
+
+/// Weight of the latest frame when smoothing the pan velocity.
+const VELOCITY_SMOOTHING: f32 = 0.6;
+
+#[derive(Debug, Default)]
+pub(crate) struct TouchState {
+    /// Fingers down that started on the page, at their last position.
+    fingers: BTreeMap<TouchId, Pos2>,
+    /// Vertical travel of the single finger before it started panning.
+    travel: f32,
+    panning: bool,
+    coasting: bool,
+    /// Scroll speed in pixels per second; positive scrolls down.
+    velocity: f32,
+}
+
+impl TouchState {
+    pub(crate) fn is_panning(&self) -> bool {
+        self.panning
+    }
+
+    /// End any momentum scroll, e.g. because another input scrolled.
+    pub(crate) fn stop_coasting(&mut self) {
+        self.coasting = false;
+        self.velocity = 0.0;
+    }
+
+    /// Feed one frame of touch events; returns how far the page scrolls.
+    fn on_frame(&mut self, events: &[Event], page_rect: Rect, dt: f32) -> f32 {
+        let mut delta = 0.0;
+        for event in events {
+            let Event::Touch { id, phase, pos, .. } = *event else {
+                continue;
+            };
+            match phase {
+                TouchPhase::Start if page_rect.contains(pos) => {
+                    self.stop_coasting();
+                    self.fingers.insert(id, pos);
+                    self.travel = 0.0;
+                    // A second finger turns the gesture into a pinch.
+                    self.panning = false;
+                }
+                TouchPhase::Move => {
+                    let single = self.fingers.len() == 1;
+                    let Some(last) = self.fingers.get_mut(&id) else {
+                        continue;
+                    };
+                    // Content follows the finger, so the offset moves against it.
+                    let moved = last.y - pos.y;
+                    *last = pos;
+                    if !single {
+                        continue;
+                    }
+                    if self.panning {
+                        delta += moved;
+                    } else {
+                        self.travel += moved;
+                        if self.travel.abs() > PAN_SLOP {
+                            self.panning = true;
+                            delta += self.travel;
+                        }
+                    }
+                }
+                TouchPhase::End | TouchPhase::Cancel => {
+                    if self.fingers.remove(&id).is_none() {
+                        continue;
+                    }
+                    if self.fingers.is_empty() {
+                        self.coasting = self.panning
+                            && phase == TouchPhase::End
+                            && self.velocity.abs() >= MOMENTUM_MIN_SPEED;
+                        self.panning = false;
+                    } else {
+                        // The finger left over from a pinch must pass the slop anew.
+                        self.travel = 0.0;
+                    }
+                }
+                TouchPhase::Start => {}
+            }
+        }
+
+        if self.panning && dt > 0.0 {
+            let latest = delta / dt;
+            self.velocity += (latest - self.velocity) * VELOCITY_SMOOTHING;
+        } else if self.coasting {
+            delta += self.velocity * dt;
+            self.velocity *= MOMENTUM_RETAINED_PER_SECOND.powf(dt);
+            if self.velocity.abs() < MOMENTUM_MIN_SPEED {
+                self.stop_coasting();
+            }
+        } else if !self.panning {
+            self.velocity = 0.0;
+        }
+        delta
+    }
+}
+
+/// New page scroll offset from a touch pan or its momentum this frame.
+///
+/// `page_rect` is the visible page; touches starting outside it are left to
+/// egui. A pan drops the text selection and the active link or control the
+/// finger's pointer press started, so lifting the finger activates nothing.
+pub(crate) fn touch_scroll_target(
+    ui: &Ui,
+    interaction: &mut InteractionState,
+    page_rect: Rect,
+) -> Option<f32> {
+    let dt = autoscroll_dt(ui);
+    let delta = ui.input(|i| interaction.touch.on_frame(&i.events, page_rect, dt));
+    if interaction.touch.coasting {
+        ui.ctx().request_repaint();
+    }
+    if interaction.touch.is_panning() {
+        if interaction.page_selection.is_dragging() {
+            interaction.page_selection.clear();
+        }
+        interaction.active = None;
+    }
+    if delta == 0.0 {
+        return None;
+    }
+    Some(clamp_scroll(
+        interaction,
+        page_rect.height(),
+        interaction.scroll_y + delta,
+    ))
+}
+
+/// Zoom factor, relative to the current zoom, of a pinch on the page this
+/// frame.
+pub(crate) fn pinch_zoom_factor(ui: &Ui, interaction: &InteractionState) -> Option<f32> {
+    if interaction.touch.fingers.len() < 2 {
+        return None;
+    }
+    let factor = ui.input(|i| i.multi_touch())?.zoom_delta;
+    (factor.is_finite() && factor > 0.0 && factor != 1.0).then_some(factor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MOMENTUM_MIN_SPEED, TouchState};
+    use egui::{Event, Pos2, Rect, TouchDeviceId, TouchId, TouchPhase, pos2, vec2};
+
+    const DT: f32 = 1.0 / 60.0;
+
+    fn page() -> Rect {
+        Rect::from_min_size(pos2(0.0, 40.0), vec2(800.0, 600.0))
+    }
+
+    fn touch(id: u64, phase: TouchPhase, pos: Pos2) -> Event {
+        Event::Touch {
+            device_id: TouchDeviceId(0),
+            id: TouchId(id),
+            phase,
+            pos,
+            force: None,
+        }
+    }
+
+    #[test]
+    fn one_finger_pans_past_the_slop_and_coasts_after_lifting() {
+        let mut state = TouchState::default();
+        let start = touch(1, TouchPhase::Start, pos2(100.0, 400.0));
+        assert_eq!(state.on_frame(&[start], page(), DT), 0.0);
+
+        let small = touch(1, TouchPhase::Move, pos2(100.0, 396.0));
+        assert_eq!(state.on_frame(&[small], page(), DT), 0.0, "within slop");
+        assert!(!state.is_panning());
+
+        // Moving the finger up scrolls the page down, including the slop.
+        let mut scrolled = 0.0;
+        for step in 1..=5 {
+            let y = 396.0 - 20.0 * step as f32;
+            scrolled += state.on_frame(&[touch(1, TouchPhase::Move, pos2(100.0, y))], page(), DT);
+        }
+        assert!(state.is_panning());
+        assert_eq!(scrolled, 104.0);
+
+        let end = touch(1, TouchPhase::End, pos2(100.0, 296.0));
+        state.on_frame(&[end], page(), DT);
+        assert!(!state.is_panning());
+        let mut coasted = 0.0;
+        let mut frames = 0;
+        loop {
+            let delta = state.on_frame(&[], page(), DT);
+            if delta == 0.0 {
+                break;
+            }
+            assert!(delta > 0.0);
+            coasted += delta;
+            frames += 1;
+            assert!(frames < 1000, "momentum must settle");
+        }
+        assert!(coasted > 100.0, "coasted only {coasted}");
+        assert!(state.velocity.abs() < MOMENTUM_MIN_SPEED);
+    }
+
+    #[test]
+    fn touches_starting_off_the_page_and_pinches_do_not_pan() {
+        let mut state = TouchState::default();
+        let chrome = [
+            touch(1, TouchPhase::Start, pos2(100.0, 20.0)),
+            touch(1, TouchPhase::Move, pos2(100.0, 200.0)),
+        ];
+        assert_eq!(state.on_frame(&chrome, page(), DT), 0.0);
+
+        let pinch = [
+            touch(2, TouchPhase::Start, pos2(100.0, 300.0)),
+            touch(3, TouchPhase::Start, pos2(200.0, 300.0)),
+            touch(2, TouchPhase::Move, pos2(80.0, 250.0)),
+            touch(3, TouchPhase::Move, pos2(220.0, 350.0)),
+        ];
+        assert_eq!(state.on_frame(&pinch, page(), DT), 0.0);
+        assert_eq!(state.fingers.len(), 2);
+
+        let lift = [
+            touch(2, TouchPhase::End, pos2(80.0, 250.0)),
+            touch(3, TouchPhase::End, pos2(220.0, 350.0)),
+        ];
+        state.on_frame(&lift, page(), DT);
+        assert_eq!(state.on_frame(&[], page(), DT), 0.0, "no momentum");
+    }
+}
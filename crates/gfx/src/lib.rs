@@ -14,8 +14,13 @@ use winit::{dpi::PhysicalSize, event::WindowEvent, window::Window};
 
 pub mod text_measurer;
 pub use text_measurer::EguiTextMeasurer;
+pub(crate) mod box_overlay;
+pub mod find;
+pub(crate) mod fragment;
 pub mod input;
 pub mod paint;
+pub mod selection;
+pub mod software;
 pub(crate) mod text_control;
 pub mod textarea;
 pub mod ui;
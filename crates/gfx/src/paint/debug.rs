@@ -468,6 +468,7 @@ fn replaced_kind_debug_label(kind: PaintReplacedKind) -> &'static str {
         PaintReplacedKind::TextArea => "textarea",
         PaintReplacedKind::InputCheckbox => "input-checkbox",
         PaintReplacedKind::InputRadio => "input-radio",
+        PaintReplacedKind::InputFile => "input-file",
         PaintReplacedKind::Button => "button",
     }
 }
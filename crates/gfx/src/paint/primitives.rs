@@ -750,6 +750,7 @@ pub enum PaintReplacedKind {
     TextArea,
     InputCheckbox,
     InputRadio,
+    InputFile,
     Button,
 }
 
@@ -761,6 +762,7 @@ impl PaintReplacedKind {
             Self::TextArea => "textarea",
             Self::InputCheckbox => "input-checkbox",
             Self::InputRadio => "input-radio",
+            Self::InputFile => "input-file",
             Self::Button => "button",
         }
     }
@@ -1088,6 +1090,7 @@ impl PaintReplacedKind {
             ReplacedKind::TextArea => Self::TextArea,
             ReplacedKind::InputCheckbox => Self::InputCheckbox,
             ReplacedKind::InputRadio => Self::InputRadio,
+            ReplacedKind::InputFile => Self::InputFile,
             ReplacedKind::Button => Self::Button,
         }
     }
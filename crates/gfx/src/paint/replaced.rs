@@ -1,6 +1,9 @@
 use css::{ComputedStyle, Length};
 use egui::{Align2, Color32, FontId, Pos2, Rect, Stroke, StrokeKind, Vec2};
-use layout::inline::button_label_from_layout;
+use layout::inline::{
+    FILE_INPUT_BUTTON_LABEL, FILE_INPUT_BUTTON_PADDING, FILE_INPUT_EMPTY_LABEL,
+    FILE_INPUT_LABEL_GAP, button_label_from_layout,
+};
 use layout::{HitKind, LayoutBox, ReplacedKind};
 
 use super::context::PaintCtx;
@@ -146,6 +149,8 @@ pub(super) fn paint_replaced_fragment(
             }
         }
 
+        ReplacedKind::InputFile => paint_input_file(rect, style, layout, ctx),
+
         ReplacedKind::Img => super::images::paint_img_fragment(rect, style, layout, ctx),
 
         ReplacedKind::InputText => super::text_control::paint_input_text(rect, style, layout, ctx),
@@ -162,6 +167,7 @@ pub(super) fn paint_replaced_fragment(
             | ReplacedKind::TextArea
             | ReplacedKind::InputCheckbox
             | ReplacedKind::InputRadio
+            | ReplacedKind::InputFile
             | ReplacedKind::Img
     ) {
         let font_id = font_id_from_style(style);
@@ -179,6 +185,76 @@ pub(super) fn paint_replaced_fragment(
     }
 }
 
+/// A "Choose file…" button followed by the chosen file names, which the
+/// browser keeps as the control's input value.
+fn paint_input_file(
+    rect: Rect,
+    style: &ComputedStyle,
+    layout: Option<&LayoutBox<'_, '_>>,
+    ctx: PaintCtx<'_>,
+) {
+    let painter = ctx.painter;
+    let font_id = font_id_from_style(style);
+    let text_color = text_color_from_style(style);
+
+    let id = layout.map(layout::LayoutBox::node_id);
+    let is_pressed = id.is_some_and(|id| {
+        ctx.active
+            .is_some_and(|a| a.id == id && matches!(a.kind, HitKind::FileInput))
+    });
+
+    let button_galley = painter.layout_no_wrap(
+        FILE_INPUT_BUTTON_LABEL.to_string(),
+        font_id.clone(),
+        text_color,
+    );
+    let button_width = (button_galley.size().x + 2.0 * FILE_INPUT_BUTTON_PADDING).min(rect.width());
+    let button_rect = Rect::from_min_size(rect.min, Vec2::new(button_width, rect.height()));
+
+    let base_fill = background_color_from_style(style);
+    let fill = if is_pressed {
+        base_fill.gamma_multiply(0.9)
+    } else {
+        base_fill
+    };
+    painter.rect_filled(button_rect, 6.0, fill);
+    let border_color = force_opaque(blend_colors(fill, text_color, 0.5));
+    let border_width = if is_pressed { 2.0 } else { 1.0 };
+    painter.rect_stroke(
+        button_rect,
+        6.0,
+        Stroke::new(border_width, border_color),
+        StrokeKind::Outside,
+    );
+    painter.galley(
+        button_rect.center() - button_galley.size() / 2.0,
+        button_galley,
+        text_color,
+    );
+
+    let chosen = id
+        .and_then(|id| ctx.input_values.get(id))
+        .filter(|names| !names.is_empty());
+    let label = chosen.unwrap_or(FILE_INPUT_EMPTY_LABEL);
+    let label_rect = Rect::from_min_max(
+        Pos2::new(button_rect.max.x + FILE_INPUT_LABEL_GAP, rect.min.y),
+        rect.max,
+    );
+    if label_rect.width() <= 0.0 {
+        return;
+    }
+    // Long file names are clipped to the control rather than resizing it.
+    painter
+        .with_clip_rect(label_rect.intersect(painter.clip_rect()))
+        .text(
+            label_rect.left_center(),
+            Align2::LEFT_CENTER,
+            label,
+            font_id,
+            text_color,
+        );
+}
+
 fn font_id_from_style(style: &ComputedStyle) -> FontId {
     match style.font_size() {
         Length::Px(px) => FontId::proportional(px),
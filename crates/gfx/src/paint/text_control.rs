@@ -1,12 +1,14 @@
 use crate::input::SelectionRange;
 use crate::textarea::{
-    TextareaCachedLine, TextareaSelectionPaintParams, layout_textarea_cached_lines,
-    paint_textarea_selection, textarea_caret_geometry, textarea_text_height,
+    RESIZE_GRIP_SIZE, TextareaCachedLine, TextareaSelectionPaintParams,
+    layout_textarea_cached_lines, paint_textarea_selection, textarea_caret_geometry,
+    textarea_text_height,
 };
 use crate::util::{clamp_to_char_boundary, input_text_padding, truncate_to_fit};
-use css::{ComputedStyle, Length};
+use css::{ComputedStyle, Length, Resize};
 use egui::{Align2, Color32, FontId, Painter, Pos2, Rect, Stroke, StrokeKind, Vec2};
 use layout::{LayoutBox, TextMeasurer};
+use std::borrow::Cow;
 
 use super::context::PaintCtx;
 
@@ -23,7 +25,7 @@ pub(super) fn paint_input_text(
 
     paint_text_control_container(painter, rect, style, is_focused, ctx.selection_stroke);
 
-    let mut value: &str = "";
+    let mut display: Cow<'_, str> = Cow::Borrowed("");
     let mut placeholder: Option<&str> = None;
     let mut caret: usize = 0;
     let mut selection: Option<SelectionRange> = None;
@@ -31,19 +33,22 @@ pub(super) fn paint_input_text(
 
     if let Some(lb) = layout {
         let id = lb.node_id();
-        if let Some((v, c, sel, sx, _sy)) = ctx.input_values.get_state(id) {
-            value = v;
+        // Masked inputs hand back the mask string with caret/selection already
+        // mapped into it, so everything below measures what is painted.
+        if let Some((v, c, sel, sx, _sy)) = ctx.input_values.get_display_state(id) {
+            display = v;
             caret = c;
             selection = sel;
             scroll_x = sx;
         }
 
-        placeholder = if value.is_empty() {
+        placeholder = if display.is_empty() {
             text_control_presentation(lb).and_then(layout::TextControlPresentation::placeholder)
         } else {
             None
         };
     }
+    let value: &str = &display;
 
     let (pad_l, pad_r, pad_t, pad_b) = input_text_padding(style);
     let available_text_w = (rect.width() - pad_l - pad_r).max(0.0);
@@ -373,6 +378,31 @@ pub(super) fn paint_textarea(
             clip_painter.rect_filled(caret_rect, 0.0, value_color);
         }
     }
+
+    if style.resize() != Resize::None {
+        paint_resize_grip(painter, rect, text_color.gamma_multiply(0.5));
+    }
+}
+
+/// Two diagonal strokes in the bottom-right corner, like the native grip.
+fn paint_resize_grip(painter: &Painter, rect: Rect, color: Color32) {
+    let corner = rect.right_bottom() - Vec2::splat(2.0);
+    let stroke = Stroke::new(1.0, color);
+    for reach in [RESIZE_GRIP_SIZE * 0.4, RESIZE_GRIP_SIZE * 0.8] {
+        painter.line_segment(
+            [
+                Pos2 {
+                    x: corner.x - reach,
+                    y: corner.y,
+                },
+                Pos2 {
+                    x: corner.x,
+                    y: corner.y - reach,
+                },
+            ],
+            stroke,
+        );
+    }
 }
 
 fn text_control_presentation<'a>(
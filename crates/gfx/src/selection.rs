@@ -0,0 +1,417 @@
+//! Page text selection over painted text.
+//!
+//! Like find-in-page, selection works on the text primitives of a
+//! [`PaintArtifact`]. Positions are a node id plus a byte offset into the text
+//! that node painted, so they stay meaningful while the page repaints. Text
+//! is ordered by layout box, which follows DOM order, not paint order.
+
+use html::internal::Id;
+use layout::Rectangle;
+use std::collections::HashMap;
+
+use crate::paint::{PaintArtifact, PaintNode, PaintPrimitive};
+
+/// A point in the page text: byte `offset` into the concatenated text painted
+/// for `node_id`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TextPosition {
+    pub node_id: Id,
+    pub offset: usize,
+}
+
+/// Document-scoped text selection made by dragging over the page.
+#[derive(Debug, Default)]
+pub struct PageSelection {
+    anchor: Option<TextPosition>,
+    focus: Option<TextPosition>,
+    dragging: bool,
+    text: String,
+}
+
+impl PageSelection {
+    /// Where the selection started and where it currently ends, unless
+    /// nothing is selected.
+    pub fn range(&self) -> Option<(TextPosition, TextPosition)> {
+        let (anchor, focus) = (self.anchor?, self.focus?);
+        (anchor != focus).then_some((anchor, focus))
+    }
+
+    /// Returns `true` while a primary-button drag extends the selection.
+    pub fn is_dragging(&self) -> bool {
+        self.dragging
+    }
+
+    /// Selected text in DOM order, if any.
+    pub fn selected_text(&self) -> Option<&str> {
+        (self.range().is_some() && !self.text.is_empty()).then_some(self.text.as_str())
+    }
+
+    /// Start a drag selection at `position`.
+    pub fn start(&mut self, position: TextPosition) {
+        self.anchor = Some(position);
+        self.focus = Some(position);
+        self.dragging = true;
+        self.text.clear();
+    }
+
+    /// Move the moving end of the selection to `position`, refreshing the
+    /// selected text. Returns `true` if the selection changed.
+    pub fn extend(&mut self, position: TextPosition, artifact: &PaintArtifact) -> bool {
+        if self.anchor.is_none() || self.focus == Some(position) {
+            return false;
+        }
+        self.focus = Some(position);
+        self.text = self
+            .range()
+            .map(|(anchor, focus)| selected_text(artifact, anchor, focus))
+            .unwrap_or_default();
+        true
+    }
+
+    pub fn finish_drag(&mut self) {
+        self.dragging = false;
+    }
+
+    /// Drop the selection. Returns `true` if anything was selected.
+    pub fn clear(&mut self) -> bool {
+        let had_selection = self.range().is_some();
+        *self = Self::default();
+        had_selection
+    }
+}
+
+struct Segment<'a> {
+    node_id: Id,
+    box_id: usize,
+    /// Offset of `text` within the text painted for `node_id`.
+    node_offset: usize,
+    text: &'a str,
+    rect: Rectangle,
+    font_size_px: f32,
+}
+
+fn collect_segments(artifact: &PaintArtifact) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    collect_node_segments(artifact.tree().root(), &mut segments);
+    // Within a box, paint order is line order.
+    segments.sort_by_key(|segment| segment.box_id);
+
+    let mut node_lengths: HashMap<Id, usize> = HashMap::new();
+    for segment in &mut segments {
+        let length = node_lengths.entry(segment.node_id).or_default();
+        segment.node_offset = *length;
+        *length += segment.text.len();
+    }
+    segments
+}
+
+fn collect_node_segments<'a>(node: &'a PaintNode, out: &mut Vec<Segment<'a>>) {
+    let texts = |primitives: &'a [PaintPrimitive]| {
+        primitives.iter().filter_map(|primitive| match primitive {
+            PaintPrimitive::Text(text) => Some(Segment {
+                node_id: text.source.node_id,
+                box_id: text.source.box_id,
+                node_offset: 0,
+                text: &text.text,
+                rect: text.rect,
+                font_size_px: text.font_size_px,
+            }),
+            _ => None,
+        })
+    };
+    out.extend(texts(node.primitives()));
+    for child in node.children() {
+        collect_node_segments(child, out);
+    }
+    out.extend(texts(node.post_primitives()));
+}
+
+/// Segment index and byte offset within it of `position`.
+fn locate(segments: &[Segment<'_>], position: TextPosition) -> Option<(usize, usize)> {
+    let mut end_of_node = None;
+    for (idx, segment) in segments.iter().enumerate() {
+        if segment.node_id != position.node_id {
+            continue;
+        }
+        let Some(offset) = position.offset.checked_sub(segment.node_offset) else {
+            continue;
+        };
+        if offset < segment.text.len() {
+            return Some((idx, offset));
+        }
+        if offset == segment.text.len() {
+            end_of_node = Some((idx, offset));
+        }
+    }
+    end_of_node
+}
+
+/// Both ends of a selection located and ordered as in the document.
+fn ordered_span(
+    segments: &[Segment<'_>],
+    anchor: TextPosition,
+    focus: TextPosition,
+) -> Option<((usize, usize), (usize, usize))> {
+    let anchor = locate(segments, anchor)?;
+    let focus = locate(segments, focus)?;
+    Some(if anchor <= focus {
+        (anchor, focus)
+    } else {
+        (focus, anchor)
+    })
+}
+
+/// Text position closest to `point` (layout coordinates): the nearest text
+/// fragment, then the nearest character boundary within it.
+///
+/// `measure(text, font_size_px)` returns the painted width of `text`.
+pub fn text_position_at(
+    artifact: &PaintArtifact,
+    point: (f32, f32),
+    measure: &dyn Fn(&str, f32) -> f32,
+) -> Option<TextPosition> {
+    let segments = collect_segments(artifact);
+    let distance = |from: f32, start: f32, len: f32| {
+        if from < start {
+            start - from
+        } else {
+            (from - start - len).max(0.0)
+        }
+    };
+    let segment = segments.iter().min_by(|a, b| {
+        let key = |s: &Segment<'_>| {
+            (
+                distance(point.1, s.rect.y, s.rect.height),
+                distance(point.0, s.rect.x, s.rect.width),
+            )
+        };
+        key(a)
+            .partial_cmp(&key(b))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    })?;
+
+    let local_x = point.0 - segment.rect.x;
+    let offset = segment
+        .text
+        .char_indices()
+        .map(|(offset, _)| offset)
+        .chain(std::iter::once(segment.text.len()))
+        .min_by(|&a, &b| {
+            let gap = |offset: usize| (x_at(segment, offset, measure) - local_x).abs();
+            gap(a).total_cmp(&gap(b))
+        })
+        .unwrap_or(0);
+    Some(TextPosition {
+        node_id: segment.node_id,
+        offset: segment.node_offset + offset,
+    })
+}
+
+/// Painted x of byte `offset` in `segment`, relative to its left edge.
+fn x_at(segment: &Segment<'_>, offset: usize, measure: &dyn Fn(&str, f32) -> f32) -> f32 {
+    if offset == 0 {
+        0.0
+    } else if offset >= segment.text.len() {
+        segment.rect.width
+    } else {
+        measure(&segment.text[..offset], segment.font_size_px)
+    }
+}
+
+/// Highlight rectangles (layout coordinates) covering the text between
+/// `anchor` and `focus`, one per fragment touched.
+pub fn selection_rects(
+    artifact: &PaintArtifact,
+    anchor: TextPosition,
+    focus: TextPosition,
+    measure: &dyn Fn(&str, f32) -> f32,
+) -> Vec<Rectangle> {
+    let segments = collect_segments(artifact);
+    let Some((start, end)) = ordered_span(&segments, anchor, focus) else {
+        return Vec::new();
+    };
+
+    let mut rects = Vec::new();
+    for (idx, segment) in segments.iter().enumerate().take(end.0 + 1).skip(start.0) {
+        let from = if idx == start.0 { start.1 } else { 0 };
+        let to = if idx == end.0 {
+            end.1
+        } else {
+            segment.text.len()
+        };
+        let x0 = x_at(segment, from, measure);
+        let x1 = x_at(segment, to, measure);
+        if x1 > x0 {
+            rects.push(Rectangle {
+                x: segment.rect.x + x0,
+                y: segment.rect.y,
+                width: x1 - x0,
+                height: segment.rect.height,
+            });
+        }
+    }
+    rects
+}
+
+/// Text between `anchor` and `focus` in DOM order, as copied to the
+/// clipboard. Separate blocks go on separate lines; wrapped lines of one
+/// block are joined with a space.
+pub fn selected_text(
+    artifact: &PaintArtifact,
+    anchor: TextPosition,
+    focus: TextPosition,
+) -> String {
+    let segments = collect_segments(artifact);
+    let Some((start, end)) = ordered_span(&segments, anchor, focus) else {
+        return String::new();
+    };
+
+    let mut out = String::new();
+    let mut prev: Option<&Segment<'_>> = None;
+    for (idx, segment) in segments.iter().enumerate().take(end.0 + 1).skip(start.0) {
+        let from = if idx == start.0 { start.1 } else { 0 };
+        let to = if idx == end.0 {
+            end.1
+        } else {
+            segment.text.len()
+        };
+        let text = &segment.text[from..to];
+        if let Some(prev) = prev {
+            if prev.box_id != segment.box_id {
+                out.push('\n');
+            } else if (prev.rect.y - segment.rect.y).abs() > 0.5
+                && !out.ends_with(char::is_whitespace)
+                && !text.starts_with(char::is_whitespace)
+            {
+                out.push(' ');
+            }
+        }
+        out.push_str(text);
+        prev = Some(segment);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paint::PaintPhaseInput;
+    use css::{ComputedStyle, Length};
+    use html::Node;
+    use layout::{LayoutPhaseInput, TextMeasurer};
+
+    struct TestMeasurer;
+
+    impl TextMeasurer for TestMeasurer {
+        fn measure(&self, text: &str, _style: &ComputedStyle) -> f32 {
+            text.chars().count() as f32 * 8.0
+        }
+
+        fn line_height(&self, style: &ComputedStyle) -> f32 {
+            let Length::Px(px) = style.font_size();
+            px * 1.2
+        }
+    }
+
+    fn measure(text: &str, _font_size_px: f32) -> f32 {
+        text.chars().count() as f32 * 8.0
+    }
+
+    fn block(id: u32, text_id: u32, text: &str) -> Node {
+        html::internal::node_element_from_parts(
+            Id(id),
+            html::internal::html_name("div"),
+            Vec::new(),
+            vec![
+                ("display".to_string(), "block".to_string()),
+                ("width".to_string(), "200px".to_string()),
+            ],
+            vec![Node::Text {
+                id: Id(text_id),
+                text: text.to_string(),
+            }],
+        )
+    }
+
+    fn artifact_for(blocks: Vec<Node>) -> PaintArtifact {
+        let dom = Node::Document {
+            id: Id(1),
+            doctype: None,
+            children: blocks,
+        };
+        let styled = css::build_style_tree(&dom, None);
+        let layout =
+            layout::layout_document(LayoutPhaseInput::new(&styled, 500.0, &TestMeasurer, None));
+        PaintArtifact::from_phase_input(PaintPhaseInput::new(&layout), &TestMeasurer)
+    }
+
+    fn rect_of(artifact: &PaintArtifact, word: &str) -> Rectangle {
+        collect_segments(artifact)
+            .into_iter()
+            .find(|segment| segment.text == word)
+            .map(|segment| segment.rect)
+            .expect("word painted")
+    }
+
+    #[test]
+    fn positions_snap_to_the_nearest_character_boundary() {
+        let artifact = artifact_for(vec![block(2, 3, "Hello world")]);
+        let hello = rect_of(&artifact, "Hello");
+        let mid = hello.y + hello.height / 2.0;
+
+        let at = |x: f32| text_position_at(&artifact, (x, mid), &measure).unwrap();
+        assert_eq!(at(hello.x + 17.0).offset, 2);
+        assert_eq!(at(hello.x - 50.0).offset, 0);
+        // Far right of the line lands at the end of the last word.
+        assert_eq!(at(hello.x + 400.0).offset, "Hello world".len());
+        assert_eq!(at(hello.x).node_id, Id(2));
+    }
+
+    #[test]
+    fn selected_text_follows_dom_order_across_blocks() {
+        let artifact = artifact_for(vec![block(2, 3, "First block"), block(4, 5, "Second")]);
+        let first = TextPosition {
+            node_id: Id(2),
+            offset: 6,
+        };
+        let second = TextPosition {
+            node_id: Id(4),
+            offset: 3,
+        };
+
+        assert_eq!(selected_text(&artifact, first, second), "block\nSec");
+        // Selecting backwards yields the same text.
+        assert_eq!(selected_text(&artifact, second, first), "block\nSec");
+
+        let rects = selection_rects(&artifact, second, first, &measure);
+        assert_eq!(rects.len(), 2);
+        assert_eq!(rects[0].width, 40.0);
+        assert_eq!(rects[1].width, 24.0);
+    }
+
+    #[test]
+    fn collapsed_selection_selects_nothing() {
+        let artifact = artifact_for(vec![block(2, 3, "Hello")]);
+        let start = TextPosition {
+            node_id: Id(2),
+            offset: 1,
+        };
+        let mut selection = PageSelection::default();
+        selection.start(start);
+        assert!(selection.is_dragging());
+        assert_eq!(selection.range(), None);
+        assert!(!selection.extend(start, &artifact));
+
+        assert!(selection.extend(
+            TextPosition {
+                node_id: Id(2),
+                offset: 4,
+            },
+            &artifact,
+        ));
+        assert_eq!(selection.selected_text(), Some("ell"));
+        selection.finish_drag();
+        assert!(selection.clear());
+        assert_eq!(selection.selected_text(), None);
+    }
+}
@@ -0,0 +1,260 @@
+//! Rendering egui frames on the CPU, for pages drawn without a window.
+//!
+//! [`SoftwareRenderer`] runs an egui pass, tessellates its shapes with the
+//! same tessellator the GPU renderer uses, and fills the triangles into an
+//! image. Colors stay premultiplied in gamma space, as in egui's own
+//! shaders, and textures are sampled nearest-neighbour so output does not
+//! depend on filtering.
+
+use egui::epaint::{ClippedPrimitive, ImageData, ImageDelta, Primitive, Vertex};
+use egui::{Color32, ColorImage, Context, Mesh, Pos2, RawInput, Rect, TextureId, vec2};
+use std::collections::HashMap;
+
+pub struct SoftwareRenderer {
+    ctx: Context,
+    textures: HashMap<TextureId, ColorImage>,
+}
+
+impl SoftwareRenderer {
+    pub fn new(ctx: Context) -> Self {
+        Self {
+            ctx,
+            textures: HashMap::new(),
+        }
+    }
+
+    pub fn context(&self) -> &Context {
+        &self.ctx
+    }
+
+    /// Run `build_ui` in a viewport of `size` pixels and draw the frame over
+    /// `background`.
+    pub fn render(
+        &mut self,
+        size: [usize; 2],
+        background: Color32,
+        build_ui: impl FnMut(&Context),
+    ) -> ColorImage {
+        let raw_input = RawInput {
+            screen_rect: Some(Rect::from_min_size(
+                Pos2::ZERO,
+                vec2(size[0] as f32, size[1] as f32),
+            )),
+            ..RawInput::default()
+        };
+        let output = self.ctx.run(raw_input, build_ui);
+        for (id, delta) in &output.textures_delta.set {
+            self.set_texture(*id, delta);
+        }
+
+        let mut canvas = ColorImage::new(size, vec![background; size[0] * size[1]]);
+        let primitives = self.ctx.tessellate(output.shapes, output.pixels_per_point);
+        for ClippedPrimitive {
+            clip_rect,
+            primitive,
+        } in &primitives
+        {
+            if let Primitive::Mesh(mesh) = primitive {
+                self.draw_mesh(&mut canvas, mesh, *clip_rect, output.pixels_per_point);
+            }
+        }
+
+        for id in &output.textures_delta.free {
+            self.textures.remove(id);
+        }
+        canvas
+    }
+
+    fn set_texture(&mut self, id: TextureId, delta: &ImageDelta) {
+        let ImageData::Color(image) = &delta.image;
+        let Some([x, y]) = delta.pos else {
+            self.textures.insert(id, (**image).clone());
+            return;
+        };
+        let Some(texture) = self.textures.get_mut(&id) else {
+            return;
+        };
+        let [width, height] = image.size;
+        for row in 0..height {
+            let start = (y + row) * texture.size[0] + x;
+            let source = &image.pixels[row * width..(row + 1) * width];
+            if let Some(target) = texture.pixels.get_mut(start..start + width) {
+                target.copy_from_slice(source);
+            }
+        }
+    }
+
+    fn draw_mesh(&self, canvas: &mut ColorImage, mesh: &Mesh, clip: Rect, pixels_per_point: f32) {
+        let texture = self.textures.get(&mesh.texture_id);
+        let clip = clip * pixels_per_point;
+        let vertex = |index: u32| {
+            let mut vertex = mesh.vertices[index as usize];
+            vertex.pos = (vertex.pos.to_vec2() * pixels_per_point).to_pos2();
+            vertex
+        };
+        for triangle in mesh.indices.chunks_exact(3) {
+            fill_triangle(
+                canvas,
+                [
+                    vertex(triangle[0]),
+                    vertex(triangle[1]),
+                    vertex(triangle[2]),
+                ],
+                clip,
+                texture,
+            );
+        }
+    }
+}
+
+/// Fill the pixels whose centers lie in the triangle. Pixels on an edge
+/// shared by two triangles are filled by exactly one of them.
+fn fill_triangle(
+    canvas: &mut ColorImage,
+    [a, mut b, mut c]: [Vertex; 3],
+    clip: Rect,
+    texture: Option<&ColorImage>,
+) {
+    let mut area = edge(a.pos, b.pos, c.pos);
+    if area == 0.0 {
+        return;
+    }
+    if area < 0.0 {
+        std::mem::swap(&mut b, &mut c);
+        area = -area;
+    }
+
+    let [width, height] = canvas.size;
+    let bounds = Rect::from_points(&[a.pos, b.pos, c.pos]).intersect(clip);
+    let first_x = bounds.min.x.floor().max(0.0) as usize;
+    let first_y = bounds.min.y.floor().max(0.0) as usize;
+    let end_x = (bounds.max.x.ceil().max(0.0) as usize).min(width);
+    let end_y = (bounds.max.y.ceil().max(0.0) as usize).min(height);
+
+    for y in first_y..end_y {
+        for x in first_x..end_x {
+            let center = Pos2::new(x as f32 + 0.5, y as f32 + 0.5);
+            if !clip.contains(center) {
+                continue;
+            }
+            let weights = [
+                edge(b.pos, c.pos, center),
+                edge(c.pos, a.pos, center),
+                edge(a.pos, b.pos, center),
+            ];
+            let owners = [(b.pos, c.pos), (c.pos, a.pos), (a.pos, b.pos)];
+            let inside = weights.iter().zip(owners).all(|(&weight, (from, to))| {
+                weight > 0.0 || (weight == 0.0 && owns_edge(from, to))
+            });
+            if !inside {
+                continue;
+            }
+
+            let [wa, wb, wc] = weights.map(|weight| weight / area);
+            let uv = (a.uv.to_vec2() * wa + b.uv.to_vec2() * wb + c.uv.to_vec2() * wc).to_pos2();
+            let color = interpolate(&[a.color, b.color, c.color], [wa, wb, wc]);
+            let texel = texture.map_or(Color32::WHITE, |texture| sample(texture, uv));
+            let pixel = &mut canvas.pixels[y * width + x];
+            *pixel = blend(multiply(color, texel), *pixel);
+        }
+    }
+}
+
+/// Twice the signed area of `(from, to, point)`; positive when `point` is
+/// clockwise of the edge on screen.
+fn edge(from: Pos2, to: Pos2, point: Pos2) -> f32 {
+    (to.x - from.x) * (point.y - from.y) - (to.y - from.y) * (point.x - from.x)
+}
+
+/// Whether a pixel center exactly on the edge belongs to this triangle: the
+/// top and left edges own their pixels.
+fn owns_edge(from: Pos2, to: Pos2) -> bool {
+    let (dx, dy) = (to.x - from.x, to.y - from.y);
+    dy < 0.0 || (dy == 0.0 && dx > 0.0)
+}
+
+fn interpolate(colors: &[Color32; 3], weights: [f32; 3]) -> Color32 {
+    let channel = |i: usize| {
+        let value: f32 = colors
+            .iter()
+            .zip(weights)
+            .map(|(color, weight)| f32::from(color.to_array()[i]) * weight)
+            .sum();
+        value.round().clamp(0.0, 255.0) as u8
+    };
+    Color32::from_rgba_premultiplied(channel(0), channel(1), channel(2), channel(3))
+}
+
+fn sample(texture: &ColorImage, uv: Pos2) -> Color32 {
+    let [width, height] = texture.size;
+    if width == 0 || height == 0 {
+        return Color32::TRANSPARENT;
+    }
+    let x = ((uv.x * width as f32) as usize).min(width - 1);
+    let y = ((uv.y * height as f32) as usize).min(height - 1);
+    texture.pixels[y * width + x]
+}
+
+fn multiply(a: Color32, b: Color32) -> Color32 {
+    let [a, b] = [a.to_array(), b.to_array()];
+    let channel = |i: usize| ((u16::from(a[i]) * u16::from(b[i]) + 127) / 255) as u8;
+    Color32::from_rgba_premultiplied(channel(0), channel(1), channel(2), channel(3))
+}
+
+/// Premultiplied source-over.
+fn blend(source: Color32, target: Color32) -> Color32 {
+    let [source, target] = [source.to_array(), target.to_array()];
+    let keep = 255 - u16::from(source[3]);
+    let channel = |i: usize| {
+        (u16::from(source[i]) + (u16::from(target[i]) * keep + 127) / 255).min(255) as u8
+    };
+    Color32::from_rgba_premultiplied(channel(0), channel(1), channel(2), channel(3))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SoftwareRenderer;
+    use egui::{Color32, Context, Pos2, Rect, vec2};
+
+    fn pixel(image: &egui::ColorImage, x: usize, y: usize) -> Color32 {
+        image.pixels[y * image.size[0] + x]
+    }
+
+    #[test]
+    fn shapes_are_filled_inside_their_bounds_only() {
+        let mut renderer = SoftwareRenderer::new(Context::default());
+        let image = renderer.render([40, 30], Color32::WHITE, |ctx| {
+            ctx.layer_painter(egui::LayerId::background()).rect_filled(
+                Rect::from_min_size(Pos2::new(10.0, 5.0), vec2(20.0, 10.0)),
+                0.0,
+                Color32::RED,
+            );
+        });
+
+        assert_eq!(image.size, [40, 30]);
+        assert_eq!(pixel(&image, 15, 10), Color32::RED);
+        assert_eq!(pixel(&image, 5, 10), Color32::WHITE);
+        assert_eq!(pixel(&image, 15, 20), Color32::WHITE);
+    }
+
+    #[test]
+    fn a_translucent_rect_covers_each_pixel_once() {
+        let mut renderer = SoftwareRenderer::new(Context::default());
+        let half_black = Color32::from_black_alpha(128);
+        let image = renderer.render([20, 20], Color32::WHITE, |ctx| {
+            let painter = ctx.layer_painter(egui::LayerId::background());
+            painter.rect_filled(
+                Rect::from_min_size(Pos2::new(2.0, 2.0), vec2(16.0, 16.0)),
+                0.0,
+                half_black,
+            );
+        });
+
+        let inside: Vec<Color32> = (4..16)
+            .flat_map(|y| (4..16).map(move |x| (x, y)))
+            .map(|(x, y)| pixel(&image, x, y))
+            .collect();
+        assert!(inside.iter().all(|&color| color == inside[0]));
+        assert_eq!(inside[0].r(), 127);
+    }
+}
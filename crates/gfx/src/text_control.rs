@@ -19,8 +19,9 @@ pub(crate) fn sync_input_scroll_for_caret<S: InputStore + ?Sized>(
     let (pad_l, pad_r, _pad_t, _pad_b) = input_text_padding(style);
     let available_text_w = (input_rect_w - pad_l - pad_r).max(0.0);
 
-    let (caret_px, text_w) = match input_values.get_state(core_id) {
+    let (caret_px, text_w) = match input_values.get_display_state(core_id) {
         Some((value, caret, _sel, _scroll_x, _scroll_y)) => {
+            let value = value.as_ref();
             let caret = clamp_to_char_boundary(value, caret);
             (
                 measurer.measure(&value[..caret], style),
@@ -43,20 +44,22 @@ pub(crate) fn set_input_caret_from_viewport_x<S: InputStore + ?Sized>(
     style: &ComputedStyle,
 ) -> usize {
     let core_id = to_input_id(input_id);
-    let Some((value, _caret, _sel, scroll_x, _scroll_y)) = input_values.get_state(core_id) else {
+    let Some((value, _caret, _sel, scroll_x, _scroll_y)) = input_values.get_display_state(core_id)
+    else {
         input_values.set_caret(core_id, 0, selecting);
         return 0;
     };
 
     let mut boundaries = Vec::new();
-    rebuild_cursor_boundaries(value, &mut boundaries);
+    rebuild_cursor_boundaries(&value, &mut boundaries);
 
     let x_in_viewport = x_in_viewport.max(0.0);
     let x_in_text = x_in_viewport + scroll_x.max(0.0);
 
-    let caret = caret_from_x_with_boundaries(value, &boundaries, x_in_text, |s| {
+    let display_caret = caret_from_x_with_boundaries(&value, &boundaries, x_in_text, |s| {
         measurer.measure(s, style)
     });
+    let caret = input_values.display_offset_to_value(core_id, display_caret);
 
     input_values.set_caret(core_id, caret, selecting);
     caret
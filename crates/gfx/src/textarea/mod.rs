@@ -1,5 +1,6 @@
 mod cache;
 mod caret;
+mod resize;
 mod selection;
 
 use crate::EguiTextMeasurer;
@@ -16,6 +17,7 @@ pub(crate) use caret::{
     TextareaVerticalMoveCtx, textarea_caret_for_x_in_lines, textarea_caret_geometry,
     textarea_line_index_from_y, textarea_move_caret_vertically,
 };
+pub(crate) use resize::{RESIZE_GRIP_SIZE, resize_grip_contains, resized_control_size};
 pub(crate) use selection::{TextareaSelectionPaintParams, paint_textarea_selection};
 
 #[derive(Default, Debug)]
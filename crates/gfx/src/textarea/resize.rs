@@ -0,0 +1,45 @@
+use css::Resize;
+use layout::Rectangle;
+
+/// Side of the square grip in the bottom-right corner of a resizable textarea.
+pub(crate) const RESIZE_GRIP_SIZE: f32 = 12.0;
+
+/// Smallest border-box size the grip can drag a control down to.
+const MIN_RESIZED_SIZE: f32 = 16.0;
+
+/// Whether `point` (layout coordinates) lies on the resize grip of a control
+/// whose border box is `rect`.
+pub(crate) fn resize_grip_contains(rect: Rectangle, point: (f32, f32)) -> bool {
+    let (x, y) = point;
+    let right = rect.x + rect.width;
+    let bottom = rect.y + rect.height;
+    x >= right - RESIZE_GRIP_SIZE && x <= right && y >= bottom - RESIZE_GRIP_SIZE && y <= bottom
+}
+
+/// Size of a control dragged by `delta` from `start_size`, changing only the
+/// axes `resize` allows.
+pub(crate) fn resized_control_size(
+    resize: Resize,
+    start_size: (f32, f32),
+    delta: (f32, f32),
+) -> (f32, f32) {
+    let (width, height) = start_size;
+    let (horizontal, vertical) = match resize {
+        Resize::None => (false, false),
+        Resize::Both => (true, true),
+        Resize::Horizontal => (true, false),
+        Resize::Vertical => (false, true),
+    };
+    (
+        if horizontal {
+            (width + delta.0).max(MIN_RESIZED_SIZE)
+        } else {
+            width
+        },
+        if vertical {
+            (height + delta.1).max(MIN_RESIZED_SIZE)
+        } else {
+            height
+        },
+    )
+}
@@ -1,6 +1,7 @@
 use core_types::BrowserInput;
 use egui::{
-    Align, Button, Context, CornerRadius, Frame, Margin, Stroke, TextEdit, TopBottomPanel, Ui,
+    Align, Area, Button, Context, CornerRadius, Frame, Image, Key, Margin, Modifiers, Order,
+    Stroke, TextEdit, TextureId, TopBottomPanel, Ui, vec2,
 };
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
@@ -8,9 +9,36 @@ pub struct NavigationIntent {
     pub go_back: bool,
     pub go_forward: bool,
     pub refresh: bool,
+    pub stop: bool,
+    pub toggle_bookmark: bool,
+    pub reset_zoom: bool,
     pub navigate_to: Option<String>,
 }
 
+/// A page offered to complete the URL field with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrlSuggestion {
+    pub url: String,
+    /// Page title, or empty if unknown.
+    pub title: String,
+}
+
+/// Per-frame state of the active page that drives toolbar button states.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NavigationState<'a> {
+    pub can_go_back: bool,
+    pub can_go_forward: bool,
+    pub is_loading: bool,
+    pub is_bookmarked: bool,
+    /// Icon of the current page, shown at the start of the URL field.
+    pub favicon: Option<TextureId>,
+    /// Page zoom in percent, or `None` at the default 100%.
+    pub zoom_percent: Option<u32>,
+    /// Completions for the URL field text, best first. Listed under the field
+    /// while it has focus; arrow keys pick one for Enter.
+    pub suggestions: &'a [UrlSuggestion],
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct NavigationWidgetsConfig {
     pub height: f32,
@@ -25,14 +53,13 @@ impl Default for NavigationWidgetsConfig {
 pub fn top_bar(
     ctx: &Context,
     url: &mut String,
-    can_go_back: bool,
-    can_go_forward: bool,
+    state: NavigationState<'_>,
     input: BrowserInput,
 ) -> NavigationIntent {
     let mut intent = NavigationIntent::default();
     TopBottomPanel::top("borrowser_topbar").show(ctx, |ui| {
         ui.horizontal(|ui| {
-            intent = navigation_widgets(ui, url, can_go_back, can_go_forward, input);
+            intent = navigation_widgets(ui, url, state, input);
         });
     });
     intent
@@ -41,25 +68,16 @@ pub fn top_bar(
 pub fn navigation_widgets(
     ui: &mut Ui,
     url: &mut String,
-    can_go_back: bool,
-    can_go_forward: bool,
+    state: NavigationState<'_>,
     input: BrowserInput,
 ) -> NavigationIntent {
-    navigation_widgets_with_config(
-        ui,
-        url,
-        can_go_back,
-        can_go_forward,
-        NavigationWidgetsConfig::default(),
-        input,
-    )
+    navigation_widgets_with_config(ui, url, state, NavigationWidgetsConfig::default(), input)
 }
 
 pub fn navigation_widgets_with_config(
     ui: &mut Ui,
     url: &mut String,
-    can_go_back: bool,
-    can_go_forward: bool,
+    state: NavigationState<'_>,
     config: NavigationWidgetsConfig,
     input: BrowserInput,
 ) -> NavigationIntent {
@@ -67,23 +85,70 @@ pub fn navigation_widgets_with_config(
     let h = config.height.max(1.0);
 
     if ui
-        .add_enabled(can_go_back, Button::new("⬅").min_size([h, h].into()))
+        .add_enabled(state.can_go_back, Button::new("⬅").min_size([h, h].into()))
         .clicked()
     {
         intent.go_back = true;
     }
     if ui
-        .add_enabled(can_go_forward, Button::new("➡").min_size([h, h].into()))
+        .add_enabled(
+            state.can_go_forward,
+            Button::new("➡").min_size([h, h].into()),
+        )
         .clicked()
     {
         intent.go_forward = true;
     }
-    if ui.add(Button::new("🔄").min_size([h, h].into())).clicked() {
+    // Reload turns into Stop while the current page is still loading.
+    if state.is_loading {
+        if ui.add(Button::new("✖").min_size([h, h].into())).clicked() {
+            intent.stop = true;
+        }
+    } else if ui.add(Button::new("🔄").min_size([h, h].into())).clicked() {
         intent.refresh = true;
     }
+    let star = if state.is_bookmarked { "★" } else { "☆" };
+    if ui.add(Button::new(star).min_size([h, h].into())).clicked() {
+        intent.toggle_bookmark = true;
+    }
+    if let Some(percent) = state.zoom_percent
+        && ui
+            .add(Button::new(format!("{percent}%")).min_size([h, h].into()))
+            .on_hover_text("Reset zoom")
+            .clicked()
+    {
+        intent.reset_zoom = true;
+    }
 
     ui.add_space(6.0);
 
+    let field_id = ui.make_persistent_id("url_field");
+    let popup_id = field_id.with("suggestions");
+    let suggestions = state.suggestions;
+    let (mut selected, popup_hovered) = ui.data(|d| {
+        (
+            d.get_temp::<usize>(popup_id)
+                .filter(|&idx| idx < suggestions.len()),
+            d.get_temp::<bool>(popup_id.with("hovered"))
+                .unwrap_or(false),
+        )
+    });
+    // Taken before the field sees them, which would move its cursor instead.
+    if !suggestions.is_empty() && ui.memory(|m| m.has_focus(field_id)) {
+        let (down, up) = ui.input_mut(|i| {
+            (
+                i.consume_key(Modifiers::NONE, Key::ArrowDown),
+                i.consume_key(Modifiers::NONE, Key::ArrowUp),
+            )
+        });
+        if down {
+            selected = Some(selected.map_or(0, |idx| (idx + 1).min(suggestions.len() - 1)));
+        }
+        if up {
+            selected = selected.and_then(|idx| idx.checked_sub(1));
+        }
+    }
+
     let resp = Frame::new()
         .stroke(Stroke::new(
             1.0,
@@ -92,19 +157,70 @@ pub fn navigation_widgets_with_config(
         .corner_radius(CornerRadius::same(6))
         .inner_margin(Margin::symmetric(6, 4))
         .show(ui, |ui| {
-            ui.add_sized(
-                [ui.available_width(), h - 8.0],
-                TextEdit::singleline(url)
-                    .return_key(None)
-                    .hint_text("Enter URL")
-                    .vertical_align(Align::Center),
-            )
+            ui.horizontal(|ui| {
+                if let Some(texture) = state.favicon {
+                    ui.add(Image::new((texture, vec2(16.0, 16.0))));
+                }
+                ui.add_sized(
+                    [ui.available_width(), h - 8.0],
+                    TextEdit::singleline(url)
+                        .id(field_id)
+                        .return_key(None)
+                        .hint_text("Enter URL")
+                        .vertical_align(Align::Center),
+                )
+            })
+            .inner
         })
         .inner;
 
+    if resp.changed() {
+        selected = None;
+    }
     if input.enter_pressed && resp.has_focus() {
-        intent.navigate_to = Some(url.clone());
+        let chosen = selected.and_then(|idx| suggestions.get(idx));
+        intent.navigate_to = Some(chosen.map_or_else(|| url.clone(), |s| s.url.clone()));
     }
 
+    // The list stays up while hovered so a click on it lands even though the
+    // press takes focus away from the field.
+    let mut hovered = false;
+    if !suggestions.is_empty()
+        && intent.navigate_to.is_none()
+        && (resp.has_focus() || popup_hovered)
+    {
+        let area = Area::new(popup_id)
+            .order(Order::Foreground)
+            .fixed_pos(resp.rect.left_bottom() + vec2(0.0, 4.0))
+            .show(ui.ctx(), |ui| {
+                Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.set_width(resp.rect.width());
+                    for (idx, suggestion) in suggestions.iter().enumerate() {
+                        let label = if suggestion.title.is_empty() {
+                            suggestion.url.clone()
+                        } else {
+                            format!("{} — {}", suggestion.title, suggestion.url)
+                        };
+                        let item = ui.add(
+                            Button::selectable(selected == Some(idx), label)
+                                .truncate()
+                                .min_size(vec2(ui.available_width(), 0.0)),
+                        );
+                        if item.clicked() {
+                            intent.navigate_to = Some(suggestion.url.clone());
+                        }
+                    }
+                });
+            });
+        hovered = area.response.contains_pointer();
+    }
+    ui.data_mut(|d| {
+        match selected {
+            Some(idx) => d.insert_temp(popup_id, idx),
+            None => d.remove::<usize>(popup_id),
+        }
+        d.insert_temp(popup_id.with("hovered"), hovered);
+    });
+
     intent
 }
@@ -1,4 +1,5 @@
 use css::ComputedStyle;
+use egui::{Color32, Context, FontId};
 use layout::TextMeasurer;
 
 // Re-export from input_core for use within gfx
@@ -13,6 +14,20 @@ pub(crate) fn input_text_padding(style: &ComputedStyle) -> (f32, f32, f32, f32)
     (pad_l, pad_r, pad_t, pad_b)
 }
 
+/// Width of `text` as painted for page text primitives of this font size.
+pub(crate) fn painted_text_width(ctx: &Context, text: &str, font_size_px: f32) -> f32 {
+    ctx.fonts(|fonts| {
+        fonts
+            .layout_no_wrap(
+                text.to_owned(),
+                FontId::proportional(font_size_px),
+                Color32::PLACEHOLDER,
+            )
+            .rect
+            .width()
+    })
+}
+
 pub(crate) fn truncate_to_fit(
     measurer: &dyn TextMeasurer,
     style: &ComputedStyle,
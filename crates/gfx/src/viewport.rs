@@ -1,28 +1,39 @@
 use crate::EguiTextMeasurer;
+use crate::box_overlay::paint_box_overlay;
+use crate::find::{FindState, find_text_matches};
+use crate::fragment::fragment_target_y;
 use crate::input::{
-    FormControlHandler, FrameInputCtx, InputValueStore, InteractionState, PageAction,
-    route_frame_input,
+    FormControlHandler, FrameInputCtx, InputValueStore, InteractionState, PageAction, clamp_scroll,
+    drag_autoscroll_delta, keyboard_scroll_target, pinch_zoom_factor, route_frame_input,
+    touch_scroll_target, wheel_scroll_target,
 };
 use crate::paint::{
     ImageProvider, PaintArgs, PaintArtifact, PaintPhaseInput, paint_page_with_artifact,
 };
+use crate::selection::selection_rects;
 use crate::text_control::{find_layout_box_by_id, sync_input_scroll_for_caret};
 use crate::textarea::sync_textarea_scroll_for_caret;
-use crate::util::input_text_padding;
+use crate::util::{input_text_padding, painted_text_width};
 use css::StylePhaseOutput;
-use egui::{Color32, Rect, ScrollArea, Sense, Stroke, Ui, Vec2};
+use egui::scroll_area::ScrollSource;
+use egui::{Color32, Painter, Pos2, Rect, ScrollArea, Sense, Stroke, Ui, Vec2};
 use html::internal::Id;
 use input_core::InputValueStore as CoreInputValueStore;
+use layout::replaced::intrinsic::IntrinsicSize;
 use layout::{
-    LayoutPhaseInput, Rectangle, ReplacedElementInfoProvider, ReplacedKind, RetainedLayoutArtifact,
-    RetainedLayoutFallbackReason, RetainedLayoutFrameAction, RetainedLayoutFrameResult,
-    RetainedLayoutKeySeed, layout_document,
+    ImagePresentation, LayoutPhaseInput, Rectangle, ReplacedElementInfoProvider, ReplacedKind,
+    RetainedLayoutArtifact, RetainedLayoutFallbackReason, RetainedLayoutFrameAction,
+    RetainedLayoutFrameResult, RetainedLayoutKeySeed, layout_document,
 };
+use std::borrow::Cow;
 use std::cell::RefCell;
 use std::collections::HashMap;
 
 pub use crate::input::PageAction as ViewportAction;
 
+const FIND_MATCH_FILL: Color32 = Color32::from_rgba_premultiplied(110, 100, 0, 110);
+const FIND_ACTIVE_FILL: Color32 = Color32::from_rgba_premultiplied(140, 70, 0, 140);
+
 #[derive(Clone, Copy, Debug)]
 pub struct ViewportConfig {
     pub scroll_id_salt: &'static str,
@@ -72,6 +83,35 @@ impl<'a, R> ViewportResourceInputs<'a, R> {
     }
 }
 
+/// Layers the sizes the user dragged controls to over the host's
+/// replaced-element info.
+struct ResizedControlInfo<'a> {
+    inner: &'a dyn ReplacedElementInfoProvider,
+    resized_controls: &'a HashMap<Id, (f32, f32)>,
+}
+
+impl ReplacedElementInfoProvider for ResizedControlInfo<'_> {
+    fn resolve_image_source(&self, source: &str) -> Option<String> {
+        self.inner.resolve_image_source(source)
+    }
+
+    fn intrinsic_for_img(&self, image: &ImagePresentation) -> Option<IntrinsicSize> {
+        self.inner.intrinsic_for_img(image)
+    }
+
+    fn resized_control_size(&self, node_id: Id) -> Option<IntrinsicSize> {
+        match self.resized_controls.get(&node_id) {
+            // No aspect ratio: the two axes resize independently.
+            Some(&(width, height)) => Some(IntrinsicSize {
+                width: Some(width),
+                height: Some(height),
+                ratio: None,
+            }),
+            None => self.inner.resized_control_size(node_id),
+        }
+    }
+}
+
 impl<'ui, 'style, R, F> ViewportCtx<'ui, 'style, R, F> {
     pub fn new(
         ui: &'ui mut Ui,
@@ -119,6 +159,10 @@ pub struct ViewportRetainedLayout<'a> {
     pub retained: Option<&'a RetainedLayoutArtifact>,
     pub reuse_allowed: bool,
     pub conservative_dirty_fallback: bool,
+    /// The layout is being computed off the UI thread; until it arrives,
+    /// `retained` is drawn instead of laying out again if it still fits the
+    /// style tree.
+    pub deferred: bool,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -171,6 +215,8 @@ pub struct ViewportFrameOutput {
     pub action: Option<PageAction>,
     pub viewport_changed: bool,
     pub requested_followup_render: bool,
+    /// A control was resized this frame, so the next frame must lay out again.
+    pub requested_followup_relayout: bool,
     pub repaint_scope: ViewportRepaintScope,
     pub retained_layout_result: Option<RetainedLayoutFrameResult>,
     pub retained_paint_result: Option<ViewportPaintArtifactResult>,
@@ -193,267 +239,442 @@ pub fn execute_viewport_frame<R: ImageProvider, F: FormControlHandler<CoreInputV
         retained_paint,
     } = ctx;
     let resources = resource_inputs.image_resources;
-    let replaced_info = resource_inputs.replaced_elements;
-
-    ScrollArea::vertical()
+    let resized_controls = interaction.resized_controls.clone();
+    let replaced_info = &ResizedControlInfo {
+        inner: resource_inputs.replaced_elements,
+        resized_controls: &resized_controls,
+    };
+
+    // The scroll area fills the remaining space, so that is the viewport.
+    let viewport_height = ui.available_height();
+    interaction.last_viewport_height = Some(viewport_height);
+    let touch_target = touch_scroll_target(ui, interaction, ui.available_rect_before_wrap());
+    if let Some(target) = keyboard_scroll_target(ui, interaction, viewport_height)
+        .or_else(|| wheel_scroll_target(ui, interaction, viewport_height))
+    {
+        interaction.scroll_target = Some(target);
+        interaction.touch.stop_coasting();
+    } else if let Some(offset) = touch_target {
+        // Stick to the finger, like a selection drag.
+        interaction.scroll_target = None;
+        interaction.pending_scroll_y = Some(offset);
+    }
+    interaction.pinch_zoom = pinch_zoom_factor(ui, interaction);
+    if let Some(delta) = drag_autoscroll_delta(ui, interaction, ui.available_rect_before_wrap()) {
+        // Follow the drag directly; an animation would lag behind it.
+        interaction.scroll_target = None;
+        interaction.pending_scroll_y = Some(clamp_scroll(
+            interaction,
+            viewport_height,
+            interaction.scroll_y + delta,
+        ));
+        // Keep scrolling while the pointer rests near the edge.
+        ui.ctx().request_repaint();
+    }
+    let pending_scroll_y = interaction.pending_scroll_y;
+    let mut scroll_area = ScrollArea::vertical()
         .id_salt(config.scroll_id_salt)
         .auto_shrink(config.auto_shrink)
-        .show(ui, |ui| {
-            let available_width = ui.available_width();
-            let min_height = ui.available_height().max(config.min_content_height);
-
-            let measurer = EguiTextMeasurer::new(ui.ctx());
-
-            let (layout_output, retained_layout_result) = match retained_layout {
-                Some(retained_layout) => {
-                    let key = retained_layout.key_seed.for_viewport_width(available_width);
-                    let retained_attempt = retained_layout
-                        .reuse_allowed
-                        .then_some(retained_layout.retained)
-                        .flatten()
-                        .filter(|artifact| artifact.key() == key)
-                        .map(|artifact| (artifact, artifact.materialize(style.root())));
-
-                    match retained_attempt {
-                        Some((artifact, Ok(output))) => (
+        .scroll_source(ScrollSource {
+            mouse_wheel: false,
+            // Touch pans are handled above; pointer drags select text.
+            drag: false,
+            ..ScrollSource::ALL
+        });
+    if let Some(offset) = pending_scroll_y {
+        scroll_area = scroll_area.vertical_scroll_offset(offset);
+    }
+
+    let mut fragment_target = None;
+    let scroll_output = scroll_area.show(ui, |ui| {
+        let available_width = ui.available_width();
+        let min_height = ui.available_height().max(config.min_content_height);
+
+        let measurer = EguiTextMeasurer::new(ui.ctx());
+
+        let (layout_output, retained_layout_result) = match retained_layout {
+            Some(retained_layout) => {
+                let key = retained_layout.key_seed.for_viewport_width(available_width);
+                let retained_attempt = retained_layout
+                    .reuse_allowed
+                    .then_some(retained_layout.retained)
+                    .flatten()
+                    .filter(|artifact| artifact.key() == key)
+                    .map(|artifact| (artifact, artifact.materialize(style.root())));
+                // While the current layout is computed elsewhere, an older
+                // artifact that still fits the style tree stands in for it.
+                let deferred_output = retained_attempt
+                    .is_none()
+                    .then_some(retained_layout.retained)
+                    .flatten()
+                    .filter(|_| retained_layout.deferred)
+                    .and_then(|artifact| {
+                        Some((artifact, artifact.materialize(style.root()).ok()?))
+                    });
+
+                match (retained_attempt, deferred_output) {
+                    (Some((artifact, Ok(output))), _) => (
+                        output,
+                        Some(RetainedLayoutFrameResult {
+                            key,
+                            action: RetainedLayoutFrameAction::Reused,
+                            artifact: artifact.clone(),
+                        }),
+                    ),
+                    (Some((_artifact, Err(_))), _) => {
+                        let output = layout_document(LayoutPhaseInput::from_style_output(
+                            style,
+                            available_width,
+                            &measurer,
+                            Some(replaced_info),
+                        ));
+                        let artifact = RetainedLayoutArtifact::from_layout_output(key, &output);
+                        (
                             output,
                             Some(RetainedLayoutFrameResult {
                                 key,
-                                action: RetainedLayoutFrameAction::Reused,
-                                artifact: artifact.clone(),
+                                action: RetainedLayoutFrameAction::ConservativeFallback(
+                                    RetainedLayoutFallbackReason::MaterializationFailed,
+                                ),
+                                artifact,
                             }),
-                        ),
-                        Some((_artifact, Err(_))) => {
-                            let output = layout_document(LayoutPhaseInput::from_style_output(
-                                style,
-                                available_width,
-                                &measurer,
-                                Some(replaced_info),
-                            ));
-                            let artifact = RetainedLayoutArtifact::from_layout_output(key, &output);
-                            (
-                                output,
-                                Some(RetainedLayoutFrameResult {
-                                    key,
-                                    action: RetainedLayoutFrameAction::ConservativeFallback(
-                                        RetainedLayoutFallbackReason::MaterializationFailed,
-                                    ),
-                                    artifact,
-                                }),
+                        )
+                    }
+                    (None, Some((artifact, output))) => (
+                        output,
+                        Some(RetainedLayoutFrameResult {
+                            key,
+                            action: RetainedLayoutFrameAction::Deferred,
+                            artifact: artifact.clone(),
+                        }),
+                    ),
+                    (None, None) => {
+                        let output = layout_document(LayoutPhaseInput::from_style_output(
+                            style,
+                            available_width,
+                            &measurer,
+                            Some(replaced_info),
+                        ));
+                        let artifact = RetainedLayoutArtifact::from_layout_output(key, &output);
+                        let action = if retained_layout.conservative_dirty_fallback {
+                            RetainedLayoutFrameAction::ConservativeFallback(
+                                RetainedLayoutFallbackReason::DirtyLayout,
+                            )
+                        } else if retained_layout.reuse_allowed
+                            && retained_layout.retained.is_none()
+                        {
+                            RetainedLayoutFrameAction::ConservativeFallback(
+                                RetainedLayoutFallbackReason::MissingRetainedArtifact,
                             )
-                        }
-                        None => {
-                            let output = layout_document(LayoutPhaseInput::from_style_output(
-                                style,
-                                available_width,
-                                &measurer,
-                                Some(replaced_info),
-                            ));
-                            let artifact = RetainedLayoutArtifact::from_layout_output(key, &output);
-                            let action = if retained_layout.conservative_dirty_fallback {
-                                RetainedLayoutFrameAction::ConservativeFallback(
-                                    RetainedLayoutFallbackReason::DirtyLayout,
-                                )
-                            } else if retained_layout.reuse_allowed
-                                && retained_layout.retained.is_none()
-                            {
-                                RetainedLayoutFrameAction::ConservativeFallback(
-                                    RetainedLayoutFallbackReason::MissingRetainedArtifact,
-                                )
-                            } else if retained_layout.reuse_allowed {
-                                RetainedLayoutFrameAction::ConservativeFallback(
-                                    RetainedLayoutFallbackReason::KeyMismatch,
-                                )
-                            } else {
-                                RetainedLayoutFrameAction::Recomputed
-                            };
-                            (
-                                output,
-                                Some(RetainedLayoutFrameResult {
-                                    key,
-                                    action,
-                                    artifact,
-                                }),
+                        } else if retained_layout.reuse_allowed {
+                            RetainedLayoutFrameAction::ConservativeFallback(
+                                RetainedLayoutFallbackReason::KeyMismatch,
                             )
-                        }
+                        } else {
+                            RetainedLayoutFrameAction::Recomputed
+                        };
+                        (
+                            output,
+                            Some(RetainedLayoutFrameResult {
+                                key,
+                                action,
+                                artifact,
+                            }),
+                        )
                     }
                 }
+            }
+            None => (
+                layout_document(LayoutPhaseInput::from_style_output(
+                    style,
+                    available_width,
+                    &measurer,
+                    Some(replaced_info),
+                )),
+                None,
+            ),
+        };
+        let layout_root = layout_output.root();
+
+        let content_height = layout_output.content_height().max(min_height);
+
+        let (content_rect, resp) =
+            ui.allocate_exact_size(Vec2::new(available_width, content_height), Sense::hover());
+
+        let painter = ui.painter_at(content_rect);
+        let origin = content_rect.min;
+
+        let viewport_width_changed = interaction
+            .last_viewport_width
+            .map(|w| (w - available_width).abs() > 0.5)
+            .unwrap_or(true);
+        interaction.last_viewport_width = Some(available_width);
+
+        let layout_root_size_changed = interaction
+            .last_layout_root_size
+            .map(|(w, h)| {
+                (w - layout_root.rect.width).abs() > 0.5
+                    || (h - layout_root.rect.height).abs() > 0.5
+            })
+            .unwrap_or(true);
+        interaction.last_layout_root_size = Some((layout_root.rect.width, layout_root.rect.height));
+
+        let layout_changed = viewport_width_changed || layout_root_size_changed;
+        let repaint_scope = repaint_policy.scope_for_frame(viewport_width_changed);
+
+        if layout_changed {
+            interaction.focused_input_rect = None;
+        }
+
+        // Keep the focused text control's scroll stable across frames (e.g. resize)
+        // and ensure the caret remains visible within the control viewport.
+        if let Some(focus_id) = interaction.focused_node_id
+            && let Some(lb) = find_layout_box_by_id(layout_root, focus_id).filter(|lb| {
+                matches!(
+                    lb.replaced,
+                    Some(ReplacedKind::InputText | ReplacedKind::TextArea)
+                )
+            })
+        {
+            let viewport = interaction.focused_input_rect.unwrap_or(lb.rect);
+
+            match lb.replaced {
+                Some(ReplacedKind::InputText) => {
+                    sync_input_scroll_for_caret(
+                        input_values.inner_mut(),
+                        focus_id,
+                        viewport.width.max(1.0),
+                        &measurer,
+                        lb.style,
+                    );
+                }
+                Some(ReplacedKind::TextArea) => {
+                    let (pad_l, pad_r, _pad_t, _pad_b) = input_text_padding(lb.style);
+                    let available_text_w = (viewport.width - pad_l - pad_r).max(0.0);
+                    let lines = interaction.textarea.ensure_layout_cache(
+                        input_values.inner(),
+                        focus_id,
+                        available_text_w,
+                        &measurer,
+                        lb.style,
+                    );
+
+                    sync_textarea_scroll_for_caret(
+                        input_values.inner_mut(),
+                        focus_id,
+                        viewport.height.max(1.0),
+                        lines,
+                        &measurer,
+                        lb.style,
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        let fragment_rects: RefCell<HashMap<Id, Rectangle>> = RefCell::new(HashMap::new());
+
+        // Paint
+        let focused = interaction.focused_node_id;
+        let active = interaction.active;
+        let (paint_artifact, paint_action, selection_bg_fill) = {
+            let selection = ui.visuals().selection;
+            let bg = selection.bg_fill;
+            let selection_bg_fill =
+                Color32::from_rgba_unmultiplied(bg.r(), bg.g(), bg.b(), bg.a().min(96));
+            let selection_stroke =
+                Stroke::new(selection.stroke.width.max(2.0), selection.stroke.color);
+
+            let focused_textarea_lines =
+                focused.and_then(|id| interaction.textarea.focused_lines(id));
+            let viewport_clip = viewport_repaint_clip(repaint_scope, content_rect, ui.clip_rect());
+            let clipped_painter = viewport_clip.map(|clip| painter.with_clip_rect(clip));
+            let paint_painter = clipped_painter.as_ref().unwrap_or(&painter);
+
+            let paint_args = PaintArgs {
+                painter: paint_painter,
+                origin,
+                measurer: &measurer,
+                resources,
+                input_values: &*input_values,
+                focused,
+                focused_textarea_lines,
+                active,
+                selection_bg_fill,
+                selection_stroke,
+                fragment_rects: Some(&fragment_rects),
+            };
+            let paint_phase_input = PaintPhaseInput::new(&layout_output);
+            let retained_reuse = retained_paint.and_then(|retained| {
+                can_reuse_retained_paint(
+                    retained.reuse_allowed,
+                    retained_layout_result.as_ref().map(|result| result.action),
+                )
+                .then_some(retained.retained)
+                .flatten()
+            });
+            let (artifact, paint_action) = match retained_reuse {
+                Some(artifact) => (Cow::Borrowed(artifact), ViewportPaintArtifactAction::Reused),
                 None => (
-                    layout_document(LayoutPhaseInput::from_style_output(
-                        style,
-                        available_width,
+                    Cow::Owned(PaintArtifact::from_phase_input(
+                        paint_phase_input,
                         &measurer,
-                        Some(replaced_info),
                     )),
-                    None,
+                    ViewportPaintArtifactAction::Recomputed,
                 ),
             };
-            let layout_root = layout_output.root();
+            paint_page_with_artifact(paint_phase_input, &artifact, paint_args);
+            let find_scroll =
+                paint_find_highlights(ui, paint_painter, origin, &artifact, &mut interaction.find);
+            if let Some(offset) = find_scroll {
+                interaction.scroll_target =
+                    Some(clamp_scroll(interaction, viewport_height, offset));
+            }
+            (artifact, paint_action, selection_bg_fill)
+        };
 
-            let content_height = layout_output.content_height().max(min_height);
+        let input_result = route_frame_input(FrameInputCtx {
+            ui,
+            resp,
+            content_rect,
+            origin,
+            layout_root,
+            measurer: &measurer,
+            layout_changed,
+            fragment_rects: &fragment_rects,
+            page_text: Some(&paint_artifact),
+            base_url,
+            input_values: input_values.inner_mut(),
+            form_controls,
+            interaction,
+        });
 
-            let (content_rect, resp) =
-                ui.allocate_exact_size(Vec2::new(available_width, content_height), Sense::hover());
+        if let Some(fragment) = interaction.pending_fragment.as_deref() {
+            fragment_target = fragment_target_y(layout_root, fragment, &measurer);
+        }
 
-            let painter = ui.painter_at(content_rect);
-            let origin = content_rect.min;
+        // Drawn after input routing so a drag shows its selection right away.
+        paint_page_selection(
+            ui,
+            &painter,
+            origin,
+            &paint_artifact,
+            interaction,
+            selection_bg_fill,
+        );
+        let retained_paint_result = retained_paint.map(|_| ViewportPaintArtifactResult {
+            action: paint_action,
+            artifact: paint_artifact.into_owned(),
+        });
 
-            let viewport_width_changed = interaction
-                .last_viewport_width
-                .map(|w| (w - available_width).abs() > 0.5)
-                .unwrap_or(true);
-            interaction.last_viewport_width = Some(available_width);
+        if interaction.box_overlay {
+            paint_box_overlay(&painter, origin, layout_root, interaction.hover);
+        }
 
-            let layout_root_size_changed = interaction
-                .last_layout_root_size
-                .map(|(w, h)| {
-                    (w - layout_root.rect.width).abs() > 0.5
-                        || (h - layout_root.rect.height).abs() > 0.5
-                })
-                .unwrap_or(true);
-            interaction.last_layout_root_size =
-                Some((layout_root.rect.width, layout_root.rect.height));
+        ViewportFrameOutput {
+            action: input_result.action,
+            viewport_changed: viewport_width_changed,
+            requested_followup_render: input_result.requested_followup_render,
+            requested_followup_relayout: input_result.requested_followup_relayout,
+            repaint_scope,
+            retained_layout_result,
+            retained_paint_result,
+        }
+    });
+
+    interaction.scroll_y = scroll_output.state.offset.y;
+    if let Some(target) = pending_scroll_y {
+        // Documents stream in, so keep re-applying the restore until the page
+        // is tall enough to reach it, unless the user scrolls first. egui
+        // keeps the applied offset within the content; any other offset is
+        // the user dragging the scrollbar.
+        let user_scrolled = ui.input(|i| i.smooth_scroll_delta.y != 0.0);
+        let max_offset =
+            (scroll_output.content_size.y - scroll_output.inner_rect.height()).max(0.0);
+        let dragged = (interaction.scroll_y - target.min(max_offset)).abs() > 0.5;
+        if user_scrolled || dragged || interaction.scroll_y >= target - 0.5 {
+            interaction.pending_scroll_y = None;
+        }
+    }
+    if interaction.pending_fragment.is_some() {
+        if let Some(target) = fragment_target {
+            // Animate there; the animation ends in a scroll restore, which
+            // keeps applying the target while the document streams in.
+            interaction.pending_fragment = None;
+            interaction.scroll_target = Some(clamp_scroll(interaction, viewport_height, target));
+            ui.ctx().request_repaint();
+        } else if ui.input(|i| i.smooth_scroll_delta.y != 0.0) {
+            interaction.pending_fragment = None;
+        }
+    }
 
-            let layout_changed = viewport_width_changed || layout_root_size_changed;
-            let repaint_scope = repaint_policy.scope_for_frame(viewport_width_changed);
+    scroll_output.inner
+}
 
-            if layout_changed {
-                interaction.focused_input_rect = None;
-            }
+fn paint_find_highlights(
+    ui: &Ui,
+    painter: &Painter,
+    origin: Pos2,
+    artifact: &PaintArtifact,
+    find: &mut FindState,
+) -> Option<f32> {
+    if !find.is_searching() {
+        find.set_match_count(0);
+        return None;
+    }
 
-            // Keep the focused text control's scroll stable across frames (e.g. resize)
-            // and ensure the caret remains visible within the control viewport.
-            if let Some(focus_id) = interaction.focused_node_id
-                && let Some(lb) = find_layout_box_by_id(layout_root, focus_id).filter(|lb| {
-                    matches!(
-                        lb.replaced,
-                        Some(ReplacedKind::InputText | ReplacedKind::TextArea)
-                    )
-                })
-            {
-                let viewport = interaction.focused_input_rect.unwrap_or(lb.rect);
-
-                match lb.replaced {
-                    Some(ReplacedKind::InputText) => {
-                        sync_input_scroll_for_caret(
-                            input_values.inner_mut(),
-                            focus_id,
-                            viewport.width.max(1.0),
-                            &measurer,
-                            lb.style,
-                        );
-                    }
-                    Some(ReplacedKind::TextArea) => {
-                        let (pad_l, pad_r, _pad_t, _pad_b) = input_text_padding(lb.style);
-                        let available_text_w = (viewport.width - pad_l - pad_r).max(0.0);
-                        let lines = interaction.textarea.ensure_layout_cache(
-                            input_values.inner(),
-                            focus_id,
-                            available_text_w,
-                            &measurer,
-                            lb.style,
-                        );
-
-                        sync_textarea_scroll_for_caret(
-                            input_values.inner_mut(),
-                            focus_id,
-                            viewport.height.max(1.0),
-                            lines,
-                            &measurer,
-                            lb.style,
-                        );
-                    }
-                    _ => {}
-                }
-            }
+    let measure = |text: &str, font_size_px: f32| painted_text_width(ui.ctx(), text, font_size_px);
+    let matches = find_text_matches(artifact, find.query(), &measure);
+    find.set_match_count(matches.len());
 
-            let fragment_rects: RefCell<HashMap<Id, Rectangle>> = RefCell::new(HashMap::new());
-
-            // Paint
-            let focused = interaction.focused_node_id;
-            let active = interaction.active;
-            let retained_paint_result = {
-                let selection = ui.visuals().selection;
-                let bg = selection.bg_fill;
-                let selection_bg_fill =
-                    Color32::from_rgba_unmultiplied(bg.r(), bg.g(), bg.b(), bg.a().min(96));
-                let selection_stroke =
-                    Stroke::new(selection.stroke.width.max(2.0), selection.stroke.color);
-
-                let focused_textarea_lines =
-                    focused.and_then(|id| interaction.textarea.focused_lines(id));
-                let viewport_clip =
-                    viewport_repaint_clip(repaint_scope, content_rect, ui.clip_rect());
-                let clipped_painter = viewport_clip.map(|clip| painter.with_clip_rect(clip));
-                let paint_painter = clipped_painter.as_ref().unwrap_or(&painter);
-
-                let paint_args = PaintArgs {
-                    painter: paint_painter,
-                    origin,
-                    measurer: &measurer,
-                    resources,
-                    input_values: &*input_values,
-                    focused,
-                    focused_textarea_lines,
-                    active,
-                    selection_bg_fill,
-                    selection_stroke,
-                    fragment_rects: Some(&fragment_rects),
-                };
-                let paint_phase_input = PaintPhaseInput::new(&layout_output);
-                let retained_reuse = retained_paint.and_then(|retained| {
-                    can_reuse_retained_paint(
-                        retained.reuse_allowed,
-                        retained_layout_result.as_ref().map(|result| result.action),
-                    )
-                    .then_some(retained.retained)
-                    .flatten()
-                });
-                match retained_reuse {
-                    Some(artifact) => {
-                        paint_page_with_artifact(paint_phase_input, artifact, paint_args);
-                        retained_paint.map(|_| ViewportPaintArtifactResult {
-                            action: ViewportPaintArtifactAction::Reused,
-                            artifact: artifact.clone(),
-                        })
-                    }
-                    None => {
-                        let artifact =
-                            PaintArtifact::from_phase_input(paint_phase_input, &measurer);
-                        paint_page_with_artifact(paint_phase_input, &artifact, paint_args);
-                        retained_paint.map(|_| ViewportPaintArtifactResult {
-                            action: ViewportPaintArtifactAction::Recomputed,
-                            artifact,
-                        })
-                    }
-                }
-            };
+    let to_screen = |r: Rectangle| {
+        Rect::from_min_size(
+            Pos2::new(origin.x + r.x, origin.y + r.y),
+            Vec2::new(r.width, r.height),
+        )
+    };
+    let active = find.active();
+    for (idx, found) in matches.iter().enumerate() {
+        let fill = if Some(idx) == active {
+            FIND_ACTIVE_FILL
+        } else {
+            FIND_MATCH_FILL
+        };
+        for rect in &found.rects {
+            painter.rect_filled(to_screen(*rect), 2.0, fill);
+        }
+    }
 
-            let input_result = route_frame_input(FrameInputCtx {
-                ui,
-                resp,
-                content_rect,
-                origin,
-                layout_root,
-                measurer: &measurer,
-                layout_changed,
-                fragment_rects: &fragment_rects,
-                base_url,
-                input_values: input_values.inner_mut(),
-                form_controls,
-                interaction,
-            });
+    // Scroll offset that centers the active match in the viewport.
+    if !find.take_scroll_request() {
+        return None;
+    }
+    let bounds = active.and_then(|idx| matches.get(idx))?.bounds();
+    Some(bounds.y + bounds.height / 2.0 - ui.clip_rect().height() / 2.0)
+}
 
-            ViewportFrameOutput {
-                action: input_result.action,
-                viewport_changed: viewport_width_changed,
-                requested_followup_render: input_result.requested_followup_render,
-                repaint_scope,
-                retained_layout_result,
-                retained_paint_result,
-            }
-        })
-        .inner
+fn paint_page_selection(
+    ui: &Ui,
+    painter: &Painter,
+    origin: Pos2,
+    artifact: &PaintArtifact,
+    interaction: &InteractionState,
+    fill: Color32,
+) {
+    let Some((anchor, focus)) = interaction.page_selection.range() else {
+        return;
+    };
+    let measure = |text: &str, font_size_px: f32| painted_text_width(ui.ctx(), text, font_size_px);
+    for rect in selection_rects(artifact, anchor, focus, &measure) {
+        painter.rect_filled(
+            Rect::from_min_size(
+                Pos2::new(origin.x + rect.x, origin.y + rect.y),
+                Vec2::new(rect.width, rect.height),
+            ),
+            0.0,
+            fill,
+        );
+    }
 }
 
 fn viewport_repaint_clip(
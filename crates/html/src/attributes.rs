@@ -22,11 +22,13 @@ impl AttributeNamespace {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct QualifiedAttributeName {
     kind: QualifiedAttributeNameKind,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 enum QualifiedAttributeNameKind {
     Unqualified { local_name: InternedLocalName },
     Xml { local_name: InternedLocalName },
@@ -102,6 +104,7 @@ impl QualifiedAttributeName {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ParserCreatedAttribute {
     name: QualifiedAttributeName,
     value: String,
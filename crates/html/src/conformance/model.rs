@@ -424,6 +424,21 @@ pub enum ObservedPatchOperation {
         node: PatchNodeLabel,
         attributes: Vec<ObservedDomAttribute>,
     },
+    SetAttribute {
+        node: PatchNodeLabel,
+        attribute: ObservedDomAttribute,
+    },
+    RemoveAttribute {
+        node: PatchNodeLabel,
+        namespace: AttributeNamespace,
+        prefix: Option<String>,
+        local_name: String,
+    },
+    SetSourceSpan {
+        node: PatchNodeLabel,
+        start: usize,
+        end: usize,
+    },
     SetText {
         node: PatchNodeLabel,
         text: String,
@@ -510,6 +525,21 @@ pub fn canonicalize_dom_patch(
             node: label_for(*key),
             attributes: attributes.iter().map(canonicalize_dom_attribute).collect(),
         },
+        DomPatch::SetAttribute { key, attribute } => ObservedPatchOperation::SetAttribute {
+            node: label_for(*key),
+            attribute: canonicalize_dom_attribute(attribute),
+        },
+        DomPatch::RemoveAttribute { key, name } => ObservedPatchOperation::RemoveAttribute {
+            node: label_for(*key),
+            namespace: name.namespace(),
+            prefix: name.prefix().map(str::to_string),
+            local_name: name.local_name().to_string(),
+        },
+        DomPatch::SetSourceSpan { key, span } => ObservedPatchOperation::SetSourceSpan {
+            node: label_for(*key),
+            start: span.start,
+            end: span.end,
+        },
         DomPatch::SetText { key, text } => ObservedPatchOperation::SetText {
             node: label_for(*key),
             text: text.clone(),
@@ -1068,7 +1098,15 @@ mod tests {
             DomPatch::RemoveNode { key: PatchKey(6) },
             DomPatch::SetAttributes {
                 key: PatchKey(3),
-                attributes: vec![xml_lang],
+                attributes: vec![xml_lang.clone()],
+            },
+            DomPatch::SetAttribute {
+                key: PatchKey(3),
+                attribute: xml_lang.clone(),
+            },
+            DomPatch::RemoveAttribute {
+                key: PatchKey(3),
+                name: xml_lang.name().clone(),
             },
             DomPatch::SetText {
                 key: PatchKey(5),
@@ -1086,7 +1124,7 @@ mod tests {
                 canonicalize_dom_patch(patch, |key| PatchNodeLabel(format!("node-{}", key.0)))
             })
             .collect::<Vec<_>>();
-        assert_eq!(observed.len(), 14);
+        assert_eq!(observed.len(), 16);
         assert!(matches!(observed[0], ObservedPatchOperation::Clear));
         assert!(matches!(
             &observed[2],
@@ -1102,8 +1140,20 @@ mod tests {
                 if attributes[0].namespace == AttributeNamespace::Xml
                     && attributes[0].prefix.as_deref() == Some("xml")
         ));
+        assert!(matches!(
+            &observed[12],
+            ObservedPatchOperation::SetAttribute { attribute, .. }
+                if attribute.namespace == AttributeNamespace::Xml && attribute.local_name == "lang"
+        ));
         assert!(matches!(
             &observed[13],
+            ObservedPatchOperation::RemoveAttribute { namespace, prefix, local_name, .. }
+                if *namespace == AttributeNamespace::Xml
+                    && prefix.as_deref() == Some("xml")
+                    && local_name == "lang"
+        ));
+        assert!(matches!(
+            &observed[15],
             ObservedPatchOperation::AppendText { text, .. } if text == " suffix"
         ));
     }
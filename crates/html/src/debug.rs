@@ -13,29 +13,6 @@ pub fn first_styles(style: &[(String, String)]) -> String {
 }
 
 pub fn outline_from_dom(root: &Node, cap: usize) -> Vec<String> {
-    struct IndentGuard {
-        indent: std::ptr::NonNull<String>,
-        prev_len: usize,
-    }
-
-    impl IndentGuard {
-        fn new(indent: &mut String) -> Self {
-            let prev_len = indent.len();
-            Self {
-                indent: std::ptr::NonNull::from(indent),
-                prev_len,
-            }
-        }
-    }
-
-    impl Drop for IndentGuard {
-        fn drop(&mut self) {
-            unsafe {
-                self.indent.as_mut().truncate(self.prev_len);
-            }
-        }
-    }
-
     fn trimmed_nonempty_slice(s: &str) -> Option<&str> {
         let start = match s.char_indices().find(|&(_, ch)| !ch.is_whitespace()) {
             Some((idx, _)) => idx,
@@ -68,6 +45,20 @@ pub fn outline_from_dom(root: &Node, cap: usize) -> Vec<String> {
     const PREVIEW_CHARS: usize = 40;
     const LINE_SLACK: usize = 64;
 
+    fn walk_children(
+        children: &[Node],
+        indent: &mut String,
+        out: &mut Vec<String>,
+        left: &mut usize,
+    ) {
+        let depth = indent.len();
+        indent.push_str(INDENT_STEP);
+        for c in children {
+            walk(c, indent, out, left);
+        }
+        indent.truncate(depth);
+    }
+
     fn walk(node: &Node, indent: &mut String, out: &mut Vec<String>, left: &mut usize) {
         if *left == 0 {
             return;
@@ -87,11 +78,7 @@ pub fn outline_from_dom(root: &Node, cap: usize) -> Vec<String> {
                     line.push_str("#document");
                 }
                 out.push(line);
-                indent.push_str(INDENT_STEP);
-                let _guard = IndentGuard::new(indent);
-                for c in children {
-                    walk(c, indent, out, left);
-                }
+                walk_children(children, indent, out, left);
             }
             Node::DocumentType { name, .. } => {
                 let mut line = String::with_capacity(indent.len() + LINE_SLACK);
@@ -136,11 +123,7 @@ pub fn outline_from_dom(root: &Node, cap: usize) -> Vec<String> {
                     line.push_str(" */");
                 }
                 out.push(line);
-                indent.push_str(INDENT_STEP);
-                let _guard = IndentGuard::new(indent);
-                for c in children {
-                    walk(c, indent, out, left);
-                }
+                walk_children(children, indent, out, left);
             }
             Node::Text { text, .. } => {
                 if let Some(trimmed) = trimmed_nonempty_slice(text) {
@@ -186,3 +169,33 @@ pub fn outline_from_dom(root: &Node, cap: usize) -> Vec<String> {
     walk(root, &mut indent, &mut out, &mut left);
     out
 }
+
+#[cfg(all(test, feature = "html5"))]
+mod tests {
+    use super::outline_from_dom;
+    use crate::{HtmlParseOptions, parse_document};
+
+    #[test]
+    fn outline_restores_indentation_after_each_subtree() {
+        let output = parse_document(
+            "<div id=a><p>one</p></div><span class=b>two</span>",
+            HtmlParseOptions::default(),
+        )
+        .expect("parse should succeed");
+
+        assert_eq!(
+            outline_from_dom(&output.document, usize::MAX),
+            [
+                "#document",
+                "  <html>",
+                "    <head>",
+                "    <body>",
+                "      <div id=\"a\">",
+                "        <p>",
+                "          \"one\"",
+                "      <span class=\"b\">",
+                "        \"two\"",
+            ]
+        );
+    }
+}
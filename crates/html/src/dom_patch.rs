@@ -6,9 +6,16 @@
 //! Notes:
 //! - This is intentionally separate from `types.rs` (internal DOM/tokenizer types).
 //! - The patch model is still evolving, so the enum is `#[non_exhaustive]`.
-//! - Core v0 does not define a dedicated `MoveNode` / `ReparentNode` opcode:
-//!   identity-preserving moves are encoded canonically as `AppendChild` or
-//!   `InsertBefore` that reference an already-created `child`.
+//! - There is deliberately no dedicated `MoveNode` / `ReparentNode` opcode:
+//!   identity-preserving moves (adoption agency, foster parenting, template
+//!   reparenting) are encoded canonically as `AppendChild` or `InsertBefore`
+//!   that reference an already-parented `child`. A second move encoding would
+//!   make equal tree transitions compare unequal as patch streams.
+//! - Attributes change either wholesale (`SetAttributes`) or one expanded
+//!   name at a time (`SetAttribute` / `RemoveAttribute`).
+//! - `SetSourceSpan` is metadata, not tree structure: it only records where
+//!   an element's start tag was in the document text, and parsers emit it only
+//!   when asked to.
 //!
 //! Invariants:
 //! - Patches are applied in order.
@@ -23,16 +30,20 @@
 //! - Attribute vectors are applied exactly as emitted. HTML5 parser-created
 //!   output canonicalizes attributes before emission; appliers must not dedupe
 //!   or reorder attributes downstream.
+//! - Single-attribute updates are order-preserving: `SetAttribute` replaces
+//!   the value in place when the expanded name is present and appends
+//!   otherwise; `RemoveAttribute` keeps the relative order of the rest.
 //! - Operations must not create cycles; a node may have at most one parent.
 //! - Batches are atomic: apply all patches in order or apply none.
 //! - Batch version transitions are monotonic and exactly +1 per non-empty batch.
 
-use crate::attributes::ParserCreatedAttribute;
+use crate::attributes::{ParserCreatedAttribute, QualifiedAttributeName};
 use crate::names::ExpandedElementName;
-use crate::types::{Id, NodeKey};
+use crate::types::{Id, NodeKey, SourceSpan};
 
 /// Opaque patch-layer key for stable node identity within a document.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PatchKey(pub u32);
 
 impl PatchKey {
@@ -82,6 +93,7 @@ impl DomPatchBatch {
 /// Incremental DOM patch operation.
 #[non_exhaustive]
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DomPatch {
     /// Clear all existing nodes for the document before applying subsequent patches.
     ///
@@ -156,6 +168,31 @@ pub enum DomPatch {
         key: PatchKey,
         attributes: Vec<ParserCreatedAttribute>,
     },
+    /// Set one attribute on an element node.
+    ///
+    /// An attribute with the same expanded name (namespace and local name)
+    /// keeps its position and takes the new value; otherwise `attribute` is
+    /// appended after the existing ones. Applying this to a non-element node
+    /// is a deterministic error.
+    SetAttribute {
+        key: PatchKey,
+        attribute: ParserCreatedAttribute,
+    },
+    /// Remove one attribute, matched by expanded name, from an element node.
+    ///
+    /// The remaining attributes keep their relative order. Applying this to
+    /// a non-element node, or naming an attribute the element does not have,
+    /// is a deterministic error.
+    RemoveAttribute {
+        key: PatchKey,
+        name: QualifiedAttributeName,
+    },
+    /// Record the source range of an element's start tag.
+    ///
+    /// Emitted right after the element's `CreateElement` when the parser is
+    /// configured to record source spans. Applying this to a non-element node
+    /// is a deterministic error.
+    SetSourceSpan { key: PatchKey, span: SourceSpan },
     /// Replace the text content of a text node.
     ///
     /// Applying this to a non-text node is a deterministic error.
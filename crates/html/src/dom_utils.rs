@@ -1,9 +1,12 @@
-//! Compatibility facade for DOM traversal, collection, and debug helpers.
-//! Prefer using the dedicated modules (`collect`, `traverse`, `debug`) directly.
+//! Compatibility facade for DOM traversal, collection, query, and debug helpers.
+//! Prefer using the dedicated modules (`collect`, `traverse`, `query`, `debug`) directly.
 
 pub use crate::collect::{
     collect_img_srcs, collect_style_texts, collect_stylesheet_hrefs, collect_visible_text,
     collect_visible_text_string,
 };
 pub use crate::debug::{first_styles, outline_from_dom};
+pub use crate::query::{
+    IdIndex, Selector, SelectorParseError, get_element_by_id, query, query_all,
+};
 pub use crate::traverse::is_non_rendering_element;
@@ -23,6 +23,22 @@ pub struct LinkTag {
     pub href: Option<String>,
 }
 
+impl HeadMetadata {
+    /// `href` of the first `<link>` whose `rel` contains `icon` (this also
+    /// matches the legacy `shortcut icon`).
+    pub fn icon_href(&self) -> Option<&str> {
+        self.links
+            .iter()
+            .filter(|link| link.rel.iter().any(|rel| rel == "icon"))
+            .find_map(|link| {
+                link.href
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|href| !href.is_empty())
+            })
+    }
+}
+
 pub fn extract_head_metadata(dom: &Node) -> HeadMetadata {
     let mut meta = HeadMetadata::default();
 
@@ -221,4 +237,30 @@ mod tests {
         assert!(metadata.meta.is_empty());
         assert!(metadata.links.is_empty());
     }
+
+    #[test]
+    fn icon_href_picks_first_icon_link_with_an_href() {
+        let links = |entries: &[(&[&str], Option<&str>)]| HeadMetadata {
+            links: entries
+                .iter()
+                .map(|(rel, href)| LinkTag {
+                    rel: rel.iter().map(|r| r.to_string()).collect(),
+                    href: href.map(str::to_string),
+                })
+                .collect(),
+            ..HeadMetadata::default()
+        };
+
+        let meta = links(&[
+            (&["stylesheet"], Some("a.css")),
+            (&["icon"], Some("  ")),
+            (&["shortcut", "icon"], Some("/fav.png")),
+            (&["icon"], Some("/other.png")),
+        ]);
+        assert_eq!(meta.icon_href(), Some("/fav.png"));
+        assert_eq!(
+            links(&[(&["apple-touch-icon"], Some("/t.png"))]).icon_href(),
+            None
+        );
+    }
 }
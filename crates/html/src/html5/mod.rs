@@ -38,9 +38,9 @@ pub use tokenizer::{
 };
 pub use tree_builder::{
     DomInvariantError, DomInvariantNode, DomInvariantNodeKind, DomInvariantState, Html5TreeBuilder,
-    PatchInvariantError, PatchSink, SuspendReason, TreeBuilderConfig, TreeBuilderControlFlow,
-    TreeBuilderError, TreeBuilderLimits, TreeBuilderStepResult, VecPatchSink, check_dom_invariants,
-    check_patch_invariants,
+    PatchInvariantError, PatchSink, PendingScript, SuspendReason, TreeBuilderConfig,
+    TreeBuilderControlFlow, TreeBuilderError, TreeBuilderLimits, TreeBuilderStepResult,
+    VecPatchSink, check_dom_invariants, check_patch_invariants,
 };
 #[cfg(feature = "dom-snapshot")]
 pub use tree_builder::{serialize_dom_for_test, serialize_dom_for_test_with_options};
@@ -1,12 +1,12 @@
+use crate::ExpandedElementName;
 use crate::dom_patch::{DomPatch, DomPatchBatch};
 use crate::html5::bridge::PatchEmitterAdapter;
 use crate::html5::shared::{
     ByteStreamDecoder, Counters, DocumentParseContext, Html5SessionError, Input, ParseError,
 };
 use crate::html5::tokenizer::{Html5Tokenizer, TokenizerConfig};
-#[cfg(test)]
 use crate::html5::tree_builder::PatchSink;
-use crate::html5::tree_builder::{Html5TreeBuilder, TreeBuilderConfig};
+use crate::html5::tree_builder::{Html5TreeBuilder, PendingScript, TreeBuilderConfig};
 
 /// Feature-gated runtime entrypoint for the HTML5 parsing path.
 pub struct Html5ParseSession {
@@ -17,6 +17,11 @@ pub struct Html5ParseSession {
     pub(super) builder: Html5TreeBuilder,
     pub(super) patch_emitter: PatchEmitterAdapter,
     pub(super) next_patch_batch_version: u64,
+    /// Tree building suspended at a script end tag and waits for `resume`.
+    pub(super) suspended: bool,
+    /// `finish` was called; it completes once no script holds the parse.
+    pub(super) finish_requested: bool,
+    pub(super) decoder_finished: bool,
 }
 
 // Post-finish draining should converge in a handful of iterations because
@@ -51,7 +56,42 @@ impl Html5ParseSession {
         let tokenizer = Html5Tokenizer::new(tokenizer_config, &mut ctx);
         let builder = Html5TreeBuilder::new(builder_config, &mut ctx)
             .map_err(|_| Html5SessionError::Invariant)?;
-        Ok(Self {
+        Ok(Self::from_parts(ctx, tokenizer, builder))
+    }
+
+    /// Parse `html` as the contents of `context` with the HTML fragment
+    /// parsing algorithm, as setting `innerHTML` does.
+    ///
+    /// The returned patches build a Document with a single `html` root
+    /// element whose children are the parsed fragment; `context` itself is
+    /// not part of them. See [`Html5TreeBuilder::new_fragment`] for the
+    /// limitations of this entry point.
+    pub fn parse_fragment(
+        context: &ExpandedElementName,
+        html: &str,
+    ) -> Result<Vec<DomPatch>, Html5SessionError> {
+        let mut ctx = DocumentParseContext::new();
+        let mut tokenizer = Html5Tokenizer::new(TokenizerConfig::default(), &mut ctx);
+        let mut builder =
+            Html5TreeBuilder::new_fragment(TreeBuilderConfig::default(), &mut ctx, context)
+                .map_err(|_| Html5SessionError::Invariant)?;
+        if let Some(control) = builder.fragment_tokenizer_control() {
+            tokenizer.apply_control(control);
+        }
+        let mut setup = builder.drain_patches();
+        let mut session = Self::from_parts(ctx, tokenizer, builder);
+        session.patch_emitter.push_many(&mut setup);
+        session.push_str(html)?;
+        session.finish()?;
+        Ok(session.take_patches())
+    }
+
+    fn from_parts(
+        ctx: DocumentParseContext,
+        tokenizer: Html5Tokenizer,
+        builder: Html5TreeBuilder,
+    ) -> Self {
+        Self {
             ctx,
             decoder: ByteStreamDecoder::new(),
             input: Input::new(),
@@ -59,7 +99,10 @@ impl Html5ParseSession {
             builder,
             patch_emitter: PatchEmitterAdapter::new(),
             next_patch_batch_version: 0,
-        })
+            suspended: false,
+            finish_requested: false,
+            decoder_finished: false,
+        }
     }
 
     pub fn push_bytes(&mut self, bytes: &[u8]) -> Result<(), Html5SessionError> {
@@ -78,17 +121,55 @@ impl Html5ParseSession {
         Ok(())
     }
 
+    /// Signal end of input. If a script suspends the parse before the end,
+    /// the remaining input is parsed and finished by `resume`.
     pub fn finish(&mut self) -> Result<(), Html5SessionError> {
+        self.finish_requested = true;
+        self.advance_finish()?;
+        self.sync_debug_counters();
+        Ok(())
+    }
+
+    /// Whether tree building is suspended at a script end tag.
+    pub fn is_suspended(&self) -> bool {
+        self.suspended
+    }
+
+    /// Take the script whose end tag suspended tree building, if any.
+    pub fn take_pending_script(&mut self) -> Option<PendingScript> {
+        self.builder.take_pending_script()
+    }
+
+    /// Continue parsing after the script that suspended it has run.
+    pub fn resume(&mut self) -> Result<(), Html5SessionError> {
+        if !self.suspended {
+            return Err(Html5SessionError::Invariant);
+        }
+        self.suspended = false;
+        if self.finish_requested {
+            self.advance_finish()?;
+        } else {
+            self.pump_live_input()?;
+        }
+        self.sync_debug_counters();
+        Ok(())
+    }
+
+    fn advance_finish(&mut self) -> Result<(), Html5SessionError> {
         self.pump_live_input()?;
-        let _ = self.decoder.finish(&mut self.input);
+        if !self.decoder_finished {
+            self.decoder_finished = true;
+            let _ = self.decoder.finish(&mut self.input);
+        }
         self.pump_live_input()?;
+        if self.suspended {
+            return Ok(());
+        }
         let _ = self
             .tokenizer
             .finish_with_context(&self.input, &mut self.ctx);
         self.drain_post_finish_batches(POST_FINISH_DRAIN_BUDGET)?;
-        self.finalize_adapter_invariants()?;
-        self.sync_debug_counters();
-        Ok(())
+        self.finalize_adapter_invariants()
     }
 
     pub fn take_patches(&mut self) -> Vec<DomPatch> {
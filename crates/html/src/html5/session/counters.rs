@@ -34,5 +34,7 @@ impl Html5ParseSession {
         self.ctx.counters.tree_builder_text_appends = self.builder.perf_text_appends();
         self.ctx.counters.tree_builder_text_coalescing_invalidations =
             self.builder.perf_text_coalescing_invalidations();
+        self.ctx.counters.tree_builder_node_limit_hits = self.builder.node_limit_hits();
+        self.ctx.counters.tree_builder_depth_limit_hits = self.builder.depth_limit_hits();
     }
 }
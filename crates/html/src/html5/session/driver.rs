@@ -1,6 +1,6 @@
 use super::api::{DrainMode, DrainOutcome, Html5ParseSession};
 use crate::html5::bridge::PatchEmitterAdapter;
-use crate::html5::shared::{DocumentParseContext, Html5SessionError, Token};
+use crate::html5::shared::{DocumentParseContext, Html5SessionError, TextSpan, Token};
 use crate::html5::tokenizer::{TextResolver, TokenizeResult, TokenizerControl};
 use crate::html5::tree_builder::{Html5TreeBuilder, TreeBuilderControlFlow, TreeBuilderStepResult};
 #[cfg(any(test, feature = "debug-stats"))]
@@ -8,12 +8,13 @@ use log::error;
 
 impl Html5ParseSession {
     pub(super) fn pump_live_input(&mut self) -> Result<(), Html5SessionError> {
-        loop {
+        while !self.suspended {
             self.builder.prepare_tokenizer_pump(&mut self.tokenizer);
             let tokenize_result = self
                 .tokenizer
                 .push_input_until_token(&mut self.input, &mut self.ctx);
             if self.drain_emitted_tokens(DrainMode::TokenGranular)? == DrainOutcome::Suspended {
+                self.suspended = true;
                 break;
             }
             if tokenize_result == TokenizeResult::NeedMoreInput {
@@ -57,6 +58,7 @@ impl Html5ParseSession {
                 &mut self.builder,
                 &mut self.patch_emitter,
                 token,
+                batch.source_spans()[0],
                 &resolver,
             )?
         };
@@ -73,12 +75,13 @@ impl Html5ParseSession {
 
             let resolver = batch.resolver();
             let mut steps = Vec::with_capacity(batch.tokens().len());
-            for token in batch.iter() {
+            for (token, source_span) in batch.iter().zip(batch.source_spans()) {
                 let step = Self::process_token(
                     &mut self.ctx,
                     &mut self.builder,
                     &mut self.patch_emitter,
                     token,
+                    *source_span,
                     &resolver,
                 )?;
                 steps.push(step);
@@ -116,11 +119,18 @@ impl Html5ParseSession {
         builder: &mut Html5TreeBuilder,
         patch_emitter: &mut PatchEmitterAdapter,
         token: &Token,
+        source_span: Option<TextSpan>,
         resolver: &dyn TextResolver,
     ) -> Result<TreeBuilderStepResult, Html5SessionError> {
         ctx.counters.tokens_processed = ctx.counters.tokens_processed.saturating_add(1);
 
-        match builder.push_token(token, &ctx.atoms, resolver, patch_emitter) {
+        match builder.push_token_with_source_span(
+            token,
+            source_span,
+            &ctx.atoms,
+            resolver,
+            patch_emitter,
+        ) {
             Ok(step) => Ok(step),
             Err(err) => {
                 ctx.counters.tree_builder_invariant_errors =
@@ -0,0 +1,102 @@
+use super::super::Html5ParseSession;
+use crate::{AtomTable, ElementNamespace, ExpandedElementName};
+
+fn context(namespace: ElementNamespace, local_name: &str) -> ExpandedElementName {
+    let mut names = AtomTable::new();
+    let atom = match namespace {
+        ElementNamespace::Html => names.intern_ascii_folded(local_name),
+        ElementNamespace::Svg | ElementNamespace::MathMl => names.intern_exact(local_name),
+    }
+    .expect("atom interning");
+    names
+        .expanded_name(namespace, atom)
+        .expect("interned context name")
+}
+
+fn fragment_dom_lines(context: &ExpandedElementName, html: &str) -> Vec<String> {
+    let patches = Html5ParseSession::parse_fragment(context, html).expect("fragment should parse");
+    let dom = crate::test_harness::materialize_patch_batches(&[patches])
+        .expect("fragment patches should materialize into a DOM");
+    crate::html5::serialize_dom_for_test(&dom)
+}
+
+#[test]
+fn fragment_parses_in_the_context_insertion_mode() {
+    assert_eq!(
+        fragment_dom_lines(&context(ElementNamespace::Html, "div"), "<p>a<b>c"),
+        vec![
+            "#dom-snapshot-v2".to_string(),
+            "#document".to_string(),
+            "  element ns=html local=\"html\" attrs=[]".to_string(),
+            "    element ns=html local=\"p\" attrs=[]".to_string(),
+            "      \"a\"".to_string(),
+            "      element ns=html local=\"b\" attrs=[]".to_string(),
+            "        \"c\"".to_string(),
+        ],
+        "a div context parses in body without implying head or body"
+    );
+    assert_eq!(
+        fragment_dom_lines(&context(ElementNamespace::Html, "tr"), "<td>x<td>y"),
+        vec![
+            "#dom-snapshot-v2".to_string(),
+            "#document".to_string(),
+            "  element ns=html local=\"html\" attrs=[]".to_string(),
+            "    element ns=html local=\"td\" attrs=[]".to_string(),
+            "      \"x\"".to_string(),
+            "    element ns=html local=\"td\" attrs=[]".to_string(),
+            "      \"y\"".to_string(),
+        ],
+        "a tr context parses in row"
+    );
+    assert_eq!(
+        fragment_dom_lines(
+            &context(ElementNamespace::Html, "table"),
+            "<tr><td>x</table>z"
+        ),
+        vec![
+            "#dom-snapshot-v2".to_string(),
+            "#document".to_string(),
+            "  element ns=html local=\"html\" attrs=[]".to_string(),
+            "    element ns=html local=\"tbody\" attrs=[]".to_string(),
+            "      element ns=html local=\"tr\" attrs=[]".to_string(),
+            "        element ns=html local=\"td\" attrs=[]".to_string(),
+            "          \"x\"".to_string(),
+            "          \"z\"".to_string(),
+        ],
+        "a table context implies tbody, and </table> cannot close the context"
+    );
+}
+
+#[test]
+fn foreign_fragment_context_creates_foreign_elements_until_breakout() {
+    assert_eq!(
+        fragment_dom_lines(
+            &context(ElementNamespace::Svg, "svg"),
+            "<path/><![CDATA[<a>]]><p>x"
+        ),
+        vec![
+            "#dom-snapshot-v2".to_string(),
+            "#document".to_string(),
+            "  element ns=html local=\"html\" attrs=[]".to_string(),
+            "    element ns=svg local=\"path\" attrs=[]".to_string(),
+            "    \"<a>\"".to_string(),
+            "    element ns=html local=\"p\" attrs=[]".to_string(),
+            "      \"x\"".to_string(),
+        ],
+        "an svg context adjusts the current node, and breakout stops at the fragment root"
+    );
+}
+
+#[test]
+fn text_mode_fragment_context_keeps_markup_literal() {
+    assert_eq!(
+        fragment_dom_lines(&context(ElementNamespace::Html, "title"), "<b>&amp;"),
+        vec![
+            "#dom-snapshot-v2".to_string(),
+            "#document".to_string(),
+            "  element ns=html local=\"html\" attrs=[]".to_string(),
+            "    \"<b>&\"".to_string(),
+        ],
+        "a title context starts the tokenizer in RCDATA"
+    );
+}
@@ -1,5 +1,7 @@
 mod aaa;
 mod formatting;
+#[cfg(feature = "dom-snapshot")]
+mod fragment;
 mod reconstruction;
 mod smoke;
 mod support;
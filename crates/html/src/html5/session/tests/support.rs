@@ -52,6 +52,9 @@ pub(super) fn create_count_by_key(patches: &[DomPatch]) -> BTreeMap<PatchKey, us
             | DomPatch::InsertBefore { .. }
             | DomPatch::RemoveNode { .. }
             | DomPatch::SetAttributes { .. }
+            | DomPatch::SetAttribute { .. }
+            | DomPatch::RemoveAttribute { .. }
+            | DomPatch::SetSourceSpan { .. }
             | DomPatch::SetText { .. }
             | DomPatch::AppendText { .. } => continue,
         };
@@ -402,3 +402,49 @@ fn session_style_rawtext_malformed_end_tag_does_not_get_stuck_and_builds_expecte
         ]
     );
 }
+
+#[cfg(feature = "dom-snapshot")]
+#[test]
+fn session_xmp_closes_open_p_and_keeps_markup_literal() {
+    let mut ctx = DocumentParseContext::new();
+    let xmp = ctx
+        .atoms
+        .intern_ascii_folded("xmp")
+        .expect("atom interning");
+    let mut session = Html5ParseSession::new(
+        TokenizerConfig::default(),
+        TreeBuilderConfig::default(),
+        ctx,
+    )
+    .expect("session init");
+
+    session.push_str_for_test("<body><p>a<xmp><b>&amp;</");
+    session.pump().expect("xmp prelude should pump");
+    assert_eq!(
+        session.tokenizer_active_text_mode_for_test(),
+        Some(TextModeSpec::rawtext_xmp(xmp)),
+        "xmp start tag must enter RAWTEXT mode"
+    );
+
+    session.push_str_for_test("div></xmp><iframe></p></iframe>");
+    session.pump().expect("xmp close and iframe should pump");
+    assert_eq!(session.tokenizer_active_text_mode_for_test(), None);
+
+    let lines = finish_session_to_dom_lines(&mut session);
+    assert_eq!(
+        lines,
+        vec![
+            "#dom-snapshot-v2".to_string(),
+            "#document".to_string(),
+            "  element ns=html local=\"html\" attrs=[]".to_string(),
+            "    element ns=html local=\"head\" attrs=[]".to_string(),
+            "    element ns=html local=\"body\" attrs=[]".to_string(),
+            "      element ns=html local=\"p\" attrs=[]".to_string(),
+            "        \"a\"".to_string(),
+            "      element ns=html local=\"xmp\" attrs=[]".to_string(),
+            "        \"<b>&amp;</div>\"".to_string(),
+            "      element ns=html local=\"iframe\" attrs=[]".to_string(),
+            "        \"</p>\"".to_string(),
+        ]
+    );
+}
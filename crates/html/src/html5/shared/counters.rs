@@ -40,4 +40,8 @@ pub struct Counters {
     pub tree_builder_text_appends: u64,
     /// Number of times text coalescing state was invalidated by structural edits.
     pub tree_builder_text_coalescing_invalidations: u64,
+    /// Nodes the tree builder dropped because the node limit was reached.
+    pub tree_builder_node_limit_hits: u64,
+    /// Elements the tree builder dropped because the depth limit was reached.
+    pub tree_builder_depth_limit_hits: u64,
 }
@@ -101,9 +101,8 @@ impl Default for Input {
 /// - Incomplete trailing UTF-8 prefixes are retained in `carry` until more
 ///   bytes arrive or `finish()` is called.
 /// - `finish()` flushes any incomplete trailing prefix as `U+FFFD`.
-/// - This is not yet the full HTML encoding-sniffing layer: BOM switching,
-///   charset sniffing/locking, and legacy encodings are intentionally
-///   unsupported for now.
+/// - Encoding sniffing and legacy encodings are not handled here: the parse
+///   runtime transcodes documents to UTF-8 before they reach the parser.
 /// - Emitted decoded text is chunk-equivalent: splitting the same byte stream
 ///   at different chunk boundaries yields the same scalar output after `finish()`.
 #[derive(Debug, Default)]
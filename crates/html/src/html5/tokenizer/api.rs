@@ -6,7 +6,7 @@ use super::states::TokenizerState;
 use super::stats::TokenizerStats;
 use super::text_mode::PendingTextModeEndTag;
 use crate::html5::shared::ParseErrorCode;
-use crate::html5::shared::{Attribute, DocumentParseContext, Input, Token};
+use crate::html5::shared::{Attribute, DocumentParseContext, Input, TextSpan, Token};
 use crate::names::ElementNamespace;
 
 /// Centralized tokenizer hardening/resource bounds.
@@ -136,6 +136,9 @@ pub struct Html5Tokenizer {
     pub(in crate::html5::tokenizer) active_text_mode: Option<TextModeSpec>,
     pub(in crate::html5::tokenizer) cursor: usize,
     pub(in crate::html5::tokenizer) tokens: Vec<Token>,
+    /// Source range of each queued token, parallel to `tokens`; only start
+    /// tags carry one.
+    pub(in crate::html5::tokenizer) source_spans: Vec<Option<TextSpan>>,
     pub(in crate::html5::tokenizer) pending_text_mode_end_tag_matcher:
         Option<IncrementalEndTagMatcher>,
     pub(in crate::html5::tokenizer) pending_text_mode_end_tag: Option<PendingTextModeEndTag>,
@@ -182,6 +185,7 @@ impl Html5Tokenizer {
             active_text_mode: None,
             cursor: 0,
             tokens: Vec::new(),
+            source_spans: Vec::new(),
             pending_text_mode_end_tag_matcher: None,
             pending_text_mode_end_tag: None,
             pending_text_start: None,
@@ -562,7 +566,12 @@ impl Html5Tokenizer {
             "next_batch input must match the tokenizer-bound Input instance"
         );
         let tokens = std::mem::take(&mut self.tokens);
-        TokenBatch { tokens, input }
+        let source_spans = std::mem::take(&mut self.source_spans);
+        TokenBatch {
+            tokens,
+            source_spans,
+            input,
+        }
     }
 
     pub fn apply_control(&mut self, control: TokenizerControl) {
@@ -16,6 +16,7 @@ pub trait TextResolver {
 /// exists (the batch holds an exclusive borrow of the decoded `Input`).
 pub struct TokenBatch<'t> {
     pub(in crate::html5::tokenizer) tokens: Vec<Token>,
+    pub(in crate::html5::tokenizer) source_spans: Vec<Option<TextSpan>>,
     pub(in crate::html5::tokenizer) input: &'t mut Input,
 }
 
@@ -28,6 +29,15 @@ impl<'t> TokenBatch<'t> {
         self.tokens.iter()
     }
 
+    /// Source range of each token, parallel to [`tokens`](Self::tokens).
+    ///
+    /// Start tags carry the range from `<` through `>` in the decoded input;
+    /// every other token has `None`. Unlike text spans inside tokens, these
+    /// offsets stay meaningful after the batch is dropped.
+    pub fn source_spans(&self) -> &[Option<TextSpan>] {
+        &self.source_spans
+    }
+
     pub fn into_tokens(self) -> Vec<Token> {
         self.tokens
     }
@@ -25,6 +25,11 @@ pub enum TextModeNamespace {
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum TextModeMatcher {
     Style,
+    Xmp,
+    Iframe,
+    Noembed,
+    Noframes,
+    Noscript,
     Title,
     Textarea,
     Script,
@@ -53,6 +58,36 @@ impl TextModeSpec {
         }
     }
 
+    pub fn rawtext_xmp(end_tag_name: AtomId) -> Self {
+        Self::rawtext(end_tag_name, TextModeMatcher::Xmp)
+    }
+
+    pub fn rawtext_iframe(end_tag_name: AtomId) -> Self {
+        Self::rawtext(end_tag_name, TextModeMatcher::Iframe)
+    }
+
+    pub fn rawtext_noembed(end_tag_name: AtomId) -> Self {
+        Self::rawtext(end_tag_name, TextModeMatcher::Noembed)
+    }
+
+    pub fn rawtext_noframes(end_tag_name: AtomId) -> Self {
+        Self::rawtext(end_tag_name, TextModeMatcher::Noframes)
+    }
+
+    /// Only selected when the tree builder's scripting flag is set.
+    pub fn rawtext_noscript(end_tag_name: AtomId) -> Self {
+        Self::rawtext(end_tag_name, TextModeMatcher::Noscript)
+    }
+
+    fn rawtext(end_tag_name: AtomId, matcher: TextModeMatcher) -> Self {
+        Self {
+            kind: TextModeKind::RawText,
+            end_tag_name,
+            namespace: TextModeNamespace::Html,
+            matcher,
+        }
+    }
+
     pub fn rcdata_title(end_tag_name: AtomId) -> Self {
         Self {
             kind: TextModeKind::Rcdata,
@@ -84,6 +119,11 @@ impl TextModeSpec {
     pub(crate) fn text_mode_end_tag_literal(self) -> &'static [u8] {
         match self.matcher {
             TextModeMatcher::Style => b"style",
+            TextModeMatcher::Xmp => b"xmp",
+            TextModeMatcher::Iframe => b"iframe",
+            TextModeMatcher::Noembed => b"noembed",
+            TextModeMatcher::Noframes => b"noframes",
+            TextModeMatcher::Noscript => b"noscript",
             TextModeMatcher::Title => b"title",
             TextModeMatcher::Textarea => b"textarea",
             TextModeMatcher::Script => b"script",
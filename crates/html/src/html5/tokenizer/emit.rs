@@ -1,13 +1,22 @@
 //! Token emission helpers.
 
-use crate::html5::shared::Token;
+use crate::html5::shared::{TextSpan, Token};
 use crate::html5::tokenizer::Html5Tokenizer;
 
 impl Html5Tokenizer {
     pub(crate) fn emit_token(&mut self, token: Token) {
+        self.emit_token_with_source_span(token, None);
+    }
+
+    pub(crate) fn emit_token_with_source_span(
+        &mut self,
+        token: Token,
+        source_span: Option<TextSpan>,
+    ) {
         #[cfg(any(test, feature = "debug-stats"))]
         log::trace!(target: "html5.tokenizer", "emit token: {token:?}");
         self.tokens.push(token);
+        self.source_spans.push(source_span);
         self.mark_progress();
         self.stats_inc_tokens_emitted();
     }
@@ -61,6 +61,9 @@ pub struct TokenizerFuzzSummary {
     pub tokens_observed: usize,
     pub span_resolve_count: usize,
     pub digest: u64,
+    /// Digest with adjacent text tokens hashed as one run; equal for every
+    /// chunking of the same input.
+    pub coalesced_digest: u64,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -91,6 +94,14 @@ pub enum TokenizerFuzzError {
     },
     DuplicateEof,
     MissingEof,
+    /// Tokenizing the input in seeded chunks produced a different token
+    /// stream than tokenizing it whole.
+    ChunkingDivergence {
+        seed: u64,
+        chunk_count: usize,
+        chunked_digest: u64,
+        whole_digest: u64,
+    },
 }
 
 impl std::fmt::Display for TokenizerFuzzError {
@@ -134,6 +145,15 @@ impl std::fmt::Display for TokenizerFuzzError {
             ),
             Self::DuplicateEof => f.write_str("tokenizer emitted duplicate EOF tokens"),
             Self::MissingEof => f.write_str("tokenizer never emitted EOF"),
+            Self::ChunkingDivergence {
+                seed,
+                chunk_count,
+                chunked_digest,
+                whole_digest,
+            } => write!(
+                f,
+                "tokenizer output depends on chunking: seed=0x{seed:016x} chunks={chunk_count} chunked_digest=0x{chunked_digest:016x} whole_digest=0x{whole_digest:016x}"
+            ),
         }
     }
 }
@@ -1,8 +1,10 @@
 use crate::html5::tokenizer::fuzz::config::{
-    TokenizerFuzzConfig, TokenizerFuzzError, TokenizerFuzzSummary,
+    TokenizerFuzzConfig, TokenizerFuzzError, TokenizerFuzzSummary, TokenizerFuzzTermination,
 };
 
-use super::session::{run_seeded_byte_fuzz_case_impl, run_seeded_controlled_text_mode_fuzz_case};
+use super::session::{
+    Chunking, run_seeded_byte_fuzz_case_impl, run_seeded_controlled_text_mode_fuzz_case,
+};
 use super::text_mode::TargetedTextModeHarnessKind;
 
 /// Run a single deterministic byte-stream fuzz case against the HTML5 tokenizer.
@@ -21,7 +23,36 @@ pub fn run_seeded_byte_fuzz_case(
     bytes: &[u8],
     config: TokenizerFuzzConfig,
 ) -> Result<TokenizerFuzzSummary, TokenizerFuzzError> {
-    run_seeded_byte_fuzz_case_impl(bytes, config)
+    run_seeded_byte_fuzz_case_impl(bytes, config, Chunking::Seeded)
+}
+
+/// Run a byte-stream fuzz case twice, in seeded chunks and as one chunk, and
+/// fail unless both runs emit the same token stream.
+///
+/// Streams are compared by `coalesced_digest`, so text split into several
+/// text tokens at chunk boundaries still matches. Both runs must also pass
+/// every invariant `run_seeded_byte_fuzz_case` checks. Cases that either run
+/// rejects for exceeding a limit are not compared.
+pub fn run_seeded_chunk_equivalence_fuzz_case(
+    bytes: &[u8],
+    config: TokenizerFuzzConfig,
+) -> Result<TokenizerFuzzSummary, TokenizerFuzzError> {
+    let chunked = run_seeded_byte_fuzz_case_impl(bytes, config, Chunking::Seeded)?;
+    let whole = run_seeded_byte_fuzz_case_impl(bytes, config, Chunking::Whole)?;
+    let completed =
+        |summary: &TokenizerFuzzSummary| summary.termination == TokenizerFuzzTermination::Completed;
+    if completed(&chunked)
+        && completed(&whole)
+        && chunked.coalesced_digest != whole.coalesced_digest
+    {
+        return Err(TokenizerFuzzError::ChunkingDivergence {
+            seed: config.seed,
+            chunk_count: chunked.chunk_count,
+            chunked_digest: chunked.coalesced_digest,
+            whole_digest: whole.coalesced_digest,
+        });
+    }
+    Ok(chunked)
 }
 
 /// Run a deterministic byte-stream fuzz case with the tokenizer entered
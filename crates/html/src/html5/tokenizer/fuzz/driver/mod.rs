@@ -5,6 +5,7 @@ mod summary;
 mod text_mode;
 
 pub use self::entry::{
-    run_seeded_byte_fuzz_case, run_seeded_rawtext_fuzz_case, run_seeded_script_data_fuzz_case,
+    run_seeded_byte_fuzz_case, run_seeded_chunk_equivalence_fuzz_case,
+    run_seeded_rawtext_fuzz_case, run_seeded_script_data_fuzz_case,
     run_seeded_textarea_rcdata_fuzz_case, run_seeded_title_rcdata_fuzz_case,
 };
@@ -10,11 +10,21 @@ use super::pump::{finish_and_drain, pump_text_mode_until_blocked, pump_until_blo
 use super::summary::rejected_summary;
 use super::text_mode::{TargetedTextModeHarnessKind, TextModeFuzzController};
 
+/// How a case splits its input into chunks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Chunking {
+    /// Random chunk lengths drawn from the config seed.
+    Seeded,
+    /// The whole input as a single chunk.
+    Whole,
+}
+
 pub(super) fn run_seeded_byte_fuzz_case_impl(
     bytes: &[u8],
     config: TokenizerFuzzConfig,
+    chunking: Chunking,
 ) -> Result<TokenizerFuzzSummary, TokenizerFuzzError> {
-    run_seeded_case(bytes, config, None)
+    run_seeded_case(bytes, config, None, chunking)
 }
 
 pub(super) fn run_seeded_controlled_text_mode_fuzz_case(
@@ -22,13 +32,14 @@ pub(super) fn run_seeded_controlled_text_mode_fuzz_case(
     config: TokenizerFuzzConfig,
     mode: TargetedTextModeHarnessKind,
 ) -> Result<TokenizerFuzzSummary, TokenizerFuzzError> {
-    run_seeded_case(bytes, config, Some(mode))
+    run_seeded_case(bytes, config, Some(mode), Chunking::Seeded)
 }
 
 fn run_seeded_case(
     bytes: &[u8],
     config: TokenizerFuzzConfig,
     mode: Option<TargetedTextModeHarnessKind>,
+    chunking: Chunking,
 ) -> Result<TokenizerFuzzSummary, TokenizerFuzzError> {
     let mut observer = TokenObserver::new(config.max_tokens_observed);
     if bytes.len() > config.max_input_bytes {
@@ -63,7 +74,11 @@ fn run_seeded_case(
     let max_chunk_len = config.max_chunk_len.max(1);
 
     while offset < bytes.len() {
-        let chunk_len = next_chunk_len(bytes.len() - offset, chunk_count, max_chunk_len, &mut rng);
+        let remaining = bytes.len() - offset;
+        let chunk_len = match chunking {
+            Chunking::Seeded => next_chunk_len(remaining, chunk_count, max_chunk_len, &mut rng),
+            Chunking::Whole => remaining,
+        };
         saw_one_byte_chunk |= chunk_len == 1;
         decoder.push_bytes(&bytes[offset..offset + chunk_len], &mut input);
         chunk_count = chunk_count.saturating_add(1);
@@ -182,6 +197,7 @@ fn run_seeded_case(
         tokens_observed: observer.tokens_observed,
         span_resolve_count: observer.span_resolve_count,
         digest: observer.digest,
+        coalesced_digest: observer.coalesced_digest,
     })
 }
 
@@ -28,5 +28,6 @@ pub(super) fn rejected_summary(
         tokens_observed: observer.tokens_observed,
         span_resolve_count: observer.span_resolve_count,
         digest: observer.digest,
+        coalesced_digest: observer.coalesced_digest,
     }
 }
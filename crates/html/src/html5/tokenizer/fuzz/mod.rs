@@ -14,7 +14,8 @@ pub use config::{
     derive_fuzz_seed,
 };
 pub use driver::{
-    run_seeded_byte_fuzz_case, run_seeded_rawtext_fuzz_case, run_seeded_script_data_fuzz_case,
+    run_seeded_byte_fuzz_case, run_seeded_chunk_equivalence_fuzz_case,
+    run_seeded_rawtext_fuzz_case, run_seeded_script_data_fuzz_case,
     run_seeded_textarea_rcdata_fuzz_case, run_seeded_title_rcdata_fuzz_case,
 };
 pub(crate) use observe::{ObserveError, TokenObserver};
@@ -1,6 +1,6 @@
 use super::super::{TextResolveError, TextResolver};
 use super::digest::{mix_atom_name, mix_bytes, mix_u64, token_discriminant};
-use crate::html5::shared::{AtomTable, AttributeValue, TextValue, Token};
+use crate::html5::shared::{AtomId, AtomTable, AttributeValue, TextValue, Token};
 
 pub(crate) struct TokenObserver {
     max_tokens_observed: usize,
@@ -8,6 +8,10 @@ pub(crate) struct TokenObserver {
     pub(crate) tokens_observed: usize,
     pub(crate) span_resolve_count: usize,
     pub(crate) digest: u64,
+    /// Like `digest`, but adjacent text tokens hash as one run, so it does
+    /// not depend on where chunk boundaries split text.
+    pub(crate) coalesced_digest: u64,
+    in_text_run: bool,
 }
 
 impl TokenObserver {
@@ -18,6 +22,8 @@ impl TokenObserver {
             tokens_observed: 0,
             span_resolve_count: 0,
             digest: 0,
+            coalesced_digest: 0,
+            in_text_run: false,
         }
     }
 
@@ -32,6 +38,11 @@ impl TokenObserver {
         }
         self.tokens_observed = self.tokens_observed.saturating_add(1);
         self.digest = mix_u64(self.digest, token_discriminant(token));
+        let continues_text_run = self.in_text_run && matches!(token, Token::Text { .. });
+        self.in_text_run = matches!(token, Token::Text { .. });
+        if !continues_text_run {
+            self.coalesced_digest = mix_u64(self.coalesced_digest, token_discriminant(token));
+        }
         match token {
             Token::Doctype {
                 name,
@@ -40,36 +51,36 @@ impl TokenObserver {
                 force_quirks,
             } => {
                 if let Some(name) = name {
-                    self.digest = mix_atom_name(self.digest, atoms, *name);
+                    self.mix_atom_name(atoms, *name);
                 }
                 if let Some(public_id) = public_id {
-                    self.digest = mix_bytes(self.digest, public_id.as_bytes());
+                    self.mix_bytes(public_id.as_bytes());
                 }
                 if let Some(system_id) = system_id {
-                    self.digest = mix_bytes(self.digest, system_id.as_bytes());
+                    self.mix_bytes(system_id.as_bytes());
                 }
-                self.digest = mix_u64(self.digest, u64::from(*force_quirks));
+                self.mix_u64(u64::from(*force_quirks));
             }
             Token::StartTag {
                 name,
                 attrs,
                 self_closing,
             } => {
-                self.digest = mix_atom_name(self.digest, atoms, *name);
-                self.digest = mix_u64(self.digest, u64::from(*self_closing));
+                self.mix_atom_name(atoms, *name);
+                self.mix_u64(u64::from(*self_closing));
                 for attr in attrs {
-                    self.digest = mix_atom_name(self.digest, atoms, attr.name);
+                    self.mix_atom_name(atoms, attr.name);
                     self.observe_attr_value(&attr.value, resolver)?;
                 }
             }
             Token::EndTag { name } => {
-                self.digest = mix_atom_name(self.digest, atoms, *name);
+                self.mix_atom_name(atoms, *name);
             }
             Token::Comment { text } | Token::Text { text } => {
                 self.observe_text_value(text, resolver)?;
             }
             Token::ProcessingInstruction(processing_instruction) => {
-                self.digest = mix_bytes(self.digest, processing_instruction.target.as_bytes());
+                self.mix_bytes(processing_instruction.target.as_bytes());
                 self.observe_text_value(&processing_instruction.data, resolver)?;
             }
             Token::Eof => {
@@ -82,6 +93,21 @@ impl TokenObserver {
         Ok(())
     }
 
+    fn mix_u64(&mut self, value: u64) {
+        self.digest = mix_u64(self.digest, value);
+        self.coalesced_digest = mix_u64(self.coalesced_digest, value);
+    }
+
+    fn mix_bytes(&mut self, bytes: &[u8]) {
+        self.digest = mix_bytes(self.digest, bytes);
+        self.coalesced_digest = mix_bytes(self.coalesced_digest, bytes);
+    }
+
+    fn mix_atom_name(&mut self, atoms: &AtomTable, id: AtomId) {
+        self.digest = mix_atom_name(self.digest, atoms, id);
+        self.coalesced_digest = mix_atom_name(self.coalesced_digest, atoms, id);
+    }
+
     fn observe_attr_value(
         &mut self,
         value: &AttributeValue,
@@ -93,10 +119,10 @@ impl TokenObserver {
                     .resolve_span(*span)
                     .map_err(ObserveError::InvalidSpan)?;
                 self.span_resolve_count = self.span_resolve_count.saturating_add(1);
-                self.digest = mix_bytes(self.digest, text.as_bytes());
+                self.mix_bytes(text.as_bytes());
             }
             AttributeValue::Owned(text) => {
-                self.digest = mix_bytes(self.digest, text.as_bytes());
+                self.mix_bytes(text.as_bytes());
             }
         }
         Ok(())
@@ -113,13 +139,13 @@ impl TokenObserver {
                     .resolve_span(*span)
                     .map_err(ObserveError::InvalidSpan)?;
                 self.span_resolve_count = self.span_resolve_count.saturating_add(1);
-                self.digest = mix_bytes(self.digest, text.as_bytes());
+                self.mix_bytes(text.as_bytes());
             }
             TextValue::Owned(text) => {
-                self.digest = mix_bytes(self.digest, text.as_bytes());
+                self.mix_bytes(text.as_bytes());
             }
             TextValue::NullNormalized { text, .. } => {
-                self.digest = mix_bytes(self.digest, text.as_bytes());
+                self.mix_bytes(text.as_bytes());
             }
         }
         Ok(())
@@ -1,5 +1,5 @@
 use super::super::config::{TokenizerFuzzConfig, TokenizerFuzzTermination, derive_fuzz_seed};
-use super::super::driver::run_seeded_byte_fuzz_case;
+use super::super::driver::{run_seeded_byte_fuzz_case, run_seeded_chunk_equivalence_fuzz_case};
 use super::super::rng::{HarnessRng, next_chunk_len};
 
 #[test]
@@ -46,6 +46,22 @@ fn seeded_byte_fuzz_harness_is_reproducible() {
     assert!(first.tokens_observed > 0);
 }
 
+#[test]
+fn chunk_equivalence_harness_accepts_text_split_across_chunks() {
+    let bytes = "<p class=\"x\">long caf\u{e9} text &amp; more</p><!-- c -->".as_bytes();
+    for seed in 1..32u64 {
+        let config = TokenizerFuzzConfig {
+            seed,
+            max_chunk_len: 3,
+            ..TokenizerFuzzConfig::default()
+        };
+        let summary = run_seeded_chunk_equivalence_fuzz_case(bytes, config)
+            .unwrap_or_else(|err| panic!("seed {seed}: {err}"));
+        assert_eq!(summary.termination, TokenizerFuzzTermination::Completed);
+        assert!(summary.chunk_count > 1);
+    }
+}
+
 #[test]
 fn seeded_byte_fuzz_harness_handles_invalid_utf8() {
     let bytes = [0xFFu8, b'<', b'a', b'>', 0xC3];
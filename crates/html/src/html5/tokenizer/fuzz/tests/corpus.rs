@@ -1,6 +1,7 @@
 use super::super::config::{TokenizerFuzzConfig, TokenizerFuzzTermination, derive_fuzz_seed};
 use super::super::driver::{
-    run_seeded_byte_fuzz_case, run_seeded_rawtext_fuzz_case, run_seeded_script_data_fuzz_case,
+    run_seeded_byte_fuzz_case, run_seeded_chunk_equivalence_fuzz_case,
+    run_seeded_rawtext_fuzz_case, run_seeded_script_data_fuzz_case,
     run_seeded_textarea_rcdata_fuzz_case, run_seeded_title_rcdata_fuzz_case,
 };
 use std::fs;
@@ -49,6 +50,24 @@ fn replay_committed_html5_tokenizer_corpus_deterministically() {
     }
 }
 
+#[test]
+fn committed_html5_tokenizer_corpus_tokenizes_the_same_in_chunks_and_whole() {
+    for entry in committed_input_entries() {
+        let bytes = fs::read(&entry)
+            .unwrap_or_else(|err| panic!("failed to read corpus entry {}: {err}", entry.display()));
+        let config = TokenizerFuzzConfig {
+            seed: derive_fuzz_seed(&bytes),
+            ..TokenizerFuzzConfig::default()
+        };
+        if let Err(err) = run_seeded_chunk_equivalence_fuzz_case(&bytes, config) {
+            panic!(
+                "committed corpus entry {} should tokenize independently of chunking: {err}",
+                entry.display()
+            );
+        }
+    }
+}
+
 #[test]
 fn replay_single_committed_seed_deterministically() {
     let entry = corpus_entry("invalid-utf8-bytes");
@@ -1,6 +1,6 @@
 use super::super::Html5Tokenizer;
 use super::super::limits::LIMIT_DETAIL_TAG_NAME;
-use crate::html5::shared::{DocumentParseContext, Input, Token};
+use crate::html5::shared::{DocumentParseContext, Input, TextSpan, Token};
 
 impl Html5Tokenizer {
     pub(super) fn emit_current_tag(&mut self, input: &Input, ctx: &mut DocumentParseContext) {
@@ -38,11 +38,17 @@ impl Html5Tokenizer {
             self.current_tag_self_closing = false;
             self.current_end_tag_trailing_error_reported = false;
             self.clear_current_attribute();
-            self.emit_token(Token::StartTag {
-                name,
-                attrs,
-                self_closing,
-            });
+            // `name_start` follows the `<`, and every caller has consumed the
+            // closing `>` before emitting.
+            let source_span = TextSpan::new(name_start.saturating_sub(1), self.cursor);
+            self.emit_token_with_source_span(
+                Token::StartTag {
+                    name,
+                    attrs,
+                    self_closing,
+                },
+                Some(source_span),
+            );
         }
     }
 }
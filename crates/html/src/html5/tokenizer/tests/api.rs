@@ -176,3 +176,34 @@ fn push_input_until_token_stays_single_token_in_script_mode_with_controls() {
         }
     }
 }
+
+#[test]
+fn batches_report_start_tag_source_spans_in_token_order() {
+    let mut ctx = DocumentParseContext::new();
+    let mut tokenizer = Html5Tokenizer::new(TokenizerConfig::default(), &mut ctx);
+    let mut input = Input::new();
+    let source = "a<p class='x'>b</p><br/><!--c-->";
+    input.push_str(source);
+    assert_push_ok(tokenizer.push_input(&mut input, &mut ctx));
+    assert_eq!(tokenizer.finish(&input), TokenizeResult::EmittedEof);
+
+    let batch = tokenizer.next_batch(&mut input);
+    assert_eq!(batch.source_spans().len(), batch.tokens().len());
+    let markup: Vec<Option<&str>> = batch
+        .source_spans()
+        .iter()
+        .map(|span| span.map(|span| &source[span.start..span.end]))
+        .collect();
+    assert_eq!(
+        markup,
+        [
+            None,
+            Some("<p class='x'>"),
+            None,
+            None,
+            Some("<br/>"),
+            None,
+            None
+        ]
+    );
+}
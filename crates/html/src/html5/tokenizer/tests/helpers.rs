@@ -356,6 +356,17 @@ pub(super) fn run_style_rawtext_chunks(chunks: &[&str]) -> (Vec<String>, Tokeniz
     run_text_mode_chunks(chunks, "style", TextModeSpec::rawtext_style)
 }
 
+pub(super) fn run_rawtext_chunks<F>(
+    chunks: &[&str],
+    tag_name: &str,
+    enter_spec: F,
+) -> (Vec<String>, TokenizerStats)
+where
+    F: Fn(crate::html5::shared::AtomId) -> TextModeSpec,
+{
+    run_text_mode_chunks(chunks, tag_name, enter_spec)
+}
+
 pub(super) fn run_style_rawtext_chunks_with_errors(
     chunks: &[&str],
 ) -> (Vec<String>, TokenizerStats, Vec<ParseError>) {
@@ -1,8 +1,9 @@
 use super::helpers::{
     assert_style_rawtext_chunk_invariant, assert_text_mode_split_close_tag_regression,
-    run_style_rawtext_chunks, run_style_rawtext_chunks_with_errors,
+    run_rawtext_chunks, run_style_rawtext_chunks, run_style_rawtext_chunks_with_errors,
 };
-use crate::html5::shared::ParseErrorCode;
+use crate::html5::shared::{AtomId, ParseErrorCode};
+use crate::html5::tokenizer::TextModeSpec;
 
 #[test]
 fn rawtext_style_split_end_tag_is_chunk_invariant_at_every_boundary() {
@@ -246,3 +247,35 @@ fn g8_regression_rawtext_style_slash_bearing_close_tag_splits_at_every_boundary(
         "rawtext-style-slash-bearing-close-tag",
     );
 }
+
+#[test]
+fn rawtext_xmp_iframe_noembed_noframes_keep_markup_literal_at_every_boundary() {
+    type EnterSpec = fn(AtomId) -> TextModeSpec;
+    let cases: [(&str, EnterSpec); 4] = [
+        ("xmp", TextModeSpec::rawtext_xmp),
+        ("iframe", TextModeSpec::rawtext_iframe),
+        ("noembed", TextModeSpec::rawtext_noembed),
+        ("noframes", TextModeSpec::rawtext_noframes),
+    ];
+    for (tag, spec) in cases {
+        let input = format!("<{tag}><b>&amp;</div></{tag}>");
+        let (whole, _) = run_rawtext_chunks(&[&input], tag, spec);
+        assert_eq!(
+            whole,
+            vec![
+                format!("START name={tag} attrs=[] self_closing=false"),
+                "CHAR text=\"<b>&amp;</div>\"".to_string(),
+                format!("END name={tag}"),
+                "EOF".to_string(),
+            ],
+            "{tag} content must stay literal until its own end tag"
+        );
+        for split in 1..input.len() {
+            let (chunked, _) = run_rawtext_chunks(&[&input[..split], &input[split..]], tag, spec);
+            assert_eq!(
+                chunked, whole,
+                "{tag} rawtext must be split-safe at {split}"
+            );
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use super::super::Html5Tokenizer;
 use super::super::control::TextModeKind;
+use super::super::states::TokenizerState;
 use crate::entities::{CharacterReferenceContext, decode_character_references};
 use crate::html5::shared::{DocumentParseContext, Input, TextSpan, TextValue, Token};
 
@@ -118,6 +119,15 @@ impl Html5Tokenizer {
     }
 
     fn character_reference_context_for_current_text(&self) -> Option<CharacterReferenceContext> {
+        // CDATA sections are literal text: `&amp;` inside one stays as is.
+        if matches!(
+            self.state,
+            TokenizerState::CdataSection
+                | TokenizerState::CdataSectionBracket
+                | TokenizerState::CdataSectionEnd
+        ) {
+            return None;
+        }
         match self.active_text_mode.map(|mode| mode.kind) {
             None => Some(CharacterReferenceContext::DataText),
             Some(TextModeKind::Rcdata) => Some(CharacterReferenceContext::RcdataText),
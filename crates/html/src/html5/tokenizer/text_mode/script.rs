@@ -128,10 +128,11 @@ impl Html5Tokenizer {
                 let _ = self.consume_if(input, '-');
                 Step::Progress
             }
+            // `-->` ends the escaped section the nested script opened in.
             Some('>') => {
                 self.ensure_pending_text_start();
                 let _ = self.consume_if(input, '>');
-                self.transition_to(TokenizerState::ScriptDataDoubleEscaped);
+                self.transition_to(TokenizerState::ScriptData);
                 Step::Progress
             }
             Some(_) => {
@@ -253,7 +254,8 @@ impl Html5Tokenizer {
                 MatchResult::Matched => {
                     self.ensure_pending_text_start();
                     let _ = self.consume_ascii_sequence(input, b"<!--");
-                    self.transition_to(TokenizerState::ScriptDataEscaped);
+                    // The opener's own dashes count, so `<!-->` closes at once.
+                    self.transition_to(TokenizerState::ScriptDataEscapedDashDash);
                     Step::Progress
                 }
                 MatchResult::NeedMoreInput => Step::NeedMoreInput,
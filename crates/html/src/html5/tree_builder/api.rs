@@ -1,8 +1,11 @@
 use crate::dom_patch::{DomPatch, PatchKey};
-use crate::html5::shared::{AtomId, AtomTable, DocumentParseContext, EngineInvariantError, Token};
+use crate::html5::shared::{
+    AtomId, AtomTable, DocumentParseContext, EngineInvariantError, TextSpan, Token,
+};
 use crate::html5::tokenizer::{Html5Tokenizer, TextModeSpec, TextResolver, TokenizerControl};
 use crate::html5::tree_builder::document::{DocumentState, PendingDoctype};
 use crate::html5::tree_builder::formatting::ActiveFormattingList;
+use crate::html5::tree_builder::fragment::FragmentContext;
 use crate::html5::tree_builder::invariants::DomInvariantState;
 use crate::html5::tree_builder::known_tags::KnownTagIds;
 use crate::html5::tree_builder::live_tree::LiveTree;
@@ -13,6 +16,7 @@ use crate::html5::tree_builder::table::PendingTableTextState;
 #[cfg(any(test, feature = "html5-fuzzing", feature = "internal-api"))]
 use crate::html5::tree_builder::template_state::TemplateInsertionMode;
 use crate::html5::tree_builder::template_state::TemplateModeStack;
+use crate::types::SourceSpan;
 use std::num::NonZeroU32;
 
 /// Centralized tree-builder hardening/resource bounds.
@@ -66,6 +70,28 @@ pub struct TreeBuilderConfig {
     pub coalesce_text: bool,
     /// Explicit tree-builder hardening/resource bounds.
     pub limits: TreeBuilderLimits,
+    /// Whether the `</script>` end tag of an HTML script element suspends
+    /// tree building with [`SuspendReason::Script`].
+    ///
+    /// The script's text is then available from
+    /// [`Html5TreeBuilder::take_pending_script`]; the caller runs it before
+    /// pushing the next token. Off by default: without an embedder that runs
+    /// scripts, nothing would ever resume the parse.
+    pub suspend_at_script_end: bool,
+    /// Whether to follow each element created from a start tag with
+    /// `DomPatch::SetSourceSpan` when the caller supplies the tag's range
+    /// (see [`Html5TreeBuilder::push_token_with_source_span`]).
+    ///
+    /// Off by default so patch streams stay free of position metadata unless
+    /// an embedder wants to map nodes back to markup.
+    pub record_source_spans: bool,
+    /// The HTML scripting flag.
+    ///
+    /// When set, `<noscript>` contents are raw text, as in a browser that
+    /// runs scripts. Off by default: nothing runs scripts yet, so
+    /// `<noscript>` fallbacks are parsed as markup and rendered, using the
+    /// "in head noscript" insertion mode inside `<head>`.
+    pub scripting: bool,
 }
 
 /// Tree builder step result.
@@ -85,6 +111,16 @@ impl TreeBuilderStepResult {
             tokenizer_control,
         }
     }
+
+    pub(in crate::html5::tree_builder) fn suspend_with(
+        reason: SuspendReason,
+        tokenizer_control: Option<TokenizerControl>,
+    ) -> Self {
+        Self {
+            flow: TreeBuilderControlFlow::Suspend(reason),
+            tokenizer_control,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -143,6 +179,15 @@ impl PendingTextareaInitialLf {
     }
 }
 
+/// A parser-inserted script whose end tag suspended tree building.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PendingScript {
+    /// The `<script>` element.
+    pub element: PatchKey,
+    /// The script's source text, as inserted into the element.
+    pub text: String,
+}
+
 /// HTML5 tree builder.
 ///
 /// Invariants:
@@ -178,6 +223,9 @@ pub struct Html5TreeBuilder {
     pub(in crate::html5::tree_builder) pending_doctype: Option<PendingDoctype>,
     pub(in crate::html5::tree_builder) document_state: DocumentState,
     pub(in crate::html5::tree_builder) non_document_nodes_created: usize,
+    /// Nodes and elements dropped because `limits` ran out.
+    pub(in crate::html5::tree_builder) node_limit_hits: u64,
+    pub(in crate::html5::tree_builder) depth_limit_hits: u64,
     // Do not push structural patches directly to `patches`.
     // Route structural edits through `push_structural_patch` so invariants stay checkable.
     pub(in crate::html5::tree_builder) patches: Vec<DomPatch>,
@@ -225,6 +273,13 @@ pub struct Html5TreeBuilder {
     pub(in crate::html5::tree_builder) foster_parenting_enabled: bool,
     pub(in crate::html5::tree_builder) pending_table_text: Option<PendingTableTextState>,
     pub(in crate::html5::tree_builder) pending_tokenizer_control: Option<TokenizerControl>,
+    /// Text of the open script element, collected while
+    /// `suspend_at_script_end` is set.
+    pub(in crate::html5::tree_builder) script_text: String,
+    pub(in crate::html5::tree_builder) pending_suspend: Option<SuspendReason>,
+    pub(in crate::html5::tree_builder) pending_script: Option<PendingScript>,
+    /// Context element when parsing a fragment; see [`Self::new_fragment`].
+    pub(in crate::html5::tree_builder) fragment_context: Option<FragmentContext>,
     #[cfg(any(test, feature = "dom-snapshot", feature = "internal-api"))]
     pub(in crate::html5::tree_builder) parse_error_kinds: Vec<&'static str>,
 }
@@ -319,6 +374,25 @@ pub struct TreeBuilderStateSnapshot {
 impl Html5TreeBuilder {
     pub(in crate::html5::tree_builder) fn adjusted_current_node(
         &self,
+    ) -> Option<crate::html5::tree_builder::foreign::AdjustedCurrentNode<'_>> {
+        // While only the fragment root is open, the context element stands
+        // in for it.
+        if self.open_elements.len() == 1
+            && let Some(context) = self.fragment_context_name()
+        {
+            return Some(
+                crate::html5::tree_builder::foreign::AdjustedCurrentNode::from_fragment_context(
+                    context,
+                    &[],
+                ),
+            );
+        }
+        self.stack_current_node()
+    }
+
+    /// The current node itself, ignoring any fragment context.
+    pub(in crate::html5::tree_builder) fn stack_current_node(
+        &self,
     ) -> Option<crate::html5::tree_builder::foreign::AdjustedCurrentNode<'_>> {
         let current = self.open_elements.current()?;
         let (expanded_name, attributes) = self.live_tree.element_semantics(current.key())?;
@@ -379,6 +453,8 @@ impl Html5TreeBuilder {
             pending_doctype: None,
             document_state: DocumentState::default(),
             non_document_nodes_created: 0,
+            node_limit_hits: 0,
+            depth_limit_hits: 0,
             patches: Vec::new(),
             last_text_patch: None,
             structural_mutation_depth: 0,
@@ -422,6 +498,10 @@ impl Html5TreeBuilder {
             foster_parenting_enabled: false,
             pending_table_text: None,
             pending_tokenizer_control: None,
+            script_text: String::new(),
+            pending_suspend: None,
+            pending_script: None,
+            fragment_context: None,
             #[cfg(any(test, feature = "dom-snapshot", feature = "internal-api"))]
             parse_error_kinds: Vec::new(),
         })
@@ -467,11 +547,76 @@ impl Html5TreeBuilder {
         text: &dyn TextResolver,
         sink: &mut dyn PatchSink,
     ) -> Result<TreeBuilderStepResult, TreeBuilderError> {
+        self.push_token_with_source_span(token, None, atoms, text, sink)
+    }
+
+    /// [`push_token`](Self::push_token) for a token whose source range is
+    /// known, as reported by `TokenBatch::source_spans`.
+    ///
+    /// With `record_source_spans` enabled, an element created for a start tag
+    /// is followed by `DomPatch::SetSourceSpan`. Elements the token only
+    /// implies (an implicit `<tbody>`, reconstructed formatting elements) get
+    /// no span; neither does a start tag that creates nothing.
+    pub fn push_token_with_source_span(
+        &mut self,
+        token: &Token,
+        source_span: Option<TextSpan>,
+        atoms: &AtomTable,
+        text: &dyn TextResolver,
+        sink: &mut dyn PatchSink,
+    ) -> Result<TreeBuilderStepResult, TreeBuilderError> {
+        let first_patch = self.patches.len();
         let result = self.process_impl(token, atoms, text)?;
+        if self.config.record_source_spans
+            && let Some(span) = source_span
+            && let Token::StartTag { name, .. } = token
+        {
+            self.record_start_tag_source_span(first_patch, *name, span, atoms);
+        }
         sink.push_many(&mut self.patches);
         Ok(result)
     }
 
+    /// The start tag's own element is the last one it creates: implied
+    /// parents and reconstructed formatting elements are inserted first.
+    fn record_start_tag_source_span(
+        &mut self,
+        first_patch: usize,
+        tag_name: AtomId,
+        span: TextSpan,
+        atoms: &AtomTable,
+    ) {
+        let Some(tag_name) = atoms.resolve(tag_name) else {
+            return;
+        };
+        // Foreign elements may have had their name case-adjusted; anything
+        // else with a different name was implied by this tag, not created
+        // from it.
+        let created = self.patches[first_patch..]
+            .iter()
+            .rev()
+            .find_map(|patch| match patch {
+                DomPatch::CreateElement { key, name, .. } => {
+                    Some((*key, name.local_name_str().eq_ignore_ascii_case(tag_name)))
+                }
+                _ => None,
+            });
+        if let Some((key, true)) = created {
+            self.push_patch(DomPatch::SetSourceSpan {
+                key,
+                span: SourceSpan {
+                    start: span.start,
+                    end: span.end,
+                },
+            });
+        }
+    }
+
+    /// Take the script whose end tag suspended tree building, if any.
+    pub fn take_pending_script(&mut self) -> Option<PendingScript> {
+        self.pending_script.take()
+    }
+
     /// Drain patches produced by previous `process()` calls.
     ///
     /// Patch ordering is stable: the returned vector preserves source token order.
@@ -591,6 +736,14 @@ impl Html5TreeBuilder {
         self.perf_text_coalescing_invalidations
     }
 
+    pub(crate) fn node_limit_hits(&self) -> u64 {
+        self.node_limit_hits
+    }
+
+    pub(crate) fn depth_limit_hits(&self) -> u64 {
+        self.depth_limit_hits
+    }
+
     #[cfg(any(test, feature = "debug-stats"))]
     #[allow(
         dead_code,
@@ -709,6 +862,7 @@ impl Html5TreeBuilder {
         _tag: Option<AtomId>,
         _mode: Option<InsertionMode>,
     ) {
+        self.count_resource_limit_hit(kind);
         #[cfg(any(test, feature = "dom-snapshot", feature = "internal-api"))]
         self.parse_error_kinds.push(kind);
         #[cfg(not(any(test, feature = "dom-snapshot", feature = "internal-api")))]
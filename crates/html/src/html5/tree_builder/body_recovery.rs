@@ -60,6 +60,10 @@ impl Html5TreeBuilder {
         self.close_p_if_in_button_scope("in-body-block-start-tag-closes-open-p")
     }
 
+    pub(in crate::html5::tree_builder) fn close_p_before_xmp(&mut self) -> bool {
+        self.close_p_if_in_button_scope("in-body-xmp-start-tag-closes-open-p")
+    }
+
     pub(in crate::html5::tree_builder) fn handle_in_body_p_start_tag(
         &mut self,
         attrs: &[Attribute],
@@ -252,6 +252,7 @@ impl Html5TreeBuilder {
             InsertionMode::BeforeHtml => self.handle_before_html(token, atoms, text),
             InsertionMode::BeforeHead => self.handle_before_head(token, atoms, text),
             InsertionMode::InHead => self.handle_in_head(token, atoms, text),
+            InsertionMode::InHeadNoscript => self.handle_in_head_noscript(token, atoms, text),
             InsertionMode::AfterHead => self.handle_after_head(token, atoms, text),
             InsertionMode::InBody => self.handle_in_body(token, atoms, text),
             InsertionMode::AfterBody => self.handle_after_body(token, atoms, text),
@@ -351,9 +352,11 @@ impl Html5TreeBuilder {
         if matches!(token, Token::Eof) {
             self.audit_html5_template_output_full()?;
         }
-        Ok(TreeBuilderStepResult::continue_with(
-            self.pending_tokenizer_control.take(),
-        ))
+        let tokenizer_control = self.pending_tokenizer_control.take();
+        Ok(match self.pending_suspend.take() {
+            Some(reason) => TreeBuilderStepResult::suspend_with(reason, tokenizer_control),
+            None => TreeBuilderStepResult::continue_with(tokenizer_control),
+        })
     }
 
     fn exact_reprocess_state(&self, mode: InsertionMode) -> ExactReprocessState {
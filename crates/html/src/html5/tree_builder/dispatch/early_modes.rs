@@ -3,7 +3,7 @@ use crate::html5::shared::{AtomTable, Token};
 use crate::html5::tokenizer::TextResolver;
 use crate::html5::tree_builder::modes::InsertionMode;
 use crate::html5::tree_builder::resolve::is_html_whitespace_text;
-use crate::html5::tree_builder::stack::ScopeKind;
+use crate::html5::tree_builder::stack::{OpenElement, ScopeKind};
 use crate::html5::tree_builder::{Html5TreeBuilder, TreeBuilderError};
 
 impl Html5TreeBuilder {
@@ -281,6 +281,7 @@ impl Html5TreeBuilder {
                 attrs,
                 self_closing,
             } if *name == self.known_tags.style
+                || *name == self.known_tags.noframes
                 || *name == self.known_tags.title
                 || *name == self.known_tags.script =>
             {
@@ -294,6 +295,25 @@ impl Html5TreeBuilder {
                 }
                 Ok(DispatchOutcome::Done)
             }
+            Token::StartTag {
+                name,
+                attrs,
+                self_closing,
+            } if *name == self.known_tags.noscript => {
+                #[expect(
+                    deprecated,
+                    reason = "frozen legacy insertion call; removal tracked separately"
+                )]
+                let inserted = self.insert_element(*name, attrs, *self_closing, atoms, text)?;
+                if !self_closing && inserted.is_some() {
+                    if self.config.scripting {
+                        self.enter_text_mode_for_element(*name);
+                    } else {
+                        self.insertion_mode = InsertionMode::InHeadNoscript;
+                    }
+                }
+                Ok(DispatchOutcome::Done)
+            }
             Token::StartTag {
                 name,
                 attrs,
@@ -337,6 +357,85 @@ impl Html5TreeBuilder {
         }
     }
 
+    /// `<noscript>` in `<head>` with scripting disabled: only the elements a
+    /// head may hold stay inside it; anything else closes it.
+    pub(in crate::html5::tree_builder) fn handle_in_head_noscript(
+        &mut self,
+        token: &Token,
+        atoms: &AtomTable,
+        text: &dyn TextResolver,
+    ) -> Result<DispatchOutcome, TreeBuilderError> {
+        match token {
+            Token::Doctype { .. } => {
+                self.record_parse_error(
+                    "in-head-noscript-doctype",
+                    None,
+                    Some(InsertionMode::InHeadNoscript),
+                );
+                Ok(DispatchOutcome::Done)
+            }
+            Token::StartTag { name, .. } if *name == self.known_tags.html => {
+                self.handle_in_body(token, atoms, text)
+            }
+            Token::EndTag { name } if *name == self.known_tags.noscript => {
+                self.pop_head_noscript();
+                Ok(DispatchOutcome::Done)
+            }
+            Token::Comment { .. } | Token::ProcessingInstruction(_) => {
+                self.handle_in_head(token, atoms, text)
+            }
+            Token::Text { text: token_text } if is_html_whitespace_text(token_text, text)? => {
+                self.handle_in_head(token, atoms, text)
+            }
+            Token::StartTag { name, .. }
+                if *name == self.known_tags.link
+                    || *name == self.known_tags.meta
+                    || *name == self.known_tags.noframes
+                    || *name == self.known_tags.style =>
+            {
+                self.handle_in_head(token, atoms, text)
+            }
+            Token::StartTag { name, .. }
+                if *name == self.known_tags.head || *name == self.known_tags.noscript =>
+            {
+                self.record_parse_error(
+                    "in-head-noscript-unexpected-start-tag",
+                    Some(*name),
+                    Some(InsertionMode::InHeadNoscript),
+                );
+                Ok(DispatchOutcome::Done)
+            }
+            Token::EndTag { name } if *name != self.known_tags.br => {
+                self.record_parse_error(
+                    "in-head-noscript-unexpected-end-tag",
+                    Some(*name),
+                    Some(InsertionMode::InHeadNoscript),
+                );
+                Ok(DispatchOutcome::Done)
+            }
+            _ => {
+                self.record_parse_error(
+                    "in-head-noscript-content-reprocessed",
+                    None,
+                    Some(InsertionMode::InHeadNoscript),
+                );
+                self.pop_head_noscript();
+                Ok(DispatchOutcome::Reprocess(InsertionMode::InHead))
+            }
+        }
+    }
+
+    fn pop_head_noscript(&mut self) {
+        debug_assert_eq!(
+            self.open_elements.current().map(OpenElement::name),
+            Some(self.known_tags.noscript)
+        );
+        if self.open_elements.pop().is_some() {
+            self.invalidate_text_coalescing();
+        }
+        self.insertion_mode = InsertionMode::InHead;
+    }
+
     pub(in crate::html5::tree_builder) fn handle_after_head(
         &mut self,
         token: &Token,
@@ -553,7 +553,13 @@ impl Html5TreeBuilder {
                 attrs,
                 self_closing,
             } => {
+                if *name == self.known_tags.xmp {
+                    let _ = self.close_p_before_xmp();
+                }
                 let _ = self.reconstruct_active_formatting_elements(atoms)?;
+                if *name == self.known_tags.xmp || *name == self.known_tags.iframe {
+                    self.document_state.frameset_ok = false;
+                }
                 #[expect(
                     deprecated,
                     reason = "frozen legacy insertion call; removal tracked separately"
@@ -625,6 +631,18 @@ impl Html5TreeBuilder {
             Token::EndTag { name } if *name == self.known_tags.select => {
                 self.handle_in_body_select_end_tag();
             }
+            Token::EndTag { name } if *name == self.known_tags.br => {
+                // `</br>` acts as a `<br>` without attributes; it is also how
+                // a `</br>` breaking out of SVG or MathML keeps its line break.
+                self.record_parse_error(
+                    "in-body-br-end-tag-treated-as-start-tag",
+                    Some(*name),
+                    Some(InsertionMode::InBody),
+                );
+                let _ = self.reconstruct_active_formatting_elements(atoms)?;
+                let _ = self.insert_void_html_element(self.known_tags.br, &[], atoms, text)?;
+                self.document_state.frameset_ok = false;
+            }
             Token::EndTag { name } => {
                 self.handle_in_body_generic_end_tag(*name, atoms)?;
             }
@@ -7,8 +7,8 @@ pub(in crate::html5::tree_builder) enum AdjustedCurrentNodeSource {
     FragmentContext,
 }
 
-/// Semantic adjusted-current-node view. A future fragment context does not
-/// need to be an open-stack entry or have a patch identity.
+/// Semantic adjusted-current-node view. A fragment context is not an
+/// open-stack entry and has no patch identity.
 #[derive(Clone, Copy, Debug)]
 pub(in crate::html5::tree_builder) struct AdjustedCurrentNode<'a> {
     pub(in crate::html5::tree_builder) key: Option<PatchKey>,
@@ -31,10 +31,6 @@ impl<'a> AdjustedCurrentNode<'a> {
         }
     }
 
-    #[allow(
-        dead_code,
-        reason = "fragment parsing is deliberately deferred after AE11"
-    )]
     pub(in crate::html5::tree_builder) fn from_fragment_context(
         expanded_name: &'a ExpandedElementName,
         attributes: &'a [ParserCreatedAttribute],
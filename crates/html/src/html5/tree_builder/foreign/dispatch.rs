@@ -72,7 +72,9 @@ impl Html5TreeBuilder {
     }
 
     fn current_is_breakout_boundary(&self) -> bool {
-        let Some(current) = self.adjusted_current_node() else {
+        // The breakout pops to the current node, not the adjusted one, so a
+        // foreign fragment context never pops the fragment root.
+        let Some(current) = self.stack_current_node() else {
             return true;
         };
         current.expanded_name.namespace() == ElementNamespace::Html
@@ -598,6 +598,63 @@ fn foreign_character_and_cdata_state_is_chunk_invariant() {
     }
 }
 
+#[test]
+fn cdata_sections_are_literal_text() {
+    let whole = run_chunks(&["<svg><![CDATA[a &amp; <b>]]]></svg>"]);
+    let split = run_chunks(&["<svg><![CDATA[a &am", "p; <b>]", "]]></svg>"]);
+    assert_eq!(whole.patches, split.patches);
+    let snapshot = materialized_snapshot(&whole.patches);
+    assert!(
+        snapshot
+            .iter()
+            .any(|line| line.contains("\"a &amp; <b>]\"")),
+        "character references and markup stay literal: {snapshot:#?}"
+    );
+    assert!(
+        !elements(&whole).contains(&(ElementNamespace::Svg, "b".to_string())),
+        "markup inside CDATA is not parsed"
+    );
+
+    let unterminated = materialized_snapshot(&run_chunks(&["<svg><![CDATA[x &lt;]]"]).patches);
+    assert!(
+        unterminated
+            .iter()
+            .any(|line| line.contains("\"x &lt;]]\"")),
+        "EOF keeps the text and brackets read so far: {unterminated:#?}"
+    );
+}
+
+#[test]
+fn br_end_tag_breaks_out_of_foreign_content_as_a_line_break() {
+    let svg = materialized_snapshot(&run_chunks(&["<svg><g></br>x</g></svg>"]).patches);
+    let br = svg
+        .iter()
+        .position(|line| line.contains("ns=html local=\"br\""))
+        .expect("</br> inserts an HTML br");
+    assert!(
+        svg[br].starts_with("      element"),
+        "the br breaks out to the body: {svg:#?}"
+    );
+    assert!(svg[br + 1].ends_with("\"x\""), "{svg:#?}");
+
+    let mathml = run_chunks(&["<math><mi></br>y</mi></math>"]);
+    let elements = elements(&mathml);
+    assert_eq!(
+        elements[elements.len() - 3..],
+        [
+            (ElementNamespace::MathMl, "math".to_string()),
+            (ElementNamespace::MathMl, "mi".to_string()),
+            (ElementNamespace::Html, "br".to_string()),
+        ],
+        "a text integration point keeps the br inside it"
+    );
+    assert!(
+        mathml
+            .errors
+            .contains(&"in-body-br-end-tag-treated-as-start-tag")
+    );
+}
+
 #[test]
 fn foreign_comments_insert_and_doctypes_report_then_ignore() {
     let run = run_chunks(&["<svg><!--kept--><!DOCTYPE html><g></g></svg>"]);
@@ -0,0 +1,123 @@
+use crate::html5::shared::{AtomId, DocumentParseContext, EngineInvariantError, TextSpan};
+use crate::html5::tokenizer::{TextResolveError, TextResolver, TokenizerControl};
+use crate::html5::tree_builder::modes::InsertionMode;
+use crate::html5::tree_builder::{Html5TreeBuilder, TreeBuilderConfig, TreeBuilderError};
+use crate::names::{ElementNamespace, ExpandedElementName};
+
+/// Context element of a fragment parse (the element whose `innerHTML` is
+/// being set).
+///
+/// The context is never part of the parsed tree: it only stands in as the
+/// adjusted current node while the fragment's root `html` element is the
+/// current node, and seeds the insertion mode reset.
+#[derive(Clone, Debug)]
+pub(in crate::html5::tree_builder) struct FragmentContext {
+    pub(in crate::html5::tree_builder) name: ExpandedElementName,
+    /// The context's local name in this document's atom table; `None` for
+    /// SVG and MathML contexts, which never select an HTML insertion mode.
+    pub(in crate::html5::tree_builder) html_name: Option<AtomId>,
+}
+
+/// The fragment root carries no attributes, so no text span is ever resolved
+/// while it is inserted.
+struct NoTextSpans;
+
+impl TextResolver for NoTextSpans {
+    fn resolve_span(&self, span: TextSpan) -> Result<&str, TextResolveError> {
+        Err(TextResolveError::InvalidSpan { span })
+    }
+}
+
+impl Html5TreeBuilder {
+    /// Create a tree builder for the HTML fragment parsing algorithm.
+    ///
+    /// The builder starts with a Document holding a single `html` root
+    /// element; the parsed fragment becomes that root's children. Its
+    /// insertion mode is reset against `context`, so `<td>` in a `tr`
+    /// context opens a cell and `<path>` in an `svg` context is an SVG
+    /// element. The context itself is never inserted.
+    ///
+    /// Apply [`Self::fragment_tokenizer_control`] before the tokenizer
+    /// consumes any input.
+    ///
+    /// Limitations: a `template` context parses as in body, and no form
+    /// element pointer is inherited from the context's ancestors.
+    pub fn new_fragment(
+        config: TreeBuilderConfig,
+        ctx: &mut DocumentParseContext,
+        context: &ExpandedElementName,
+    ) -> Result<Self, TreeBuilderError> {
+        let mut builder = Self::new(config, ctx)?;
+        let html_name = if context.namespace() == ElementNamespace::Html {
+            Some(
+                ctx.atoms
+                    .intern_ascii_folded(context.local_name_str())
+                    .map_err(|_| EngineInvariantError)?,
+            )
+        } else {
+            None
+        };
+        builder.fragment_context = Some(FragmentContext {
+            name: context.clone(),
+            html_name,
+        });
+        let _ = builder.ensure_document_created()?;
+        let _ = builder.insert_normal_html_element(
+            builder.known_tags.html,
+            &[],
+            &ctx.atoms,
+            &NoTextSpans,
+        )?;
+        let _ = builder.reset_supported_insertion_mode_from_soe()?;
+        Ok(builder)
+    }
+
+    /// Tokenizer state a fragment's content starts in: RCDATA, RAWTEXT or
+    /// script data when the context is an HTML text-mode element such as
+    /// `title`, `style` or `script`, otherwise the data state (`None`).
+    ///
+    /// The tree builder never leaves this text mode, since the context
+    /// element is not open; an end tag naming the context is dropped.
+    pub fn fragment_tokenizer_control(&self) -> Option<TokenizerControl> {
+        let name = self.fragment_context.as_ref()?.html_name?;
+        self.text_mode_spec_for_tag(name)
+            .map(TokenizerControl::EnterTextMode)
+    }
+
+    /// Insertion mode selected by the fragment context when the reset
+    /// reaches the fragment root (`last` in the reset algorithm), or `None`
+    /// when the root `html` element itself decides it.
+    pub(in crate::html5::tree_builder) fn fragment_context_insertion_mode(
+        &self,
+    ) -> Option<InsertionMode> {
+        let context = self.fragment_context.as_ref()?;
+        let Some(name) = context.html_name else {
+            return Some(InsertionMode::InBody);
+        };
+        let tags = &self.known_tags;
+        if name == tags.html {
+            return None;
+        }
+        Some(if name == tags.tr {
+            InsertionMode::InRow
+        } else if name == tags.tbody || name == tags.thead || name == tags.tfoot {
+            InsertionMode::InTableBody
+        } else if name == tags.caption {
+            InsertionMode::InCaption
+        } else if name == tags.colgroup {
+            InsertionMode::InColumnGroup
+        } else if name == tags.table {
+            InsertionMode::InTable
+        } else {
+            // `td`, `th` and `head` only select their modes when they are
+            // not the last node, so as a context they parse in body.
+            InsertionMode::InBody
+        })
+    }
+
+    pub(in crate::html5::tree_builder) fn fragment_context_name(
+        &self,
+    ) -> Option<&ExpandedElementName> {
+        self.fragment_context.as_ref().map(|context| &context.name)
+    }
+}
@@ -166,6 +166,27 @@ impl FuzzDigest {
                         self.push_str(attribute.value());
                     }
                 }
+                DomPatch::SetAttribute { key, attribute } => {
+                    self.push_u8(24);
+                    self.push_u32(key.0);
+                    self.push_str(attribute.namespace().snapshot_name());
+                    self.push_opt_str(attribute.prefix());
+                    self.push_str(attribute.local_name());
+                    self.push_str(attribute.value());
+                }
+                DomPatch::RemoveAttribute { key, name } => {
+                    self.push_u8(25);
+                    self.push_u32(key.0);
+                    self.push_str(name.namespace().snapshot_name());
+                    self.push_opt_str(name.prefix());
+                    self.push_str(name.local_name());
+                }
+                DomPatch::SetSourceSpan { key, span } => {
+                    self.push_u8(26);
+                    self.push_u32(key.0);
+                    self.push_usize(span.start);
+                    self.push_usize(span.end);
+                }
                 DomPatch::SetText { key, text } => {
                     self.push_u8(19);
                     self.push_u32(key.0);
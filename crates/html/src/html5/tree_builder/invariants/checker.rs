@@ -264,6 +264,30 @@ pub fn check_patch_invariants(
                     DomInvariantNodeKind::Element,
                 )?;
             }
+            DomPatch::SetAttribute { key, .. } => {
+                staged.apply_kind_checked_patch(
+                    patch_index,
+                    *key,
+                    "SetAttribute",
+                    DomInvariantNodeKind::Element,
+                )?;
+            }
+            DomPatch::RemoveAttribute { key, .. } => {
+                staged.apply_kind_checked_patch(
+                    patch_index,
+                    *key,
+                    "RemoveAttribute",
+                    DomInvariantNodeKind::Element,
+                )?;
+            }
+            DomPatch::SetSourceSpan { key, .. } => {
+                staged.apply_kind_checked_patch(
+                    patch_index,
+                    *key,
+                    "SetSourceSpan",
+                    DomInvariantNodeKind::Element,
+                )?;
+            }
             DomPatch::SetText { key, .. } => {
                 staged.apply_kind_checked_patch(
                     patch_index,
@@ -52,6 +52,11 @@ pub(in crate::html5::tree_builder) struct KnownTagIds {
     pub(in crate::html5::tree_builder) strike: AtomId,
     pub(in crate::html5::tree_builder) strong: AtomId,
     pub(in crate::html5::tree_builder) style: AtomId,
+    pub(in crate::html5::tree_builder) xmp: AtomId,
+    pub(in crate::html5::tree_builder) iframe: AtomId,
+    pub(in crate::html5::tree_builder) noembed: AtomId,
+    pub(in crate::html5::tree_builder) noframes: AtomId,
+    pub(in crate::html5::tree_builder) noscript: AtomId,
     pub(in crate::html5::tree_builder) title: AtomId,
     pub(in crate::html5::tree_builder) tt: AtomId,
     pub(in crate::html5::tree_builder) textarea: AtomId,
@@ -172,6 +177,11 @@ impl KnownTagIds {
             strike: atoms.intern_ascii_folded("strike")?,
             strong: atoms.intern_ascii_folded("strong")?,
             style: atoms.intern_ascii_folded("style")?,
+            xmp: atoms.intern_ascii_folded("xmp")?,
+            iframe: atoms.intern_ascii_folded("iframe")?,
+            noembed: atoms.intern_ascii_folded("noembed")?,
+            noframes: atoms.intern_ascii_folded("noframes")?,
+            noscript: atoms.intern_ascii_folded("noscript")?,
             title: atoms.intern_ascii_folded("title")?,
             tt: atoms.intern_ascii_folded("tt")?,
             textarea: atoms.intern_ascii_folded("textarea")?,
@@ -5,6 +5,7 @@ use crate::html5::tree_builder::Html5TreeBuilder;
 const LIMIT_PARSE_ERROR_SOE_DEPTH: &str = "resource-limit-soe-depth";
 const LIMIT_PARSE_ERROR_NODE_COUNT: &str = "resource-limit-node-count";
 const LIMIT_PARSE_ERROR_CHILDREN_PER_NODE: &str = "resource-limit-children-per-node";
+const LIMIT_PARSE_ERROR_TEMPLATE_MODE_DEPTH: &str = "resource-limit-template-mode-depth";
 
 impl Html5TreeBuilder {
     pub(in crate::html5::tree_builder) fn allow_non_self_closing_element(
@@ -49,6 +50,20 @@ impl Html5TreeBuilder {
         false
     }
 
+    /// Count parse errors that dropped content because a node or depth
+    /// limit ran out, so embedders can tell a truncated tree apart.
+    pub(in crate::html5::tree_builder) fn count_resource_limit_hit(&mut self, kind: &str) {
+        match kind {
+            LIMIT_PARSE_ERROR_NODE_COUNT => {
+                self.node_limit_hits = self.node_limit_hits.saturating_add(1);
+            }
+            LIMIT_PARSE_ERROR_SOE_DEPTH | LIMIT_PARSE_ERROR_TEMPLATE_MODE_DEPTH => {
+                self.depth_limit_hits = self.depth_limit_hits.saturating_add(1);
+            }
+            _ => {}
+        }
+    }
+
     pub(in crate::html5::tree_builder) fn note_node_created(&mut self) {
         self.non_document_nodes_created = self.non_document_nodes_created.saturating_add(1);
     }
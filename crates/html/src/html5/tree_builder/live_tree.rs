@@ -198,6 +198,9 @@ impl LiveTree {
             DomPatch::RemoveNode { key } => self.remove_node(*key),
             DomPatch::Clear
             | DomPatch::SetAttributes { .. }
+            | DomPatch::SetAttribute { .. }
+            | DomPatch::RemoveAttribute { .. }
+            | DomPatch::SetSourceSpan { .. }
             | DomPatch::SetText { .. }
             | DomPatch::AppendText { .. } => {}
         }
@@ -251,6 +254,19 @@ impl LiveTree {
             .any(|child| matches!(self.node(*child).kind, LiveNodeKind::DocumentType))
     }
 
+    pub(in crate::html5::tree_builder) fn has_html_element_child(
+        &self,
+        key: PatchKey,
+        local_name: &str,
+    ) -> bool {
+        self.node(key).children.iter().any(|child| {
+            self.node(*child)
+                .expanded_name
+                .as_ref()
+                .is_some_and(|name| name.is(ElementNamespace::Html, local_name))
+        })
+    }
+
     pub(in crate::html5::tree_builder) fn children_snapshot(&self, key: PatchKey) -> Vec<PatchKey> {
         self.node(key).children.clone()
     }
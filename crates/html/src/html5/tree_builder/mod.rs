@@ -15,6 +15,7 @@ mod dispatch;
 pub(crate) mod document;
 mod foreign;
 mod formatting;
+mod fragment;
 #[cfg(any(test, feature = "html5-fuzzing"))]
 mod fuzz;
 mod html_semantics;
@@ -36,8 +37,8 @@ mod text_mode;
 #[cfg(any(test, feature = "html5-fuzzing"))]
 pub(crate) use api::TreeBuilderProgressWitness;
 pub use api::{
-    Html5TreeBuilder, SuspendReason, TreeBuilderConfig, TreeBuilderControlFlow, TreeBuilderError,
-    TreeBuilderInternalError, TreeBuilderLimits, TreeBuilderStepResult,
+    Html5TreeBuilder, PendingScript, SuspendReason, TreeBuilderConfig, TreeBuilderControlFlow,
+    TreeBuilderError, TreeBuilderInternalError, TreeBuilderLimits, TreeBuilderStepResult,
 };
 #[cfg(any(test, feature = "html5-fuzzing"))]
 pub(crate) use formatting::AfeDiagnosticEntry;
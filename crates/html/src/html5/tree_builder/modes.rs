@@ -12,6 +12,7 @@ pub(crate) enum InsertionMode {
     BeforeHtml,
     BeforeHead,
     InHead,
+    InHeadNoscript,
     AfterHead,
     InBody,
     AfterBody,
@@ -47,6 +48,7 @@ impl InsertionMode {
             Self::InCell => 14,
             Self::InTemplate => 15,
             Self::Text => 16,
+            Self::InHeadNoscript => 17,
         }
     }
 }
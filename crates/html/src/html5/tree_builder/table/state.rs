@@ -155,6 +155,12 @@ impl Html5TreeBuilder {
             self.perf_reset_insertion_mode_scan_steps =
                 self.perf_reset_insertion_mode_scan_steps.saturating_add(1);
             let entry = self.open_elements.get(index).ok_or(EngineInvariantError)?;
+            if index == 0
+                && let Some(mode) = self.fragment_context_insertion_mode()
+            {
+                self.insertion_mode = mode;
+                return Ok(mode);
+            }
             if entry.namespace() != crate::ElementNamespace::Html {
                 continue;
             }
@@ -185,11 +191,18 @@ impl Html5TreeBuilder {
             } else if name == self.known_tags.body {
                 Some(InsertionMode::InBody)
             } else if name == self.known_tags.html {
-                Some(if self.head_element_pointer.is_some() {
-                    InsertionMode::AfterHead
-                } else {
-                    InsertionMode::BeforeHead
-                })
+                // `</body>` pops body, so a template or table closed after it
+                // lands back on html; resume after the body rather than
+                // opening a second one.
+                Some(
+                    if self.live_tree.has_html_element_child(entry.key(), "body") {
+                        InsertionMode::AfterBody
+                    } else if self.head_element_pointer.is_some() {
+                        InsertionMode::AfterHead
+                    } else {
+                        InsertionMode::BeforeHead
+                    },
+                )
             } else {
                 None
             };
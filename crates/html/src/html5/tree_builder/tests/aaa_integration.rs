@@ -71,6 +71,9 @@ fn create_count_by_key(patches: &[DomPatch]) -> BTreeMap<PatchKey, usize> {
             | DomPatch::InsertBefore { .. }
             | DomPatch::RemoveNode { .. }
             | DomPatch::SetAttributes { .. }
+            | DomPatch::SetAttribute { .. }
+            | DomPatch::RemoveAttribute { .. }
+            | DomPatch::SetSourceSpan { .. }
             | DomPatch::SetText { .. }
             | DomPatch::AppendText { .. } => continue,
         };
@@ -635,6 +635,30 @@ fn unmatched_template_end_tag_is_ignored_without_state_corruption() {
     );
 }
 
+#[test]
+fn template_or_table_closed_after_body_does_not_open_a_second_body() {
+    for (input, closed) in [
+        ("<body></body><template>a</template>b", "template"),
+        ("<body></body><table></table>b", "table"),
+    ] {
+        let lines = materialized_dom_lines(&[input]);
+        let bodies = lines
+            .iter()
+            .filter(|line| line.contains("local=\"body\""))
+            .count();
+        assert_eq!(bodies, 1, "{input} must keep a single body: {lines:#?}");
+        let closed_at = lines
+            .iter()
+            .position(|line| line.contains(&format!("local=\"{closed}\"")))
+            .expect("closed element");
+        assert_eq!(
+            lines[closed_at + 1..].last().map(String::as_str),
+            Some("    \"b\""),
+            "{input} resumes after the body"
+        );
+    }
+}
+
 #[test]
 fn template_patch_keys_are_contiguous() {
     let patches = run_tree_builder_chunks(&["<template></template>"]);
@@ -1,6 +1,6 @@
 use crate::html5::shared::{AtomId, AtomTable, Token};
 use crate::html5::tokenizer::{TextModeSpec, TextResolver, TokenizerControl};
-use crate::html5::tree_builder::api::PendingTextareaInitialLf;
+use crate::html5::tree_builder::api::{PendingScript, PendingTextareaInitialLf, SuspendReason};
 use crate::html5::tree_builder::dispatch::DispatchOutcome;
 use crate::html5::tree_builder::modes::InsertionMode;
 use crate::html5::tree_builder::resolve::{resolve_atom, resolve_text_value};
@@ -92,9 +92,20 @@ impl Html5TreeBuilder {
                 self.record_parse_error("doctype-in-text-mode", None, None);
             }
             Token::EndTag { name } => {
+                let element = self.open_elements.current().map(OpenElement::key);
                 let closed = self.active_text_mode_end_tag_name() == Some(*name)
                     && self.close_active_text_mode_element();
                 if closed {
+                    if *name == self.known_tags.script
+                        && self.config.suspend_at_script_end
+                        && let Some(element) = element
+                    {
+                        self.pending_script = Some(PendingScript {
+                            element,
+                            text: std::mem::take(&mut self.script_text),
+                        });
+                        self.pending_suspend = Some(SuspendReason::Script);
+                    }
                     self.exit_text_mode();
                 } else {
                     self.record_parse_error(
@@ -174,6 +185,8 @@ impl Html5TreeBuilder {
             );
             assert!(pending.textarea() != crate::dom_patch::PatchKey::INVALID);
         }
+        // A script closed by EOF is never run.
+        self.script_text.clear();
         self.active_text_mode = None;
         self.insertion_mode = self
             .original_insertion_mode
@@ -192,6 +205,11 @@ impl Html5TreeBuilder {
         text: &dyn TextResolver,
     ) -> Result<(), TreeBuilderError> {
         let resolved = resolve_text_value(token_text, text)?;
+        if self.config.suspend_at_script_end
+            && self.active_text_mode_end_tag_name() == Some(self.known_tags.script)
+        {
+            self.script_text.push_str(&resolved);
+        }
         let Some(pending) = self.pending_textarea_initial_lf else {
             return self.insert_resolved_text(&resolved);
         };
@@ -252,6 +270,11 @@ impl Html5TreeBuilder {
     pub(in crate::html5::tree_builder) fn is_text_mode_container_tag(&self, name: AtomId) -> bool {
         name == self.known_tags.script
             || name == self.known_tags.style
+            || name == self.known_tags.xmp
+            || name == self.known_tags.iframe
+            || name == self.known_tags.noembed
+            || name == self.known_tags.noframes
+            || (name == self.known_tags.noscript && self.config.scripting)
             || name == self.known_tags.title
             || name == self.known_tags.textarea
     }
@@ -262,6 +285,16 @@ impl Html5TreeBuilder {
     ) -> Option<TextModeSpec> {
         if name == self.known_tags.style {
             Some(TextModeSpec::rawtext_style(name))
+        } else if name == self.known_tags.xmp {
+            Some(TextModeSpec::rawtext_xmp(name))
+        } else if name == self.known_tags.iframe {
+            Some(TextModeSpec::rawtext_iframe(name))
+        } else if name == self.known_tags.noembed {
+            Some(TextModeSpec::rawtext_noembed(name))
+        } else if name == self.known_tags.noframes {
+            Some(TextModeSpec::rawtext_noframes(name))
+        } else if name == self.known_tags.noscript && self.config.scripting {
+            Some(TextModeSpec::rawtext_noscript(name))
         } else if name == self.known_tags.title || name == self.known_tags.textarea {
             if name == self.known_tags.title {
                 Some(TextModeSpec::rcdata_title(name))
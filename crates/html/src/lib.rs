@@ -21,6 +21,7 @@ pub mod perf_fixtures;
 mod perf_guards_heavy;
 #[cfg(all(test, feature = "html5"))]
 mod perf_guards_smoke;
+pub mod query;
 #[cfg(all(test, feature = "html5"))]
 mod streaming_parity;
 #[cfg(feature = "html5")]
@@ -39,6 +40,7 @@ mod entities;
 #[cfg(any(test, feature = "test-harness", feature = "html5"))]
 mod patch_validation;
 mod processing_instruction;
+mod serialize;
 mod types;
 
 use memchr::{memchr, memchr2};
@@ -108,10 +110,11 @@ pub use crate::names::{
 #[cfg(feature = "html5")]
 pub use crate::parser::{
     HtmlErrorPolicy, HtmlParseCounters, HtmlParseError, HtmlParseEvent, HtmlParseOptions,
-    HtmlParser, HtmlTokenizerLimits, HtmlTokenizerOptions, HtmlTreeBuilderLimits,
+    HtmlParser, HtmlScript, HtmlTokenizerLimits, HtmlTokenizerOptions, HtmlTreeBuilderLimits,
     HtmlTreeBuilderOptions, ParseOutput, parse_document,
 };
-pub use crate::types::{ElementNode, Node, ProcessingInstructionNode};
+pub use crate::serialize::serialize;
+pub use crate::types::{ElementNode, Node, ProcessingInstructionNode, SourceSpan};
 
 #[cfg(feature = "internal-api")]
 pub mod internal {
@@ -122,7 +125,10 @@ pub mod internal {
         DocumentFragmentNode, Id, NodeId, NodeKey, ParserCreatedFragmentKind,
         ProcessingInstructionNode,
     };
-    use super::{ElementNamespace, ExpandedElementName, Node, ParserCreatedAttribute};
+    use super::{
+        AttributeNamespace, ElementNamespace, ExpandedElementName, Node, ParserCreatedAttribute,
+        QualifiedAttributeName,
+    };
     use std::sync::{Mutex, OnceLock};
 
     fn synthetic_name_interner() -> &'static Mutex<super::AtomTable> {
@@ -167,6 +173,37 @@ pub mod internal {
             .expect("synthetic attribute name missing after interning")
     }
 
+    /// Rebuilds an attribute from its namespace and exact local name, for
+    /// attributes captured outside the parser. An `Xmlns` attribute named
+    /// `xmlns` is the default namespace declaration; any other local name is
+    /// an `xmlns:` prefixed one.
+    #[must_use]
+    pub fn qualified_attribute(
+        namespace: AttributeNamespace,
+        local_name: &str,
+        value: impl Into<String>,
+    ) -> ParserCreatedAttribute {
+        let mut names = synthetic_name_interner()
+            .lock()
+            .expect("synthetic DOM name interner poisoned");
+        let atom = names
+            .intern_exact(local_name)
+            .expect("synthetic DOM attribute-name interner exhausted");
+        let local_name = names
+            .resolve_local_name(atom)
+            .expect("synthetic attribute name missing after interning");
+        let name = match namespace {
+            AttributeNamespace::None => QualifiedAttributeName::unqualified(local_name),
+            AttributeNamespace::Xml => QualifiedAttributeName::xml(local_name),
+            AttributeNamespace::XLink => QualifiedAttributeName::xlink(local_name),
+            AttributeNamespace::Xmlns if local_name.as_str() == "xmlns" => {
+                QualifiedAttributeName::xmlns_default()
+            }
+            AttributeNamespace::Xmlns => QualifiedAttributeName::xmlns_prefixed(local_name),
+        };
+        ParserCreatedAttribute::new(name, value.into())
+    }
+
     #[must_use]
     pub fn node_element_from_parts(
         id: Id,
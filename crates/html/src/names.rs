@@ -6,6 +6,7 @@ use std::sync::Arc;
 use std::sync::atomic::{AtomicU32, Ordering};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ElementNamespace {
     Html,
     Svg,
@@ -50,6 +51,7 @@ impl NameAtomId {
 
 /// Exact interned local name retained by parser-created DOM values.
 #[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InternedLocalName(Arc<str>);
 
 impl InternedLocalName {
@@ -74,6 +76,7 @@ impl AsRef<str> for InternedLocalName {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExpandedElementName {
     namespace: ElementNamespace,
     local_name: InternedLocalName,
@@ -14,4 +14,4 @@ pub use self::options::{
 };
 pub use self::output::ParseOutput;
 pub use self::session::HtmlParser;
-pub use self::types::{HtmlParseCounters, HtmlParseError, HtmlParseEvent};
+pub use self::types::{HtmlParseCounters, HtmlParseError, HtmlParseEvent, HtmlScript};
@@ -144,6 +144,18 @@ impl From<HtmlTreeBuilderLimits> for TreeBuilderLimits {
 pub struct HtmlTreeBuilderOptions {
     pub coalesce_text: bool,
     pub limits: HtmlTreeBuilderLimits,
+    /// Stop at each script end tag until [`HtmlParser::resume`] is called.
+    ///
+    /// [`HtmlParser::resume`]: super::HtmlParser::resume
+    pub suspend_at_script_end: bool,
+    /// Emit `DomPatch::SetSourceSpan` for elements created from start tags,
+    /// so [`ElementNode::source_span`] is populated.
+    ///
+    /// [`ElementNode::source_span`]: crate::ElementNode::source_span
+    pub record_source_spans: bool,
+    /// Parse with the scripting flag set, so `<noscript>` contents are raw
+    /// text instead of markup.
+    pub scripting: bool,
 }
 
 impl Default for HtmlTreeBuilderOptions {
@@ -152,6 +164,9 @@ impl Default for HtmlTreeBuilderOptions {
         Self {
             coalesce_text: config.coalesce_text,
             limits: HtmlTreeBuilderLimits::default(),
+            suspend_at_script_end: config.suspend_at_script_end,
+            record_source_spans: config.record_source_spans,
+            scripting: config.scripting,
         }
     }
 }
@@ -161,6 +176,9 @@ impl From<HtmlTreeBuilderOptions> for TreeBuilderConfig {
         Self {
             coalesce_text: value.coalesce_text,
             limits: value.limits.into(),
+            suspend_at_script_end: value.suspend_at_script_end,
+            record_source_spans: value.record_source_spans,
+            scripting: value.scripting,
         }
     }
 }
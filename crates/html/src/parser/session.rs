@@ -5,7 +5,7 @@ use crate::patch_validation::PatchValidationArena;
 
 use super::options::HtmlParseOptions;
 use super::output::ParseOutput;
-use super::types::{HtmlParseCounters, HtmlParseError, HtmlParseEvent};
+use super::types::{HtmlParseCounters, HtmlParseError, HtmlParseEvent, HtmlScript};
 
 /// Stable engine-level HTML parser API backed exclusively by the HTML5 pipeline.
 ///
@@ -87,6 +87,33 @@ impl HtmlParser {
         Ok(())
     }
 
+    /// Whether parsing stopped at a script end tag; see
+    /// [`HtmlTreeBuilderOptions::suspend_at_script_end`].
+    ///
+    /// While suspended, `push_bytes` keeps buffering input and `pump` does no
+    /// work. `finish` records the end of input and completes on `resume`.
+    ///
+    /// [`HtmlTreeBuilderOptions::suspend_at_script_end`]: super::HtmlTreeBuilderOptions::suspend_at_script_end
+    pub fn is_suspended(&self) -> bool {
+        self.session.is_suspended()
+    }
+
+    /// Take the script parsing stopped at. Drain patches first so the
+    /// document up to and including the script is available to run it
+    /// against.
+    pub fn take_script(&mut self) -> Option<HtmlScript> {
+        self.session.take_pending_script().map(HtmlScript::from)
+    }
+
+    /// Continue parsing after the script parsing stopped at has run.
+    ///
+    /// Returns `HtmlParseError::Invariant` if parsing is not suspended.
+    pub fn resume(&mut self) -> Result<(), HtmlParseError> {
+        self.ensure_not_poisoned()?;
+        self.session.resume()?;
+        Ok(())
+    }
+
     /// Drain the currently available patches as one ordered vector.
     ///
     /// Draining patches updates the parser's internal DOM mirror. If non-empty
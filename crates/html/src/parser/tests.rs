@@ -1,5 +1,5 @@
 use super::types::{HtmlParseEventCode, HtmlParseEventOrigin};
-use super::{HtmlErrorPolicy, HtmlParseOptions, HtmlParser, parse_document};
+use super::{HtmlErrorPolicy, HtmlParseError, HtmlParseOptions, HtmlParser, parse_document};
 use crate::{DomPatch, Node, PatchKey};
 
 fn first_child_element_named<'a>(node: &'a Node, name: &str) -> Option<&'a Node> {
@@ -136,6 +136,82 @@ fn finish_is_required_to_flush_eof_sensitive_text_mode_content() {
     );
 }
 
+fn created_texts(patches: &[DomPatch]) -> Vec<&str> {
+    patches
+        .iter()
+        .filter_map(|patch| match patch {
+            DomPatch::CreateText { text, .. } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn script_end_tags_suspend_parsing_until_resume() {
+    let mut options = HtmlParseOptions::default();
+    options.tree_builder.suspend_at_script_end = true;
+    let mut parser = HtmlParser::new(options).expect("session init");
+
+    parser
+        .push_str("<p>one</p><script>a = 1;</script><p>two</p>")
+        .expect("push");
+    parser.pump().expect("pump");
+    assert!(parser.is_suspended());
+    let patches = parser.take_patches().expect("drain at the script");
+    assert_eq!(created_texts(&patches), ["one", "a = 1;"]);
+    let script = parser.take_script().expect("script parsing stopped at");
+    assert_eq!(script.text, "a = 1;");
+    assert!(patches.iter().any(|patch| matches!(
+        patch,
+        DomPatch::CreateElement { key, name, .. } if *key == script.element && name.is_html("script")
+    )));
+
+    // Input keeps buffering and the end of input waits for the script.
+    parser
+        .push_str("<script>b = 2;</script><p>three</p>")
+        .expect("push");
+    parser.pump().expect("pump while suspended");
+    parser.finish().expect("finish while suspended");
+    assert!(parser.take_patches().expect("drain").is_empty());
+    assert!(parser.take_script().is_none());
+
+    parser.resume().expect("resume after the first script");
+    assert!(parser.is_suspended());
+    assert_eq!(
+        created_texts(&parser.take_patches().expect("drain")),
+        ["two", "b = 2;"]
+    );
+    assert_eq!(parser.take_script().expect("second script").text, "b = 2;");
+
+    parser.resume().expect("resume after the second script");
+    assert!(!parser.is_suspended());
+    assert_eq!(parser.resume(), Err(HtmlParseError::Invariant));
+}
+
+#[test]
+fn suspended_parse_builds_the_same_document_as_an_uninterrupted_one() {
+    let input = "<title>t</title><script>1</script><div><script>2</script>x</div>";
+    let mut options = HtmlParseOptions::default();
+    options.tree_builder.suspend_at_script_end = true;
+    let mut parser = HtmlParser::new(options).expect("session init");
+    parser.push_str(input).expect("push");
+    parser.finish().expect("finish");
+    let mut scripts = Vec::new();
+    while parser.is_suspended() {
+        scripts.push(parser.take_script().expect("script").text);
+        parser.resume().expect("resume");
+    }
+    assert_eq!(scripts, ["1", "2"]);
+
+    let suspended = parser.into_output().expect("suspended output");
+    let whole = parse_document(input, HtmlParseOptions::default()).expect("whole output");
+    let mut suspended_summary = Vec::new();
+    summarize(&suspended.document, &mut suspended_summary);
+    let mut whole_summary = Vec::new();
+    summarize(&whole.document, &mut whole_summary);
+    assert_eq!(suspended_summary, whole_summary);
+}
+
 #[test]
 fn take_patches_and_take_patch_batch_materialize_the_same_dom() {
     let input = "<div><span>a</span><span>b</span><span>c</span></div>";
@@ -231,6 +307,27 @@ fn parser_surface_exposes_parse_events_without_html5_types() {
     assert_eq!(output.parse_errors[0].detail, Some("tag-name-truncated"));
 }
 
+#[test]
+fn counters_report_content_dropped_at_node_and_depth_limits() {
+    let whole =
+        parse_document("<p>a</p><p>b</p>", HtmlParseOptions::default()).expect("parse should work");
+    assert_eq!(whole.counters.tree_builder_node_limit_hits, 0);
+    assert_eq!(whole.counters.tree_builder_depth_limit_hits, 0);
+
+    let mut options = HtmlParseOptions::default();
+    options.tree_builder.limits.max_nodes_created = 4;
+    let output = parse_document("<p>a</p><p>b</p>", options).expect("parse should work");
+    assert!(output.counters.tree_builder_node_limit_hits > 0);
+    assert_eq!(output.counters.tree_builder_depth_limit_hits, 0);
+
+    let mut options = HtmlParseOptions::default();
+    options.tree_builder.limits.max_open_elements_depth = 4;
+    let output = parse_document("<div><div><div>deep</div></div></div>", options)
+        .expect("parse should work");
+    assert!(output.counters.tree_builder_depth_limit_hits > 0);
+    assert_eq!(output.counters.tree_builder_node_limit_hits, 0);
+}
+
 #[test]
 fn parse_document_keeps_head_metadata_out_of_body_and_void_elements_do_not_capture_content() {
     let input = "<!doctype html><html lang=en><head><title>Example Domain</title><meta name=viewport content=\"width=device-width, initial-scale=1\"><style>body{background:#eee}h1{font-size:1.5em}</style></head><body><div><h1>Example Domain</h1><p>Visible body text.</p></div></body></html>";
@@ -305,3 +402,110 @@ fn patch_validation_failure_poisons_parser_for_future_mutation_and_drains() {
         crate::HtmlParseError::Invariant
     );
 }
+
+fn element_markup<'a>(node: &Node, input: &'a str, out: &mut Vec<(String, Option<&'a str>)>) {
+    if let Node::Element { element } = node {
+        let markup = element
+            .source_span()
+            .map(|span| &input[span.start..span.end]);
+        out.push((element.name().to_string(), markup));
+    }
+    for child in node.children().unwrap_or_default() {
+        element_markup(child, input, out);
+    }
+}
+
+#[test]
+fn source_spans_cover_start_tags_and_skip_implied_elements() {
+    let input = "<!doctype html><table><tr><td class=x>a</td></tr></table>\
+                 <svg><foreignObject/></svg><p\nid=y>b";
+    let mut options = HtmlParseOptions::default();
+    options.tree_builder.record_source_spans = true;
+    let output = parse_document(input, options).expect("parse should succeed");
+
+    let mut markup = Vec::new();
+    element_markup(&output.document, input, &mut markup);
+    let markup: Vec<(&str, Option<&str>)> = markup
+        .iter()
+        .map(|(name, markup)| (name.as_str(), *markup))
+        .collect();
+    assert_eq!(
+        markup,
+        [
+            ("html", None),
+            ("head", None),
+            ("body", None),
+            ("table", Some("<table>")),
+            ("tbody", None),
+            ("tr", Some("<tr>")),
+            ("td", Some("<td class=x>")),
+            ("svg", Some("<svg>")),
+            ("foreignObject", Some("<foreignObject/>")),
+            ("p", Some("<p\nid=y>")),
+        ]
+    );
+}
+
+#[test]
+fn source_spans_are_off_by_default_and_independent_of_chunking() {
+    let input = "<div><span title='a b'>x</span><img src=i.png></div>";
+    let plain = parse_document(input, HtmlParseOptions::default()).expect("plain parse");
+    assert!(
+        !plain
+            .patches
+            .iter()
+            .any(|patch| matches!(patch, DomPatch::SetSourceSpan { .. }))
+    );
+    let mut plain_markup = Vec::new();
+    element_markup(&plain.document, input, &mut plain_markup);
+    assert!(plain_markup.iter().all(|(_, markup)| markup.is_none()));
+
+    let mut options = HtmlParseOptions::default();
+    options.tree_builder.record_source_spans = true;
+    let whole = parse_document(input, options.clone()).expect("whole parse");
+    let mut parser = HtmlParser::new(options).expect("session init");
+    for byte in input.as_bytes() {
+        parser.push_bytes(std::slice::from_ref(byte)).expect("push");
+        parser.pump().expect("pump");
+    }
+    parser.finish().expect("finish");
+    let chunked = parser.into_output().expect("chunked output");
+
+    let mut whole_markup = Vec::new();
+    element_markup(&whole.document, input, &mut whole_markup);
+    let mut chunked_markup = Vec::new();
+    element_markup(&chunked.document, input, &mut chunked_markup);
+    assert_eq!(whole_markup, chunked_markup);
+    assert!(whole_markup.contains(&("img".to_string(), Some("<img src=i.png>"))));
+}
+
+#[test]
+fn noscript_contents_are_markup_unless_scripting_is_enabled() {
+    let input = "<head><noscript><link rel=stylesheet href=a.css><!--c--><p>x</p></noscript>\
+                 <body><noscript><img src=i.png></noscript>";
+    let output = parse_document(input, HtmlParseOptions::default()).expect("parse should succeed");
+    assert_eq!(
+        crate::serialize(&output.document),
+        "<html><head><noscript><link rel=\"stylesheet\" href=\"a.css\"><!--c--></noscript></head>\
+         <body><p>x</p><noscript><img src=\"i.png\"></noscript></body></html>"
+    );
+
+    let mut options = HtmlParseOptions::default();
+    options.tree_builder.scripting = true;
+    let output = parse_document(input, options).expect("parse should succeed");
+    let noscript_text = |node: &Node| match node.children() {
+        Some([Node::Text { text, .. }]) => text.clone(),
+        children => panic!("noscript should hold one text node, got {children:?}"),
+    };
+    let html = first_child_element_named(&output.document, "html").expect("html");
+    let head = first_child_element_named(html, "head").expect("head");
+    let body = first_child_element_named(html, "body").expect("body");
+    assert_eq!(
+        noscript_text(first_child_element_named(head, "noscript").expect("head noscript")),
+        "<link rel=stylesheet href=a.css><!--c--><p>x</p>"
+    );
+    assert_eq!(
+        noscript_text(first_child_element_named(body, "noscript").expect("body noscript")),
+        "<img src=i.png>"
+    );
+}
@@ -1,7 +1,9 @@
+use crate::dom_patch::PatchKey;
 use crate::html5::Html5SessionError;
 use crate::html5::shared::{
     Counters as Html5Counters, ErrorOrigin, ParseError as Html5ParseError, ParseErrorCode,
 };
+use crate::html5::tree_builder::PendingScript;
 
 /// Stable origin classification for surfaced parse events.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -93,6 +95,8 @@ pub struct HtmlParseCounters {
     pub tree_builder_text_nodes_created: u64,
     pub tree_builder_text_appends: u64,
     pub tree_builder_text_coalescing_invalidations: u64,
+    pub tree_builder_node_limit_hits: u64,
+    pub tree_builder_depth_limit_hits: u64,
 }
 
 impl From<Html5Counters> for HtmlParseCounters {
@@ -121,6 +125,26 @@ impl From<Html5Counters> for HtmlParseCounters {
             tree_builder_text_appends: value.tree_builder_text_appends,
             tree_builder_text_coalescing_invalidations: value
                 .tree_builder_text_coalescing_invalidations,
+            tree_builder_node_limit_hits: value.tree_builder_node_limit_hits,
+            tree_builder_depth_limit_hits: value.tree_builder_depth_limit_hits,
+        }
+    }
+}
+
+/// A parser-inserted script the parser stopped at, waiting for the embedder
+/// to run it before [`HtmlParser::resume`](super::HtmlParser::resume).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HtmlScript {
+    /// Key of the `<script>` element in the emitted patches.
+    pub element: PatchKey,
+    pub text: String,
+}
+
+impl From<PendingScript> for HtmlScript {
+    fn from(value: PendingScript) -> Self {
+        Self {
+            element: value.element,
+            text: value.text,
         }
     }
 }
@@ -464,6 +464,7 @@ impl PatchValidationArena {
                                 name: name.clone(),
                                 attributes: attributes.clone(),
                                 template_contents: None,
+                                source_span: None,
                             },
                             parent: None,
                             children: Vec::new(),
@@ -629,6 +630,70 @@ impl PatchValidationArena {
                         }
                     }
                 }
+                DomPatch::SetAttribute { key, attribute } => {
+                    self.ensure_node(*key, "SetAttribute target")?;
+                    let node = self.nodes.get_mut(key).ok_or_else(|| {
+                        PatchValidationError::new("SetAttribute target", "missing node")
+                    })?;
+                    match &mut node.kind {
+                        PatchKind::Element { attributes, .. } => {
+                            match attributes
+                                .iter_mut()
+                                .find(|slot| slot.name().same_expanded_name(attribute.name()))
+                            {
+                                Some(slot) => slot.clone_from(attribute),
+                                None => attributes.push(attribute.clone()),
+                            }
+                        }
+                        _ => {
+                            return Err(PatchValidationError::new(
+                                "SetAttribute target",
+                                "applied to non-element",
+                            ));
+                        }
+                    }
+                }
+                DomPatch::RemoveAttribute { key, name } => {
+                    self.ensure_node(*key, "RemoveAttribute target")?;
+                    let node = self.nodes.get_mut(key).ok_or_else(|| {
+                        PatchValidationError::new("RemoveAttribute target", "missing node")
+                    })?;
+                    match &mut node.kind {
+                        PatchKind::Element { attributes, .. } => {
+                            let Some(index) = attributes
+                                .iter()
+                                .position(|slot| slot.name().same_expanded_name(name))
+                            else {
+                                return Err(PatchValidationError::new(
+                                    "RemoveAttribute target",
+                                    format!("missing attribute `{}`", name.local_name()),
+                                ));
+                            };
+                            attributes.remove(index);
+                        }
+                        _ => {
+                            return Err(PatchValidationError::new(
+                                "RemoveAttribute target",
+                                "applied to non-element",
+                            ));
+                        }
+                    }
+                }
+                DomPatch::SetSourceSpan { key, span } => {
+                    self.ensure_node(*key, "SetSourceSpan target")?;
+                    let node = self.nodes.get_mut(key).ok_or_else(|| {
+                        PatchValidationError::new("SetSourceSpan target", "missing node")
+                    })?;
+                    match &mut node.kind {
+                        PatchKind::Element { source_span, .. } => *source_span = Some(*span),
+                        _ => {
+                            return Err(PatchValidationError::new(
+                                "SetSourceSpan target",
+                                "applied to non-element",
+                            ));
+                        }
+                    }
+                }
                 DomPatch::SetText { key, text } => {
                     self.ensure_node(*key, "SetText target")?;
                     let node = self.nodes.get_mut(key).ok_or_else(|| {
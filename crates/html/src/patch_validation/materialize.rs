@@ -46,17 +46,24 @@ impl PatchValidationArena {
                 name,
                 attributes,
                 template_contents,
-            } => crate::Node::from_element_parts(
-                Id::INVALID,
-                name.clone(),
-                attributes.clone(),
-                Vec::new(),
-                template_contents
-                    .map(|contents| self.materialize_fragment(contents))
-                    .transpose()?
-                    .map(Box::new),
-                children,
-            ),
+                source_span,
+            } => {
+                let mut node = crate::Node::from_element_parts(
+                    Id::INVALID,
+                    name.clone(),
+                    attributes.clone(),
+                    Vec::new(),
+                    template_contents
+                        .map(|contents| self.materialize_fragment(contents))
+                        .transpose()?
+                        .map(Box::new),
+                    children,
+                );
+                if let Some(element) = node.element_mut() {
+                    element.set_source_span(*source_span);
+                }
+                node
+            }
             PatchKind::DocumentFragment { .. } => {
                 return Err(PatchValidationError::new(
                     "materialize",
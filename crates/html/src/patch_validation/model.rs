@@ -3,7 +3,7 @@ use crate::dom_patch::PatchKey;
 use crate::names::ExpandedElementName;
 use std::collections::{HashMap, HashSet};
 
-use crate::types::ParserCreatedFragmentKind;
+use crate::types::{ParserCreatedFragmentKind, SourceSpan};
 
 #[derive(Clone, Debug)]
 pub(crate) enum PatchKind {
@@ -19,6 +19,7 @@ pub(crate) enum PatchKind {
         name: ExpandedElementName,
         attributes: Vec<ParserCreatedAttribute>,
         template_contents: Option<PatchKey>,
+        source_span: Option<SourceSpan>,
     },
     DocumentFragment {
         kind: ParserCreatedFragmentKind,
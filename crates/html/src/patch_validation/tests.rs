@@ -1,6 +1,6 @@
 use super::PatchValidationArena;
 use crate::dom_patch::PatchKey;
-use crate::test_support::html_name;
+use crate::test_support::{html_attribute, html_name};
 use crate::{DomPatch, Node};
 
 #[test]
@@ -498,3 +498,65 @@ fn processing_instruction_patch_rejects_non_parser_producible_payloads_atomicall
         assert_eq!(before, after);
     }
 }
+
+#[test]
+fn single_attribute_patches_update_in_place_and_preserve_order() {
+    let mut arena = PatchValidationArena::default();
+    arena
+        .apply_batch(&[
+            DomPatch::CreateDocument {
+                key: PatchKey(1),
+                doctype: None,
+            },
+            DomPatch::CreateElement {
+                key: PatchKey(2),
+                name: html_name("div"),
+                attributes: vec![
+                    html_attribute("id", Some("a")),
+                    html_attribute("title", Some("t")),
+                    html_attribute("class", Some("c")),
+                ],
+            },
+            DomPatch::AppendChild {
+                parent: PatchKey(1),
+                child: PatchKey(2),
+            },
+            DomPatch::SetAttribute {
+                key: PatchKey(2),
+                attribute: html_attribute("class", Some("d")),
+            },
+            DomPatch::RemoveAttribute {
+                key: PatchKey(2),
+                name: html_attribute("id", None).name().clone(),
+            },
+            DomPatch::SetAttribute {
+                key: PatchKey(2),
+                attribute: html_attribute("id", Some("b")),
+            },
+        ])
+        .expect("single-attribute patches should apply");
+
+    let dom = arena.materialize().expect("valid arena should materialize");
+    let Node::Document { children, .. } = dom else {
+        panic!("expected document root");
+    };
+    let element = children[0].element().expect("element child");
+    let attributes = element
+        .attributes()
+        .iter()
+        .map(|attribute| format!("{}={}", attribute.local_name(), attribute.value()))
+        .collect::<Vec<_>>();
+    assert_eq!(attributes, ["title=t", "class=d", "id=b"]);
+
+    let err = arena
+        .apply_batch(&[DomPatch::RemoveAttribute {
+            key: PatchKey(2),
+            name: html_attribute("lang", None).name().clone(),
+        }])
+        .expect_err("removing an absent attribute must fail");
+    assert!(
+        err.to_string()
+            .contains("RemoveAttribute target: missing attribute `lang`"),
+        "unexpected remove-attribute error: {err}"
+    );
+}
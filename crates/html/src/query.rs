@@ -0,0 +1,353 @@
+//! Element lookup over a materialized DOM: compound selectors and an id index.
+//!
+//! This is the shared replacement for hand-rolled recursive searches in the
+//! browser layer. Lookups walk the light tree in document order; parser-created
+//! template contents are not part of the tree and are never matched.
+
+use crate::Node;
+use crate::names::ElementNamespace;
+use std::collections::HashMap;
+use std::fmt;
+
+/// A compound selector: an optional type selector followed by any number of
+/// `#id`, `.class` and `[attr]` / `[attr=value]` simple selectors, e.g.
+/// `input.big[type=checkbox]` or `*#main`.
+///
+/// Combinators, pseudo-classes and attribute operators other than `=` are
+/// not supported and fail to parse.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Selector {
+    /// Type selector as written; `None` for `*` or when omitted.
+    tag: Option<String>,
+    ids: Vec<String>,
+    classes: Vec<String>,
+    attributes: Vec<AttributeSelector>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct AttributeSelector {
+    name: String,
+    value: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SelectorParseError {
+    pub offset: usize,
+    pub reason: &'static str,
+}
+
+impl fmt::Display for SelectorParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid selector at byte {}: {}",
+            self.offset, self.reason
+        )
+    }
+}
+
+impl std::error::Error for SelectorParseError {}
+
+impl Selector {
+    pub fn parse(source: &str) -> Result<Self, SelectorParseError> {
+        SelectorParser { source, offset: 0 }.parse()
+    }
+
+    /// Whether `node` is an element matching every simple selector.
+    ///
+    /// Type and attribute names match ASCII case-insensitively on HTML
+    /// elements and exactly on foreign ones; id, class and attribute values
+    /// always match exactly.
+    pub fn matches(&self, node: &Node) -> bool {
+        let Node::Element { element } = node else {
+            return false;
+        };
+        if let Some(tag) = &self.tag {
+            let matched = if element.namespace() == ElementNamespace::Html {
+                element.name().eq_ignore_ascii_case(tag)
+            } else {
+                element.name() == tag
+            };
+            if !matched {
+                return false;
+            }
+        }
+        self.ids.iter().all(|id| node.attr("id") == Some(id))
+            && self.classes.iter().all(|class| {
+                node.attr("class")
+                    .is_some_and(|value| split_ascii_whitespace(value).any(|token| token == class))
+            })
+            && self.attributes.iter().all(|attribute| {
+                match (node.attr(&attribute.name), &attribute.value) {
+                    (Some(actual), Some(expected)) => actual == expected,
+                    (Some(_), None) => true,
+                    (None, _) => false,
+                }
+            })
+    }
+}
+
+/// The first element under `root` (inclusive) matching `selector`, in
+/// document order.
+pub fn query<'dom>(root: &'dom Node, selector: &Selector) -> Option<&'dom Node> {
+    preorder(root).find(|node| selector.matches(node))
+}
+
+/// Every element under `root` (inclusive) matching `selector`, in document
+/// order.
+pub fn query_all<'dom>(root: &'dom Node, selector: &Selector) -> Vec<&'dom Node> {
+    preorder(root)
+        .filter(|node| selector.matches(node))
+        .collect()
+}
+
+/// The first element under `root` (inclusive) whose `id` is exactly `id`.
+///
+/// Prefer [`IdIndex`] when looking up more than one id in the same tree.
+pub fn get_element_by_id<'dom>(root: &'dom Node, id: &str) -> Option<&'dom Node> {
+    if id.is_empty() {
+        return None;
+    }
+    preorder(root).find(|node| node.attr("id") == Some(id))
+}
+
+/// `id` → element lookup for one materialized tree.
+///
+/// When several elements share an id, the first in document order wins, as
+/// with `getElementById`. Empty ids are never indexed. The index borrows the
+/// tree, so it has to be rebuilt whenever the tree is re-materialized.
+#[derive(Clone, Debug, Default)]
+pub struct IdIndex<'dom> {
+    elements: HashMap<&'dom str, &'dom Node>,
+}
+
+impl<'dom> IdIndex<'dom> {
+    pub fn new(root: &'dom Node) -> Self {
+        let mut elements = HashMap::new();
+        for node in preorder(root) {
+            if let Some(id) = node.attr("id").filter(|id| !id.is_empty()) {
+                elements.entry(id).or_insert(node);
+            }
+        }
+        Self { elements }
+    }
+
+    pub fn get(&self, id: &str) -> Option<&'dom Node> {
+        self.elements.get(id).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+}
+
+/// Iterative pre-order walk of the light tree, so deep documents cannot
+/// overflow the stack.
+fn preorder(root: &Node) -> impl Iterator<Item = &Node> {
+    let mut stack = vec![root];
+    std::iter::from_fn(move || {
+        let node = stack.pop()?;
+        if let Some(children) = node.children() {
+            stack.extend(children.iter().rev());
+        }
+        Some(node)
+    })
+}
+
+fn split_ascii_whitespace(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split(|ch: char| ch.is_ascii_whitespace())
+        .filter(|token| !token.is_empty())
+}
+
+struct SelectorParser<'a> {
+    source: &'a str,
+    offset: usize,
+}
+
+impl SelectorParser<'_> {
+    fn parse(mut self) -> Result<Selector, SelectorParseError> {
+        let mut selector = Selector::default();
+        if self.eat('*') {
+            // Universal selector: no type constraint.
+        } else if self.peek().is_some_and(is_name_char) {
+            selector.tag = Some(self.name()?.to_string());
+        }
+        while let Some(ch) = self.peek() {
+            match ch {
+                '#' => {
+                    self.offset += 1;
+                    selector.ids.push(self.name()?.to_string());
+                }
+                '.' => {
+                    self.offset += 1;
+                    selector.classes.push(self.name()?.to_string());
+                }
+                '[' => {
+                    self.offset += 1;
+                    selector.attributes.push(self.attribute()?);
+                }
+                _ => return Err(self.error("unsupported selector syntax")),
+            }
+        }
+        if selector == Selector::default() && !self.source.starts_with('*') {
+            return Err(self.error("empty selector"));
+        }
+        Ok(selector)
+    }
+
+    fn attribute(&mut self) -> Result<AttributeSelector, SelectorParseError> {
+        self.skip_whitespace();
+        let name = self.name()?.to_string();
+        self.skip_whitespace();
+        let value = if self.eat('=') {
+            self.skip_whitespace();
+            let value = match self.peek() {
+                Some(quote @ ('"' | '\'')) => {
+                    self.offset += 1;
+                    let rest = &self.source[self.offset..];
+                    let end = rest
+                        .find(quote)
+                        .ok_or_else(|| self.error("unterminated attribute value"))?;
+                    self.offset += end + 1;
+                    rest[..end].to_string()
+                }
+                _ => self.name()?.to_string(),
+            };
+            self.skip_whitespace();
+            Some(value)
+        } else {
+            None
+        };
+        if !self.eat(']') {
+            return Err(self.error("expected `]`"));
+        }
+        Ok(AttributeSelector { name, value })
+    }
+
+    fn name(&mut self) -> Result<&str, SelectorParseError> {
+        let rest = &self.source[self.offset..];
+        let len = rest
+            .find(|ch: char| !is_name_char(ch))
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return Err(self.error("expected a name"));
+        }
+        self.offset += len;
+        Ok(&rest[..len])
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.source[self.offset..].chars().next()
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.offset += expected.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|ch| ch.is_ascii_whitespace()) {
+            self.offset += 1;
+        }
+    }
+
+    fn error(&self, reason: &'static str) -> SelectorParseError {
+        SelectorParseError {
+            offset: self.offset,
+            reason,
+        }
+    }
+}
+
+/// CSS identifier characters, without escapes.
+fn is_name_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' || !ch.is_ascii()
+}
+
+#[cfg(all(test, feature = "html5"))]
+mod tests {
+    use super::{IdIndex, Selector, get_element_by_id, query, query_all};
+    use crate::{HtmlParseOptions, Node, parse_document};
+
+    fn parse(html: &str) -> Node {
+        parse_document(html, HtmlParseOptions::default())
+            .expect("parse should succeed")
+            .document
+    }
+
+    fn selector(source: &str) -> Selector {
+        Selector::parse(source).expect("selector should parse")
+    }
+
+    fn text_of(node: &Node) -> String {
+        let mut out = String::new();
+        crate::collect::collect_visible_text(node, &mut out);
+        out.trim().to_string()
+    }
+
+    #[test]
+    fn compound_selectors_match_tag_id_class_and_attributes() {
+        let dom = parse(
+            "<p class='a b'>one</p><P CLASS=b id=x>two</P>\
+             <input type=checkbox name=c><input type=text name=c>",
+        );
+
+        assert_eq!(text_of(query(&dom, &selector("p.b")).unwrap()), "one");
+        assert_eq!(text_of(query(&dom, &selector("P#x.b")).unwrap()), "two");
+        assert_eq!(query_all(&dom, &selector(".b")).len(), 2);
+        assert!(query(&dom, &selector(".B")).is_none());
+        assert_eq!(query_all(&dom, &selector("input[name=c]")).len(), 2);
+        assert_eq!(
+            query(&dom, &selector("[TYPE = \"text\"]"))
+                .unwrap()
+                .attr("type"),
+            Some("text")
+        );
+        assert!(query(&dom, &selector("input[type=radio]")).is_none());
+        assert_eq!(query_all(&dom, &selector("*[name]")).len(), 2);
+    }
+
+    #[test]
+    fn foreign_type_selectors_match_case_sensitively() {
+        let dom = parse("<svg><foreignObject/><lineargradient/></svg>");
+
+        assert!(query(&dom, &selector("foreignObject")).is_some());
+        assert!(query(&dom, &selector("linearGradient")).is_some());
+        assert!(query(&dom, &selector("lineargradient")).is_none());
+    }
+
+    #[test]
+    fn unsupported_selectors_are_rejected() {
+        for source in ["", "div p", "div > p", "a:hover", "[href^=x]", "#", "[x"] {
+            assert!(
+                Selector::parse(source).is_err(),
+                "{source:?} should not parse"
+            );
+        }
+    }
+
+    #[test]
+    fn id_lookups_return_the_first_element_in_document_order() {
+        let dom = parse(
+            "<div id=dup>first</div><template><p id=inner></p></template>\
+             <span id=dup>second</span><b id=''></b>",
+        );
+        let index = IdIndex::new(&dom);
+
+        assert_eq!(text_of(index.get("dup").unwrap()), "first");
+        assert_eq!(text_of(get_element_by_id(&dom, "dup").unwrap()), "first");
+        assert!(index.get("inner").is_none());
+        assert!(index.get("").is_none());
+        assert!(get_element_by_id(&dom, "").is_none());
+        assert_eq!(index.len(), 1);
+    }
+}
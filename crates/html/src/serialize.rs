@@ -0,0 +1,241 @@
+//! DOM → HTML text, following the HTML fragment serialization algorithm.
+//!
+//! The output is what a browser's `outerHTML` would produce for the same
+//! tree: void elements have no end tag, attribute values are always
+//! double-quoted, and text inside rawtext elements is written unescaped.
+//! Template contents are serialized inside their `<template>` element.
+//!
+//! As in browsers, the output does not always parse back to the same tree:
+//! a leading newline in `<pre>` or `<textarea>` is written as-is and dropped
+//! again by the parser.
+
+use crate::Node;
+use crate::names::ElementNamespace;
+use crate::types::ElementNode;
+
+/// Serialize `node` and everything below it.
+///
+/// Document nodes serialize as their children; every other node serializes
+/// as itself, like `outerHTML`.
+pub fn serialize(node: &Node) -> String {
+    let mut out = String::new();
+    let mut stack = vec![Step::Node {
+        node,
+        raw_text: false,
+    }];
+    // Iterative, so deep documents cannot overflow the stack.
+    while let Some(step) = stack.pop() {
+        match step {
+            Step::Node { node, raw_text } => write_node(node, raw_text, &mut out, &mut stack),
+            Step::EndTag(element) => {
+                out.push_str("</");
+                out.push_str(element.name());
+                out.push('>');
+            }
+        }
+    }
+    out
+}
+
+enum Step<'dom> {
+    Node {
+        node: &'dom Node,
+        /// Whether the parent is a rawtext element, so text is not escaped.
+        raw_text: bool,
+    },
+    EndTag(&'dom ElementNode),
+}
+
+fn write_node<'dom>(
+    node: &'dom Node,
+    raw_text: bool,
+    out: &mut String,
+    stack: &mut Vec<Step<'dom>>,
+) {
+    match node {
+        Node::Document { children, .. } => push_children(children, false, stack),
+        Node::DocumentType { name, .. } => {
+            out.push_str("<!DOCTYPE ");
+            out.push_str(name.as_deref().unwrap_or_default());
+            out.push('>');
+        }
+        Node::Element { element } => {
+            write_start_tag(element, out);
+            if is_void(element) {
+                return;
+            }
+            stack.push(Step::EndTag(element));
+            let children = match element.template_contents() {
+                Some(contents) => contents.children(),
+                None => element.children(),
+            };
+            push_children(children, is_raw_text(element), stack);
+        }
+        Node::Text { text, .. } if raw_text => out.push_str(text),
+        Node::Text { text, .. } => escape(text, false, out),
+        Node::Comment { text, .. } => {
+            out.push_str("<!--");
+            out.push_str(text);
+            out.push_str("-->");
+        }
+        Node::ProcessingInstruction {
+            processing_instruction,
+        } => {
+            out.push_str("<?");
+            out.push_str(processing_instruction.target());
+            out.push(' ');
+            out.push_str(processing_instruction.data());
+            out.push('>');
+        }
+    }
+}
+
+fn push_children<'dom>(children: &'dom [Node], raw_text: bool, stack: &mut Vec<Step<'dom>>) {
+    stack.extend(
+        children
+            .iter()
+            .rev()
+            .map(|node| Step::Node { node, raw_text }),
+    );
+}
+
+fn write_start_tag(element: &ElementNode, out: &mut String) {
+    out.push('<');
+    out.push_str(element.name());
+    for attribute in element.attributes() {
+        out.push(' ');
+        if let Some(prefix) = attribute.prefix() {
+            out.push_str(prefix);
+            out.push(':');
+        }
+        out.push_str(attribute.local_name());
+        out.push_str("=\"");
+        escape(attribute.value(), true, out);
+        out.push('"');
+    }
+    out.push('>');
+}
+
+/// The spec's "escaping a string": `&` and U+00A0 always, `"` in attribute
+/// values, and `<` / `>` everywhere.
+fn escape(value: &str, attribute_mode: bool, out: &mut String) {
+    for ch in value.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '\u{a0}' => out.push_str("&nbsp;"),
+            '"' if attribute_mode => out.push_str("&quot;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            _ => out.push(ch),
+        }
+    }
+}
+
+fn is_void(element: &ElementNode) -> bool {
+    element.namespace() == ElementNamespace::Html
+        && matches!(
+            element.name(),
+            "area"
+                | "base"
+                | "basefont"
+                | "bgsound"
+                | "br"
+                | "col"
+                | "embed"
+                | "frame"
+                | "hr"
+                | "img"
+                | "input"
+                | "keygen"
+                | "link"
+                | "meta"
+                | "param"
+                | "source"
+                | "track"
+                | "wbr"
+        )
+}
+
+/// Elements whose text children are written as-is. `noscript` is not
+/// included: documents are parsed with scripting disabled by default, so its
+/// children are markup.
+fn is_raw_text(element: &ElementNode) -> bool {
+    element.namespace() == ElementNamespace::Html
+        && matches!(
+            element.name(),
+            "style" | "script" | "xmp" | "iframe" | "noembed" | "noframes" | "plaintext"
+        )
+}
+
+#[cfg(all(test, feature = "html5"))]
+mod tests {
+    use super::serialize;
+    use crate::{HtmlParseOptions, Node, parse_document};
+
+    fn parse(html: &str) -> Node {
+        parse_document(html, HtmlParseOptions::default())
+            .expect("parse should succeed")
+            .document
+    }
+
+    fn body_html(html: &str) -> String {
+        let serialized = serialize(&parse(html));
+        let start = serialized.find("<body>").expect("body start tag") + "<body>".len();
+        let end = serialized.rfind("</body>").expect("body end tag");
+        serialized[start..end].to_string()
+    }
+
+    #[test]
+    fn documents_serialize_with_implied_elements_and_doctype() {
+        assert_eq!(
+            serialize(&parse("<!doctype html><title>t</title><p>x")),
+            "<!DOCTYPE html><html><head><title>t</title></head><body><p>x</p></body></html>"
+        );
+    }
+
+    #[test]
+    fn void_elements_have_no_end_tag_and_attributes_are_escaped() {
+        assert_eq!(
+            body_html("<img src=a.png alt='say \"hi\" & <bye>'><br><input disabled>"),
+            "<img src=\"a.png\" alt=\"say &quot;hi&quot; &amp; &lt;bye&gt;\"><br><input disabled=\"\">"
+        );
+    }
+
+    #[test]
+    fn text_is_escaped_except_inside_rawtext_elements() {
+        assert_eq!(
+            body_html(
+                "<p>a &lt; b &amp;&nbsp;\"c\"</p><script>if (a < b && c) {}</script>\
+                 <style>p > a {}</style><textarea>&lt;x&gt;</textarea><!--note-->"
+            ),
+            "<p>a &lt; b &amp;&nbsp;\"c\"</p><script>if (a < b && c) {}</script>\
+             <style>p > a {}</style><textarea>&lt;x&gt;</textarea><!--note-->"
+        );
+    }
+
+    #[test]
+    fn foreign_names_and_template_contents_are_preserved() {
+        assert_eq!(
+            body_html(
+                "<svg viewbox='0 0 1 1' xlink:href=#a><foreignobject/></svg>\
+                 <template><td>cell</td></template>"
+            ),
+            "<svg viewBox=\"0 0 1 1\" xlink:href=\"#a\"><foreignObject></foreignObject></svg>\
+             <template><td>cell</td></template>"
+        );
+    }
+
+    #[test]
+    fn serialized_output_round_trips_through_the_parser() {
+        for html in [
+            "<!DOCTYPE html><html><head><meta charset=\"utf-8\"></head>\
+             <body><ul><li>one</li><li class=\"b\">two &amp; three</li></ul></body></html>",
+            "<table><tr><td>x</td></tr></table><pre>keep\n</pre>",
+            "<math><mi>x</mi></math><div title=\"&lt;&quot;\">y</div>",
+        ] {
+            let once = serialize(&parse(html));
+            let twice = serialize(&parse(&once));
+            assert_eq!(once, twice, "serialization of {html:?} should round-trip");
+        }
+    }
+}
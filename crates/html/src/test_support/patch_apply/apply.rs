@@ -161,6 +161,46 @@ impl TestPatchArena {
                         *attrs = attributes.clone();
                     }
                 }
+                DomPatch::SetAttribute { key, attribute } => {
+                    let Some(node) = self.nodes.get_mut(key) else {
+                        return Err("missing node".to_string());
+                    };
+                    let TestKind::Element { attributes, .. } = &mut node.kind else {
+                        return Err("SetAttribute applied to non-element node".to_string());
+                    };
+                    match attributes
+                        .iter_mut()
+                        .find(|slot| slot.name().same_expanded_name(attribute.name()))
+                    {
+                        Some(slot) => *slot = attribute.clone(),
+                        None => attributes.push(attribute.clone()),
+                    }
+                }
+                DomPatch::RemoveAttribute { key, name } => {
+                    let Some(node) = self.nodes.get_mut(key) else {
+                        return Err("missing node".to_string());
+                    };
+                    let TestKind::Element { attributes, .. } = &mut node.kind else {
+                        return Err("RemoveAttribute applied to non-element node".to_string());
+                    };
+                    let Some(index) = attributes
+                        .iter()
+                        .position(|slot| slot.name().same_expanded_name(name))
+                    else {
+                        return Err(format!("missing attribute `{}`", name.local_name()));
+                    };
+                    attributes.remove(index);
+                }
+                DomPatch::SetSourceSpan { key, .. } => {
+                    // Spans are not part of the test DOM; only the target is
+                    // checked.
+                    let Some(node) = self.nodes.get(key) else {
+                        return Err("missing node".to_string());
+                    };
+                    if !matches!(node.kind, TestKind::Element { .. }) {
+                        return Err("SetSourceSpan applied to non-element node".to_string());
+                    }
+                }
                 DomPatch::SetText { key, text } => {
                     let Some(node) = self.nodes.get_mut(key) else {
                         return Err("missing node".to_string());
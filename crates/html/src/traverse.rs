@@ -109,37 +109,12 @@ pub(crate) fn full_model_node_count(root: &Node) -> usize {
     full_model_preorder(root).count()
 }
 
-// Centralizes raw-pointer traversal handling for `assign_missing_ids_allow_collisions`.
+/// A full-model entry still waiting for an identity during
+/// `assign_missing_ids_allow_collisions`.
 #[cfg(any(test, all(feature = "test-harness", feature = "internal-api")))]
-enum FullModelMutPtr {
-    Node(*mut Node),
-    Fragment(*mut DocumentFragmentNode),
-}
-
-#[cfg(any(test, all(feature = "test-harness", feature = "internal-api")))]
-struct NodeStack {
-    stack: Vec<FullModelMutPtr>,
-}
-
-#[cfg(any(test, all(feature = "test-harness", feature = "internal-api")))]
-impl NodeStack {
-    fn new(root: &mut Node) -> Self {
-        Self {
-            stack: {
-                let mut stack = Vec::with_capacity(128);
-                stack.push(FullModelMutPtr::Node(root as *mut Node));
-                stack
-            },
-        }
-    }
-
-    fn pop_ptr(&mut self) -> Option<FullModelMutPtr> {
-        self.stack.pop()
-    }
-
-    fn push_ptr(&mut self, ptr: FullModelMutPtr) {
-        self.stack.push(ptr);
-    }
+enum FullModelNodeMut<'a> {
+    Node(&'a mut Node),
+    DocumentFragment(&'a mut DocumentFragmentNode),
 }
 
 /// Assigns missing IDs in depth-first, pre-order traversal (document/element before children).
@@ -153,37 +128,36 @@ impl NodeStack {
 pub(crate) fn assign_missing_ids_allow_collisions(root: &mut Node) {
     use crate::types::Id;
     let mut next = 1;
-    let mut stack = NodeStack::new(root);
-
-    while let Some(item) = stack.pop_ptr() {
-        // SAFETY: pointers originate from ordinary node vectors or typed
-        // `DocumentFragmentNode::children` vectors physically owned by `root`.
-        // We never mutate either class of child vector during traversal, so no
-        // backing storage reallocates. Only identity fields are changed, which
-        // does not move nodes or fragment roots.
-        match item {
-            FullModelMutPtr::Node(node_ptr) => {
-                let node = unsafe { &mut *node_ptr };
-                if node.id() == Id::INVALID {
-                    let id = Id(next);
-                    next = next.checked_add(1).expect("node id overflow");
+    let mut assign = |id: Id| {
+        if id != Id::INVALID {
+            return None;
+        }
+        let id = Id(next);
+        next = next.checked_add(1).expect("node id overflow");
+        Some(id)
+    };
+
+    // Each entry borrows a disjoint subtree of `root`, visited in the same
+    // order as `full_model_preorder`.
+    let mut stack = Vec::with_capacity(128);
+    stack.push(FullModelNodeMut::Node(root));
+    while let Some(entry) = stack.pop() {
+        match entry {
+            FullModelNodeMut::Node(node) => {
+                if let Some(id) = assign(node.id()) {
                     node.set_id(id);
                 }
-
                 match node {
                     Node::Document { children, .. } => {
-                        for child in children.iter_mut().rev() {
-                            stack.push_ptr(FullModelMutPtr::Node(child as *mut Node));
-                        }
+                        stack.extend(children.iter_mut().rev().map(FullModelNodeMut::Node));
                     }
                     Node::Element { element } => {
-                        for child in element.children_mut().iter_mut().rev() {
-                            stack.push_ptr(FullModelMutPtr::Node(child as *mut Node));
-                        }
-                        if let Some(contents) = element.template_contents_mut() {
-                            stack.push_ptr(FullModelMutPtr::Fragment(
-                                contents as *mut DocumentFragmentNode,
-                            ));
+                        let (children, contents) = element.children_and_template_contents_mut();
+                        // Ordinary children are pushed first so the association
+                        // is popped and visited before them.
+                        stack.extend(children.iter_mut().rev().map(FullModelNodeMut::Node));
+                        if let Some(contents) = contents {
+                            stack.push(FullModelNodeMut::DocumentFragment(contents));
                         }
                     }
                     Node::DocumentType { .. }
@@ -192,16 +166,17 @@ pub(crate) fn assign_missing_ids_allow_collisions(root: &mut Node) {
                     | Node::ProcessingInstruction { .. } => {}
                 }
             }
-            FullModelMutPtr::Fragment(fragment_ptr) => {
-                let fragment = unsafe { &mut *fragment_ptr };
-                if fragment.id() == Id::INVALID {
-                    let id = Id(next);
-                    next = next.checked_add(1).expect("node id overflow");
+            FullModelNodeMut::DocumentFragment(fragment) => {
+                if let Some(id) = assign(fragment.id()) {
                     fragment.set_id(id);
                 }
-                for child in fragment.children_mut().iter_mut().rev() {
-                    stack.push_ptr(FullModelMutPtr::Node(child as *mut Node));
-                }
+                stack.extend(
+                    fragment
+                        .children_mut()
+                        .iter_mut()
+                        .rev()
+                        .map(FullModelNodeMut::Node),
+                );
             }
         }
     }
@@ -629,4 +604,91 @@ mod tests {
             ]
         );
     }
+
+    /// Small deterministic generator for the property test below.
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn below(&mut self, bound: u64) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 % bound
+        }
+
+        /// A missing id most of the time, otherwise an arbitrary existing one.
+        fn id(&mut self) -> Id {
+            if self.below(4) == 0 {
+                Id(self.below(50) as u32 + 1)
+            } else {
+                Id::INVALID
+            }
+        }
+    }
+
+    fn random_nodes(rng: &mut XorShift, depth: usize) -> Vec<Node> {
+        let count = if depth == 0 { 0 } else { rng.below(4) };
+        (0..count)
+            .map(|_| match rng.below(5) {
+                0 => Node::Text {
+                    id: rng.id(),
+                    text: "t".to_string(),
+                },
+                1 => Node::Comment {
+                    id: rng.id(),
+                    text: "c".to_string(),
+                },
+                2 => {
+                    let id = rng.id();
+                    let contents = DocumentFragmentNode::new_template_contents(
+                        rng.id(),
+                        random_nodes(rng, depth - 1),
+                    );
+                    template_element(id, contents, random_nodes(rng, depth - 1))
+                }
+                _ => crate::Node::from_element_parts(
+                    rng.id(),
+                    html_name("div"),
+                    Vec::new(),
+                    Vec::new(),
+                    None,
+                    random_nodes(rng, depth - 1),
+                ),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn assign_missing_ids_numbers_missing_entries_in_full_model_preorder() {
+        for seed in 1..=200 {
+            let mut rng = XorShift(seed);
+            let mut root = Node::Document {
+                id: rng.id(),
+                doctype: None,
+                children: random_nodes(&mut rng, 5),
+            };
+            let before = full_model_preorder(&root)
+                .map(|visit| visit.entry.id())
+                .collect::<Vec<_>>();
+
+            assign_missing_ids_allow_collisions(&mut root);
+
+            let mut next = 0;
+            let expected = before
+                .iter()
+                .map(|&id| {
+                    if id == Id::INVALID {
+                        next += 1;
+                        Id(next)
+                    } else {
+                        id
+                    }
+                })
+                .collect::<Vec<_>>();
+            let after = full_model_preorder(&root)
+                .map(|visit| visit.entry.id())
+                .collect::<Vec<_>>();
+            assert_eq!(after, expected, "seed {seed}");
+        }
+    }
 }
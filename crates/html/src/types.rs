@@ -21,6 +21,7 @@ pub type NodeId = u32;
 /// - IDs are assigned by the owning DOM builder/patch applier.
 /// - `0` is reserved to represent "unassigned/invalid" during construction.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Id(pub NodeId);
 
 impl Id {
@@ -123,6 +124,18 @@ pub enum ParserCreatedFragmentKind {
     TestOnlyUnsupported,
 }
 
+/// Byte range of the markup an element was created from.
+///
+/// Offsets index the decoded document text after newline normalization, not
+/// the raw network bytes. For a start tag the range runs from `<` through the
+/// closing `>`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SourceSpan {
+    pub start: usize,
+    pub end: usize,
+}
+
 /// Opaque payload for an ordinary element node.
 ///
 /// The parser-created template-contents association is intentionally private.
@@ -136,6 +149,7 @@ pub struct ElementNode {
     style: Vec<(String, String)>,
     children: Vec<Node>,
     template_contents: Option<Box<DocumentFragmentNode>>,
+    source_span: Option<SourceSpan>,
 }
 
 /// Minimum parser-created processing-instruction representation.
@@ -230,6 +244,16 @@ impl ElementNode {
     pub fn children_mut(&mut self) -> &mut Vec<Node> {
         &mut self.children
     }
+    /// Where the parser found this element's start tag, when it recorded one.
+    ///
+    /// Elements the parser implied (such as `<tbody>` inside a bare table)
+    /// and elements built outside the parser have no span.
+    pub fn source_span(&self) -> Option<SourceSpan> {
+        self.source_span
+    }
+    pub fn set_source_span(&mut self, span: Option<SourceSpan>) {
+        self.source_span = span;
+    }
 
     pub(crate) fn from_parts(
         id: Id,
@@ -250,6 +274,7 @@ impl ElementNode {
             style,
             children,
             template_contents,
+            source_span: None,
         }
     }
 
@@ -259,10 +284,18 @@ impl ElementNode {
     pub(crate) fn template_contents(&self) -> Option<&DocumentFragmentNode> {
         self.template_contents.as_deref()
     }
-    #[cfg(any(test, feature = "test-harness"))]
+    #[cfg(test)]
     pub(crate) fn template_contents_mut(&mut self) -> Option<&mut DocumentFragmentNode> {
         self.template_contents.as_deref_mut()
     }
+    /// Ordinary children and template contents, borrowed together so a
+    /// traversal can hold both.
+    #[cfg(any(test, all(feature = "test-harness", feature = "internal-api")))]
+    pub(crate) fn children_and_template_contents_mut(
+        &mut self,
+    ) -> (&mut Vec<Node>, Option<&mut DocumentFragmentNode>) {
+        (&mut self.children, self.template_contents.as_deref_mut())
+    }
 }
 
 #[derive(Debug)]
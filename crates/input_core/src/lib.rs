@@ -7,6 +7,10 @@
 //! - [`InputValueStore`]: Central store for input values, caret positions, and selections
 //! - [`SelectionRange`]: Represents a text selection with start/end byte offsets
 //! - [`InputStore`]: Trait abstracting input store operations for dependency inversion
+//! - [`InputConstraints`] / [`Validity`]: Constraint validation (required, pattern,
+//!   length and numeric bounds), recomputed on every edit
+//! - [`InputStoreSnapshot`]: Plain-data snapshot of store contents (serde behind the
+//!   `serde` feature)
 //!
 //! ## Design Principles
 //!
@@ -15,7 +19,7 @@
 //! - Layout or hit-testing systems
 //! - Platform-specific APIs
 //!
-//! It depends only on `std` and provides pure editing semantics that can be
+//! It depends only on `std` (plus optional `serde` for snapshots) and provides pure editing semantics that can be
 //! tested independently and reused across different UI implementations.
 //!
 //! Layout- or measurement-dependent behaviors (caret hit-testing, text shaping,
@@ -34,16 +38,26 @@
 //! ```
 
 mod id;
+mod mask;
+mod pattern;
 mod selection;
+mod snapshot;
 mod state;
 mod store;
 mod text;
 mod traits;
+mod validation;
 
 pub use id::InputId;
+pub use mask::{
+    DisplayState, MASK_CHAR, masked_display, masked_offset_to_value, value_offset_to_masked,
+};
+pub use pattern::Pattern;
 pub use selection::SelectionRange;
+pub use snapshot::{InputSnapshot, InputStoreSnapshot};
 pub use store::InputValueStore;
 pub use traits::InputStore;
+pub use validation::{InputConstraints, InvalidReason, Validity};
 
 // Re-export text utilities for use by integration layers that need
 // caret positioning with custom measurement functions.
@@ -0,0 +1,129 @@
+//! Masked (password-style) display helpers.
+//!
+//! A masked input renders one [`MASK_CHAR`] per extended grapheme cluster of
+//! the real value, so a user-perceived character such as `é` written with a
+//! combining accent, or a flag emoji, shows as a single bullet. Caret stops in
+//! this crate are Unicode scalar values (see
+//! [`next_cursor_boundary`](crate::next_cursor_boundary)); a stop inside a
+//! cluster maps to the start of that cluster's bullet.
+//!
+//! Integration layers paint and hit-test against the display string and use
+//! the offset mapping functions to translate back into real byte offsets.
+
+use crate::selection::SelectionRange;
+use crate::text::clamp_to_char_boundary;
+use std::borrow::Cow;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// State tuple in display coordinates.
+///
+/// `(display, caret, selection, scroll_x, scroll_y)`, where `caret` and
+/// `selection` are byte offsets into `display`.
+pub type DisplayState<'a> = (Cow<'a, str>, usize, Option<SelectionRange>, f32, f32);
+
+/// Character used to mask each grapheme cluster of a masked value.
+pub const MASK_CHAR: char = '•';
+
+/// Build the masked display string for `value`.
+///
+/// # Examples
+///
+/// ```
+/// use input_core::masked_display;
+///
+/// assert_eq!(masked_display(""), "");
+/// assert_eq!(masked_display("a€b"), "•••");
+/// assert_eq!(masked_display("e\u{301}🇳🇱"), "••");
+/// ```
+pub fn masked_display(value: &str) -> String {
+    std::iter::repeat_n(MASK_CHAR, value.graphemes(true).count()).collect()
+}
+
+/// Map a byte offset in the real value to the byte offset in its masked display.
+///
+/// Offsets inside a grapheme cluster snap back to the start of its bullet.
+///
+/// # Examples
+///
+/// ```
+/// use input_core::{MASK_CHAR, value_offset_to_masked};
+///
+/// let w = MASK_CHAR.len_utf8();
+/// assert_eq!(value_offset_to_masked("a€b", 0), 0);
+/// assert_eq!(value_offset_to_masked("a€b", 4), 2 * w); // before 'b'
+/// assert_eq!(value_offset_to_masked("a€b", 2), w); // mid '€' -> start of '€'
+/// assert_eq!(value_offset_to_masked("e\u{301}x", 1), 0); // before the accent
+/// ```
+pub fn value_offset_to_masked(value: &str, offset: usize) -> usize {
+    let offset = clamp_to_char_boundary(value, offset);
+    let whole_clusters = value
+        .grapheme_indices(true)
+        .take_while(|(start, cluster)| start + cluster.len() <= offset)
+        .count();
+    whole_clusters * MASK_CHAR.len_utf8()
+}
+
+/// Map a byte offset in a masked display string back to the real value.
+///
+/// Offsets inside a mask character snap back to the start of its grapheme
+/// cluster; offsets past the end clamp to `value.len()`.
+///
+/// # Examples
+///
+/// ```
+/// use input_core::{MASK_CHAR, masked_offset_to_value};
+///
+/// let w = MASK_CHAR.len_utf8();
+/// assert_eq!(masked_offset_to_value("a€b", 0), 0);
+/// assert_eq!(masked_offset_to_value("a€b", 2 * w), 4);
+/// assert_eq!(masked_offset_to_value("a€b", 100), 5);
+/// ```
+pub fn masked_offset_to_value(value: &str, masked_offset: usize) -> usize {
+    let stop = masked_offset / MASK_CHAR.len_utf8();
+    value
+        .grapheme_indices(true)
+        .nth(stop)
+        .map(|(idx, _)| idx)
+        .unwrap_or(value.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masked_display_counts_grapheme_clusters() {
+        assert_eq!(masked_display("hello"), "•••••");
+        assert_eq!(masked_display("€€"), "••");
+        // Combining accent, ZWJ family and regional-indicator flag.
+        assert_eq!(masked_display("e\u{301}"), "•");
+        assert_eq!(masked_display("👨\u{200d}👩\u{200d}👧"), "•");
+        assert_eq!(masked_display("🇳🇱🇧🇪"), "••");
+    }
+
+    #[test]
+    fn offsets_inside_a_cluster_snap_to_its_start() {
+        let value = "ae\u{301}b";
+        let w = MASK_CHAR.len_utf8();
+        assert_eq!(value_offset_to_masked(value, "ae".len()), w);
+        assert_eq!(value_offset_to_masked(value, "ae\u{301}".len()), 2 * w);
+        assert_eq!(masked_offset_to_value(value, 2 * w), "ae\u{301}".len());
+        assert_eq!(masked_offset_to_value(value, 3 * w), value.len());
+    }
+
+    #[test]
+    fn offsets_round_trip_on_every_boundary() {
+        let value = "a€b😀ce\u{301}🇳🇱";
+        for (idx, _) in value.grapheme_indices(true).chain([(value.len(), "")]) {
+            let masked = value_offset_to_masked(value, idx);
+            assert_eq!(masked_offset_to_value(value, masked), idx);
+        }
+    }
+
+    #[test]
+    fn masked_offset_inside_mask_char_snaps_to_start() {
+        let value = "ab";
+        assert_eq!(masked_offset_to_value(value, 1), 0);
+        assert_eq!(masked_offset_to_value(value, MASK_CHAR.len_utf8() + 1), 1);
+    }
+}
@@ -0,0 +1,598 @@
+//! Minimal regular-expression matcher for the HTML `pattern` attribute.
+//!
+//! The `pattern` attribute is matched against the whole value (as if wrapped in
+//! `^(?:…)$`). This module supports the subset of syntax that covers the vast
+//! majority of real-world patterns:
+//!
+//! - literals and `\`-escaped metacharacters
+//! - `.` and the classes `\d \D \w \W \s \S`
+//! - bracket classes `[a-z0-9_]`, `[^…]`, including escapes inside
+//! - groups `(…)` / `(?:…)` and alternation `|`
+//! - quantifiers `* + ? {n} {n,} {n,m}` (a trailing lazy `?` is accepted)
+//! - `^` / `$` assertions
+//!
+//! Anything else (backreferences, lookaround, named groups, …) makes
+//! [`Pattern::new`] return `None`. Per the HTML spec an invalid pattern imposes
+//! no constraint, so callers simply skip the check in that case.
+
+/// Upper bound on matcher steps before giving up.
+///
+/// The matcher simulates the pattern's NFA, so a step is one thread visited at
+/// one input position and the cost is linear in the value length. A value
+/// that still exhausts the budget is treated as matching so a pathological
+/// pattern cannot stall the UI thread or wrongly block a form.
+const STEP_BUDGET: usize = 5_000_000;
+
+/// Longest pattern source, in characters, that [`Pattern::new`] compiles.
+const MAX_PATTERN_LEN: usize = 4_096;
+
+/// Deepest group nesting that [`Pattern::new`] accepts.
+const MAX_NESTING: usize = 64;
+
+/// Largest compiled program; bounded repeats such as `(a{100}){100}` expand
+/// into one copy per iteration.
+const MAX_PROGRAM_LEN: usize = 20_000;
+
+/// A compiled `pattern` attribute.
+#[derive(Clone, Debug)]
+pub struct Pattern {
+    source: String,
+    program: Vec<Inst>,
+}
+
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Pattern {
+    /// Compile a pattern. Returns `None` for unsupported or malformed syntax,
+    /// and for patterns over the length, nesting or size limits.
+    pub fn new(source: &str) -> Option<Self> {
+        let chars: Vec<char> = source.chars().collect();
+        if chars.len() > MAX_PATTERN_LEN {
+            return None;
+        }
+        let mut parser = Parser {
+            chars,
+            pos: 0,
+            depth: 0,
+        };
+        let root = parser.parse_alternation()?;
+        if parser.pos != parser.chars.len() {
+            return None;
+        }
+
+        let mut compiler = Compiler {
+            program: Vec::new(),
+        };
+        compiler.alternation(&root)?;
+        compiler.emit(Inst::Match)?;
+        Some(Self {
+            source: source.to_string(),
+            program: compiler.program,
+        })
+    }
+
+    /// The original pattern source.
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns `true` if the whole of `value` matches the pattern.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use input_core::Pattern;
+    ///
+    /// let zip = Pattern::new(r"\d{4} ?[A-Z]{2}").unwrap();
+    /// assert!(zip.matches("1234 AB"));
+    /// assert!(!zip.matches("1234 AB!"));
+    /// ```
+    pub fn matches(&self, value: &str) -> bool {
+        let input: Vec<char> = value.chars().collect();
+        let mut matcher = Matcher {
+            program: &self.program,
+            input: &input,
+            steps: 0,
+            seen: vec![0; self.program.len()],
+            stack: Vec::new(),
+        };
+        matcher.run()
+    }
+}
+
+type Alternation = Vec<Sequence>;
+type Sequence = Vec<Term>;
+
+#[derive(Clone, Debug)]
+enum Term {
+    Char(char),
+    Any,
+    Class(CharClass),
+    Start,
+    End,
+    Group(Alternation),
+    Repeat {
+        term: Box<Term>,
+        min: usize,
+        max: Option<usize>,
+    },
+}
+
+#[derive(Clone, Debug)]
+struct CharClass {
+    negated: bool,
+    items: Vec<ClassItem>,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum ClassItem {
+    Range(char, char),
+    Digit(bool),
+    Word(bool),
+    Space(bool),
+}
+
+impl ClassItem {
+    fn contains(self, ch: char) -> bool {
+        match self {
+            ClassItem::Range(lo, hi) => lo <= ch && ch <= hi,
+            ClassItem::Digit(negated) => ch.is_ascii_digit() != negated,
+            ClassItem::Word(negated) => (ch.is_ascii_alphanumeric() || ch == '_') != negated,
+            ClassItem::Space(negated) => ch.is_whitespace() != negated,
+        }
+    }
+}
+
+impl CharClass {
+    fn contains(&self, ch: char) -> bool {
+        self.items.iter().any(|item| item.contains(ch)) != self.negated
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    /// Groups currently open around `pos`.
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let ch = self.peek()?;
+        self.pos += 1;
+        Some(ch)
+    }
+
+    fn eat(&mut self, ch: char) -> bool {
+        if self.peek() == Some(ch) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_alternation(&mut self) -> Option<Alternation> {
+        let mut alternatives = vec![self.parse_sequence()?];
+        while self.eat('|') {
+            alternatives.push(self.parse_sequence()?);
+        }
+        Some(alternatives)
+    }
+
+    fn parse_sequence(&mut self) -> Option<Sequence> {
+        let mut terms = Vec::new();
+        while let Some(ch) = self.peek() {
+            if ch == '|' || ch == ')' {
+                break;
+            }
+            let atom = self.parse_atom()?;
+            terms.push(self.parse_quantifier(atom)?);
+        }
+        Some(terms)
+    }
+
+    fn parse_atom(&mut self) -> Option<Term> {
+        match self.bump()? {
+            '.' => Some(Term::Any),
+            '^' => Some(Term::Start),
+            '$' => Some(Term::End),
+            '(' => {
+                if self.eat('?') && !self.eat(':') {
+                    // Lookaround and named groups are unsupported.
+                    return None;
+                }
+                if self.depth == MAX_NESTING {
+                    return None;
+                }
+                self.depth += 1;
+                let inner = self.parse_alternation()?;
+                self.depth -= 1;
+                self.eat(')').then_some(Term::Group(inner))
+            }
+            '[' => self.parse_class().map(Term::Class),
+            '\\' => match self.parse_escape()? {
+                ClassItem::Range(lo, _) => Some(Term::Char(lo)),
+                item => Some(Term::Class(CharClass {
+                    negated: false,
+                    items: vec![item],
+                })),
+            },
+            '*' | '+' | '?' | '{' | ')' => None,
+            ch => Some(Term::Char(ch)),
+        }
+    }
+
+    fn parse_escape(&mut self) -> Option<ClassItem> {
+        let item = match self.bump()? {
+            'd' => ClassItem::Digit(false),
+            'D' => ClassItem::Digit(true),
+            'w' => ClassItem::Word(false),
+            'W' => ClassItem::Word(true),
+            's' => ClassItem::Space(false),
+            'S' => ClassItem::Space(true),
+            'n' => ClassItem::Range('\n', '\n'),
+            'r' => ClassItem::Range('\r', '\r'),
+            't' => ClassItem::Range('\t', '\t'),
+            ch if ch.is_ascii_alphanumeric() => return None,
+            ch => ClassItem::Range(ch, ch),
+        };
+        Some(item)
+    }
+
+    fn parse_class(&mut self) -> Option<CharClass> {
+        let negated = self.eat('^');
+        let mut items = Vec::new();
+        loop {
+            let ch = self.bump()?;
+            if ch == ']' {
+                break;
+            }
+
+            let item = if ch == '\\' {
+                self.parse_escape()?
+            } else {
+                ClassItem::Range(ch, ch)
+            };
+
+            // `a-z` ranges; a trailing `-` is a literal.
+            if let ClassItem::Range(lo, _) = item
+                && self.peek() == Some('-')
+                && self
+                    .chars
+                    .get(self.pos + 1)
+                    .is_some_and(|&next| next != ']')
+            {
+                self.pos += 1;
+                let hi = match self.bump()? {
+                    '\\' => match self.parse_escape()? {
+                        ClassItem::Range(hi, _) => hi,
+                        _ => return None,
+                    },
+                    hi => hi,
+                };
+                if hi < lo {
+                    return None;
+                }
+                items.push(ClassItem::Range(lo, hi));
+                continue;
+            }
+            items.push(item);
+        }
+        Some(CharClass { negated, items })
+    }
+
+    fn parse_quantifier(&mut self, atom: Term) -> Option<Term> {
+        let (min, max) = match self.peek() {
+            Some('*') => {
+                self.pos += 1;
+                (0, None)
+            }
+            Some('+') => {
+                self.pos += 1;
+                (1, None)
+            }
+            Some('?') => {
+                self.pos += 1;
+                (0, Some(1))
+            }
+            Some('{') => {
+                self.pos += 1;
+                self.parse_braces()?
+            }
+            _ => return Some(atom),
+        };
+        // Lazy quantifiers accept the same set of full matches.
+        self.eat('?');
+
+        if matches!(atom, Term::Start | Term::End) {
+            return None;
+        }
+        Some(Term::Repeat {
+            term: Box::new(atom),
+            min,
+            max,
+        })
+    }
+
+    fn parse_braces(&mut self) -> Option<(usize, Option<usize>)> {
+        let min = self.parse_number()?;
+        let max = if self.eat(',') {
+            if self.peek() == Some('}') {
+                None
+            } else {
+                Some(self.parse_number()?)
+            }
+        } else {
+            Some(min)
+        };
+        if !self.eat('}') || max.is_some_and(|max| max < min) {
+            return None;
+        }
+        Some((min, max))
+    }
+
+    fn parse_number(&mut self) -> Option<usize> {
+        let start = self.pos;
+        while self.peek().is_some_and(|ch| ch.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return None;
+        }
+        self.chars[start..self.pos]
+            .iter()
+            .collect::<String>()
+            .parse()
+            .ok()
+    }
+}
+
+/// One instruction of the compiled NFA.
+#[derive(Clone, Debug)]
+enum Inst {
+    Char(char),
+    Any,
+    Class(CharClass),
+    Start,
+    End,
+    /// Continue at both targets.
+    Split(usize, usize),
+    Jump(usize),
+    Match,
+}
+
+struct Compiler {
+    program: Vec<Inst>,
+}
+
+impl Compiler {
+    fn emit(&mut self, inst: Inst) -> Option<usize> {
+        if self.program.len() == MAX_PROGRAM_LEN {
+            return None;
+        }
+        self.program.push(inst);
+        Some(self.program.len() - 1)
+    }
+
+    fn alternation(&mut self, alternatives: &Alternation) -> Option<()> {
+        let mut exits = Vec::new();
+        for (index, sequence) in alternatives.iter().enumerate() {
+            if index + 1 == alternatives.len() {
+                self.sequence(sequence)?;
+                break;
+            }
+            let split = self.emit(Inst::Split(0, 0))?;
+            self.sequence(sequence)?;
+            exits.push(self.emit(Inst::Jump(0))?);
+            self.program[split] = Inst::Split(split + 1, self.program.len());
+        }
+
+        let end = self.program.len();
+        for exit in exits {
+            self.program[exit] = Inst::Jump(end);
+        }
+        Some(())
+    }
+
+    fn sequence(&mut self, terms: &[Term]) -> Option<()> {
+        terms.iter().try_for_each(|term| self.term(term))
+    }
+
+    fn term(&mut self, term: &Term) -> Option<()> {
+        match term {
+            Term::Char(ch) => self.emit(Inst::Char(*ch)).map(drop),
+            Term::Any => self.emit(Inst::Any).map(drop),
+            Term::Class(class) => self.emit(Inst::Class(class.clone())).map(drop),
+            Term::Start => self.emit(Inst::Start).map(drop),
+            Term::End => self.emit(Inst::End).map(drop),
+            Term::Group(alternatives) => self.alternation(alternatives),
+            Term::Repeat { term, min, max } => {
+                for _ in 0..*min {
+                    self.term(term)?;
+                }
+                match max {
+                    None => {
+                        let split = self.emit(Inst::Split(0, 0))?;
+                        self.term(term)?;
+                        self.emit(Inst::Jump(split))?;
+                        self.program[split] = Inst::Split(split + 1, self.program.len());
+                    }
+                    Some(max) => {
+                        for _ in *min..*max {
+                            let split = self.emit(Inst::Split(0, 0))?;
+                            self.term(term)?;
+                            self.program[split] = Inst::Split(split + 1, self.program.len());
+                        }
+                    }
+                }
+                Some(())
+            }
+        }
+    }
+}
+
+/// Thompson-style NFA simulation: every live thread advances over the input
+/// in lockstep, so matching needs no backtracking and no recursion.
+struct Matcher<'a> {
+    program: &'a [Inst],
+    input: &'a [char],
+    steps: usize,
+    /// `pos + 1` of the last position each instruction was queued for.
+    seen: Vec<usize>,
+    stack: Vec<usize>,
+}
+
+impl Matcher<'_> {
+    fn run(&mut self) -> bool {
+        let mut current = Vec::new();
+        let mut next = Vec::new();
+        if !self.add_thread(&mut current, 0, 0) {
+            return true;
+        }
+
+        for pos in 0..=self.input.len() {
+            if current.is_empty() {
+                return false;
+            }
+            let ch = self.input.get(pos).copied();
+            for &pc in &current {
+                let advances = match (&self.program[pc], ch) {
+                    (Inst::Match, _) if pos == self.input.len() => return true,
+                    (Inst::Char(expected), Some(ch)) => *expected == ch,
+                    (Inst::Any, Some(ch)) => ch != '\n' && ch != '\r',
+                    (Inst::Class(class), Some(ch)) => class.contains(ch),
+                    _ => false,
+                };
+                if advances && !self.add_thread(&mut next, pc + 1, pos + 1) {
+                    return true;
+                }
+            }
+            std::mem::swap(&mut current, &mut next);
+            next.clear();
+        }
+        false
+    }
+
+    /// Queues `pc` and everything reachable from it without consuming input.
+    /// Returns `false` once the step budget is exhausted.
+    fn add_thread(&mut self, list: &mut Vec<usize>, pc: usize, pos: usize) -> bool {
+        self.stack.push(pc);
+        while let Some(pc) = self.stack.pop() {
+            if self.seen[pc] == pos + 1 {
+                continue;
+            }
+            self.seen[pc] = pos + 1;
+            self.steps += 1;
+            if self.steps > STEP_BUDGET {
+                self.stack.clear();
+                return false;
+            }
+
+            match self.program[pc] {
+                Inst::Jump(target) => self.stack.push(target),
+                Inst::Split(first, second) => {
+                    self.stack.push(second);
+                    self.stack.push(first);
+                }
+                Inst::Start => {
+                    if pos == 0 {
+                        self.stack.push(pc + 1);
+                    }
+                }
+                Inst::End => {
+                    if pos == self.input.len() {
+                        self.stack.push(pc + 1);
+                    }
+                }
+                _ => list.push(pc),
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, value: &str) -> bool {
+        Pattern::new(pattern).unwrap().matches(value)
+    }
+
+    #[test]
+    fn pattern_is_anchored_to_the_whole_value() {
+        assert!(matches("abc", "abc"));
+        assert!(!matches("abc", "abcd"));
+        assert!(!matches("abc", "xabc"));
+        assert!(matches("a|ab", "ab"));
+    }
+
+    #[test]
+    fn classes_and_quantifiers() {
+        assert!(matches("[a-z]+", "hello"));
+        assert!(!matches("[a-z]+", "Hello"));
+        assert!(matches("[^0-9]*", "abc"));
+        assert!(matches(r"\d{3}-\d{4}", "555-1234"));
+        assert!(!matches(r"\d{3}-\d{4}", "55-1234"));
+        assert!(matches(r"\w+@\w+\.com", "me@example.com"));
+        assert!(matches("a{2,}", "aaaa"));
+        assert!(!matches("a{2,3}", "aaaa"));
+        assert!(matches("[-a]+", "-a-"));
+    }
+
+    #[test]
+    fn groups_and_alternation() {
+        assert!(matches("(ab)+c", "ababc"));
+        assert!(matches("(?:red|green|blue)", "green"));
+        assert!(!matches("(?:red|green|blue)", "greenish"));
+        assert!(matches("(a*)*b", "aaab"));
+    }
+
+    #[test]
+    fn unsupported_syntax_is_rejected() {
+        assert!(Pattern::new("(?=a)").is_none());
+        assert!(Pattern::new(r"(a)\1").is_none());
+        assert!(Pattern::new("a{3,1}").is_none());
+        assert!(Pattern::new("[z-a]").is_none());
+        assert!(Pattern::new("(abc").is_none());
+        assert!(Pattern::new("*a").is_none());
+    }
+
+    #[test]
+    fn deep_or_long_patterns_are_invalid_instead_of_overflowing() {
+        let deep = format!("{}a{}", "(".repeat(50_000), ")".repeat(50_000));
+        assert!(Pattern::new(&deep).is_none());
+
+        let nested = format!("{}a{}", "(".repeat(MAX_NESTING), ")".repeat(MAX_NESTING));
+        assert!(matches(&nested, "a"));
+        let too_nested = format!("({nested})");
+        assert!(Pattern::new(&too_nested).is_none());
+
+        assert!(Pattern::new(&"a".repeat(MAX_PATTERN_LEN + 1)).is_none());
+        assert!(Pattern::new("(a{1000}){1000}").is_none());
+    }
+
+    #[test]
+    fn long_values_match_without_recursion() {
+        let long = "a".repeat(100_000);
+        assert!(matches("[a-z]+", &long));
+        assert!(!matches("[a-z]+", &format!("{long}1")));
+        assert!(matches("(a|b)*", &long));
+        assert!(matches("(?:a*)*b", &format!("{long}b")));
+    }
+
+    #[test]
+    fn unicode_values_match_per_scalar_value() {
+        assert!(matches("..", "€€"));
+        assert!(!matches(".", "€€"));
+    }
+}
@@ -0,0 +1,94 @@
+//! Plain-data snapshots of [`InputValueStore`](crate::InputValueStore) contents.
+//!
+//! Snapshots capture everything needed to bring inputs back to the state the
+//! user left them in: value, checked/masked flags, caret, selection anchor and
+//! scroll offsets. Entries are keyed by [`InputId`] and sorted by it, so two
+//! stores with the same contents always produce equal snapshots.
+//!
+//! With the `serde` feature enabled, snapshots implement `Serialize` and
+//! `Deserialize` so they can be persisted (e.g. for session restore).
+//! Deserialized entries are sorted by id, and the last entry wins when an id
+//! repeats, so lookups on persisted data stay valid.
+
+use crate::id::InputId;
+use std::collections::BTreeMap;
+
+/// Snapshot of a single input's state.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputSnapshot {
+    /// The current text value.
+    pub value: String,
+    /// For checkbox/radio inputs: whether the control is checked.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub checked: bool,
+    /// Whether the value is displayed masked.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub masked: bool,
+    /// Caret byte index into `value`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub caret: usize,
+    /// Selection anchor byte index into `value`, if a selection is active.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub selection_anchor: Option<usize>,
+    /// Horizontal scroll offset in px.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub scroll_x: f32,
+    /// Vertical scroll offset in px.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub scroll_y: f32,
+}
+
+/// Snapshot of a whole [`InputValueStore`](crate::InputValueStore).
+///
+/// Entries are sorted by [`InputId`] to keep the representation deterministic.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "UnsortedStoreSnapshot"))]
+pub struct InputStoreSnapshot {
+    pub entries: Vec<(InputId, InputSnapshot)>,
+}
+
+/// Wire form of [`InputStoreSnapshot`] before its entries are sorted.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UnsortedStoreSnapshot {
+    entries: Vec<(InputId, InputSnapshot)>,
+}
+
+#[cfg(feature = "serde")]
+impl From<UnsortedStoreSnapshot> for InputStoreSnapshot {
+    fn from(snapshot: UnsortedStoreSnapshot) -> Self {
+        Self::from_entries(snapshot.entries)
+    }
+}
+
+impl InputStoreSnapshot {
+    /// Builds a snapshot from entries in any order.
+    ///
+    /// Entries are sorted by id; when an id repeats, the last entry wins.
+    pub fn from_entries(entries: impl IntoIterator<Item = (InputId, InputSnapshot)>) -> Self {
+        let entries: BTreeMap<_, _> = entries.into_iter().collect();
+        Self {
+            entries: entries.into_iter().collect(),
+        }
+    }
+
+    /// Returns the snapshot entry for `id`, if present.
+    pub fn get(&self, id: InputId) -> Option<&InputSnapshot> {
+        self.entries
+            .binary_search_by_key(&id, |(entry_id, _)| *entry_id)
+            .ok()
+            .map(|idx| &self.entries[idx].1)
+    }
+
+    /// Returns `true` if the snapshot has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the number of entries in the snapshot.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
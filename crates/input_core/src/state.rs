@@ -2,6 +2,8 @@
 //!
 //! This module contains the per-input state that is stored in the InputValueStore.
 
+use crate::validation::{InputConstraints, Validity};
+
 /// Internal state for a single input element.
 ///
 /// This is not exposed publicly; it is managed by [`InputValueStore`](crate::InputValueStore).
@@ -17,6 +19,9 @@ pub(crate) struct InputState {
     /// For checkbox/radio inputs: whether the control is checked.
     pub checked: bool,
 
+    /// Whether the value is displayed masked (e.g. `<input type=password>`).
+    pub masked: bool,
+
     /// Caret position as a byte index into `value` (always on a UTF-8 char boundary).
     pub caret: usize,
 
@@ -30,6 +35,12 @@ pub(crate) struct InputState {
 
     /// Vertical scroll offset in px for multi-line text controls.
     pub scroll_y: f32,
+
+    /// Constraint validation rules, if any were seeded for this input.
+    pub constraints: Option<Box<InputConstraints>>,
+
+    /// Cached validation result, recomputed on value/checked changes.
+    pub validity: Validity,
 }
 
 impl Default for InputState {
@@ -38,10 +49,13 @@ impl Default for InputState {
             value: String::new(),
             value_rev: 0,
             checked: false,
+            masked: false,
             caret: 0,
             selection_anchor: None,
             scroll_x: 0.0,
             scroll_y: 0.0,
+            constraints: None,
+            validity: Validity::Valid,
         }
     }
 }
@@ -1,9 +1,10 @@
 use super::InputValueStore;
-use super::state_utils::selection_range;
+use super::state_utils::{revalidate, selection_range};
 use crate::id::InputId;
 use crate::selection::SelectionRange;
 use crate::state::InputState;
 use crate::text::clamp_to_char_boundary;
+use crate::validation::Validity;
 
 impl InputValueStore {
     /// Returns `true` if an entry exists for this input.
@@ -62,6 +63,9 @@ impl InputValueStore {
         self.with_state_mut(id, |state| {
             let changed = state.checked != checked;
             state.checked = checked;
+            if changed {
+                revalidate(state);
+            }
             changed
         })
     }
@@ -74,6 +78,9 @@ impl InputValueStore {
             let new_value = !state.checked;
             let changed = state.checked != new_value;
             state.checked = new_value;
+            if changed {
+                revalidate(state);
+            }
             changed
         })
     }
@@ -93,24 +100,28 @@ impl InputValueStore {
     /// This resets the caret to the end and clears any selection.
     pub fn set(&mut self, id: InputId, value: String) {
         let caret = clamp_to_char_boundary(&value, value.len());
-        let checked = self.values.get(&id).is_some_and(|state| state.checked);
-        let value_rev = self
-            .values
-            .get(&id)
+        let previous = self.values.remove(&id);
+        let checked = previous.as_ref().is_some_and(|state| state.checked);
+        let masked = previous.as_ref().is_some_and(|state| state.masked);
+        let value_rev = previous
+            .as_ref()
             .map(|state| state.value_rev.wrapping_add(1))
             .unwrap_or(0);
-        self.values.insert(
-            id,
-            InputState {
-                value,
-                value_rev,
-                checked,
-                caret,
-                selection_anchor: None,
-                scroll_x: 0.0,
-                scroll_y: 0.0,
-            },
-        );
+        let constraints = previous.and_then(|state| state.constraints);
+        let mut state = InputState {
+            value,
+            value_rev,
+            checked,
+            masked,
+            caret,
+            selection_anchor: None,
+            scroll_x: 0.0,
+            scroll_y: 0.0,
+            constraints,
+            validity: Validity::Valid,
+        };
+        revalidate(&mut state);
+        self.values.insert(id, state);
     }
 
     /// Ensure an entry exists; if missing, inserts the provided initial value.
@@ -120,10 +131,13 @@ impl InputValueStore {
             value: initial,
             value_rev: 0,
             checked: false,
+            masked: false,
             caret,
             selection_anchor: None,
             scroll_x: 0.0,
             scroll_y: 0.0,
+            constraints: None,
+            validity: Validity::Valid,
         });
     }
 }
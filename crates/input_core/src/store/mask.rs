@@ -0,0 +1,85 @@
+use super::InputValueStore;
+use super::state_utils::selection_range;
+use crate::id::InputId;
+use crate::mask::{DisplayState, masked_display, masked_offset_to_value, value_offset_to_masked};
+use crate::selection::SelectionRange;
+use crate::text::clamp_to_char_boundary;
+use std::borrow::Cow;
+
+impl InputValueStore {
+    /// Returns `true` if this input's value is displayed masked.
+    pub fn is_masked(&self, id: InputId) -> bool {
+        self.values.get(&id).is_some_and(|state| state.masked)
+    }
+
+    /// Enable or disable masked display for an input.
+    ///
+    /// The stored value is unaffected; only the display string changes.
+    /// Returns `true` if the flag actually changed.
+    pub fn set_masked(&mut self, id: InputId, masked: bool) -> bool {
+        self.with_state_mut(id, |state| {
+            let changed = state.masked != masked;
+            state.masked = masked;
+            changed
+        })
+    }
+
+    /// Returns the string that should be painted for this input, if any.
+    ///
+    /// Masked inputs yield one mask character per grapheme cluster; unmasked
+    /// inputs borrow the stored value.
+    pub fn display_value(&self, id: InputId) -> Option<Cow<'_, str>> {
+        self.values.get(&id).map(|state| {
+            if state.masked {
+                Cow::Owned(masked_display(&state.value))
+            } else {
+                Cow::Borrowed(state.value.as_str())
+            }
+        })
+    }
+
+    /// Get the full state tuple in display coordinates.
+    ///
+    /// Like [`get_state`](Self::get_state), but the string is the display string
+    /// and the caret/selection are byte offsets into it. For unmasked inputs
+    /// this is identical to `get_state`.
+    pub fn get_display_state(&self, id: InputId) -> Option<DisplayState<'_>> {
+        self.values.get(&id).map(|state| {
+            let selection = selection_range(&state.value, state.selection_anchor, state.caret);
+            if !state.masked {
+                return (
+                    Cow::Borrowed(state.value.as_str()),
+                    state.caret,
+                    selection,
+                    state.scroll_x,
+                    state.scroll_y,
+                );
+            }
+
+            let value = state.value.as_str();
+            let selection = selection.map(|sel| SelectionRange {
+                start: value_offset_to_masked(value, sel.start),
+                end: value_offset_to_masked(value, sel.end),
+            });
+            (
+                Cow::Owned(masked_display(value)),
+                value_offset_to_masked(value, state.caret),
+                selection,
+                state.scroll_x,
+                state.scroll_y,
+            )
+        })
+    }
+
+    /// Map a byte offset in the display string back to the stored value.
+    ///
+    /// Use this to translate hit-test results against the display string
+    /// into a caret position for [`set_caret`](Self::set_caret).
+    pub fn display_offset_to_value(&self, id: InputId, display_offset: usize) -> usize {
+        match self.values.get(&id) {
+            Some(state) if state.masked => masked_offset_to_value(&state.value, display_offset),
+            Some(state) => clamp_to_char_boundary(&state.value, display_offset),
+            None => 0,
+        }
+    }
+}
@@ -10,9 +10,12 @@ use std::collections::HashMap;
 
 mod access;
 mod caret;
+mod mask;
 mod scroll;
+mod snapshot;
 mod state_utils;
 mod text_edit;
+mod validation;
 
 #[cfg(test)]
 mod tests;
@@ -28,6 +31,8 @@ use state_utils::{clamp_state, clear_selection};
 /// - Text selection
 /// - Scroll offsets for overflow handling
 /// - Checkbox/radio checked state
+/// - Masked (password-style) display
+/// - Constraint validation state
 ///
 /// # Thread Safety
 ///
@@ -0,0 +1,74 @@
+use super::InputValueStore;
+use super::state_utils::{clamp_state, normalize_selection_anchor, revalidate};
+use crate::snapshot::{InputSnapshot, InputStoreSnapshot};
+use crate::state::InputState;
+use crate::validation::Validity;
+
+impl InputValueStore {
+    /// Capture the full store contents as a deterministic snapshot.
+    ///
+    /// Revision counters are not captured; they are only meaningful to caches
+    /// that live alongside this store instance.
+    pub fn snapshot(&self) -> InputStoreSnapshot {
+        let mut entries: Vec<_> = self
+            .values
+            .iter()
+            .map(|(id, state)| {
+                (
+                    *id,
+                    InputSnapshot {
+                        value: state.value.clone(),
+                        checked: state.checked,
+                        masked: state.masked,
+                        caret: state.caret,
+                        selection_anchor: state.selection_anchor,
+                        scroll_x: state.scroll_x,
+                        scroll_y: state.scroll_y,
+                    },
+                )
+            })
+            .collect();
+        entries.sort_unstable_by_key(|(id, _)| *id);
+        InputStoreSnapshot { entries }
+    }
+
+    /// Restore entries from a snapshot.
+    ///
+    /// Each snapshot entry overwrites the matching input (entries for other
+    /// inputs are left alone). Caret and selection are clamped to valid
+    /// boundaries, and the value revision is bumped for inputs that already
+    /// existed so dependent caches are invalidated. Seeded constraints are
+    /// kept and validity is recomputed against the restored value.
+    pub fn restore(&mut self, snapshot: &InputStoreSnapshot) {
+        for (id, entry) in &snapshot.entries {
+            let previous = self.values.remove(id);
+            let value_rev = previous
+                .as_ref()
+                .map(|state| state.value_rev.wrapping_add(1))
+                .unwrap_or(0);
+            let mut state = InputState {
+                value: entry.value.clone(),
+                value_rev,
+                checked: entry.checked,
+                masked: entry.masked,
+                caret: entry.caret,
+                selection_anchor: entry.selection_anchor,
+                scroll_x: entry.scroll_x,
+                scroll_y: entry.scroll_y,
+                constraints: previous.and_then(|state| state.constraints),
+                validity: Validity::Valid,
+            };
+            clamp_state(&mut state);
+            normalize_selection_anchor(&mut state);
+            revalidate(&mut state);
+            self.values.insert(*id, state);
+        }
+    }
+
+    /// Build a new store from a snapshot.
+    pub fn from_snapshot(snapshot: &InputStoreSnapshot) -> Self {
+        let mut store = Self::new();
+        store.restore(snapshot);
+        store
+    }
+}
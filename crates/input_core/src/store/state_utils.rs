@@ -1,6 +1,7 @@
 use crate::selection::SelectionRange;
 use crate::state::InputState;
 use crate::text::clamp_to_char_boundary;
+use crate::validation::Validity;
 
 pub(super) fn selection_range(
     value: &str,
@@ -78,4 +79,12 @@ pub(super) fn clear_selection(state: &mut InputState) {
 
 pub(super) fn mark_text_dirty(state: &mut InputState) {
     state.value_rev = state.value_rev.wrapping_add(1);
+    revalidate(state);
+}
+
+pub(super) fn revalidate(state: &mut InputState) {
+    state.validity = match state.constraints.as_deref() {
+        Some(constraints) => constraints.validate(&state.value, state.checked),
+        None => Validity::Valid,
+    };
 }
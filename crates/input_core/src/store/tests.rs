@@ -1,5 +1,8 @@
 use super::InputValueStore;
-use crate::{InputId, SelectionRange, caret_from_x};
+use crate::{
+    InputConstraints, InputId, InputSnapshot, InputStoreSnapshot, InvalidReason, Pattern,
+    SelectionRange, caret_from_x,
+};
 
 #[test]
 fn insert_text_keeps_caret_on_char_boundary() {
@@ -49,6 +52,31 @@ fn invalid_caret_is_clamped_before_insert() {
     assert!(value.is_char_boundary(caret));
 }
 
+#[test]
+fn cut_and_copy_take_the_selection_except_from_masked_inputs() {
+    let mut store = InputValueStore::new();
+    let id = InputId::from_raw(1);
+
+    store.set(id, "hello world".to_string());
+    store.focus(id);
+    assert_eq!(store.copy_selection(id), None);
+
+    store.set_caret(id, 6, false);
+    store.set_caret(id, 11, true);
+    assert_eq!(store.copy_selection(id).as_deref(), Some("world"));
+    assert_eq!(store.get(id), Some("hello world"));
+
+    assert_eq!(store.cut_selection(id).as_deref(), Some("world"));
+    assert_eq!(store.get(id), Some("hello "));
+    assert_eq!(store.caret(id), Some(6));
+
+    store.select_all(id);
+    store.set_masked(id, true);
+    assert_eq!(store.copy_selection(id), None);
+    assert_eq!(store.cut_selection(id), None);
+    assert_eq!(store.get(id), Some("hello "));
+}
+
 #[test]
 fn move_caret_left_right_moves_by_unicode_scalar_value() {
     let mut store = InputValueStore::new();
@@ -215,3 +243,184 @@ fn checked_mutators_return_changed() {
     assert!(store.toggle_checked(id));
     assert!(!store.is_checked(id));
 }
+
+#[test]
+fn masked_display_state_maps_caret_and_selection() {
+    let mut store = InputValueStore::new();
+    let id = InputId::from_raw(1);
+
+    store.set(id, "a€b".to_string());
+    assert!(store.set_masked(id, true));
+    assert!(!store.set_masked(id, true));
+    store.focus(id);
+    store.move_caret_left(id, true);
+
+    let (display, caret, selection, _scroll_x, _scroll_y) = store.get_display_state(id).unwrap();
+    let w = crate::MASK_CHAR.len_utf8();
+    assert_eq!(display, "•••");
+    assert_eq!(caret, 2 * w);
+    assert_eq!(selection, Some(SelectionRange::new(2 * w, 3 * w)));
+
+    // The real value and offsets are untouched.
+    assert_eq!(store.get(id), Some("a€b"));
+    assert_eq!(store.caret(id), Some("a€".len()));
+    assert_eq!(store.display_offset_to_value(id, w), "a".len());
+
+    // A combining accent joins its base under one bullet; the caret between
+    // them shows before that bullet.
+    store.set(id, "ae\u{301}".to_string());
+    store.move_caret_left(id, false);
+    let (display, caret, ..) = store.get_display_state(id).unwrap();
+    assert_eq!(display, "••");
+    assert_eq!(caret, w);
+}
+
+#[test]
+fn set_preserves_masked_flag() {
+    let mut store = InputValueStore::new();
+    let id = InputId::from_raw(1);
+
+    store.set_masked(id, true);
+    store.set(id, "secret".to_string());
+
+    assert!(store.is_masked(id));
+    assert_eq!(store.display_value(id).as_deref(), Some("••••••"));
+}
+
+#[test]
+fn snapshot_round_trips_and_is_sorted_by_id() {
+    let mut store = InputValueStore::new();
+    let a = InputId::from_raw(7);
+    let b = InputId::from_raw(2);
+
+    store.set(a, "hello".to_string());
+    store.focus(a);
+    store.move_caret_left(a, true);
+    store.set_checked(b, true);
+
+    let snapshot = store.snapshot();
+    let ids: Vec<_> = snapshot.entries.iter().map(|(id, _)| *id).collect();
+    assert_eq!(ids, vec![b, a]);
+    assert_eq!(snapshot.get(a).unwrap().selection_anchor, Some(5));
+
+    let restored = InputValueStore::from_snapshot(&snapshot);
+    assert_eq!(restored.snapshot(), snapshot);
+    assert_eq!(
+        restored.get_state(a).unwrap().2,
+        Some(SelectionRange::new(4, 5))
+    );
+    assert!(restored.is_checked(b));
+}
+
+#[test]
+fn restore_clamps_offsets_and_bumps_revision() {
+    let mut store = InputValueStore::new();
+    let id = InputId::from_raw(1);
+    store.set(id, "old".to_string());
+    let rev = store.value_revision(id);
+
+    let snapshot = InputStoreSnapshot {
+        entries: vec![(
+            id,
+            InputSnapshot {
+                value: "€".to_string(),
+                caret: 2,
+                selection_anchor: Some(99),
+                ..InputSnapshot::default()
+            },
+        )],
+    };
+    store.restore(&snapshot);
+
+    assert_eq!(store.get(id), Some("€"));
+    assert_eq!(store.caret(id), Some(0));
+    assert_eq!(
+        store.get_state(id).unwrap().2,
+        Some(SelectionRange::new(0, 3))
+    );
+    assert_ne!(store.value_revision(id), rev);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn snapshot_serde_round_trip() {
+    let mut store = InputValueStore::new();
+    let id = InputId::from_raw(3);
+    store.set(id, "pw".to_string());
+    store.set_masked(id, true);
+
+    let snapshot = store.snapshot();
+    let json = serde_json::to_string(&snapshot).unwrap();
+    let back: InputStoreSnapshot = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, snapshot);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn deserialized_snapshots_are_sorted_and_deduplicated() {
+    let json = r#"{"entries": [
+        [9, {"value": "nine"}],
+        [2, {"value": "stale"}],
+        [5, {"value": "five"}],
+        [2, {"value": "two"}]
+    ]}"#;
+    let snapshot: InputStoreSnapshot = serde_json::from_str(json).unwrap();
+
+    let ids: Vec<_> = snapshot.entries.iter().map(|(id, _)| id.as_raw()).collect();
+    assert_eq!(ids, [2, 5, 9]);
+    for (raw, value) in [(2, "two"), (5, "five"), (9, "nine")] {
+        assert_eq!(snapshot.get(InputId::from_raw(raw)).unwrap().value, value);
+    }
+}
+
+#[test]
+fn validity_is_recomputed_on_edits() {
+    let mut store = InputValueStore::new();
+    let id = InputId::from_raw(1);
+
+    store.ensure_initial(id, String::new());
+    let validity = store.set_constraints(
+        id,
+        InputConstraints {
+            required: true,
+            pattern: Pattern::new("[a-z]+"),
+            ..InputConstraints::default()
+        },
+    );
+    assert_eq!(validity.reason(), Some(InvalidReason::ValueMissing));
+
+    store.focus(id);
+    store.insert_text(id, "ab1");
+    assert_eq!(
+        store.validity(id).reason(),
+        Some(InvalidReason::PatternMismatch)
+    );
+
+    store.backspace(id);
+    assert!(store.is_valid(id));
+
+    store.set(id, String::new());
+    assert_eq!(
+        store.validity(id).reason(),
+        Some(InvalidReason::ValueMissing)
+    );
+}
+
+#[test]
+fn required_checkbox_tracks_checked_state() {
+    let mut store = InputValueStore::new();
+    let id = InputId::from_raw(1);
+
+    store.ensure_initial_checked(id, false);
+    store.set_constraints(
+        id,
+        InputConstraints {
+            required: true,
+            ..InputConstraints::default()
+        },
+    );
+    assert!(!store.is_valid(id));
+
+    store.toggle_checked(id);
+    assert!(store.is_valid(id));
+}
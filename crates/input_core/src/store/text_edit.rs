@@ -1,5 +1,5 @@
 use super::InputValueStore;
-use super::state_utils::{clamp_state, delete_selection_if_any, mark_text_dirty};
+use super::state_utils::{clamp_state, delete_selection_if_any, mark_text_dirty, selection_range};
 use crate::id::InputId;
 use crate::text::{
     clamp_to_char_boundary, filter_single_line, next_cursor_boundary, normalize_newlines,
@@ -90,4 +90,22 @@ impl InputValueStore {
             mark_text_dirty(state);
         });
     }
+
+    /// Selected text to put on the clipboard, if any.
+    ///
+    /// Masked inputs never give their value away.
+    pub fn copy_selection(&self, id: InputId) -> Option<String> {
+        let state = self.values.get(&id).filter(|state| !state.masked)?;
+        let selection = selection_range(&state.value, state.selection_anchor, state.caret)?;
+        Some(selection.slice(&state.value).to_string())
+    }
+
+    /// Remove the selection and return it for the clipboard.
+    ///
+    /// Masked inputs are left untouched, like for [`copy_selection`](Self::copy_selection).
+    pub fn cut_selection(&mut self, id: InputId) -> Option<String> {
+        let text = self.copy_selection(id)?;
+        self.with_existing_state_mut(id, delete_selection_if_any);
+        Some(text)
+    }
 }
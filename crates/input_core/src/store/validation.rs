@@ -0,0 +1,39 @@
+use super::InputValueStore;
+use super::state_utils::revalidate;
+use crate::id::InputId;
+use crate::validation::{InputConstraints, Validity};
+
+impl InputValueStore {
+    /// Set the validation constraints for an input and recompute its validity.
+    ///
+    /// Passing empty constraints removes them. Returns the new validity.
+    pub fn set_constraints(&mut self, id: InputId, constraints: InputConstraints) -> Validity {
+        self.with_state_mut(id, |state| {
+            state.constraints = (!constraints.is_empty()).then(|| Box::new(constraints));
+            revalidate(state);
+            state.validity
+        })
+    }
+
+    /// Returns the validation constraints for an input, if any.
+    pub fn constraints(&self, id: InputId) -> Option<&InputConstraints> {
+        self.values
+            .get(&id)
+            .and_then(|state| state.constraints.as_deref())
+    }
+
+    /// Returns the cached validity for an input.
+    ///
+    /// Inputs without an entry or without constraints are valid.
+    pub fn validity(&self, id: InputId) -> Validity {
+        self.values
+            .get(&id)
+            .map(|state| state.validity)
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` if the input satisfies its constraints.
+    pub fn is_valid(&self, id: InputId) -> bool {
+        self.validity(id).is_valid()
+    }
+}
@@ -12,7 +12,9 @@
 //! - Trait is object-safe where practical and avoids UI or layout dependencies
 
 use crate::id::InputId;
+use crate::mask::DisplayState;
 use crate::selection::SelectionRange;
+use crate::validation::Validity;
 
 /// Trait defining the input store interface.
 ///
@@ -21,8 +23,10 @@ use crate::selection::SelectionRange;
 /// - Text editing (insertion, deletion)
 /// - Caret and selection manipulation
 /// - Read-only state access for rendering/metrics
+/// - Masked display mapping for password-style inputs
 /// - Scroll position management for caret visibility
 /// - Checkbox/radio state management
+/// - Cached constraint validity for blocking form submission
 ///
 /// Layout, measurement, and hit-testing should be handled by the integration
 /// layer. This trait deals only in byte indices, selections, and scroll values.
@@ -90,6 +94,20 @@ pub trait InputStore {
     /// If there is a selection, deletes the selection instead.
     fn delete(&mut self, id: InputId);
 
+    // =========================================================================
+    // Clipboard
+    // =========================================================================
+
+    /// Selected text to put on the clipboard, if any. Masked inputs return `None`.
+    fn copy_selection(&self, id: InputId) -> Option<String>;
+
+    /// Remove the selection and return it for the clipboard. Masked inputs are
+    /// left untouched and return `None`.
+    ///
+    /// Pasting is plain insertion: [`insert_text`](Self::insert_text) or
+    /// [`insert_text_multiline`](Self::insert_text_multiline).
+    fn cut_selection(&mut self, id: InputId) -> Option<String>;
+
     // =========================================================================
     // Caret Movement
     // =========================================================================
@@ -143,10 +161,26 @@ pub trait InputStore {
     /// Increments on any text change. Useful for cache invalidation.
     fn value_revision(&self, id: InputId) -> u64;
 
+    // =========================================================================
+    // Masked Display
+    // =========================================================================
+
+    /// Get the full state tuple in display coordinates.
+    ///
+    /// For masked inputs the string is the mask and caret/selection are byte
+    /// offsets into it; otherwise this matches [`get_state`](Self::get_state).
+    fn get_display_state(&self, id: InputId) -> Option<DisplayState<'_>>;
+
+    /// Map a byte offset in the display string back to the stored value.
+    fn display_offset_to_value(&self, id: InputId, display_offset: usize) -> usize;
+
     // =========================================================================
     // Checkbox/Radio
     // =========================================================================
 
+    /// Returns `true` if this checkbox/radio input is checked.
+    fn is_checked(&self, id: InputId) -> bool;
+
     /// Toggle the checked state for a checkbox/radio input.
     ///
     /// Returns `true` if the state changed.
@@ -157,6 +191,15 @@ pub trait InputStore {
     /// Returns `true` if the state changed.
     fn set_checked(&mut self, id: InputId, checked: bool) -> bool;
 
+    // =========================================================================
+    // Validation
+    // =========================================================================
+
+    /// Returns the cached constraint validation result for an input.
+    ///
+    /// Inputs without constraints are valid.
+    fn validity(&self, id: InputId) -> Validity;
+
     // =========================================================================
     // Scroll Management
     // =========================================================================
@@ -226,6 +269,16 @@ impl InputStore for crate::store::InputValueStore {
         crate::store::InputValueStore::delete(self, id)
     }
 
+    #[inline]
+    fn copy_selection(&self, id: InputId) -> Option<String> {
+        crate::store::InputValueStore::copy_selection(self, id)
+    }
+
+    #[inline]
+    fn cut_selection(&mut self, id: InputId) -> Option<String> {
+        crate::store::InputValueStore::cut_selection(self, id)
+    }
+
     #[inline]
     fn move_caret_left(&mut self, id: InputId, selecting: bool) {
         crate::store::InputValueStore::move_caret_left(self, id, selecting)
@@ -271,6 +324,21 @@ impl InputStore for crate::store::InputValueStore {
         crate::store::InputValueStore::value_revision(self, id)
     }
 
+    #[inline]
+    fn get_display_state(&self, id: InputId) -> Option<DisplayState<'_>> {
+        crate::store::InputValueStore::get_display_state(self, id)
+    }
+
+    #[inline]
+    fn display_offset_to_value(&self, id: InputId, display_offset: usize) -> usize {
+        crate::store::InputValueStore::display_offset_to_value(self, id, display_offset)
+    }
+
+    #[inline]
+    fn is_checked(&self, id: InputId) -> bool {
+        crate::store::InputValueStore::is_checked(self, id)
+    }
+
     #[inline]
     fn toggle_checked(&mut self, id: InputId) -> bool {
         crate::store::InputValueStore::toggle_checked(self, id)
@@ -281,6 +349,11 @@ impl InputStore for crate::store::InputValueStore {
         crate::store::InputValueStore::set_checked(self, id, checked)
     }
 
+    #[inline]
+    fn validity(&self, id: InputId) -> Validity {
+        crate::store::InputValueStore::validity(self, id)
+    }
+
     #[inline]
     fn update_scroll_for_caret(
         &mut self,
@@ -0,0 +1,213 @@
+//! Constraint validation for input values.
+//!
+//! Constraints are seeded by the integration layer (typically from DOM
+//! attributes) and the store recomputes [`Validity`] whenever the value or
+//! checked state changes. Consumers read the cached result to style invalid
+//! controls or block form submission.
+
+use crate::pattern::Pattern;
+
+/// Constraints that apply to a single input.
+///
+/// All constraints except `required` are skipped for empty values, matching
+/// HTML constraint validation.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InputConstraints {
+    /// The control must have a non-empty value (or be checked, for
+    /// checkbox-like controls).
+    pub required: bool,
+    /// The whole value must match this pattern.
+    pub pattern: Option<Pattern>,
+    /// Minimum value length in UTF-16 code units.
+    pub min_length: Option<usize>,
+    /// Maximum value length in UTF-16 code units.
+    pub max_length: Option<usize>,
+    /// Minimum numeric value (for numeric controls).
+    pub min: Option<f64>,
+    /// Maximum numeric value (for numeric controls).
+    pub max: Option<f64>,
+}
+
+impl InputConstraints {
+    /// Returns `true` if no constraint is set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Validate a value (and checked state) against these constraints.
+    ///
+    /// When several constraints fail, the first one in [`InvalidReason`]
+    /// declaration order is reported.
+    pub fn validate(&self, value: &str, checked: bool) -> Validity {
+        if value.is_empty() {
+            return if self.required && !checked {
+                Validity::Invalid(InvalidReason::ValueMissing)
+            } else {
+                Validity::Valid
+            };
+        }
+
+        if self.min.is_some() || self.max.is_some() {
+            let Ok(number) = value.trim().parse::<f64>() else {
+                return Validity::Invalid(InvalidReason::BadInput);
+            };
+            if !number.is_finite() {
+                return Validity::Invalid(InvalidReason::BadInput);
+            }
+            if self.min.is_some_and(|min| number < min) {
+                return Validity::Invalid(InvalidReason::RangeUnderflow);
+            }
+            if self.max.is_some_and(|max| number > max) {
+                return Validity::Invalid(InvalidReason::RangeOverflow);
+            }
+        }
+
+        if self.min_length.is_some() || self.max_length.is_some() {
+            let len = value.encode_utf16().count();
+            if self.min_length.is_some_and(|min| len < min) {
+                return Validity::Invalid(InvalidReason::TooShort);
+            }
+            if self.max_length.is_some_and(|max| len > max) {
+                return Validity::Invalid(InvalidReason::TooLong);
+            }
+        }
+
+        if self
+            .pattern
+            .as_ref()
+            .is_some_and(|pattern| !pattern.matches(value))
+        {
+            return Validity::Invalid(InvalidReason::PatternMismatch);
+        }
+
+        Validity::Valid
+    }
+}
+
+/// Why an input failed constraint validation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvalidReason {
+    /// `required` is set but the value is empty / the control is unchecked.
+    ValueMissing,
+    /// The value could not be interpreted for a numeric constraint.
+    BadInput,
+    /// The numeric value is below `min`.
+    RangeUnderflow,
+    /// The numeric value is above `max`.
+    RangeOverflow,
+    /// The value is shorter than `min_length`.
+    TooShort,
+    /// The value is longer than `max_length`.
+    TooLong,
+    /// The value does not match `pattern`.
+    PatternMismatch,
+}
+
+/// Cached constraint validation result for an input.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Validity {
+    #[default]
+    Valid,
+    Invalid(InvalidReason),
+}
+
+impl Validity {
+    /// Returns `true` if the input satisfies all its constraints.
+    #[inline]
+    pub fn is_valid(self) -> bool {
+        matches!(self, Validity::Valid)
+    }
+
+    /// Returns the failure reason, if invalid.
+    #[inline]
+    pub fn reason(self) -> Option<InvalidReason> {
+        match self {
+            Validity::Valid => None,
+            Validity::Invalid(reason) => Some(reason),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn required_only_applies_to_empty_unchecked_values() {
+        let constraints = InputConstraints {
+            required: true,
+            ..InputConstraints::default()
+        };
+        assert_eq!(
+            constraints.validate("", false),
+            Validity::Invalid(InvalidReason::ValueMissing)
+        );
+        assert!(constraints.validate("", true).is_valid());
+        assert!(constraints.validate("x", false).is_valid());
+    }
+
+    #[test]
+    fn empty_values_skip_non_required_constraints() {
+        let constraints = InputConstraints {
+            pattern: Pattern::new("[0-9]+"),
+            min_length: Some(3),
+            min: Some(1.0),
+            ..InputConstraints::default()
+        };
+        assert!(constraints.validate("", false).is_valid());
+    }
+
+    #[test]
+    fn length_counts_utf16_code_units() {
+        let constraints = InputConstraints {
+            min_length: Some(2),
+            max_length: Some(3),
+            ..InputConstraints::default()
+        };
+        assert_eq!(
+            constraints.validate("a", false).reason(),
+            Some(InvalidReason::TooShort)
+        );
+        // U+1F600 is two UTF-16 code units.
+        assert!(constraints.validate("😀", false).is_valid());
+        assert_eq!(
+            constraints.validate("abcd", false).reason(),
+            Some(InvalidReason::TooLong)
+        );
+    }
+
+    #[test]
+    fn numeric_bounds() {
+        let constraints = InputConstraints {
+            min: Some(1.0),
+            max: Some(10.0),
+            ..InputConstraints::default()
+        };
+        assert!(constraints.validate("5", false).is_valid());
+        assert_eq!(
+            constraints.validate("0.5", false).reason(),
+            Some(InvalidReason::RangeUnderflow)
+        );
+        assert_eq!(
+            constraints.validate("11", false).reason(),
+            Some(InvalidReason::RangeOverflow)
+        );
+        assert_eq!(
+            constraints.validate("ten", false).reason(),
+            Some(InvalidReason::BadInput)
+        );
+    }
+
+    #[test]
+    fn pattern_mismatch() {
+        let constraints = InputConstraints {
+            pattern: Pattern::new("[a-z]+"),
+            ..InputConstraints::default()
+        };
+        assert!(constraints.validate("abc", false).is_valid());
+        assert_eq!(
+            constraints.validate("abc1", false).reason(),
+            Some(InvalidReason::PatternMismatch)
+        );
+    }
+}
@@ -199,6 +199,9 @@ impl<'style_tree, 'dom> BoxTreeBuilder<'style_tree, 'dom> {
             Some(ReplacedElementPresentation::Image(image)) => {
                 replaced_info.and_then(|provider| provider.intrinsic_for_img(image))
             }
+            _ if replaced_kind == Some(ReplacedKind::TextArea) => {
+                replaced_info.and_then(|provider| provider.resized_control_size(styled.node_id))
+            }
             _ => None,
         };
 
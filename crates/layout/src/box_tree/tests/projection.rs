@@ -896,6 +896,52 @@ fn replaced_inline_margins_reduce_available_inline_size() {
     assert_eq!(input.rect.width, 50.0);
 }
 
+#[test]
+fn resized_textarea_size_overrides_css_and_intrinsic_size() {
+    struct ResizedTextarea;
+
+    impl crate::ReplacedElementInfoProvider for ResizedTextarea {
+        fn resolve_image_source(&self, _source: &str) -> Option<String> {
+            None
+        }
+
+        fn intrinsic_for_img(
+            &self,
+            _image: &crate::ImagePresentation,
+        ) -> Option<crate::replaced::intrinsic::IntrinsicSize> {
+            None
+        }
+
+        fn resized_control_size(
+            &self,
+            node_id: Id,
+        ) -> Option<crate::replaced::intrinsic::IntrinsicSize> {
+            (node_id == Id(3)).then_some(crate::replaced::intrinsic::IntrinsicSize {
+                width: Some(180.0),
+                height: Some(90.0),
+                ratio: None,
+            })
+        }
+    }
+
+    let dom = doc(vec![element(
+        2,
+        "div",
+        vec![("width", "300px")],
+        vec![
+            element(3, "textarea", vec![("width", "100px")], Vec::new()),
+            element(4, "textarea", vec![("width", "100px")], Vec::new()),
+        ],
+    )]);
+    let styled = css::build_style_tree(&dom, None);
+    let layout = crate::layout_block_tree(&styled, 500.0, &TestMeasurer, Some(&ResizedTextarea));
+    let resized = find_layout_by_direct_node_id(&layout, Id(3)).expect("textarea layout box");
+    let untouched = find_layout_by_direct_node_id(&layout, Id(4)).expect("textarea layout box");
+
+    assert_eq!((resized.rect.width, resized.rect.height), (180.0, 90.0));
+    assert_eq!(untouched.rect.width, 100.0);
+}
+
 #[test]
 fn layout_sizing_debug_snapshot_pins_flow_and_used_size_metadata() {
     let dom = doc(vec![element(
@@ -631,6 +631,7 @@ pub(crate) fn replaced_kind_debug_label(replaced: Option<ReplacedKind>) -> Strin
         Some(ReplacedKind::TextArea) => "textarea".to_string(),
         Some(ReplacedKind::InputCheckbox) => "input-checkbox".to_string(),
         Some(ReplacedKind::InputRadio) => "input-radio".to_string(),
+        Some(ReplacedKind::InputFile) => "input-file".to_string(),
         Some(ReplacedKind::Button) => "button".to_string(),
     }
 }
@@ -1,5 +1,5 @@
 use crate::{
-    BoxKind, LayoutBox, Rectangle, ReplacedKind, TextMeasurer,
+    BoxKind, LayoutBox, Rectangle, ReplacedElementPresentation, ReplacedKind, TextMeasurer,
     inline::{InlineAction, InlineActionKind, InlineFragment, layout_inline_for_paint},
 };
 use css::Display;
@@ -12,6 +12,8 @@ pub enum HitKind {
     Input,
     Checkbox,
     Radio,
+    /// A file input; activating it opens the file picker.
+    FileInput,
     Image,
     Button,
     InlineBlockBox,
@@ -25,6 +27,11 @@ pub struct HitResult {
     pub fragment_rect: Rectangle, // in layout coords
     pub local_pos: (f32, f32),    // point - fragment_rect.min
     pub href: Option<String>,
+    /// The hit link has `target="_blank"`.
+    pub opens_new_tab: bool,
+    /// Resolved `src` of an image under the point, also set for images
+    /// inside links (whose `kind` is [`HitKind::Link`]).
+    pub image_src: Option<String>,
 }
 
 fn point_in_rect(p: (f32, f32), r: Rectangle) -> bool {
@@ -75,6 +82,8 @@ fn hit_test_box<'style_tree, 'dom>(
         fragment_rect: node.rect,
         local_pos: (point.0 - node.rect.x, point.1 - node.rect.y),
         href: None,
+        opens_new_tab: false,
+        image_src: None,
     })
 }
 
@@ -121,13 +130,15 @@ fn hit_test_inline_fragments<'style_tree, 'dom>(
 
             match &frag.kind {
                 InlineFragment::Text { action, .. } => {
-                    if let Some((link_id, href)) = as_link(action) {
+                    if let Some((link_id, href, opens_new_tab)) = as_link(action) {
                         return Some(HitResult {
                             node_id: link_id,
                             kind: HitKind::Link,
                             fragment_rect: paint_rect,
                             local_pos,
                             href,
+                            opens_new_tab,
+                            image_src: None,
                         });
                     }
 
@@ -137,6 +148,8 @@ fn hit_test_inline_fragments<'style_tree, 'dom>(
                         fragment_rect: paint_rect,
                         local_pos,
                         href: None,
+                        opens_new_tab: false,
+                        image_src: None,
                     });
                 }
 
@@ -146,13 +159,15 @@ fn hit_test_inline_fragments<'style_tree, 'dom>(
                     ..
                 } => {
                     // If box is inside <a>, clicking it should be a link click.
-                    if let Some((link_id, href)) = as_link(action) {
+                    if let Some((link_id, href, opens_new_tab)) = as_link(action) {
                         return Some(HitResult {
                             node_id: link_id,
                             kind: HitKind::Link,
                             fragment_rect: paint_rect,
                             local_pos,
                             href,
+                            opens_new_tab,
+                            image_src: None,
                         });
                     }
 
@@ -163,6 +178,8 @@ fn hit_test_inline_fragments<'style_tree, 'dom>(
                         fragment_rect: paint_rect,
                         local_pos,
                         href: None,
+                        opens_new_tab: false,
+                        image_src: None,
                     });
                 }
 
@@ -172,14 +189,24 @@ fn hit_test_inline_fragments<'style_tree, 'dom>(
                     action,
                     ..
                 } => {
+                    let image_src = frag_layout
+                        .and_then(|lb| lb.replaced_presentation())
+                        .and_then(|presentation| match presentation {
+                            ReplacedElementPresentation::Image(image) => image.resolved_source(),
+                            ReplacedElementPresentation::TextControl(_) => None,
+                        })
+                        .map(str::to_owned);
+
                     // If replaced is inside <a>, it’s a link click
-                    if let Some((link_id, href)) = as_link(action) {
+                    if let Some((link_id, href, opens_new_tab)) = as_link(action) {
                         return Some(HitResult {
                             node_id: link_id,
                             kind: HitKind::Link,
                             fragment_rect: paint_rect,
                             local_pos,
                             href,
+                            opens_new_tab,
+                            image_src,
                         });
                     }
 
@@ -190,6 +217,7 @@ fn hit_test_inline_fragments<'style_tree, 'dom>(
                         ReplacedKind::TextArea => HitKind::Input,
                         ReplacedKind::InputCheckbox => HitKind::Checkbox,
                         ReplacedKind::InputRadio => HitKind::Radio,
+                        ReplacedKind::InputFile => HitKind::FileInput,
                         ReplacedKind::Button => HitKind::Button,
                     };
 
@@ -199,6 +227,8 @@ fn hit_test_inline_fragments<'style_tree, 'dom>(
                         fragment_rect: paint_rect,
                         local_pos,
                         href: None,
+                        opens_new_tab: false,
+                        image_src,
                     });
                 }
             }
@@ -208,15 +238,16 @@ fn hit_test_inline_fragments<'style_tree, 'dom>(
     None
 }
 
-fn as_link(action: &Option<InlineAction>) -> Option<(Id, Option<String>)> {
+fn as_link(action: &Option<InlineAction>) -> Option<(Id, Option<String>, bool)> {
     match action {
         Some(InlineAction {
             target,
             kind: InlineActionKind::Link,
             href,
+            opens_new_tab,
         }) => {
             let href = href.as_ref().map(|s| s.as_ref().to_string());
-            Some((*target, href))
+            Some((*target, href, *opens_new_tab))
         }
         _ => None,
     }
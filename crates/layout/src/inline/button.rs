@@ -1,6 +1,19 @@
+use css::ComputedStyle;
 use html::Node;
 
-use crate::LayoutBox;
+use crate::{LayoutBox, TextMeasurer};
+
+/// Label of the button part of a file input.
+pub const FILE_INPUT_BUTTON_LABEL: &str = "Choose file…";
+
+/// Shown after a file input's button while no file is chosen.
+pub const FILE_INPUT_EMPTY_LABEL: &str = "No file chosen";
+
+/// Horizontal chrome on each side of the file input's button label.
+pub const FILE_INPUT_BUTTON_PADDING: f32 = 8.0;
+
+/// Gap between a file input's button and the chosen file names.
+pub const FILE_INPUT_LABEL_GAP: f32 = 6.0;
 
 fn collect_text_content(node: &LayoutBox<'_, '_>, out: &mut String) {
     match node.node.node {
@@ -29,3 +42,13 @@ pub fn button_label_from_layout(lb: &LayoutBox<'_, '_>) -> String {
         collapsed
     }
 }
+
+/// Width of a file input's content: its button and the "no file chosen"
+/// label. Chosen file names are elided to this width, so picking files never
+/// changes layout.
+pub(crate) fn file_input_label_width(measurer: &dyn TextMeasurer, style: &ComputedStyle) -> f32 {
+    measurer.measure(FILE_INPUT_BUTTON_LABEL, style).max(0.0)
+        + 2.0 * FILE_INPUT_BUTTON_PADDING
+        + FILE_INPUT_LABEL_GAP
+        + measurer.measure(FILE_INPUT_EMPTY_LABEL, style).max(0.0)
+}
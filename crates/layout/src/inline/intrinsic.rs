@@ -7,7 +7,7 @@ use crate::{
 };
 
 use super::{
-    button::button_label_from_layout,
+    button::{button_label_from_layout, file_input_label_width},
     dom_attrs::{get_attr, img_intrinsic_from_dom},
 };
 
@@ -280,6 +280,9 @@ fn replaced_intrinsic_contributions(
             (size_chars as f32) * avg_char_w + 8.0
         }
         ReplacedKind::TextArea => {
+            if let Some(width) = node.replaced_intrinsic.and_then(|size| size.width) {
+                return InlineContributions::from_unbreakable(css_px(width));
+            }
             let cols = get_attr(node.node.node, "cols")
                 .and_then(|s| s.trim().parse::<u32>().ok())
                 .filter(|n| *n > 0)
@@ -298,6 +301,10 @@ fn replaced_intrinsic_contributions(
             // padding is applied later by the content-box sizing model.
             (text_w + 18.0).max(24.0)
         }
+        ReplacedKind::InputFile => {
+            let text_w = file_input_label_width(measurer, node.style);
+            (text_w + 18.0).max(24.0)
+        }
     };
 
     InlineContributions::from_unbreakable(css_px(width))
@@ -338,6 +345,9 @@ fn replaced_preferred_block_size(
         }
         ReplacedKind::InputText => Some(non_negative_line_height(measurer, node.style).max(18.0)),
         ReplacedKind::TextArea => {
+            if let Some(height) = node.replaced_intrinsic.and_then(|size| size.height) {
+                return Some(css_px(height));
+            }
             let rows = get_attr(node.node.node, "rows")
                 .and_then(|s| s.trim().parse::<u32>().ok())
                 .filter(|n| *n > 0)
@@ -348,7 +358,7 @@ fn replaced_preferred_block_size(
             let Length::Px(font_px) = node.style.font_size();
             Some(font_px.max(12.0))
         }
-        ReplacedKind::Button => {
+        ReplacedKind::Button | ReplacedKind::InputFile => {
             // UA-like internal control chrome, not author CSS padding.
             Some((non_negative_line_height(measurer, node.style) + 10.0).max(18.0))
         }
@@ -15,7 +15,10 @@ mod types;
 #[cfg(test)]
 mod tests;
 
-pub use button::button_label_from_layout;
+pub use button::{
+    FILE_INPUT_BUTTON_LABEL, FILE_INPUT_BUTTON_PADDING, FILE_INPUT_EMPTY_LABEL,
+    FILE_INPUT_LABEL_GAP, button_label_from_layout,
+};
 pub(crate) use dom_attrs::get_attr;
 pub use engine::layout_inline_for_paint;
 pub use refine::refine_layout_with_inline;
@@ -5,7 +5,7 @@ use crate::{BoxKind, CssPx, LayoutBox, ReplacedKind, SignedCssPx, TextMeasurer};
 use crate::replaced::size::compute_replaced_size;
 
 use super::{
-    button::button_label_from_layout,
+    button::{button_label_from_layout, file_input_label_width},
     dom_attrs::{get_attr, img_intrinsic_from_dom},
 };
 
@@ -124,7 +124,7 @@ pub(crate) fn size_replaced_inline_children<'style_tree, 'dom>(
                         let fudge_x = 8.0;
                         let intrinsic_w = (cols as f32) * avg_char_w + fudge_x;
 
-                        let w =
+                        let mut w =
                             resolve_replaced_width_px(child.style, available_width, intrinsic_w);
 
                         let bm = child.style.box_metrics();
@@ -138,6 +138,16 @@ pub(crate) fn size_replaced_inline_children<'style_tree, 'dom>(
                             h = px;
                         }
 
+                        // A size set by dragging the resize grip wins over CSS.
+                        if let Some(resized) = child.replaced_intrinsic {
+                            if let Some(px) = resized.width {
+                                w = px.min(available_width.max(0.0));
+                            }
+                            if let Some(px) = resized.height {
+                                h = px;
+                            }
+                        }
+
                         child.rect.width = w;
                         child.rect.height = h;
                     }
@@ -160,10 +170,15 @@ pub(crate) fn size_replaced_inline_children<'style_tree, 'dom>(
                         child.rect.height = h.max(1.0);
                     }
 
-                    ReplacedKind::Button => {
-                        // Measure label text from subtree.
-                        let label = button_label_from_layout(child);
-                        let text_w = measurer.measure(&label, child.style);
+                    ReplacedKind::Button | ReplacedKind::InputFile => {
+                        // Measure label text from subtree; a file input has
+                        // a fixed generated label instead.
+                        let text_w = if kind == ReplacedKind::InputFile {
+                            file_input_label_width(measurer, child.style)
+                        } else {
+                            let label = button_label_from_layout(child);
+                            measurer.measure(&label, child.style)
+                        };
 
                         let bm = child.style.box_metrics();
 
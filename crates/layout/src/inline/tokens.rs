@@ -24,6 +24,7 @@ pub(super) struct InlineContext {
     pub(super) link_target: Option<Id>,
     /// Cloned per token; keep this cheap (shared string).
     pub(super) link_href: Option<Arc<str>>,
+    pub(super) link_opens_new_tab: bool,
     pub(super) text_decoration_line: Option<TextDecorationLine>,
 }
 
@@ -35,6 +36,7 @@ impl InlineContext {
             target: id,
             kind: InlineActionKind::Link,
             href: self.link_href.clone(),
+            opens_new_tab: self.link_opens_new_tab,
         })
     }
 
@@ -326,6 +328,8 @@ fn collect_inline_tokens_from_layout_box<'style_tree, 'dom>(
             ) {
                 next_ctx.link_target = Some(layout.node_id());
                 next_ctx.link_href = get_attr(layout.node.node, "href").map(Arc::from);
+                next_ctx.link_opens_new_tab = get_attr(layout.node.node, "target")
+                    .is_some_and(|target| target.eq_ignore_ascii_case("_blank"));
             }
             next_ctx = next_ctx.with_style_decoration(layout.style);
 
@@ -43,6 +43,8 @@ pub struct InlineAction {
     pub target: Id,
     pub kind: InlineActionKind,
     pub href: Option<Arc<str>>,
+    /// The link has `target="_blank"`.
+    pub opens_new_tab: bool,
 }
 
 /// Layout-owned text decoration metadata for one inline text fragment.
@@ -1,4 +1,4 @@
-use html::Node;
+use html::{Node, internal::Id};
 
 use crate::replaced::intrinsic::IntrinsicSize;
 
@@ -49,6 +49,8 @@ pub enum ReplacedKind {
     TextArea,
     InputCheckbox,
     InputRadio,
+    /// `<input type=file>`: a "Choose file…" button and the chosen file names.
+    InputFile,
     Button,
 }
 
@@ -60,6 +62,12 @@ pub trait ReplacedElementInfoProvider {
     fn resolve_image_source(&self, source: &str) -> Option<String>;
 
     fn intrinsic_for_img(&self, image: &ImagePresentation) -> Option<IntrinsicSize>;
+
+    /// Border-box size the user gave a resizable control (a textarea dragged
+    /// by its resize grip). It replaces the control's CSS and intrinsic size.
+    fn resized_control_size(&self, _node_id: Id) -> Option<IntrinsicSize> {
+        None
+    }
 }
 
 /// Resolve the supported HTML attribute semantics needed by generated layout
@@ -90,7 +98,10 @@ pub(crate) fn replaced_element_presentation(
                 placeholder: exact_html_attribute(node, "placeholder").map(str::to_owned),
             }),
         ),
-        ReplacedKind::InputCheckbox | ReplacedKind::InputRadio | ReplacedKind::Button => None,
+        ReplacedKind::InputCheckbox
+        | ReplacedKind::InputRadio
+        | ReplacedKind::InputFile
+        | ReplacedKind::Button => None,
     }
 }
 
@@ -138,7 +149,11 @@ pub(crate) fn classify_replaced_kind(node: &Node) -> Option<ReplacedKind> {
 
                 match ty {
                     None => return Some(ReplacedKind::InputText),
-                    Some(t) if t.eq_ignore_ascii_case("text") => {
+                    Some(t)
+                        if t.eq_ignore_ascii_case("text") || t.eq_ignore_ascii_case("password") =>
+                    {
+                        // Password fields are text controls; the input store
+                        // masks their painted value.
                         return Some(ReplacedKind::InputText);
                     }
                     Some(t) if t.eq_ignore_ascii_case("checkbox") => {
@@ -147,6 +162,9 @@ pub(crate) fn classify_replaced_kind(node: &Node) -> Option<ReplacedKind> {
                     Some(t) if t.eq_ignore_ascii_case("radio") => {
                         return Some(ReplacedKind::InputRadio);
                     }
+                    Some(t) if t.eq_ignore_ascii_case("file") => {
+                        return Some(ReplacedKind::InputFile);
+                    }
                     _ => {}
                 }
             }
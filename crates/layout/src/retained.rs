@@ -20,6 +20,7 @@ use crate::{
 use crate::box_tree::AnonymousBoxKind;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RetainedViewportWidthKey(i64);
 
 impl RetainedViewportWidthKey {
@@ -55,6 +56,7 @@ impl RetainedLayoutKeySeed {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RetainedLayoutKey {
     pub identity_domain: u64,
     pub layout_input_generation: u64,
@@ -64,6 +66,24 @@ pub struct RetainedLayoutKey {
     pub replaced_metadata_generation: u64,
 }
 
+impl RetainedLayoutKey {
+    /// The key without its viewport width.
+    pub fn seed(self) -> RetainedLayoutKeySeed {
+        RetainedLayoutKeySeed {
+            identity_domain: self.identity_domain,
+            layout_input_generation: self.layout_input_generation,
+            layout_style_generation: self.layout_style_generation,
+            text_measurement_generation: self.text_measurement_generation,
+            replaced_metadata_generation: self.replaced_metadata_generation,
+        }
+    }
+
+    /// Viewport width the layout was computed for, in CSS px.
+    pub fn viewport_width_px(self) -> f32 {
+        self.viewport_width.value() as f32 / 2.0
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum RetainedLayoutFallbackReason {
     MissingRetainedArtifact,
@@ -75,6 +95,9 @@ pub enum RetainedLayoutFallbackReason {
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum RetainedLayoutFrameAction {
     Reused,
+    /// A retained artifact laid out for older inputs stood in while the
+    /// current layout is computed off the UI thread.
+    Deferred,
     Recomputed,
     ConservativeFallback(RetainedLayoutFallbackReason),
 }
@@ -111,10 +134,7 @@ impl RetainedLayoutArtifact {
         let mut anchors = HashMap::new();
         collect_styled_nodes(style_root, &mut anchors);
         let root = self.root.materialize(&anchors)?;
-        Ok(LayoutPhaseOutput::new(
-            root,
-            self.key.viewport_width.value() as f32 / 2.0,
-        ))
+        Ok(LayoutPhaseOutput::new(root, self.key.viewport_width_px()))
     }
 
     #[cfg(test)]
@@ -0,0 +1,11 @@
+//! Fragment-tree golden snapshots; see `layout_test_support::fixture` for the
+//! fixture layout and how to bless new baselines.
+
+use std::path::Path;
+
+#[test]
+fn layout_golden_fragments() {
+    layout_test_support::check_fixtures(
+        &Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/fragments"),
+    );
+}
@@ -0,0 +1,315 @@
+//! Layout golden fixtures.
+//!
+//! Every subdirectory of a fixture root is one fixture: `input.html` is the
+//! document and `fragments.txt` its expected fragment tree.
+//!
+//! ```text
+//! # format: layout-fragments-v1
+//! # viewport: 400
+//!
+//! document x=0.00 y=0.00 w=400.00 h=19.20
+//!   html x=0.00 y=0.00 w=400.00 h=19.20
+//! ...
+//! ```
+//!
+//! The viewport header is optional and defaults to 800 px. An optional
+//! `# text: ahem` header lays text out with the metrics of the Ahem test font
+//! instead of [`FixedTextMeasurer`]'s. Set
+//! `BORROWSER_LAYOUT_FIXTURE_UPDATE=1` to write the current output as the
+//! expected file, including for fixtures that do not have one yet.
+
+use crate::measurer::{AhemTextMeasurer, FixedTextMeasurer};
+use crate::snapshot::fragment_snapshot_with;
+use html_test_support::diff_lines;
+use layout::TextMeasurer;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const FRAGMENT_SNAPSHOT_FORMAT_V1: &str = "layout-fragments-v1";
+
+const DEFAULT_VIEWPORT_WIDTH: f32 = 800.0;
+const UPDATE_ENV: &str = "BORROWSER_LAYOUT_FIXTURE_UPDATE";
+
+/// Text metrics a fixture is laid out with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TextMetrics {
+    /// [`FixedTextMeasurer`].
+    #[default]
+    Fixed,
+    /// [`AhemTextMeasurer`], for tests written against the Ahem font.
+    Ahem,
+}
+
+impl TextMetrics {
+    pub fn measurer(self) -> &'static dyn TextMeasurer {
+        match self {
+            Self::Fixed => &FixedTextMeasurer,
+            Self::Ahem => &AhemTextMeasurer,
+        }
+    }
+
+    fn header_value(self) -> &'static str {
+        match self {
+            Self::Fixed => "fixed",
+            Self::Ahem => "ahem",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExpectedFragments {
+    pub viewport_width: f32,
+    pub text_metrics: TextMetrics,
+    pub lines: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayoutFixture {
+    pub name: String,
+    pub dir: PathBuf,
+    pub input: String,
+    /// `None` until the fixture has been blessed.
+    pub expected: Option<ExpectedFragments>,
+}
+
+impl LayoutFixture {
+    pub fn viewport_width(&self) -> f32 {
+        self.expected
+            .as_ref()
+            .map_or(DEFAULT_VIEWPORT_WIDTH, |expected| expected.viewport_width)
+    }
+
+    pub fn text_metrics(&self) -> TextMetrics {
+        self.expected
+            .as_ref()
+            .map_or(TextMetrics::Fixed, |expected| expected.text_metrics)
+    }
+
+    /// Lay the fixture out and serialize its fragment tree.
+    pub fn snapshot(&self) -> Result<Vec<String>, String> {
+        fragment_snapshot_with(
+            &self.input,
+            self.viewport_width(),
+            self.text_metrics().measurer(),
+        )
+    }
+
+    fn expected_path(&self) -> PathBuf {
+        self.dir.join("fragments.txt")
+    }
+}
+
+/// Load every fixture under `root`, sorted by name.
+pub fn load_fixtures(root: &Path) -> Vec<LayoutFixture> {
+    let mut entries: Vec<_> = fs::read_dir(root)
+        .unwrap_or_else(|err| panic!("failed to read fixture root {root:?}: {err}"))
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_dir())
+        .collect();
+    entries.sort_by_key(|entry| entry.file_name());
+
+    entries
+        .into_iter()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') {
+                return None;
+            }
+            let dir = entry.path();
+            let input_path = dir.join("input.html");
+            let input = fs::read_to_string(&input_path)
+                .unwrap_or_else(|err| panic!("failed to read input {input_path:?}: {err}"));
+            let expected_path = dir.join("fragments.txt");
+            let expected = expected_path.exists().then(|| {
+                let text = fs::read_to_string(&expected_path).unwrap_or_else(|err| {
+                    panic!("failed to read expected fragments {expected_path:?}: {err}")
+                });
+                parse_expected(&text)
+                    .unwrap_or_else(|err| panic!("invalid fragments file {expected_path:?}: {err}"))
+            });
+            Some(LayoutFixture {
+                name,
+                dir,
+                input,
+                expected,
+            })
+        })
+        .collect()
+}
+
+pub fn update_mode() -> bool {
+    matches!(env::var(UPDATE_ENV).as_deref(), Ok("1"))
+}
+
+pub fn write_expected_fragments(fixture: &LayoutFixture, lines: &[String]) {
+    let expected = ExpectedFragments {
+        viewport_width: fixture.viewport_width(),
+        text_metrics: fixture.text_metrics(),
+        lines: lines.to_vec(),
+    };
+    write_expected_file(&fixture.expected_path(), &expected);
+}
+
+/// Write `expected` to `path` as a fragments file.
+pub fn write_expected_file(path: &Path, expected: &ExpectedFragments) {
+    let mut out = format!("# format: {FRAGMENT_SNAPSHOT_FORMAT_V1}\n");
+    if expected.viewport_width != DEFAULT_VIEWPORT_WIDTH {
+        out.push_str(&format!("# viewport: {}\n", expected.viewport_width));
+    }
+    if expected.text_metrics != TextMetrics::Fixed {
+        out.push_str(&format!(
+            "# text: {}\n",
+            expected.text_metrics.header_value()
+        ));
+    }
+    out.push('\n');
+    for line in &expected.lines {
+        out.push_str(line);
+        out.push('\n');
+    }
+    fs::write(path, out)
+        .unwrap_or_else(|err| panic!("failed to write expected fragments {path:?}: {err}"));
+}
+
+/// Lay out every fixture under `root` and compare it against its expected
+/// fragment tree; in update mode, write the expected files instead. Panics
+/// listing every mismatching fixture.
+pub fn check_fixtures(root: &Path) {
+    let fixtures = load_fixtures(root);
+    assert!(!fixtures.is_empty(), "no layout fixtures in {root:?}");
+    let update = update_mode();
+    let mut failures = Vec::new();
+    for fixture in &fixtures {
+        let actual = fixture
+            .snapshot()
+            .unwrap_or_else(|err| panic!("layout fixture '{}': {err}", fixture.name));
+        if update {
+            write_expected_fragments(fixture, &actual);
+            continue;
+        }
+        match &fixture.expected {
+            Some(expected) if expected.lines == actual => {}
+            Some(expected) => failures.push(format!(
+                "fixture '{}':\n{}",
+                fixture.name,
+                diff_lines(&expected.lines, &actual)
+            )),
+            None => failures.push(format!(
+                "fixture '{}' has no fragments.txt; run with {UPDATE_ENV}=1 to create it",
+                fixture.name
+            )),
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "{} of {} layout fixtures failed (run with {UPDATE_ENV}=1 to bless):\n{}",
+        failures.len(),
+        fixtures.len(),
+        failures.join("\n")
+    );
+}
+
+fn parse_expected(text: &str) -> Result<ExpectedFragments, String> {
+    let mut format = None;
+    let mut viewport_width = DEFAULT_VIEWPORT_WIDTH;
+    let mut text_metrics = TextMetrics::Fixed;
+    let mut lines = Vec::new();
+    let mut in_header = true;
+    for line in text.lines() {
+        if in_header {
+            if let Some(header) = line.strip_prefix("# ") {
+                let (key, value) = header
+                    .split_once(':')
+                    .ok_or_else(|| format!("malformed header '{line}'"))?;
+                match key.trim() {
+                    "format" => format = Some(value.trim().to_string()),
+                    "viewport" => {
+                        viewport_width = value
+                            .trim()
+                            .parse()
+                            .map_err(|_| format!("invalid viewport '{}'", value.trim()))?;
+                    }
+                    "text" => {
+                        text_metrics = match value.trim() {
+                            "fixed" => TextMetrics::Fixed,
+                            "ahem" => TextMetrics::Ahem,
+                            other => return Err(format!("unknown text metrics '{other}'")),
+                        };
+                    }
+                    other => return Err(format!("unknown header '{other}'")),
+                }
+                continue;
+            }
+            in_header = false;
+            if line.is_empty() {
+                continue;
+            }
+        }
+        lines.push(line.to_string());
+    }
+    match format.as_deref() {
+        Some(FRAGMENT_SNAPSHOT_FORMAT_V1) => Ok(ExpectedFragments {
+            viewport_width,
+            text_metrics,
+            lines,
+        }),
+        Some(other) => Err(format!("unsupported format '{other}'")),
+        None => Err(format!("missing '# format: {FRAGMENT_SNAPSHOT_FORMAT_V1}'")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ExpectedFragments, TextMetrics, load_fixtures, parse_expected, write_expected_fragments,
+    };
+    use std::fs;
+
+    #[test]
+    fn expected_files_carry_format_viewport_and_lines() {
+        let expected = parse_expected(
+            "# format: layout-fragments-v1\n# viewport: 320\n\ndocument x=0.00\n  html x=0.00\n",
+        )
+        .expect("valid expected file");
+        assert_eq!(
+            expected,
+            ExpectedFragments {
+                viewport_width: 320.0,
+                text_metrics: TextMetrics::Fixed,
+                lines: vec!["document x=0.00".to_string(), "  html x=0.00".to_string()],
+            }
+        );
+
+        let ahem = parse_expected("# format: layout-fragments-v1\n# text: ahem\n\ndocument\n")
+            .expect("valid expected file");
+        assert_eq!(ahem.text_metrics, TextMetrics::Ahem);
+        assert_eq!(ahem.viewport_width, 800.0);
+
+        assert!(parse_expected("document\n").is_err());
+        assert!(parse_expected("# format: layout-fragments-v1\n# text: serif\n\nx\n").is_err());
+        assert!(parse_expected("# format: layout-fragments-v0\n\ndocument\n").is_err());
+    }
+
+    #[test]
+    fn blessing_keeps_the_viewport_and_round_trips() {
+        let temp = tempfile::tempdir().expect("temporary fixture root");
+        let dir = temp.path().join("narrow");
+        fs::create_dir(&dir).expect("fixture dir");
+        fs::write(dir.join("input.html"), "<p>x</p>").expect("input");
+        fs::write(
+            dir.join("fragments.txt"),
+            "# format: layout-fragments-v1\n# viewport: 200\n# text: ahem\n\nstale\n",
+        )
+        .expect("expected");
+
+        let fixture = load_fixtures(temp.path()).remove(0);
+        let lines = vec!["document".to_string(), "  html".to_string()];
+        write_expected_fragments(&fixture, &lines);
+
+        let blessed = load_fixtures(temp.path()).remove(0);
+        assert_eq!(blessed.viewport_width(), 200.0);
+        assert_eq!(blessed.text_metrics(), TextMetrics::Ahem);
+        assert_eq!(blessed.expected.expect("blessed").lines, lines);
+    }
+}
@@ -0,0 +1,21 @@
+//! Golden-snapshot support for layout tests.
+//!
+//! Fixtures are HTML documents, styled by their own `<style>` elements, that
+//! run through the style and layout phases with [`FixedTextMeasurer`] so text
+//! geometry does not depend on fonts. The resulting fragment tree (block
+//! boxes, line boxes, and inline fragments) is serialized one line per node
+//! and compared against the fixture's golden file.
+
+pub mod fixture;
+pub mod measurer;
+pub mod snapshot;
+
+pub use fixture::{
+    ExpectedFragments, FRAGMENT_SNAPSHOT_FORMAT_V1, LayoutFixture, TextMetrics, check_fixtures,
+    load_fixtures, update_mode, write_expected_file, write_expected_fragments,
+};
+pub use measurer::{AhemTextMeasurer, FixedTextMeasurer};
+pub use snapshot::{
+    FIXTURE_UA_STYLESHEET, fragment_snapshot, fragment_snapshot_with, fragment_tree_lines,
+    with_layout,
+};
@@ -0,0 +1,37 @@
+use css::ComputedStyle;
+use css::values::Length;
+use layout::TextMeasurer;
+
+/// Deterministic text metrics: every character is half the font size wide
+/// and lines are 1.2 times the font size tall.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FixedTextMeasurer;
+
+impl TextMeasurer for FixedTextMeasurer {
+    fn measure(&self, text: &str, style: &ComputedStyle) -> f32 {
+        let Length::Px(font_px) = style.font_size();
+        text.chars().count() as f32 * font_px * 0.5
+    }
+
+    fn line_height(&self, style: &ComputedStyle) -> f32 {
+        let Length::Px(font_px) = style.font_size();
+        font_px * 1.2
+    }
+}
+
+/// Metrics of the Ahem test font that WPT layout tests rely on: every glyph
+/// is a square one font size wide, and lines are one font size tall.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AhemTextMeasurer;
+
+impl TextMeasurer for AhemTextMeasurer {
+    fn measure(&self, text: &str, style: &ComputedStyle) -> f32 {
+        let Length::Px(font_px) = style.font_size();
+        text.chars().count() as f32 * font_px
+    }
+
+    fn line_height(&self, style: &ComputedStyle) -> f32 {
+        let Length::Px(font_px) = style.font_size();
+        font_px
+    }
+}
@@ -0,0 +1,213 @@
+use crate::measurer::FixedTextMeasurer;
+use css::{
+    CascadeOrigin, Display, ParseOptions, StylePhaseOutput, StylesheetCascadeInput,
+    build_style_tree_from_computed_styles, compute_document_styles_from_resolved_styles,
+    parse_stylesheet_with_options, resolve_document_styles_from_cascade_inputs,
+};
+use html::dom_utils::collect_style_texts;
+use html::{ElementNamespace, HtmlParseOptions, Node, parse_document};
+use html_test_support::escape_text;
+use layout::inline::{InlineFragment, LineFragment};
+use layout::{
+    LayoutBox, LayoutPhaseInput, LineBox, Rectangle, ReplacedKind, TextMeasurer, layout_document,
+    layout_inline_for_paint,
+};
+
+/// Block-level defaults for the elements fixtures use. Everything else comes
+/// from the fixture's own `<style>` elements.
+pub const FIXTURE_UA_STYLESHEET: &str = r#"
+html, body, div, p, section, article, header, footer, main, nav, aside,
+h1, h2, h3, h4, h5, h6, ul, ol, blockquote, pre, figure, dl, dt, dd {
+    display: block;
+}
+
+li {
+    display: list-item;
+}
+
+input, button, textarea {
+    display: inline-block;
+}
+
+head, title, meta, link, style, script {
+    display: none;
+}
+"#;
+
+/// Parse `input`, style it with its `<style>` elements, lay it out in a
+/// viewport `viewport_width` px wide, and serialize the fragment tree.
+pub fn fragment_snapshot(input: &str, viewport_width: f32) -> Result<Vec<String>, String> {
+    fragment_snapshot_with(input, viewport_width, &FixedTextMeasurer)
+}
+
+/// [`fragment_snapshot`] with the given text metrics.
+pub fn fragment_snapshot_with(
+    input: &str,
+    viewport_width: f32,
+    measurer: &dyn TextMeasurer,
+) -> Result<Vec<String>, String> {
+    with_layout(input, viewport_width, measurer, |root| {
+        fragment_tree_lines(root, measurer)
+    })
+}
+
+/// Parse, style, and lay out `input` the way [`fragment_snapshot`] does, and
+/// hand the root of the layout tree to `inspect`.
+pub fn with_layout<R>(
+    input: &str,
+    viewport_width: f32,
+    measurer: &dyn TextMeasurer,
+    inspect: impl FnOnce(&LayoutBox<'_, '_>) -> R,
+) -> Result<R, String> {
+    let dom = parse_document(input, HtmlParseOptions::default())
+        .map_err(|err| format!("parse failed: {err:?}"))?
+        .document;
+
+    let mut author_css = String::new();
+    collect_style_texts(&dom, &mut author_css);
+    let ua = parse_stylesheet_with_options(FIXTURE_UA_STYLESHEET, &ParseOptions::stylesheet());
+    let author = parse_stylesheet_with_options(&author_css, &ParseOptions::stylesheet());
+    let sheets = [
+        StylesheetCascadeInput::user_agent_for_namespace(&ua, ElementNamespace::Html),
+        StylesheetCascadeInput::new(CascadeOrigin::Author, &author),
+    ];
+    let resolved = resolve_document_styles_from_cascade_inputs(&dom, &sheets)
+        .map_err(|err| format!("style resolution failed: {err}"))?;
+    let computed = compute_document_styles_from_resolved_styles(&dom, &resolved)
+        .map_err(|err| format!("computed styles failed: {err}"))?;
+    let root = build_style_tree_from_computed_styles(&dom, &computed)
+        .map_err(|err| format!("style tree failed: {err}"))?;
+    let style = StylePhaseOutput::new(root);
+
+    let output = layout_document(LayoutPhaseInput::from_style_output(
+        &style,
+        viewport_width,
+        measurer,
+        None,
+    ));
+    Ok(inspect(output.root()))
+}
+
+/// Serialize the fragment tree under `root`: one line per block container
+/// with its border box, followed by its line boxes and their fragments.
+/// Inline boxes and text runs appear only as fragments; children are
+/// indented two spaces under their parent.
+pub fn fragment_tree_lines(root: &LayoutBox<'_, '_>, measurer: &dyn TextMeasurer) -> Vec<String> {
+    let mut out = Vec::new();
+    append_box(&mut out, root, measurer, 0);
+    out
+}
+
+fn append_box(
+    out: &mut Vec<String>,
+    layout: &LayoutBox<'_, '_>,
+    measurer: &dyn TextMeasurer,
+    depth: usize,
+) {
+    let mut child_depth = depth;
+    if is_block_container(layout) {
+        let indent = "  ".repeat(depth);
+        out.push(format!(
+            "{indent}{} {}",
+            box_label(layout),
+            rect_label(layout.rect)
+        ));
+        if hosts_inline_content(layout) {
+            for line in layout_inline_for_paint(measurer, content_rect(layout), layout) {
+                append_line(out, &line, depth + 1);
+            }
+        }
+        child_depth = depth + 1;
+    }
+    for child in &layout.children {
+        append_box(out, child, measurer, child_depth);
+    }
+}
+
+fn append_line(out: &mut Vec<String>, line: &LineBox<'_, '_>, depth: usize) {
+    let indent = "  ".repeat(depth);
+    out.push(format!(
+        "{indent}line {} baseline={:.2}",
+        rect_label(line.rect),
+        line.baseline
+    ));
+    for fragment in &line.fragments {
+        out.push(format!("{indent}  {}", fragment_label(fragment)));
+    }
+}
+
+fn fragment_label(fragment: &LineFragment<'_, '_>) -> String {
+    let rect = rect_label(fragment.paint_rect.rect());
+    match &fragment.kind {
+        InlineFragment::Text { text, .. } => format!("text \"{}\" {rect}", escape_text(text)),
+        InlineFragment::Box { layout, .. } => match layout {
+            Some(layout) => format!("box {} {rect}", box_label(layout)),
+            None => format!("box {rect}"),
+        },
+        InlineFragment::Replaced { kind, .. } => {
+            format!("replaced {} {rect}", replaced_label(*kind))
+        }
+    }
+}
+
+/// Boxes that get a line of their own: the document, anonymous blocks, and
+/// elements that are not laid out inline.
+fn is_block_container(layout: &LayoutBox<'_, '_>) -> bool {
+    if layout.is_anonymous() {
+        return true;
+    }
+    match layout.node.node {
+        Node::Document { .. } => true,
+        Node::Element { .. } => !matches!(layout.style.display(), Display::Inline),
+        _ => false,
+    }
+}
+
+/// Mirrors paint: block-like, non-replaced boxes lay out their own inline
+/// content.
+fn hosts_inline_content(layout: &LayoutBox<'_, '_>) -> bool {
+    layout.replaced.is_none() && !matches!(layout.node.node, Node::Document { .. })
+}
+
+fn content_rect(layout: &LayoutBox<'_, '_>) -> Rectangle {
+    let (x, width) = layout.content_x_and_width();
+    Rectangle {
+        x,
+        y: layout.content_y(),
+        width,
+        height: layout.content_height(),
+    }
+}
+
+fn box_label(layout: &LayoutBox<'_, '_>) -> String {
+    if layout.is_anonymous() {
+        return "anonymous".to_string();
+    }
+    match layout.node.node {
+        Node::Document { .. } => "document".to_string(),
+        Node::Element { element } => match layout.replaced {
+            Some(kind) => format!("{} replaced={}", element.name(), replaced_label(kind)),
+            None => element.name().to_string(),
+        },
+        _ => "other".to_string(),
+    }
+}
+
+fn replaced_label(kind: ReplacedKind) -> &'static str {
+    match kind {
+        ReplacedKind::Img => "img",
+        ReplacedKind::InputText => "input-text",
+        ReplacedKind::TextArea => "textarea",
+        ReplacedKind::InputCheckbox => "input-checkbox",
+        ReplacedKind::InputRadio => "input-radio",
+        ReplacedKind::InputFile => "input-file",
+        ReplacedKind::Button => "button",
+    }
+}
+
+fn rect_label(rect: Rectangle) -> String {
+    format!(
+        "x={:.2} y={:.2} w={:.2} h={:.2}",
+        rect.x, rect.y, rect.width, rect.height
+    )
+}
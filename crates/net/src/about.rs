@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use core_types::{NetError, NetworkResponseInfo};
+
+use crate::NetEvent;
+
+pub(crate) fn is_about_url(url: &str) -> bool {
+    url.starts_with("about:")
+}
+
+/// Serve the generated page at the `about:` URL `url` as one chunk.
+///
+/// `about:crash-test` panics instead, on the calling thread, so it crashes
+/// the runtime that asked for it.
+pub(crate) fn fetch_about_url(
+    request_id: u64,
+    url: &str,
+    callback: &Arc<dyn Fn(NetEvent) + Send + Sync>,
+) {
+    let name = url
+        .strip_prefix("about:")
+        .expect("about fetch called with about URL");
+    // `about://version` names the same page as `about:version`.
+    let name = name.trim_start_matches("//");
+    let name = name.split(['?', '#']).next().unwrap_or_default();
+    let body = match name {
+        "blank" => String::new(),
+        "version" => version_page_html(),
+        "crash-test" => panic!("about:crash-test"),
+        _ => {
+            callback(NetEvent::Error {
+                request_id,
+                url: url.to_string(),
+                error: NetError::HttpStatus(404),
+                message: format!("no internal page named {url}"),
+            });
+            return;
+        }
+    };
+
+    let response = NetworkResponseInfo {
+        requested_url: url.to_string(),
+        final_url: url.to_string(),
+        status_code: None,
+        content_type: Some("text/html; charset=utf-8".to_string()),
+        content_length: Some(body.len() as u64),
+        headers: Vec::new(),
+    };
+    callback(NetEvent::Start {
+        request_id,
+        response: response.clone(),
+        validators: None,
+    });
+    let bytes_received = body.len();
+    if !body.is_empty() {
+        callback(NetEvent::Chunk {
+            request_id,
+            url: url.to_string(),
+            chunk: body.into_bytes(),
+        });
+    }
+    callback(NetEvent::Done {
+        request_id,
+        response,
+        bytes_received,
+    });
+}
+
+fn version_page_html() -> String {
+    format!(
+        "<!DOCTYPE html>\
+<html><head><title>About Borrowser</title><style>\
+body {{ background-color: #f4f4f4; color: #303030; margin: 0; padding: 32px 48px; }}\
+h1 {{ font-size: 24px; margin-bottom: 16px; }}\
+.meta {{ color: #808080; font-size: 13px; }}\
+</style></head><body>\
+<h1>Borrowser {}</h1>\
+<p class=\"meta\">{} on {}-{}</p>\
+</body></html>",
+        env!("CARGO_PKG_VERSION"),
+        crate::HttpClientPolicy::default().user_agent,
+        std::env::consts::ARCH,
+        std::env::consts::OS,
+    )
+}
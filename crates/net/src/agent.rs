@@ -1,19 +1,27 @@
-use std::sync::OnceLock;
+use std::sync::Mutex;
 
-use crate::{HttpClientPolicy, tls::build_rustls_client_config};
+use crate::{HttpClientPolicy, pool::ConnectionTrackingResolver, tls::build_rustls_client_config};
 
-pub(crate) fn agent_for_policy(policy: &HttpClientPolicy) -> ureq::Agent {
-    if *policy == HttpClientPolicy::default() {
-        default_http_agent().clone()
-    } else {
-        build_http_agent(policy)
-    }
-}
+/// Idle keep-alive connections kept per origin, matching the number of
+/// parallel connections browsers open to one origin.
+const MAX_IDLE_CONNECTIONS_PER_ORIGIN: usize = 6;
+/// Idle keep-alive connections kept across all origins.
+const MAX_IDLE_CONNECTIONS: usize = 64;
 
-fn default_http_agent() -> &'static ureq::Agent {
-    static HTTP_AGENT: OnceLock<ureq::Agent> = OnceLock::new();
+/// The agent for `policy`. Agents are shared by every fetch with the same
+/// policy, so fetches to one origin reuse each other's connections.
+pub(crate) fn agent_for_policy(policy: &HttpClientPolicy) -> ureq::Agent {
+    static AGENTS: Mutex<Vec<(HttpClientPolicy, ureq::Agent)>> = Mutex::new(Vec::new());
 
-    HTTP_AGENT.get_or_init(|| build_http_agent(&HttpClientPolicy::default()))
+    let mut agents = AGENTS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some((_, agent)) = agents.iter().find(|(built_for, _)| built_for == policy) {
+        return agent.clone();
+    }
+    let agent = build_http_agent(policy);
+    agents.push((policy.clone(), agent.clone()));
+    agent
 }
 
 fn build_http_agent(policy: &HttpClientPolicy) -> ureq::Agent {
@@ -24,7 +32,11 @@ fn build_http_agent(policy: &HttpClientPolicy) -> ureq::Agent {
         .timeout_connect(policy.timeouts.connect)
         .timeout_read(policy.timeouts.read)
         .timeout_write(policy.timeouts.write)
-        .redirects(policy.redirects)
+        // Fetches follow redirects themselves, to report every hop.
+        .redirects(0)
+        .max_idle_connections(MAX_IDLE_CONNECTIONS)
+        .max_idle_connections_per_host(MAX_IDLE_CONNECTIONS_PER_ORIGIN)
+        .resolver(ConnectionTrackingResolver)
         .tls_config(tls_config)
         .build()
 }
@@ -0,0 +1,45 @@
+use std::error::Error;
+use std::io;
+
+use core_types::NetError;
+
+/// What went wrong with a request that never got a response.
+pub(crate) fn transport_error(transport: &ureq::Transport) -> NetError {
+    if transport.kind() == ureq::ErrorKind::Dns {
+        return NetError::Dns;
+    }
+    match io_error(transport) {
+        // rustls reports handshake failures as `InvalidData` I/O errors
+        // wrapping its own error.
+        Some(io)
+            if io
+                .get_ref()
+                .is_some_and(|inner| inner.is::<rustls::Error>()) =>
+        {
+            NetError::Tls
+        }
+        Some(io) => read_error(io),
+        None => NetError::Io,
+    }
+}
+
+/// `Timeout` if reading failed because the server took too long, `Io`
+/// otherwise. ureq reports expired socket timeouts as `TimedOut`.
+pub(crate) fn read_error(err: &io::Error) -> NetError {
+    match err.kind() {
+        io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock => NetError::Timeout,
+        _ => NetError::Io,
+    }
+}
+
+/// The first I/O error behind `transport`, if it came from one.
+pub(crate) fn io_error(transport: &ureq::Transport) -> Option<&io::Error> {
+    let mut source = transport.source();
+    while let Some(err) = source {
+        if let Some(io) = err.downcast_ref::<io::Error>() {
+            return Some(io);
+        }
+        source = err.source();
+    }
+    None
+}
@@ -1,9 +1,28 @@
-use core_types::{NetworkErrorKind, NetworkResponseInfo};
+use core_types::{NetError, NetworkResponseInfo};
+
+use crate::Validators;
 
 pub enum NetEvent {
     Start {
         request_id: u64,
         response: NetworkResponseInfo,
+        /// For revalidating a stored copy later; `None` if the response
+        /// has no validators or must not be stored.
+        validators: Option<Validators>,
+    },
+    /// The server redirected the request; it continues at `to` under the
+    /// same `request_id`.
+    Redirected {
+        request_id: u64,
+        from: String,
+        to: String,
+    },
+    /// A request of the fetch was sent, over a keep-alive connection from
+    /// the pool of `origin` if `reused`, or else over a new one.
+    Connection {
+        request_id: u64,
+        origin: String,
+        reused: bool,
     },
     Chunk {
         request_id: u64,
@@ -15,11 +34,16 @@ pub enum NetEvent {
         response: NetworkResponseInfo,
         bytes_received: usize,
     },
+    /// Answer to a revalidating fetch: the stored copy is still current, and
+    /// no body follows.
+    NotModified {
+        request_id: u64,
+        response: NetworkResponseInfo,
+    },
     Error {
         request_id: u64,
         url: String,
-        error_kind: NetworkErrorKind,
-        status_code: Option<u16>,
-        error: String,
+        error: NetError,
+        message: String,
     },
 }
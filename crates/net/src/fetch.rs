@@ -2,14 +2,19 @@ use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread;
 
-use core_types::{NetworkErrorKind, NetworkResponseInfo, ResourceKind};
+use core_types::{NetError, NetworkResponseInfo, RequestPayload, ResourceKind};
 
 use crate::{
-    HttpClientPolicy, NetEvent,
+    HttpClientPolicy, NetEvent, Validators,
+    about::{fetch_about_url, is_about_url},
     agent::agent_for_policy,
+    error::transport_error,
     file::{fetch_file_url, is_file_url},
     limits::should_stream_http_status,
     log::log_network_error,
+    pool::{origin_of, track_reuse},
+    redirect::{method_after_redirect, redirect_target},
+    retry::send_with_retries,
     stream::stream_reader,
 };
 
@@ -19,35 +24,95 @@ pub fn fetch_stream(
     kind: ResourceKind,
     cancel_token: Arc<AtomicBool>,
     callback: Arc<dyn Fn(NetEvent) + Send + Sync>,
+) {
+    fetch_stream_with_payload(
+        request_id,
+        url,
+        kind,
+        RequestPayload::default(),
+        cancel_token,
+        callback,
+    );
+}
+
+/// Like [`fetch_stream`], sending the method, headers, and body of
+/// `payload`.
+pub fn fetch_stream_with_payload(
+    request_id: u64,
+    url: String,
+    kind: ResourceKind,
+    payload: RequestPayload,
+    cancel_token: Arc<AtomicBool>,
+    callback: Arc<dyn Fn(NetEvent) + Send + Sync>,
+) {
+    fetch_stream_with_policy(
+        request_id,
+        url,
+        kind,
+        payload,
+        HttpClientPolicy::default(),
+        None,
+        cancel_token,
+        callback,
+    );
+}
+
+/// Like [`fetch_stream`], but asks the server whether a stored copy with
+/// `validators` is still current. If it is, `NetEvent::NotModified` is the
+/// only event; otherwise the response streams as usual.
+pub fn fetch_stream_revalidating(
+    request_id: u64,
+    url: String,
+    kind: ResourceKind,
+    validators: Validators,
+    cancel_token: Arc<AtomicBool>,
+    callback: Arc<dyn Fn(NetEvent) + Send + Sync>,
 ) {
     fetch_stream_with_policy(
         request_id,
         url,
         kind,
+        RequestPayload::default(),
         HttpClientPolicy::default(),
+        Some(validators),
         cancel_token,
         callback,
     );
 }
 
-pub(crate) fn fetch_stream_with_policy(
+/// Like [`fetch_stream_with_payload`], with the client set up by `policy`,
+/// and revalidating a stored copy if `validators` are given.
+#[allow(clippy::too_many_arguments)]
+pub fn fetch_stream_with_policy(
     request_id: u64,
     url: String,
     kind: ResourceKind,
+    payload: RequestPayload,
     policy: HttpClientPolicy,
+    validators: Option<Validators>,
     cancel_token: Arc<AtomicBool>,
     callback: Arc<dyn Fn(NetEvent) + Send + Sync>,
 ) {
+    if is_about_url(&url) {
+        // Generated pages are in memory already; serving them here lets
+        // `about:crash-test` crash the caller rather than a fetch thread.
+        fetch_about_url(request_id, &url, &callback);
+        return;
+    }
+
     let agent = agent_for_policy(&policy);
+    // The fetch thread continues the caller's span, so a trace shows the
+    // whole fetch under the request that started it.
+    let span = tracing::Span::current();
 
     thread::spawn(move || {
+        let _span = span.entered();
         if cancel_token.load(Ordering::Relaxed) {
             callback(NetEvent::Error {
                 request_id,
                 url: url.clone(),
-                error_kind: NetworkErrorKind::Cancelled,
-                status_code: None,
-                error: "cancelled".into(),
+                error: NetError::Cancelled,
+                message: "cancelled".into(),
             });
             return;
         }
@@ -57,39 +122,18 @@ pub(crate) fn fetch_stream_with_policy(
             return;
         }
 
-        let response = match agent.get(&url).call() {
-            Ok(response) => response,
-            Err(ureq::Error::Status(code, response)) if should_stream_http_status(kind, code) => {
-                response
-            }
-            Err(ureq::Error::Status(code, _)) => {
-                log_network_error(
-                    request_id,
-                    kind,
-                    &url,
-                    "http-status",
-                    &format!("HTTP {code}"),
-                );
-                callback(NetEvent::Error {
-                    request_id,
-                    url: url.clone(),
-                    error_kind: NetworkErrorKind::HttpStatus,
-                    status_code: Some(code),
-                    error: format!("HTTP {code}"),
-                });
-                return;
-            }
-            Err(err) => {
-                log_network_error(request_id, kind, &url, "transport", &err.to_string());
-                callback(NetEvent::Error {
-                    request_id,
-                    url: url.clone(),
-                    error_kind: NetworkErrorKind::Transport,
-                    status_code: None,
-                    error: err.to_string(),
-                });
-                return;
-            }
+        let Some(response) = call_following_redirects(
+            &agent,
+            request_id,
+            &url,
+            kind,
+            &payload,
+            &policy,
+            validators.as_ref(),
+            &cancel_token,
+            &callback,
+        ) else {
+            return;
         };
 
         let response_info = NetworkResponseInfo {
@@ -97,11 +141,24 @@ pub(crate) fn fetch_stream_with_policy(
             final_url: response.get_url().to_string(),
             status_code: Some(response.status()),
             content_type: response.header("Content-Type").map(ToOwned::to_owned),
+            content_length: response
+                .header("Content-Length")
+                .and_then(|length| length.trim().parse().ok()),
+            headers: response_headers(&response),
         };
 
+        if validators.is_some() && response.status() == 304 {
+            callback(NetEvent::NotModified {
+                request_id,
+                response: response_info,
+            });
+            return;
+        }
+
         callback(NetEvent::Start {
             request_id,
             response: response_info.clone(),
+            validators: Validators::from_response(&response),
         });
 
         let mut reader = response.into_reader();
@@ -114,13 +171,12 @@ pub(crate) fn fetch_stream_with_policy(
             &mut reader,
         ) {
             Ok(total) => total,
-            Err(kind_and_error) => {
+            Err((error, message)) => {
                 callback(NetEvent::Error {
                     request_id,
                     url: url.clone(),
-                    error_kind: kind_and_error.0,
-                    status_code: response_info.status_code,
-                    error: kind_and_error.1,
+                    error,
+                    message,
                 });
                 return;
             }
@@ -133,3 +189,131 @@ pub(crate) fn fetch_stream_with_policy(
         });
     });
 }
+
+/// Request `url` with `payload`, following up to `policy.redirects`
+/// redirects and reporting each hop; each hop is retried as `policy.retry`
+/// allows. `None` means the fetch failed and its error was
+/// reported.
+#[allow(clippy::too_many_arguments)]
+fn call_following_redirects(
+    agent: &ureq::Agent,
+    request_id: u64,
+    url: &str,
+    kind: ResourceKind,
+    payload: &RequestPayload,
+    policy: &HttpClientPolicy,
+    validators: Option<&Validators>,
+    cancel_token: &AtomicBool,
+    callback: &Arc<dyn Fn(NetEvent) + Send + Sync>,
+) -> Option<ureq::Response> {
+    // Errors name the requested URL, like every other event; the log names
+    // the hop that failed.
+    let fail = |hop: &str, stage, error, message: String| {
+        log_network_error(request_id, kind, hop, stage, &message);
+        callback(NetEvent::Error {
+            request_id,
+            url: url.to_string(),
+            error,
+            message,
+        });
+    };
+
+    let mut current = url.to_string();
+    let mut payload = payload.clone();
+    let mut redirects = 0;
+    loop {
+        let mut request = agent.request(payload.method.as_str(), &current);
+        for (name, value) in &payload.headers {
+            request = request.set(name, value);
+        }
+        if let Some(validators) = validators {
+            request = validators.apply(request);
+        }
+        let (sent, reused) = track_reuse(|| {
+            send_with_retries(&request, &payload, &policy.retry, cancel_token, |err| {
+                log_network_error(request_id, kind, &current, "retry", &err.to_string());
+            })
+        });
+        callback(NetEvent::Connection {
+            request_id,
+            origin: origin_of(&current),
+            reused,
+        });
+        let response = match sent {
+            Ok(response) => response,
+            Err(ureq::Error::Status(code, response)) if should_stream_http_status(kind, code) => {
+                return Some(response);
+            }
+            Err(ureq::Error::Status(code, _)) => {
+                fail(
+                    &current,
+                    "http-status",
+                    NetError::HttpStatus(code),
+                    format!("HTTP {code}"),
+                );
+                return None;
+            }
+            Err(ureq::Error::Transport(transport)) => {
+                let error = if cancel_token.load(Ordering::Relaxed) {
+                    NetError::Cancelled
+                } else {
+                    transport_error(&transport)
+                };
+                fail(&current, "transport", error, transport.to_string());
+                return None;
+            }
+        };
+
+        let Some(target) = redirect_target(&current, &response) else {
+            return Some(response);
+        };
+        if redirects == policy.redirects {
+            fail(
+                &current,
+                "redirect",
+                NetError::TooManyRedirects,
+                format!("too many redirects (limit {})", policy.redirects),
+            );
+            return None;
+        }
+        if cancel_token.load(Ordering::Relaxed) {
+            callback(NetEvent::Error {
+                request_id,
+                url: url.to_string(),
+                error: NetError::Cancelled,
+                message: "cancelled".into(),
+            });
+            return None;
+        }
+        redirects += 1;
+        if method_after_redirect(response.status(), payload.method) != payload.method {
+            // The body belonged to the original method; the next hop is a
+            // plain GET.
+            payload = RequestPayload::default();
+        }
+        callback(NetEvent::Redirected {
+            request_id,
+            from: current,
+            to: target.clone(),
+        });
+        current = target;
+    }
+}
+
+/// The headers of `response`, grouped by name in the order the names first
+/// appeared. Values that are not valid UTF-8 are left out.
+fn response_headers(response: &ureq::Response) -> Vec<(String, String)> {
+    let mut names = response.headers_names();
+    let mut seen = std::collections::HashSet::new();
+    names.retain(|name| seen.insert(name.clone()));
+    names
+        .into_iter()
+        .flat_map(|name| {
+            response
+                .all(&name)
+                .into_iter()
+                .map(|value| (name.clone(), value.to_string()))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
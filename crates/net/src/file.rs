@@ -3,7 +3,7 @@ use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 
-use core_types::{NetworkErrorKind, NetworkResponseInfo, ResourceKind};
+use core_types::{NetError, NetworkResponseInfo, ResourceKind};
 
 use crate::{NetEvent, content_type::guess_content_type_from_path, stream::stream_reader};
 
@@ -31,6 +31,8 @@ pub(crate) fn fetch_file_url(
         final_url: url.to_string(),
         status_code: None,
         content_type: guess_content_type_from_path(path),
+        content_length: std::fs::metadata(path).ok().map(|metadata| metadata.len()),
+        headers: Vec::new(),
     };
 
     let mut file = match File::open(path) {
@@ -39,9 +41,8 @@ pub(crate) fn fetch_file_url(
             callback(NetEvent::Error {
                 request_id,
                 url: url.to_string(),
-                error_kind: NetworkErrorKind::LocalFile,
-                status_code: None,
-                error: format!("file open error: {err}"),
+                error: NetError::Io,
+                message: format!("file open error: {err}"),
             });
             return;
         }
@@ -50,18 +51,18 @@ pub(crate) fn fetch_file_url(
     callback(NetEvent::Start {
         request_id,
         response: response.clone(),
+        validators: None,
     });
 
     let bytes_received =
         match stream_reader(request_id, url, kind, cancel_token, callback, &mut file) {
             Ok(total) => total,
-            Err(kind_and_error) => {
+            Err((error, message)) => {
                 callback(NetEvent::Error {
                     request_id,
                     url: url.to_string(),
-                    error_kind: kind_and_error.0,
-                    status_code: None,
-                    error: kind_and_error.1,
+                    error,
+                    message,
                 });
                 return;
             }
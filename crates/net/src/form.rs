@@ -0,0 +1,22 @@
+use core_types::{HttpMethod, RequestPayload};
+use url::form_urlencoded;
+
+/// POST payload sending `entries` as `application/x-www-form-urlencoded`,
+/// the encoding of an HTML form without `enctype`.
+pub fn urlencoded_form<K, V>(entries: impl IntoIterator<Item = (K, V)>) -> RequestPayload
+where
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    let body = form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(entries)
+        .finish();
+    RequestPayload {
+        method: HttpMethod::Post,
+        headers: vec![(
+            "Content-Type".to_string(),
+            "application/x-www-form-urlencoded".to_string(),
+        )],
+        body: body.into_bytes(),
+    }
+}
@@ -1,24 +1,35 @@
 //! Network loading subsystem.
 //!
-//! This crate owns browser resource fetching for HTML, CSS, images, and local
-//! files. It exposes a small streaming event API while keeping HTTP client
-//! policy, TLS configuration, byte-limit enforcement, and transport details
-//! behind focused internal modules.
+//! This crate owns browser resource fetching for HTML, CSS, images, local
+//! files, and the generated `about:` pages. It exposes a small streaming
+//! event API while keeping HTTP client policy, TLS configuration, byte-limit
+//! enforcement, and transport details behind focused internal modules.
 
+mod about;
 mod agent;
 mod content_type;
+mod error;
 mod event;
 mod fetch;
 mod file;
+mod form;
 mod limits;
 mod log;
 mod policy;
+mod pool;
+mod redirect;
+mod retry;
 mod stream;
 mod tls;
+mod validators;
 
 pub use event::NetEvent;
-pub use fetch::fetch_stream;
-pub use policy::{HttpClientPolicy, HttpTimeoutPolicy, TlsTrustStore};
+pub use fetch::{
+    fetch_stream, fetch_stream_revalidating, fetch_stream_with_payload, fetch_stream_with_policy,
+};
+pub use form::urlencoded_form;
+pub use policy::{HttpClientPolicy, HttpRetryPolicy, HttpTimeoutPolicy, TlsTrustStore};
+pub use validators::Validators;
 
 #[cfg(test)]
 mod tests;
@@ -17,6 +17,25 @@ impl Default for HttpTimeoutPolicy {
     }
 }
 
+/// How GETs that failed before a response arrived are retried: the first
+/// retry waits `initial_backoff`, and each one after it twice as long as the
+/// one before.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HttpRetryPolicy {
+    /// Retries after the first attempt; `0` never retries.
+    pub retries: u32,
+    pub initial_backoff: Duration,
+}
+
+impl Default for HttpRetryPolicy {
+    fn default() -> Self {
+        Self {
+            retries: 2,
+            initial_backoff: Duration::from_millis(250),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
 pub enum TlsTrustStore {
     #[default]
@@ -28,8 +47,10 @@ pub enum TlsTrustStore {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct HttpClientPolicy {
     pub user_agent: String,
+    /// Redirects followed before the fetch fails; `0` fails on the first.
     pub redirects: u32,
     pub timeouts: HttpTimeoutPolicy,
+    pub retry: HttpRetryPolicy,
     pub tls: TlsTrustStore,
 }
 
@@ -39,6 +60,7 @@ impl Default for HttpClientPolicy {
             user_agent: default_user_agent(),
             redirects: 10,
             timeouts: HttpTimeoutPolicy::default(),
+            retry: HttpRetryPolicy::default(),
             tls: TlsTrustStore::NativeRoots,
         }
     }
@@ -0,0 +1,39 @@
+//! Keep-alive connection reuse.
+//!
+//! The ureq agent pools idle connections per origin and hands them to later
+//! requests to the same origin. It only resolves a host name when it opens
+//! a new connection, and it sends a request on the calling thread, so a
+//! resolver that flags the thread tells whether a request reused a pooled
+//! connection.
+
+use std::cell::Cell;
+use std::io;
+use std::net::{SocketAddr, ToSocketAddrs};
+
+thread_local! {
+    static OPENED_CONNECTION: Cell<bool> = const { Cell::new(false) };
+}
+
+pub(crate) struct ConnectionTrackingResolver;
+
+impl ureq::Resolver for ConnectionTrackingResolver {
+    fn resolve(&self, netloc: &str) -> io::Result<Vec<SocketAddr>> {
+        OPENED_CONNECTION.set(true);
+        netloc.to_socket_addrs().map(Iterator::collect)
+    }
+}
+
+/// Run `send` and report whether every request it made went over a pooled
+/// connection.
+pub(crate) fn track_reuse<T>(send: impl FnOnce() -> T) -> (T, bool) {
+    OPENED_CONNECTION.set(false);
+    let sent = send();
+    (sent, !OPENED_CONNECTION.get())
+}
+
+/// The origin `url` is pooled under, e.g. `https://example.com:8443`.
+pub(crate) fn origin_of(url: &str) -> String {
+    url::Url::parse(url)
+        .map(|url| url.origin().ascii_serialization())
+        .unwrap_or_else(|_| url.to_string())
+}
@@ -0,0 +1,24 @@
+use core_types::HttpMethod;
+use url::Url;
+
+/// Where `response` to a request for `url` redirects to, if it is a
+/// redirect with a usable `Location`.
+pub(crate) fn redirect_target(url: &str, response: &ureq::Response) -> Option<String> {
+    if !matches!(response.status(), 301 | 302 | 303 | 307 | 308) {
+        return None;
+    }
+    let location = response.header("Location")?.trim();
+    // `Location` may be relative to the URL that answered.
+    let target = Url::parse(url).ok()?.join(location).ok()?;
+    matches!(target.scheme(), "http" | "https").then(|| target.into())
+}
+
+/// Method of the request that follows a `status` redirect of a `method`
+/// request: `303 See Other` always continues with GET, and so do POSTs
+/// redirected by 301 or 302, as browsers do; 307 and 308 keep the method.
+pub(crate) fn method_after_redirect(status: u16, method: HttpMethod) -> HttpMethod {
+    match (status, method) {
+        (303, _) | (301 | 302, HttpMethod::Post) => HttpMethod::Get,
+        _ => method,
+    }
+}
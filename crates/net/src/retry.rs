@@ -0,0 +1,83 @@
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use core_types::{HttpMethod, RequestPayload};
+
+use crate::{HttpRetryPolicy, error::io_error};
+
+/// Send `request` with `payload`. GETs are idempotent, so one that failed
+/// transiently before any response arrived is sent again, after a backoff,
+/// up to `policy.retries` times; `on_retry` hears about each failed attempt.
+/// Cancelling during a backoff gives up at once with the last failure.
+// The error is ureq's own, handed back as `call` would return it.
+#[allow(clippy::result_large_err)]
+pub(crate) fn send_with_retries(
+    request: &ureq::Request,
+    payload: &RequestPayload,
+    policy: &HttpRetryPolicy,
+    cancel_token: &AtomicBool,
+    mut on_retry: impl FnMut(&ureq::Transport),
+) -> Result<ureq::Response, ureq::Error> {
+    let mut backoff = policy.initial_backoff;
+    let mut retries = 0;
+    loop {
+        let sent = match payload.method {
+            HttpMethod::Get => request.clone().call(),
+            HttpMethod::Post => request.clone().send_bytes(&payload.body),
+        };
+        match sent {
+            Err(ureq::Error::Transport(transport))
+                if payload.method == HttpMethod::Get
+                    && is_transient(&transport)
+                    && retries < policy.retries
+                    && !cancel_token.load(Ordering::Relaxed) =>
+            {
+                on_retry(&transport);
+                if !sleep_unless_cancelled(backoff, cancel_token) {
+                    return Err(ureq::Error::Transport(transport));
+                }
+                backoff = backoff.saturating_mul(2);
+                retries += 1;
+            }
+            sent => return sent,
+        }
+    }
+}
+
+/// How often a backoff checks for cancellation.
+const CANCEL_POLL: Duration = Duration::from_millis(10);
+
+/// Sleep for `duration` in short slices; `false` if `cancel_token` was set
+/// before it was over.
+fn sleep_unless_cancelled(duration: Duration, cancel_token: &AtomicBool) -> bool {
+    let deadline = Instant::now() + duration;
+    loop {
+        if cancel_token.load(Ordering::Relaxed) {
+            return false;
+        }
+        let left = deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return true;
+        }
+        thread::sleep(left.min(CANCEL_POLL));
+    }
+}
+
+/// Timeouts and dropped connections may go away on their own; bad URLs,
+/// unknown hosts and rejected certificates won't.
+fn is_transient(transport: &ureq::Transport) -> bool {
+    io_error(transport).is_some_and(|io| {
+        matches!(
+            io.kind(),
+            io::ErrorKind::TimedOut
+                | io::ErrorKind::WouldBlock
+                | io::ErrorKind::ConnectionRefused
+                | io::ErrorKind::ConnectionReset
+                | io::ErrorKind::ConnectionAborted
+                | io::ErrorKind::BrokenPipe
+                | io::ErrorKind::UnexpectedEof
+        )
+    })
+}
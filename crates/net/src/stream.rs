@@ -2,9 +2,9 @@ use std::io::Read;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 
-use core_types::{NetworkErrorKind, ResourceKind};
+use core_types::{NetError, ResourceKind};
 
-use crate::{NetEvent, limits::resource_byte_limit};
+use crate::{NetEvent, error::read_error, limits::resource_byte_limit};
 
 pub(crate) fn stream_reader<R: Read>(
     request_id: u64,
@@ -13,20 +13,20 @@ pub(crate) fn stream_reader<R: Read>(
     cancel_token: &Arc<AtomicBool>,
     callback: &Arc<dyn Fn(NetEvent) + Send + Sync>,
     reader: &mut R,
-) -> Result<usize, (NetworkErrorKind, String)> {
+) -> Result<usize, (NetError, String)> {
     let mut buffer = [0_u8; 32 * 1024];
     let mut total = 0_usize;
-    let byte_limit = resource_byte_limit(kind);
+    let byte_limit = resource_byte_limit(kind).unwrap_or(usize::MAX);
 
     // Resource-limit policy is streaming-by-default: bytes up to the configured
     // cap may already have been delivered before the over-limit read is
     // observed. Callers must treat `ResourceLimit` as terminal failure and
     // discard partial state where a complete resource is required. In
-    // Borrowser that means images clear buffered bytes, CSS aborts buffered
-    // parser state, and HTML never receives a terminal `Done` event.
+    // Borrowser that means images clear buffered bytes and CSS aborts buffered
+    // parser state. Documents are uncapped; the parser stops reading them.
     loop {
         if cancel_token.load(Ordering::Relaxed) {
-            return Err((NetworkErrorKind::Cancelled, "cancelled".into()));
+            return Err((NetError::Cancelled, "cancelled".into()));
         }
 
         match reader.read(&mut buffer) {
@@ -45,7 +45,7 @@ pub(crate) fn stream_reader<R: Read>(
 
                 if n > take {
                     return Err((
-                        NetworkErrorKind::ResourceLimit,
+                        NetError::TooLarge,
                         format!(
                             "{} response exceeded byte limit of {} bytes",
                             kind.as_str(),
@@ -54,7 +54,7 @@ pub(crate) fn stream_reader<R: Read>(
                     ));
                 }
             }
-            Err(err) => return Err((NetworkErrorKind::Read, format!("read error: {err}"))),
+            Err(err) => return Err((read_error(&err), format!("read error: {err}"))),
         }
     }
 }
@@ -0,0 +1,59 @@
+use super::support::{collect_fetch, collect_fetch_terminal};
+use crate::{HttpClientPolicy, NetEvent, fetch_stream};
+use core_types::{NetError, ResourceKind};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+#[test]
+fn serves_generated_pages_as_html() {
+    let blank = collect_fetch(
+        "about:blank".to_string(),
+        ResourceKind::Html,
+        HttpClientPolicy::default(),
+    );
+    assert_eq!(
+        blank.start.response.content_type.as_deref(),
+        Some("text/html; charset=utf-8")
+    );
+    assert_eq!(blank.start.response.final_url, "about:blank");
+    assert!(blank.body.is_empty());
+
+    let version = collect_fetch(
+        "about://version".to_string(),
+        ResourceKind::Html,
+        HttpClientPolicy::default(),
+    );
+    let html = String::from_utf8(version.body).expect("utf-8 page");
+    assert!(
+        html.contains(&format!("Borrowser {}", env!("CARGO_PKG_VERSION"))),
+        "{html}"
+    );
+    assert_eq!(version.done.bytes_received, html.len());
+}
+
+#[test]
+fn unknown_pages_fail_as_not_found() {
+    let terminal = collect_fetch_terminal(
+        "about:nope".to_string(),
+        ResourceKind::Html,
+        HttpClientPolicy::default(),
+    );
+    let error = terminal.error.expect("unknown page error");
+    assert_eq!(error.error, NetError::HttpStatus(404));
+    assert!(terminal.start.is_none());
+}
+
+#[test]
+fn crash_test_panics_on_the_calling_thread() {
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        fetch_stream(
+            1,
+            "about:crash-test".to_string(),
+            ResourceKind::Html,
+            Arc::new(AtomicBool::new(false)),
+            Arc::new(|_: NetEvent| {}),
+        );
+    }));
+    assert!(result.is_err());
+}
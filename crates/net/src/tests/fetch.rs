@@ -1,6 +1,9 @@
-use super::support::{HttpReply, TestHttpServer, collect_fetch};
-use crate::HttpClientPolicy;
-use core_types::ResourceKind;
+use super::support::{
+    HttpReply, TestHttpServer, collect_fetch, collect_fetch_error, collect_fetch_terminal,
+    collect_fetch_with_payload, collect_revalidation,
+};
+use crate::{HttpClientPolicy, Validators, urlencoded_form};
+use core_types::{NetError, ResourceKind};
 
 #[test]
 fn follows_redirects_and_reports_final_url() {
@@ -26,8 +29,260 @@ fn follows_redirects_and_reports_final_url() {
         HttpClientPolicy::default(),
     );
 
+    assert_eq!(
+        result.redirects,
+        vec![(server.url("/redirect"), server.url("/final"))]
+    );
     assert_eq!(result.start.response.status_code, Some(200));
+    assert_eq!(result.start.response.requested_url, server.url("/redirect"));
     assert_eq!(result.start.response.final_url, server.url("/final"));
     assert_eq!(result.done.bytes_received, b"<p>ok</p>".len());
     assert_eq!(result.body, b"<p>ok</p>");
 }
+
+#[test]
+fn start_carries_every_response_header() {
+    let server = TestHttpServer::spawn(|_| {
+        HttpReply::response(
+            "200 OK",
+            vec![
+                ("Content-Type", "text/html; charset=utf-8".to_string()),
+                ("Set-Cookie", "a=1".to_string()),
+                ("X-Frame-Options", "DENY".to_string()),
+                ("Set-Cookie", "b=2".to_string()),
+                ("Refresh", "5; url=/next".to_string()),
+            ],
+            b"<p>ok</p>".to_vec(),
+        )
+    });
+
+    let result = collect_fetch(
+        server.url("/"),
+        ResourceKind::Html,
+        HttpClientPolicy::default(),
+    );
+
+    let response = &result.start.response;
+    let header = |name: &str, value: &str| (name.to_string(), value.to_string());
+    assert_eq!(
+        response.headers,
+        vec![
+            header("content-type", "text/html; charset=utf-8"),
+            header("set-cookie", "a=1"),
+            header("set-cookie", "b=2"),
+            header("x-frame-options", "DENY"),
+            header("refresh", "5; url=/next"),
+            header("content-length", "9"),
+            header("connection", "close"),
+        ]
+    );
+    assert_eq!(response.header("X-Frame-Options"), Some("DENY"));
+    assert_eq!(response.header("Content-Disposition"), None);
+}
+
+/// Redirects `/hop/<n>` to `/hop/<n - 1>` with a relative `Location`,
+/// cycling through the redirect statuses, and answers `/hop/0`.
+fn hop_server() -> TestHttpServer {
+    const STATUSES: [&str; 5] = [
+        "301 Moved Permanently",
+        "302 Found",
+        "303 See Other",
+        "307 Temporary Redirect",
+        "308 Permanent Redirect",
+    ];
+    TestHttpServer::spawn(|req| {
+        let hops: usize = req
+            .path
+            .strip_prefix("/hop/")
+            .and_then(|hops| hops.parse().ok())
+            .unwrap_or(0);
+        if hops == 0 {
+            return HttpReply::response(
+                "200 OK",
+                vec![("Content-Type", "text/html".to_string())],
+                b"landed".to_vec(),
+            );
+        }
+        HttpReply::response(
+            STATUSES[hops % STATUSES.len()],
+            vec![("Location", format!("{}", hops - 1))],
+            Vec::new(),
+        )
+    })
+}
+
+#[test]
+fn reports_every_hop_of_a_redirect_chain() {
+    let server = hop_server();
+    let result = collect_fetch(
+        server.url("/hop/5"),
+        ResourceKind::Css,
+        HttpClientPolicy::default(),
+    );
+
+    let hops: Vec<_> = (1..=5)
+        .rev()
+        .map(|hop| {
+            (
+                server.url(&format!("/hop/{hop}")),
+                server.url(&format!("/hop/{}", hop - 1)),
+            )
+        })
+        .collect();
+    assert_eq!(result.redirects, hops);
+    assert_eq!(result.start.response.final_url, server.url("/hop/0"));
+    assert_eq!(result.body, b"landed");
+}
+
+#[test]
+fn fails_once_the_redirect_limit_is_exceeded() {
+    let server = hop_server();
+    let policy = HttpClientPolicy {
+        redirects: 2,
+        ..HttpClientPolicy::default()
+    };
+
+    let landed = collect_fetch(server.url("/hop/2"), ResourceKind::Html, policy.clone());
+    assert_eq!(landed.redirects.len(), 2);
+
+    let terminal = collect_fetch_terminal(server.url("/hop/3"), ResourceKind::Html, policy);
+    let error = terminal.error.expect("redirect limit error");
+    assert_eq!(error.url, server.url("/hop/3"));
+    assert_eq!(error.error, NetError::TooManyRedirects);
+    assert!(
+        error.message.contains("too many redirects"),
+        "{}",
+        error.message
+    );
+    assert!(terminal.start.is_none());
+}
+
+#[test]
+fn unknown_hosts_fail_as_dns_errors() {
+    // `.invalid` never resolves (RFC 2606).
+    let error = collect_fetch_error(
+        "http://borrowser.invalid/".to_string(),
+        ResourceKind::Html,
+        HttpClientPolicy::default(),
+    );
+
+    assert_eq!(error.error, NetError::Dns, "{}", error.message);
+}
+
+/// Echoes the method, content type, and body of `/echo` requests, and
+/// redirects `/see-other` and `/temporary` there.
+fn echo_server() -> TestHttpServer {
+    TestHttpServer::spawn(|req| {
+        let status = match req.path.as_str() {
+            "/see-other" => "303 See Other",
+            "/temporary" => "307 Temporary Redirect",
+            _ => {
+                let echo = format!(
+                    "{} {} {}",
+                    req.method,
+                    req.header("Content-Type").unwrap_or("-"),
+                    String::from_utf8_lossy(&req.body)
+                );
+                return HttpReply::response(
+                    "200 OK",
+                    vec![("Content-Type", "text/plain".to_string())],
+                    echo.into_bytes(),
+                );
+            }
+        };
+        HttpReply::response(status, vec![("Location", "/echo".to_string())], Vec::new())
+    })
+}
+
+#[test]
+fn posts_urlencoded_form_bodies() {
+    let server = echo_server();
+
+    let result = collect_fetch_with_payload(
+        server.url("/echo"),
+        ResourceKind::Html,
+        urlencoded_form([("q", "rust & egui"), ("t", "a\r\nb")]),
+        HttpClientPolicy::default(),
+    );
+
+    assert_eq!(
+        String::from_utf8_lossy(&result.body),
+        "POST application/x-www-form-urlencoded q=rust+%26+egui&t=a%0D%0Ab"
+    );
+}
+
+#[test]
+fn redirected_posts_keep_their_body_only_through_307_and_308() {
+    let server = echo_server();
+    let post = || urlencoded_form([("a", "1")]);
+
+    let see_other = collect_fetch_with_payload(
+        server.url("/see-other"),
+        ResourceKind::Html,
+        post(),
+        HttpClientPolicy::default(),
+    );
+    assert_eq!(String::from_utf8_lossy(&see_other.body), "GET - ");
+
+    let temporary = collect_fetch_with_payload(
+        server.url("/temporary"),
+        ResourceKind::Html,
+        post(),
+        HttpClientPolicy::default(),
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&temporary.body),
+        "POST application/x-www-form-urlencoded a=1"
+    );
+}
+
+fn etag_server() -> TestHttpServer {
+    TestHttpServer::spawn(|req| {
+        if req.header("If-None-Match") == Some("\"v1\"") {
+            HttpReply::response("304 Not Modified", vec![], Vec::new())
+        } else {
+            HttpReply::response(
+                "200 OK",
+                vec![
+                    ("Content-Type", "text/css".to_string()),
+                    ("ETag", "\"v2\"".to_string()),
+                ],
+                b"p{}".to_vec(),
+            )
+        }
+    })
+}
+
+#[test]
+fn revalidation_answers_not_modified_when_validators_match() {
+    let server = etag_server();
+    let validators = Validators {
+        etag: Some("\"v1\"".to_string()),
+        last_modified: None,
+    };
+
+    let answer =
+        collect_revalidation(server.url("/site.css"), validators).expect("not-modified answer");
+
+    assert_eq!(answer.response.status_code, Some(304));
+}
+
+#[test]
+fn revalidation_streams_the_new_body_when_validators_differ() {
+    let server = etag_server();
+    let stale = Validators {
+        etag: Some("\"v0\"".to_string()),
+        last_modified: None,
+    };
+    assert!(collect_revalidation(server.url("/site.css"), stale).is_none());
+
+    let result = collect_fetch(
+        server.url("/site.css"),
+        ResourceKind::Css,
+        HttpClientPolicy::default(),
+    );
+    assert_eq!(
+        result.start.validators.and_then(|v| v.etag).as_deref(),
+        Some("\"v2\"")
+    );
+}
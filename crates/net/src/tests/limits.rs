@@ -1,58 +1,39 @@
 use super::support::{HttpReply, TestHttpServer, collect_fetch, collect_fetch_terminal};
 use crate::{HttpClientPolicy, limits::resource_byte_limit};
-use core_types::{NetworkErrorKind, ResourceKind};
-use tools::common::{MAX_DOCUMENT_BYTES, MAX_IMAGE_BYTES, MAX_STYLESHEET_BYTES};
+use core_types::{NetError, ResourceKind};
+use tools::common::{MAX_IMAGE_BYTES, MAX_STYLESHEET_BYTES};
 
 #[test]
 fn assigns_resource_specific_byte_limits() {
-    assert_eq!(resource_byte_limit(ResourceKind::Html), MAX_DOCUMENT_BYTES);
-    assert_eq!(resource_byte_limit(ResourceKind::Css), MAX_STYLESHEET_BYTES);
-    assert_eq!(resource_byte_limit(ResourceKind::Image), MAX_IMAGE_BYTES);
-}
-
-#[test]
-fn allows_document_response_exactly_at_limit() {
-    let server = TestHttpServer::spawn(|_req| {
-        HttpReply::response(
-            "200 OK",
-            vec![("Content-Type", "text/html".to_string())],
-            vec![b'a'; MAX_DOCUMENT_BYTES],
-        )
-    });
-
-    let result = collect_fetch(
-        server.url("/limit.html"),
-        ResourceKind::Html,
-        HttpClientPolicy::default(),
+    assert_eq!(resource_byte_limit(ResourceKind::Html), None);
+    assert_eq!(
+        resource_byte_limit(ResourceKind::Css),
+        Some(MAX_STYLESHEET_BYTES)
+    );
+    assert_eq!(
+        resource_byte_limit(ResourceKind::Image),
+        Some(MAX_IMAGE_BYTES)
     );
-
-    assert_eq!(result.done.bytes_received, MAX_DOCUMENT_BYTES);
-    assert_eq!(result.body.len(), MAX_DOCUMENT_BYTES);
 }
 
 #[test]
-fn oversized_document_response_emits_resource_limit_without_done() {
+fn documents_stream_past_the_subresource_limits() {
     let server = TestHttpServer::spawn(|_req| {
         HttpReply::response(
             "200 OK",
             vec![("Content-Type", "text/html".to_string())],
-            vec![b'a'; MAX_DOCUMENT_BYTES + 1],
+            vec![b'a'; MAX_STYLESHEET_BYTES + 1],
         )
     });
 
-    let result = collect_fetch_terminal(
-        server.url("/too-large.html"),
+    let result = collect_fetch(
+        server.url("/large.html"),
         ResourceKind::Html,
         HttpClientPolicy::default(),
     );
 
-    assert_eq!(result.start.expect("start").response.status_code, Some(200));
-    assert_eq!(result.body.len(), MAX_DOCUMENT_BYTES);
-    assert!(result.done.is_none(), "unexpected Done after limit failure");
-    let error = result.error.expect("resource limit error");
-    assert_eq!(error.error_kind, NetworkErrorKind::ResourceLimit);
-    assert_eq!(error.status_code, Some(200));
-    assert!(error.error.contains("exceeded byte limit"));
+    assert_eq!(result.done.bytes_received, MAX_STYLESHEET_BYTES + 1);
+    assert_eq!(result.body.len(), MAX_STYLESHEET_BYTES + 1);
 }
 
 #[test]
@@ -75,9 +56,8 @@ fn oversized_stylesheet_response_emits_resource_limit_without_done() {
     assert_eq!(result.body.len(), MAX_STYLESHEET_BYTES);
     assert!(result.done.is_none(), "unexpected Done after limit failure");
     let error = result.error.expect("resource limit error");
-    assert_eq!(error.error_kind, NetworkErrorKind::ResourceLimit);
-    assert_eq!(error.status_code, Some(200));
-    assert!(error.error.contains("exceeded byte limit"));
+    assert_eq!(error.error, NetError::TooLarge);
+    assert!(error.message.contains("exceeded byte limit"));
 }
 
 #[test]
@@ -100,7 +80,6 @@ fn oversized_image_response_emits_resource_limit_without_done() {
     assert_eq!(result.body.len(), MAX_IMAGE_BYTES);
     assert!(result.done.is_none(), "unexpected Done after limit failure");
     let error = result.error.expect("resource limit error");
-    assert_eq!(error.error_kind, NetworkErrorKind::ResourceLimit);
-    assert_eq!(error.status_code, Some(200));
-    assert!(error.error.contains("exceeded byte limit"));
+    assert_eq!(error.error, NetError::TooLarge);
+    assert!(error.message.contains("exceeded byte limit"));
 }
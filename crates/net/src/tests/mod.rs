@@ -1,5 +1,8 @@
+mod about;
 mod fetch;
 mod limits;
+mod pool;
+mod retry;
 mod status;
 mod support;
 mod tls;
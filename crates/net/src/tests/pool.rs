@@ -0,0 +1,52 @@
+use super::support::{HttpReply, TestHttpServer, collect_fetch};
+use crate::HttpClientPolicy;
+use core_types::ResourceKind;
+
+fn stylesheet() -> HttpReply {
+    HttpReply::response(
+        "200 OK",
+        vec![("Content-Type", "text/css".to_string())],
+        b"p { color: red }".to_vec(),
+    )
+}
+
+fn origin_of(server: &TestHttpServer) -> String {
+    server.url("").trim_end_matches('/').to_string()
+}
+
+#[test]
+fn fetches_to_one_origin_reuse_keep_alive_connections() {
+    let server = TestHttpServer::spawn(|_| stylesheet().keep_alive());
+    let origin = origin_of(&server);
+
+    let reused = ["/a.css", "/b.css", "/c.css"].map(|path| {
+        let result = collect_fetch(
+            server.url(path),
+            ResourceKind::Css,
+            HttpClientPolicy::default(),
+        );
+        assert_eq!(result.body, b"p { color: red }");
+        assert_eq!(result.connections.len(), 1);
+        assert_eq!(result.connections[0].0, origin);
+        result.connections[0].1
+    });
+
+    assert_eq!(reused, [false, true, true]);
+}
+
+#[test]
+fn closed_connections_are_not_reused() {
+    let server = TestHttpServer::spawn(|_| stylesheet());
+
+    let reused = ["/a.css", "/b.css"].map(|path| {
+        collect_fetch(
+            server.url(path),
+            ResourceKind::Css,
+            HttpClientPolicy::default(),
+        )
+        .connections[0]
+            .1
+    });
+
+    assert_eq!(reused, [false, false]);
+}
@@ -0,0 +1,134 @@
+use super::support::{
+    HttpReply, TestHttpServer, collect_fetch, collect_fetch_error, collect_fetch_error_with_payload,
+};
+use crate::retry::send_with_retries;
+use crate::{HttpClientPolicy, HttpRetryPolicy, HttpTimeoutPolicy, urlencoded_form};
+use core_types::{NetError, RequestPayload, ResourceKind};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const STALL: Duration = Duration::from_millis(500);
+
+/// A policy that gives up on a silent server after 100ms and retries it
+/// twice, quickly.
+fn impatient_policy() -> HttpClientPolicy {
+    HttpClientPolicy {
+        timeouts: HttpTimeoutPolicy {
+            read: Duration::from_millis(100),
+            ..HttpTimeoutPolicy::default()
+        },
+        retry: HttpRetryPolicy {
+            retries: 2,
+            initial_backoff: Duration::from_millis(10),
+        },
+        ..HttpClientPolicy::default()
+    }
+}
+
+fn page() -> HttpReply {
+    HttpReply::response(
+        "200 OK",
+        vec![("Content-Type", "text/html".to_string())],
+        b"<p>ok</p>".to_vec(),
+    )
+}
+
+#[test]
+fn retries_gets_that_timed_out_until_the_server_answers() {
+    static ATTEMPTS: AtomicUsize = AtomicUsize::new(0);
+    let server = TestHttpServer::spawn(|_| {
+        if ATTEMPTS.fetch_add(1, Ordering::SeqCst) < 2 {
+            page().after(STALL)
+        } else {
+            page()
+        }
+    });
+
+    let result = collect_fetch(server.url("/"), ResourceKind::Html, impatient_policy());
+
+    assert_eq!(result.body, b"<p>ok</p>");
+    assert_eq!(ATTEMPTS.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn reports_a_timeout_once_every_retry_timed_out() {
+    static ATTEMPTS: AtomicUsize = AtomicUsize::new(0);
+    let server = TestHttpServer::spawn(|_| {
+        ATTEMPTS.fetch_add(1, Ordering::SeqCst);
+        page().after(STALL)
+    });
+
+    let error = collect_fetch_error(server.url("/"), ResourceKind::Html, impatient_policy());
+
+    assert_eq!(error.error, NetError::Timeout);
+    assert_eq!(error.url, server.url("/"));
+    assert_eq!(ATTEMPTS.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn never_retries_posts() {
+    static ATTEMPTS: AtomicUsize = AtomicUsize::new(0);
+    let server = TestHttpServer::spawn(|_| {
+        ATTEMPTS.fetch_add(1, Ordering::SeqCst);
+        page().after(STALL)
+    });
+
+    let error = collect_fetch_error_with_payload(
+        server.url("/submit"),
+        ResourceKind::Html,
+        urlencoded_form([("q", "stalled")]),
+        impatient_policy(),
+    );
+
+    assert_eq!(error.error, NetError::Timeout);
+    assert_eq!(ATTEMPTS.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn refused_connections_are_io_errors_not_timeouts() {
+    // Bind a port, then free it so nothing is listening there.
+    let closed = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+    let port = closed.local_addr().expect("addr").port();
+    drop(closed);
+
+    let error = collect_fetch_error(
+        format!("http://127.0.0.1:{port}/"),
+        ResourceKind::Html,
+        impatient_policy(),
+    );
+
+    assert_eq!(error.error, NetError::Io);
+}
+
+#[test]
+fn cancelling_during_a_backoff_gives_up_without_waiting_it_out() {
+    let closed = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+    let port = closed.local_addr().expect("addr").port();
+    drop(closed);
+    let policy = HttpRetryPolicy {
+        retries: 1,
+        initial_backoff: Duration::from_secs(30),
+    };
+    let cancel_token = AtomicBool::new(false);
+    let mut retried = 0;
+
+    let started = Instant::now();
+    let sent = thread::scope(|scope| {
+        scope.spawn(|| {
+            thread::sleep(Duration::from_millis(100));
+            cancel_token.store(true, Ordering::Relaxed);
+        });
+        send_with_retries(
+            &ureq::get(&format!("http://127.0.0.1:{port}/")),
+            &RequestPayload::default(),
+            &policy,
+            &cancel_token,
+            |_| retried += 1,
+        )
+    });
+
+    assert!(matches!(sent, Err(ureq::Error::Transport(_))));
+    assert_eq!(retried, 1);
+    assert!(started.elapsed() < Duration::from_secs(10));
+}
@@ -1,6 +1,6 @@
 use super::support::{HttpReply, TestHttpServer, collect_fetch, collect_fetch_error};
 use crate::{HttpClientPolicy, limits::should_stream_http_status};
-use core_types::{NetworkErrorKind, ResourceKind};
+use core_types::{NetError, ResourceKind};
 
 #[test]
 fn streams_error_documents_for_html_and_css() {
@@ -48,6 +48,5 @@ fn streams_html_404_body_but_classifies_image_404_as_http_error() {
         ResourceKind::Image,
         HttpClientPolicy::default(),
     );
-    assert_eq!(image_err.error_kind, NetworkErrorKind::HttpStatus);
-    assert_eq!(image_err.status_code, Some(404));
+    assert_eq!(image_err.error, NetError::HttpStatus(404));
 }
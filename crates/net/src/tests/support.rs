@@ -1,7 +1,7 @@
-use crate::{HttpClientPolicy, NetEvent, fetch::fetch_stream_with_policy};
-use core_types::{NetworkErrorKind, NetworkResponseInfo, ResourceKind};
+use crate::{HttpClientPolicy, NetEvent, Validators, fetch::fetch_stream_with_policy};
+use core_types::{NetError, NetworkResponseInfo, RequestPayload, ResourceKind};
 use rustls::pki_types::CertificateDer;
-use std::io::{BufRead, BufReader, Read, Write};
+use std::io::{self, BufRead, BufReader, Write};
 use std::net::TcpListener;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
@@ -14,6 +14,10 @@ pub(super) const LOCALHOST_KEY_PEM: &str = include_str!("../../testdata/localhos
 pub(super) const TEST_ROOT_CA_PEM: &str = include_str!("../../testdata/test-root-ca.pem");
 
 pub(super) struct FetchResult {
+    /// `(from, to)` of every redirect hop, in order.
+    pub(super) redirects: Vec<(String, String)>,
+    /// `(origin, reused)` of every request sent.
+    pub(super) connections: Vec<(String, bool)>,
     pub(super) start: StartEvent,
     pub(super) done: DoneEvent,
     pub(super) body: Vec<u8>,
@@ -28,6 +32,7 @@ pub(super) struct FetchTerminal {
 
 pub(super) struct StartEvent {
     pub(super) response: NetworkResponseInfo,
+    pub(super) validators: Option<Validators>,
 }
 
 pub(super) struct DoneEvent {
@@ -35,28 +40,41 @@ pub(super) struct DoneEvent {
 }
 
 pub(super) struct ErrorEvent {
-    pub(super) error_kind: NetworkErrorKind,
-    pub(super) status_code: Option<u16>,
-    pub(super) error: String,
+    pub(super) url: String,
+    pub(super) error: NetError,
+    pub(super) message: String,
 }
 
 pub(super) fn collect_fetch(
     url: String,
     kind: ResourceKind,
     policy: HttpClientPolicy,
+) -> FetchResult {
+    collect_fetch_with_payload(url, kind, RequestPayload::default(), policy)
+}
+
+pub(super) fn collect_fetch_with_payload(
+    url: String,
+    kind: ResourceKind,
+    payload: RequestPayload,
+    policy: HttpClientPolicy,
 ) -> FetchResult {
     let (tx, rx) = mpsc::channel();
     fetch_stream_with_policy(
         1,
         url.clone(),
         kind,
+        payload,
         policy,
+        None,
         Arc::new(AtomicBool::new(false)),
         Arc::new(move |event| {
             let _ = tx.send(event);
         }),
     );
 
+    let mut redirects = Vec::new();
+    let mut connections = Vec::new();
     let mut start = None;
     let mut body = Vec::new();
 
@@ -65,7 +83,25 @@ pub(super) fn collect_fetch(
             .recv_timeout(Duration::from_secs(5))
             .expect("fetch event")
         {
-            NetEvent::Start { response, .. } => start = Some(StartEvent { response }),
+            NetEvent::Redirected {
+                request_id,
+                from,
+                to,
+            } => {
+                assert_eq!(request_id, 1, "redirects keep the request id");
+                redirects.push((from, to));
+            }
+            NetEvent::Connection { origin, reused, .. } => connections.push((origin, reused)),
+            NetEvent::Start {
+                response,
+                validators,
+                ..
+            } => {
+                start = Some(StartEvent {
+                    response,
+                    validators,
+                })
+            }
             NetEvent::Chunk { chunk, .. } => body.extend_from_slice(&chunk),
             NetEvent::Done {
                 response: _response,
@@ -73,12 +109,53 @@ pub(super) fn collect_fetch(
                 ..
             } => {
                 return FetchResult {
+                    redirects,
+                    connections,
                     start: start.expect("start event"),
                     done: DoneEvent { bytes_received },
                     body,
                 };
             }
-            NetEvent::Error { error, .. } => panic!("unexpected fetch error: {error}"),
+            NetEvent::Error { message, .. } => panic!("unexpected fetch error: {message}"),
+            NetEvent::NotModified { .. } => panic!("unexpected not-modified answer"),
+        }
+    }
+}
+
+/// Fetch `url` revalidating a stored copy with `validators`. Returns the
+/// response of a `NotModified` answer, or `None` if a new body arrived.
+pub(super) fn collect_revalidation(url: String, validators: Validators) -> Option<StartEvent> {
+    let (tx, rx) = mpsc::channel();
+    fetch_stream_with_policy(
+        1,
+        url,
+        ResourceKind::Css,
+        RequestPayload::default(),
+        HttpClientPolicy::default(),
+        Some(validators),
+        Arc::new(AtomicBool::new(false)),
+        Arc::new(move |event| {
+            let _ = tx.send(event);
+        }),
+    );
+
+    loop {
+        match rx
+            .recv_timeout(Duration::from_secs(5))
+            .expect("fetch event")
+        {
+            NetEvent::NotModified { response, .. } => {
+                return Some(StartEvent {
+                    response,
+                    validators: None,
+                });
+            }
+            NetEvent::Done { .. } => return None,
+            NetEvent::Error { message, .. } => panic!("unexpected fetch error: {message}"),
+            NetEvent::Redirected { .. }
+            | NetEvent::Connection { .. }
+            | NetEvent::Start { .. }
+            | NetEvent::Chunk { .. } => {}
         }
     }
 }
@@ -87,13 +164,24 @@ pub(super) fn collect_fetch_error(
     url: String,
     kind: ResourceKind,
     policy: HttpClientPolicy,
+) -> ErrorEvent {
+    collect_fetch_error_with_payload(url, kind, RequestPayload::default(), policy)
+}
+
+pub(super) fn collect_fetch_error_with_payload(
+    url: String,
+    kind: ResourceKind,
+    payload: RequestPayload,
+    policy: HttpClientPolicy,
 ) -> ErrorEvent {
     let (tx, rx) = mpsc::channel();
     fetch_stream_with_policy(
         1,
         url,
         kind,
+        payload,
         policy,
+        None,
         Arc::new(AtomicBool::new(false)),
         Arc::new(move |event| {
             let _ = tx.send(event);
@@ -106,15 +194,15 @@ pub(super) fn collect_fetch_error(
             .expect("fetch event")
         {
             NetEvent::Error {
-                error_kind,
-                status_code,
+                url,
                 error,
+                message,
                 ..
             } => {
                 return ErrorEvent {
-                    error_kind,
-                    status_code,
+                    url,
                     error,
+                    message,
                 };
             }
             NetEvent::Done { .. } => panic!("unexpected successful fetch"),
@@ -133,7 +221,9 @@ pub(super) fn collect_fetch_terminal(
         1,
         url,
         kind,
+        RequestPayload::default(),
         policy,
+        None,
         Arc::new(AtomicBool::new(false)),
         Arc::new(move |event| {
             let _ = tx.send(event);
@@ -148,8 +238,19 @@ pub(super) fn collect_fetch_terminal(
             .recv_timeout(Duration::from_secs(5))
             .expect("fetch event")
         {
-            NetEvent::Start { response, .. } => start = Some(StartEvent { response }),
+            NetEvent::Start {
+                response,
+                validators,
+                ..
+            } => {
+                start = Some(StartEvent {
+                    response,
+                    validators,
+                })
+            }
+            NetEvent::Redirected { .. } | NetEvent::Connection { .. } => {}
             NetEvent::Chunk { chunk, .. } => body.extend_from_slice(&chunk),
+            NetEvent::NotModified { .. } => panic!("unexpected not-modified answer"),
             NetEvent::Done { bytes_received, .. } => {
                 assert!(
                     rx.recv_timeout(Duration::from_millis(200)).is_err(),
@@ -163,9 +264,9 @@ pub(super) fn collect_fetch_terminal(
                 };
             }
             NetEvent::Error {
-                error_kind,
-                status_code,
+                url,
                 error,
+                message,
                 ..
             } => {
                 assert!(
@@ -177,9 +278,9 @@ pub(super) fn collect_fetch_terminal(
                     done: None,
                     body,
                     error: Some(ErrorEvent {
-                        error_kind,
-                        status_code,
+                        url,
                         error,
+                        message,
                     }),
                 };
             }
@@ -188,13 +289,26 @@ pub(super) fn collect_fetch_terminal(
 }
 
 pub(super) struct RequestParts {
+    pub(super) method: String,
     pub(super) path: String,
+    headers: Vec<(String, String)>,
+    pub(super) body: Vec<u8>,
+}
+
+impl RequestParts {
+    pub(super) fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
 }
 
 pub(super) struct HttpReply {
     status_line: String,
     headers: Vec<(String, String)>,
     pub(super) body: Vec<u8>,
+    delay: Duration,
 }
 
 impl HttpReply {
@@ -209,20 +323,42 @@ impl HttpReply {
             status_line: status_line.to_string(),
             headers: built_headers,
             body,
+            delay: Duration::ZERO,
         }
     }
 
-    fn write_to(&self, mut stream: impl Write) {
-        write!(stream, "HTTP/1.1 {}\r\n", self.status_line).expect("status line");
+    /// Leave the connection open for the client's next request.
+    pub(super) fn keep_alive(mut self) -> Self {
+        self.headers.retain(|(name, _)| name != "Connection");
+        self
+    }
+
+    fn closes_connection(&self) -> bool {
+        self.headers.iter().any(|(name, _)| name == "Connection")
+    }
+
+    /// Stall for `delay` before answering, as an overloaded server would.
+    pub(super) fn after(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    fn write_to(&self, mut stream: impl Write) -> io::Result<()> {
+        thread::sleep(self.delay);
+        write!(stream, "HTTP/1.1 {}\r\n", self.status_line)?;
         for (name, value) in &self.headers {
-            write!(stream, "{name}: {value}\r\n").expect("header");
+            write!(stream, "{name}: {value}\r\n")?;
         }
-        write!(stream, "\r\n").expect("header terminator");
-        stream.write_all(&self.body).expect("body");
-        stream.flush().expect("flush");
+        write!(stream, "\r\n")?;
+        stream.write_all(&self.body)?;
+        stream.flush()
     }
 }
 
+/// Connections a [`TestHttpServer`] answers: enough for the longest
+/// redirect chain or retry sequence the tests follow.
+const TEST_HTTP_SERVER_CONNECTIONS: usize = 8;
+
 pub(super) struct TestHttpServer {
     addr: std::net::SocketAddr,
     _thread: thread::JoinHandle<()>,
@@ -233,10 +369,20 @@ impl TestHttpServer {
         let listener = TcpListener::bind("127.0.0.1:0").expect("bind http server");
         let addr = listener.local_addr().expect("http local addr");
         let thread = thread::spawn(move || {
-            for stream in listener.incoming().take(2) {
-                let mut stream = stream.expect("incoming stream");
-                let req = read_request(&mut stream);
-                handler(req).write_to(stream);
+            // Each connection gets its own thread so a stalled reply doesn't
+            // hold up the retry behind it. A client that timed out has hung
+            // up, so failed writes are expected.
+            for stream in listener.incoming().take(TEST_HTTP_SERVER_CONNECTIONS) {
+                let stream = stream.expect("incoming stream");
+                thread::spawn(move || {
+                    let mut reader = BufReader::new(&stream);
+                    while let Some(req) = read_request(&mut reader) {
+                        let reply = handler(req);
+                        if reply.write_to(&stream).is_err() || reply.closes_connection() {
+                            break;
+                        }
+                    }
+                });
             }
         });
 
@@ -289,13 +435,14 @@ impl TestHttpsServer {
             let (tcp, _) = listener.accept().expect("accept tls client");
             let conn = rustls::ServerConnection::new(server_config).expect("server conn");
             let mut tls = rustls::StreamOwned::new(conn, tcp);
-            let _ = read_request(&mut tls);
+            let _ = read_request(&mut BufReader::new(&mut tls));
             HttpReply::response(
                 "200 OK",
                 vec![("Content-Type", "text/html".to_string())],
                 b"<p>secure</p>".to_vec(),
             )
-            .write_to(&mut tls);
+            .write_to(&mut tls)
+            .expect("tls reply");
         });
 
         Self {
@@ -309,23 +456,39 @@ impl TestHttpsServer {
     }
 }
 
-fn read_request(stream: &mut impl Read) -> RequestParts {
-    let mut reader = BufReader::new(stream);
+/// The next request on a connection, or `None` once the client hung up.
+fn read_request(reader: &mut impl BufRead) -> Option<RequestParts> {
     let mut request_line = String::new();
-    reader.read_line(&mut request_line).expect("request line");
-    let path = request_line
-        .split_whitespace()
-        .nth(1)
-        .expect("request path")
-        .to_string();
+    if reader.read_line(&mut request_line).ok()? == 0 {
+        return None;
+    }
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().expect("request method").to_string();
+    let path = parts.next().expect("request path").to_string();
 
+    let mut headers = Vec::new();
     loop {
         let mut line = String::new();
         reader.read_line(&mut line).expect("header line");
         if line == "\r\n" || line.is_empty() {
             break;
         }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
     }
 
-    RequestParts { path }
+    let mut request = RequestParts {
+        method,
+        path,
+        headers,
+        body: Vec::new(),
+    };
+    let length = request
+        .header("Content-Length")
+        .and_then(|length| length.parse().ok())
+        .unwrap_or(0);
+    request.body = vec![0; length];
+    reader.read_exact(&mut request.body).expect("request body");
+    Some(request)
 }
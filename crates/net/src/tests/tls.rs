@@ -1,6 +1,6 @@
 use super::support::{TEST_ROOT_CA_PEM, TestHttpsServer, collect_fetch, collect_fetch_error};
 use crate::{HttpClientPolicy, TlsTrustStore};
-use core_types::{NetworkErrorKind, ResourceKind};
+use core_types::{NetError, ResourceKind};
 
 #[test]
 fn https_rejects_untrusted_cert_by_default() {
@@ -11,11 +11,11 @@ fn https_rejects_untrusted_cert_by_default() {
         HttpClientPolicy::default(),
     );
 
-    assert_eq!(error.error_kind, NetworkErrorKind::Transport);
+    assert_eq!(error.error, NetError::Tls);
     assert!(
-        error.error.contains("certificate") || error.error.contains("UnknownIssuer"),
+        error.message.contains("certificate") || error.message.contains("UnknownIssuer"),
         "expected certificate transport failure, got: {}",
-        error.error
+        error.message
     );
 }
 
@@ -0,0 +1,38 @@
+/// What a response offers for revalidating a stored copy of it.
+///
+/// A cache sends them back with a later request as `If-None-Match` and
+/// `If-Modified-Since`; a `304 Not Modified` answer means the stored body is
+/// still current.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Validators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl Validators {
+    /// The validators of `response`, or `None` if it has none or asks not
+    /// to be stored.
+    pub(crate) fn from_response(response: &ureq::Response) -> Option<Self> {
+        let no_store = response.header("Cache-Control").is_some_and(|value| {
+            value
+                .split(',')
+                .any(|directive| directive.trim().eq_ignore_ascii_case("no-store"))
+        });
+        let validators = Self {
+            etag: response.header("ETag").map(ToOwned::to_owned),
+            last_modified: response.header("Last-Modified").map(ToOwned::to_owned),
+        };
+        let usable = validators.etag.is_some() || validators.last_modified.is_some();
+        (usable && !no_store).then_some(validators)
+    }
+
+    pub(crate) fn apply(&self, mut request: ureq::Request) -> ureq::Request {
+        if let Some(etag) = &self.etag {
+            request = request.set("If-None-Match", etag);
+        }
+        if let Some(last_modified) = &self.last_modified {
+            request = request.set("If-Modified-Since", last_modified);
+        }
+        request
+    }
+}
@@ -0,0 +1,49 @@
+//! Chrome trace output for profiling page loads.
+//!
+//! The runtimes open `tracing` spans for every stage of a load, each tagged
+//! with its tab and request: `fetch` on the fetch thread, `parse` in the
+//! parse runtime, `style` and `layout` under `layout_job` in the layout
+//! runtime, and `paint` on the UI thread. With the `chrome-trace` feature
+//! those spans are written as Chrome trace JSON, which `chrome://tracing`
+//! and Perfetto load, so a slow load shows which stage it waited on.
+
+use std::path::Path;
+use tracing_chrome::{ChromeLayerBuilder, FlushGuard};
+use tracing_subscriber::prelude::*;
+
+/// Record spans to the trace file at `path` until the guard is dropped,
+/// which finishes the file.
+///
+/// Does nothing if a global subscriber is already installed.
+pub fn start_chrome_trace(path: impl AsRef<Path>) -> ChromeTraceGuard {
+    let (layer, flush) = ChromeLayerBuilder::new()
+        .file(path)
+        .include_args(true)
+        .build();
+    let _ = tracing_subscriber::registry().with(layer).try_init();
+    ChromeTraceGuard { _flush: flush }
+}
+
+/// Keeps the trace file open; see [`start_chrome_trace`].
+pub struct ChromeTraceGuard {
+    _flush: FlushGuard,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::start_chrome_trace;
+
+    #[test]
+    fn spans_are_written_to_the_trace_file() {
+        let path =
+            std::env::temp_dir().join(format!("borrowser-trace-{}.json", std::process::id()));
+        let guard = start_chrome_trace(&path);
+        tracing::info_span!("parse", tab_id = 1, request_id = 2).in_scope(|| {});
+        drop(guard);
+
+        let trace = std::fs::read_to_string(&path).expect("trace file");
+        let _ = std::fs::remove_file(&path);
+        assert!(trace.contains("\"parse\""), "unexpected trace: {trace}");
+        assert!(trace.contains("\"tab_id\""), "unexpected trace: {trace}");
+    }
+}
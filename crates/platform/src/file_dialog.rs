@@ -0,0 +1,63 @@
+use bus::{CoreCommand, CoreEvent};
+use core_types::TabId;
+use html::internal::Id;
+use rfd::AsyncFileDialog;
+use std::sync::mpsc;
+use std::thread::{self, JoinHandle};
+
+/// Show a file picker for each `PickFiles` command received. Dialogs still
+/// open at shutdown are left to close with the process.
+pub(crate) fn start_file_dialog_runtime(
+    cmd_rx: mpsc::Receiver<CoreCommand>,
+    evt_tx: mpsc::Sender<CoreEvent>,
+) -> JoinHandle<()> {
+    thread::spawn(move || {
+        while let Ok(cmd) = cmd_rx.recv() {
+            match cmd {
+                CoreCommand::PickFiles {
+                    tab_id,
+                    request_id,
+                    input_id,
+                    multiple,
+                } => spawn_file_picker(tab_id, request_id, input_id, multiple, evt_tx.clone()),
+                CoreCommand::Shutdown { ack } => {
+                    let _ = ack.into_inner().send(());
+                    return;
+                }
+                _ => {}
+            }
+        }
+    })
+}
+
+/// Show the native file picker for a page's file input without blocking the
+/// command router, and report the chosen files back on the event bus.
+fn spawn_file_picker(
+    tab_id: TabId,
+    request_id: u64,
+    input_id: Id,
+    multiple: bool,
+    evt_tx: mpsc::Sender<CoreEvent>,
+) {
+    thread::spawn(move || {
+        let dialog = AsyncFileDialog::new().set_title("Choose file");
+        let handles = if multiple {
+            pollster::block_on(dialog.pick_files())
+        } else {
+            pollster::block_on(dialog.pick_file()).map(|handle| vec![handle])
+        };
+        // A cancelled dialog leaves the current selection alone.
+        let Some(handles) = handles else {
+            return;
+        };
+        let _ = evt_tx.send(CoreEvent::FilesPicked {
+            tab_id,
+            request_id,
+            input_id,
+            paths: handles
+                .iter()
+                .map(|handle| handle.path().to_path_buf())
+                .collect(),
+        });
+    });
+}
@@ -0,0 +1,127 @@
+//! Coalesces redraw requests into at most one frame per display refresh.
+//!
+//! Runtime events, input, and the app's repaint handle all ask for frames,
+//! often many times between two refreshes of the display while a page
+//! streams in. The scheduler remembers what the next frame has to redo and
+//! when the last one started; the event loop asks it once per iteration
+//! whether to redraw now, later, or not at all.
+
+use std::time::{Duration, Instant};
+
+/// Refresh interval assumed until the monitor reports its own.
+pub(crate) const DEFAULT_FRAME_INTERVAL: Duration = Duration::from_nanos(16_666_667);
+
+/// What a frame has to redo; a frame that lays out also paints.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum FrameWork {
+    /// Only the look of what is on screen changed: hover, focus, scrolling,
+    /// animations.
+    Paint,
+    /// Content, window size, or theme changed, so the app may have to style
+    /// and lay out again.
+    Layout,
+}
+
+pub(crate) struct FrameScheduler {
+    /// Work requested since the last frame started.
+    pending: Option<FrameWork>,
+    last_frame: Option<Instant>,
+    interval: Duration,
+}
+
+impl Default for FrameScheduler {
+    fn default() -> Self {
+        Self::new(DEFAULT_FRAME_INTERVAL)
+    }
+}
+
+impl FrameScheduler {
+    pub(crate) fn new(interval: Duration) -> Self {
+        Self {
+            pending: None,
+            last_frame: None,
+            interval,
+        }
+    }
+
+    /// Use the refresh rate of the monitor the window is on.
+    pub(crate) fn set_refresh_rate_millihertz(&mut self, millihertz: u32) {
+        if millihertz > 0 {
+            self.interval = Duration::from_secs(1000) / millihertz;
+        }
+    }
+
+    pub(crate) fn request(&mut self, work: FrameWork) {
+        self.pending = self.pending.max(Some(work));
+    }
+
+    /// When the next frame should be drawn: `None` if nothing asked for
+    /// one, otherwise one refresh interval after the last frame started, or
+    /// `now` if that already passed.
+    pub(crate) fn next_frame(&self, now: Instant) -> Option<Instant> {
+        self.pending?;
+        let due = self
+            .last_frame
+            .map_or(now, |last| (last + self.interval).max(now));
+        Some(due)
+    }
+
+    /// Start a frame and return what it has to redo. A frame nothing asked
+    /// for, such as one the window system requests after exposing the
+    /// window, only paints.
+    pub(crate) fn begin_frame(&mut self, now: Instant) -> FrameWork {
+        self.last_frame = Some(now);
+        self.pending.take().unwrap_or(FrameWork::Paint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FrameScheduler, FrameWork};
+    use std::time::{Duration, Instant};
+
+    const INTERVAL: Duration = Duration::from_millis(16);
+
+    #[test]
+    fn requests_between_frames_become_one_frame_doing_the_most_work() {
+        let mut frames = FrameScheduler::new(INTERVAL);
+        let start = Instant::now();
+        assert_eq!(frames.next_frame(start), None);
+
+        frames.request(FrameWork::Paint);
+        frames.request(FrameWork::Layout);
+        frames.request(FrameWork::Paint);
+        assert_eq!(frames.next_frame(start), Some(start));
+        assert_eq!(frames.begin_frame(start), FrameWork::Layout);
+        assert_eq!(frames.next_frame(start), None);
+    }
+
+    #[test]
+    fn a_frame_waits_for_the_refresh_interval_to_pass() {
+        let mut frames = FrameScheduler::new(INTERVAL);
+        let start = Instant::now();
+        frames.begin_frame(start);
+
+        frames.request(FrameWork::Paint);
+        let soon = start + Duration::from_millis(5);
+        assert_eq!(frames.next_frame(soon), Some(start + INTERVAL));
+
+        let late = start + Duration::from_millis(40);
+        assert_eq!(frames.next_frame(late), Some(late));
+        assert_eq!(frames.begin_frame(late), FrameWork::Paint);
+    }
+
+    #[test]
+    fn the_interval_follows_the_monitor_refresh_rate() {
+        let mut frames = FrameScheduler::default();
+        frames.set_refresh_rate_millihertz(120_000);
+        let start = Instant::now();
+        frames.begin_frame(start);
+        frames.request(FrameWork::Paint);
+
+        assert_eq!(
+            frames.next_frame(start),
+            Some(start + Duration::from_nanos(8_333_333))
+        );
+    }
+}
@@ -1,22 +1,47 @@
 use app_api::{Repaint, RepaintHandle, UiApp};
-use bus::{CoreCommand, CoreEvent};
+use bus::{ChunkCredits, CoreCommand, CoreEvent};
+use core_types::TabId;
 use egui::Visuals;
 use gfx::Renderer;
 use runtime_css::start_css_runtime;
+use runtime_image::start_image_runtime;
+use runtime_layout::start_layout_runtime;
 use runtime_net::start_net_runtime;
 use runtime_parse::start_parse_runtime;
+use runtime_script::start_script_runtime;
+use runtime_timer::start_timer_runtime;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::mpsc;
-use std::{thread, time::Duration};
+use std::thread;
+use std::time::{Duration, Instant};
 use winit::{
     application::ApplicationHandler,
     dpi::PhysicalSize,
     event::WindowEvent,
-    event_loop::{ActiveEventLoop, EventLoop, EventLoopProxy},
+    event_loop::{ActiveEventLoop, ControlFlow, EventLoop, EventLoopProxy},
     window::{Theme, Window, WindowId},
 };
 
+#[cfg(feature = "chrome-trace")]
+mod chrome_trace;
+mod file_dialog;
+mod frame_scheduler;
+mod router;
+mod shutdown;
+mod tab_runtimes;
+
+#[cfg(feature = "chrome-trace")]
+pub use chrome_trace::{ChromeTraceGuard, start_chrome_trace};
+use frame_scheduler::{FrameScheduler, FrameWork};
+use router::{Router, router_thread};
+pub use shutdown::{RuntimeThreads, SHUTDOWN_TIMEOUT};
+
+const WINDOW_TITLE: &str = "Borrowser";
+
+/// Tab id of the platform's own timers; the app numbers its tabs from 1.
+const PLATFORM_TIMER_TAB: TabId = 0;
+
 pub enum UserEvent {
     Core(CoreEvent),
     Repaint,
@@ -34,6 +59,110 @@ pub fn run_with<A: UiApp + 'static>(app: A) {
     event_loop.run_app(&mut platform).expect("crashed");
 }
 
+/// Start the net and timer runtimes and the command router, which starts
+/// parse, css, layout, image, and script runtimes for each tab.
+///
+/// Returns the bus ends: commands go into the sender, events from every
+/// runtime come out of the receiver. Used by the windowed app and by
+/// headless tools that drive a tab without a window, which stop the
+/// runtimes with [`RuntimeThreads::shutdown`] before exiting.
+pub fn start_runtimes() -> (
+    mpsc::Sender<CoreCommand>,
+    mpsc::Receiver<CoreEvent>,
+    RuntimeThreads,
+) {
+    // --- create Bus channels (one cmd in, one evt out) ---
+    let (cmd_tx_main, cmd_rx_main) = mpsc::channel::<CoreCommand>();
+    let (evt_tx_main, evt_rx_main) = mpsc::channel::<CoreEvent>();
+
+    // Document chunks are flow controlled between the net runtime and the
+    // tab's parse runtime; see `bus::ChunkCredits`.
+    let credits = ChunkCredits::default();
+    let mut router = Router::new(credits.clone());
+
+    // --- shared runtimes (one for all tabs) ---
+    let (evt_tx, net_credits) = (evt_tx_main.clone(), credits.clone());
+    router.register(is_net_command, move |cmd_rx| {
+        start_net_runtime(cmd_rx, evt_tx, net_credits)
+    });
+
+    let evt_tx = evt_tx_main.clone();
+    router.register(
+        |cmd| {
+            matches!(
+                cmd,
+                CoreCommand::SetTimeout { .. } | CoreCommand::ClearTimeout { .. }
+            )
+        },
+        move |cmd_rx| start_timer_runtime(cmd_rx, evt_tx),
+    );
+
+    // File dialogs are shown by the platform itself
+    let evt_tx = evt_tx_main.clone();
+    router.register(
+        |cmd| matches!(cmd, CoreCommand::PickFiles { .. }),
+        move |cmd_rx| file_dialog::start_file_dialog_runtime(cmd_rx, evt_tx),
+    );
+
+    // --- per-tab runtimes, started with a tab's first command ---
+    let (evt_tx, parse_credits) = (evt_tx_main.clone(), credits);
+    router.register_per_tab(
+        is_parse_command,
+        Box::new(move |cmd_rx| start_parse_runtime(cmd_rx, evt_tx.clone(), parse_credits.clone())),
+    );
+    let evt_tx = evt_tx_main.clone();
+    router.register_per_tab(
+        is_css_command,
+        Box::new(move |cmd_rx| start_css_runtime(cmd_rx, evt_tx.clone())),
+    );
+    let evt_tx = evt_tx_main.clone();
+    router.register_per_tab(
+        |cmd| matches!(cmd, CoreCommand::LayoutDocument { .. }),
+        Box::new(move |cmd_rx| start_layout_runtime(cmd_rx, evt_tx.clone())),
+    );
+    let evt_tx = evt_tx_main.clone();
+    router.register_per_tab(
+        |cmd| matches!(cmd, CoreCommand::DecodeImage { .. }),
+        Box::new(move |cmd_rx| start_image_runtime(cmd_rx, evt_tx.clone())),
+    );
+    let evt_tx = evt_tx_main;
+    router.register_per_tab(
+        |cmd| matches!(cmd, CoreCommand::RunScript { .. }),
+        Box::new(move |cmd_rx| start_script_runtime(cmd_rx, evt_tx.clone())),
+    );
+
+    // --- route CoreCommand → proper runtime ---
+    let router = router_thread(cmd_rx_main, router);
+
+    let threads = RuntimeThreads::new(cmd_tx_main.clone(), router);
+    (cmd_tx_main, evt_rx_main, threads)
+}
+
+fn is_net_command(cmd: &CoreCommand) -> bool {
+    matches!(
+        cmd,
+        CoreCommand::FetchStream { .. } | CoreCommand::CancelRequest { .. }
+    )
+}
+
+fn is_parse_command(cmd: &CoreCommand) -> bool {
+    matches!(
+        cmd,
+        CoreCommand::ParseHtmlStart { .. }
+            | CoreCommand::ParseHtmlChunk { .. }
+            | CoreCommand::ParseHtmlDone { .. }
+            | CoreCommand::ParseHtmlResume { .. }
+            | CoreCommand::DomUpdateApplied { .. }
+    )
+}
+
+fn is_css_command(cmd: &CoreCommand) -> bool {
+    matches!(
+        cmd,
+        CoreCommand::CssChunk { .. } | CoreCommand::CssDone { .. } | CoreCommand::CssAbort { .. }
+    )
+}
+
 fn start_bus_bridge(
     proxy: EventLoopProxy<UserEvent>,
     evt_rx: std::sync::mpsc::Receiver<bus::CoreEvent>,
@@ -45,44 +174,20 @@ fn start_bus_bridge(
     });
 }
 
-fn router_thread(
-    cmd_rx_main: mpsc::Receiver<CoreCommand>,
-    net_tx: mpsc::Sender<CoreCommand>,
-    parse_tx: mpsc::Sender<CoreCommand>,
-    css_tx: mpsc::Sender<CoreCommand>,
-) {
-    thread::spawn(move || {
-        while let Ok(cmd) = cmd_rx_main.recv() {
-            match cmd {
-                // Networking goes to net runtime
-                CoreCommand::FetchStream { .. } | CoreCommand::CancelRequest { .. } => {
-                    let _ = net_tx.send(cmd);
-                }
-
-                // HTML parsing commands go to parse runtime
-                CoreCommand::ParseHtmlStart { .. }
-                | CoreCommand::ParseHtmlChunk { .. }
-                | CoreCommand::ParseHtmlDone { .. } => {
-                    let _ = parse_tx.send(cmd);
-                }
-
-                // CSS byte streaming/assembly goes to css runtime
-                CoreCommand::CssChunk { .. }
-                | CoreCommand::CssDone { .. }
-                | CoreCommand::CssAbort { .. } => {
-                    let _ = css_tx.send(cmd);
-                }
-            }
-        }
-    });
-}
-
 struct PlatformApp {
     window: Option<Arc<Window>>,
     proxy: EventLoopProxy<UserEvent>,
     renderer: Option<Renderer>,
     repaint: Option<Arc<PlatformRepaint>>,
     app: Option<Box<dyn UiApp>>,
+    /// Runtime threads, stopped before the event loop exits.
+    runtimes: Option<RuntimeThreads>,
+    /// Title last set on the window.
+    window_title: String,
+    /// OS theme last reported to the app.
+    dark_mode: Option<bool>,
+    /// Decides when the redraws asked for are drawn.
+    frames: FrameScheduler,
 }
 
 impl PlatformApp {
@@ -93,6 +198,10 @@ impl PlatformApp {
             renderer: None,
             repaint: None,
             app: None,
+            runtimes: None,
+            window_title: WINDOW_TITLE.to_string(),
+            dark_mode: None,
+            frames: FrameScheduler::default(),
         }
     }
 
@@ -101,9 +210,10 @@ impl PlatformApp {
             return;
         }
         let raw_window = event_loop
-            .create_window(Window::default_attributes().with_title("Borrowser"))
+            .create_window(Window::default_attributes().with_title(WINDOW_TITLE))
             .expect("create window");
         let window = Arc::new(raw_window);
+        self.follow_refresh_rate(&window);
         self.window = Some(window);
     }
 
@@ -116,24 +226,39 @@ impl PlatformApp {
         self.renderer = Some(renderer);
     }
 
+    /// Pace frames to the refresh rate of the monitor the window is on.
+    fn follow_refresh_rate(&mut self, window: &Window) {
+        if let Some(millihertz) = window
+            .current_monitor()
+            .and_then(|monitor| monitor.refresh_rate_millihertz())
+        {
+            self.frames.set_refresh_rate_millihertz(millihertz);
+        }
+    }
+
     fn on_resize(&mut self, new_size: PhysicalSize<u32>) {
         if let Some(renderer) = self.renderer.as_mut() {
             renderer.resize(new_size);
         }
     }
 
-    fn draw_frame(&mut self) {
+    fn draw_frame(&mut self, work: FrameWork) {
         let window = self.window.as_ref().unwrap();
         let renderer = self.renderer.as_mut().unwrap();
         let app = self.app.as_mut().expect("UiApp not injected");
 
         // ---- 1) Sync egui visuals with OS theme ----
-        if let Some(theme) = window.theme() {
+        // The theme only changes with a `ThemeChanged` event, which asks for
+        // layout; frames that only paint keep the visuals they have.
+        if work == FrameWork::Layout
+            && let Some(theme) = window.theme()
+        {
             let ctx = renderer.context();
             let visuals = ctx.style().visuals.clone();
 
             let want_dark = matches!(theme, Theme::Dark);
             let is_current_dark = visuals.dark_mode;
+            report_dark_mode(app.as_mut(), &mut self.dark_mode, want_dark);
 
             if want_dark != is_current_dark {
                 if want_dark {
@@ -146,6 +271,21 @@ impl PlatformApp {
 
         // ---- 2) Normal rendering ----
         renderer.render(window.as_ref(), |ctx| app.ui(ctx));
+
+        // ---- 3) Follow the app's title (e.g. the active page's <title>) ----
+        if let Some(title) = app.window_title()
+            && title != self.window_title
+        {
+            window.set_title(&title);
+            self.window_title = title;
+        }
+    }
+}
+
+fn report_dark_mode(app: &mut dyn UiApp, reported: &mut Option<bool>, dark: bool) {
+    if *reported != Some(dark) {
+        *reported = Some(dark);
+        app.set_dark_mode(dark);
     }
 }
 
@@ -155,33 +295,17 @@ impl ApplicationHandler<UserEvent> for PlatformApp {
         self.init_window(event_loop);
         self.init_renderer();
 
-        // --- create Bus channels (one cmd in, one evt out) ---
-        let (cmd_tx_main, cmd_rx_main) = mpsc::channel::<CoreCommand>();
-        let (evt_tx_main, evt_rx_main) = mpsc::channel::<CoreEvent>();
-
-        // --- per-runtime command channels ---
-        let (net_cmd_tx, net_cmd_rx) = mpsc::channel::<CoreCommand>();
-        let (par_cmd_tx, par_cmd_rx) = mpsc::channel::<CoreCommand>();
-        let (css_cmd_tx, css_cmd_rx) = mpsc::channel::<CoreCommand>();
-
-        // --- start runtimes (each gets its cmd_rx + shared evt_tx) ---
-        start_net_runtime(net_cmd_rx, evt_tx_main.clone());
-        start_parse_runtime(par_cmd_rx, evt_tx_main.clone());
-        start_css_runtime(css_cmd_rx, evt_tx_main.clone());
-
-        // --- route CoreCommand → proper runtime ---
-        router_thread(
-            cmd_rx_main,
-            net_cmd_tx.clone(),
-            par_cmd_tx.clone(),
-            css_cmd_tx.clone(),
-        );
+        let (cmd_tx_main, evt_rx_main, runtimes) = start_runtimes();
+        self.runtimes = Some(runtimes);
 
         // --- bridge CoreEvent → winit user events ---
         start_bus_bridge(self.proxy.clone(), evt_rx_main);
 
         // --- install repaint handle (unchanged) ---
-        let repaint = Arc::new(PlatformRepaint::new(self.proxy.clone()));
+        let repaint = Arc::new(PlatformRepaint::new(
+            self.proxy.clone(),
+            cmd_tx_main.clone(),
+        ));
         self.repaint = Some(repaint.clone());
         if let Some(app) = self.app.as_mut() {
             let handle: RepaintHandle = repaint;
@@ -192,29 +316,31 @@ impl ApplicationHandler<UserEvent> for PlatformApp {
         }
 
         // --- first frame ---
-        if let Some(window) = self.window.as_ref() {
-            window.request_redraw();
-        }
+        self.frames.request(FrameWork::Layout);
     }
 
     fn user_event(&mut self, _event_loop: &ActiveEventLoop, event: UserEvent) {
         match event {
+            UserEvent::Core(CoreEvent::TimeoutFired {
+                tab_id: PLATFORM_TIMER_TAB,
+                ..
+            }) => {
+                if let Some(repaint) = self.repaint.as_ref() {
+                    repaint.request_now();
+                }
+            }
             UserEvent::Core(core_evt) => {
                 if let Some(app) = self.app.as_mut() {
                     // new single entry-point:
                     app.on_core_event(core_evt);
                 }
-                if let Some(window) = self.window.as_ref() {
-                    window.request_redraw();
-                }
+                self.frames.request(FrameWork::Layout);
             }
             UserEvent::Repaint => {
                 if let Some(repaint) = self.repaint.as_ref() {
                     repaint.clear_pending();
                 }
-                if let Some(window) = self.window.as_ref() {
-                    window.request_redraw();
-                }
+                self.frames.request(FrameWork::Paint);
             }
         }
     }
@@ -224,19 +350,32 @@ impl ApplicationHandler<UserEvent> for PlatformApp {
             renderer.on_window_event(window.as_ref(), &event);
         }
         match event {
-            WindowEvent::ThemeChanged(_theme) => {
-                // Don’t set visuals here anymore, just redraw.
-                if let Some(window) = self.window.as_ref() {
-                    window.request_redraw();
+            WindowEvent::ThemeChanged(theme) => {
+                // Visuals follow in the next frame; the app restyles pages now.
+                if let Some(app) = self.app.as_mut() {
+                    report_dark_mode(app.as_mut(), &mut self.dark_mode, theme == Theme::Dark);
                 }
+                self.frames.request(FrameWork::Layout);
             }
             WindowEvent::CloseRequested => {
+                if let Some(app) = self.app.as_mut() {
+                    app.on_exit();
+                }
+                if let Some(runtimes) = self.runtimes.take() {
+                    let running = runtimes.shutdown(SHUTDOWN_TIMEOUT);
+                    if running > 0 {
+                        eprintln!("{running} runtime thread(s) still running at exit");
+                    }
+                }
                 event_loop.exit();
             }
             WindowEvent::Resized(new_size) => {
                 self.on_resize(new_size);
-                if let Some(window) = self.window.as_ref() {
-                    window.request_redraw();
+                self.frames.request(FrameWork::Layout);
+            }
+            WindowEvent::Moved(_) => {
+                if let Some(window) = self.window.clone() {
+                    self.follow_refresh_rate(&window);
                 }
             }
             WindowEvent::KeyboardInput { .. }
@@ -245,28 +384,49 @@ impl ApplicationHandler<UserEvent> for PlatformApp {
             | WindowEvent::MouseWheel { .. }
             | WindowEvent::ModifiersChanged(_)
             | WindowEvent::Touch { .. } => {
-                if let Some(window) = self.window.as_ref() {
-                    window.request_redraw();
-                }
+                self.frames.request(FrameWork::Paint);
             }
             WindowEvent::RedrawRequested => {
-                self.draw_frame();
+                let work = self.frames.begin_frame(Instant::now());
+                self.draw_frame(work);
             }
             _ => {}
         }
     }
+
+    /// Once the events of this loop iteration are handled, redraw if a
+    /// frame is due, or sleep until it is.
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        let Some(window) = self.window.as_ref() else {
+            return;
+        };
+        let now = Instant::now();
+        match self.frames.next_frame(now) {
+            Some(due) if due > now => event_loop.set_control_flow(ControlFlow::WaitUntil(due)),
+            Some(_) => {
+                window.request_redraw();
+                event_loop.set_control_flow(ControlFlow::Wait);
+            }
+            None => event_loop.set_control_flow(ControlFlow::Wait),
+        }
+    }
 }
 
 pub struct PlatformRepaint {
-    pending: Arc<AtomicBool>,
+    pending: AtomicBool,
     proxy: EventLoopProxy<UserEvent>,
+    /// Delayed repaints are timeouts of the timer runtime.
+    cmd_tx: mpsc::Sender<CoreCommand>,
+    next_timer: AtomicU64,
 }
 
 impl PlatformRepaint {
-    pub fn new(proxy: EventLoopProxy<UserEvent>) -> Self {
+    pub fn new(proxy: EventLoopProxy<UserEvent>, cmd_tx: mpsc::Sender<CoreCommand>) -> Self {
         Self {
-            pending: Arc::new(AtomicBool::new(false)),
+            pending: AtomicBool::new(false),
             proxy,
+            cmd_tx,
+            next_timer: AtomicU64::new(0),
         }
     }
 
@@ -283,13 +443,13 @@ impl Repaint for PlatformRepaint {
     }
 
     fn request_after(&self, duration: Duration) {
-        let pending = self.pending.clone();
-        let proxy = self.proxy.clone();
-        thread::spawn(move || {
-            thread::sleep(duration);
-            if !pending.swap(true, Ordering::AcqRel) {
-                let _ = proxy.send_event(UserEvent::Repaint);
-            }
+        // Each request gets its own token so a later, longer delay does not
+        // replace an earlier one.
+        let token = self.next_timer.fetch_add(1, Ordering::Relaxed);
+        let _ = self.cmd_tx.send(CoreCommand::SetTimeout {
+            tab_id: PLATFORM_TIMER_TAB,
+            token,
+            delay: duration,
         });
     }
 }
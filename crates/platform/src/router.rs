@@ -0,0 +1,441 @@
+//! Routing of `CoreCommand`s to the runtimes.
+//!
+//! Runtimes register with the [`Router`] together with a matcher for the
+//! commands they take, so adding one does not touch the routing itself.
+//!
+//! Commands that pile up while the router is busy are scheduled rather than
+//! passed through in arrival order: network commands go first so fetches
+//! start and cancellations land promptly, then document parsing, layout,
+//! stylesheets, and image decoding. Commands for one runtime keep their
+//! order, and a tab's close is forwarded after everything queued before it.
+//!
+//! A cancelled load is not worth finishing: once a `CancelRequest` or
+//! `CancelGeneration` for a tab's request comes in, streaming commands of
+//! that and older requests are dropped instead of forwarded. Work for the
+//! page still on screen, such as layout jobs and image decodes, is forwarded
+//! regardless.
+//!
+//! `Shutdown` is queued behind everything else. The router passes it on to
+//! every runtime and stops, handing back the runtime threads so they can be
+//! joined.
+
+use bus::{ChunkCredits, CoreCommand, InProcess};
+use core_types::{RequestId, TabId};
+use std::collections::{HashMap, VecDeque};
+use std::sync::mpsc;
+use std::thread::{self, JoinHandle};
+
+use crate::tab_runtimes::{StartRuntime, TabRuntimeId, TabRuntimes};
+
+/// Picks the commands a registered runtime takes.
+pub(crate) type CommandMatcher = fn(&CoreCommand) -> bool;
+
+enum RouteTarget {
+    /// One runtime serving every tab.
+    Shared(mpsc::Sender<CoreCommand>),
+    /// A runtime each tab gets its own worker of.
+    PerTab(TabRuntimeId),
+}
+
+/// Forwards each command to the first registered runtime whose matcher
+/// accepts it.
+///
+/// Runtimes plug in through [`Router::register`] and
+/// [`Router::register_per_tab`]; the router itself only knows the commands
+/// that concern every runtime: `CancelGeneration` reaches all of them, and
+/// `TabClosed` stops the tab's per-tab workers and reaches the shared ones.
+pub(crate) struct Router {
+    routes: Vec<(CommandMatcher, RouteTarget)>,
+    /// Threads of the shared runtimes.
+    threads: Vec<JoinHandle<()>>,
+    tab_runtimes: TabRuntimes,
+}
+
+impl Router {
+    pub(crate) fn new(credits: ChunkCredits) -> Self {
+        Self {
+            routes: Vec::new(),
+            threads: Vec::new(),
+            tab_runtimes: TabRuntimes::new(credits),
+        }
+    }
+
+    /// Send the commands `matcher` accepts to a runtime shared by all tabs,
+    /// started right away with `start`.
+    pub(crate) fn register(
+        &mut self,
+        matcher: CommandMatcher,
+        start: impl FnOnce(mpsc::Receiver<CoreCommand>) -> JoinHandle<()>,
+    ) {
+        let (tx, rx) = mpsc::channel::<CoreCommand>();
+        self.threads.push(start(rx));
+        self.routes.push((matcher, RouteTarget::Shared(tx)));
+    }
+
+    /// Send the commands `matcher` accepts to a worker of the tab they
+    /// belong to, started with `start` on the tab's first command.
+    pub(crate) fn register_per_tab(&mut self, matcher: CommandMatcher, start: StartRuntime) {
+        let runtime = self.tab_runtimes.add(start);
+        self.routes.push((matcher, RouteTarget::PerTab(runtime)));
+    }
+
+    fn route(&mut self, cmd: CoreCommand) {
+        match cmd {
+            // A navigation's cancellation reaches every runtime
+            CoreCommand::CancelGeneration { tab_id, request_id } => {
+                self.tab_runtimes.cancel_generation(tab_id, request_id);
+                for (_, target) in &self.routes {
+                    if let RouteTarget::Shared(tx) = target {
+                        let _ = tx.send(CoreCommand::CancelGeneration { tab_id, request_id });
+                    }
+                }
+            }
+            // A tab's close stops its workers; shared runtimes drop what
+            // they keep for the tab, such as its timers
+            CoreCommand::TabClosed { tab_id } => {
+                self.tab_runtimes.close(tab_id);
+                for (_, target) in &self.routes {
+                    if let RouteTarget::Shared(tx) = target {
+                        let _ = tx.send(CoreCommand::TabClosed { tab_id });
+                    }
+                }
+            }
+            // Handled by the router thread, which stops routing
+            CoreCommand::Shutdown { .. } => {}
+            cmd => {
+                let Some((_, target)) = self.routes.iter().find(|(matches, _)| matches(&cmd))
+                else {
+                    return;
+                };
+                match target {
+                    RouteTarget::Shared(tx) => {
+                        let _ = tx.send(cmd);
+                    }
+                    RouteTarget::PerTab(runtime) => {
+                        if let Some((tab_id, _)) = cmd.request() {
+                            self.tab_runtimes.send(*runtime, tab_id, cmd);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Ask every runtime to shut down, acknowledging on `ack`, and return
+    /// their threads.
+    fn shutdown(mut self, ack: &mpsc::Sender<()>) -> Vec<JoinHandle<()>> {
+        for (_, target) in &self.routes {
+            if let RouteTarget::Shared(tx) = target {
+                let _ = tx.send(CoreCommand::Shutdown {
+                    ack: InProcess::new(ack.clone()),
+                });
+            }
+        }
+        let mut threads = self.tab_runtimes.shutdown(ack);
+        threads.append(&mut self.threads);
+        threads
+    }
+}
+
+/// Route commands until `Shutdown` arrives or every command sender is gone.
+/// The router acknowledges a shutdown on its `ack` as well, once it passed
+/// it on, and the thread returns the runtime threads.
+pub(crate) fn router_thread(
+    cmd_rx_main: mpsc::Receiver<CoreCommand>,
+    mut router: Router,
+) -> JoinHandle<Vec<JoinHandle<()>>> {
+    thread::spawn(move || {
+        let mut queue = CommandQueue::default();
+        loop {
+            for cmd in cmd_rx_main.try_iter() {
+                queue.push(cmd);
+            }
+            let Some(cmd) = queue.pop() else {
+                match cmd_rx_main.recv() {
+                    Ok(cmd) => queue.push(cmd),
+                    Err(_) => return Vec::new(),
+                }
+                continue;
+            };
+            if let CoreCommand::Shutdown { ack } = cmd {
+                let ack = ack.into_inner();
+                let threads = router.shutdown(&ack);
+                let _ = ack.send(());
+                return threads;
+            }
+            router.route(cmd);
+        }
+    })
+}
+
+/// Scheduling class of a command; lower lanes are serviced first.
+#[derive(Clone, Copy, Debug)]
+enum Lane {
+    Net,
+    Parse,
+    Layout,
+    Css,
+    Image,
+    /// Tab closes and the shutdown go last so they follow every command
+    /// queued before them.
+    Close,
+}
+
+const LANES: usize = Lane::Close as usize + 1;
+
+impl Lane {
+    fn of(cmd: &CoreCommand) -> Self {
+        match cmd {
+            CoreCommand::FetchStream { .. }
+            | CoreCommand::CancelRequest { .. }
+            | CoreCommand::CancelGeneration { .. }
+            | CoreCommand::PickFiles { .. }
+            | CoreCommand::SetTimeout { .. }
+            | CoreCommand::ClearTimeout { .. } => Lane::Net,
+            CoreCommand::ParseHtmlStart { .. }
+            | CoreCommand::ParseHtmlChunk { .. }
+            | CoreCommand::ParseHtmlDone { .. }
+            | CoreCommand::ParseHtmlResume { .. }
+            | CoreCommand::DomUpdateApplied { .. }
+            | CoreCommand::RunScript { .. } => Lane::Parse,
+            CoreCommand::LayoutDocument { .. } => Lane::Layout,
+            CoreCommand::CssChunk { .. }
+            | CoreCommand::CssDone { .. }
+            | CoreCommand::CssAbort { .. } => Lane::Css,
+            CoreCommand::DecodeImage { .. } => Lane::Image,
+            CoreCommand::TabClosed { .. } | CoreCommand::Shutdown { .. } => Lane::Close,
+        }
+    }
+}
+
+/// Commands waiting to be forwarded.
+#[derive(Default)]
+struct CommandQueue {
+    lanes: [VecDeque<CoreCommand>; LANES],
+    /// Newest cancelled request per tab.
+    cancelled: HashMap<TabId, RequestId>,
+}
+
+impl CommandQueue {
+    fn push(&mut self, cmd: CoreCommand) {
+        if let CoreCommand::CancelRequest { tab_id, request_id }
+        | CoreCommand::CancelGeneration { tab_id, request_id } = cmd
+        {
+            let newest = self.cancelled.entry(tab_id).or_insert(request_id);
+            *newest = (*newest).max(request_id);
+        }
+        self.lanes[Lane::of(&cmd) as usize].push_back(cmd);
+    }
+
+    /// The next command to forward, skipping those of cancelled loads.
+    fn pop(&mut self) -> Option<CoreCommand> {
+        loop {
+            let cmd = self.lanes.iter_mut().find_map(VecDeque::pop_front)?;
+            if let CoreCommand::TabClosed { tab_id } = cmd {
+                self.cancelled.remove(&tab_id);
+            }
+            if !self.is_cancelled(&cmd) {
+                return Some(cmd);
+            }
+        }
+    }
+
+    fn is_cancelled(&self, cmd: &CoreCommand) -> bool {
+        let streaming = matches!(
+            cmd,
+            CoreCommand::FetchStream { .. }
+                | CoreCommand::ParseHtmlStart { .. }
+                | CoreCommand::ParseHtmlChunk { .. }
+                | CoreCommand::ParseHtmlDone { .. }
+                | CoreCommand::ParseHtmlResume { .. }
+                | CoreCommand::DomUpdateApplied { .. }
+                | CoreCommand::RunScript { .. }
+                | CoreCommand::CssChunk { .. }
+                | CoreCommand::CssDone { .. }
+        );
+        streaming
+            && cmd.request().is_some_and(|(tab_id, request_id)| {
+                self.cancelled
+                    .get(&tab_id)
+                    .is_some_and(|&cancelled| request_id <= cancelled)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CommandQueue, Router, router_thread};
+    use bus::{ChunkCredits, CoreCommand, InProcess};
+    use core_types::StylesheetSlotId;
+    use std::sync::mpsc;
+    use std::thread::{self, JoinHandle};
+    use std::time::Duration;
+
+    fn parse_chunk(tab_id: u64, request_id: u64) -> CoreCommand {
+        CoreCommand::ParseHtmlChunk {
+            tab_id,
+            request_id,
+            bytes: b"<p>".to_vec(),
+        }
+    }
+
+    fn css_chunk(tab_id: u64, request_id: u64) -> CoreCommand {
+        CoreCommand::CssChunk {
+            tab_id,
+            request_id,
+            stylesheet_slot_id: StylesheetSlotId(1),
+            url: "https://example.com/site.css".to_string(),
+            bytes: b"p{}".to_vec(),
+        }
+    }
+
+    fn drain(queue: &mut CommandQueue) -> Vec<String> {
+        std::iter::from_fn(|| queue.pop())
+            .map(|cmd| match cmd {
+                CoreCommand::ParseHtmlChunk {
+                    tab_id, request_id, ..
+                } => format!("parse {tab_id}/{request_id}"),
+                CoreCommand::CssChunk {
+                    tab_id, request_id, ..
+                } => format!("css {tab_id}/{request_id}"),
+                CoreCommand::CancelRequest { tab_id, request_id } => {
+                    format!("cancel {tab_id}/{request_id}")
+                }
+                CoreCommand::TabClosed { tab_id } => format!("close {tab_id}"),
+                other => format!("{other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn backed_up_parse_commands_go_before_stylesheets_and_closes_go_last() {
+        let mut queue = CommandQueue::default();
+        queue.push(css_chunk(1, 1));
+        queue.push(CoreCommand::TabClosed { tab_id: 2 });
+        queue.push(parse_chunk(1, 1));
+        queue.push(css_chunk(1, 2));
+        queue.push(parse_chunk(1, 2));
+
+        assert_eq!(
+            drain(&mut queue),
+            ["parse 1/1", "parse 1/2", "css 1/1", "css 1/2", "close 2"]
+        );
+    }
+
+    #[test]
+    fn streaming_commands_of_cancelled_loads_are_dropped() {
+        let mut queue = CommandQueue::default();
+        queue.push(parse_chunk(1, 3));
+        queue.push(css_chunk(1, 3));
+        queue.push(parse_chunk(2, 3));
+        queue.push(CoreCommand::CancelRequest {
+            tab_id: 1,
+            request_id: 3,
+        });
+        queue.push(parse_chunk(1, 2));
+        queue.push(parse_chunk(1, 4));
+
+        assert_eq!(drain(&mut queue), ["cancel 1/3", "parse 2/3", "parse 1/4"]);
+    }
+
+    /// A runtime that reports each command it receives on `seen`.
+    fn recording_runtime(
+        seen: mpsc::Sender<String>,
+    ) -> impl Fn(mpsc::Receiver<CoreCommand>) -> JoinHandle<()> + Send + 'static {
+        move |cmd_rx| {
+            let seen = seen.clone();
+            thread::spawn(move || {
+                for cmd in cmd_rx {
+                    let _ = seen.send(format!("{cmd:?}"));
+                    if let CoreCommand::Shutdown { ack } = cmd {
+                        let ack = ack.into_inner();
+                        let _ = ack.send(());
+                        return;
+                    }
+                }
+            })
+        }
+    }
+
+    fn recv(seen: &mpsc::Receiver<String>) -> String {
+        seen.recv_timeout(Duration::from_secs(2))
+            .expect("runtime should receive a command")
+    }
+
+    #[test]
+    fn commands_go_to_the_runtime_registered_for_them() {
+        let mut router = Router::new(ChunkCredits::default());
+        let (shared_tx, shared_rx) = mpsc::channel();
+        router.register(
+            |cmd| matches!(cmd, CoreCommand::CancelRequest { .. }),
+            recording_runtime(shared_tx),
+        );
+        let (per_tab_tx, per_tab_rx) = mpsc::channel();
+        router.register_per_tab(
+            |cmd| matches!(cmd, CoreCommand::ParseHtmlChunk { .. }),
+            Box::new(recording_runtime(per_tab_tx)),
+        );
+
+        router.route(parse_chunk(1, 1));
+        router.route(css_chunk(1, 1));
+        router.route(CoreCommand::CancelRequest {
+            tab_id: 1,
+            request_id: 1,
+        });
+        router.route(CoreCommand::CancelGeneration {
+            tab_id: 1,
+            request_id: 1,
+        });
+        router.route(CoreCommand::TabClosed { tab_id: 1 });
+
+        assert!(recv(&per_tab_rx).starts_with("ParseHtmlChunk"));
+        assert!(recv(&per_tab_rx).starts_with("CancelGeneration"));
+        assert!(recv(&shared_rx).starts_with("CancelRequest"));
+        assert!(recv(&shared_rx).starts_with("CancelGeneration"));
+        assert!(recv(&shared_rx).starts_with("TabClosed"));
+        assert!(per_tab_rx.try_recv().is_err() && shared_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn shutdown_reaches_every_runtime_after_queued_commands() {
+        let mut router = Router::new(ChunkCredits::default());
+        let (seen_tx, seen_rx) = mpsc::channel();
+        router.register(
+            |cmd| matches!(cmd, CoreCommand::CancelRequest { .. }),
+            recording_runtime(seen_tx.clone()),
+        );
+        router.register_per_tab(
+            |cmd| matches!(cmd, CoreCommand::ParseHtmlChunk { .. }),
+            Box::new(recording_runtime(seen_tx)),
+        );
+        let (cmd_tx, cmd_rx) = mpsc::channel();
+        let (ack_tx, ack_rx) = mpsc::channel();
+        cmd_tx
+            .send(CoreCommand::Shutdown {
+                ack: InProcess::new(ack_tx),
+            })
+            .unwrap();
+        cmd_tx.send(parse_chunk(1, 1)).unwrap();
+
+        let threads = router_thread(cmd_rx, router).join().expect("router thread");
+
+        assert_eq!(threads.len(), 2);
+        for thread in threads {
+            thread.join().expect("runtime thread");
+        }
+        // The router stops at the shutdown, so the chunk sent after it was
+        // queued ahead of it.
+        let mut seen: Vec<String> = seen_rx
+            .try_iter()
+            .map(|cmd| {
+                cmd.split_whitespace()
+                    .next()
+                    .unwrap_or_default()
+                    .to_string()
+            })
+            .collect();
+        seen.sort();
+        assert_eq!(seen, ["ParseHtmlChunk", "Shutdown", "Shutdown"]);
+        assert_eq!(ack_rx.try_iter().count(), 3);
+    }
+}
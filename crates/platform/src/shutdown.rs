@@ -0,0 +1,79 @@
+//! Orderly shutdown of the runtime threads.
+//!
+//! Exiting the process while a runtime is mid-write would cut that write
+//! short. `CoreCommand::Shutdown` goes through the router behind every
+//! command sent before it; each runtime handles what it has queued, drops
+//! its state, acknowledges, and stops, and the threads are then joined.
+
+use bus::{CoreCommand, InProcess};
+use std::sync::mpsc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// How long the app waits for the runtimes to stop when it exits.
+pub const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The router and runtime threads behind the bus.
+pub struct RuntimeThreads {
+    cmd_tx: mpsc::Sender<CoreCommand>,
+    router: JoinHandle<Vec<JoinHandle<()>>>,
+}
+
+impl RuntimeThreads {
+    pub(crate) fn new(
+        cmd_tx: mpsc::Sender<CoreCommand>,
+        router: JoinHandle<Vec<JoinHandle<()>>>,
+    ) -> Self {
+        Self { cmd_tx, router }
+    }
+
+    /// Stop every runtime once it has handled the commands sent before, and
+    /// join the threads that acknowledge within `timeout`. Returns how many
+    /// threads were still running at the deadline; those are left detached.
+    pub fn shutdown(self, timeout: Duration) -> usize {
+        let deadline = Instant::now() + timeout;
+        let (ack_tx, ack_rx) = mpsc::channel();
+        if self
+            .cmd_tx
+            .send(CoreCommand::Shutdown {
+                ack: InProcess::new(ack_tx),
+            })
+            .is_err()
+        {
+            // The router is gone and the runtimes with it.
+            return 0;
+        }
+
+        // No runtime sees the shutdown before the router passed it on, so the
+        // first acknowledgement means the router is returning the threads.
+        if !wait_for_acks(&ack_rx, 1, deadline) {
+            return 1;
+        }
+        let Ok(threads) = self.router.join() else {
+            return 0;
+        };
+        // A runtime stops right after acknowledging, so once every runtime
+        // did, joining them all only waits for those last steps.
+        let all_acked = wait_for_acks(&ack_rx, threads.len(), deadline);
+        let mut running = 0;
+        for thread in threads {
+            if all_acked || thread.is_finished() {
+                let _ = thread.join();
+            } else {
+                running += 1;
+            }
+        }
+        running
+    }
+}
+
+/// Wait until `count` acknowledgements arrived, or the deadline passed.
+fn wait_for_acks(ack_rx: &mpsc::Receiver<()>, count: usize, deadline: Instant) -> bool {
+    for _ in 0..count {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if ack_rx.recv_timeout(remaining).is_err() {
+            return false;
+        }
+    }
+    true
+}
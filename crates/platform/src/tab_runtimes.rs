@@ -0,0 +1,153 @@
+//! Per-tab runtimes.
+//!
+//! Each tab gets its own thread of every per-tab runtime registered with the
+//! router (parse, css, layout, image, and script), so a heavy document in one
+//! tab only queues behind its own work. Workers start with the first command
+//! for a tab and stop once `CoreCommand::TabClosed` drops their command
+//! senders; the router forwards commands in order, so nothing for a closed
+//! tab arrives after its close.
+
+use bus::{ChunkCredits, CoreCommand, InProcess};
+use core_types::{RequestId, TabId};
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::JoinHandle;
+
+/// Starts one tab's worker of a runtime on the given command receiver.
+pub(crate) type StartRuntime = Box<dyn Fn(Receiver<CoreCommand>) -> JoinHandle<()> + Send>;
+
+/// Index of a per-tab runtime, in registration order.
+pub(crate) type TabRuntimeId = usize;
+
+/// One tab's worker of a runtime.
+struct Worker {
+    tx: Sender<CoreCommand>,
+    thread: JoinHandle<()>,
+}
+
+pub(crate) struct TabRuntimes {
+    credits: ChunkCredits,
+    runtimes: Vec<StartRuntime>,
+    /// Each tab's workers, indexed by `TabRuntimeId`.
+    tabs: HashMap<TabId, Vec<Worker>>,
+}
+
+impl TabRuntimes {
+    pub(crate) fn new(credits: ChunkCredits) -> Self {
+        Self {
+            credits,
+            runtimes: Vec::new(),
+            tabs: HashMap::new(),
+        }
+    }
+
+    /// Add a runtime that every tab started from now on gets a worker of.
+    pub(crate) fn add(&mut self, start: StartRuntime) -> TabRuntimeId {
+        self.runtimes.push(start);
+        self.runtimes.len() - 1
+    }
+
+    pub(crate) fn send(&mut self, runtime: TabRuntimeId, tab_id: TabId, cmd: CoreCommand) {
+        if let Some(worker) = self.workers(tab_id).get(runtime) {
+            let _ = worker.tx.send(cmd);
+        }
+    }
+
+    /// Tell each of the tab's workers to drop its state for `request_id` and
+    /// older requests. A tab without workers has nothing to drop.
+    pub(crate) fn cancel_generation(&mut self, tab_id: TabId, request_id: RequestId) {
+        let Some(workers) = self.tabs.get(&tab_id) else {
+            return;
+        };
+        for worker in workers {
+            let _ = worker
+                .tx
+                .send(CoreCommand::CancelGeneration { tab_id, request_id });
+        }
+    }
+
+    /// Ask every open tab's workers to shut down, acknowledging on `ack`.
+    /// Returns their threads. Workers of closed tabs already stopped.
+    pub(crate) fn shutdown(&mut self, ack: &Sender<()>) -> Vec<JoinHandle<()>> {
+        let mut threads = Vec::new();
+        for (_, workers) in self.tabs.drain() {
+            for worker in workers {
+                let _ = worker.tx.send(CoreCommand::Shutdown {
+                    ack: InProcess::new(ack.clone()),
+                });
+                threads.push(worker.thread);
+            }
+        }
+        threads
+    }
+
+    /// Stop the tab's workers; any document they were still parsing is dropped
+    /// and its stream no longer waits for credit.
+    pub(crate) fn close(&mut self, tab_id: TabId) {
+        self.tabs.remove(&tab_id);
+        self.credits.close_tab(tab_id);
+    }
+
+    fn workers(&mut self, tab_id: TabId) -> &[Worker] {
+        let runtimes = &self.runtimes;
+        self.tabs.entry(tab_id).or_insert_with(|| {
+            runtimes
+                .iter()
+                .map(|start| {
+                    let (tx, rx) = mpsc::channel::<CoreCommand>();
+                    Worker {
+                        tx,
+                        thread: start(rx),
+                    }
+                })
+                .collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TabRuntimes;
+    use bus::{ChunkCredits, CoreCommand, CoreEvent};
+    use core_types::StylesheetSlotId;
+    use runtime_css::start_css_runtime;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    fn css_done(tab_id: u64) -> CoreCommand {
+        CoreCommand::CssDone {
+            tab_id,
+            request_id: 1,
+            stylesheet_slot_id: StylesheetSlotId(1),
+            url: "https://example.com/site.css".to_string(),
+        }
+    }
+
+    #[test]
+    fn each_tab_gets_its_own_workers_until_it_closes() {
+        let (evt_tx, evt_rx) = mpsc::channel::<CoreEvent>();
+        let mut runtimes = TabRuntimes::new(ChunkCredits::default());
+        let css = runtimes.add(Box::new(move |cmd_rx| {
+            start_css_runtime(cmd_rx, evt_tx.clone())
+        }));
+
+        runtimes.send(css, 1, css_done(1));
+        runtimes.send(css, 2, css_done(2));
+        assert_eq!(runtimes.tabs.len(), 2);
+
+        let mut done_tabs = Vec::new();
+        while done_tabs.len() < 2 {
+            match evt_rx.recv_timeout(Duration::from_secs(2)) {
+                Ok(CoreEvent::CssSheetDone { tab_id, .. }) => done_tabs.push(tab_id),
+                Ok(_) => {}
+                Err(err) => panic!("expected CssSheetDone for both tabs: {err}"),
+            }
+        }
+        done_tabs.sort_unstable();
+        assert_eq!(done_tabs, [1, 2]);
+
+        runtimes.close(1);
+        assert!(!runtimes.tabs.contains_key(&1));
+        assert!(runtimes.tabs.contains_key(&2));
+    }
+}
@@ -0,0 +1,128 @@
+//! Pixel comparison of two renderings.
+
+use crate::manifest::Tolerance;
+use egui::{Color32, ColorImage};
+
+/// How far apart two renderings of the same size are.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Difference {
+    /// Pixels that differ in any channel.
+    pub pixels: usize,
+    /// Largest difference in any channel of any pixel.
+    pub max_channel_difference: u8,
+}
+
+impl Difference {
+    pub fn within(&self, tolerance: &Tolerance) -> bool {
+        self.pixels <= tolerance.max_pixels
+            && self.max_channel_difference <= tolerance.max_channel_difference
+    }
+}
+
+/// Compare two renderings. Renderings of different sizes differ in every
+/// pixel of the larger one.
+pub fn compare(a: &ColorImage, b: &ColorImage) -> Difference {
+    if a.size != b.size {
+        return Difference {
+            pixels: a.pixels.len().max(b.pixels.len()),
+            max_channel_difference: u8::MAX,
+        };
+    }
+    let mut difference = Difference::default();
+    for (a, b) in a.pixels.iter().zip(&b.pixels) {
+        let channel = channel_difference(*a, *b);
+        if channel > 0 {
+            difference.pixels += 1;
+            difference.max_channel_difference = difference.max_channel_difference.max(channel);
+        }
+    }
+    difference
+}
+
+/// An image of where two renderings differ: matching pixels are a faded
+/// copy of `a`, differing pixels are red.
+pub fn diff_image(a: &ColorImage, b: &ColorImage) -> ColorImage {
+    let size = [a.size[0].max(b.size[0]), a.size[1].max(b.size[1])];
+    let pixel = |image: &ColorImage, x: usize, y: usize| {
+        (x < image.size[0] && y < image.size[1]).then(|| image.pixels[y * image.size[0] + x])
+    };
+    let mut pixels = Vec::with_capacity(size[0] * size[1]);
+    for y in 0..size[1] {
+        for x in 0..size[0] {
+            pixels.push(match (pixel(a, x, y), pixel(b, x, y)) {
+                (Some(a), Some(b)) if a == b => fade(a),
+                _ => Color32::RED,
+            });
+        }
+    }
+    ColorImage::new(size, pixels)
+}
+
+fn channel_difference(a: Color32, b: Color32) -> u8 {
+    a.to_array()
+        .into_iter()
+        .zip(b.to_array())
+        .map(|(a, b)| a.abs_diff(b))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Blend an opaque copy of `color` three quarters towards white.
+fn fade(color: Color32) -> Color32 {
+    let [r, g, b, _] = color.to_array();
+    let fade = |channel: u8| 255 - (255 - channel) / 4;
+    Color32::from_rgb(fade(r), fade(g), fade(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Difference, compare, diff_image};
+    use crate::manifest::Tolerance;
+    use egui::{Color32, ColorImage};
+
+    fn image(pixels: [Color32; 4]) -> ColorImage {
+        ColorImage::new([2, 2], pixels.to_vec())
+    }
+
+    #[test]
+    fn differences_count_pixels_and_the_largest_channel_gap() {
+        let white = Color32::WHITE;
+        let a = image([white; 4]);
+        let b = image([
+            white,
+            Color32::from_gray(250),
+            white,
+            Color32::from_gray(200),
+        ]);
+
+        let difference = compare(&a, &b);
+        assert_eq!(
+            difference,
+            Difference {
+                pixels: 2,
+                max_channel_difference: 55,
+            }
+        );
+        assert!(difference.within(&Tolerance {
+            max_channel_difference: 55,
+            max_pixels: 2,
+        }));
+        assert!(!difference.within(&Tolerance {
+            max_channel_difference: 54,
+            max_pixels: 2,
+        }));
+        assert!(!difference.within(&Tolerance::default()));
+        assert!(compare(&a, &a).within(&Tolerance::default()));
+    }
+
+    #[test]
+    fn the_diff_image_marks_differing_pixels_red() {
+        let a = image([Color32::BLACK; 4]);
+        let mut b = image([Color32::BLACK; 4]);
+        b.pixels[2] = Color32::WHITE;
+
+        let diff = diff_image(&a, &b);
+        assert_eq!(diff.pixels[2], Color32::RED);
+        assert_eq!(diff.pixels[0], Color32::from_gray(192));
+    }
+}
@@ -0,0 +1,18 @@
+//! Reftests: pairs of pages that must render the same pixels, or must not.
+//!
+//! Each test renders a test page and a reference page headlessly in the
+//! same viewport and compares the pixels, within the tolerance the manifest
+//! gives the test. Reference pages reach the expected rendering another way,
+//! usually with simpler markup, so a layout or paint regression shows up as
+//! a pixel difference without checked-in screenshots. Failing tests can
+//! write both renderings and a diff as PNG files.
+//!
+//! Run a manifest with `cargo run -p reftest -- <manifest> [--artifacts <dir>]`.
+
+pub mod compare;
+pub mod manifest;
+pub mod runner;
+
+pub use compare::{Difference, compare, diff_image};
+pub use manifest::{Manifest, ManifestError, Reftest, Relation, Tolerance};
+pub use runner::{Outcome, RunError, RunOptions, Runner};
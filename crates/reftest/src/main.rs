@@ -0,0 +1,65 @@
+use reftest::{Manifest, Outcome, RunOptions, Runner};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (manifest_path, artifacts) = match args.as_slice() {
+        [manifest] => (manifest, None),
+        [manifest, flag, dir] if flag == "--artifacts" => (manifest, Some(PathBuf::from(dir))),
+        _ => {
+            eprintln!("usage: reftest <manifest> [--artifacts <dir>]");
+            return ExitCode::FAILURE;
+        }
+    };
+    let manifest = match Manifest::load(manifest_path.as_ref()) {
+        Ok(manifest) => manifest,
+        Err(err) => {
+            eprintln!("{manifest_path}: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let (cmd_tx, evt_rx, runtimes) = platform::start_runtimes();
+    let options = RunOptions {
+        artifacts,
+        ..RunOptions::default()
+    };
+    let mut runner = Runner::new(cmd_tx, evt_rx, options);
+    let mut failures = 0;
+    for test in &manifest.tests {
+        match runner.run(test) {
+            Ok(Outcome::Pass) => println!("PASS {}", test.name()),
+            Ok(Outcome::Fail {
+                difference,
+                artifacts,
+            }) => {
+                failures += 1;
+                println!(
+                    "FAIL {}: {} pixels differ, by up to {}",
+                    test.name(),
+                    difference.pixels,
+                    difference.max_channel_difference
+                );
+                for artifact in artifacts {
+                    println!("     {}", artifact.display());
+                }
+            }
+            Err(err) => {
+                failures += 1;
+                println!("FAIL {}: {err}", test.name());
+            }
+        }
+    }
+    runtimes.shutdown(platform::SHUTDOWN_TIMEOUT);
+
+    println!(
+        "{} passed, {failures} failed",
+        manifest.tests.len() - failures
+    );
+    if failures == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,197 @@
+//! Reftest manifests.
+//!
+//! A manifest lists one reftest per line; paths are relative to the
+//! manifest. Blank lines and text after `#` are ignored.
+//!
+//! ```text
+//! # The two pages must render the same pixels.
+//! == block-margins.html block-margins-ref.html
+//! # The two pages must differ somewhere.
+//! != color.html color-notref.html
+//! # Up to 40 pixels may differ, by at most 8 in any channel.
+//! fuzzy(8,40) == text-wrap.html text-wrap-ref.html
+//! ```
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// How the test page's rendering relates to its reference.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Relation {
+    /// `==`: the renderings match within the test's tolerance.
+    Equal,
+    /// `!=`: the renderings differ by more than the test's tolerance.
+    NotEqual,
+}
+
+/// Differences two renderings may have and still count as equal.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Tolerance {
+    /// Largest difference allowed in any color channel of a pixel.
+    pub max_channel_difference: u8,
+    /// Number of pixels allowed to differ, by at most the channel
+    /// difference.
+    pub max_pixels: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reftest {
+    pub relation: Relation,
+    pub test: PathBuf,
+    pub reference: PathBuf,
+    pub tolerance: Tolerance,
+    /// Line of the manifest the test is listed on, from 1.
+    pub line: usize,
+}
+
+impl Reftest {
+    /// Name used in reports and for failure artifacts: the test file's stem.
+    pub fn name(&self) -> String {
+        self.test
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| format!("line-{}", self.line))
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+    pub tests: Vec<Reftest>,
+}
+
+#[derive(Debug)]
+pub enum ManifestError {
+    Io(PathBuf, io::Error),
+    Syntax { line: usize, message: String },
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(path, err) => write!(f, "{}: {err}", path.display()),
+            Self::Syntax { line, message } => write!(f, "line {line}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for ManifestError {}
+
+impl Manifest {
+    /// Read the manifest at `path`; test paths resolve against its directory.
+    pub fn load(path: &Path) -> Result<Self, ManifestError> {
+        let text =
+            fs::read_to_string(path).map_err(|err| ManifestError::Io(path.to_path_buf(), err))?;
+        Self::parse(&text, path.parent().unwrap_or(Path::new(".")))
+    }
+
+    pub fn parse(text: &str, base: &Path) -> Result<Self, ManifestError> {
+        let mut tests = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let content = line.split('#').next().unwrap_or_default();
+            let mut words = content.split_whitespace().peekable();
+            if words.peek().is_none() {
+                continue;
+            }
+            let syntax = |message: String| ManifestError::Syntax {
+                line: line_number,
+                message,
+            };
+
+            let tolerance = match words.next_if(|word| word.starts_with("fuzzy(")) {
+                Some(word) => parse_fuzzy(word).map_err(syntax)?,
+                None => Tolerance::default(),
+            };
+            let relation = match words.next() {
+                Some("==") => Relation::Equal,
+                Some("!=") => Relation::NotEqual,
+                Some(other) => {
+                    return Err(syntax(format!("expected `==` or `!=`, found `{other}`")));
+                }
+                None => return Err(syntax("expected `==` or `!=`".to_string())),
+            };
+            let (Some(test), Some(reference), None) = (words.next(), words.next(), words.next())
+            else {
+                return Err(syntax(
+                    "expected a test file and a reference file".to_string(),
+                ));
+            };
+            tests.push(Reftest {
+                relation,
+                test: base.join(test),
+                reference: base.join(reference),
+                tolerance,
+                line: line_number,
+            });
+        }
+        Ok(Self { tests })
+    }
+}
+
+/// Parse `fuzzy(<max channel difference>,<max pixels>)`.
+fn parse_fuzzy(word: &str) -> Result<Tolerance, String> {
+    let invalid = || format!("expected `fuzzy(<max difference>,<max pixels>)`, found `{word}`");
+    let arguments = word
+        .strip_prefix("fuzzy(")
+        .and_then(|rest| rest.strip_suffix(')'))
+        .ok_or_else(invalid)?;
+    let (difference, pixels) = arguments.split_once(',').ok_or_else(invalid)?;
+    Ok(Tolerance {
+        max_channel_difference: difference.trim().parse().map_err(|_| invalid())?,
+        max_pixels: pixels.trim().parse().map_err(|_| invalid())?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Manifest, ManifestError, Relation, Tolerance};
+    use std::path::Path;
+
+    #[test]
+    fn lines_list_relation_files_and_tolerance() {
+        let manifest = Manifest::parse(
+            "# header\n\n== a.html a-ref.html\nfuzzy(8,40) != b.html  b-ref.html # note\n",
+            Path::new("suite"),
+        )
+        .expect("valid manifest");
+
+        assert_eq!(manifest.tests.len(), 2);
+        let [equal, not_equal] = &manifest.tests[..] else {
+            unreachable!();
+        };
+        assert_eq!(equal.relation, Relation::Equal);
+        assert_eq!(equal.test, Path::new("suite/a.html"));
+        assert_eq!(equal.reference, Path::new("suite/a-ref.html"));
+        assert_eq!(equal.tolerance, Tolerance::default());
+        assert_eq!(equal.line, 3);
+        assert_eq!(equal.name(), "a");
+
+        assert_eq!(not_equal.relation, Relation::NotEqual);
+        assert_eq!(
+            not_equal.tolerance,
+            Tolerance {
+                max_channel_difference: 8,
+                max_pixels: 40,
+            }
+        );
+    }
+
+    #[test]
+    fn malformed_lines_report_their_line() {
+        for text in [
+            "== a.html",
+            "~= a.html b.html",
+            "fuzzy(8) == a.html b.html",
+            "== a.html b.html c.html",
+        ] {
+            let err = Manifest::parse(&format!("\n{text}"), Path::new("."))
+                .expect_err("malformed manifest");
+            assert!(
+                matches!(err, ManifestError::Syntax { line: 2, .. }),
+                "{text}: {err}"
+            );
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Rendering a reftest's pages and judging the result.
+
+use crate::compare::{Difference, compare, diff_image};
+use crate::manifest::{Reftest, Relation};
+use bus::{CoreCommand, CoreEvent};
+use core_types::TabId;
+use egui::ColorImage;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, Sender};
+use std::time::Duration;
+use url::Url;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunOptions {
+    /// Viewport both pages render in, in pixels.
+    pub viewport: [usize; 2],
+    /// How long a page may take to load.
+    pub timeout: Duration,
+    /// Where failing tests write their renderings and a diff; nowhere if
+    /// `None`.
+    pub artifacts: Option<PathBuf>,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            viewport: [800, 600],
+            timeout: Duration::from_secs(30),
+            artifacts: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Outcome {
+    Pass,
+    Fail {
+        difference: Difference,
+        /// Artifacts written for the failure.
+        artifacts: Vec<PathBuf>,
+    },
+}
+
+#[derive(Debug)]
+pub enum RunError {
+    /// A page could not be loaded.
+    Load { path: PathBuf, reason: String },
+    /// A failure artifact could not be written.
+    Artifact {
+        path: PathBuf,
+        error: image::ImageError,
+    },
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Load { path, reason } => write!(f, "could not load {}: {reason}", path.display()),
+            Self::Artifact { path, error } => {
+                write!(f, "could not write {}: {error}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for RunError {}
+
+/// Renders reftest pages in offscreen tabs over a running runtime bus.
+pub struct Runner {
+    cmd_tx: Sender<CoreCommand>,
+    evt_rx: Receiver<CoreEvent>,
+    options: RunOptions,
+    /// Every page gets a tab of its own, so late events of an earlier page
+    /// cannot reach it.
+    last_tab: TabId,
+}
+
+impl Runner {
+    /// `cmd_tx` and `evt_rx` are the two ends of a running runtime bus.
+    pub fn new(
+        cmd_tx: Sender<CoreCommand>,
+        evt_rx: Receiver<CoreEvent>,
+        options: RunOptions,
+    ) -> Self {
+        Self {
+            cmd_tx,
+            evt_rx,
+            options,
+            last_tab: 0,
+        }
+    }
+
+    pub fn run(&mut self, test: &Reftest) -> Result<Outcome, RunError> {
+        let rendered = self.render(&test.test)?;
+        let reference = self.render(&test.reference)?;
+        let difference = compare(&rendered, &reference);
+        let passed = match test.relation {
+            Relation::Equal => difference.within(&test.tolerance),
+            Relation::NotEqual => !difference.within(&test.tolerance),
+        };
+        if passed {
+            return Ok(Outcome::Pass);
+        }
+
+        let mut artifacts = Vec::new();
+        if let Some(dir) = &self.options.artifacts {
+            let name = test.name();
+            for (suffix, image) in [
+                ("test", &rendered),
+                ("ref", &reference),
+                ("diff", &diff_image(&rendered, &reference)),
+            ] {
+                let path = dir.join(format!("{name}.{suffix}.png"));
+                write_png(&path, image)?;
+                artifacts.push(path);
+            }
+        }
+        Ok(Outcome::Fail {
+            difference,
+            artifacts,
+        })
+    }
+
+    /// Load the page at `path` in a new tab and render it.
+    pub fn render(&mut self, path: &Path) -> Result<ColorImage, RunError> {
+        let load_error = |reason: String| RunError::Load {
+            path: path.to_path_buf(),
+            reason,
+        };
+        let absolute = fs::canonicalize(path).map_err(|err| load_error(err.to_string()))?;
+        let url = Url::from_file_path(&absolute)
+            .map_err(|()| load_error("not a file path".to_string()))?;
+        self.last_tab += 1;
+        browser::headless::render_url(
+            url.as_str(),
+            self.last_tab,
+            self.cmd_tx.clone(),
+            &self.evt_rx,
+            self.options.viewport,
+            self.options.timeout,
+        )
+        .map_err(load_error)
+    }
+}
+
+pub fn write_png(path: &Path, image: &ColorImage) -> Result<(), RunError> {
+    let artifact_error = |error| RunError::Artifact {
+        path: path.to_path_buf(),
+        error,
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|err| artifact_error(err.into()))?;
+    }
+    let [width, height] = image.size;
+    let bytes = image
+        .pixels
+        .iter()
+        .flat_map(|pixel| pixel.to_srgba_unmultiplied())
+        .collect();
+    image::RgbaImage::from_raw(width as u32, height as u32, bytes)
+        .expect("one RGBA pixel per texel")
+        .save(path)
+        .map_err(artifact_error)
+}
@@ -0,0 +1,84 @@
+use reftest::{Manifest, Outcome, Reftest, Relation, RunOptions, Runner, Tolerance};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn suite() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/reftests")
+}
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock after epoch")
+        .as_nanos();
+    std::env::temp_dir().join(format!("borrowser-reftest-{name}-{nanos}"))
+}
+
+#[test]
+fn the_engine_passes_its_reftests() {
+    let manifest = Manifest::load(&suite().join("reftest.list")).expect("manifest");
+    let (cmd_tx, evt_rx, runtimes) = platform::start_runtimes();
+    let mut runner = Runner::new(cmd_tx, evt_rx, RunOptions::default());
+
+    let failures: Vec<String> = manifest
+        .tests
+        .iter()
+        .filter_map(|test| match runner.run(test) {
+            Ok(Outcome::Pass) => None,
+            Ok(Outcome::Fail { difference, .. }) => {
+                Some(format!("{}: {difference:?}", test.name()))
+            }
+            Err(err) => Some(format!("{}: {err}", test.name())),
+        })
+        .collect();
+    runtimes.shutdown(platform::SHUTDOWN_TIMEOUT);
+    assert!(!manifest.tests.is_empty());
+    assert!(failures.is_empty(), "failing reftests: {failures:#?}");
+}
+
+#[test]
+fn a_failing_reftest_writes_both_renderings_and_a_diff() {
+    let artifacts = scratch_dir("artifacts");
+    let (cmd_tx, evt_rx, runtimes) = platform::start_runtimes();
+    let mut runner = Runner::new(
+        cmd_tx,
+        evt_rx,
+        RunOptions {
+            artifacts: Some(artifacts.clone()),
+            ..RunOptions::default()
+        },
+    );
+    let test = Reftest {
+        relation: Relation::Equal,
+        test: suite().join("block-color.html"),
+        reference: suite().join("block-color-notref.html"),
+        tolerance: Tolerance::default(),
+        line: 1,
+    };
+
+    let outcome = runner.run(&test).expect("pages load");
+    runtimes.shutdown(platform::SHUTDOWN_TIMEOUT);
+    let Outcome::Fail {
+        difference,
+        artifacts: written,
+    } = outcome
+    else {
+        panic!("a green box does not match a red one");
+    };
+    // The 100px box and nothing else differs.
+    assert_eq!(difference.pixels, 100 * 100);
+    let names: Vec<_> = written
+        .iter()
+        .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "block-color.test.png",
+            "block-color.ref.png",
+            "block-color.diff.png"
+        ]
+    );
+    assert!(written.iter().all(|path| path.is_file()));
+    let _ = std::fs::remove_dir_all(artifacts);
+}
@@ -1,15 +1,16 @@
 //! Stylesheet transport/assembly runtime.
 //!
 //! This runtime owns byte buffering and incremental UTF-8 assembly for external
-//! stylesheets. It does not own CSS tokenization or syntax parsing; it forwards
-//! fully decoded stylesheet text to the main integration path, where the
-//! `css::syntax` entry points are invoked.
+//! stylesheets. It does not own CSS syntax parsing; it forwards fully decoded
+//! stylesheet text to the main integration path, where the `css::syntax` entry
+//! points are invoked. The one thing it reads in the text is the sheet's
+//! `@import` rules, reported first so the tab can fetch the imported sheets.
 
-use bus::{CoreCommand, CoreEvent};
+use bus::{CoreCommand, CoreEvent, RuntimeKind, run_supervised};
 use core_types::{RequestId, StylesheetSlotId, TabId};
 use std::collections::HashMap;
 use std::sync::mpsc::{Receiver, Sender};
-use std::thread;
+use std::thread::{self, JoinHandle};
 use tools::utf8::{finish_utf8, push_utf8_chunk};
 
 type Key = (TabId, RequestId, StylesheetSlotId);
@@ -19,12 +20,17 @@ struct CssState {
     text: String,
 }
 
-pub fn start_css_runtime(cmd_rx: Receiver<CoreCommand>, evt_tx: Sender<CoreEvent>) {
+pub fn start_css_runtime(
+    cmd_rx: Receiver<CoreCommand>,
+    evt_tx: Sender<CoreEvent>,
+) -> JoinHandle<()> {
     thread::spawn(move || {
-        let mut map: HashMap<Key, CssState> = HashMap::new();
-
-        while let Ok(cmd) = cmd_rx.recv() {
-            match cmd {
+        run_supervised(
+            RuntimeKind::Css,
+            &cmd_rx,
+            &evt_tx,
+            HashMap::new,
+            |map: &mut HashMap<Key, CssState>, cmd| match cmd {
                 CoreCommand::CssChunk {
                     tab_id,
                     request_id,
@@ -49,6 +55,15 @@ pub fn start_css_runtime(cmd_rx: Receiver<CoreCommand>, evt_tx: Sender<CoreEvent
                     let key = (tab_id, request_id, stylesheet_slot_id);
                     if let Some(mut st) = map.remove(&key) {
                         finish_utf8(&mut st.text, &mut st.carry);
+                        for import in css::stylesheet_imports(&st.text) {
+                            let _ = evt_tx.send(CoreEvent::CssImport {
+                                tab_id,
+                                request_id,
+                                stylesheet_slot_id,
+                                href: import.href,
+                                media: import.media,
+                            });
+                        }
                         // Forward one decoded stylesheet text block. Syntax
                         // parsing happens outside this runtime.
                         let _ = evt_tx.send(CoreEvent::CssDecodedBlock {
@@ -76,10 +91,13 @@ pub fn start_css_runtime(cmd_rx: Receiver<CoreCommand>, evt_tx: Sender<CoreEvent
                     let _ = url;
                     map.remove(&(tab_id, request_id, stylesheet_slot_id));
                 }
+                CoreCommand::CancelGeneration { tab_id, request_id } => {
+                    map.retain(|&(tab, request, _), _| tab != tab_id || request > request_id);
+                }
                 _ => {}
-            }
-        }
-    });
+            },
+        );
+    })
 }
 
 #[cfg(test)]
@@ -121,6 +139,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cancel_generation_drops_buffered_stylesheets_of_older_requests() {
+        let (cmd_tx, cmd_rx) = mpsc::channel();
+        let (evt_tx, evt_rx) = mpsc::channel();
+        start_css_runtime(cmd_rx, evt_tx);
+
+        let url = "https://example.com/site.css".to_string();
+        for request_id in [6, 8] {
+            cmd_tx
+                .send(CoreCommand::CssChunk {
+                    tab_id: 1,
+                    request_id,
+                    stylesheet_slot_id: StylesheetSlotId(1),
+                    url: url.clone(),
+                    bytes: b"p { color: red; }".to_vec(),
+                })
+                .expect("send CssChunk");
+        }
+        cmd_tx
+            .send(CoreCommand::CancelGeneration {
+                tab_id: 1,
+                request_id: 7,
+            })
+            .expect("send CancelGeneration");
+        for request_id in [6, 8] {
+            cmd_tx
+                .send(CoreCommand::CssDone {
+                    tab_id: 1,
+                    request_id,
+                    stylesheet_slot_id: StylesheetSlotId(1),
+                    url: url.clone(),
+                })
+                .expect("send CssDone");
+        }
+
+        let blocks: Vec<_> =
+            std::iter::from_fn(|| evt_rx.recv_timeout(Duration::from_millis(200)).ok())
+                .filter_map(|event| match event {
+                    CoreEvent::CssDecodedBlock { request_id, .. } => Some(request_id),
+                    _ => None,
+                })
+                .collect();
+        assert_eq!(blocks, [8]);
+    }
+
     #[test]
     fn css_done_emits_decoded_block_and_sheet_done() {
         let (cmd_tx, cmd_rx) = mpsc::channel();
@@ -174,6 +237,57 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn css_done_reports_imports_ahead_of_the_decoded_block() {
+        let (cmd_tx, cmd_rx) = mpsc::channel();
+        let (evt_tx, evt_rx) = mpsc::channel();
+        start_css_runtime(cmd_rx, evt_tx);
+
+        let url = "https://example.com/site.css".to_string();
+        cmd_tx
+            .send(CoreCommand::CssChunk {
+                tab_id: 1,
+                request_id: 7,
+                stylesheet_slot_id: StylesheetSlotId(3),
+                url: url.clone(),
+                bytes: b"@import url(base.css); @import 'print.css' print; p { color: red; }"
+                    .to_vec(),
+            })
+            .expect("send CssChunk");
+        cmd_tx
+            .send(CoreCommand::CssDone {
+                tab_id: 1,
+                request_id: 7,
+                stylesheet_slot_id: StylesheetSlotId(3),
+                url,
+            })
+            .expect("send CssDone");
+
+        let events: Vec<_> = (0..4)
+            .map(|_| {
+                evt_rx
+                    .recv_timeout(Duration::from_secs(1))
+                    .expect("CSS runtime event")
+            })
+            .collect();
+        let imports: Vec<_> = events[..2]
+            .iter()
+            .map(|event| match event {
+                CoreEvent::CssImport {
+                    tab_id: 1,
+                    request_id: 7,
+                    stylesheet_slot_id: StylesheetSlotId(3),
+                    href,
+                    media,
+                } => (href.as_str(), media.as_str()),
+                other => panic!("expected an import, got {other:?}"),
+            })
+            .collect();
+        assert_eq!(imports, [("base.css", ""), ("print.css", "print")]);
+        assert!(matches!(events[2], CoreEvent::CssDecodedBlock { .. }));
+        assert!(matches!(events[3], CoreEvent::CssSheetDone { .. }));
+    }
+
     #[test]
     fn css_chunks_are_assembled_and_emitted_once_on_done() {
         let (cmd_tx, cmd_rx) = mpsc::channel();
@@ -0,0 +1,288 @@
+//! Image decoding runtime.
+//!
+//! Decodes fetched image bodies off the UI thread. A `CoreCommand::DecodeImage`
+//! carries the encoded bytes of one PNG, JPEG, GIF, WebP, or ICO image; the
+//! format is sniffed from the bytes. This runtime decodes them to RGBA, every
+//! frame of an animated GIF, APNG, or WebP, and sends the pixels back with
+//! `CoreEvent::ImageDecoded`, so even a multi-megapixel JPEG never holds up
+//! event handling or painting. The UI thread only uploads the pixels as
+//! textures.
+
+use bus::{AnimationFrame, CoreCommand, CoreEvent, DecodedImage, RuntimeKind, run_supervised};
+use image::codecs::gif::GifDecoder;
+use image::codecs::png::PngDecoder;
+use image::codecs::webp::WebPDecoder;
+use image::{AnimationDecoder, Frame, Frames, ImageFormat, ImageResult};
+use std::io::Cursor;
+use std::sync::mpsc::{Receiver, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+pub fn start_image_runtime(
+    cmd_rx: Receiver<CoreCommand>,
+    evt_tx: Sender<CoreEvent>,
+) -> JoinHandle<()> {
+    thread::spawn(move || {
+        run_supervised(
+            RuntimeKind::Image,
+            &cmd_rx,
+            &evt_tx,
+            || (),
+            |_, cmd| {
+                let CoreCommand::DecodeImage {
+                    tab_id,
+                    request_id,
+                    kind,
+                    url,
+                    bytes,
+                } = cmd
+                else {
+                    return;
+                };
+                let image = decode_image(&bytes);
+                let _ = evt_tx.send(CoreEvent::ImageDecoded {
+                    tab_id,
+                    request_id,
+                    kind,
+                    url,
+                    image,
+                });
+            },
+        );
+    })
+}
+
+/// Pixels across all frames of an animation; frames past the limit are
+/// dropped, and playback loops over the ones before it.
+const MAX_ANIMATION_PIXELS: usize = 67_108_864; // 16 frames of 2048 * 2048
+
+/// Frames that ask for this little time or less show for
+/// [`DEFAULT_FRAME_DELAY`] instead, as in other browsers.
+const MIN_FRAME_DELAY: Duration = Duration::from_millis(10);
+const DEFAULT_FRAME_DELAY: Duration = Duration::from_millis(100);
+
+/// Decode an encoded image body to RGBA8. Animated GIF, PNG (APNG), and WebP
+/// images keep every frame.
+pub fn decode_image(bytes: &[u8]) -> Result<DecodedImage, String> {
+    if bytes.is_empty() {
+        return Err("empty image response".to_string());
+    }
+
+    if let Some(frames) = animation_frames(bytes).map_err(|e| e.to_string())? {
+        return decode_animation(frames);
+    }
+
+    let img = image::load_from_memory(bytes).map_err(|e| e.to_string())?;
+    let rgba = img.to_rgba8();
+    let (w, h) = rgba.dimensions();
+
+    Ok(DecodedImage::still(
+        rgba.into_raw(),
+        [w as usize, h as usize],
+    ))
+}
+
+/// The frames of `bytes` if they are in a format that can animate and
+/// declare an animation; `None` for still images.
+fn animation_frames(bytes: &[u8]) -> ImageResult<Option<Frames<'_>>> {
+    let Ok(format) = image::guess_format(bytes) else {
+        return Ok(None);
+    };
+    let frames = match format {
+        ImageFormat::Gif => GifDecoder::new(Cursor::new(bytes))?.into_frames(),
+        ImageFormat::Png => {
+            let decoder = PngDecoder::new(Cursor::new(bytes))?;
+            if !decoder.is_apng()? {
+                return Ok(None);
+            }
+            decoder.apng()?.into_frames()
+        }
+        ImageFormat::WebP => {
+            let decoder = WebPDecoder::new(Cursor::new(bytes))?;
+            if !decoder.has_animation() {
+                return Ok(None);
+            }
+            decoder.into_frames()
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(frames))
+}
+
+/// Collect decoded `frames`; a single frame makes a still image.
+fn decode_animation(frames: Frames<'_>) -> Result<DecodedImage, String> {
+    let mut image: Option<DecodedImage> = None;
+    let mut pixels = 0usize;
+    for frame in frames {
+        let frame = frame.map_err(|e| e.to_string())?;
+        let delay = frame_delay(&frame);
+        let buffer = frame.into_buffer();
+        let (w, h) = buffer.dimensions();
+        let size = [w as usize, h as usize];
+        pixels = pixels.saturating_add(size[0].saturating_mul(size[1]));
+        match &mut image {
+            None => {
+                let mut first = DecodedImage::still(buffer.into_raw(), size);
+                first.delay = delay;
+                image = Some(first);
+            }
+            Some(image) if image.size == size && pixels <= MAX_ANIMATION_PIXELS => {
+                image.frames.push(AnimationFrame {
+                    rgba: buffer.into_raw(),
+                    delay,
+                });
+            }
+            Some(_) => break,
+        }
+    }
+    let mut image = image.ok_or_else(|| "animation has no frames".to_string())?;
+    if !image.is_animated() {
+        image.delay = Duration::ZERO;
+    }
+    Ok(image)
+}
+
+fn frame_delay(frame: &Frame) -> Duration {
+    let (numer, denom) = frame.delay().numer_denom_ms();
+    let delay = Duration::from_millis(u64::from(numer / denom.max(1)));
+    if delay <= MIN_FRAME_DELAY {
+        DEFAULT_FRAME_DELAY
+    } else {
+        delay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::start_image_runtime;
+    use bus::{CoreCommand, CoreEvent};
+    use core_types::ResourceKind;
+    use image::codecs::gif::{GifEncoder, Repeat};
+    use image::{Delay, Frame, ImageFormat, Rgba, RgbaImage};
+    use std::io::Cursor;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    fn decode(cmd_tx: &mpsc::Sender<CoreCommand>, url: &str, bytes: Vec<u8>) {
+        cmd_tx
+            .send(CoreCommand::DecodeImage {
+                tab_id: 3,
+                request_id: 9,
+                kind: ResourceKind::Image,
+                url: url.to_string(),
+                bytes: bytes.into(),
+            })
+            .expect("send DecodeImage");
+    }
+
+    #[test]
+    fn decode_image_replies_with_rgba_pixels_or_the_decoder_error() {
+        let (cmd_tx, cmd_rx) = mpsc::channel();
+        let (evt_tx, evt_rx) = mpsc::channel();
+        start_image_runtime(cmd_rx, evt_tx);
+
+        let mut png = Vec::new();
+        RgbaImage::from_pixel(3, 2, Rgba([10, 20, 30, 255]))
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .expect("encode png");
+        decode(&cmd_tx, "https://example.com/a.png", png);
+        decode(
+            &cmd_tx,
+            "https://example.com/b.png",
+            b"not an image".to_vec(),
+        );
+
+        let event = evt_rx
+            .recv_timeout(Duration::from_secs(5))
+            .expect("image runtime event");
+        let CoreEvent::ImageDecoded {
+            tab_id: 3,
+            request_id: 9,
+            kind: ResourceKind::Image,
+            url,
+            image: Ok(image),
+        } = event
+        else {
+            panic!("expected decoded pixels: {event:?}");
+        };
+        assert_eq!(url, "https://example.com/a.png");
+        assert_eq!(image.size, [3, 2]);
+        assert_eq!(image.rgba.len(), 3 * 2 * 4);
+        assert_eq!(&image.rgba[..4], &[10, 20, 30, 255]);
+
+        let event = evt_rx
+            .recv_timeout(Duration::from_secs(5))
+            .expect("image runtime event");
+        assert!(matches!(
+            event,
+            CoreEvent::ImageDecoded { ref url, image: Err(_), .. }
+                if url == "https://example.com/b.png"
+        ));
+    }
+
+    #[test]
+    fn decode_image_sniffs_the_format_from_the_bytes() {
+        let pixels = RgbaImage::from_pixel(2, 2, Rgba([200, 100, 50, 255]));
+        for format in [ImageFormat::Png, ImageFormat::Jpeg, ImageFormat::WebP] {
+            let mut encoded = Vec::new();
+            let written = if format == ImageFormat::Jpeg {
+                image::DynamicImage::ImageRgba8(pixels.clone())
+                    .to_rgb8()
+                    .write_to(&mut Cursor::new(&mut encoded), format)
+            } else {
+                pixels.write_to(&mut Cursor::new(&mut encoded), format)
+            };
+            written.unwrap_or_else(|err| panic!("encode {format:?}: {err}"));
+
+            let image = super::decode_image(&encoded)
+                .unwrap_or_else(|err| panic!("decode {format:?}: {err}"));
+            assert_eq!(image.size, [2, 2], "{format:?}");
+            assert_eq!(image.rgba.len(), 2 * 2 * 4, "{format:?}");
+            assert_eq!(image.rgba[3], 255, "{format:?} is opaque");
+        }
+    }
+
+    fn gif(frames: &[([u8; 4], u32)]) -> Vec<u8> {
+        let mut gif = Vec::new();
+        {
+            let mut encoder = GifEncoder::new(&mut gif);
+            encoder.set_repeat(Repeat::Infinite).expect("set repeat");
+            encoder
+                .encode_frames(frames.iter().map(|&(pixel, delay_ms)| {
+                    Frame::from_parts(
+                        RgbaImage::from_pixel(2, 1, Rgba(pixel)),
+                        0,
+                        0,
+                        Delay::from_numer_denom_ms(delay_ms, 1),
+                    )
+                }))
+                .expect("encode gif");
+        }
+        gif
+    }
+
+    #[test]
+    fn animated_gifs_keep_every_frame_and_its_delay() {
+        let bytes = gif(&[([255, 0, 0, 255], 50), ([0, 0, 255, 255], 0)]);
+
+        let image = super::decode_image(&bytes).expect("decode gif");
+        assert_eq!(image.size, [2, 1]);
+        assert_eq!(&image.rgba[..4], &[255, 0, 0, 255]);
+        assert_eq!(image.delay, Duration::from_millis(50));
+        assert_eq!(image.frames.len(), 1);
+        assert_eq!(&image.frames[0].rgba[..4], &[0, 0, 255, 255]);
+        assert_eq!(
+            image.frames[0].delay,
+            Duration::from_millis(100),
+            "a zero delay plays at the default frame rate"
+        );
+    }
+
+    #[test]
+    fn single_frame_gifs_are_still_images() {
+        let image = super::decode_image(&gif(&[([0, 255, 0, 255], 70)])).expect("decode gif");
+        assert!(!image.is_animated());
+        assert_eq!(image.delay, Duration::ZERO);
+        assert_eq!(&image.rgba[..4], &[0, 255, 0, 255]);
+    }
+}
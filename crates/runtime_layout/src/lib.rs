@@ -0,0 +1,271 @@
+//! Style and layout runtime.
+//!
+//! Lays documents out off the UI thread. A `CoreCommand::LayoutDocument` job
+//! carries a snapshot of the DOM, its cascade stylesheets, and the viewport
+//! inputs; this runtime computes styles, lays the document out, and sends the
+//! owned retained layout artifact back with `CoreEvent::LayoutReady`. The UI
+//! thread materializes that artifact against its own style tree to paint.
+
+use bus::{
+    CoreCommand, CoreEvent, InProcess, LayoutJob, RuntimeKind, RuntimeMetrics, run_supervised,
+};
+use css::{
+    CascadeOrigin, ComputedDocumentStyle, ComputedStyleResolutionError, StylePhaseOutput,
+    StyleResolutionLimits, build_style_tree_from_computed_styles,
+    compute_document_styles_from_resolved_styles_with_reuse_stats,
+    try_resolve_document_styles_from_cascade_inputs_with_limits,
+};
+use html::internal::Id;
+use layout::replaced::intrinsic::IntrinsicSize;
+use layout::{
+    ImagePresentation, LayoutPhaseInput, LayoutPhaseOutput, ReplacedElementInfoProvider,
+    RetainedLayoutArtifact, layout_document,
+};
+use std::sync::mpsc::{Receiver, Sender};
+use std::thread::{self, JoinHandle};
+
+pub fn start_layout_runtime(
+    cmd_rx: Receiver<CoreCommand>,
+    evt_tx: Sender<CoreEvent>,
+) -> JoinHandle<()> {
+    thread::spawn(move || {
+        run_supervised(
+            RuntimeKind::Layout,
+            &cmd_rx,
+            &evt_tx,
+            || (),
+            |_, cmd| {
+                let CoreCommand::LayoutDocument {
+                    tab_id,
+                    request_id,
+                    job,
+                } = cmd
+                else {
+                    return;
+                };
+                let job = job.into_inner();
+                let _span = tracing::info_span!("layout_job", tab_id, request_id).entered();
+                let layout = lay_out(&job)
+                    .map(|artifact| InProcess::new(Box::new(artifact)))
+                    .map_err(|error| error.to_string());
+                let _ = evt_tx.send(CoreEvent::LayoutReady {
+                    tab_id,
+                    request_id,
+                    key: job.key,
+                    layout,
+                });
+                let _ = evt_tx.send(CoreEvent::Metrics {
+                    tab_id,
+                    request_id,
+                    which: RuntimeKind::Layout,
+                    metrics: RuntimeMetrics {
+                        css_rules_applied: page_rule_count(&job),
+                        ..RuntimeMetrics::default()
+                    },
+                });
+            },
+        );
+    })
+}
+
+/// Compute styles for the job's DOM and lay it out.
+pub fn lay_out(job: &LayoutJob) -> Result<RetainedLayoutArtifact, ComputedStyleResolutionError> {
+    style_and_lay_out(job, |_, output| {
+        RetainedLayoutArtifact::from_layout_output(job.key, output)
+    })
+}
+
+/// Run the job's style and layout phases and hand the computed styles and
+/// the layout output to `inspect`, for callers that need more than the
+/// retained artifact.
+pub fn style_and_lay_out<R>(
+    job: &LayoutJob,
+    inspect: impl FnOnce(&ComputedDocumentStyle, &LayoutPhaseOutput<'_, '_>) -> R,
+) -> Result<R, ComputedStyleResolutionError> {
+    let style_span = tracing::info_span!("style").entered();
+    let sheets: Vec<_> = job
+        .stylesheets
+        .iter()
+        .map(|sheet| sheet.cascade_input())
+        .collect();
+    let resolved = try_resolve_document_styles_from_cascade_inputs_with_limits(
+        &job.dom,
+        &sheets,
+        &job.element_states,
+        &StyleResolutionLimits::default(),
+    )
+    .map_err(ComputedStyleResolutionError::StyleResolution)?;
+    let computed = compute_document_styles_from_resolved_styles_with_reuse_stats(
+        &job.dom, &resolved, &job.media,
+    )?
+    .computed;
+    let root = build_style_tree_from_computed_styles(&job.dom, &computed)?;
+    let style = StylePhaseOutput::new(root).with_zoom(job.zoom);
+    drop(style_span);
+
+    let _span = tracing::info_span!("layout").entered();
+    let replaced_info = JobReplacedElementInfo { job };
+    let output = layout_document(LayoutPhaseInput::from_style_output(
+        &style,
+        job.viewport_width,
+        job.measurer.as_ref(),
+        Some(&replaced_info),
+    ));
+    Ok(inspect(&computed, &output))
+}
+
+/// Rules of the job's author and user stylesheets; the user agent sheet is
+/// the same for every page.
+fn page_rule_count(job: &LayoutJob) -> u64 {
+    job.stylesheets
+        .iter()
+        .filter(|sheet| sheet.origin != CascadeOrigin::UserAgent)
+        .map(|sheet| sheet.stylesheet.stats.rules_emitted as u64)
+        .sum()
+}
+
+/// Replaced-element info from the snapshot the browser took for the job.
+struct JobReplacedElementInfo<'a> {
+    job: &'a LayoutJob,
+}
+
+impl ReplacedElementInfoProvider for JobReplacedElementInfo<'_> {
+    fn resolve_image_source(&self, source: &str) -> Option<String> {
+        self.job.image_sources.get(source).cloned()
+    }
+
+    fn intrinsic_for_img(&self, image: &ImagePresentation) -> Option<IntrinsicSize> {
+        let url = image.resolved_source()?;
+        let &(width, height) = self.job.image_sizes.get(url)?;
+        Some(IntrinsicSize::from_w_h(
+            Some(width as f32 * self.job.zoom),
+            Some(height as f32 * self.job.zoom),
+        ))
+    }
+
+    fn resized_control_size(&self, node_id: Id) -> Option<IntrinsicSize> {
+        let &(width, height) = self.job.resized_controls.get(&node_id)?;
+        // No aspect ratio: the two axes resize independently.
+        Some(IntrinsicSize {
+            width: Some(width),
+            height: Some(height),
+            ratio: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::start_layout_runtime;
+    use bus::{CoreCommand, CoreEvent, InProcess, LayoutJob, LayoutStylesheet};
+    use css::{
+        CascadeOrigin, ComputedStyle, MediaEnvironment, ParseOptions, SelectorElementStates,
+        parse_stylesheet_with_options,
+    };
+    use html::Node;
+    use html::internal::{Id, html_name, node_element_from_parts};
+    use layout::{RetainedLayoutKeySeed, TextMeasurer};
+    use std::collections::HashMap;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    struct FixedMeasurer;
+
+    impl TextMeasurer for FixedMeasurer {
+        fn measure(&self, text: &str, _style: &ComputedStyle) -> f32 {
+            text.chars().count() as f32 * 8.0
+        }
+
+        fn line_height(&self, _style: &ComputedStyle) -> f32 {
+            16.0
+        }
+    }
+
+    #[test]
+    fn layout_document_applies_stylesheets_and_zoom_and_replies_with_the_job_key() {
+        let (cmd_tx, cmd_rx) = mpsc::channel();
+        let (evt_tx, evt_rx) = mpsc::channel();
+        start_layout_runtime(cmd_rx, evt_tx);
+
+        let document = || Node::Document {
+            id: Id(1),
+            doctype: None,
+            children: vec![node_element_from_parts(
+                Id(2),
+                html_name("div"),
+                Vec::new(),
+                Vec::new(),
+                Vec::new(),
+            )],
+        };
+        let key = RetainedLayoutKeySeed {
+            identity_domain: 1,
+            layout_input_generation: 2,
+            layout_style_generation: 3,
+            text_measurement_generation: 0,
+            replaced_metadata_generation: 0,
+        }
+        .for_viewport_width(400.0);
+        cmd_tx
+            .send(CoreCommand::LayoutDocument {
+                tab_id: 4,
+                request_id: 5,
+                job: InProcess::new(Box::new(LayoutJob {
+                    dom: Box::new(document()),
+                    stylesheets: vec![LayoutStylesheet {
+                        origin: CascadeOrigin::Author,
+                        stylesheet: parse_stylesheet_with_options(
+                            "div { display: block; height: 30px; }",
+                            &ParseOptions::stylesheet(),
+                        ),
+                    }],
+                    element_states: SelectorElementStates::default(),
+                    viewport_width: 400.0,
+                    media: MediaEnvironment::default(),
+                    zoom: 2.0,
+                    key,
+                    image_sources: HashMap::new(),
+                    image_sizes: HashMap::new(),
+                    resized_controls: HashMap::new(),
+                    measurer: Box::new(FixedMeasurer),
+                })),
+            })
+            .expect("send LayoutDocument");
+
+        let event = evt_rx
+            .recv_timeout(Duration::from_secs(5))
+            .expect("layout runtime event");
+        let CoreEvent::LayoutReady {
+            tab_id: 4,
+            request_id: 5,
+            key: event_key,
+            layout: Ok(artifact),
+        } = event
+        else {
+            panic!("expected a layout for the job: {event:?}");
+        };
+        let artifact = artifact.into_inner();
+        assert_eq!(event_key, key);
+        assert_eq!(artifact.key(), key);
+
+        let dom = document();
+        let style_root = css::build_style_tree(&dom, None);
+        let output = artifact.materialize(&style_root).expect("materialize");
+        assert_eq!(output.viewport_width(), 400.0);
+        assert_eq!(output.content_height(), 60.0);
+
+        let event = evt_rx
+            .recv_timeout(Duration::from_secs(5))
+            .expect("layout metrics event");
+        let CoreEvent::Metrics {
+            tab_id: 4,
+            request_id: 5,
+            metrics,
+            ..
+        } = event
+        else {
+            panic!("expected metrics for the job: {event:?}");
+        };
+        assert_eq!(metrics.css_rules_applied, 1);
+    }
+}
@@ -0,0 +1,117 @@
+//! In-memory cache of stylesheets and images, shared by every tab.
+
+use core_types::{NetworkResponseInfo, ResourceKind};
+use net::Validators;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+/// Bytes of response bodies the cache keeps before evicting the oldest.
+pub(crate) const RESOURCE_CACHE_BYTES: usize = 64 * 1024 * 1024;
+
+/// A resource and what it is cached under.
+pub(crate) type CacheKey = (ResourceKind, String);
+
+/// A stored response. It is only used after the server confirmed that
+/// `validators` still match, so a changed resource is never served.
+pub(crate) struct CachedResource {
+    pub(crate) validators: Validators,
+    pub(crate) response: NetworkResponseInfo,
+    pub(crate) body: Arc<[u8]>,
+}
+
+/// Stored responses by kind and URL, evicted oldest first once their bodies
+/// exceed the byte budget.
+pub(crate) struct ResourceCache {
+    entries: HashMap<CacheKey, CachedResource>,
+    /// Keys in insertion order, oldest first.
+    order: VecDeque<CacheKey>,
+    bytes: usize,
+    budget: usize,
+}
+
+impl Default for ResourceCache {
+    fn default() -> Self {
+        Self::with_budget(RESOURCE_CACHE_BYTES)
+    }
+}
+
+impl ResourceCache {
+    pub(crate) fn with_budget(budget: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            bytes: 0,
+            budget,
+        }
+    }
+
+    pub(crate) fn get(&self, key: &CacheKey) -> Option<&CachedResource> {
+        self.entries.get(key)
+    }
+
+    /// Store `resource` under `key`, replacing an older copy. Bodies larger
+    /// than the whole budget are not stored.
+    pub(crate) fn insert(&mut self, key: CacheKey, resource: CachedResource) {
+        self.remove(&key);
+        if resource.body.len() > self.budget {
+            return;
+        }
+        self.bytes += resource.body.len();
+        self.order.push_back(key.clone());
+        self.entries.insert(key, resource);
+        while self.bytes > self.budget {
+            let Some(oldest) = self.order.front().cloned() else {
+                break;
+            };
+            self.remove(&oldest);
+        }
+    }
+
+    fn remove(&mut self, key: &CacheKey) {
+        if let Some(old) = self.entries.remove(key) {
+            self.bytes -= old.body.len();
+            self.order.retain(|queued| queued != key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CachedResource, ResourceCache};
+    use core_types::{NetworkResponseInfo, ResourceKind};
+    use net::Validators;
+
+    fn resource(len: usize) -> CachedResource {
+        CachedResource {
+            validators: Validators::default(),
+            response: NetworkResponseInfo {
+                requested_url: String::new(),
+                final_url: String::new(),
+                status_code: Some(200),
+                content_type: None,
+                content_length: None,
+                headers: Vec::new(),
+            },
+            body: vec![0; len].into(),
+        }
+    }
+
+    fn key(url: &str) -> (ResourceKind, String) {
+        (ResourceKind::Image, url.to_string())
+    }
+
+    #[test]
+    fn the_oldest_entries_go_once_the_budget_is_exceeded() {
+        let mut cache = ResourceCache::with_budget(10);
+        cache.insert(key("a"), resource(4));
+        cache.insert(key("b"), resource(4));
+        cache.insert(key("a"), resource(4));
+        cache.insert(key("c"), resource(4));
+        cache.insert(key("huge"), resource(11));
+
+        assert!(cache.get(&key("b")).is_none());
+        assert!(cache.get(&key("a")).is_some());
+        assert!(cache.get(&key("c")).is_some());
+        assert!(cache.get(&key("huge")).is_none());
+    }
+}
@@ -1,48 +1,137 @@
-use core_types::{RequestId, TabId};
+use core_types::{RequestId, ResourceKind, TabId};
 use std::collections::HashMap;
 use std::sync::{
     Arc,
     atomic::{AtomicBool, Ordering},
     mpsc::{Receiver, Sender},
 };
-use std::thread;
+use std::thread::{self, JoinHandle};
 
-use bus::{CoreCommand, CoreEvent};
-use net::{NetEvent, fetch_stream};
+use bus::{ChunkCredits, CoreCommand, CoreEvent, RuntimeKind, RuntimeMetrics, run_supervised};
+use net::{NetEvent, fetch_stream, fetch_stream_revalidating, fetch_stream_with_payload};
 
-pub fn start_net_runtime(cmd_rx: Receiver<CoreCommand>, evt_tx: Sender<CoreEvent>) {
+mod cache;
+mod shared;
+
+use shared::{Requester, SharedFetchesHandle};
+
+/// Cancel flags of the document fetches, and the shared fetches of
+/// stylesheets and images.
+#[derive(Default)]
+struct NetState {
+    // one cancel flag per navigation request_id
+    cancels: HashMap<(TabId, RequestId), Arc<AtomicBool>>,
+    shared: SharedFetchesHandle,
+}
+
+/// Start the network runtime.
+///
+/// Document streams are flow controlled through `credits`: a fetch thread
+/// waits for the parse runtime before sending more than a window of document
+/// bytes. Cancelling a request closes its window, so a waiting fetch thread
+/// wakes up and stops. Once the parse runtime has read as much of a document
+/// as its `ParseLimits` allow, it stops the window and the download ends
+/// with a `Cancelled` error.
+///
+/// Stylesheets and images are fetched once for everyone asking for them at
+/// the same time, and revalidated from an in-memory cache shared by all
+/// tabs; see the `shared` module.
+///
+/// Every completed fetch reports the bytes it received with
+/// `CoreEvent::Metrics`.
+pub fn start_net_runtime(
+    cmd_rx: Receiver<CoreCommand>,
+    evt_tx: Sender<CoreEvent>,
+    credits: ChunkCredits,
+) -> JoinHandle<()> {
     thread::spawn(move || {
-        // one cancel flag per navigation request_id
-        let mut cancels: HashMap<(TabId, RequestId), Arc<AtomicBool>> = HashMap::new();
+        run_supervised(
+            RuntimeKind::Net,
+            &cmd_rx,
+            &evt_tx,
+            NetState::default,
+            |state: &mut NetState, cmd| match cmd {
+                CoreCommand::FetchStream {
+                    tab_id,
+                    request_id,
+                    stylesheet_slot_id,
+                    url,
+                    kind,
+                    payload: _,
+                } if kind != ResourceKind::Html => {
+                    let requester = Requester {
+                        tab_id,
+                        request_id,
+                        stylesheet_slot_id,
+                    };
+                    fetch_shared(&state.shared, (kind, url), requester, &evt_tx);
+                }
 
-        while let Ok(cmd) = cmd_rx.recv() {
-            match cmd {
+                // Documents are fetched per navigation, with the method and body
+                // the tab asked for, and flow controlled
                 CoreCommand::FetchStream {
                     tab_id,
                     request_id,
                     stylesheet_slot_id,
                     url,
                     kind,
+                    payload,
                 } => {
                     // Get or create the cancel flag in a short scope so the mutable borrow ends here:
                     let cancel = {
-                        cancels
+                        state
+                            .cancels
                             .entry((tab_id, request_id))
                             .or_insert_with(|| Arc::new(AtomicBool::new(false)))
                             .clone()
                     };
 
                     let evt_tx = evt_tx.clone();
+                    let credits = credits.clone();
+                    let stop = cancel.clone();
+                    credits.open(tab_id, request_id);
 
-                    fetch_stream(
+                    let _span =
+                        tracing::info_span!("fetch", tab_id, request_id, url = %url).entered();
+                    fetch_stream_with_payload(
                         request_id,
                         url.clone(),
                         kind,
+                        payload,
                         cancel.clone(),
                         Arc::new(move |e: NetEvent| match e {
+                            NetEvent::Redirected {
+                                request_id,
+                                from,
+                                to,
+                            } => {
+                                let _ = evt_tx.send(CoreEvent::NetworkRedirected {
+                                    tab_id,
+                                    request_id,
+                                    stylesheet_slot_id,
+                                    kind,
+                                    from,
+                                    to,
+                                });
+                            }
+                            NetEvent::Connection {
+                                request_id,
+                                origin,
+                                reused,
+                            } => {
+                                let _ = evt_tx.send(CoreEvent::NetworkConnection {
+                                    tab_id,
+                                    request_id,
+                                    stylesheet_slot_id,
+                                    kind,
+                                    origin,
+                                    reused,
+                                });
+                            }
                             NetEvent::Start {
                                 request_id,
                                 response,
+                                ..
                             } => {
                                 let _ = evt_tx.send(CoreEvent::NetworkStart {
                                     tab_id,
@@ -57,6 +146,10 @@ pub fn start_net_runtime(cmd_rx: Receiver<CoreCommand>, evt_tx: Sender<CoreEvent
                                 url,
                                 chunk,
                             } => {
+                                if !credits.acquire(tab_id, request_id, chunk.len()) {
+                                    stop.store(true, Ordering::Release);
+                                    return;
+                                }
                                 let _ = evt_tx.send(CoreEvent::NetworkChunk {
                                     tab_id,
                                     request_id,
@@ -71,6 +164,7 @@ pub fn start_net_runtime(cmd_rx: Receiver<CoreCommand>, evt_tx: Sender<CoreEvent
                                 response,
                                 bytes_received,
                             } => {
+                                credits.close(tab_id, request_id);
                                 let _ = evt_tx.send(CoreEvent::NetworkDone {
                                     tab_id,
                                     request_id,
@@ -79,37 +173,108 @@ pub fn start_net_runtime(cmd_rx: Receiver<CoreCommand>, evt_tx: Sender<CoreEvent
                                     response,
                                     bytes_received,
                                 });
+                                let _ = evt_tx.send(CoreEvent::Metrics {
+                                    tab_id,
+                                    request_id,
+                                    which: RuntimeKind::Net,
+                                    metrics: RuntimeMetrics {
+                                        bytes_fetched: bytes_received as u64,
+                                        ..RuntimeMetrics::default()
+                                    },
+                                });
                             }
                             NetEvent::Error {
                                 request_id,
                                 url,
-                                error_kind,
-                                status_code,
                                 error,
+                                message,
                             } => {
+                                credits.close(tab_id, request_id);
                                 let _ = evt_tx.send(CoreEvent::NetworkError {
                                     tab_id,
                                     request_id,
                                     stylesheet_slot_id,
                                     kind,
                                     url,
-                                    error_kind,
-                                    status_code,
                                     error,
+                                    message,
                                 });
                             }
+                            // Documents are not revalidated
+                            NetEvent::NotModified { .. } => {}
                         }),
                     );
                 }
 
                 CoreCommand::CancelRequest { tab_id, request_id } => {
-                    if let Some(flag) = cancels.get(&(tab_id, request_id)) {
+                    if let Some(flag) = state.cancels.get(&(tab_id, request_id)) {
                         flag.store(true, Ordering::Release);
                     }
+                    // Wakes a fetch thread waiting for document credit.
+                    credits.close(tab_id, request_id);
+                    state.shared.lock().cancel(
+                        |tab, request| tab == tab_id && request == request_id,
+                        &evt_tx,
+                    );
+                }
+
+                CoreCommand::CancelGeneration { tab_id, request_id } => {
+                    state.shared.lock().cancel(
+                        |tab, request| tab == tab_id && request <= request_id,
+                        &evt_tx,
+                    );
+                    state.cancels.retain(|&(tab, request), flag| {
+                        if tab != tab_id || request > request_id {
+                            return true;
+                        }
+                        flag.store(true, Ordering::Release);
+                        credits.close(tab, request);
+                        false
+                    });
+                }
+
+                CoreCommand::TabClosed { tab_id } => {
+                    state.shared.lock().cancel(|tab, _| tab == tab_id, &evt_tx);
                 }
 
                 _ => {}
-            }
-        }
+            },
+        );
+    })
+}
+
+/// Join the running fetch of `key` or start one, revalidating a cached copy
+/// if there is one.
+fn fetch_shared(
+    shared: &SharedFetchesHandle,
+    key: (ResourceKind, String),
+    requester: Requester,
+    evt_tx: &Sender<CoreEvent>,
+) {
+    let Some(fetch) = shared.lock().join(&key, requester, evt_tx) else {
+        return;
+    };
+    let (kind, url) = key.clone();
+    let _span = tracing::info_span!(
+        "fetch",
+        tab_id = requester.tab_id,
+        request_id = requester.request_id,
+        url = %url
+    )
+    .entered();
+    let (shared, evt_tx) = (shared.clone(), evt_tx.clone());
+    let callback = Arc::new(move |e: NetEvent| {
+        shared.lock().on_event(&key, fetch.id, e, &evt_tx);
     });
+    match fetch.revalidate {
+        Some(validators) => fetch_stream_revalidating(
+            requester.request_id,
+            url,
+            kind,
+            validators,
+            fetch.cancel,
+            callback,
+        ),
+        None => fetch_stream(requester.request_id, url, kind, fetch.cancel, callback),
+    }
 }
@@ -0,0 +1,612 @@
+//! Stylesheet and image fetches shared by every request for the same URL.
+//!
+//! When tabs, or one page twice, ask for a resource that is already being
+//! fetched, the new request joins the running fetch instead of starting
+//! another: it gets what arrived so far replayed, redirects included, then
+//! the rest of the stream along with the others. A request that is
+//! cancelled leaves the fetch with a `Cancelled` error of its own, and the
+//! fetch itself stops only once no request is left.
+//!
+//! Finished responses that carry validators go into the [`ResourceCache`].
+//! A later fetch of the same URL revalidates them, and on `304 Not Modified`
+//! the stored body is streamed to the requesters as if it had been fetched,
+//! metrics included.
+
+use crate::cache::{CacheKey, CachedResource, ResourceCache};
+use bus::{CoreEvent, RuntimeKind, RuntimeMetrics};
+use core_types::{NetError, NetworkResponseInfo, RequestId, ResourceKind, StylesheetSlotId, TabId};
+use net::{NetEvent, Validators};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// A request waiting for a shared fetch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Requester {
+    pub(crate) tab_id: TabId,
+    pub(crate) request_id: RequestId,
+    pub(crate) stylesheet_slot_id: Option<StylesheetSlotId>,
+}
+
+/// A fetch the caller of [`SharedFetches::join`] has to start.
+pub(crate) struct NewFetch {
+    /// Identifies the fetch in [`SharedFetches::on_event`].
+    pub(crate) id: u64,
+    pub(crate) cancel: Arc<AtomicBool>,
+    /// Validators of a cached copy to revalidate, if there is one.
+    pub(crate) revalidate: Option<Validators>,
+}
+
+struct SharedFetch {
+    id: u64,
+    requesters: Vec<Requester>,
+    cancel: Arc<AtomicBool>,
+    /// What arrived so far, replayed to requests that join late.
+    redirects: Vec<(String, String)>,
+    response: Option<NetworkResponseInfo>,
+    validators: Option<Validators>,
+    body: Vec<u8>,
+}
+
+/// Running shared fetches and the cache behind them.
+#[derive(Default)]
+pub(crate) struct SharedFetches {
+    fetches: HashMap<CacheKey, SharedFetch>,
+    cache: ResourceCache,
+    next_id: u64,
+}
+
+/// [`SharedFetches`] as the runtime thread and the fetch threads use it.
+#[derive(Clone, Default)]
+pub(crate) struct SharedFetchesHandle(Arc<Mutex<SharedFetches>>);
+
+impl SharedFetchesHandle {
+    pub(crate) fn lock(&self) -> MutexGuard<'_, SharedFetches> {
+        // Every update leaves the fetches consistent, so a panic while the
+        // lock is held does not corrupt them.
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl SharedFetches {
+    /// Add `requester` to the fetch of `key`. Returns the fetch to start if
+    /// none is running; otherwise what the fetch received so far is sent to
+    /// `requester` right away.
+    pub(crate) fn join(
+        &mut self,
+        key: &CacheKey,
+        requester: Requester,
+        evt_tx: &Sender<CoreEvent>,
+    ) -> Option<NewFetch> {
+        if let Some(fetch) = self.fetches.get_mut(key) {
+            let (kind, url) = key;
+            for (from, to) in &fetch.redirects {
+                send_redirect(evt_tx, requester, *kind, from, to);
+            }
+            if let Some(response) = &fetch.response {
+                send_start(evt_tx, requester, *kind, response);
+            }
+            if !fetch.body.is_empty() {
+                send_chunk(evt_tx, requester, *kind, url, fetch.body.clone());
+            }
+            fetch.requesters.push(requester);
+            return None;
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.fetches.insert(
+            key.clone(),
+            SharedFetch {
+                id,
+                requesters: vec![requester],
+                cancel: cancel.clone(),
+                redirects: Vec::new(),
+                response: None,
+                validators: None,
+                body: Vec::new(),
+            },
+        );
+        Some(NewFetch {
+            id,
+            cancel,
+            revalidate: self.cache.get(key).map(|cached| cached.validators.clone()),
+        })
+    }
+
+    /// Pass `event` of fetch `id` on to everyone waiting for it. Events of a
+    /// fetch that was cancelled or replaced are dropped.
+    pub(crate) fn on_event(
+        &mut self,
+        key: &CacheKey,
+        id: u64,
+        event: NetEvent,
+        evt_tx: &Sender<CoreEvent>,
+    ) {
+        let Some(fetch) = self.fetches.get_mut(key).filter(|fetch| fetch.id == id) else {
+            return;
+        };
+        let (kind, _) = key;
+        match event {
+            NetEvent::Redirected { from, to, .. } => {
+                for &requester in &fetch.requesters {
+                    send_redirect(evt_tx, requester, *kind, &from, &to);
+                }
+                fetch.redirects.push((from, to));
+            }
+            NetEvent::Connection { origin, reused, .. } => {
+                for &requester in &fetch.requesters {
+                    let _ = evt_tx.send(CoreEvent::NetworkConnection {
+                        tab_id: requester.tab_id,
+                        request_id: requester.request_id,
+                        stylesheet_slot_id: requester.stylesheet_slot_id,
+                        kind: *kind,
+                        origin: origin.clone(),
+                        reused,
+                    });
+                }
+            }
+            NetEvent::Start {
+                response,
+                validators,
+                ..
+            } => {
+                for &requester in &fetch.requesters {
+                    send_start(evt_tx, requester, *kind, &response);
+                }
+                fetch.response = Some(response);
+                fetch.validators = validators;
+            }
+            NetEvent::Chunk { url, chunk, .. } => {
+                for &requester in &fetch.requesters {
+                    send_chunk(evt_tx, requester, *kind, &url, chunk.clone());
+                }
+                fetch.body.extend_from_slice(&chunk);
+            }
+            NetEvent::Done {
+                response,
+                bytes_received,
+                ..
+            } => {
+                let Some(fetch) = self.fetches.remove(key) else {
+                    return;
+                };
+                for &requester in &fetch.requesters {
+                    send_done(evt_tx, requester, *kind, &response, bytes_received);
+                    send_metrics(evt_tx, requester, bytes_received);
+                }
+                let succeeded = response
+                    .status_code
+                    .is_some_and(|status| (200..300).contains(&status));
+                if let (Some(validators), true) = (fetch.validators, succeeded) {
+                    self.cache.insert(
+                        key.clone(),
+                        CachedResource {
+                            validators,
+                            response,
+                            body: fetch.body.into(),
+                        },
+                    );
+                }
+            }
+            NetEvent::NotModified { .. } => {
+                let Some(fetch) = self.fetches.remove(key) else {
+                    return;
+                };
+                let (kind, url) = key;
+                let Some(cached) = self.cache.get(key) else {
+                    // Evicted while the server was asked; nothing to serve.
+                    for &requester in &fetch.requesters {
+                        send_error(
+                            evt_tx,
+                            requester,
+                            *kind,
+                            url,
+                            NetError::Io,
+                            "cached copy evicted during revalidation".to_string(),
+                        );
+                    }
+                    return;
+                };
+                for &requester in &fetch.requesters {
+                    send_start(evt_tx, requester, *kind, &cached.response);
+                    send_chunk(evt_tx, requester, *kind, url, cached.body.to_vec());
+                    send_done(
+                        evt_tx,
+                        requester,
+                        *kind,
+                        &cached.response,
+                        cached.body.len(),
+                    );
+                    send_metrics(evt_tx, requester, cached.body.len());
+                }
+            }
+            NetEvent::Error {
+                url,
+                error,
+                message,
+                ..
+            } => {
+                let Some(fetch) = self.fetches.remove(key) else {
+                    return;
+                };
+                for &requester in &fetch.requesters {
+                    let _ = evt_tx.send(CoreEvent::NetworkError {
+                        tab_id: requester.tab_id,
+                        request_id: requester.request_id,
+                        stylesheet_slot_id: requester.stylesheet_slot_id,
+                        kind: *kind,
+                        url: url.clone(),
+                        error,
+                        message: message.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    /// Take the requests `cancelled` selects out of their fetches, sending
+    /// each a `Cancelled` error. Fetches nobody waits for anymore stop.
+    pub(crate) fn cancel(
+        &mut self,
+        cancelled: impl Fn(TabId, RequestId) -> bool,
+        evt_tx: &Sender<CoreEvent>,
+    ) {
+        self.fetches.retain(|(kind, url), fetch| {
+            fetch.requesters.retain(|&requester| {
+                if !cancelled(requester.tab_id, requester.request_id) {
+                    return true;
+                }
+                send_error(
+                    evt_tx,
+                    requester,
+                    *kind,
+                    url,
+                    NetError::Cancelled,
+                    "cancelled".to_string(),
+                );
+                false
+            });
+            if fetch.requesters.is_empty() {
+                fetch.cancel.store(true, Ordering::Release);
+                return false;
+            }
+            true
+        });
+    }
+}
+
+fn send_redirect(
+    evt_tx: &Sender<CoreEvent>,
+    requester: Requester,
+    kind: ResourceKind,
+    from: &str,
+    to: &str,
+) {
+    let _ = evt_tx.send(CoreEvent::NetworkRedirected {
+        tab_id: requester.tab_id,
+        request_id: requester.request_id,
+        stylesheet_slot_id: requester.stylesheet_slot_id,
+        kind,
+        from: from.to_string(),
+        to: to.to_string(),
+    });
+}
+
+fn send_start(
+    evt_tx: &Sender<CoreEvent>,
+    requester: Requester,
+    kind: ResourceKind,
+    response: &NetworkResponseInfo,
+) {
+    let _ = evt_tx.send(CoreEvent::NetworkStart {
+        tab_id: requester.tab_id,
+        request_id: requester.request_id,
+        stylesheet_slot_id: requester.stylesheet_slot_id,
+        kind,
+        response: response.clone(),
+    });
+}
+
+fn send_chunk(
+    evt_tx: &Sender<CoreEvent>,
+    requester: Requester,
+    kind: ResourceKind,
+    url: &str,
+    bytes: Vec<u8>,
+) {
+    let _ = evt_tx.send(CoreEvent::NetworkChunk {
+        tab_id: requester.tab_id,
+        request_id: requester.request_id,
+        stylesheet_slot_id: requester.stylesheet_slot_id,
+        kind,
+        url: url.to_string(),
+        bytes,
+    });
+}
+
+fn send_done(
+    evt_tx: &Sender<CoreEvent>,
+    requester: Requester,
+    kind: ResourceKind,
+    response: &NetworkResponseInfo,
+    bytes_received: usize,
+) {
+    let _ = evt_tx.send(CoreEvent::NetworkDone {
+        tab_id: requester.tab_id,
+        request_id: requester.request_id,
+        stylesheet_slot_id: requester.stylesheet_slot_id,
+        kind,
+        response: response.clone(),
+        bytes_received,
+    });
+}
+
+fn send_metrics(evt_tx: &Sender<CoreEvent>, requester: Requester, bytes_fetched: usize) {
+    let _ = evt_tx.send(CoreEvent::Metrics {
+        tab_id: requester.tab_id,
+        request_id: requester.request_id,
+        which: RuntimeKind::Net,
+        metrics: RuntimeMetrics {
+            bytes_fetched: bytes_fetched as u64,
+            ..RuntimeMetrics::default()
+        },
+    });
+}
+
+fn send_error(
+    evt_tx: &Sender<CoreEvent>,
+    requester: Requester,
+    kind: ResourceKind,
+    url: &str,
+    error: NetError,
+    message: String,
+) {
+    let _ = evt_tx.send(CoreEvent::NetworkError {
+        tab_id: requester.tab_id,
+        request_id: requester.request_id,
+        stylesheet_slot_id: requester.stylesheet_slot_id,
+        kind,
+        url: url.to_string(),
+        error,
+        message,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Requester, SharedFetches};
+    use bus::CoreEvent;
+    use core_types::{NetError, NetworkResponseInfo, ResourceKind, StylesheetSlotId};
+    use net::{NetEvent, Validators};
+    use std::sync::atomic::Ordering;
+    use std::sync::mpsc;
+
+    const URL: &str = "https://example.com/site.css";
+
+    fn key() -> (ResourceKind, String) {
+        (ResourceKind::Css, URL.to_string())
+    }
+
+    fn requester(tab_id: u64) -> Requester {
+        Requester {
+            tab_id,
+            request_id: 1,
+            stylesheet_slot_id: Some(StylesheetSlotId(tab_id)),
+        }
+    }
+
+    fn response(status: u16) -> NetworkResponseInfo {
+        NetworkResponseInfo {
+            requested_url: URL.to_string(),
+            final_url: URL.to_string(),
+            status_code: Some(status),
+            content_type: Some("text/css".to_string()),
+            content_length: None,
+            headers: Vec::new(),
+        }
+    }
+
+    fn chunk(bytes: &[u8]) -> NetEvent {
+        NetEvent::Chunk {
+            request_id: 1,
+            url: URL.to_string(),
+            chunk: bytes.to_vec(),
+        }
+    }
+
+    fn done(bytes_received: usize) -> NetEvent {
+        NetEvent::Done {
+            request_id: 1,
+            response: response(200),
+            bytes_received,
+        }
+    }
+
+    /// The network events received, as `tab: event` lines.
+    fn received(evt_rx: &mpsc::Receiver<CoreEvent>) -> Vec<String> {
+        evt_rx
+            .try_iter()
+            .filter_map(|evt| match evt {
+                CoreEvent::NetworkStart { tab_id, .. } => Some(format!("{tab_id}: start")),
+                CoreEvent::NetworkChunk { tab_id, bytes, .. } => {
+                    Some(format!("{tab_id}: {}", String::from_utf8_lossy(&bytes)))
+                }
+                CoreEvent::NetworkDone { tab_id, .. } => Some(format!("{tab_id}: done")),
+                CoreEvent::NetworkRedirected { tab_id, to, .. } => {
+                    Some(format!("{tab_id}: redirected to {to}"))
+                }
+                CoreEvent::Metrics {
+                    tab_id, metrics, ..
+                } => Some(format!("{tab_id}: {} bytes", metrics.bytes_fetched)),
+                CoreEvent::NetworkError { tab_id, error, .. } => {
+                    Some(format!("{tab_id}: {error:?}"))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn a_request_joining_a_running_fetch_gets_what_arrived_and_the_rest() {
+        let (evt_tx, evt_rx) = mpsc::channel();
+        let mut shared = SharedFetches::default();
+        let fetch = shared
+            .join(&key(), requester(1), &evt_tx)
+            .expect("first request starts the fetch");
+        shared.on_event(
+            &key(),
+            fetch.id,
+            NetEvent::Start {
+                request_id: 1,
+                response: response(200),
+                validators: None,
+            },
+            &evt_tx,
+        );
+        shared.on_event(&key(), fetch.id, chunk(b"p{"), &evt_tx);
+
+        assert!(shared.join(&key(), requester(2), &evt_tx).is_none());
+        shared.on_event(&key(), fetch.id, chunk(b"}"), &evt_tx);
+        shared.on_event(&key(), fetch.id, done(3), &evt_tx);
+
+        assert_eq!(
+            received(&evt_rx),
+            [
+                "1: start",
+                "1: p{",
+                "2: start",
+                "2: p{",
+                "1: }",
+                "2: }",
+                "1: done",
+                "1: 3 bytes",
+                "2: done",
+                "2: 3 bytes",
+            ]
+        );
+        // Finished without validators: the next request fetches again.
+        let next = shared
+            .join(&key(), requester(3), &evt_tx)
+            .expect("new fetch");
+        assert!(next.revalidate.is_none());
+    }
+
+    #[test]
+    fn a_fetch_stops_once_every_request_for_it_is_cancelled() {
+        let (evt_tx, evt_rx) = mpsc::channel();
+        let mut shared = SharedFetches::default();
+        let fetch = shared.join(&key(), requester(1), &evt_tx).expect("fetch");
+        assert!(shared.join(&key(), requester(2), &evt_tx).is_none());
+
+        shared.cancel(|tab, _| tab == 1, &evt_tx);
+        assert!(!fetch.cancel.load(Ordering::Acquire));
+        shared.cancel(|tab, _| tab == 2, &evt_tx);
+        assert!(fetch.cancel.load(Ordering::Acquire));
+
+        // The stopped fetch's own error is not passed on again.
+        shared.on_event(
+            &key(),
+            fetch.id,
+            NetEvent::Error {
+                request_id: 1,
+                url: URL.to_string(),
+                error: NetError::Cancelled,
+                message: "cancelled".to_string(),
+            },
+            &evt_tx,
+        );
+        assert_eq!(received(&evt_rx), ["1: Cancelled", "2: Cancelled"]);
+    }
+
+    #[test]
+    fn a_cached_copy_the_server_confirms_is_served_from_memory() {
+        let (evt_tx, evt_rx) = mpsc::channel();
+        let mut shared = SharedFetches::default();
+        let validators = Validators {
+            etag: Some("\"v1\"".to_string()),
+            last_modified: None,
+        };
+        let fetch = shared.join(&key(), requester(1), &evt_tx).expect("fetch");
+        shared.on_event(
+            &key(),
+            fetch.id,
+            NetEvent::Start {
+                request_id: 1,
+                response: response(200),
+                validators: Some(validators.clone()),
+            },
+            &evt_tx,
+        );
+        shared.on_event(&key(), fetch.id, chunk(b"p{}"), &evt_tx);
+        shared.on_event(&key(), fetch.id, done(3), &evt_tx);
+        let _ = received(&evt_rx);
+
+        let fetch = shared.join(&key(), requester(2), &evt_tx).expect("fetch");
+        assert_eq!(fetch.revalidate, Some(validators));
+        shared.on_event(
+            &key(),
+            fetch.id,
+            NetEvent::NotModified {
+                request_id: 1,
+                response: response(304),
+            },
+            &evt_tx,
+        );
+
+        assert_eq!(
+            received(&evt_rx),
+            ["2: start", "2: p{}", "2: done", "2: 3 bytes"]
+        );
+    }
+
+    #[test]
+    fn a_request_joining_a_redirected_fetch_hears_about_every_hop() {
+        let (evt_tx, evt_rx) = mpsc::channel();
+        let mut shared = SharedFetches::default();
+        let fetch = shared.join(&key(), requester(1), &evt_tx).expect("fetch");
+        for (from, to) in [("/a.css", "/b.css"), ("/b.css", URL)] {
+            shared.on_event(
+                &key(),
+                fetch.id,
+                NetEvent::Redirected {
+                    request_id: 1,
+                    from: from.to_string(),
+                    to: to.to_string(),
+                },
+                &evt_tx,
+            );
+        }
+        shared.on_event(
+            &key(),
+            fetch.id,
+            NetEvent::Start {
+                request_id: 1,
+                response: response(200),
+                validators: None,
+            },
+            &evt_tx,
+        );
+
+        assert!(shared.join(&key(), requester(2), &evt_tx).is_none());
+        shared.on_event(&key(), fetch.id, chunk(b"p{}"), &evt_tx);
+
+        let late: Vec<String> = received(&evt_rx)
+            .into_iter()
+            .filter(|line| line.starts_with("2:"))
+            .collect();
+        assert_eq!(
+            late,
+            [
+                "2: redirected to /b.css".to_string(),
+                format!("2: redirected to {URL}"),
+                "2: start".to_string(),
+                "2: p{}".to_string(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,88 @@
+//! Streaming transcoder between the network bytes of a document and the
+//! parser, which takes Unicode text.
+
+use encoding_rs::{Decoder, Encoding};
+
+use crate::sniff::{
+    PRESCAN_BYTES, bom_encoding, content_type_encoding, fallback_encoding, prescan,
+};
+
+/// Decodes a document's bytes, chunk by chunk, in the encoding
+/// [`crate::sniff`] picks for it.
+///
+/// Without a BOM or `Content-Type` charset, the encoding depends on the
+/// first [`PRESCAN_BYTES`] bytes, so those are held back until they all
+/// arrived or the document ended.
+pub(crate) struct DocumentDecoder {
+    transport: Option<&'static Encoding>,
+    held: Vec<u8>,
+    decoder: Option<Decoder>,
+}
+
+impl DocumentDecoder {
+    /// A decoder for a document served with `content_type`.
+    pub(crate) fn new(content_type: Option<&str>) -> Self {
+        Self {
+            transport: content_type.and_then(content_type_encoding),
+            held: Vec::new(),
+            decoder: None,
+        }
+    }
+
+    /// Decode the next chunk of the document. Returns the text that is
+    /// ready, which is empty while the encoding is still undecided.
+    pub(crate) fn push(&mut self, bytes: &[u8]) -> String {
+        if let Some(decoder) = &mut self.decoder {
+            return decode(decoder, bytes, false);
+        }
+        self.held.extend_from_slice(bytes);
+        self.decide(false);
+        self.drain_held()
+    }
+
+    /// Decode whatever is left at the end of the document.
+    pub(crate) fn finish(&mut self) -> String {
+        if self.decoder.is_none() {
+            self.decide(true);
+        }
+        let mut text = self.drain_held();
+        if let Some(decoder) = &mut self.decoder {
+            text.push_str(&decode(decoder, &[], true));
+        }
+        text
+    }
+
+    fn decide(&mut self, complete: bool) {
+        // The longest BOM is three bytes.
+        let encoding = if self.held.len() < 3 && !complete {
+            None
+        } else if let Some(encoding) = bom_encoding(&self.held) {
+            Some(encoding)
+        } else if let Some(encoding) = self.transport {
+            Some(encoding)
+        } else if self.held.len() >= PRESCAN_BYTES || complete {
+            let prefix = &self.held[..self.held.len().min(PRESCAN_BYTES)];
+            Some(prescan(prefix).unwrap_or_else(|| fallback_encoding(prefix, complete)))
+        } else {
+            None
+        };
+        self.decoder = encoding.map(Encoding::new_decoder_with_bom_removal);
+    }
+
+    /// Decode the bytes held back while the encoding was undecided.
+    fn drain_held(&mut self) -> String {
+        match &mut self.decoder {
+            Some(decoder) => decode(decoder, &std::mem::take(&mut self.held), false),
+            None => String::new(),
+        }
+    }
+}
+
+fn decode(decoder: &mut Decoder, bytes: &[u8], last: bool) -> String {
+    let capacity = decoder
+        .max_utf8_buffer_length(bytes.len())
+        .unwrap_or(bytes.len().saturating_mul(3));
+    let mut text = String::with_capacity(capacity);
+    let _ = decoder.decode_to_string(bytes, &mut text, last);
+    text
+}
@@ -1,9 +1,9 @@
 use std::sync::mpsc::Sender;
 use std::time::Instant;
 
-use bus::CoreEvent;
+use bus::{CoreEvent, ParseLimit, RuntimeKind, RuntimeMetrics};
 use core_types::{RequestId, TabId};
-use html::HtmlParseError;
+use html::{DomPatch, HtmlParseError};
 use log::error;
 
 use crate::patching::{emit_patch_update, estimate_patch_bytes_slice};
@@ -33,10 +33,31 @@ fn log_runtime_parse_error(tab_id: TabId, request_id: RequestId, err: &HtmlParse
     }
 }
 
+fn creates_node(patch: &DomPatch) -> bool {
+    matches!(
+        patch,
+        DomPatch::CreateDocument { .. }
+            | DomPatch::CreateDocumentType { .. }
+            | DomPatch::CreateElement { .. }
+            | DomPatch::CreateTemplateContents { .. }
+            | DomPatch::CreateText { .. }
+            | DomPatch::CreateComment { .. }
+            | DomPatch::CreateProcessingInstruction { .. }
+    )
+}
+
 impl RuntimeState {
     pub(crate) fn drain_patches(&mut self) -> Result<(), HtmlParseError> {
         let new_patches = self.parser.take_patches()?;
         if !new_patches.is_empty() {
+            if self.milestones.observe(&new_patches) {
+                self.milestone_pending = true;
+            }
+            let created = new_patches
+                .iter()
+                .filter(|patch| creates_node(patch))
+                .count();
+            self.dom_nodes = self.dom_nodes.saturating_add(created as u64);
             self.pending_patch_bytes = self
                 .pending_patch_bytes
                 .saturating_add(estimate_patch_bytes_slice(&new_patches));
@@ -78,6 +99,121 @@ impl RuntimeState {
         }
     }
 
+    /// Send the script parsing stopped at, after the patches leading up to it
+    /// so the script element is in the tab's DOM when the script runs.
+    pub(crate) fn emit_pending_script(
+        &mut self,
+        evt_tx: &Sender<CoreEvent>,
+        tab_id: TabId,
+        request_id: RequestId,
+    ) {
+        let Some(script) = self.parser.take_script() else {
+            return;
+        };
+        self.flush_patch_buffer(evt_tx, tab_id, request_id);
+        if self.failed {
+            return;
+        }
+        if evt_tx
+            .send(CoreEvent::ScriptParsed {
+                tab_id,
+                request_id,
+                source: script.text,
+            })
+            .is_err()
+        {
+            self.failed = true;
+        }
+    }
+
+    /// Send the subresources the preload scanner finds in `text`, before the
+    /// parser sees it.
+    pub(crate) fn emit_resource_hints(
+        &mut self,
+        text: &str,
+        evt_tx: &Sender<CoreEvent>,
+        tab_id: TabId,
+        request_id: RequestId,
+    ) {
+        let Some(preload) = self.preload.as_mut() else {
+            return;
+        };
+        for hint in preload.push_str(text) {
+            if evt_tx
+                .send(CoreEvent::ResourceHint {
+                    tab_id,
+                    request_id,
+                    url: hint.url,
+                    kind: hint.kind,
+                })
+                .is_err()
+            {
+                self.failed = true;
+                return;
+            }
+        }
+    }
+
+    /// Report `limit` the first time the document runs into it.
+    pub(crate) fn emit_truncated(
+        &mut self,
+        limit: ParseLimit,
+        evt_tx: &Sender<CoreEvent>,
+        tab_id: TabId,
+        request_id: RequestId,
+    ) {
+        if self.truncated.contains(&limit) {
+            return;
+        }
+        self.truncated.push(limit);
+        if evt_tx
+            .send(CoreEvent::ResourceTruncated {
+                tab_id,
+                request_id,
+                limit,
+            })
+            .is_err()
+        {
+            self.failed = true;
+        }
+    }
+
+    /// Report the node and depth limits the tree builder dropped content at.
+    fn emit_tree_builder_truncations(
+        &mut self,
+        evt_tx: &Sender<CoreEvent>,
+        tab_id: TabId,
+        request_id: RequestId,
+    ) {
+        let counters = self.parser.counters();
+        if counters.tree_builder_node_limit_hits > 0 {
+            self.emit_truncated(ParseLimit::Nodes, evt_tx, tab_id, request_id);
+        }
+        if counters.tree_builder_depth_limit_hits > 0 {
+            self.emit_truncated(ParseLimit::Depth, evt_tx, tab_id, request_id);
+        }
+    }
+
+    /// Report what parsing this document took; sent once the parse is over.
+    pub(crate) fn emit_metrics(
+        &self,
+        evt_tx: &Sender<CoreEvent>,
+        tab_id: TabId,
+        request_id: RequestId,
+    ) {
+        let _ = evt_tx.send(CoreEvent::Metrics {
+            tab_id,
+            request_id,
+            which: RuntimeKind::Parse,
+            metrics: RuntimeMetrics {
+                tokens_emitted: self.parser.tokens_processed(),
+                parse_time: self.parse_time,
+                dom_nodes: self.dom_nodes,
+                ..RuntimeMetrics::default()
+            },
+        });
+    }
+
     pub(crate) fn update_patch_buffer_max(&mut self) {
         let len = self.patch_buffer.len();
         if len > self.max_patch_buffer_len {
@@ -89,6 +225,7 @@ impl RuntimeState {
     }
 
     pub(crate) fn reset_pending(&mut self) {
+        self.milestone_pending = false;
         self.pending_bytes = 0;
         self.pending_tokens = 0;
         self.pending_patch_bytes = 0;
@@ -114,10 +251,24 @@ pub(crate) fn handle_runtime_chunk(
     if st.failed {
         return true;
     }
+    if st.input_done {
+        // The byte limit cut the document off; the rest is dropped.
+        return false;
+    }
+
+    // Past the byte limit the document ends as if the input had.
+    let allowed = st.limits.max_bytes.saturating_sub(st.total_bytes);
+    let over_byte_limit = bytes.len() > allowed;
+    let bytes = &bytes[..bytes.len().min(allowed)];
 
     st.total_bytes = st.total_bytes.saturating_add(bytes.len());
     st.pending_bytes = st.pending_bytes.saturating_add(bytes.len());
-    if let Err(err) = st.parser.push_bytes(bytes) {
+    let text = st.decoder.push(bytes);
+    st.emit_resource_hints(&text, evt_tx, tab_id, request_id);
+    if st.failed {
+        return true;
+    }
+    if let Err(err) = st.parser.push_str(&text) {
         log_runtime_parse_error(tab_id, request_id, &err);
         st.failed = true;
         st.reset_pending();
@@ -131,15 +282,28 @@ pub(crate) fn handle_runtime_chunk(
         st.reset_pending();
     } else {
         st.update_pending_tokens();
+        st.emit_tree_builder_truncations(evt_tx, tab_id, request_id);
+        st.emit_pending_script(evt_tx, tab_id, request_id);
+        if st.failed {
+            return true;
+        }
     }
 
-    if policy.should_flush(
-        now.saturating_duration_since(st.last_emit),
-        st.pending_tokens,
-        st.pending_bytes,
-        st.patch_buffer.len(),
-        st.pending_patch_bytes,
-    ) {
+    let unapplied_updates = st.version.0.saturating_sub(st.applied_version.0);
+    let policy = PreviewPolicy {
+        tick: policy.tick_for(st.total_bytes, unapplied_updates),
+        ..*policy
+    };
+    let milestone = policy.flush_on_milestones && st.milestone_pending;
+    if milestone
+        || policy.should_flush(
+            now.saturating_duration_since(st.last_emit),
+            st.pending_tokens,
+            st.pending_bytes,
+            st.patch_buffer.len(),
+            st.pending_patch_bytes,
+        )
+    {
         st.last_emit = now;
         maybe_log_large_buffer(st.total_bytes, &mut st.logged_large_buffer);
         st.flush_patch_buffer(evt_tx, tab_id, request_id);
@@ -148,20 +312,37 @@ pub(crate) fn handle_runtime_chunk(
         }
     }
 
+    if over_byte_limit {
+        st.emit_truncated(ParseLimit::Bytes, evt_tx, tab_id, request_id);
+        return handle_runtime_done(st, evt_tx, tab_id, request_id);
+    }
     false
 }
 
+/// Returns whether the parse is over, so its state can be dropped; a parse
+/// stopped at a script finishes on resume.
 pub(crate) fn handle_runtime_done(
-    mut st: Box<RuntimeState>,
+    st: &mut RuntimeState,
     evt_tx: &Sender<CoreEvent>,
     tab_id: TabId,
     request_id: RequestId,
-) {
+) -> bool {
     if st.failed {
         st.flush_patch_buffer(evt_tx, tab_id, request_id);
-        return;
+        return true;
+    }
+    if st.input_done {
+        // Already ended at the byte limit.
+        return false;
     }
-    if let Err(err) = st.parser.finish() {
+    st.input_done = true;
+    let text = st.decoder.finish();
+    st.emit_resource_hints(&text, evt_tx, tab_id, request_id);
+    st.preload = None;
+    if st.failed {
+        return true;
+    }
+    if let Err(err) = st.parser.push_str(&text).and_then(|()| st.parser.finish()) {
         log_runtime_parse_error(tab_id, request_id, &err);
         if matches!(err, HtmlParseError::Decode) {
             st.update_pending_tokens();
@@ -170,14 +351,51 @@ pub(crate) fn handle_runtime_done(
         }
         st.failed = true;
         st.reset_pending();
-        return;
+        return true;
     }
+    flush_after_parse_step(st, evt_tx, tab_id, request_id)
+}
+
+/// Continue a parse stopped at a script. Returns whether the parse is over.
+pub(crate) fn handle_runtime_resume(
+    st: &mut RuntimeState,
+    evt_tx: &Sender<CoreEvent>,
+    tab_id: TabId,
+    request_id: RequestId,
+) -> bool {
+    if st.failed {
+        return true;
+    }
+    if !st.parser.is_suspended() {
+        return false;
+    }
+    if let Err(err) = st.parser.resume() {
+        log_runtime_parse_error(tab_id, request_id, &err);
+        st.failed = true;
+        st.reset_pending();
+        return true;
+    }
+    flush_after_parse_step(st, evt_tx, tab_id, request_id)
+}
+
+fn flush_after_parse_step(
+    st: &mut RuntimeState,
+    evt_tx: &Sender<CoreEvent>,
+    tab_id: TabId,
+    request_id: RequestId,
+) -> bool {
     st.update_pending_tokens();
     if let Err(err) = st.drain_patches() {
         log_runtime_parse_error(tab_id, request_id, &err);
         st.failed = true;
         st.reset_pending();
-        return;
+        return true;
+    }
+    st.emit_tree_builder_truncations(evt_tx, tab_id, request_id);
+    if st.parser.is_suspended() {
+        st.emit_pending_script(evt_tx, tab_id, request_id);
+        return st.failed;
     }
     st.flush_patch_buffer(evt_tx, tab_id, request_id);
+    st.failed || st.input_done
 }
@@ -1,12 +1,16 @@
 mod clock;
+mod decoder;
 mod driver;
+mod milestones;
 mod patching;
 mod policy;
+mod preload;
 mod runtime;
+mod sniff;
 mod state;
 
 #[cfg(test)]
 mod tests;
 
-pub use policy::PreviewPolicy;
+pub use policy::{AdaptiveTick, PreviewPolicy};
 pub use runtime::{start_parse_runtime, start_parse_runtime_with_policy};
@@ -0,0 +1,57 @@
+//! Points in a document worth showing right away.
+//!
+//! Both are read off the patch stream. The end of the head is the first node
+//! the tree builder puts into `<html>` after `<head>`: whitespace after
+//! `</head>` or the body itself. Body content starts with the first node
+//! appended to `<body>`.
+
+use html::{DomPatch, PatchKey};
+
+#[derive(Debug, Default)]
+pub(crate) struct DocumentMilestones {
+    html: Option<PatchKey>,
+    head: Option<PatchKey>,
+    body: Option<PatchKey>,
+    head_done: bool,
+    body_started: bool,
+}
+
+impl DocumentMilestones {
+    /// Track `patches`; returns whether they reach a milestone.
+    pub(crate) fn observe(&mut self, patches: &[DomPatch]) -> bool {
+        if self.body_started {
+            return false;
+        }
+        let mut reached = false;
+        for patch in patches {
+            match patch {
+                DomPatch::CreateElement { key, name, .. } => {
+                    if name.is_html("html") {
+                        self.html.get_or_insert(*key);
+                    } else if name.is_html("head") {
+                        self.head.get_or_insert(*key);
+                    } else if name.is_html("body") {
+                        self.body.get_or_insert(*key);
+                    }
+                }
+                DomPatch::AppendChild { parent, child }
+                | DomPatch::InsertBefore { parent, child, .. } => {
+                    if !self.head_done
+                        && self.head.is_some()
+                        && Some(*parent) == self.html
+                        && Some(*child) != self.head
+                    {
+                        self.head_done = true;
+                        reached = true;
+                    }
+                    if !self.body_started && Some(*parent) == self.body {
+                        self.body_started = true;
+                        reached = true;
+                    }
+                }
+                _ => {}
+            }
+        }
+        reached
+    }
+}
@@ -64,10 +64,18 @@ pub(crate) fn estimate_patch_bytes(patch: &DomPatch) -> usize {
             PATCH_OVERHEAD + target.len() + data.len()
         }
         DomPatch::CreateTemplateContents { .. } => PATCH_OVERHEAD,
+        DomPatch::SetAttribute { attribute, .. } => {
+            PATCH_OVERHEAD
+                + attribute.local_name().len()
+                + attribute.prefix().map(str::len).unwrap_or(0)
+                + attribute.value().len()
+        }
         DomPatch::AppendChild { .. }
         | DomPatch::InsertBefore { .. }
         | DomPatch::RemoveNode { .. }
         | DomPatch::SetAttributes { .. }
+        | DomPatch::RemoveAttribute { .. }
+        | DomPatch::SetSourceSpan { .. }
         | DomPatch::SetText { .. } => PATCH_OVERHEAD,
         DomPatch::AppendText { text, .. } => PATCH_OVERHEAD + text.len(),
         _ => PATCH_OVERHEAD,
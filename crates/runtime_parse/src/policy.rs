@@ -24,6 +24,12 @@ pub(crate) fn patch_buffer_retain_target(
     base.min(MAX_PATCH_BUFFER_RETAIN)
 }
 
+pub(crate) const DEFAULT_MIN_TICK: Duration = Duration::from_millis(30);
+pub(crate) const DEFAULT_LARGE_DOCUMENT_BYTES: usize = 256 * 1024;
+pub(crate) const DEFAULT_UI_BACKLOG: u64 = 2;
+/// A UI backlog stretches the tick by at most `2^MAX_BACKOFF_SHIFT`.
+pub(crate) const MAX_BACKOFF_SHIFT: u64 = 3;
+
 /// Preview flush strategy for incremental parse.
 ///
 /// The policy is evaluated when new input arrives. A flush occurs if any enabled
@@ -32,28 +38,88 @@ pub(crate) fn patch_buffer_retain_target(
 /// driven: ticks are evaluated only when input arrives (no background timer). If
 /// input stalls, pending patches are flushed on `ParseHtmlDone`. Boundedness
 /// assumes continued input or an eventual `ParseHtmlDone`.
+///
+/// Tests that need deterministic flushes use [`PreviewPolicy::fixed`], which
+/// turns adaptivity and milestone flushes off.
 #[derive(Clone, Copy, Debug)]
 pub struct PreviewPolicy {
     pub tick: Duration,
+    /// Scale the tick with document size and UI backlog; `None` keeps `tick`.
+    pub adaptive: Option<AdaptiveTick>,
+    /// Flush as soon as the head is complete and when body content starts.
+    pub flush_on_milestones: bool,
     pub token_threshold: Option<usize>,
     pub byte_threshold: Option<usize>,
     pub patch_threshold: Option<usize>,
     pub patch_byte_threshold: Option<usize>,
 }
 
+/// How the preview tick adapts to the document and to the UI.
+#[derive(Clone, Copy, Debug)]
+pub struct AdaptiveTick {
+    /// Tick at the start of a document. It grows linearly to
+    /// [`PreviewPolicy::tick`], reached at `large_document_bytes`.
+    pub min_tick: Duration,
+    pub large_document_bytes: usize,
+    /// Patch updates the UI may leave unapplied before the tick backs off.
+    /// Each further one doubles it, up to eight times the size-based tick.
+    pub ui_backlog: u64,
+}
+
+impl Default for AdaptiveTick {
+    fn default() -> Self {
+        Self {
+            min_tick: DEFAULT_MIN_TICK,
+            large_document_bytes: DEFAULT_LARGE_DOCUMENT_BYTES,
+            ui_backlog: DEFAULT_UI_BACKLOG,
+        }
+    }
+}
+
 impl Default for PreviewPolicy {
     fn default() -> Self {
+        Self {
+            adaptive: Some(AdaptiveTick::default()),
+            flush_on_milestones: true,
+            ..Self::fixed()
+        }
+    }
+}
+
+impl PreviewPolicy {
+    /// The default tick with neither adaptivity nor milestone flushes.
+    pub fn fixed() -> Self {
         Self {
             tick: DEFAULT_TICK,
+            adaptive: None,
+            flush_on_milestones: false,
             token_threshold: None,
             byte_threshold: None,
             patch_threshold: None,
             patch_byte_threshold: None,
         }
     }
-}
 
-impl PreviewPolicy {
+    /// Tick for a document of `total_bytes` while the UI has
+    /// `unapplied_updates` patch updates still to apply.
+    pub(crate) fn tick_for(&self, total_bytes: usize, unapplied_updates: u64) -> Duration {
+        let Some(adaptive) = self.adaptive else {
+            return self.tick;
+        };
+        if self.tick == Duration::ZERO {
+            return self.tick;
+        }
+        let progress = if adaptive.large_document_bytes == 0 {
+            1.0
+        } else {
+            (total_bytes as f64 / adaptive.large_document_bytes as f64).min(1.0)
+        };
+        let min_tick = adaptive.min_tick.min(self.tick);
+        let tick = min_tick + (self.tick - min_tick).mul_f64(progress);
+        let behind = unapplied_updates.saturating_sub(adaptive.ui_backlog);
+        tick * (1 << behind.min(MAX_BACKOFF_SHIFT))
+    }
+
     pub(crate) fn is_bounded(&self) -> bool {
         self.tick != Duration::ZERO
             || self.token_threshold.is_some()
@@ -0,0 +1,244 @@
+//! Speculative resource discovery ahead of the tree builder.
+//!
+//! The parser stops at every script until the tab has run it, and buffers
+//! patches between flushes, so a stylesheet or image further down the page
+//! would otherwise only be fetched once its element reaches the tab's DOM.
+//! The preload scanner tokenizes the same decoded text on its own, without
+//! building a tree, and reports `<link rel=stylesheet href>` and `<img src>`
+//! as soon as their start tags are complete.
+//!
+//! Hints are a guess: the scanner follows the tree builder's text modes so
+//! markup inside `<script>` or `<textarea>` is not mistaken for elements, and
+//! skips template contents, but it knows nothing else about the tree.
+
+use std::collections::HashSet;
+
+use core_types::ResourceKind;
+use html::HtmlParseError;
+use html::html5::{
+    AtomError, AtomId, AtomTable, Attribute, AttributeValue, DocumentParseContext, Html5Tokenizer,
+    Input, TextModeSpec, TextResolver, Token, TokenizeResult, TokenizerConfig, TokenizerControl,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ResourceHint {
+    /// The attribute value as written, trimmed; not resolved against any base.
+    pub(crate) url: String,
+    pub(crate) kind: ResourceKind,
+}
+
+pub(crate) struct PreloadScanner {
+    ctx: DocumentParseContext,
+    tokenizer: Html5Tokenizer,
+    input: Input,
+    scan: ScanState,
+}
+
+impl PreloadScanner {
+    pub(crate) fn new() -> Result<Self, HtmlParseError> {
+        let mut ctx = DocumentParseContext::new();
+        let names = ScanNames::intern(&mut ctx.atoms).map_err(|_| HtmlParseError::Invariant)?;
+        let tokenizer = Html5Tokenizer::new(TokenizerConfig::default(), &mut ctx);
+        Ok(Self {
+            ctx,
+            tokenizer,
+            input: Input::new(),
+            scan: ScanState {
+                names,
+                in_text_mode: false,
+                template_depth: 0,
+                seen: HashSet::new(),
+            },
+        })
+    }
+
+    /// Scan the next chunk of decoded text; returns the resources first seen
+    /// in it, in document order.
+    pub(crate) fn push_str(&mut self, text: &str) -> Vec<ResourceHint> {
+        let mut hints = Vec::new();
+        if text.is_empty() {
+            return hints;
+        }
+        self.input.push_str(text);
+        loop {
+            // One token at a time, so a text mode is entered before the
+            // tokenizer looks past the start tag that opens it.
+            let result = self
+                .tokenizer
+                .push_input_until_token(&mut self.input, &mut self.ctx);
+            let control = {
+                let batch = self.tokenizer.next_batch(&mut self.input);
+                let resolver = batch.resolver();
+                let mut control = None;
+                for token in batch.iter() {
+                    control = self.scan.observe(token, &resolver, &mut hints).or(control);
+                }
+                control
+            };
+            if let Some(control) = control {
+                self.tokenizer.apply_control(control);
+            }
+            if result == TokenizeResult::NeedMoreInput {
+                break;
+            }
+        }
+        hints
+    }
+}
+
+struct ScanState {
+    names: ScanNames,
+    in_text_mode: bool,
+    template_depth: usize,
+    /// Hints already reported, so a resource referenced twice is hinted once.
+    seen: HashSet<(ResourceKind, String)>,
+}
+
+impl ScanState {
+    fn observe(
+        &mut self,
+        token: &Token,
+        resolver: &dyn TextResolver,
+        hints: &mut Vec<ResourceHint>,
+    ) -> Option<TokenizerControl> {
+        let names = &self.names;
+        match token {
+            Token::StartTag { name, attrs, .. } => {
+                if let Some(spec) = names.text_mode_spec(*name) {
+                    self.in_text_mode = true;
+                    return Some(TokenizerControl::EnterTextMode(spec));
+                }
+                if *name == names.template {
+                    self.template_depth += 1;
+                } else if self.template_depth == 0
+                    && let Some(hint) = names.hint(*name, attrs, resolver)
+                    && self.seen.insert((hint.kind, hint.url.clone()))
+                {
+                    hints.push(hint);
+                }
+                None
+            }
+            // In a text mode the only end tag the tokenizer emits is the one
+            // closing it.
+            Token::EndTag { .. } if self.in_text_mode => {
+                self.in_text_mode = false;
+                Some(TokenizerControl::ExitTextMode)
+            }
+            Token::EndTag { name } if *name == names.template => {
+                self.template_depth = self.template_depth.saturating_sub(1);
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Tag and attribute names the scanner looks at, interned in its own table.
+struct ScanNames {
+    link: AtomId,
+    img: AtomId,
+    template: AtomId,
+    rel: AtomId,
+    href: AtomId,
+    src: AtomId,
+    style: AtomId,
+    xmp: AtomId,
+    iframe: AtomId,
+    noembed: AtomId,
+    noframes: AtomId,
+    title: AtomId,
+    textarea: AtomId,
+    script: AtomId,
+}
+
+impl ScanNames {
+    fn intern(atoms: &mut AtomTable) -> Result<Self, AtomError> {
+        let mut intern = |name: &str| atoms.intern_ascii_folded(name);
+        Ok(Self {
+            link: intern("link")?,
+            img: intern("img")?,
+            template: intern("template")?,
+            rel: intern("rel")?,
+            href: intern("href")?,
+            src: intern("src")?,
+            style: intern("style")?,
+            xmp: intern("xmp")?,
+            iframe: intern("iframe")?,
+            noembed: intern("noembed")?,
+            noframes: intern("noframes")?,
+            title: intern("title")?,
+            textarea: intern("textarea")?,
+            script: intern("script")?,
+        })
+    }
+
+    /// The text mode the tree builder switches the tokenizer to after this
+    /// start tag.
+    fn text_mode_spec(&self, name: AtomId) -> Option<TextModeSpec> {
+        let spec = if name == self.style {
+            TextModeSpec::rawtext_style
+        } else if name == self.xmp {
+            TextModeSpec::rawtext_xmp
+        } else if name == self.iframe {
+            TextModeSpec::rawtext_iframe
+        } else if name == self.noembed {
+            TextModeSpec::rawtext_noembed
+        } else if name == self.noframes {
+            TextModeSpec::rawtext_noframes
+        } else if name == self.title {
+            TextModeSpec::rcdata_title
+        } else if name == self.textarea {
+            TextModeSpec::rcdata_textarea
+        } else if name == self.script {
+            TextModeSpec::script_data
+        } else {
+            return None;
+        };
+        Some(spec(name))
+    }
+
+    fn hint(
+        &self,
+        name: AtomId,
+        attrs: &[Attribute],
+        resolver: &dyn TextResolver,
+    ) -> Option<ResourceHint> {
+        let (kind, url) = if name == self.link {
+            let rel = attribute_value(attrs, self.rel, resolver)?;
+            if !rel
+                .split_ascii_whitespace()
+                .any(|token| token.eq_ignore_ascii_case("stylesheet"))
+            {
+                return None;
+            }
+            (
+                ResourceKind::Css,
+                attribute_value(attrs, self.href, resolver)?,
+            )
+        } else if name == self.img {
+            (
+                ResourceKind::Image,
+                attribute_value(attrs, self.src, resolver)?,
+            )
+        } else {
+            return None;
+        };
+        let url = url.trim_ascii();
+        (!url.is_empty()).then(|| ResourceHint {
+            url: url.to_string(),
+            kind,
+        })
+    }
+}
+
+fn attribute_value<'a>(
+    attrs: &'a [Attribute],
+    name: AtomId,
+    resolver: &'a dyn TextResolver,
+) -> Option<&'a str> {
+    let attr = attrs.iter().find(|attr| attr.name == name)?;
+    match &attr.value {
+        AttributeValue::Span(span) => resolver.resolve_span(*span).ok(),
+        AttributeValue::Owned(value) => Some(value),
+    }
+}
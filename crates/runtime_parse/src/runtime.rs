@@ -1,14 +1,15 @@
 use std::collections::HashMap;
 use std::sync::atomic::Ordering;
 use std::sync::mpsc::{Receiver, Sender};
-use std::thread;
+use std::thread::{self, JoinHandle};
+use std::time::Instant;
 
-use bus::{CoreCommand, CoreEvent};
+use bus::{ChunkCredits, CoreCommand, CoreEvent, ParseLimits, RuntimeKind, run_supervised};
 use core_types::{DomHandle, RequestId, TabId};
 use log::error;
 
 use crate::clock::{PreviewClock, SystemClock};
-use crate::driver::{handle_runtime_chunk, handle_runtime_done};
+use crate::driver::{handle_runtime_chunk, handle_runtime_done, handle_runtime_resume};
 use crate::policy::{PreviewPolicy, patch_buffer_retain_target};
 use crate::state::{HANDLE_GEN, Key, RuntimeState};
 
@@ -16,16 +17,35 @@ use crate::state::{HANDLE_GEN, Key, RuntimeState};
 ///
 /// The runtime path is backed exclusively by the HTML5 parser facade. Patch
 /// emission is buffered and flushed on ticks while parser state is retained
-/// between chunks so work stays proportional to new input.
-pub fn start_parse_runtime(cmd_rx: Receiver<CoreCommand>, evt_tx: Sender<CoreEvent>) {
-    start_parse_runtime_with_policy(cmd_rx, evt_tx, PreviewPolicy::default())
+/// between chunks so work stays proportional to new input. A panic while
+/// parsing drops all parser state and is reported as
+/// `CoreEvent::RuntimeCrashed`.
+///
+/// Each consumed document chunk gives its credit back to `credits`, which
+/// lets the network runtime send more of the document. A finished parse
+/// reports its token count, parse time, and node count with
+/// `CoreEvent::Metrics`.
+///
+/// Each document is parsed within the `ParseLimits` its `ParseHtmlStart`
+/// carries. Input past the byte limit is dropped, the document ends there,
+/// and its credit window is stopped so the download ends too. Every limit a
+/// document runs into is reported once with `CoreEvent::ResourceTruncated`.
+pub fn start_parse_runtime(
+    cmd_rx: Receiver<CoreCommand>,
+    evt_tx: Sender<CoreEvent>,
+    credits: ChunkCredits,
+) -> JoinHandle<()> {
+    let policy = PreviewPolicy::default().ensure_bounded();
+    spawn_parse_runtime(cmd_rx, evt_tx, policy, SystemClock, credits)
 }
 
+/// Like [`start_parse_runtime`], without sharing credit with a network
+/// runtime.
 pub fn start_parse_runtime_with_policy(
     cmd_rx: Receiver<CoreCommand>,
     evt_tx: Sender<CoreEvent>,
     policy: PreviewPolicy,
-) {
+) -> JoinHandle<()> {
     let policy = policy.ensure_bounded();
     start_parse_runtime_with_policy_and_clock(cmd_rx, evt_tx, policy, SystemClock)
 }
@@ -35,47 +55,96 @@ pub(crate) fn start_parse_runtime_with_policy_and_clock<C: PreviewClock + 'stati
     evt_tx: Sender<CoreEvent>,
     policy: PreviewPolicy,
     clock: C,
-) {
+) -> JoinHandle<()> {
+    spawn_parse_runtime(cmd_rx, evt_tx, policy, clock, ChunkCredits::default())
+}
+
+fn spawn_parse_runtime<C: PreviewClock + 'static>(
+    cmd_rx: Receiver<CoreCommand>,
+    evt_tx: Sender<CoreEvent>,
+    policy: PreviewPolicy,
+    clock: C,
+    credits: ChunkCredits,
+) -> JoinHandle<()> {
     thread::spawn(move || {
         let patch_buffer_retain =
             patch_buffer_retain_target(policy.patch_threshold, policy.patch_byte_threshold);
-        let mut htmls: HashMap<Key, RuntimeState> = HashMap::new();
-
-        while let Ok(cmd) = cmd_rx.recv() {
-            let now = clock.now();
-            match cmd {
-                CoreCommand::ParseHtmlStart { tab_id, request_id } => {
-                    handle_parse_start(&mut htmls, now, patch_buffer_retain, tab_id, request_id);
-                }
-                CoreCommand::ParseHtmlChunk {
-                    tab_id,
-                    request_id,
-                    bytes,
-                } => {
-                    handle_parse_chunk(
-                        &mut htmls, &evt_tx, &policy, now, tab_id, request_id, &bytes,
-                    );
+        run_supervised(
+            RuntimeKind::Parse,
+            &cmd_rx,
+            &evt_tx,
+            HashMap::new,
+            |htmls: &mut HashMap<Key, RuntimeState>, cmd| {
+                let now = clock.now();
+                match cmd {
+                    CoreCommand::ParseHtmlStart {
+                        tab_id,
+                        request_id,
+                        content_type,
+                        limits,
+                    } => {
+                        handle_parse_start(
+                            htmls,
+                            &credits,
+                            now,
+                            patch_buffer_retain,
+                            tab_id,
+                            request_id,
+                            content_type.as_deref(),
+                            limits,
+                        );
+                    }
+                    CoreCommand::ParseHtmlChunk {
+                        tab_id,
+                        request_id,
+                        bytes,
+                    } => {
+                        handle_parse_chunk(
+                            htmls, &evt_tx, &credits, &policy, now, tab_id, request_id, &bytes,
+                        );
+                        credits.release(tab_id, request_id, bytes.len());
+                    }
+                    CoreCommand::ParseHtmlDone { tab_id, request_id } => {
+                        handle_parse_done(htmls, &evt_tx, tab_id, request_id);
+                    }
+                    CoreCommand::ParseHtmlResume { tab_id, request_id } => {
+                        handle_parse_resume(htmls, &evt_tx, tab_id, request_id);
+                    }
+                    CoreCommand::DomUpdateApplied {
+                        tab_id,
+                        request_id,
+                        version,
+                    } => {
+                        if let Some(state) = htmls.get_mut(&(tab_id, request_id)) {
+                            state.applied_version = state.applied_version.max(version);
+                        }
+                    }
+                    CoreCommand::CancelGeneration { tab_id, request_id } => {
+                        drop_parses(htmls, &credits, tab_id, request_id);
+                    }
+                    _ => {}
                 }
-                CoreCommand::ParseHtmlDone { tab_id, request_id } => {
-                    handle_parse_done(&mut htmls, &evt_tx, tab_id, request_id);
-                }
-                _ => {}
-            }
-        }
-    });
+            },
+        );
+    })
 }
 
+#[allow(clippy::too_many_arguments)]
 fn handle_parse_start(
     htmls: &mut HashMap<Key, RuntimeState>,
-    now: std::time::Instant,
+    credits: &ChunkCredits,
+    now: Instant,
     patch_buffer_retain: usize,
     tab_id: TabId,
     request_id: RequestId,
+    content_type: Option<&str>,
+    limits: ParseLimits,
 ) {
     let Some(dom_handle) = next_dom_handle(tab_id, request_id) else {
         return;
     };
-    let state = match RuntimeState::new(now, patch_buffer_retain, dom_handle) {
+    let state = match RuntimeState::new(now, patch_buffer_retain, dom_handle, content_type, limits)
+    {
         Ok(state) => state,
         Err(err) => {
             error!(
@@ -85,24 +154,56 @@ fn handle_parse_start(
             return;
         }
     };
+    // A tab parses one document at a time. An older parse may be stopped at
+    // a script the tab will no longer resume. The tab stops forwarding the
+    // older stream, so its credit window is closed as well.
+    drop_parses(htmls, credits, tab_id, RequestId::MAX);
     htmls.insert((tab_id, request_id), state);
 }
 
+/// Drop the tab's parses up to and including `through`, closing their
+/// credit windows.
+fn drop_parses(
+    htmls: &mut HashMap<Key, RuntimeState>,
+    credits: &ChunkCredits,
+    tab_id: TabId,
+    through: RequestId,
+) {
+    htmls.retain(|&(tab, request), _| {
+        if tab != tab_id || request > through {
+            return true;
+        }
+        credits.close(tab, request);
+        false
+    });
+}
+
+#[allow(clippy::too_many_arguments)]
 fn handle_parse_chunk(
     htmls: &mut HashMap<Key, RuntimeState>,
     evt_tx: &Sender<CoreEvent>,
+    credits: &ChunkCredits,
     policy: &PreviewPolicy,
-    now: std::time::Instant,
+    now: Instant,
     tab_id: TabId,
     request_id: RequestId,
     bytes: &[u8],
 ) {
-    let mut remove_state = false;
-    if let Some(state) = htmls.get_mut(&(tab_id, request_id)) {
-        remove_state = handle_runtime_chunk(state, bytes, policy, now, evt_tx, tab_id, request_id);
+    let key = (tab_id, request_id);
+    let Some(state) = htmls.get_mut(&key) else {
+        return;
+    };
+    let _span = tracing::info_span!("parse", tab_id, request_id).entered();
+    let started = Instant::now();
+    let input_done = state.input_done;
+    let over = handle_runtime_chunk(state, bytes, policy, now, evt_tx, tab_id, request_id);
+    state.parse_time += started.elapsed();
+    if !input_done && state.input_done {
+        // The chunk crossed the byte limit; nothing more will be read.
+        credits.stop(tab_id, request_id);
     }
-    if remove_state {
-        htmls.remove(&(tab_id, request_id));
+    if over {
+        finish_parse(htmls, evt_tx, key);
     }
 }
 
@@ -112,8 +213,42 @@ fn handle_parse_done(
     tab_id: TabId,
     request_id: RequestId,
 ) {
-    if let Some(state) = htmls.remove(&(tab_id, request_id)) {
-        handle_runtime_done(Box::new(state), evt_tx, tab_id, request_id);
+    let key = (tab_id, request_id);
+    let Some(state) = htmls.get_mut(&key) else {
+        return;
+    };
+    let _span = tracing::info_span!("parse", tab_id, request_id).entered();
+    let started = Instant::now();
+    let over = handle_runtime_done(state, evt_tx, tab_id, request_id);
+    state.parse_time += started.elapsed();
+    if over {
+        finish_parse(htmls, evt_tx, key);
+    }
+}
+
+fn handle_parse_resume(
+    htmls: &mut HashMap<Key, RuntimeState>,
+    evt_tx: &Sender<CoreEvent>,
+    tab_id: TabId,
+    request_id: RequestId,
+) {
+    let key = (tab_id, request_id);
+    let Some(state) = htmls.get_mut(&key) else {
+        return;
+    };
+    let _span = tracing::info_span!("parse", tab_id, request_id).entered();
+    let started = Instant::now();
+    let over = handle_runtime_resume(state, evt_tx, tab_id, request_id);
+    state.parse_time += started.elapsed();
+    if over {
+        finish_parse(htmls, evt_tx, key);
+    }
+}
+
+/// Drop a parse that is over and report its metrics.
+fn finish_parse(htmls: &mut HashMap<Key, RuntimeState>, evt_tx: &Sender<CoreEvent>, key: Key) {
+    if let Some(state) = htmls.remove(&key) {
+        state.emit_metrics(evt_tx, key.0, key.1);
     }
 }
 
@@ -0,0 +1,238 @@
+//! Encoding sniffing: which character encoding a document's bytes are in.
+//!
+//! Follows the HTML encoding sniffing algorithm: a byte order mark wins,
+//! then the `charset` of the response's `Content-Type`, then a `<meta>`
+//! charset declaration found by prescanning the first [`PRESCAN_BYTES`]
+//! bytes. Documents that declare nothing are read as UTF-8 if their start
+//! is valid UTF-8, and as windows-1252 otherwise.
+
+use encoding_rs::{Encoding, UTF_8, UTF_16BE, UTF_16LE, WINDOWS_1252, X_USER_DEFINED};
+
+/// Bytes the `<meta>` prescan looks at.
+pub(crate) const PRESCAN_BYTES: usize = 1024;
+
+/// The encoding a byte order mark at the start of `bytes` selects.
+pub(crate) fn bom_encoding(bytes: &[u8]) -> Option<&'static Encoding> {
+    Encoding::for_bom(bytes).map(|(encoding, _)| encoding)
+}
+
+/// The encoding a `Content-Type` header value names, e.g. Shift_JIS for
+/// `text/html; charset=Shift_JIS`.
+pub(crate) fn content_type_encoding(content_type: &str) -> Option<&'static Encoding> {
+    charset_in_content(content_type.as_bytes()).and_then(Encoding::for_label)
+}
+
+/// The encoding for a document that declares none: UTF-8 if `prefix`, the
+/// start of the document, is valid UTF-8, windows-1252 otherwise.
+pub(crate) fn fallback_encoding(prefix: &[u8], complete: bool) -> &'static Encoding {
+    match std::str::from_utf8(prefix) {
+        Ok(_) => UTF_8,
+        // A sequence cut off by the end of the prefix may well be valid.
+        Err(err) if err.error_len().is_none() && !complete => UTF_8,
+        Err(_) => WINDOWS_1252,
+    }
+}
+
+/// The encoding a `<meta charset>` or `<meta http-equiv="Content-Type">`
+/// in `bytes` declares.
+pub(crate) fn prescan(bytes: &[u8]) -> Option<&'static Encoding> {
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let rest = &bytes[pos..];
+        if rest.starts_with(b"<!--") {
+            pos += find(&rest[4..], b"-->").map_or(rest.len(), |end| 4 + end + 3);
+        } else if starts_with_ignore_case(rest, b"<meta")
+            && rest.get(5).is_some_and(|&b| is_space(b) || b == b'/')
+        {
+            pos += 5;
+            if let Some(encoding) = meta_encoding(bytes, &mut pos) {
+                return Some(encoding);
+            }
+        } else if rest.len() >= 2
+            && rest[0] == b'<'
+            && (rest[1].is_ascii_alphabetic()
+                || (rest[1] == b'/' && rest.get(2).is_some_and(u8::is_ascii_alphabetic)))
+        {
+            // Skip the tag name, then its attributes.
+            pos += rest
+                .iter()
+                .position(|&b| is_space(b) || b == b'>')
+                .unwrap_or(rest.len());
+            while attribute(bytes, &mut pos).is_some() {}
+        } else if rest.starts_with(b"<!") || rest.starts_with(b"</") || rest.starts_with(b"<?") {
+            pos += find(rest, b">").map_or(rest.len(), |end| end + 1);
+        } else {
+            pos += 1;
+        }
+    }
+    None
+}
+
+/// Read the attributes of a `<meta>` tag starting at `pos` and return the
+/// encoding they declare.
+fn meta_encoding(bytes: &[u8], pos: &mut usize) -> Option<&'static Encoding> {
+    let mut seen = Vec::new();
+    let mut got_pragma = false;
+    let mut need_pragma = None;
+    let mut charset = None;
+    while let Some((name, value)) = attribute(bytes, pos) {
+        if seen.contains(&name) {
+            continue;
+        }
+        match name.as_slice() {
+            b"http-equiv" if value.eq_ignore_ascii_case(b"content-type") => got_pragma = true,
+            b"charset" if charset.is_none() => {
+                charset = Encoding::for_label(trim_space(&value));
+                need_pragma = Some(false);
+            }
+            b"content" if charset.is_none() => {
+                if let Some(encoding) = charset_in_content(&value).and_then(Encoding::for_label) {
+                    charset = Some(encoding);
+                    need_pragma = Some(true);
+                }
+            }
+            _ => {}
+        }
+        seen.push(name);
+    }
+    let charset = match need_pragma? {
+        true if !got_pragma => return None,
+        _ => charset?,
+    };
+    // A document can't describe itself as UTF-16 in bytes it could read.
+    Some(match charset {
+        encoding if encoding == UTF_16BE || encoding == UTF_16LE => UTF_8,
+        encoding if encoding == X_USER_DEFINED => WINDOWS_1252,
+        encoding => encoding,
+    })
+}
+
+/// Read the attribute starting at `pos`, lowercasing its name. `None` at
+/// the end of the tag.
+fn attribute(bytes: &[u8], pos: &mut usize) -> Option<(Vec<u8>, Vec<u8>)> {
+    let next = |pos: &mut usize| {
+        let byte = bytes.get(*pos).copied();
+        *pos += 1;
+        byte
+    };
+    while bytes.get(*pos).is_some_and(|&b| is_space(b) || b == b'/') {
+        *pos += 1;
+    }
+    if bytes.get(*pos) == Some(&b'>') {
+        *pos += 1;
+        return None;
+    }
+
+    let mut name = Vec::new();
+    let mut value = Vec::new();
+    loop {
+        match next(pos)? {
+            b'=' if !name.is_empty() => break,
+            b if is_space(b) => {
+                while bytes.get(*pos).is_some_and(|&b| is_space(b)) {
+                    *pos += 1;
+                }
+                if bytes.get(*pos) != Some(&b'=') {
+                    return Some((name, value));
+                }
+                *pos += 1;
+                break;
+            }
+            b'/' | b'>' => {
+                *pos -= 1;
+                return Some((name, value));
+            }
+            b => name.push(b.to_ascii_lowercase()),
+        }
+    }
+
+    while bytes.get(*pos).is_some_and(|&b| is_space(b)) {
+        *pos += 1;
+    }
+    match next(pos)? {
+        quote @ (b'"' | b'\'') => loop {
+            match next(pos)? {
+                b if b == quote => return Some((name, value)),
+                b => value.push(b.to_ascii_lowercase()),
+            }
+        },
+        b'>' => {
+            *pos -= 1;
+            Some((name, value))
+        }
+        b => {
+            value.push(b.to_ascii_lowercase());
+            loop {
+                match bytes.get(*pos) {
+                    None => return None,
+                    Some(&b) if is_space(b) || b == b'>' => return Some((name, value)),
+                    Some(&b) => value.push(b.to_ascii_lowercase()),
+                }
+                *pos += 1;
+            }
+        }
+    }
+}
+
+/// The value of the `charset` parameter in a `Content-Type` value or
+/// `<meta content>` attribute.
+fn charset_in_content(content: &[u8]) -> Option<&[u8]> {
+    let mut pos = 0;
+    loop {
+        pos += find_ignore_case(&content[pos..], b"charset")? + b"charset".len();
+        let rest = trim_space_start(&content[pos..]);
+        if let Some(rest) = rest.strip_prefix(b"=") {
+            let rest = trim_space_start(rest);
+            return match rest.first()? {
+                &quote @ (b'"' | b'\'') => {
+                    let end = rest[1..].iter().position(|&b| b == quote)?;
+                    Some(&rest[1..1 + end])
+                }
+                _ => {
+                    let end = rest
+                        .iter()
+                        .position(|&b| is_space(b) || b == b';')
+                        .unwrap_or(rest.len());
+                    Some(&rest[..end])
+                }
+            };
+        }
+    }
+}
+
+fn is_space(byte: u8) -> bool {
+    matches!(byte, b'\t' | b'\n' | b'\x0C' | b'\r' | b' ')
+}
+
+fn trim_space_start(bytes: &[u8]) -> &[u8] {
+    let start = bytes
+        .iter()
+        .position(|&b| !is_space(b))
+        .unwrap_or(bytes.len());
+    &bytes[start..]
+}
+
+fn trim_space(bytes: &[u8]) -> &[u8] {
+    let bytes = trim_space_start(bytes);
+    let end = bytes
+        .iter()
+        .rposition(|&b| !is_space(b))
+        .map_or(0, |end| end + 1);
+    &bytes[..end]
+}
+
+fn starts_with_ignore_case(bytes: &[u8], prefix: &[u8]) -> bool {
+    bytes.len() >= prefix.len() && bytes[..prefix.len()].eq_ignore_ascii_case(prefix)
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn find_ignore_case(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window.eq_ignore_ascii_case(needle))
+}
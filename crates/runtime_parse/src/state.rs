@@ -1,9 +1,14 @@
 use std::sync::atomic::AtomicU64;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
+use bus::{ParseLimit, ParseLimits};
 use core_types::{DomHandle, DomVersion, RequestId, TabId};
 use html::{DomPatch, HtmlParseError, HtmlParseOptions, HtmlParser};
 
+use crate::decoder::DocumentDecoder;
+use crate::milestones::DocumentMilestones;
+use crate::preload::PreloadScanner;
+
 pub(crate) static HANDLE_GEN: AtomicU64 = AtomicU64::new(0);
 
 pub(crate) type Key = (TabId, RequestId);
@@ -17,13 +22,31 @@ pub(crate) struct RuntimeState {
     pub(crate) last_emit: Instant,
     pub(crate) logged_large_buffer: bool,
     pub(crate) failed: bool,
+    /// `ParseHtmlDone` arrived or the byte limit cut the input off; the
+    /// state goes once no script holds the parse.
+    pub(crate) input_done: bool,
+    pub(crate) limits: ParseLimits,
+    /// Limits already reported with `ResourceTruncated`.
+    pub(crate) truncated: Vec<ParseLimit>,
+    pub(crate) decoder: DocumentDecoder,
     pub(crate) parser: HtmlParser,
+    /// Looks ahead of `parser` for subresources; dropped once the input is in.
+    pub(crate) preload: Option<PreloadScanner>,
     pub(crate) patch_buffer: Vec<DomPatch>,
     pub(crate) patch_buffer_retain: usize,
     pub(crate) max_patch_buffer_len: usize,
     pub(crate) max_patch_buffer_bytes: usize,
     pub(crate) dom_handle: DomHandle,
     pub(crate) version: DomVersion,
+    /// Newest version the tab reported as applied.
+    pub(crate) applied_version: DomVersion,
+    pub(crate) milestones: DocumentMilestones,
+    /// Buffered patches reach a milestone and are due for a flush.
+    pub(crate) milestone_pending: bool,
+    /// Wall time spent handling this document's commands.
+    pub(crate) parse_time: Duration,
+    /// Nodes created by the patches drained so far.
+    pub(crate) dom_nodes: u64,
 }
 
 impl RuntimeState {
@@ -31,6 +54,8 @@ impl RuntimeState {
         now: Instant,
         patch_buffer_retain: usize,
         dom_handle: DomHandle,
+        content_type: Option<&str>,
+        limits: ParseLimits,
     ) -> Result<Self, HtmlParseError> {
         Ok(Self {
             total_bytes: 0,
@@ -41,18 +66,35 @@ impl RuntimeState {
             last_emit: now,
             logged_large_buffer: false,
             failed: false,
-            parser: HtmlParser::new(runtime_parse_options())?,
+            input_done: false,
+            limits,
+            truncated: Vec::new(),
+            decoder: DocumentDecoder::new(content_type),
+            parser: HtmlParser::new(runtime_parse_options(limits))?,
+            preload: Some(PreloadScanner::new()?),
             patch_buffer: Vec::new(),
             patch_buffer_retain,
             max_patch_buffer_len: 0,
             max_patch_buffer_bytes: 0,
             dom_handle,
             version: DomVersion::INITIAL,
+            applied_version: DomVersion::INITIAL,
+            milestones: DocumentMilestones::default(),
+            milestone_pending: false,
+            parse_time: Duration::ZERO,
+            dom_nodes: 0,
         })
     }
 }
 
-fn runtime_parse_options() -> HtmlParseOptions {
-    // Keep runtime policy explicit even while it matches the library defaults.
-    HtmlParseOptions::default()
+fn runtime_parse_options(limits: ParseLimits) -> HtmlParseOptions {
+    let mut options = HtmlParseOptions::default();
+    options.tree_builder.limits.max_nodes_created = limits.max_nodes;
+    options.tree_builder.limits.max_open_elements_depth = limits.max_depth;
+    // Scripts run in the script runtime; the tab resumes the parse once the
+    // script is done.
+    options.tree_builder.suspend_at_script_end = true;
+    // Lets inspector tooling map a tab's elements back to the markup.
+    options.tree_builder.record_source_spans = true;
+    options
 }
@@ -1,7 +1,7 @@
 use std::sync::mpsc;
 use std::time::{Duration, Instant};
 
-use bus::{CoreCommand, CoreEvent};
+use bus::{CoreCommand, CoreEvent, ParseLimits};
 use core_types::DomHandle;
 use html::{DomPatch, PatchKey};
 
@@ -27,6 +27,8 @@ fn processing_instruction_patch_bytes_include_target_and_data() {
 fn patch_buffer_does_not_grow_unbounded_in_streaming() {
     let policy = PreviewPolicy {
         tick: Duration::ZERO,
+        adaptive: None,
+        flush_on_milestones: false,
         token_threshold: None,
         byte_threshold: None,
         patch_threshold: Some(256),
@@ -36,7 +38,9 @@ fn patch_buffer_does_not_grow_unbounded_in_streaming() {
     let patch_byte_threshold = policy
         .patch_byte_threshold
         .expect("patch byte threshold missing");
-    let slack_patches = 64usize;
+    // The decoder's sniffing prefix reaches the parser as one block, and every
+    // element in it costs a `SetSourceSpan` on top of its structural patches.
+    let slack_patches = 128usize;
     let slack_bytes = 32 * 1024usize;
 
     let now = Instant::now();
@@ -44,6 +48,8 @@ fn patch_buffer_does_not_grow_unbounded_in_streaming() {
         now,
         patch_buffer_retain_target(policy.patch_threshold, policy.patch_byte_threshold),
         DomHandle(1),
+        None,
+        ParseLimits::default(),
     )
     .expect("runtime state init");
     let (evt_tx, _evt_rx) = mpsc::channel();
@@ -85,6 +91,8 @@ fn patch_buffer_does_not_grow_unbounded_in_streaming() {
 fn patch_updates_are_bounded_under_streaming_policy() {
     let policy = PreviewPolicy {
         tick: Duration::ZERO,
+        adaptive: None,
+        flush_on_milestones: false,
         token_threshold: None,
         byte_threshold: None,
         patch_threshold: Some(200),
@@ -98,7 +106,12 @@ fn patch_updates_are_bounded_under_streaming_policy() {
     let tab_id = 1;
     let request_id = 42;
     cmd_tx
-        .send(CoreCommand::ParseHtmlStart { tab_id, request_id })
+        .send(CoreCommand::ParseHtmlStart {
+            tab_id,
+            request_id,
+            content_type: None,
+            limits: ParseLimits::default(),
+        })
         .unwrap();
 
     let input = "<div><span>hi</span></div>".repeat(1_000);
@@ -117,7 +130,9 @@ fn patch_updates_are_bounded_under_streaming_policy() {
 
     let mut max_patches = 0usize;
     let mut max_bytes = 0usize;
-    let slack_patches = 64usize;
+    // The decoder's sniffing prefix reaches the parser as one block, and every
+    // element in it costs a `SetSourceSpan` on top of its structural patches.
+    let slack_patches = 128usize;
     let slack_bytes = 16 * 1024usize;
 
     let mut saw_update = false;
@@ -163,6 +178,8 @@ fn patch_buffer_retain_capacity_is_bounded_on_flush() {
         now,
         patch_buffer_retain_target(Some(128), None),
         DomHandle(1),
+        None,
+        ParseLimits::default(),
     )
     .expect("runtime state init");
     st.patch_buffer = Vec::with_capacity(100_000);
@@ -179,3 +196,32 @@ fn patch_buffer_retain_capacity_is_bounded_on_flush() {
         "expected retain capacity to be at least the floor, got {cap}"
     );
 }
+
+#[test]
+fn milestones_flush_the_head_and_first_body_content_before_the_tick() {
+    let policy = PreviewPolicy {
+        tick: Duration::from_secs(60),
+        flush_on_milestones: true,
+        ..PreviewPolicy::fixed()
+    };
+    let now = Instant::now();
+    let mut st = RuntimeState::new(
+        now,
+        MIN_PATCH_BUFFER_RETAIN,
+        DomHandle(1),
+        Some("text/html; charset=utf-8"),
+        ParseLimits::default(),
+    )
+    .expect("runtime state init");
+    let (evt_tx, evt_rx) = mpsc::channel();
+    let mut updates_after = |chunk: &str| {
+        let remove = handle_runtime_chunk(&mut st, chunk.as_bytes(), &policy, now, &evt_tx, 1, 1);
+        assert!(!remove);
+        evt_rx.try_iter().count()
+    };
+
+    assert_eq!(updates_after("<html><head><title>T</title>"), 0);
+    assert_eq!(updates_after("</head><body>"), 1, "end of head");
+    assert_eq!(updates_after("<p>first"), 1, "first body content");
+    assert_eq!(updates_after("<p>second"), 0);
+}
@@ -0,0 +1,86 @@
+use encoding_rs::{SHIFT_JIS, UTF_8, WINDOWS_1252};
+
+use crate::decoder::DocumentDecoder;
+use crate::sniff::{PRESCAN_BYTES, content_type_encoding, prescan};
+
+fn decode_in_chunks(content_type: Option<&str>, bytes: &[u8], chunk: usize) -> String {
+    let mut decoder = DocumentDecoder::new(content_type);
+    let mut text = String::new();
+    for part in bytes.chunks(chunk) {
+        text.push_str(&decoder.push(part));
+    }
+    text.push_str(&decoder.finish());
+    text
+}
+
+#[test]
+fn byte_order_mark_wins_over_the_declared_charset() {
+    let mut bytes = vec![0xEF, 0xBB, 0xBF];
+    bytes.extend_from_slice("<p>café</p>".as_bytes());
+    let text = decode_in_chunks(Some("text/html; charset=windows-1252"), &bytes, 1);
+    assert_eq!(text, "<p>café</p>", "BOM picks UTF-8 and is stripped");
+}
+
+#[test]
+fn content_type_charset_decodes_from_the_first_chunk() {
+    let mut decoder = DocumentDecoder::new(Some("text/html; charset=\"ISO-8859-1\""));
+    assert_eq!(decoder.push(b"<p>caf\xE9"), "<p>café");
+    assert_eq!(decoder.push(b" \x80</p>"), " €</p>");
+    assert_eq!(decoder.finish(), "");
+}
+
+#[test]
+fn meta_charset_is_found_by_the_prescan() {
+    let mut bytes = b"<!-- <meta charset=utf-8> --><html><head>".to_vec();
+    bytes.extend_from_slice(
+        b"<meta http-equiv=\"Content-Type\" content=\"text/html; charset=Shift_JIS\">",
+    );
+    let (body, _, _) = SHIFT_JIS.encode("<p>日本語</p>");
+    bytes.extend_from_slice(&body);
+
+    assert_eq!(prescan(&bytes), Some(SHIFT_JIS), "comments are skipped");
+    let text = decode_in_chunks(None, &bytes, 7);
+    assert!(
+        text.ends_with("<p>日本語</p>"),
+        "decoded as Shift_JIS: {text}"
+    );
+}
+
+#[test]
+fn undeclared_documents_wait_for_the_prescan_window() {
+    let mut decoder = DocumentDecoder::new(None);
+    assert_eq!(
+        decoder.push(b"<html>"),
+        "",
+        "held until the encoding is known"
+    );
+    let filler = vec![b' '; PRESCAN_BYTES];
+    let text = decoder.push(&filler);
+    assert!(
+        text.starts_with("<html> "),
+        "released once the window is full"
+    );
+}
+
+#[test]
+fn undeclared_documents_fall_back_by_content() {
+    assert_eq!(
+        decode_in_chunks(None, "<p>naïve</p>".as_bytes(), 4),
+        "<p>naïve</p>"
+    );
+    assert_eq!(
+        decode_in_chunks(None, b"<p>na\xEFve</p>", 4),
+        "<p>naïve</p>"
+    );
+}
+
+#[test]
+fn content_type_labels_resolve_like_the_encoding_standard() {
+    assert_eq!(
+        content_type_encoding("text/html; charset=latin1"),
+        Some(WINDOWS_1252)
+    );
+    assert_eq!(content_type_encoding("text/html;charset=UTF8"), Some(UTF_8));
+    assert_eq!(content_type_encoding("text/html"), None);
+    assert_eq!(content_type_encoding("text/html; charset=bogus"), None);
+}
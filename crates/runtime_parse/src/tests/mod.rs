@@ -1,5 +1,7 @@
 mod buffering;
+mod decoding;
 mod diff;
 mod helpers;
 mod policy;
+mod preload;
 mod runtime;
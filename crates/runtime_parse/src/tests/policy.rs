@@ -1,12 +1,14 @@
 use std::time::Duration;
 
-use crate::PreviewPolicy;
 use crate::policy::{MAX_PATCH_BUFFER_RETAIN, MIN_PATCH_BUFFER_RETAIN, patch_buffer_retain_target};
+use crate::{AdaptiveTick, PreviewPolicy};
 
 #[test]
 fn preview_policy_flushes_on_thresholds() {
     let policy = PreviewPolicy {
         tick: Duration::from_millis(100),
+        adaptive: None,
+        flush_on_milestones: false,
         token_threshold: Some(10),
         byte_threshold: Some(256),
         patch_threshold: Some(5),
@@ -47,6 +49,8 @@ fn preview_policy_flushes_on_thresholds() {
 fn preview_policy_unbounded_is_clamped() {
     let policy = PreviewPolicy {
         tick: Duration::ZERO,
+        adaptive: None,
+        flush_on_milestones: false,
         token_threshold: None,
         byte_threshold: None,
         patch_threshold: None,
@@ -78,3 +82,30 @@ fn patch_buffer_retain_target_has_floor() {
     let retain = patch_buffer_retain_target(None, None);
     assert_eq!(retain, MIN_PATCH_BUFFER_RETAIN);
 }
+
+#[test]
+fn adaptive_tick_grows_with_the_document_and_backs_off_for_a_busy_ui() {
+    let policy = PreviewPolicy {
+        tick: Duration::from_millis(200),
+        adaptive: Some(AdaptiveTick {
+            min_tick: Duration::from_millis(20),
+            large_document_bytes: 1_000,
+            ui_backlog: 2,
+        }),
+        ..PreviewPolicy::fixed()
+    };
+
+    assert_eq!(policy.tick_for(0, 0), Duration::from_millis(20));
+    assert_eq!(policy.tick_for(500, 0), Duration::from_millis(110));
+    assert_eq!(policy.tick_for(5_000, 0), Duration::from_millis(200));
+
+    assert_eq!(policy.tick_for(0, 2), Duration::from_millis(20));
+    assert_eq!(policy.tick_for(0, 3), Duration::from_millis(40));
+    assert_eq!(policy.tick_for(0, 50), Duration::from_millis(160));
+
+    assert_eq!(
+        PreviewPolicy::fixed().tick_for(0, 50),
+        PreviewPolicy::fixed().tick,
+        "a fixed policy pins the tick"
+    );
+}
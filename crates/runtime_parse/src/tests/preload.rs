@@ -0,0 +1,127 @@
+use std::sync::mpsc;
+
+use bus::{CoreCommand, CoreEvent, ParseLimits};
+use core_types::ResourceKind;
+
+use crate::PreviewPolicy;
+use crate::clock::SystemClock;
+use crate::preload::{PreloadScanner, ResourceHint};
+use crate::runtime::start_parse_runtime_with_policy_and_clock;
+
+use super::runtime::drain_events;
+
+fn scan(chunks: &[&str]) -> Vec<(ResourceKind, String)> {
+    let mut scanner = PreloadScanner::new().expect("scanner");
+    chunks
+        .iter()
+        .flat_map(|chunk| scanner.push_str(chunk))
+        .map(|ResourceHint { url, kind }| (kind, url))
+        .collect()
+}
+
+#[test]
+fn scanner_reports_stylesheets_and_images_in_document_order() {
+    let hints = scan(&[
+        "<head><link rel='preload stylesheet' href=' a.css '><link rel=icon href=i.png>\
+         <LINK REL=StyleSheet HREF=b.css><link rel=stylesheet></head>\
+         <body><img src=x.png><img src=''><img src=x.png><link rel=stylesheet href=a.css>",
+    ]);
+
+    assert_eq!(
+        hints,
+        vec![
+            (ResourceKind::Css, "a.css".to_string()),
+            (ResourceKind::Css, "b.css".to_string()),
+            (ResourceKind::Image, "x.png".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn scanner_ignores_markup_in_text_modes_and_templates() {
+    let hints = scan(&["<script>document.write('<img src=script.png>')</script>\
+         <textarea><img src=textarea.png></textarea><title><img src=title.png></title>\
+         <style>/* <link rel=stylesheet href=style.css> */</style>\
+         <template><img src=template.png></template><img src=after.png>"]);
+
+    assert_eq!(hints, vec![(ResourceKind::Image, "after.png".to_string())]);
+}
+
+#[test]
+fn scanner_waits_for_a_start_tag_split_across_chunks() {
+    let mut scanner = PreloadScanner::new().expect("scanner");
+
+    assert!(scanner.push_str("<p>text</p><img sr").is_empty());
+    assert!(scanner.push_str("c=\"split.png").is_empty());
+    assert_eq!(
+        scanner.push_str("\">"),
+        vec![ResourceHint {
+            url: "split.png".to_string(),
+            kind: ResourceKind::Image,
+        }]
+    );
+}
+
+#[test]
+fn runtime_hints_resources_behind_a_pending_script() {
+    let (cmd_tx, cmd_rx) = mpsc::channel();
+    let (evt_tx, evt_rx) = mpsc::channel();
+    start_parse_runtime_with_policy_and_clock(
+        cmd_rx,
+        evt_tx,
+        PreviewPolicy::default(),
+        SystemClock,
+    );
+
+    let tab_id = 3;
+    let request_id = 4;
+    cmd_tx
+        .send(CoreCommand::ParseHtmlStart {
+            tab_id,
+            request_id,
+            content_type: None,
+            limits: ParseLimits::default(),
+        })
+        .unwrap();
+    cmd_tx
+        .send(CoreCommand::ParseHtmlChunk {
+            tab_id,
+            request_id,
+            bytes: b"<script>run()</script><link rel=stylesheet href=late.css>".to_vec(),
+        })
+        .unwrap();
+    cmd_tx
+        .send(CoreCommand::ParseHtmlDone { tab_id, request_id })
+        .unwrap();
+
+    let events = drain_events(&evt_rx);
+    let hint_at = events
+        .iter()
+        .position(|event| {
+            matches!(
+                event,
+                CoreEvent::ResourceHint {
+                    tab_id: 3,
+                    request_id: 4,
+                    url,
+                    kind: ResourceKind::Css,
+                } if url == "late.css"
+            )
+        })
+        .expect("the stylesheet should be hinted while the script holds the parse");
+    let script_at = events
+        .iter()
+        .position(|event| matches!(event, CoreEvent::ScriptParsed { .. }))
+        .expect("parsing should stop at the script");
+    assert!(hint_at < script_at);
+
+    cmd_tx
+        .send(CoreCommand::ParseHtmlResume { tab_id, request_id })
+        .unwrap();
+    assert!(
+        !drain_events(&evt_rx)
+            .iter()
+            .any(|event| matches!(event, CoreEvent::ResourceHint { .. })),
+        "a resource is hinted once"
+    );
+}
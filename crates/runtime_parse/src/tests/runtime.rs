@@ -1,14 +1,17 @@
 use std::sync::{Arc, Mutex, mpsc};
 use std::time::{Duration, Instant};
 
-use bus::{CoreCommand, CoreEvent};
+use bus::{
+    ChunkCredits, CoreCommand, CoreEvent, HTML_CHUNK_WINDOW_BYTES, ParseLimit, ParseLimits,
+    RuntimeKind,
+};
 use core_types::{DomHandle, DomVersion};
 use html::DomPatch;
 use html::html5::serialize_dom_for_test;
 
 use crate::PreviewPolicy;
 use crate::clock::{PreviewClock, SystemClock};
-use crate::runtime::start_parse_runtime_with_policy_and_clock;
+use crate::runtime::{start_parse_runtime, start_parse_runtime_with_policy_and_clock};
 
 type RuntimeUpdate = (DomHandle, DomVersion, DomVersion, Vec<DomPatch>);
 
@@ -22,7 +25,12 @@ fn collect_runtime_updates(chunks: &[&[u8]]) -> Vec<RuntimeUpdate> {
     let tab_id = 7;
     let request_id = 99;
     cmd_tx
-        .send(CoreCommand::ParseHtmlStart { tab_id, request_id })
+        .send(CoreCommand::ParseHtmlStart {
+            tab_id,
+            request_id,
+            content_type: None,
+            limits: ParseLimits::default(),
+        })
         .unwrap();
     for chunk in chunks {
         cmd_tx
@@ -137,6 +145,33 @@ fn runtime_chunked_parsing_matches_single_chunk_materialization() {
     );
 }
 
+#[test]
+fn runtime_records_start_tag_source_spans() {
+    let batches = assert_runtime_updates_are_well_formed(
+        collect_runtime_updates(&[b"<div><sp", b"an id=a>ok</span></div>"]),
+        true,
+        "source spans",
+    );
+    let patches: Vec<&DomPatch> = batches.iter().flatten().collect();
+    let spans: Vec<(&str, usize, usize)> = patches
+        .iter()
+        .filter_map(|patch| match patch {
+            DomPatch::SetSourceSpan { key, span } => {
+                let name = patches.iter().find_map(|patch| match patch {
+                    DomPatch::CreateElement {
+                        key: created, name, ..
+                    } if created == key => Some(name.local_name_str()),
+                    _ => None,
+                })?;
+                Some((name, span.start, span.end))
+            }
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(spans, [("div", 0, 5), ("span", 5, 16)]);
+}
+
 #[test]
 fn runtime_template_patches_preserve_typed_contents_and_chunk_parity() {
     let input = b"<template><div>inert<template>x</template></div></template><p>active</p>";
@@ -197,6 +232,8 @@ fn runtime_flushes_on_tick_without_sleeping() {
 
     let policy = PreviewPolicy {
         tick: Duration::from_millis(50),
+        adaptive: None,
+        flush_on_milestones: false,
         token_threshold: None,
         byte_threshold: None,
         patch_threshold: None,
@@ -208,7 +245,13 @@ fn runtime_flushes_on_tick_without_sleeping() {
     let tab_id = 1;
     let request_id = 1;
     cmd_tx
-        .send(CoreCommand::ParseHtmlStart { tab_id, request_id })
+        .send(CoreCommand::ParseHtmlStart {
+            tab_id,
+            request_id,
+            // Declared, so the first bytes decode without waiting for more.
+            content_type: Some("text/html; charset=utf-8".to_string()),
+            limits: ParseLimits::default(),
+        })
         .unwrap();
     cmd_tx
         .send(CoreCommand::ParseHtmlChunk {
@@ -246,3 +289,335 @@ fn runtime_flushes_on_tick_without_sleeping() {
 
     let _ = cmd_tx.send(CoreCommand::ParseHtmlDone { tab_id, request_id });
 }
+
+pub(super) fn drain_events(evt_rx: &mpsc::Receiver<CoreEvent>) -> Vec<CoreEvent> {
+    let mut events = Vec::new();
+    while let Ok(event) = evt_rx.recv_timeout(Duration::from_millis(100)) {
+        events.push(event);
+    }
+    events
+}
+
+fn created_texts(events: &[CoreEvent]) -> Vec<String> {
+    events
+        .iter()
+        .filter_map(|event| match event {
+            CoreEvent::DomPatchUpdate { patches, .. } => Some(patches),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|patch| match patch {
+            DomPatch::CreateText { text, .. } => Some(text.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn runtime_stops_at_script_end_until_resumed() {
+    let (cmd_tx, cmd_rx) = mpsc::channel();
+    let (evt_tx, evt_rx) = mpsc::channel();
+    start_parse_runtime_with_policy_and_clock(
+        cmd_rx,
+        evt_tx,
+        PreviewPolicy::default(),
+        SystemClock,
+    );
+
+    let tab_id = 2;
+    let request_id = 5;
+    cmd_tx
+        .send(CoreCommand::ParseHtmlStart {
+            tab_id,
+            request_id,
+            content_type: None,
+            limits: ParseLimits::default(),
+        })
+        .unwrap();
+    cmd_tx
+        .send(CoreCommand::ParseHtmlChunk {
+            tab_id,
+            request_id,
+            bytes: b"<p>before</p><script>run()</script><p>after</p>".to_vec(),
+        })
+        .unwrap();
+    cmd_tx
+        .send(CoreCommand::ParseHtmlDone { tab_id, request_id })
+        .unwrap();
+
+    let events = drain_events(&evt_rx);
+    let script_at = events
+        .iter()
+        .position(|event| {
+            matches!(
+                event,
+                CoreEvent::ScriptParsed { tab_id: 2, request_id: 5, source } if source == "run()"
+            )
+        })
+        .expect("runtime should hand the script over");
+    assert_eq!(
+        script_at,
+        events.len() - 1,
+        "nothing is sent past the script"
+    );
+    let texts = created_texts(&events);
+    assert!(texts.contains(&"before".to_string()));
+    assert!(texts.contains(&"run()".to_string()));
+    assert!(!texts.contains(&"after".to_string()));
+
+    cmd_tx
+        .send(CoreCommand::ParseHtmlResume { tab_id, request_id })
+        .unwrap();
+    let events = drain_events(&evt_rx);
+    assert_eq!(created_texts(&events), vec!["after".to_string()]);
+    assert!(
+        !events
+            .iter()
+            .any(|event| matches!(event, CoreEvent::ScriptParsed { .. }))
+    );
+
+    // The finished parse is dropped, so a late resume is ignored.
+    cmd_tx
+        .send(CoreCommand::ParseHtmlResume { tab_id, request_id })
+        .unwrap();
+    assert!(drain_events(&evt_rx).is_empty());
+}
+
+#[test]
+fn consumed_chunks_give_their_credit_back_to_the_network_side() {
+    let (cmd_tx, cmd_rx) = mpsc::channel();
+    let (evt_tx, _evt_rx) = mpsc::channel();
+    let credits = ChunkCredits::default();
+    start_parse_runtime(cmd_rx, evt_tx, credits.clone());
+
+    let tab_id = 3;
+    let request_id = 8;
+    credits.open(tab_id, request_id);
+    credits.acquire(tab_id, request_id, HTML_CHUNK_WINDOW_BYTES);
+    let (sent_tx, sent_rx) = mpsc::channel();
+    let producer = credits.clone();
+    std::thread::spawn(move || {
+        producer.acquire(tab_id, request_id, 3);
+        let _ = sent_tx.send(());
+    });
+    assert!(sent_rx.recv_timeout(Duration::from_millis(50)).is_err());
+
+    cmd_tx
+        .send(CoreCommand::ParseHtmlStart {
+            tab_id,
+            request_id,
+            content_type: None,
+            limits: ParseLimits::default(),
+        })
+        .unwrap();
+    cmd_tx
+        .send(CoreCommand::ParseHtmlChunk {
+            tab_id,
+            request_id,
+            bytes: b"<p>".to_vec(),
+        })
+        .unwrap();
+    sent_rx
+        .recv_timeout(Duration::from_secs(5))
+        .expect("parsing a chunk should let the network side send more");
+}
+
+#[test]
+fn cancel_generation_drops_the_parse_of_an_abandoned_navigation() {
+    let (cmd_tx, cmd_rx) = mpsc::channel();
+    let (evt_tx, evt_rx) = mpsc::channel();
+    start_parse_runtime_with_policy_and_clock(
+        cmd_rx,
+        evt_tx,
+        PreviewPolicy::default(),
+        SystemClock,
+    );
+
+    let tab_id = 4;
+    let request_id = 2;
+    cmd_tx
+        .send(CoreCommand::ParseHtmlStart {
+            tab_id,
+            request_id,
+            content_type: None,
+            limits: ParseLimits::default(),
+        })
+        .unwrap();
+    cmd_tx
+        .send(CoreCommand::ParseHtmlChunk {
+            tab_id,
+            request_id,
+            bytes: b"<p>abandoned".to_vec(),
+        })
+        .unwrap();
+    cmd_tx
+        .send(CoreCommand::CancelGeneration { tab_id, request_id })
+        .unwrap();
+    cmd_tx
+        .send(CoreCommand::ParseHtmlDone { tab_id, request_id })
+        .unwrap();
+
+    assert!(
+        created_texts(&drain_events(&evt_rx)).is_empty(),
+        "a cancelled parse must not flush its buffered patches"
+    );
+}
+
+#[test]
+fn finished_parse_reports_its_metrics() {
+    let (cmd_tx, cmd_rx) = mpsc::channel();
+    let (evt_tx, evt_rx) = mpsc::channel();
+    start_parse_runtime_with_policy_and_clock(
+        cmd_rx,
+        evt_tx,
+        PreviewPolicy::default(),
+        SystemClock,
+    );
+
+    let tab_id = 6;
+    let request_id = 3;
+    cmd_tx
+        .send(CoreCommand::ParseHtmlStart {
+            tab_id,
+            request_id,
+            content_type: None,
+            limits: ParseLimits::default(),
+        })
+        .unwrap();
+    cmd_tx
+        .send(CoreCommand::ParseHtmlChunk {
+            tab_id,
+            request_id,
+            bytes: b"<p>one<p>two".to_vec(),
+        })
+        .unwrap();
+    cmd_tx
+        .send(CoreCommand::ParseHtmlDone { tab_id, request_id })
+        .unwrap();
+
+    let events = drain_events(&evt_rx);
+    let samples: Vec<_> = events
+        .iter()
+        .filter_map(|event| match event {
+            CoreEvent::Metrics {
+                tab_id: 6,
+                request_id: 3,
+                which: RuntimeKind::Parse,
+                metrics,
+            } => Some(metrics),
+            _ => None,
+        })
+        .collect();
+    let [metrics] = samples[..] else {
+        panic!("expected one metrics sample: {events:?}");
+    };
+    // document, html, head, body, two paragraphs, and their texts
+    assert_eq!(metrics.dom_nodes, 8);
+    assert!(metrics.tokens_emitted > 0);
+    assert_eq!(metrics.bytes_fetched, 0);
+}
+
+fn truncations(events: &[CoreEvent]) -> Vec<ParseLimit> {
+    events
+        .iter()
+        .filter_map(|event| match event {
+            CoreEvent::ResourceTruncated { limit, .. } => Some(*limit),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn input_past_the_byte_limit_ends_the_document_and_its_download() {
+    let (cmd_tx, cmd_rx) = mpsc::channel();
+    let (evt_tx, evt_rx) = mpsc::channel();
+    let credits = ChunkCredits::default();
+    start_parse_runtime(cmd_rx, evt_tx, credits.clone());
+
+    let tab_id = 5;
+    let request_id = 4;
+    credits.open(tab_id, request_id);
+    cmd_tx
+        .send(CoreCommand::ParseHtmlStart {
+            tab_id,
+            request_id,
+            content_type: None,
+            limits: ParseLimits {
+                max_bytes: 12,
+                ..ParseLimits::default()
+            },
+        })
+        .unwrap();
+    for chunk in [&b"<p>first"[..], b"<p>second", b"<p>third"] {
+        cmd_tx
+            .send(CoreCommand::ParseHtmlChunk {
+                tab_id,
+                request_id,
+                bytes: chunk.to_vec(),
+            })
+            .unwrap();
+    }
+
+    let events = drain_events(&evt_rx);
+    assert_eq!(truncations(&events), vec![ParseLimit::Bytes]);
+    // The document ends at byte 12 without waiting for `ParseHtmlDone`.
+    assert_eq!(created_texts(&events), vec!["first", "s"]);
+    assert!(
+        events
+            .iter()
+            .any(|event| matches!(event, CoreEvent::Metrics { .. })),
+        "the truncated parse should be over: {events:?}"
+    );
+    assert!(
+        !credits.acquire(tab_id, request_id, 1),
+        "the network side should be told to stop sending"
+    );
+}
+
+#[test]
+fn node_and_depth_limits_are_reported_once_each() {
+    let (cmd_tx, cmd_rx) = mpsc::channel();
+    let (evt_tx, evt_rx) = mpsc::channel();
+    start_parse_runtime_with_policy_and_clock(
+        cmd_rx,
+        evt_tx,
+        PreviewPolicy::default(),
+        SystemClock,
+    );
+
+    let tab_id = 8;
+    let request_id = 1;
+    cmd_tx
+        .send(CoreCommand::ParseHtmlStart {
+            tab_id,
+            request_id,
+            content_type: None,
+            limits: ParseLimits {
+                max_nodes: 12,
+                max_depth: 4,
+                ..ParseLimits::default()
+            },
+        })
+        .unwrap();
+    for chunk in [
+        &b"<div><div><div>deep</div></div></div>"[..],
+        b"<div><div><div>deeper</div></div></div>",
+        b"<p>a<p>b<p>c<p>d<p>e<p>f",
+    ] {
+        cmd_tx
+            .send(CoreCommand::ParseHtmlChunk {
+                tab_id,
+                request_id,
+                bytes: chunk.to_vec(),
+            })
+            .unwrap();
+    }
+    cmd_tx
+        .send(CoreCommand::ParseHtmlDone { tab_id, request_id })
+        .unwrap();
+
+    let truncations = truncations(&drain_events(&evt_rx));
+    assert_eq!(truncations.len(), 2, "{truncations:?}");
+    assert!(truncations.contains(&ParseLimit::Depth));
+    assert!(truncations.contains(&ParseLimit::Nodes));
+}
@@ -1,4 +1,4 @@
-use bus::{CoreCommand, CoreEvent};
+use bus::{CoreCommand, CoreEvent, ParseLimits};
 use html::DomPatch;
 use html::parse_guards;
 use runtime_parse::{PreviewPolicy, start_parse_runtime_with_policy};
@@ -24,6 +24,8 @@ fn runtime_parse_linear_streaming_guard() {
     let chunk_size = 64usize;
     let policy = PreviewPolicy {
         tick: Duration::ZERO,
+        adaptive: None,
+        flush_on_milestones: false,
         token_threshold: None,
         byte_threshold: Some(chunk_size),
         patch_threshold: None,
@@ -107,7 +109,12 @@ fn run_runtime_parse_stream(
     let tab_id = 1;
     let request_id = 1;
     cmd_tx
-        .send(CoreCommand::ParseHtmlStart { tab_id, request_id })
+        .send(CoreCommand::ParseHtmlStart {
+            tab_id,
+            request_id,
+            content_type: None,
+            limits: ParseLimits::default(),
+        })
         .unwrap();
 
     let bytes = input.as_bytes();
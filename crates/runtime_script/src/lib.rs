@@ -0,0 +1,85 @@
+//! Script runtime.
+//!
+//! The parse runtime stops at every `</script>` and the tab forwards the
+//! script text here as `CoreCommand::RunScript`. Once the script has run, this
+//! runtime answers with `CoreEvent::ScriptDone` and the tab resumes the parse,
+//! so anything a script writes into the document lands before the markup
+//! after it. There is no JavaScript engine yet: scripts are accepted without
+//! running, which keeps the suspend/resume contract exercised end to end.
+
+use bus::{CoreCommand, CoreEvent, RuntimeKind, run_supervised};
+use std::sync::mpsc::{Receiver, Sender};
+use std::thread::{self, JoinHandle};
+
+pub fn start_script_runtime(
+    cmd_rx: Receiver<CoreCommand>,
+    evt_tx: Sender<CoreEvent>,
+) -> JoinHandle<()> {
+    thread::spawn(move || {
+        run_supervised(
+            RuntimeKind::Script,
+            &cmd_rx,
+            &evt_tx,
+            || (),
+            |_, cmd| {
+                let CoreCommand::RunScript {
+                    tab_id,
+                    request_id,
+                    source,
+                } = cmd
+                else {
+                    return;
+                };
+                let result = run_script(&source);
+                let _ = evt_tx.send(CoreEvent::ScriptDone {
+                    tab_id,
+                    request_id,
+                    result,
+                });
+            },
+        );
+    })
+}
+
+/// Run one classic script. Stub: every script completes without effect.
+pub fn run_script(_source: &str) -> Result<(), String> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::start_script_runtime;
+    use bus::{CoreCommand, CoreEvent};
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    #[test]
+    fn run_script_replies_with_script_done_for_the_same_request() {
+        let (cmd_tx, cmd_rx) = mpsc::channel();
+        let (evt_tx, evt_rx) = mpsc::channel();
+        start_script_runtime(cmd_rx, evt_tx);
+
+        cmd_tx
+            .send(CoreCommand::RunScript {
+                tab_id: 4,
+                request_id: 11,
+                source: "document.title = 'x';".to_string(),
+            })
+            .expect("send RunScript");
+
+        let event = evt_rx
+            .recv_timeout(Duration::from_secs(5))
+            .expect("script runtime event");
+        assert!(
+            matches!(
+                event,
+                CoreEvent::ScriptDone {
+                    tab_id: 4,
+                    request_id: 11,
+                    result: Ok(()),
+                }
+            ),
+            "unexpected event: {event:?}"
+        );
+    }
+}
@@ -0,0 +1,185 @@
+//! Timer runtime.
+//!
+//! One thread keeps the pending timeouts of every tab and answers each
+//! `CoreCommand::SetTimeout` with a `CoreEvent::TimeoutFired` once its delay
+//! has passed. `ClearTimeout` cancels a timeout before it fires, and a tab's
+//! close cancels all of the tab's timeouts. Timeouts with the same deadline
+//! fire in the order they were set.
+//!
+//! Unlike the other runtimes this one does not block on its command channel
+//! alone: it waits for the next command or the next deadline, whichever
+//! comes first.
+
+use bus::{CoreCommand, CoreEvent};
+use core_types::TabId;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+pub fn start_timer_runtime(
+    cmd_rx: Receiver<CoreCommand>,
+    evt_tx: Sender<CoreEvent>,
+) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let mut timers = Timers::default();
+        loop {
+            let received = match timers.next_deadline() {
+                Some(deadline) => {
+                    cmd_rx.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                }
+                None => cmd_rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+            match received {
+                Ok(CoreCommand::SetTimeout {
+                    tab_id,
+                    token,
+                    delay,
+                }) => timers.set(tab_id, token, delay),
+                Ok(CoreCommand::ClearTimeout { tab_id, token }) => timers.clear(tab_id, token),
+                Ok(CoreCommand::TabClosed { tab_id }) => timers.clear_tab(tab_id),
+                Ok(CoreCommand::Shutdown { ack }) => {
+                    drop(timers);
+                    let _ = ack.into_inner().send(());
+                    return;
+                }
+                Ok(_) | Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+            for (tab_id, token) in timers.take_due(Instant::now()) {
+                let _ = evt_tx.send(CoreEvent::TimeoutFired { tab_id, token });
+            }
+        }
+    })
+}
+
+/// Pending timeouts, ordered by deadline.
+#[derive(Default)]
+struct Timers {
+    /// Keyed by deadline, then by when the timeout was set.
+    queue: BTreeMap<(Instant, u64), (TabId, u64)>,
+    /// Queue key of each pending timeout.
+    pending: HashMap<(TabId, u64), (Instant, u64)>,
+    next_seq: u64,
+}
+
+impl Timers {
+    fn set(&mut self, tab_id: TabId, token: u64, delay: Duration) {
+        self.clear(tab_id, token);
+        let key = (Instant::now() + delay, self.next_seq);
+        self.next_seq += 1;
+        self.queue.insert(key, (tab_id, token));
+        self.pending.insert((tab_id, token), key);
+    }
+
+    fn clear(&mut self, tab_id: TabId, token: u64) {
+        if let Some(key) = self.pending.remove(&(tab_id, token)) {
+            self.queue.remove(&key);
+        }
+    }
+
+    fn clear_tab(&mut self, tab_id: TabId) {
+        self.queue.retain(|_, (tab, _)| *tab != tab_id);
+        self.pending.retain(|(tab, _), _| *tab != tab_id);
+    }
+
+    fn next_deadline(&self) -> Option<Instant> {
+        self.queue.keys().next().map(|(deadline, _)| *deadline)
+    }
+
+    /// Remove and return the timeouts whose deadline is not after `now`.
+    fn take_due(&mut self, now: Instant) -> Vec<(TabId, u64)> {
+        let mut due = Vec::new();
+        while let Some(entry) = self.queue.first_entry() {
+            if entry.key().0 > now {
+                break;
+            }
+            let timer = entry.remove();
+            self.pending.remove(&timer);
+            due.push(timer);
+        }
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::start_timer_runtime;
+    use bus::{CoreCommand, CoreEvent, InProcess};
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    fn set(tab_id: u64, token: u64, millis: u64) -> CoreCommand {
+        CoreCommand::SetTimeout {
+            tab_id,
+            token,
+            delay: Duration::from_millis(millis),
+        }
+    }
+
+    fn fired(evt_rx: &mpsc::Receiver<CoreEvent>) -> Option<(u64, u64)> {
+        match evt_rx.recv_timeout(Duration::from_millis(500)) {
+            Ok(CoreEvent::TimeoutFired { tab_id, token }) => Some((tab_id, token)),
+            Ok(other) => panic!("unexpected event: {other:?}"),
+            Err(_) => None,
+        }
+    }
+
+    #[test]
+    fn timeouts_fire_in_deadline_order() {
+        let (cmd_tx, cmd_rx) = mpsc::channel();
+        let (evt_tx, evt_rx) = mpsc::channel();
+        start_timer_runtime(cmd_rx, evt_tx);
+
+        cmd_tx.send(set(1, 7, 60)).unwrap();
+        cmd_tx.send(set(2, 3, 10)).unwrap();
+        cmd_tx.send(set(1, 8, 30)).unwrap();
+
+        assert_eq!(fired(&evt_rx), Some((2, 3)));
+        assert_eq!(fired(&evt_rx), Some((1, 8)));
+        assert_eq!(fired(&evt_rx), Some((1, 7)));
+        assert_eq!(fired(&evt_rx), None);
+    }
+
+    #[test]
+    fn cleared_timeouts_and_those_of_closed_tabs_do_not_fire() {
+        let (cmd_tx, cmd_rx) = mpsc::channel();
+        let (evt_tx, evt_rx) = mpsc::channel();
+        start_timer_runtime(cmd_rx, evt_tx);
+
+        cmd_tx.send(set(1, 1, 20)).unwrap();
+        cmd_tx.send(set(1, 2, 20)).unwrap();
+        cmd_tx.send(set(2, 1, 20)).unwrap();
+        // Setting a token again replaces its timeout
+        cmd_tx.send(set(2, 1, 40)).unwrap();
+        cmd_tx
+            .send(CoreCommand::ClearTimeout {
+                tab_id: 1,
+                token: 2,
+            })
+            .unwrap();
+        cmd_tx.send(CoreCommand::TabClosed { tab_id: 1 }).unwrap();
+
+        assert_eq!(fired(&evt_rx), Some((2, 1)));
+        assert_eq!(fired(&evt_rx), None);
+    }
+
+    #[test]
+    fn shutdown_drops_pending_timeouts() {
+        let (cmd_tx, cmd_rx) = mpsc::channel();
+        let (evt_tx, evt_rx) = mpsc::channel();
+        let thread = start_timer_runtime(cmd_rx, evt_tx);
+        let (ack_tx, ack_rx) = mpsc::channel();
+
+        cmd_tx.send(set(1, 1, 10)).unwrap();
+        cmd_tx
+            .send(CoreCommand::Shutdown {
+                ack: InProcess::new(ack_tx),
+            })
+            .unwrap();
+
+        thread.join().expect("timer thread");
+        assert!(ack_rx.try_recv().is_ok());
+        assert!(evt_rx.try_recv().is_err());
+    }
+}
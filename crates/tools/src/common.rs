@@ -1,3 +1,2 @@
-pub const MAX_DOCUMENT_BYTES: usize = 10 * 1024 * 1024;
 pub const MAX_STYLESHEET_BYTES: usize = 2 * 1024 * 1024;
 pub const MAX_IMAGE_BYTES: usize = 20 * 1024 * 1024;
@@ -0,0 +1,174 @@
+//! URL resolution for documents.
+//!
+//! Every place that turns markup into a fetch or a navigation — resource
+//! discovery, images, anchors, CSS `url()` — resolves through here, so they
+//! all agree on whitespace handling and on the document base URL.
+//!
+//! Parsing, joining, normalization and percent-encoding follow the URL
+//! Standard as implemented by the `url` crate: a parsed [`Url`] always
+//! serializes in normalized form, with the scheme and host lowercased, dot
+//! segments removed, default ports dropped and disallowed characters
+//! percent-encoded. This crate adds the HTML rules on top.
+
+pub use url::Url;
+
+/// Parse an absolute URL.
+pub fn parse(input: &str) -> Option<Url> {
+    Url::parse(input).ok()
+}
+
+/// Resolve a URL-valued attribute (`href`, `src`) or a CSS `url()` against
+/// `base`.
+///
+/// Surrounding HTML ASCII whitespace is stripped first, as HTML does for
+/// URL-valued attributes. An empty value names no resource and yields
+/// `None`.
+pub fn resolve(base: &Url, value: &str) -> Option<Url> {
+    let value = strip_html_ascii_whitespace(value);
+    if value.is_empty() {
+        return None;
+    }
+    base.join(value).ok()
+}
+
+/// [`resolve`] with the base as stored on a page. Without a usable base only
+/// absolute values resolve.
+pub fn resolve_str(base: Option<&str>, value: &str) -> Option<String> {
+    let resolved = match base.and_then(parse) {
+        Some(base) => resolve(&base, value),
+        None => {
+            let value = strip_html_ascii_whitespace(value);
+            if value.is_empty() {
+                return None;
+            }
+            parse(value)
+        }
+    };
+    resolved.map(String::from)
+}
+
+/// The document base URL: the `href` of the document's first `<base href>`
+/// resolved against the document's own URL, or the document URL itself when
+/// there is no such element or its `href` does not resolve.
+pub fn document_base_url(document_url: &Url, base_href: Option<&str>) -> Url {
+    base_href
+        .and_then(|href| resolve(document_url, href))
+        .unwrap_or_else(|| document_url.clone())
+}
+
+/// The percent-decoded fragment of `url`, which is what element ids are
+/// matched against when scrolling to a fragment.
+pub fn fragment_id(url: &Url) -> Option<String> {
+    url.fragment().map(|fragment| {
+        percent_encoding::percent_decode_str(fragment)
+            .decode_utf8_lossy()
+            .into_owned()
+    })
+}
+
+/// `url` with its fragment removed. Two URLs that are equal without their
+/// fragments address the same document.
+pub fn without_fragment(url: &Url) -> Url {
+    let mut url = url.clone();
+    url.set_fragment(None);
+    url
+}
+
+/// Strip leading and trailing tab, LF, FF, CR and space. Other whitespace,
+/// such as U+00A0, is part of the value.
+pub fn strip_html_ascii_whitespace(value: &str) -> &str {
+    value.trim_matches(|character| {
+        matches!(
+            character,
+            '\u{0009}' | '\u{000A}' | '\u{000C}' | '\u{000D}' | '\u{0020}'
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        document_base_url, fragment_id, parse, resolve, resolve_str, strip_html_ascii_whitespace,
+        without_fragment,
+    };
+
+    #[test]
+    fn attribute_values_resolve_against_the_base_after_whitespace_stripping() {
+        assert_eq!(
+            resolve_str(
+                Some("https://example.com/docs/page.html"),
+                "\t\n\u{000C}\r ../hero.png \t\n\u{000C}\r"
+            )
+            .as_deref(),
+            Some("https://example.com/hero.png")
+        );
+        assert_eq!(
+            resolve_str(
+                Some("https://ignored.example/base/"),
+                " \thttps://cdn.example/hero.png\r\n"
+            )
+            .as_deref(),
+            Some("https://cdn.example/hero.png")
+        );
+        assert_eq!(resolve_str(Some("https://example.com/"), ""), None);
+        assert_eq!(
+            resolve_str(Some("https://example.com/"), " \t\n\u{000C}\r"),
+            None
+        );
+        assert_eq!(resolve_str(None, "relative.png"), None);
+        assert_eq!(
+            resolve_str(None, "https://cdn.example/hero.png").as_deref(),
+            Some("https://cdn.example/hero.png")
+        );
+    }
+
+    #[test]
+    fn only_html_ascii_whitespace_is_stripped() {
+        let non_ascii_whitespace = "\u{00A0}https://cdn.example/hero.png\u{00A0}";
+        assert_eq!(
+            strip_html_ascii_whitespace(non_ascii_whitespace),
+            non_ascii_whitespace
+        );
+        assert_eq!(resolve_str(None, non_ascii_whitespace), None);
+    }
+
+    #[test]
+    fn parsed_urls_are_normalized() {
+        assert_eq!(
+            parse("HTTPS://Example.COM:443/a/./b/../c d?q=ü").map(String::from),
+            Some("https://example.com/a/c%20d?q=%C3%BC".to_string())
+        );
+        assert!(parse("not a url").is_none());
+    }
+
+    #[test]
+    fn base_href_overrides_the_document_url_when_it_resolves() {
+        let document = parse("https://example.com/docs/page.html").unwrap();
+
+        let base = document_base_url(&document, Some(" /assets/ "));
+        assert_eq!(base.as_str(), "https://example.com/assets/");
+        assert_eq!(
+            resolve(&base, "logo.png").map(String::from).as_deref(),
+            Some("https://example.com/assets/logo.png")
+        );
+        assert_eq!(
+            document_base_url(&document, Some("https://cdn.example/")).as_str(),
+            "https://cdn.example/"
+        );
+        assert_eq!(document_base_url(&document, None), document);
+        assert_eq!(document_base_url(&document, Some("")), document);
+        assert_eq!(document_base_url(&document, Some("http://[::1")), document);
+    }
+
+    #[test]
+    fn fragments_are_percent_decoded_and_removable() {
+        let url = parse("https://example.com/page?q=1#caf%C3%A9").unwrap();
+
+        assert_eq!(fragment_id(&url).as_deref(), Some("café"));
+        assert_eq!(
+            without_fragment(&url).as_str(),
+            "https://example.com/page?q=1"
+        );
+        assert_eq!(fragment_id(&without_fragment(&url)), None);
+    }
+}
@@ -0,0 +1,77 @@
+//! Known failures of the imported WPT slice.
+//!
+//! One test per line, `<test id>: <reason>`; blank lines and lines starting
+//! with `#` are ignored. Tests not listed are expected to pass, so the file
+//! shrinks as the engine catches up with the specs.
+//!
+//! ```text
+//! # Needs `writing-mode`.
+//! vertical-lr-width-001: writing-mode is not implemented
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Expectations {
+    /// Reason each known failure fails, by test id.
+    pub failures: BTreeMap<String, String>,
+}
+
+impl Expectations {
+    /// Read the expectations at `path`; a missing file expects every test to
+    /// pass.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = fs::read_to_string(path).map_err(|err| format!("{}: {err}", path.display()))?;
+        Self::parse(&text).map_err(|err| format!("{}: {err}", path.display()))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut failures = BTreeMap::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (id, reason) = line
+                .split_once(':')
+                .map(|(id, reason)| (id.trim(), reason.trim()))
+                .filter(|(id, reason)| !id.is_empty() && !reason.is_empty())
+                .ok_or_else(|| format!("line {}: expected `<test id>: <reason>`", index + 1))?;
+            if failures
+                .insert(id.to_string(), reason.to_string())
+                .is_some()
+            {
+                return Err(format!("line {}: `{id}` is listed twice", index + 1));
+            }
+        }
+        Ok(Self { failures })
+    }
+
+    pub fn expects_failure(&self, id: &str) -> bool {
+        self.failures.contains_key(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Expectations;
+
+    #[test]
+    fn listed_tests_are_expected_to_fail_with_a_reason() {
+        let expectations =
+            Expectations::parse("# header\n\nfloat-001: floats\nflex-002 : gaps: not yet\n")
+                .expect("valid expectations");
+        assert!(expectations.expects_failure("float-001"));
+        assert_eq!(expectations.failures["flex-002"], "gaps: not yet");
+        assert!(!expectations.expects_failure("block-001"));
+
+        assert!(Expectations::parse("float-001").is_err());
+        assert!(Expectations::parse("float-001:").is_err());
+        assert!(Expectations::parse("a: x\na: y").is_err());
+    }
+}
@@ -0,0 +1,323 @@
+//! Converting WPT tests into the local fixture formats.
+//!
+//! The suite directory's `import.list` names the tests to import, one
+//! WPT-relative path per line (`#` starts a comment). Each becomes:
+//!
+//! - a reftest, if it has a `<link rel=match>` or `<link rel=mismatch>`
+//!   reference: the test and its reference are copied under `vendor/` and
+//!   listed in `reftest.list`, with the test's `<meta name=fuzzy>` as its
+//!   tolerance;
+//! - a layout test, if it carries `check-layout-th.js` expectations: the test
+//!   becomes the layout fixture `layout/<id>/input.html`, and its current
+//!   fragment tree, laid out with Ahem metrics, is blessed as
+//!   `layout/<id>/fragments.txt`.
+//!
+//! Tests that are neither, that need more than one reference, or that load
+//! support files other than the harness's `/fonts/` and `/resources/` are
+//! rejected with a reason. Importing replaces `vendor/`, `layout/`, and
+//! `reftest.list` wholesale, so they always mirror `import.list`.
+
+use crate::source::TestSource;
+use layout_test_support::{
+    AhemTextMeasurer, ExpectedFragments, TextMetrics, fragment_snapshot_with, write_expected_file,
+};
+use reftest::{Relation, Tolerance};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+/// Viewport imported layout tests are laid out in, matching the reftests'.
+pub const LAYOUT_VIEWPORT_WIDTH: f32 = 800.0;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    /// Ids of the imported reftests.
+    pub reftests: Vec<String>,
+    /// Ids of the imported layout tests.
+    pub layout_tests: Vec<String>,
+    /// WPT paths that were not imported, with the reason.
+    pub rejected: Vec<(String, String)>,
+}
+
+enum Plan {
+    Reftest {
+        relation: Relation,
+        tolerance: Tolerance,
+        test: String,
+        reference: String,
+    },
+    Layout {
+        id: String,
+        input: String,
+    },
+}
+
+/// Import the tests `suite/import.list` names from the WPT checkout at
+/// `wpt_root` into `suite`.
+pub fn import(wpt_root: &Path, suite: &Path) -> Result<ImportReport, String> {
+    let list_path = suite.join("import.list");
+    let list =
+        fs::read_to_string(&list_path).map_err(|err| format!("{}: {err}", list_path.display()))?;
+
+    let mut report = ImportReport::default();
+    let mut plans = Vec::new();
+    let mut ids = BTreeSet::new();
+    for path in list
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty())
+    {
+        match plan(wpt_root, path) {
+            Ok(plan) => {
+                let id = test_id(path);
+                if !ids.insert(id.clone()) {
+                    report
+                        .rejected
+                        .push((path.to_string(), format!("another test is named `{id}`")));
+                    continue;
+                }
+                plans.push(plan);
+            }
+            Err(reason) => report.rejected.push((path.to_string(), reason)),
+        }
+    }
+
+    for dir in ["vendor", "layout"] {
+        let dir = suite.join(dir);
+        if dir.exists() {
+            fs::remove_dir_all(&dir).map_err(|err| format!("{}: {err}", dir.display()))?;
+        }
+    }
+    let mut manifest = String::from(
+        "# Generated by `cargo run -p wpt-css -- import`; edit import.list instead.\n",
+    );
+    for plan in plans {
+        match plan {
+            Plan::Reftest {
+                relation,
+                tolerance,
+                test,
+                reference,
+            } => {
+                for path in [&test, &reference] {
+                    copy_into(&wpt_root.join(path), &suite.join("vendor").join(path))?;
+                }
+                if tolerance != Tolerance::default() {
+                    manifest.push_str(&format!(
+                        "fuzzy({},{}) ",
+                        tolerance.max_channel_difference, tolerance.max_pixels
+                    ));
+                }
+                let relation = match relation {
+                    Relation::Equal => "==",
+                    Relation::NotEqual => "!=",
+                };
+                manifest.push_str(&format!("{relation} vendor/{test} vendor/{reference}\n"));
+                report.reftests.push(test_id(&test));
+            }
+            Plan::Layout { id, input } => {
+                let dir = suite.join("layout").join(&id);
+                fs::create_dir_all(&dir).map_err(|err| format!("{}: {err}", dir.display()))?;
+                let lines =
+                    fragment_snapshot_with(&input, LAYOUT_VIEWPORT_WIDTH, &AhemTextMeasurer)
+                        .map_err(|err| format!("{id}: {err}"))?;
+                fs::write(dir.join("input.html"), &input)
+                    .map_err(|err| format!("{}: {err}", dir.display()))?;
+                write_expected_file(
+                    &dir.join("fragments.txt"),
+                    &ExpectedFragments {
+                        viewport_width: LAYOUT_VIEWPORT_WIDTH,
+                        text_metrics: TextMetrics::Ahem,
+                        lines,
+                    },
+                );
+                report.layout_tests.push(id);
+            }
+        }
+    }
+    let manifest_path = suite.join("reftest.list");
+    fs::write(&manifest_path, manifest)
+        .map_err(|err| format!("{}: {err}", manifest_path.display()))?;
+    Ok(report)
+}
+
+/// A test's id: its file name without the extension.
+pub fn test_id(path: &str) -> String {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    name.rsplit_once('.')
+        .map_or(name, |(stem, _)| stem)
+        .to_string()
+}
+
+fn plan(wpt_root: &Path, path: &str) -> Result<Plan, String> {
+    let input = read_test(wpt_root, path)?;
+    let source = TestSource::scan(&input)?;
+    check_resources(&source)?;
+    match source.references.as_slice() {
+        [] if source.has_layout_checks => Ok(Plan::Layout {
+            id: test_id(path),
+            input,
+        }),
+        [] => Err("neither a reftest nor a check-layout test".to_string()),
+        [(relation, href)] => {
+            let reference = resolve(path, href)?;
+            let reference_source = TestSource::scan(&read_test(wpt_root, &reference)?)?;
+            if !reference_source.references.is_empty() {
+                return Err(format!("reference `{reference}` has references of its own"));
+            }
+            check_resources(&reference_source)?;
+            Ok(Plan::Reftest {
+                relation: *relation,
+                tolerance: source.fuzzy.unwrap_or_default(),
+                test: path.to_string(),
+                reference,
+            })
+        }
+        _ => Err("more than one reference".to_string()),
+    }
+}
+
+fn read_test(wpt_root: &Path, path: &str) -> Result<String, String> {
+    fs::read_to_string(wpt_root.join(path)).map_err(|err| format!("{path}: {err}"))
+}
+
+/// Harness-provided resources are fine to leave behind: `/fonts/ahem.css`
+/// only declares the font, which layout tests get from their text metrics.
+fn check_resources(source: &TestSource) -> Result<(), String> {
+    match source.resources.iter().find(|resource| {
+        !(resource.starts_with("/fonts/")
+            || resource.starts_with("/resources/")
+            || resource.starts_with("data:"))
+    }) {
+        Some(resource) => Err(format!("depends on support file `{resource}`")),
+        None => Ok(()),
+    }
+}
+
+/// Resolve `href` against the WPT path of the test that links to it.
+fn resolve(test: &str, href: &str) -> Result<String, String> {
+    let mut parts = Vec::new();
+    let relative = match href.strip_prefix('/') {
+        Some(absolute) => absolute,
+        None => {
+            parts.extend(test.split('/'));
+            parts.pop();
+            href
+        }
+    };
+    for part in relative.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts
+                    .pop()
+                    .ok_or_else(|| format!("reference `{href}` leaves the WPT checkout"))?;
+            }
+            part => parts.push(part),
+        }
+    }
+    Ok(parts.join("/"))
+}
+
+fn copy_into(from: &Path, to: &Path) -> Result<(), String> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent).map_err(|err| format!("{}: {err}", parent.display()))?;
+    }
+    fs::copy(from, to).map_err(|err| format!("{}: {err}", from.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{import, resolve};
+    use std::fs;
+    use std::path::Path;
+
+    fn write(root: &Path, path: &str, text: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, text).unwrap();
+    }
+
+    #[test]
+    fn references_resolve_against_the_test_or_the_checkout_root() {
+        assert_eq!(
+            resolve("css/box/a.html", "a-ref.html").as_deref(),
+            Ok("css/box/a-ref.html")
+        );
+        assert_eq!(
+            resolve("css/box/a.html", "../reference/green.html").as_deref(),
+            Ok("css/reference/green.html")
+        );
+        assert_eq!(
+            resolve("css/box/a.html", "/css/reference/green.html").as_deref(),
+            Ok("css/reference/green.html")
+        );
+        assert!(resolve("a.html", "../../b.html").is_err());
+    }
+
+    #[test]
+    fn import_writes_reftests_and_layout_fixtures_and_rejects_the_rest() {
+        let wpt = tempfile::tempdir().expect("wpt checkout");
+        let suite = tempfile::tempdir().expect("suite");
+        write(
+            wpt.path(),
+            "css/box/square.html",
+            r#"<link rel="match" href="../reference/square-ref.html"><meta name="fuzzy" content="0-2;0-10"><div></div>"#,
+        );
+        write(wpt.path(), "css/reference/square-ref.html", "<div></div>");
+        write(
+            wpt.path(),
+            "css/flex/grow.html",
+            r#"<link rel="stylesheet" href="/fonts/ahem.css"><div data-expected-width="800"></div>"#,
+        );
+        write(wpt.path(), "css/dom/script.html", "<script>test()</script>");
+        write(
+            wpt.path(),
+            "css/box/image.html",
+            r#"<link rel="match" href="image-ref.html"><img src="support/a.png">"#,
+        );
+        write(
+            suite.path(),
+            "import.list",
+            "# curated\ncss/box/square.html\ncss/flex/grow.html # layout\ncss/dom/script.html\ncss/box/image.html\ncss/box/missing.html\n",
+        );
+        // Stale output from an earlier import is dropped.
+        write(suite.path(), "layout/stale/input.html", "");
+
+        let report = import(wpt.path(), suite.path()).expect("import succeeds");
+
+        assert_eq!(report.reftests, ["square"]);
+        assert_eq!(report.layout_tests, ["grow"]);
+        let rejected: Vec<_> = report
+            .rejected
+            .iter()
+            .map(|(path, _)| path.as_str())
+            .collect();
+        assert_eq!(
+            rejected,
+            [
+                "css/dom/script.html",
+                "css/box/image.html",
+                "css/box/missing.html"
+            ]
+        );
+        assert_eq!(
+            fs::read_to_string(suite.path().join("reftest.list"))
+                .unwrap()
+                .lines()
+                .nth(1),
+            Some("fuzzy(2,10) == vendor/css/box/square.html vendor/css/reference/square-ref.html")
+        );
+        assert!(
+            suite
+                .path()
+                .join("vendor/css/reference/square-ref.html")
+                .is_file()
+        );
+        let fragments = fs::read_to_string(suite.path().join("layout/grow/fragments.txt")).unwrap();
+        assert!(fragments.starts_with("# format: layout-fragments-v1\n# text: ahem\n"));
+        assert!(!suite.path().join("layout/stale").exists());
+        reftest::Manifest::load(&suite.path().join("reftest.list")).expect("valid manifest");
+    }
+}
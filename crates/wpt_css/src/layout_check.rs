@@ -0,0 +1,167 @@
+//! `check-layout-th.js` expectations, checked against the layout tree.
+//!
+//! WPT layout tests annotate elements with the geometry they must end up
+//! with: `data-expected-width` and `data-expected-height` (border box),
+//! `data-expected-client-width` and `data-expected-client-height` (padding
+//! box), and `data-offset-x` and `data-offset-y` (border box position
+//! relative to the padding edge of the nearest positioned ancestor, or to
+//! the initial containing block). Every annotated element is checked, not
+//! only those the test's `checkLayout()` selector names. Values match when
+//! they are less than a pixel apart.
+
+use html::Node;
+use layout::LayoutBox;
+use layout_test_support::{AhemTextMeasurer, with_layout};
+
+/// A `data-*` expectation the layout did not meet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayoutMismatch {
+    /// The element, as `tag#id.class`.
+    pub element: String,
+    pub attribute: String,
+    pub expected: String,
+    /// What layout produced, or why the expectation could not be checked.
+    pub actual: String,
+}
+
+/// Lay `input` out with Ahem metrics in a viewport `viewport_width` px wide
+/// and check every annotated element.
+pub fn check_layout(input: &str, viewport_width: f32) -> Result<Vec<LayoutMismatch>, String> {
+    with_layout(input, viewport_width, &AhemTextMeasurer, |root| {
+        let mut mismatches = Vec::new();
+        check_box(root, &mut Vec::new(), &mut mismatches);
+        mismatches
+    })
+}
+
+fn check_box<'a, 's, 'd>(
+    layout: &'a LayoutBox<'s, 'd>,
+    ancestors: &mut Vec<&'a LayoutBox<'s, 'd>>,
+    mismatches: &mut Vec<LayoutMismatch>,
+) {
+    if !layout.is_anonymous()
+        && let Node::Element { element } = layout.node.node
+    {
+        for attribute in element.attributes() {
+            let name = attribute.local_name();
+            if !(name.starts_with("data-expected-") || name.starts_with("data-offset-")) {
+                continue;
+            }
+            let mismatch = |actual: String| LayoutMismatch {
+                element: element_label(layout.node.node),
+                attribute: name.to_string(),
+                expected: attribute.value().to_string(),
+                actual,
+            };
+            let Ok(expected) = attribute.value().trim().parse::<f32>() else {
+                mismatches.push(mismatch("expected value is not a number".to_string()));
+                continue;
+            };
+            let Some(actual) = measure(name, layout, ancestors) else {
+                mismatches.push(mismatch("unsupported expectation".to_string()));
+                continue;
+            };
+            if (actual - expected).abs() >= 1.0 {
+                mismatches.push(mismatch(format!("{actual}")));
+            }
+        }
+    }
+    ancestors.push(layout);
+    for child in &layout.children {
+        check_box(child, ancestors, mismatches);
+    }
+    ancestors.pop();
+}
+
+fn measure(
+    name: &str,
+    layout: &LayoutBox<'_, '_>,
+    ancestors: &[&LayoutBox<'_, '_>],
+) -> Option<f32> {
+    let bm = layout.box_metrics();
+    let value = match name {
+        "data-expected-width" => layout.rect.width,
+        "data-expected-height" => layout.rect.height,
+        "data-expected-client-width" => layout.rect.width - bm.border_left - bm.border_right,
+        "data-expected-client-height" => layout.rect.height - bm.border_top - bm.border_bottom,
+        "data-offset-x" => layout.rect.x - offset_origin(ancestors).0,
+        "data-offset-y" => layout.rect.y - offset_origin(ancestors).1,
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// The padding-box origin of the nearest positioned ancestor element other
+/// than the root, or the initial containing block's origin.
+fn offset_origin(ancestors: &[&LayoutBox<'_, '_>]) -> (f32, f32) {
+    let parent = ancestors.iter().rev().find(|ancestor| {
+        !ancestor.is_anonymous()
+            && ancestor.establishes_positioned_containing_block()
+            && matches!(ancestor.node.node, Node::Element { element } if element.name() != "html")
+    });
+    parent.map_or((0.0, 0.0), |parent| {
+        let bm = parent.box_metrics();
+        (
+            parent.rect.x + bm.border_left,
+            parent.rect.y + bm.border_top,
+        )
+    })
+}
+
+fn element_label(node: &Node) -> String {
+    let Node::Element { element } = node else {
+        return String::new();
+    };
+    let mut label = element.name().to_string();
+    if let Some(id) = node.attr("id") {
+        label.push('#');
+        label.push_str(id);
+    }
+    for class in node.attr("class").unwrap_or_default().split_whitespace() {
+        label.push('.');
+        label.push_str(class);
+    }
+    label
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check_layout;
+
+    #[test]
+    fn annotated_elements_are_checked_against_their_boxes() {
+        let mismatches = check_layout(
+            r#"<style>body { margin: 0 }
+#outer { position: relative; width: 200px; padding-left: 5px; padding-top: 4px }
+#inner { height: 30px; margin-left: 10px }</style>
+<div id="outer" data-expected-width="205" data-expected-client-width="205">
+<div id="inner" class="a b" data-expected-height="30" data-offset-x="15" data-offset-y="99"
+     data-expected-scroll-width="1"></div>
+</div>"#,
+            800.0,
+        )
+        .expect("document lays out");
+
+        let reported: Vec<_> = mismatches
+            .iter()
+            .map(|mismatch| {
+                (
+                    mismatch.element.as_str(),
+                    mismatch.attribute.as_str(),
+                    mismatch.actual.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            reported,
+            [
+                ("div#inner.a.b", "data-offset-y", "4"),
+                (
+                    "div#inner.a.b",
+                    "data-expected-scroll-width",
+                    "unsupported expectation"
+                ),
+            ]
+        );
+    }
+}
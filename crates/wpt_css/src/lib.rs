@@ -0,0 +1,23 @@
+//! A curated slice of the web-platform-tests CSS suites.
+//!
+//! [`import`] converts WPT reftests into entries of a [`reftest`] manifest
+//! and `check-layout-th.js` layout tests into [`layout_test_support`]
+//! fixtures laid out with Ahem metrics. [`run_suite`] runs the imported slice
+//! and summarizes how much of it passes, judged against a list of known
+//! failures, so progress against the specs shows up as a number.
+//!
+//! Import with `cargo run -p wpt-css -- import <wpt checkout>` and run with
+//! `cargo run -p wpt-css -- run`; both default to the slice in
+//! `tests/wpt/css`.
+
+pub mod expectations;
+pub mod import;
+pub mod layout_check;
+pub mod source;
+pub mod suite;
+
+pub use expectations::Expectations;
+pub use import::{ImportReport, import};
+pub use layout_check::{LayoutMismatch, check_layout};
+pub use source::TestSource;
+pub use suite::{Summary, TestKind, TestResult, run_suite};
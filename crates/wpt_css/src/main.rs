@@ -0,0 +1,79 @@
+use reftest::RunOptions;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: wpt-css import <wpt checkout> [<suite>]
+       wpt-css run [<suite>] [--artifacts <dir>]";
+
+fn default_suite() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/wpt/css")
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match args.as_slice() {
+        ["import", wpt_root] => import(Path::new(wpt_root), &default_suite()),
+        ["import", wpt_root, suite] => import(Path::new(wpt_root), Path::new(suite)),
+        ["run", rest @ ..] => {
+            let (suite, artifacts) = match rest {
+                [] => (default_suite(), None),
+                [suite] => (PathBuf::from(suite), None),
+                ["--artifacts", dir] => (default_suite(), Some(PathBuf::from(dir))),
+                [suite, "--artifacts", dir] => (PathBuf::from(suite), Some(PathBuf::from(dir))),
+                _ => {
+                    eprintln!("{USAGE}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            run(&suite, artifacts)
+        }
+        _ => {
+            eprintln!("{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn import(wpt_root: &Path, suite: &Path) -> ExitCode {
+    let report = match wpt_css::import(wpt_root, suite) {
+        Ok(report) => report,
+        Err(err) => {
+            eprintln!("import failed: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    for (path, reason) in &report.rejected {
+        println!("SKIP {path}: {reason}");
+    }
+    println!(
+        "imported {} reftests and {} layout tests; skipped {}",
+        report.reftests.len(),
+        report.layout_tests.len(),
+        report.rejected.len()
+    );
+    ExitCode::SUCCESS
+}
+
+fn run(suite: &Path, artifacts: Option<PathBuf>) -> ExitCode {
+    let options = RunOptions {
+        artifacts,
+        ..RunOptions::default()
+    };
+    let summary = match wpt_css::run_suite(suite, options) {
+        Ok(summary) => summary,
+        Err(err) => {
+            eprintln!("run failed: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    for result in &summary.results {
+        println!("{result}");
+    }
+    println!("{summary}");
+    if summary.unexpected().next().is_none() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
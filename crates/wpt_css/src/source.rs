@@ -0,0 +1,137 @@
+//! What a WPT test file declares about itself.
+
+use html::{HtmlParseOptions, Node, parse_document};
+use reftest::{Relation, Tolerance};
+
+/// The parts of a WPT test's markup the importer cares about.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TestSource {
+    /// `<link rel=match>` and `<link rel=mismatch>` references, in document
+    /// order.
+    pub references: Vec<(Relation, String)>,
+    /// Tolerance from `<meta name=fuzzy>`, if the test declares one.
+    pub fuzzy: Option<Tolerance>,
+    /// Whether any element carries a `check-layout-th.js` expectation
+    /// (`data-expected-*` or `data-offset-*`).
+    pub has_layout_checks: bool,
+    /// Stylesheets and images the test loads, as written.
+    pub resources: Vec<String>,
+}
+
+impl TestSource {
+    pub fn scan(input: &str) -> Result<Self, String> {
+        let dom = parse_document(input, HtmlParseOptions::default())
+            .map_err(|err| format!("parse failed: {err:?}"))?
+            .document;
+        let mut source = Self::default();
+        source.visit(&dom)?;
+        Ok(source)
+    }
+
+    fn visit(&mut self, node: &Node) -> Result<(), String> {
+        if let Node::Element { element } = node {
+            match element.name() {
+                "link" => {
+                    let href = node.attr("href").unwrap_or_default().to_string();
+                    if node.attr_has_token("rel", "match") {
+                        self.references.push((Relation::Equal, href));
+                    } else if node.attr_has_token("rel", "mismatch") {
+                        self.references.push((Relation::NotEqual, href));
+                    } else if node.attr_has_token("rel", "stylesheet") {
+                        self.resources.push(href);
+                    }
+                }
+                "meta" if node.attr("name").is_some_and(|name| name == "fuzzy") => {
+                    let content = node.attr("content").unwrap_or_default();
+                    self.fuzzy = Some(parse_fuzzy(content)?);
+                }
+                "img" => {
+                    if let Some(src) = node.attr("src") {
+                        self.resources.push(src.to_string());
+                    }
+                }
+                _ => {}
+            }
+            self.has_layout_checks |= element.attributes().iter().any(|attribute| {
+                let name = attribute.local_name();
+                name.starts_with("data-expected-") || name.starts_with("data-offset-")
+            });
+        }
+        for child in node.children().unwrap_or_default() {
+            self.visit(child)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse a WPT fuzzy annotation, `[<reference>:]<difference>;<pixels>`,
+/// where both parts are a number or a `<min>-<max>` range and may be named
+/// `maxDifference=` and `totalPixels=`. A range allows up to its maximum.
+fn parse_fuzzy(content: &str) -> Result<Tolerance, String> {
+    let invalid = || format!("unsupported fuzzy annotation `{content}`");
+    let ranges = match content.split_once(':') {
+        Some((_, ranges)) => ranges,
+        None => content,
+    };
+    let (difference, pixels) = ranges.split_once(';').ok_or_else(invalid)?;
+    let upper = |part: &str, name: &str| -> Option<usize> {
+        let part = part.trim();
+        let value = part.strip_prefix(name).unwrap_or(part);
+        let max = value.rsplit('-').next()?;
+        max.trim().parse().ok()
+    };
+    let difference = upper(difference, "maxDifference=").ok_or_else(invalid)?;
+    let pixels = upper(pixels, "totalPixels=").ok_or_else(invalid)?;
+    Ok(Tolerance {
+        max_channel_difference: u8::try_from(difference).map_err(|_| invalid())?,
+        max_pixels: pixels,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TestSource, parse_fuzzy};
+    use reftest::{Relation, Tolerance};
+
+    #[test]
+    fn scan_finds_references_fuzziness_checks_and_resources() {
+        let source = TestSource::scan(
+            r#"<!DOCTYPE html>
+<link rel="match" href="box-ref.html">
+<link rel="stylesheet" href="/fonts/ahem.css">
+<meta name="fuzzy" content="maxDifference=0-2;totalPixels=0-40">
+<div data-expected-width="100"><img src="support/pattern.png"></div>"#,
+        )
+        .expect("test parses");
+
+        assert_eq!(
+            source.references,
+            [(Relation::Equal, "box-ref.html".to_string())]
+        );
+        assert_eq!(
+            source.fuzzy,
+            Some(Tolerance {
+                max_channel_difference: 2,
+                max_pixels: 40,
+            })
+        );
+        assert!(source.has_layout_checks);
+        assert_eq!(source.resources, ["/fonts/ahem.css", "support/pattern.png"]);
+    }
+
+    #[test]
+    fn fuzzy_annotations_take_the_upper_bound_of_each_range() {
+        let tolerance = |max_channel_difference, max_pixels| Tolerance {
+            max_channel_difference,
+            max_pixels,
+        };
+        assert_eq!(parse_fuzzy("0-3;0-100"), Ok(tolerance(3, 100)));
+        assert_eq!(parse_fuzzy("ref.html:5;20"), Ok(tolerance(5, 20)));
+        assert_eq!(
+            parse_fuzzy("maxDifference=1;totalPixels=10-12"),
+            Ok(tolerance(1, 12))
+        );
+        assert!(parse_fuzzy("2").is_err());
+        assert!(parse_fuzzy("0-300;0-1").is_err());
+    }
+}
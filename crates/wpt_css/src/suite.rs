@@ -0,0 +1,200 @@
+//! Running the imported slice and summarizing the results.
+
+use crate::expectations::Expectations;
+use crate::layout_check::check_layout;
+use layout_test_support::load_fixtures;
+use reftest::{Manifest, Outcome, RunOptions, Runner};
+use std::fmt;
+use std::path::Path;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TestKind {
+    Reftest,
+    Layout,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TestResult {
+    pub id: String,
+    pub kind: TestKind,
+    /// Why the test failed; `None` if it passed.
+    pub failure: Option<String>,
+    /// Whether `expectations.txt` lists the test as a known failure.
+    pub expected_failure: bool,
+}
+
+impl TestResult {
+    pub fn passed(&self) -> bool {
+        self.failure.is_none()
+    }
+
+    /// A failure that is not a known one, or a pass of a known failure
+    /// whose entry should now be removed.
+    pub fn is_unexpected(&self) -> bool {
+        self.passed() == self.expected_failure
+    }
+
+    /// `PASS`, `FAIL`, `XFAIL` (known failure), or `XPASS` (known failure
+    /// that passed).
+    pub fn label(&self) -> &'static str {
+        match (self.passed(), self.expected_failure) {
+            (true, false) => "PASS",
+            (false, false) => "FAIL",
+            (false, true) => "XFAIL",
+            (true, true) => "XPASS",
+        }
+    }
+}
+
+impl fmt::Display for TestResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.label(), self.id)?;
+        match &self.failure {
+            Some(reason) => write!(f, ": {reason}"),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub results: Vec<TestResult>,
+}
+
+impl Summary {
+    pub fn passed(&self, kind: Option<TestKind>) -> usize {
+        self.of_kind(kind).filter(|result| result.passed()).count()
+    }
+
+    pub fn total(&self, kind: Option<TestKind>) -> usize {
+        self.of_kind(kind).count()
+    }
+
+    pub fn unexpected(&self) -> impl Iterator<Item = &TestResult> {
+        self.results.iter().filter(|result| result.is_unexpected())
+    }
+
+    fn of_kind(&self, kind: Option<TestKind>) -> impl Iterator<Item = &TestResult> {
+        self.results
+            .iter()
+            .filter(move |result| kind.is_none_or(|kind| result.kind == kind))
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (label, kind) in [
+            ("reftests", TestKind::Reftest),
+            ("layout", TestKind::Layout),
+        ] {
+            writeln!(
+                f,
+                "{label}: {} of {} passed",
+                self.passed(Some(kind)),
+                self.total(Some(kind))
+            )?;
+        }
+        let total = self.total(None);
+        let passed = self.passed(None);
+        let percent = if total == 0 {
+            0.0
+        } else {
+            passed as f64 * 100.0 / total as f64
+        };
+        let known = self
+            .results
+            .iter()
+            .filter(|result| result.expected_failure)
+            .count();
+        write!(
+            f,
+            "total: {passed} of {total} passed ({percent:.1}%); {known} known failures, {} unexpected results",
+            self.unexpected().count()
+        )
+    }
+}
+
+/// Run every imported test in `suite` and judge the results against its
+/// `expectations.txt`. Reftests render over runtimes started for the run.
+pub fn run_suite(suite: &Path, options: RunOptions) -> Result<Summary, String> {
+    let expectations = Expectations::load(&suite.join("expectations.txt"))?;
+    let mut results = run_reftests(suite, options)?;
+    results.extend(run_layout_tests(suite)?);
+    for result in &mut results {
+        result.expected_failure = expectations.expects_failure(&result.id);
+    }
+    let unknown: Vec<_> = expectations
+        .failures
+        .keys()
+        .filter(|id| !results.iter().any(|result| &result.id == *id))
+        .collect();
+    if !unknown.is_empty() {
+        return Err(format!("expectations.txt lists unknown tests: {unknown:?}"));
+    }
+    Ok(Summary { results })
+}
+
+fn run_reftests(suite: &Path, options: RunOptions) -> Result<Vec<TestResult>, String> {
+    let manifest_path = suite.join("reftest.list");
+    let manifest = Manifest::load(&manifest_path)
+        .map_err(|err| format!("{}: {err}", manifest_path.display()))?;
+    let (cmd_tx, evt_rx, runtimes) = platform::start_runtimes();
+    let mut runner = Runner::new(cmd_tx, evt_rx, options);
+    let results = manifest
+        .tests
+        .iter()
+        .map(|test| {
+            let failure = match runner.run(test) {
+                Ok(Outcome::Pass) => None,
+                Ok(Outcome::Fail { difference, .. }) => Some(format!(
+                    "{} pixels differ, by up to {}",
+                    difference.pixels, difference.max_channel_difference
+                )),
+                Err(err) => Some(err.to_string()),
+            };
+            TestResult {
+                id: test.name(),
+                kind: TestKind::Reftest,
+                failure,
+                expected_failure: false,
+            }
+        })
+        .collect();
+    runtimes.shutdown(platform::SHUTDOWN_TIMEOUT);
+    Ok(results)
+}
+
+fn run_layout_tests(suite: &Path) -> Result<Vec<TestResult>, String> {
+    let root = suite.join("layout");
+    if !root.is_dir() {
+        return Ok(Vec::new());
+    }
+    load_fixtures(&root)
+        .into_iter()
+        .map(|fixture| {
+            let mismatches = check_layout(&fixture.input, fixture.viewport_width())
+                .map_err(|err| format!("{}: {err}", fixture.name))?;
+            let failure = (!mismatches.is_empty()).then(|| {
+                mismatches
+                    .iter()
+                    .map(|mismatch| {
+                        format!(
+                            "{} {}={} but was {}",
+                            mismatch.element,
+                            mismatch.attribute,
+                            mismatch.expected,
+                            mismatch.actual
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            });
+            Ok(TestResult {
+                id: fixture.name,
+                kind: TestKind::Layout,
+                failure,
+                expected_failure: false,
+            })
+        })
+        .collect()
+}
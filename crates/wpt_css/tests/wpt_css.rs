@@ -0,0 +1,26 @@
+//! The imported CSS/layout WPT slice in `tests/wpt/css`; see `wpt_css` for
+//! how it is imported and judged.
+
+use reftest::RunOptions;
+use std::path::{Path, PathBuf};
+
+fn suite() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/wpt/css")
+}
+
+#[test]
+fn imported_layout_tests_match_their_fragment_snapshots() {
+    layout_test_support::check_fixtures(&suite().join("layout"));
+}
+
+#[test]
+fn imported_tests_meet_their_expectations() {
+    let summary = wpt_css::run_suite(&suite(), RunOptions::default()).expect("suite runs");
+    assert!(summary.total(None) > 0);
+    let unexpected: Vec<String> = summary.unexpected().map(ToString::to_string).collect();
+    assert!(
+        unexpected.is_empty(),
+        "unexpected WPT results (update tests/wpt/css/expectations.txt if intended):\n{}\n{summary}",
+        unexpected.join("\n")
+    );
+}
@@ -0,0 +1,26 @@
+#![no_main]
+
+use html::dom_snapshot::{DomSnapshotOptions, compare_dom};
+use html::html5::tokenizer::derive_fuzz_seed;
+use html::test_harness::{FuzzMode, random_chunk_plan, run_chunked, run_full};
+use libfuzzer_sys::fuzz_target;
+
+const MAX_INPUT_BYTES: usize = 4 * 1024;
+
+fuzz_target!(|data: &[u8]| {
+    if data.len() > MAX_INPUT_BYTES {
+        return;
+    }
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    let chunking = random_chunk_plan(input, derive_fuzz_seed(data), FuzzMode::Mixed);
+    let whole = run_full(input);
+    let chunked = run_chunked(input, &chunking.plan);
+    if let Err(mismatch) = compare_dom(&whole, &chunked, DomSnapshotOptions::default()) {
+        panic!(
+            "html5 DOM depends on chunking ({}): {mismatch}",
+            chunking.summary
+        );
+    }
+});
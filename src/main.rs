@@ -1,7 +1,49 @@
+use std::process::ExitCode;
+use std::time::Duration;
+
 #[global_allocator]
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
-fn main() {
-    let app = browser::ShellApp::new();
-    platform::run_with(app);
+/// How long a headless print waits for the page to load.
+const PRINT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Where the `chrome-trace` build writes its trace unless `BORROWSER_TRACE`
+/// names another file.
+#[cfg(feature = "chrome-trace")]
+const DEFAULT_TRACE_PATH: &str = "borrowser-trace.json";
+
+fn main() -> ExitCode {
+    #[cfg(feature = "chrome-trace")]
+    let _trace = platform::start_chrome_trace(
+        std::env::var_os("BORROWSER_TRACE").unwrap_or_else(|| DEFAULT_TRACE_PATH.into()),
+    );
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.as_slice() {
+        [] => {
+            let app = browser::ShellApp::new();
+            platform::run_with(app);
+            ExitCode::SUCCESS
+        }
+        [flag, output, url] if flag == "--print-to-pdf" => print_to_pdf(url, output),
+        _ => {
+            eprintln!("usage: borrowser [--print-to-pdf <output.pdf> <url>]");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn print_to_pdf(url: &str, output: &str) -> ExitCode {
+    let (cmd_tx, evt_rx, runtimes) = platform::start_runtimes();
+    let options = browser::print::PrintOptions::default();
+    let result = browser::print::print_url_to_pdf(url, cmd_tx, &evt_rx, &options, PRINT_TIMEOUT)
+        .and_then(|pdf| std::fs::write(output, pdf).map_err(Into::into));
+    runtimes.shutdown(platform::SHUTDOWN_TIMEOUT);
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("could not print {url}: {err}");
+            ExitCode::FAILURE
+        }
+    }
 }